@@ -34,6 +34,9 @@ pub struct AgreementResults {
     pub by_depth: HashMap<usize, f64>,
     /// Results by attractor count
     pub by_attractors: HashMap<usize, f64>,
+    /// Share of pairs whose grammatical member scores higher under graded
+    /// acceptability, if the forced-choice evaluation was run
+    pub forced_choice_accuracy: Option<f64>,
 }
 
 /// Generate agreement test suite
@@ -105,34 +108,131 @@ pub fn generate_agreement_tests() -> Vec<AgreementTest> {
     ]
 }
 
+/// Most attractors (and embeddings) in a generated item
+pub const MAX_ATTRACTORS: usize = 5;
+
+/// Construction separating the subject from its verb
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Embedding {
+    /// Stacked PPs modifying the subject: "the student near the teachers in the tutors …"
+    Prepositional,
+    /// Center-embedded object relatives: "the student who the teachers who the tutor likes like …"
+    ObjectRelative,
+}
+
+impl Embedding {
+    /// Every embedding type
+    pub const ALL: [Embedding; 2] = [Embedding::Prepositional, Embedding::ObjectRelative];
+}
+
+/// Nouns of the generated items, as (singular, plural)
+const NOUNS: [(&str, &str); 3] = [("student", "students"), ("teacher", "teachers"), ("tutor", "tutors")];
+
+fn form(noun: (&'static str, &'static str), plural: bool) -> &'static str {
+    if plural { noun.1 } else { noun.0 }
+}
+
+/// Generate agreement items for every embedding depth up to `max_attractors`
+/// and every attractor count up to the depth
+///
+/// Each embedding adds one noun between the subject and its verb; the first
+/// `attractor_count` of them take the number opposite the subject's. Items vary
+/// the subject noun, its number and the predicate, so each (embedding,
+/// depth, attractors) cell holds twelve pairs; depth 0 is generated once.
+pub fn generate_scaled_agreement_tests(max_attractors: usize, embeddings: &[Embedding]) -> Vec<AgreementTest> {
+    let mut tests = Vec::new();
+    for depth in 0..=max_attractors {
+        let embeddings = if depth == 0 { &embeddings[..embeddings.len().min(1)] } else { embeddings };
+        for &embedding in embeddings {
+            for attractor_count in 0..=depth {
+                for subject in 0..NOUNS.len() {
+                    for plural in [false, true] {
+                        for predicate in ["here", "smart"] {
+                            // Embedded nouns cycle through the other nouns
+                            let embedded: Vec<(&str, bool)> = (0..depth)
+                                .map(|i| (NOUNS[(subject + 1 + i) % NOUNS.len()], plural != (i < attractor_count)))
+                                .map(|(noun, plural)| (form(noun, plural), plural))
+                                .collect();
+                            let modifier = match embedding {
+                                Embedding::Prepositional => embedded.iter()
+                                    .enumerate()
+                                    .map(|(i, (noun, _))| format!(" {} the {}", ["near", "in"][i % 2], noun))
+                                    .collect::<String>(),
+                                Embedding::ObjectRelative => {
+                                    let subjects: String = embedded.iter()
+                                        .map(|(noun, _)| format!(" who the {}", noun))
+                                        .collect();
+                                    let verbs: String = embedded.iter()
+                                        .rev()
+                                        .map(|(_, plural)| if *plural { " like" } else { " likes" })
+                                        .collect();
+                                    subjects + &verbs
+                                }
+                            };
+                            let sentence = |plural_verb: bool| format!(
+                                "the {}{} {} {}",
+                                form(NOUNS[subject], plural),
+                                modifier,
+                                if plural_verb { "are" } else { "is" },
+                                predicate
+                            );
+                            tests.push(AgreementTest {
+                                grammatical: sentence(plural),
+                                ungrammatical: sentence(!plural),
+                                attractor_count,
+                                depth,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    tests
+}
+
 /// Extended lexicon for agreement testing
+///
+/// Nouns carry their person and number, and each finite verb probes its
+/// subject with the number it inflects for, so a verb that disagrees with
+/// the subject's head noun cannot merge with it.
 pub fn agreement_lexicon() -> Vec<LexItem> {
+    let singular = Feature::Agr(AgrBundle::third(Number::Singular));
+    let plural = Feature::Agr(AgrBundle::third(Number::Plural));
+    let probe = |number| Feature::Agr(AgrBundle::number(number));
+    
     let mut lexicon = test_lexicon();
+    for item in lexicon.iter_mut().filter(|item| item.feats == [Feature::Cat(Category::N)]) {
+        item.feats.push(singular.clone());
+    }
     
     // Add agreement-sensitive items
     lexicon.extend(vec![
-        LexItem::new("students", &[Feature::Cat(Category::N)]),
-        LexItem::new("teachers", &[Feature::Cat(Category::N)]),
-        LexItem::new("is", &[Feature::Cat(Category::V)]),
-        LexItem::new("are", &[Feature::Cat(Category::V)]),
-        LexItem::new("likes", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
-        LexItem::new("like", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
-        LexItem::new("near", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
-        LexItem::new("in", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
-        LexItem::new("room", &[Feature::Cat(Category::N)]),
-        LexItem::new("here", &[Feature::Cat(Category::V)]),
+        LexItem::new("students", &[Feature::Cat(Category::N), plural.clone()]),
+        LexItem::new("teachers", &[Feature::Cat(Category::N), plural.clone()]),
+        LexItem::new("tutors", &[Feature::Cat(Category::N), plural]),
+        LexItem::new("is", &[Feature::Cat(Category::V), Feature::Sel(Category::P), Feature::Spec(Category::D), probe(Number::Singular)]),
+        LexItem::new("are", &[Feature::Cat(Category::V), Feature::Sel(Category::P), Feature::Spec(Category::D), probe(Number::Plural)]),
+        LexItem::new("likes", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D), probe(Number::Singular)]),
+        LexItem::new("like", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D), probe(Number::Plural)]),
+        // Prepositions head PPs adjoined to NP (attractor position) or VP
+        LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N), Feature::Adjoin(Category::V)]),
+        LexItem::new("in", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N), Feature::Adjoin(Category::V)]),
+        LexItem::new("room", &[Feature::Cat(Category::N), singular.clone()]),
+        // Locative pro-form: an intransitive P
+        LexItem::new("here", &[Feature::Cat(Category::P)]),
         LexItem::new("smart", &[Feature::Cat(Category::V)]),
-        LexItem::new("Mary", &[Feature::Cat(Category::N)]),
-        LexItem::new("knows", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
+        LexItem::new("Mary", &[Feature::Cat(Category::N), singular.clone()]),
+        LexItem::new("knows", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D), probe(Number::Singular)]),
     ]);
     
     lexicon
 }
 
 /// Test agreement for a single sentence pair
-pub fn test_agreement_pair(test: &AgreementTest, lexicon: &[LexItem]) -> (bool, bool) {
-    let grammatical_result = parse_sentence(&test.grammatical, lexicon);
-    let ungrammatical_result = parse_sentence(&test.ungrammatical, lexicon);
+pub fn test_agreement_pair(test: &AgreementTest, lexicon: &[LexItem], options: &GrammarOptions) -> (bool, bool) {
+    let grammatical_result = parse_with_options(&test.grammatical, lexicon, options);
+    let ungrammatical_result = parse_with_options(&test.ungrammatical, lexicon, options);
     
     let grammatical_parsed = grammatical_result.is_ok();
     let ungrammatical_rejected = ungrammatical_result.is_err();
@@ -142,49 +242,80 @@ pub fn test_agreement_pair(test: &AgreementTest, lexicon: &[LexItem]) -> (bool,
 
 /// Run complete agreement test suite
 pub fn run_agreement_suite() -> AgreementResults {
+    run_agreement_suite_with(&agreement_lexicon(), &GrammarOptions::default())
+}
+
+/// Run agreement test suite with a given lexicon and grammar options
+pub fn run_agreement_suite_with(lexicon: &[LexItem], options: &GrammarOptions) -> AgreementResults {
+    println!("🧪 Running Agreement Test Suite (Linzen et al. 2016)");
+    println!("{}", "=".repeat(60));
+    
     let tests = generate_agreement_tests();
-    let lexicon = agreement_lexicon();
+    let pairs = tests.iter().map(|test| (test.grammatical.as_str(), test.ungrammatical.as_str()));
+    let forced_choice = forced_choice(pairs, lexicon, options, &Acceptability::default());
     
+    AgreementResults {
+        forced_choice_accuracy: Some(forced_choice.accuracy),
+        ..run_agreement_tests(&tests, lexicon, options, true)
+    }
+}
+
+/// Run the generated agreement items up to `MAX_ATTRACTORS` attractors
+pub fn run_scaled_agreement_suite() -> AgreementResults {
+    let tests = generate_scaled_agreement_tests(MAX_ATTRACTORS, &Embedding::ALL);
+    println!("🧪 Running Scaled Agreement Suite ({} generated pairs)", tests.len());
+    
+    let (lexicon, options) = (agreement_lexicon(), GrammarOptions::default());
+    let pairs = tests.iter().map(|test| (test.grammatical.as_str(), test.ungrammatical.as_str()));
+    let forced_choice = forced_choice(pairs, &lexicon, &options, &Acceptability::default());
+    
+    AgreementResults {
+        forced_choice_accuracy: Some(forced_choice.accuracy),
+        ..run_agreement_tests(&tests, &lexicon, &options, false)
+    }
+}
+
+/// Judge agreement items, printing each pair when `verbose`
+pub fn run_agreement_tests(tests: &[AgreementTest], lexicon: &[LexItem], options: &GrammarOptions, verbose: bool) -> AgreementResults {
     let mut total = 0;
     let mut correct_grammatical = 0;
     let mut correct_ungrammatical = 0;
     let mut by_depth: HashMap<usize, Vec<bool>> = HashMap::new();
     let mut by_attractors: HashMap<usize, Vec<bool>> = HashMap::new();
     
-    println!("🧪 Running Agreement Test Suite (Linzen et al. 2016)");
-    println!("=" .repeat(60));
-    
-    for test in &tests {
-        let (gram_ok, ungram_rejected) = test_agreement_pair(test, &lexicon);
+    for test in tests {
+        let (gram_ok, ungram_rejected) = test_agreement_pair(test, lexicon, options);
         
         total += 2; // Each test has grammatical + ungrammatical
         
         if gram_ok {
             correct_grammatical += 1;
-            println!("✅ GRAM: {}", test.grammatical);
-        } else {
-            println!("❌ GRAM: {}", test.grammatical);
         }
-        
         if ungram_rejected {
             correct_ungrammatical += 1;
-            println!("✅ UNGRAM: {} (correctly rejected)", test.ungrammatical);
-        } else {
-            println!("❌ UNGRAM: {} (incorrectly accepted)", test.ungrammatical);
+        }
+        
+        if verbose {
+            let mark = |ok: bool| if ok { "✅" } else { "❌" };
+            println!("{} GRAM: {}", mark(gram_ok), test.grammatical);
+            if ungram_rejected {
+                println!("✅ UNGRAM: {} (correctly rejected)", test.ungrammatical);
+            } else {
+                println!("❌ UNGRAM: {} (incorrectly accepted)", test.ungrammatical);
+            }
+            println!("   Depth: {}, Attractors: {}", test.depth, test.attractor_count);
+            println!();
         }
         
         // Track by depth
         by_depth.entry(test.depth)
-            .or_insert_with(Vec::new)
+            .or_default()
             .extend(vec![gram_ok, ungram_rejected]);
             
         // Track by attractors
         by_attractors.entry(test.attractor_count)
-            .or_insert_with(Vec::new)
+            .or_default()
             .extend(vec![gram_ok, ungram_rejected]);
-        
-        println!("   Depth: {}, Attractors: {}", test.depth, test.attractor_count);
-        println!();
     }
     
     let accuracy = (correct_grammatical + correct_ungrammatical) as f64 / total as f64;
@@ -214,30 +345,34 @@ pub fn run_agreement_suite() -> AgreementResults {
         accuracy,
         by_depth: depth_accuracy,
         by_attractors: attractor_accuracy,
+        forced_choice_accuracy: None,
     }
 }
 
 /// Print detailed results analysis
 pub fn print_agreement_analysis(results: &AgreementResults) {
     println!("\n📊 AGREEMENT TEST RESULTS");
-    println!("=" .repeat(40));
+    println!("{}", "=".repeat(40));
     println!("Total test cases: {}", results.total);
     println!("Correct grammatical: {}/{}", results.correct_grammatical, results.total / 2);
     println!("Correct ungrammatical: {}/{}", results.correct_ungrammatical, results.total / 2);
     println!("Overall accuracy: {:.1}%", results.accuracy * 100.0);
+    if let Some(accuracy) = results.forced_choice_accuracy {
+        println!("Forced-choice accuracy: {:.1}%", accuracy * 100.0);
+    }
     
     println!("\n📈 ACCURACY BY EMBEDDING DEPTH:");
-    for depth in 0..=2 {
-        if let Some(&accuracy) = results.by_depth.get(&depth) {
-            println!("  Depth {}: {:.1}%", depth, accuracy * 100.0);
-        }
+    let mut depths: Vec<_> = results.by_depth.iter().collect();
+    depths.sort_by_key(|&(depth, _)| *depth);
+    for (depth, accuracy) in depths {
+        println!("  Depth {}: {:.1}%", depth, accuracy * 100.0);
     }
     
     println!("\n📈 ACCURACY BY ATTRACTOR COUNT:");
-    for attractors in 0..=2 {
-        if let Some(&accuracy) = results.by_attractors.get(&attractors) {
-            println!("  {} attractors: {:.1}%", attractors, accuracy * 100.0);
-        }
+    let mut attractor_counts: Vec<_> = results.by_attractors.iter().collect();
+    attractor_counts.sort_by_key(|&(attractors, _)| *attractors);
+    for (attractors, accuracy) in attractor_counts {
+        println!("  {} attractors: {:.1}%", attractors, accuracy * 100.0);
     }
     
     // Performance analysis
@@ -281,6 +416,38 @@ mod tests {
         println!("Generated {} agreement test cases", tests.len());
     }
     
+    #[test]
+    fn test_scaled_agreement_generation() {
+        let tests = generate_scaled_agreement_tests(MAX_ATTRACTORS, &Embedding::ALL);
+        // One depth-0 cell, then (depth + 1) attractor counts per embedding
+        assert_eq!(tests.len(), 12 * (1 + 2 * (2..=MAX_ATTRACTORS + 1).sum::<usize>()));
+        
+        for attractors in 0..=MAX_ATTRACTORS {
+            let cell = tests.iter().filter(|test| test.attractor_count == attractors).count();
+            assert!(cell >= 12, "{} attractors should have at least twelve pairs", attractors);
+        }
+        for test in &tests {
+            assert!(test.attractor_count <= test.depth);
+            assert_ne!(test.grammatical, test.ungrammatical);
+        }
+    }
+    
+    #[test]
+    fn test_scaled_agreement_items() {
+        let tests = generate_scaled_agreement_tests(2, &[Embedding::ObjectRelative]);
+        let item = tests.iter()
+            .find(|test| test.depth == 2 && test.attractor_count == 1 && test.grammatical.ends_with("is here"))
+            .expect("a singular-subject item with one attractor");
+        assert_eq!(item.grammatical, "the student who the teachers who the tutor likes like is here");
+        assert_eq!(item.ungrammatical, "the student who the teachers who the tutor likes like are here");
+        
+        let tests = generate_scaled_agreement_tests(2, &[Embedding::Prepositional]);
+        let item = tests.iter()
+            .find(|test| test.depth == 2 && test.attractor_count == 2 && test.grammatical.starts_with("the students"))
+            .expect("a plural-subject item with two attractors");
+        assert_eq!(item.grammatical, "the students near the teacher in the tutor are here");
+    }
+    
     #[test]
     fn test_agreement_lexicon() {
         let lexicon = agreement_lexicon();
@@ -307,6 +474,47 @@ mod tests {
         println!("Simple agreement test result: {:?}", result.is_ok());
     }
     
+    #[test]
+    fn test_agreement_rejects_mismatch() {
+        let lexicon = agreement_lexicon();
+        
+        assert!(parse_sentence("the student is here", &lexicon).is_ok());
+        assert!(parse_sentence("the student are here", &lexicon).is_err());
+        assert!(parse_sentence("the students is here", &lexicon).is_err());
+        
+        // The attractor's number never reaches the verb's probe
+        assert!(parse_sentence("the student near the teachers is here", &lexicon).is_ok());
+        assert!(parse_sentence("the student near the teachers are here", &lexicon).is_err());
+        assert!(parse_sentence("the students near the teacher in the room is here", &lexicon).is_err());
+        
+        // Nor does an object's reach a subject selector without a probe
+        assert!(parse_sentence("the student thinks the teachers", &lexicon).is_ok());
+        assert!(parse_sentence("the students thinks the teacher", &lexicon).is_ok());
+    }
+    
+    #[test]
+    fn test_attractor_pp_attachment() {
+        let lexicon = agreement_lexicon();
+        
+        // The PP adjoins inside the subject, so the attractor is never the subject
+        let tree = parse_sentence("the student near the teachers is here", &lexicon)
+            .expect("attractor sentence should parse");
+        let subject = &tree.children[0];
+        assert_eq!(subject.linearize(), "the student near the teachers");
+        
+        assert_eq!(subject.label, Category::DP);
+        let noun_phrase = &subject.children[1];
+        assert_eq!(noun_phrase.label, Category::N);
+        assert_eq!(noun_phrase.children[1].label, Category::PP);
+        assert_eq!(noun_phrase.children[0].phon.as_deref(), Some("student"));
+        assert_eq!(noun_phrase.children[1].linearize(), "near the teachers");
+        
+        // Stacked PPs stay inside the subject as well
+        let tree = parse_sentence("the students near the teacher in the room are here", &lexicon)
+            .expect("two-attractor sentence should parse");
+        assert_eq!(tree.children[0].linearize(), "the students near the teacher in the room");
+    }
+    
     #[test]
     fn test_agreement_suite_runs() {
         // This test verifies the test suite runs without crashing
@@ -314,7 +522,17 @@ mod tests {
         
         assert_eq!(results.total, generate_agreement_tests().len() * 2);
         assert!(results.accuracy >= 0.0 && results.accuracy <= 1.0);
+        assert!(results.forced_choice_accuracy.is_some_and(|accuracy| (0.0..=1.0).contains(&accuracy)));
         
         print_agreement_analysis(&results);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_agreement_tests_by_attractors() {
+        let tests = generate_scaled_agreement_tests(1, &Embedding::ALL);
+        let results = run_agreement_tests(&tests, &agreement_lexicon(), &GrammarOptions::default(), false);
+        
+        assert_eq!(results.total, tests.len() * 2);
+        assert_eq!(results.by_attractors.keys().count(), 2);
+    }
+}
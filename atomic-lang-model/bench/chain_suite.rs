@@ -0,0 +1,86 @@
+//! Movement Chain Suite
+//!
+//! Gold trees with coindexed traces for relative clauses with subject and
+//! object gaps, at the top level and embedded. The parser's chains are
+//! scored against them (see `chain_eval`) under each relative-clause
+//! analysis: operator movement posits exactly the gold chains, while
+//! promotion adds the raising of the head noun, which costs precision.
+
+use atomic_lang_model::*;
+
+/// Gold trees, in Penn Treebank notation
+pub const GOLD_TREES: &str = "
+    (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left)))))
+    (S (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left))))) (VP (VBD smiled)))
+    (S (NP (NP (DT a) (NN tutor)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD arrived))))) (VP (VBD left)))
+    (S (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (DT the) (NN teacher)) (VP (VBZ thinks) (NP (-NONE- *T*-1)))))) (VP (VBD smiled)))
+    (S (NP-SBJ (DT the) (NN teacher)) (VP (VBZ thinks) (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left)))))))
+";
+
+/// Results of the chain suite
+#[derive(Debug, Clone)]
+pub struct ChainResults {
+    /// Scores under each relative-clause analysis
+    pub by_analysis: Vec<(RelativeClauseAnalysis, ChainScore)>,
+}
+
+impl ChainResults {
+    /// Score under the default grammar
+    pub fn default_score(&self) -> ChainScore {
+        let default = GrammarOptions::default().relative_clauses;
+        self.by_analysis.iter()
+            .find(|(analysis, _)| *analysis == default)
+            .map(|(_, score)| *score)
+            .unwrap_or_default()
+    }
+}
+
+/// Score the gold chains under both relative-clause analyses
+pub fn run_chain_suite() -> ChainResults {
+    let gold = parse_indexed_treebank(GOLD_TREES).expect("gold trees are well-formed");
+    let by_analysis = [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion]
+        .into_iter()
+        .map(|analysis| {
+            let options = GrammarOptions { relative_clauses: analysis, ..Default::default() };
+            (analysis, evaluate_chains(&gold, &options.lexicon(), &options))
+        })
+        .collect();
+    ChainResults { by_analysis }
+}
+
+/// Print precision and recall per analysis
+pub fn print_chain_analysis(results: &ChainResults) {
+    println!("⛓️  MOVEMENT CHAINS:");
+    for (analysis, score) in &results.by_analysis {
+        println!(
+            "  {:?}: precision {:.1}%, recall {:.1}% ({}/{} correct, {} gold)",
+            analysis,
+            score.precision() * 100.0,
+            score.recall() * 100.0,
+            score.correct,
+            score.predicted,
+            score.gold
+        );
+        println!("    landing sites {}/{}, attachment sites {}/{}", score.landings, score.predicted, score.attachments, score.predicted);
+        if score.unparsed > 0 {
+            println!("    ❌ {} gold sentences not parsed", score.unparsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operator_movement_recovers_gold_chains() {
+        let results = run_chain_suite();
+        let score = results.default_score();
+        assert_eq!(score.gold, 5);
+        assert_eq!(score.unparsed, 0);
+        assert_eq!((score.precision(), score.recall()), (1.0, 1.0));
+
+        let (_, promotion) = results.by_analysis[1];
+        assert!(promotion.precision() < 1.0);
+    }
+}
@@ -0,0 +1,290 @@
+//! A/B Grammar Comparison
+//!
+//! Runs two grammar variants across every benchmark suite and tabulates
+//! their accuracies side by side, broken down the way each suite reports
+//! (depth, attractors, complexity, category, constraint, environment).
+
+use crate::agreement_suite::*;
+use crate::colorless_green::*;
+use crate::ecp_suite::*;
+use crate::extraction_suite::*;
+use atomic_lang_model::*;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// Grammar configuration under evaluation
+#[derive(Debug, Clone)]
+pub struct GrammarVariant {
+    /// Name shown in the comparison table
+    pub name: String,
+    /// Items layered over each suite's lexicon, replacing items with the same phonology
+    pub lexical_overrides: Vec<LexItem>,
+    /// Grammar options for every suite; `None` keeps each suite's own options
+    pub options: Option<GrammarOptions>,
+    /// Operations for every suite, overriding the options' own; `None` keeps them
+    pub operations: Option<Operations>,
+}
+
+impl GrammarVariant {
+    /// Variant running every suite exactly as shipped
+    pub fn baseline() -> Self {
+        Self {
+            name: "baseline".to_string(),
+            lexical_overrides: Vec::new(),
+            options: None,
+            operations: None,
+        }
+    }
+
+    /// Ablation running every suite as shipped but without Move
+    pub fn without_movement() -> Self {
+        Self {
+            name: "merge only".to_string(),
+            operations: Some(Operations::MERGE_ONLY),
+            ..Self::baseline()
+        }
+    }
+
+    /// Suite lexicon with this variant's overrides applied
+    pub fn lexicon(&self, base: Vec<LexItem>) -> Vec<LexItem> {
+        let mut lexicon = self.lexical_overrides.clone();
+        lexicon.extend(
+            base.into_iter()
+                .filter(|item| !self.lexical_overrides.iter().any(|own| own.phon == item.phon)),
+        );
+        lexicon
+    }
+
+    /// Grammar options for a suite whose own defaults are `suite`
+    pub fn options(&self, suite: GrammarOptions) -> GrammarOptions {
+        let mut options = self.options.clone().unwrap_or(suite);
+        if let Some(operations) = self.operations {
+            options.operations = operations;
+        }
+        options
+    }
+}
+
+/// One line of the comparison table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonRow {
+    /// Benchmark suite
+    pub suite: &'static str,
+    /// Breakdown within the suite ("overall" for the suite total)
+    pub breakdown: String,
+    /// Accuracy of variant A
+    pub a: f64,
+    /// Accuracy of variant B
+    pub b: f64,
+}
+
+impl ComparisonRow {
+    /// Accuracy change from A to B
+    pub fn delta(&self) -> f64 {
+        self.b - self.a
+    }
+}
+
+/// Side-by-side results of two grammar variants
+#[derive(Debug, Clone)]
+pub struct GrammarComparison {
+    /// Name of variant A
+    pub a: String,
+    /// Name of variant B
+    pub b: String,
+    /// Accuracy rows, suite by suite
+    pub rows: Vec<ComparisonRow>,
+}
+
+impl GrammarComparison {
+    /// Rows whose accuracy differs between the variants
+    pub fn changed(&self) -> impl Iterator<Item = &ComparisonRow> {
+        self.rows.iter().filter(|row| row.delta().abs() > f64::EPSILON)
+    }
+}
+
+/// Suite results for one variant
+struct SuiteResults {
+    agreement: AgreementResults,
+    colorless_green: ColorlessGreenResults,
+    extraction: ExtractionResults,
+    ecp: EcpResults,
+}
+
+/// Run every suite under a variant
+fn run_variant(variant: &GrammarVariant) -> SuiteResults {
+    let extraction = variant.lexicon(extraction_lexicon());
+    SuiteResults {
+        agreement: run_agreement_suite_with(
+            &variant.lexicon(agreement_lexicon()),
+            &variant.options(GrammarOptions::default()),
+        ),
+        colorless_green: run_colorless_green_suite_with(
+            &variant.lexicon(colorless_green_lexicon()),
+            &variant.options(GrammarOptions::default()),
+        ),
+        extraction: run_extraction_suite_with(&extraction, &variant.options(extraction_options())),
+        ecp: run_ecp_suite_with(&extraction, &variant.options(ecp_options())),
+    }
+}
+
+/// Append one row per breakdown key, in sorted order
+fn breakdown_rows<K: Clone + Ord + Hash + Display>(
+    rows: &mut Vec<ComparisonRow>,
+    suite: &'static str,
+    label: &str,
+    a: &HashMap<K, f64>,
+    b: &HashMap<K, f64>,
+) {
+    let mut keys: Vec<K> = a.keys().chain(b.keys()).cloned().collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        rows.push(ComparisonRow {
+            suite,
+            breakdown: format!("{}{}", label, key),
+            a: a.get(&key).copied().unwrap_or(0.0),
+            b: b.get(&key).copied().unwrap_or(0.0),
+        });
+    }
+}
+
+/// Run both variants across all suites and compare them
+pub fn compare_grammars(a: &GrammarVariant, b: &GrammarVariant) -> GrammarComparison {
+    let results_a = run_variant(a);
+    let results_b = run_variant(b);
+    let mut rows = Vec::new();
+
+    let overall = |suite, a, b| ComparisonRow { suite, breakdown: "overall".to_string(), a, b };
+
+    rows.push(overall("agreement", results_a.agreement.accuracy, results_b.agreement.accuracy));
+    breakdown_rows(&mut rows, "agreement", "depth ", &results_a.agreement.by_depth, &results_b.agreement.by_depth);
+    breakdown_rows(&mut rows, "agreement", "attractors ", &results_a.agreement.by_attractors, &results_b.agreement.by_attractors);
+    rows.push(ComparisonRow {
+        suite: "agreement",
+        breakdown: "forced choice".to_string(),
+        a: results_a.agreement.forced_choice_accuracy.unwrap_or(0.0),
+        b: results_b.agreement.forced_choice_accuracy.unwrap_or(0.0),
+    });
+
+    rows.push(overall("colorless green", results_a.colorless_green.accuracy, results_b.colorless_green.accuracy));
+    breakdown_rows(&mut rows, "colorless green", "complexity ", &results_a.colorless_green.by_complexity, &results_b.colorless_green.by_complexity);
+    breakdown_rows(&mut rows, "colorless green", "", &results_a.colorless_green.by_category, &results_b.colorless_green.by_category);
+    rows.push(ComparisonRow {
+        suite: "colorless green",
+        breakdown: "forced choice".to_string(),
+        a: results_a.colorless_green.forced_choice_accuracy,
+        b: results_b.colorless_green.forced_choice_accuracy,
+    });
+
+    rows.push(overall("extraction", results_a.extraction.accuracy, results_b.extraction.accuracy));
+    breakdown_rows(&mut rows, "extraction", "", &results_a.extraction.by_constraint, &results_b.extraction.by_constraint);
+
+    rows.push(overall("ECP", results_a.ecp.accuracy, results_b.ecp.accuracy));
+    breakdown_rows(&mut rows, "ECP", "", &results_a.ecp.by_environment, &results_b.ecp.by_environment);
+
+    GrammarComparison {
+        a: a.name.clone(),
+        b: b.name.clone(),
+        rows,
+    }
+}
+
+/// Print the side-by-side comparison table
+pub fn print_comparison_table(comparison: &GrammarComparison) {
+    println!("\n⚖️  GRAMMAR COMPARISON: {} vs {}", comparison.a, comparison.b);
+    println!("{}", "=".repeat(70));
+    println!("{:<16} {:<22} {:>9} {:>9} {:>9}", "Suite", "Breakdown", "A", "B", "Δ");
+    println!("{}", "-".repeat(70));
+
+    for row in &comparison.rows {
+        let marker = if row.delta() > f64::EPSILON {
+            "📈"
+        } else if row.delta() < -f64::EPSILON {
+            "📉"
+        } else {
+            ""
+        };
+        println!(
+            "{:<16} {:<22} {:>8.1}% {:>8.1}% {:>+8.1}% {}",
+            row.suite,
+            row.breakdown,
+            row.a * 100.0,
+            row.b * 100.0,
+            row.delta() * 100.0,
+            marker,
+        );
+    }
+
+    println!("{}", "-".repeat(70));
+    println!("Changed rows: {}/{}", comparison.changed().count(), comparison.rows.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_variants() {
+        let comparison = compare_grammars(&GrammarVariant::baseline(), &GrammarVariant::baseline());
+
+        assert!(comparison.rows.iter().any(|row| row.suite == "ECP"));
+        assert_eq!(comparison.changed().count(), 0);
+    }
+
+    #[test]
+    fn test_variant_deltas() {
+        // A non-bridge `think` takes no clausal complement, so extraction
+        // through it fails while the other suites are unaffected
+        let non_bridge = GrammarVariant {
+            name: "non-bridge think".to_string(),
+            lexical_overrides: vec![
+                LexItem::new("think", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+            ],
+            options: None,
+            operations: None,
+        };
+        let comparison = compare_grammars(&GrammarVariant::baseline(), &non_bridge);
+        print_comparison_table(&comparison);
+
+        let row = |suite: &str, breakdown: &str| {
+            comparison.rows.iter()
+                .find(|row| row.suite == suite && row.breakdown == breakdown)
+                .cloned()
+                .expect("row should exist")
+        };
+        assert!(row("extraction", "that-trace").delta() < 0.0);
+        assert!(row("ECP", "silent C").delta() < 0.0);
+        assert_eq!(row("agreement", "overall").delta(), 0.0);
+        assert_eq!(row("colorless green", "overall").delta(), 0.0);
+
+        // Dropping every constraint undoes the extraction judgments
+        let unconstrained = GrammarVariant {
+            name: "unconstrained".to_string(),
+            lexical_overrides: Vec::new(),
+            options: Some(GrammarOptions::default()),
+            operations: None,
+        };
+        let comparison = compare_grammars(&GrammarVariant::baseline(), &unconstrained);
+        assert!(comparison.changed().all(|row| row.delta() <= 0.0));
+        assert!(comparison.changed().any(|row| row.suite == "extraction"));
+        assert!(comparison.changed().any(|row| row.suite == "ECP"));
+    }
+
+    #[test]
+    fn test_movement_ablation() {
+        // Agreement needs only Merge; extraction is what Move buys
+        let comparison = compare_grammars(&GrammarVariant::baseline(), &GrammarVariant::without_movement());
+        print_comparison_table(&comparison);
+
+        let overall = |suite: &str| {
+            comparison.rows.iter()
+                .find(|row| row.suite == suite && row.breakdown == "overall")
+                .cloned()
+                .expect("row should exist")
+        };
+        assert_eq!(overall("agreement").delta(), 0.0);
+        assert!(overall("extraction").delta() < 0.0);
+    }
+}
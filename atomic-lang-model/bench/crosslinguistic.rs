@@ -0,0 +1,85 @@
+//! Cross-Linguistic Agreement
+//!
+//! Runs the agreement pairs of every enabled grammar fragment (see
+//! `grammars`) through the agreement suite, one language at a time, to
+//! check that the same Agree machinery handles head-final agreement
+//! (Hindi: the verb follows its subject, and the possessive particle
+//! agrees with the noun that follows it) and rich agreement (Italian:
+//! person, number and gender, with attractors that differ in all three).
+//! Fragments without agreement pairs are skipped, and without `--features
+//! grammars` so is the whole phase.
+
+use super::agreement_suite::*;
+use atomic_lang_model::grammars::{fragments, Fragment};
+
+/// Results of the cross-linguistic agreement phase
+#[derive(Debug, Clone, Default)]
+pub struct CrossLinguisticResults {
+    /// Agreement results per language, in `fragments()` order
+    pub by_language: Vec<(&'static str, AgreementResults)>,
+}
+
+/// A fragment's agreement pairs as agreement tests, with one level of
+/// embedding per attractor
+pub fn fragment_agreement_tests(fragment: &Fragment) -> Vec<AgreementTest> {
+    fragment.agreement.iter()
+        .map(|&(grammatical, ungrammatical, attractors)| AgreementTest {
+            grammatical: grammatical.to_string(),
+            ungrammatical: ungrammatical.to_string(),
+            attractor_count: attractors,
+            depth: attractors,
+        })
+        .collect()
+}
+
+/// Run the agreement pairs of every enabled fragment that has them
+pub fn run_crosslinguistic_suite() -> CrossLinguisticResults {
+    let by_language = fragments()
+        .into_iter()
+        .filter(|fragment| !fragment.agreement.is_empty())
+        .map(|fragment| {
+            let tests = fragment_agreement_tests(&fragment);
+            (fragment.language, run_agreement_tests(&tests, &fragment.lexicon, &fragment.options, false))
+        })
+        .collect();
+    CrossLinguisticResults { by_language }
+}
+
+/// Print accuracy per language, by attractor count
+pub fn print_crosslinguistic_analysis(results: &CrossLinguisticResults) {
+    println!("🌍 CROSS-LINGUISTIC AGREEMENT:");
+    if results.by_language.is_empty() {
+        println!("  Skipped: this phase needs the agreement fragments (build with `--features grammars`)");
+    }
+    for (language, agreement) in &results.by_language {
+        println!("  {}: {:.1}% ({} sentences)", language, agreement.accuracy * 100.0, agreement.total);
+        let mut attractors: Vec<_> = agreement.by_attractors.iter().collect();
+        attractors.sort_by_key(|&(count, _)| *count);
+        for (count, accuracy) in attractors {
+            println!("    {} attractors: {:.1}%", count, accuracy * 100.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "grammars-hindi", feature = "grammars-italian"))]
+    fn test_agreement_generalizes() {
+        let results = run_crosslinguistic_suite();
+        let languages: Vec<_> = results.by_language.iter().map(|(language, _)| *language).collect();
+        assert_eq!(languages, ["Hindi", "Italian"]);
+        for (language, agreement) in &results.by_language {
+            assert_eq!(agreement.accuracy, 1.0, "{}", language);
+            assert!(agreement.by_attractors.contains_key(&1), "{}", language);
+        }
+    }
+
+    #[test]
+    fn test_fragments_without_pairs_are_skipped() {
+        let results = run_crosslinguistic_suite();
+        assert!(results.by_language.iter().all(|(language, _)| !["English", "Japanese", "German"].contains(language)));
+    }
+}
@@ -0,0 +1,218 @@
+//! ECP Test Suite
+//!
+//! Subject/object extraction asymmetries (Huang 1982, Lasnik & Saito 1984).
+//! Objects are lexically governed and extract freely under the ECP; subjects
+//! rely on antecedent government, which an overt `that` or a filled clause
+//! edge disrupts. Only the ECP is enforced, so weak-island object extraction
+//! (a Subjacency violation) counts as accepted here.
+
+use crate::extraction_suite::extraction_lexicon;
+use atomic_lang_model::*;
+use std::collections::HashMap;
+
+/// Subject/object minimal pair in a given extraction environment
+#[derive(Debug, Clone)]
+pub struct EcpTest {
+    /// Object extraction (lexically governed trace)
+    pub object: String,
+    /// Subject extraction (trace needs antecedent government)
+    pub subject: String,
+    /// Whether the subject trace is properly governed
+    pub subject_licensed: bool,
+    /// Clause the dependency crosses
+    pub environment: &'static str,
+}
+
+/// Results of ECP testing
+#[derive(Debug, Clone)]
+pub struct EcpResults {
+    /// Total test cases
+    pub total: usize,
+    /// Correct judgments
+    pub correct: usize,
+    /// Accuracy score
+    pub accuracy: f64,
+    /// Results by extraction environment
+    pub by_environment: HashMap<&'static str, f64>,
+}
+
+/// Generate ECP test suite
+pub fn generate_ecp_tests() -> Vec<EcpTest> {
+    vec![
+        EcpTest {
+            object: "who do you think Mary saw".to_string(),
+            subject: "who do you think saw Mary".to_string(),
+            subject_licensed: true,
+            environment: "silent C",
+        },
+        EcpTest {
+            object: "who did the teacher say the student saw".to_string(),
+            subject: "who did the teacher say saw the student".to_string(),
+            subject_licensed: true,
+            environment: "silent C",
+        },
+        EcpTest {
+            object: "who do you think that Mary saw".to_string(),
+            subject: "who do you think that saw Mary".to_string(),
+            subject_licensed: false,
+            environment: "that",
+        },
+        EcpTest {
+            object: "who does Mary say that you saw".to_string(),
+            subject: "who does Mary say that saw you".to_string(),
+            subject_licensed: false,
+            environment: "that",
+        },
+        EcpTest {
+            object: "who do you wonder whether Mary saw".to_string(),
+            subject: "who do you wonder whether saw Mary".to_string(),
+            subject_licensed: false,
+            environment: "wh-island",
+        },
+        EcpTest {
+            object: "who did the teacher wonder if the student saw".to_string(),
+            subject: "who did the teacher wonder if saw the student".to_string(),
+            subject_licensed: false,
+            environment: "wh-island",
+        },
+    ]
+}
+
+/// Grammar options enforcing only the ECP
+pub fn ecp_options() -> GrammarOptions {
+    GrammarOptions {
+        ecp: Some(Ecp::english()),
+        ..Default::default()
+    }
+}
+
+/// Test a single pair, returning whether each judgment is correct
+pub fn test_ecp_pair(test: &EcpTest, lexicon: &[LexItem], options: &GrammarOptions) -> (bool, bool) {
+    let object_ok = parse_with_options(&test.object, lexicon, options).is_ok();
+    let subject_ok = parse_with_options(&test.subject, lexicon, options).is_ok();
+
+    (object_ok, subject_ok == test.subject_licensed)
+}
+
+/// Run complete ECP test suite
+pub fn run_ecp_suite() -> EcpResults {
+    run_ecp_suite_with(&extraction_lexicon(), &ecp_options())
+}
+
+/// Run ECP test suite with a given lexicon and grammar options
+pub fn run_ecp_suite_with(lexicon: &[LexItem], options: &GrammarOptions) -> EcpResults {
+    let tests = generate_ecp_tests();
+
+    let mut total = 0;
+    let mut correct = 0;
+    let mut by_environment: HashMap<&'static str, Vec<bool>> = HashMap::new();
+
+    println!("🧪 Running ECP Test Suite");
+    println!("{}", "=".repeat(60));
+
+    for test in &tests {
+        let (object_correct, subject_correct) = test_ecp_pair(test, lexicon, options);
+
+        total += 2;
+        correct += object_correct as usize + subject_correct as usize;
+
+        let mark = |ok: bool| if ok { "✅" } else { "❌" };
+        let star = if test.subject_licensed { "" } else { "*" };
+        println!("{} OBJ: {}", mark(object_correct), test.object);
+        println!("{} SUBJ: {}{}", mark(subject_correct), star, test.subject);
+
+        by_environment.entry(test.environment)
+            .or_default()
+            .extend(vec![object_correct, subject_correct]);
+
+        println!("   Environment: {}", test.environment);
+        println!();
+    }
+
+    let accuracy = correct as f64 / total as f64;
+
+    let environment_accuracy: HashMap<&'static str, f64> = by_environment.iter()
+        .map(|(&environment, results)| {
+            let correct = results.iter().filter(|&&x| x).count();
+            (environment, correct as f64 / results.len() as f64)
+        })
+        .collect();
+
+    EcpResults {
+        total,
+        correct,
+        accuracy,
+        by_environment: environment_accuracy,
+    }
+}
+
+/// Print detailed results analysis
+pub fn print_ecp_analysis(results: &EcpResults) {
+    println!("\n📊 ECP TEST RESULTS");
+    println!("{}", "=".repeat(40));
+    println!("Total test cases: {}", results.total);
+    println!("Correct judgments: {}/{}", results.correct, results.total);
+    println!("Overall accuracy: {:.1}%", results.accuracy * 100.0);
+
+    println!("\n📈 ACCURACY BY ENVIRONMENT:");
+    let mut environments: Vec<_> = results.by_environment.iter().collect();
+    environments.sort_by_key(|&(name, _)| *name);
+    for (environment, accuracy) in environments {
+        println!("  {}: {:.1}%", environment, accuracy * 100.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subject_object_asymmetry() {
+        let lexicon = extraction_lexicon();
+        let options = ecp_options();
+
+        // Object traces are lexically governed, even inside a wh-island
+        assert!(parse_with_options("who do you wonder whether Mary saw", &lexicon, &options).is_ok());
+        assert_eq!(
+            parse_with_options("who do you wonder whether saw Mary", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(Ecp::NAME))
+        );
+
+        // `that` blocks antecedent government of the subject; silent C does not
+        assert!(parse_with_options("who do you think saw Mary", &lexicon, &options).is_ok());
+        assert_eq!(
+            parse_with_options("who do you think that saw Mary", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(Ecp::NAME))
+        );
+    }
+
+    #[test]
+    fn test_government_is_configurable() {
+        let lexicon = extraction_lexicon();
+
+        // Without antecedent government only lexically governed traces survive
+        let lexical_only = GrammarOptions {
+            ecp: Some(Ecp { antecedent_government: false, ..Ecp::english() }),
+            ..Default::default()
+        };
+        assert!(parse_with_options("who do you think Mary saw", &lexicon, &lexical_only).is_ok());
+        assert!(parse_with_options("who do you think saw Mary", &lexicon, &lexical_only).is_err());
+
+        // Without blocking complementizers the that-trace effect disappears
+        let no_blocking = GrammarOptions {
+            ecp: Some(Ecp { blocking_complementizers: Vec::new(), ..Ecp::english() }),
+            ..Default::default()
+        };
+        assert!(parse_with_options("who do you think that saw Mary", &lexicon, &no_blocking).is_ok());
+    }
+
+    #[test]
+    fn test_ecp_suite_runs() {
+        let results = run_ecp_suite();
+
+        assert_eq!(results.total, generate_ecp_tests().len() * 2);
+        assert_eq!(results.accuracy, 1.0);
+
+        print_ecp_analysis(&results);
+    }
+}
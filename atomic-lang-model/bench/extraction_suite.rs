@@ -0,0 +1,277 @@
+//! Extraction Test Suite
+//!
+//! Minimal pairs for long-distance wh-extraction (that-trace effects and
+//! wh-islands). Each ungrammatical item
+//! converges in the bare engine and must be ruled out by the named constraint.
+
+use atomic_lang_model::*;
+use std::collections::HashMap;
+
+/// Movement index for wh-phrases (`+wh` / `-wh`)
+pub const WH: u8 = 1;
+
+/// Minimal pair for an extraction constraint
+#[derive(Debug, Clone)]
+pub struct ExtractionTest {
+    /// Grammatical extraction
+    pub grammatical: String,
+    /// Ungrammatical extraction
+    pub ungrammatical: String,
+    /// Constraint expected to rule out the ungrammatical item
+    pub constraint: &'static str,
+}
+
+/// Results of extraction testing
+#[derive(Debug, Clone)]
+pub struct ExtractionResults {
+    /// Total test cases
+    pub total: usize,
+    /// Correctly accepted grammatical sentences
+    pub correct_grammatical: usize,
+    /// Correctly rejected ungrammatical sentences
+    pub correct_ungrammatical: usize,
+    /// Accuracy score
+    pub accuracy: f64,
+    /// Results by constraint
+    pub by_constraint: HashMap<&'static str, f64>,
+}
+
+/// Generate extraction test suite
+pub fn generate_extraction_tests() -> Vec<ExtractionTest> {
+    vec![
+        // Subject extraction across a silent vs. overt complementizer
+        ExtractionTest {
+            grammatical: "who do you think left".to_string(),
+            ungrammatical: "who do you think that left".to_string(),
+            constraint: ThatTraceFilter::NAME,
+        },
+        ExtractionTest {
+            grammatical: "who does Mary think smiled".to_string(),
+            ungrammatical: "who does Mary think that smiled".to_string(),
+            constraint: ThatTraceFilter::NAME,
+        },
+        // Object extraction across `that` is fine; subject extraction is not
+        ExtractionTest {
+            grammatical: "who do you think that Mary saw".to_string(),
+            ungrammatical: "who do you think that saw Mary".to_string(),
+            constraint: ThatTraceFilter::NAME,
+        },
+        ExtractionTest {
+            grammatical: "who did the teacher say left".to_string(),
+            ungrammatical: "who did the teacher say that left".to_string(),
+            constraint: ThatTraceFilter::NAME,
+        },
+        // Wh-islands: `whether` closes the escape hatch of its clause
+        ExtractionTest {
+            grammatical: "who do you think that Mary saw".to_string(),
+            ungrammatical: "who do you wonder whether Mary saw".to_string(),
+            constraint: Subjacency::NAME,
+        },
+        ExtractionTest {
+            grammatical: "who does Mary think you saw".to_string(),
+            ungrammatical: "who does Mary wonder whether you saw".to_string(),
+            constraint: Subjacency::NAME,
+        },
+        ExtractionTest {
+            grammatical: "who did the teacher say that the student saw".to_string(),
+            ungrammatical: "who did the teacher wonder if the student saw".to_string(),
+            constraint: Subjacency::NAME,
+        },
+    ]
+}
+
+/// Lexicon for wh-extraction out of embedded clauses
+pub fn extraction_lexicon() -> Vec<LexItem> {
+    vec![
+        // Wh-phrase and the auxiliaries whose C attracts it
+        LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(WH)]),
+        LexItem::new("do", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(WH)]),
+        LexItem::new("does", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(WH)]),
+        LexItem::new("did", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(WH)]),
+        // Embedded complementizers, overt and silent
+        LexItem::new("that", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+        LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+        LexItem::new("whether", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+        LexItem::new("if", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+        LexItem::new("you", &[Feature::Cat(Category::D)]),
+        LexItem::new("Mary", &[Feature::Cat(Category::D)]),
+        LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+        LexItem::new("teacher", &[Feature::Cat(Category::N)]),
+        LexItem::new("student", &[Feature::Cat(Category::N)]),
+        // Bridge verbs taking clausal complements
+        LexItem::new("think", &[Feature::Cat(Category::V), Feature::Sel(Category::C), Feature::Spec(Category::D)]),
+        LexItem::new("say", &[Feature::Cat(Category::V), Feature::Sel(Category::C), Feature::Spec(Category::D)]),
+        LexItem::new("wonder", &[Feature::Cat(Category::V), Feature::Sel(Category::C), Feature::Spec(Category::D)]),
+        LexItem::new("left", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("smiled", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("saw", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+    ]
+}
+
+/// Grammar options enforcing the constraints exercised by the suite
+pub fn extraction_options() -> GrammarOptions {
+    GrammarOptions {
+        that_trace: Some(ThatTraceFilter::english()),
+        subjacency: Some(Subjacency::english()),
+        ..Default::default()
+    }
+}
+
+/// Test extraction for a single sentence pair
+pub fn test_extraction_pair(test: &ExtractionTest, lexicon: &[LexItem], options: &GrammarOptions) -> (bool, bool) {
+    let grammatical_parsed = parse_with_options(&test.grammatical, lexicon, options).is_ok();
+    let ungrammatical_rejected = parse_with_options(&test.ungrammatical, lexicon, options).is_err();
+
+    (grammatical_parsed, ungrammatical_rejected)
+}
+
+/// Run complete extraction test suite
+pub fn run_extraction_suite() -> ExtractionResults {
+    run_extraction_suite_with(&extraction_lexicon(), &extraction_options())
+}
+
+/// Run extraction test suite with a given lexicon and grammar options
+pub fn run_extraction_suite_with(lexicon: &[LexItem], options: &GrammarOptions) -> ExtractionResults {
+    let tests = generate_extraction_tests();
+
+    let mut total = 0;
+    let mut correct_grammatical = 0;
+    let mut correct_ungrammatical = 0;
+    let mut by_constraint: HashMap<&'static str, Vec<bool>> = HashMap::new();
+
+    println!("🧪 Running Extraction Test Suite");
+    println!("{}", "=".repeat(60));
+
+    for test in &tests {
+        let (gram_ok, ungram_rejected) = test_extraction_pair(test, lexicon, options);
+
+        total += 2;
+
+        if gram_ok {
+            correct_grammatical += 1;
+            println!("✅ GRAM: {}", test.grammatical);
+        } else {
+            println!("❌ GRAM: {}", test.grammatical);
+        }
+
+        if ungram_rejected {
+            correct_ungrammatical += 1;
+            println!("✅ UNGRAM: *{} (correctly rejected)", test.ungrammatical);
+        } else {
+            println!("❌ UNGRAM: *{} (incorrectly accepted)", test.ungrammatical);
+        }
+
+        by_constraint.entry(test.constraint)
+            .or_default()
+            .extend(vec![gram_ok, ungram_rejected]);
+
+        println!("   Constraint: {}", test.constraint);
+        println!();
+    }
+
+    let accuracy = (correct_grammatical + correct_ungrammatical) as f64 / total as f64;
+
+    let constraint_accuracy: HashMap<&'static str, f64> = by_constraint.iter()
+        .map(|(&constraint, results)| {
+            let correct = results.iter().filter(|&&x| x).count();
+            (constraint, correct as f64 / results.len() as f64)
+        })
+        .collect();
+
+    ExtractionResults {
+        total,
+        correct_grammatical,
+        correct_ungrammatical,
+        accuracy,
+        by_constraint: constraint_accuracy,
+    }
+}
+
+/// Print detailed results analysis
+pub fn print_extraction_analysis(results: &ExtractionResults) {
+    println!("\n📊 EXTRACTION TEST RESULTS");
+    println!("{}", "=".repeat(40));
+    println!("Total test cases: {}", results.total);
+    println!("Correct grammatical: {}/{}", results.correct_grammatical, results.total / 2);
+    println!("Correct ungrammatical: {}/{}", results.correct_ungrammatical, results.total / 2);
+    println!("Overall accuracy: {:.1}%", results.accuracy * 100.0);
+
+    println!("\n📈 ACCURACY BY CONSTRAINT:");
+    let mut constraints: Vec<_> = results.by_constraint.iter().collect();
+    constraints.sort_by_key(|&(name, _)| *name);
+    for (constraint, accuracy) in constraints {
+        println!("  {}: {:.1}%", constraint, accuracy * 100.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extraction_generation() {
+        for test in &generate_extraction_tests() {
+            assert_ne!(test.grammatical, test.ungrammatical, "Sentences should differ");
+        }
+    }
+
+    #[test]
+    fn test_that_trace_minimal_pair() {
+        let lexicon = extraction_lexicon();
+        let options = extraction_options();
+
+        let tree = parse_with_options("who do you think left", &lexicon, &options)
+            .expect("subject extraction across silent C should parse");
+        assert_eq!(tree.label, Category::CP);
+        assert_eq!(tree.linearize(), "who do you think left");
+
+        assert_eq!(
+            parse_with_options("who do you think that left", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(ThatTraceFilter::NAME))
+        );
+
+        // Object extraction across `that` is fine
+        assert!(parse_with_options("who do you think that Mary saw", &lexicon, &options).is_ok());
+    }
+
+    #[test]
+    fn test_wh_island() {
+        let lexicon = extraction_lexicon();
+        let options = extraction_options();
+
+        assert_eq!(
+            parse_with_options("who do you wonder whether Mary saw", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(Subjacency::NAME))
+        );
+
+        // Each clause boundary is crossed through a free edge
+        let tree = parse_with_options("who do you think that Mary saw", &lexicon, &options)
+            .expect("long extraction through open edges should parse");
+        let subjacency = Subjacency::english();
+        let chains = constraints::chains(&tree);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(subjacency.crossings(&tree, &chains[0]), 1);
+
+        // Treating CP as bounding (Italian) does not affect this escape-hatch route
+        let italian = GrammarOptions { subjacency: Some(Subjacency::italian()), ..Default::default() };
+        assert!(parse_with_options("who do you think that Mary saw", &lexicon, &italian).is_ok());
+    }
+
+    #[test]
+    fn test_filter_is_configurable() {
+        // Without the filter the subject extraction converges
+        let lexicon = extraction_lexicon();
+        assert!(parse_sentence("who do you think that left", &lexicon).is_ok());
+        assert!(parse_sentence("who do you wonder whether Mary saw", &lexicon).is_ok());
+    }
+
+    #[test]
+    fn test_extraction_suite_runs() {
+        let results = run_extraction_suite();
+
+        assert_eq!(results.total, generate_extraction_tests().len() * 2);
+        assert_eq!(results.accuracy, 1.0);
+
+        print_extraction_analysis(&results);
+    }
+}
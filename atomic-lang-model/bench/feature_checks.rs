@@ -0,0 +1,124 @@
+//! Feature Checking
+//!
+//! Times the pairwise checks of a workspace scan two ways: `can_merge` and
+//! `can_adjoin` over feature vectors, and the same checks on `FeatureSet`
+//! summaries built once per object. Both must accept exactly the same
+//! pairs; the summaries' construction time is reported separately, since
+//! it is paid once per object rather than once per pair.
+
+use atomic_lang_model::determinism::xorshift;
+use atomic_lang_model::fuzz::FuzzInput;
+use atomic_lang_model::*;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Objects checked pairwise
+pub const OBJECTS: usize = 200;
+
+/// Passes over every pair, to get measurable times
+pub const ROUNDS: usize = 20;
+
+/// Results of the feature-checking phase
+#[derive(Debug, Clone)]
+pub struct FeatureCheckResults {
+    /// Ordered pairs checked per pass
+    pub pairs: usize,
+    /// Pairs that can merge or adjoin, by the vector checks
+    pub vector_matches: usize,
+    /// Pairs that can merge or adjoin, by the bitset checks
+    pub bitset_matches: usize,
+    /// Time per pair with feature vectors (nanoseconds)
+    pub vector_ns: f64,
+    /// Time per pair with bitsets (nanoseconds)
+    pub bitset_ns: f64,
+    /// Time to summarize one object (nanoseconds)
+    pub summary_ns: f64,
+}
+
+impl FeatureCheckResults {
+    /// How many times faster the bitset checks are
+    pub fn speedup(&self) -> f64 {
+        self.vector_ns / self.bitset_ns.max(f64::EPSILON)
+    }
+}
+
+/// Lexical leaves of the default grammar plus pseudo-random objects
+fn objects(count: usize) -> Vec<SyntacticObject> {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let data: Vec<u8> = (0..count * 64).map(|_| xorshift(&mut state) as u8).collect();
+    let mut input = FuzzInput::new(&data);
+    let mut objects: Vec<SyntacticObject> = GrammarOptions::default().lexicon().iter().map(SyntacticObject::from_lex).collect();
+    while objects.len() < count {
+        objects.push(input.object(2));
+    }
+    objects.truncate(count);
+    objects
+}
+
+/// Check every ordered pair of `count` objects `rounds` times each way
+pub fn run_feature_checks_with(count: usize, rounds: usize) -> FeatureCheckResults {
+    let objects = objects(count);
+    let pairs = objects.len() * objects.len();
+
+    let start = Instant::now();
+    let mut vector_matches = 0;
+    for _ in 0..rounds {
+        vector_matches = 0;
+        for a in &objects {
+            for b in &objects {
+                vector_matches += (black_box(can_merge(a, b)) || black_box(can_adjoin(a, b))) as usize;
+            }
+        }
+    }
+    let vector_ns = start.elapsed().as_nanos() as f64 / (rounds * pairs).max(1) as f64;
+
+    let start = Instant::now();
+    let mut sets = Vec::new();
+    for _ in 0..rounds {
+        sets = objects.iter().map(|obj| black_box(FeatureSet::of(obj))).collect();
+    }
+    let summary_ns = start.elapsed().as_nanos() as f64 / (rounds * objects.len()).max(1) as f64;
+
+    let start = Instant::now();
+    let mut bitset_matches = 0;
+    for _ in 0..rounds {
+        bitset_matches = 0;
+        for a in &sets {
+            for b in &sets {
+                bitset_matches += (black_box(a.can_merge(b)) || black_box(a.can_adjoin(b))) as usize;
+            }
+        }
+    }
+    let bitset_ns = start.elapsed().as_nanos() as f64 / (rounds * pairs).max(1) as f64;
+
+    FeatureCheckResults { pairs, vector_matches, bitset_matches, vector_ns, bitset_ns, summary_ns }
+}
+
+/// Run the feature-checking phase
+pub fn run_feature_checks() -> FeatureCheckResults {
+    run_feature_checks_with(OBJECTS, ROUNDS)
+}
+
+/// Print timings per pair
+pub fn print_feature_check_analysis(results: &FeatureCheckResults) {
+    println!("🧮 FEATURE CHECKING ({} pairs, {} combinable):", results.pairs, results.vector_matches);
+    println!("  feature vectors: {:>7.1} ns/pair", results.vector_ns);
+    println!("  bitsets:         {:>7.1} ns/pair ({:.1}x)", results.bitset_ns, results.speedup());
+    println!("  summary:         {:>7.1} ns/object", results.summary_ns);
+    if results.bitset_matches != results.vector_matches {
+        println!("  ❌ bitsets accepted {} pairs", results.bitset_matches);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitsets_accept_the_same_pairs() {
+        let results = run_feature_checks_with(60, 1);
+        assert_eq!(results.pairs, 3600);
+        assert!(results.vector_matches > 0);
+        assert_eq!(results.bitset_matches, results.vector_matches);
+    }
+}
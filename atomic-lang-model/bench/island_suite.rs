@@ -0,0 +1,281 @@
+//! Island Test Suite
+//!
+//! Minimal pairs probing the locality of wh-movement: wh-islands, adjunct
+//! islands and superiority. Each ungrammatical item converges in the bare
+//! engine and must be ruled out by the named constraint. Adjunct clauses
+//! project no C layer, so they offer no escape hatch and Subjacency alone
+//! makes them islands.
+
+use crate::extraction_suite::{extraction_lexicon, WH};
+use atomic_lang_model::*;
+use std::collections::HashMap;
+
+/// Minimal pair for an island or locality constraint
+#[derive(Debug, Clone)]
+pub struct IslandTest {
+    /// Grammatical movement
+    pub grammatical: String,
+    /// Ungrammatical movement
+    pub ungrammatical: String,
+    /// Phenomenon the pair probes
+    pub phenomenon: &'static str,
+    /// Constraint expected to rule out the ungrammatical item
+    pub constraint: &'static str,
+}
+
+/// Results of island testing
+#[derive(Debug, Clone)]
+pub struct IslandResults {
+    /// Total test cases
+    pub total: usize,
+    /// Correctly accepted grammatical sentences
+    pub correct_grammatical: usize,
+    /// Correctly rejected ungrammatical sentences
+    pub correct_ungrammatical: usize,
+    /// Accuracy score
+    pub accuracy: f64,
+    /// Results by phenomenon
+    pub by_phenomenon: HashMap<&'static str, f64>,
+    /// Results by constraint
+    pub by_constraint: HashMap<&'static str, f64>,
+}
+
+/// Generate island test suite
+pub fn generate_island_tests() -> Vec<IslandTest> {
+    vec![
+        // Wh-islands: `whether` and `if` fill the edge of their clause
+        IslandTest {
+            grammatical: "who do you think Mary saw".to_string(),
+            ungrammatical: "who do you wonder whether Mary saw".to_string(),
+            phenomenon: "wh-island",
+            constraint: Subjacency::NAME,
+        },
+        IslandTest {
+            grammatical: "who does the teacher say that you saw".to_string(),
+            ungrammatical: "who does the teacher wonder if you saw".to_string(),
+            phenomenon: "wh-island",
+            constraint: Subjacency::NAME,
+        },
+        // Adjunct islands: extraction from the matrix clause is fine,
+        // extraction out of the adjunct is not
+        IslandTest {
+            grammatical: "who did Mary see before you smiled".to_string(),
+            ungrammatical: "who did Mary smile before you saw".to_string(),
+            phenomenon: "adjunct island",
+            constraint: Subjacency::NAME,
+        },
+        IslandTest {
+            grammatical: "who did the student see after Mary left".to_string(),
+            ungrammatical: "who did the student leave after Mary saw".to_string(),
+            phenomenon: "adjunct island",
+            constraint: Subjacency::NAME,
+        },
+        // Superiority: the higher wh-phrase must be the one that moves
+        IslandTest {
+            grammatical: "who saw what".to_string(),
+            ungrammatical: "what did who see".to_string(),
+            phenomenon: "superiority",
+            constraint: Superiority::NAME,
+        },
+        IslandTest {
+            grammatical: "who do you think saw what".to_string(),
+            ungrammatical: "what do you think who saw".to_string(),
+            phenomenon: "superiority",
+            constraint: Superiority::NAME,
+        },
+    ]
+}
+
+/// Lexicon for island extraction: the extraction lexicon with clausal
+/// adjuncts, wh-phrases in situ and a silent interrogative C
+pub fn island_lexicon() -> Vec<LexItem> {
+    let mut lexicon = extraction_lexicon();
+    lexicon.extend([
+        // `what` moves like `who`; both may also stay in situ
+        LexItem::new("what", &[Feature::Cat(Category::D), Feature::Neg(WH)]),
+        LexItem::new("who", &[Feature::Cat(Category::D)]),
+        LexItem::new("what", &[Feature::Cat(Category::D)]),
+        // Silent C of matrix subject questions
+        LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(WH)]),
+        // Temporal adjuncts take a bare clause and adjoin to the verb phrase
+        LexItem::new("before", &[Feature::Cat(Category::P), Feature::Sel(Category::V), Feature::Adjoin(Category::V)]),
+        LexItem::new("after", &[Feature::Cat(Category::P), Feature::Sel(Category::V), Feature::Adjoin(Category::V)]),
+        // Bare forms under the auxiliaries
+        LexItem::new("see", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+        LexItem::new("smile", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("leave", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+    ]);
+    lexicon
+}
+
+/// Grammar options enforcing the constraints exercised by the suite
+pub fn island_options() -> GrammarOptions {
+    GrammarOptions {
+        subjacency: Some(Subjacency::english()),
+        superiority: Some(Superiority::english()),
+        ..Default::default()
+    }
+}
+
+/// Test island extraction for a single sentence pair
+pub fn test_island_pair(test: &IslandTest, lexicon: &[LexItem], options: &GrammarOptions) -> (bool, bool) {
+    let grammatical_parsed = parse_with_options(&test.grammatical, lexicon, options).is_ok();
+    let ungrammatical_rejected = parse_with_options(&test.ungrammatical, lexicon, options).is_err();
+
+    (grammatical_parsed, ungrammatical_rejected)
+}
+
+/// Run complete island test suite
+pub fn run_island_suite() -> IslandResults {
+    run_island_suite_with(&island_lexicon(), &island_options())
+}
+
+/// Share of correct judgments in each group
+fn group_accuracy(groups: HashMap<&'static str, Vec<bool>>) -> HashMap<&'static str, f64> {
+    groups.into_iter()
+        .map(|(group, results)| {
+            let correct = results.iter().filter(|&&x| x).count();
+            (group, correct as f64 / results.len() as f64)
+        })
+        .collect()
+}
+
+/// Run island test suite with a given lexicon and grammar options
+pub fn run_island_suite_with(lexicon: &[LexItem], options: &GrammarOptions) -> IslandResults {
+    let tests = generate_island_tests();
+
+    let mut total = 0;
+    let mut correct_grammatical = 0;
+    let mut correct_ungrammatical = 0;
+    let mut by_phenomenon: HashMap<&'static str, Vec<bool>> = HashMap::new();
+    let mut by_constraint: HashMap<&'static str, Vec<bool>> = HashMap::new();
+
+    println!("🧪 Running Island Test Suite");
+    println!("{}", "=".repeat(60));
+
+    for test in &tests {
+        let (gram_ok, ungram_rejected) = test_island_pair(test, lexicon, options);
+
+        total += 2;
+
+        if gram_ok {
+            correct_grammatical += 1;
+            println!("✅ GRAM: {}", test.grammatical);
+        } else {
+            println!("❌ GRAM: {}", test.grammatical);
+        }
+
+        if ungram_rejected {
+            correct_ungrammatical += 1;
+            println!("✅ UNGRAM: *{} (correctly rejected)", test.ungrammatical);
+        } else {
+            println!("❌ UNGRAM: *{} (incorrectly accepted)", test.ungrammatical);
+        }
+
+        by_phenomenon.entry(test.phenomenon)
+            .or_default()
+            .extend([gram_ok, ungram_rejected]);
+        by_constraint.entry(test.constraint)
+            .or_default()
+            .extend([gram_ok, ungram_rejected]);
+
+        println!("   Phenomenon: {} ({})", test.phenomenon, test.constraint);
+        println!();
+    }
+
+    let accuracy = (correct_grammatical + correct_ungrammatical) as f64 / total as f64;
+
+    IslandResults {
+        total,
+        correct_grammatical,
+        correct_ungrammatical,
+        accuracy,
+        by_phenomenon: group_accuracy(by_phenomenon),
+        by_constraint: group_accuracy(by_constraint),
+    }
+}
+
+/// Print detailed results analysis
+pub fn print_island_analysis(results: &IslandResults) {
+    println!("\n📊 ISLAND TEST RESULTS");
+    println!("{}", "=".repeat(40));
+    println!("Total test cases: {}", results.total);
+    println!("Correct grammatical: {}/{}", results.correct_grammatical, results.total / 2);
+    println!("Correct ungrammatical: {}/{}", results.correct_ungrammatical, results.total / 2);
+    println!("Overall accuracy: {:.1}%", results.accuracy * 100.0);
+
+    for (heading, groups) in [("PHENOMENON", &results.by_phenomenon), ("CONSTRAINT", &results.by_constraint)] {
+        println!("\n📈 ACCURACY BY {}:", heading);
+        let mut groups: Vec<_> = groups.iter().collect();
+        groups.sort_by_key(|&(name, _)| *name);
+        for (group, accuracy) in groups {
+            println!("  {}: {:.1}%", group, accuracy * 100.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_island_generation() {
+        for test in &generate_island_tests() {
+            assert_ne!(test.grammatical, test.ungrammatical, "Sentences should differ");
+        }
+    }
+
+    #[test]
+    fn test_adjunct_island() {
+        let lexicon = island_lexicon();
+        let options = island_options();
+
+        assert_eq!(
+            parse_with_options("who did Mary smile before you saw", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(Subjacency::NAME))
+        );
+
+        // Matrix extraction skips the adjunct entirely
+        let tree = parse_with_options("who did Mary see before you smiled", &lexicon, &options)
+            .expect("extraction from the matrix clause should parse");
+        let chains = constraints::chains(&tree);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(Subjacency::english().crossings(&tree, &chains[0]), 1);
+    }
+
+    #[test]
+    fn test_superiority_minimal_pair() {
+        let lexicon = island_lexicon();
+        let options = island_options();
+
+        let tree = parse_with_options("who saw what", &lexicon, &options)
+            .expect("moving the higher wh-phrase should parse");
+        assert_eq!(tree.linearize(), "who saw what");
+
+        assert_eq!(
+            parse_with_options("what did who see", &lexicon, &options),
+            Err(DerivationError::ConstraintViolation(Superiority::NAME))
+        );
+    }
+
+    #[test]
+    fn test_constraints_are_configurable() {
+        // Without the constraints every ungrammatical item converges
+        let lexicon = island_lexicon();
+        for test in &generate_island_tests() {
+            assert!(parse_sentence(&test.ungrammatical, &lexicon).is_ok(), "{}", test.ungrammatical);
+        }
+    }
+
+    #[test]
+    fn test_island_suite_runs() {
+        let results = run_island_suite();
+
+        assert_eq!(results.total, generate_island_tests().len() * 2);
+        assert_eq!(results.accuracy, 1.0);
+        assert_eq!(results.by_phenomenon.len(), 3);
+        assert_eq!(results.by_constraint.len(), 2);
+
+        print_island_analysis(&results);
+    }
+}
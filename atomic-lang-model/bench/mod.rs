@@ -1,17 +1,62 @@
 //! Evaluation Benchmark Suite
 //! 
 //! Comprehensive testing harness for the atomic language model including:
-//! - Agreement tests (Linzen et al. 2016)
+//! - Agreement tests (Linzen et al. 2016), plus generated items with 0-5 attractors
+//! - Cross-linguistic agreement (the Hindi and Italian fragments, see `grammars`)
 //! - Colorless green tests (Gulordava et al. 2018)
+//! - Extraction tests (that-trace and wh-island minimal pairs)
+//! - ECP tests (subject/object extraction asymmetries)
+//! - Island tests (wh-island, adjunct island and superiority minimal pairs)
+//! - Synthesized tests (one construction per lexical frame, see `synthesis`)
+//! - Movement chains (precision/recall against gold trees with traces)
 //! - Performance and memory profiling
+//! - Sustained throughput (sentences/sec, p50/p95 latency)
+//! - Long-sentence scaling (empirical polynomial exponent)
+//! - Best-first search heuristics (expansions versus uninformed search)
+//! - Forest pruning (accuracy versus the exact search)
+//! - Feature checking with bitsets versus feature-vector scans
+//! - Noise robustness (parse rate under token swaps, deletions and typos)
+//! - aⁿbⁿ stress generation and recognition up to n = 10⁶ (`parallel` feature)
 //! - Recursive capability verification
+//! - A/B comparison of grammar variants across all suites
+//! - Self-contained HTML report with regression deltas against a baseline
 
 pub mod agreement_suite;
+pub mod chain_suite;
 pub mod colorless_green;
+pub mod comparison;
+pub mod crosslinguistic;
+pub mod ecp_suite;
+pub mod extraction_suite;
+pub mod feature_checks;
+pub mod island_suite;
+pub mod noise;
+pub mod pruning;
+pub mod report;
+pub mod scaling;
+pub mod search_heuristics;
+#[cfg(feature = "parallel")]
+pub mod stress;
+pub mod synthesized_suite;
+pub mod throughput;
 
 use atomic_lang_model::*;
 use agreement_suite::*;
+use chain_suite::*;
 use colorless_green::*;
+use crosslinguistic::*;
+use ecp_suite::*;
+use extraction_suite::*;
+use feature_checks::*;
+use island_suite::*;
+use noise::*;
+use pruning::*;
+use scaling::*;
+use search_heuristics::*;
+#[cfg(feature = "parallel")]
+use stress::*;
+use synthesized_suite::*;
+use throughput::*;
 use std::time::Instant;
 
 /// Combined benchmark results
@@ -19,10 +64,22 @@ use std::time::Instant;
 pub struct BenchmarkResults {
     /// Agreement test results
     pub agreement: AgreementResults,
+    /// Agreement results of each language fragment with agreement pairs
+    pub crosslinguistic: CrossLinguisticResults,
     /// Colorless green test results
     pub colorless_green: ColorlessGreenResults,
+    /// Extraction test results
+    pub extraction: ExtractionResults,
+    /// ECP test results
+    pub ecp: EcpResults,
+    /// Synthesized test results
+    pub synthesized: SynthesizedResults,
+    /// Movement chain results
+    pub chains: ChainResults,
     /// Performance metrics
     pub performance: PerformanceMetrics,
+    /// Long-sentence scaling results
+    pub scaling: ScalingResults,
     /// Overall score
     pub overall_score: f64,
 }
@@ -34,18 +91,24 @@ pub struct PerformanceMetrics {
     pub total_runtime_ms: f64,
     /// Average parse time per sentence (microseconds)
     pub avg_parse_time_us: f64,
-    /// Peak memory usage (bytes)
+    /// Peak memory usage reached during derivation (bytes)
     pub peak_memory_bytes: usize,
     /// Successful parse rate
     pub parse_success_rate: f64,
     /// Recursive depth achieved
     pub max_recursive_depth: usize,
+    /// Sustained throughput over generated sentences
+    pub sentences_per_sec: f64,
+    /// Median parse latency under sustained load (microseconds)
+    pub p50_latency_us: f64,
+    /// 95th-percentile parse latency under sustained load (microseconds)
+    pub p95_latency_us: f64,
 }
 
 /// Run complete benchmark suite
 pub fn run_complete_benchmark() -> BenchmarkResults {
     println!("🚀 ATOMIC LANGUAGE MODEL - COMPLETE BENCHMARK SUITE");
-    println!("=" .repeat(70));
+    println!("{}", "=".repeat(70));
     println!("Testing recursive universal grammar with mathematical rigor");
     println!();
     
@@ -53,28 +116,116 @@ pub fn run_complete_benchmark() -> BenchmarkResults {
     
     // 1. Agreement Tests
     println!("Phase 1: Agreement Test Suite");
-    println!("-" .repeat(30));
+    println!("{}", "-".repeat(30));
     let agreement_results = run_agreement_suite();
     print_agreement_analysis(&agreement_results);
     println!();
     
+    println!("Phase 1b: Scaled Agreement Suite (0-{} attractors)", MAX_ATTRACTORS);
+    println!("{}", "-".repeat(30));
+    print_agreement_analysis(&run_scaled_agreement_suite());
+    println!();
+    
+    println!("Phase 1c: Cross-Linguistic Agreement");
+    println!("{}", "-".repeat(30));
+    let crosslinguistic_results = run_crosslinguistic_suite();
+    print_crosslinguistic_analysis(&crosslinguistic_results);
+    println!();
+    
     // 2. Colorless Green Tests  
     println!("Phase 2: Colorless Green Test Suite");
-    println!("-" .repeat(30));
+    println!("{}", "-".repeat(30));
     let colorless_green_results = run_colorless_green_suite();
     print_colorless_green_analysis(&colorless_green_results);
     println!();
     
-    // 3. Performance Tests
-    println!("Phase 3: Performance and Memory Profiling");
-    println!("-" .repeat(30));
+    // 3. Extraction Tests
+    println!("Phase 3: Extraction Test Suite");
+    println!("{}", "-".repeat(30));
+    let extraction_results = run_extraction_suite();
+    print_extraction_analysis(&extraction_results);
+    println!();
+    
+    // 4. ECP Tests
+    println!("Phase 4: ECP Test Suite");
+    println!("{}", "-".repeat(30));
+    let ecp_results = run_ecp_suite();
+    print_ecp_analysis(&ecp_results);
+    println!();
+    
+    // 4a. Island Tests
+    println!("Phase 4a: Island Test Suite");
+    println!("{}", "-".repeat(30));
+    print_island_analysis(&run_island_suite());
+    println!();
+    
+    // 4b. Synthesized Tests
+    println!("Phase 4b: Synthesized Test Suite");
+    println!("{}", "-".repeat(30));
+    let synthesized_results = run_synthesized_suite();
+    print_synthesized_analysis(&synthesized_results);
+    println!();
+    
+    // 4c. Movement Chains
+    println!("Phase 4c: Movement Chains");
+    println!("{}", "-".repeat(30));
+    let chain_results = run_chain_suite();
+    print_chain_analysis(&chain_results);
+    println!();
+    
+    // 5. Performance Tests
+    println!("Phase 5: Performance and Memory Profiling");
+    println!("{}", "-".repeat(30));
     let performance_results = run_performance_tests();
     print_performance_analysis(&performance_results);
     println!();
     
-    // 4. Recursive Capability Tests
-    println!("Phase 4: Recursive Capability Verification");
-    println!("-" .repeat(30));
+    // 6. Throughput Tests
+    println!("Phase 6: Sustained Throughput");
+    println!("{}", "-".repeat(30));
+    let throughput_results = run_throughput(THROUGHPUT_SENTENCES);
+    print_throughput_analysis(&throughput_results);
+    println!();
+    
+    // 7. Long-Sentence Scaling
+    println!("Phase 7: Long-Sentence Polynomial-Time Verification");
+    println!("{}", "-".repeat(30));
+    let scaling_results = run_scaling();
+    print_scaling_analysis(&scaling_results);
+    println!();
+    
+    // aⁿbⁿ stress testing needs the `parallel` feature
+    #[cfg(feature = "parallel")]
+    {
+        println!("Phase 7b: aⁿbⁿ Stress Generation and Verification");
+        println!("{}", "-".repeat(30));
+        print_stress_analysis(&run_stress());
+        println!();
+    }
+    
+    println!("Phase 7c: Best-First Search Heuristics");
+    println!("{}", "-".repeat(30));
+    print_heuristics_analysis(&run_heuristics());
+    println!();
+    
+    println!("Phase 7d: Forest Pruning");
+    println!("{}", "-".repeat(30));
+    print_pruning_analysis(&run_pruning());
+    println!();
+    
+    println!("Phase 7e: Feature Checking");
+    println!("{}", "-".repeat(30));
+    print_feature_check_analysis(&run_feature_checks());
+    println!();
+    
+    println!("Phase 7f: Noise Robustness");
+    println!("{}", "-".repeat(30));
+    print_noise_analysis(&run_noise());
+    println!();
+    
+    // 8. Recursive Capability Tests
+    println!("Phase 8: Recursive Capability Verification");
+    println!("{}", "-".repeat(30));
     run_recursive_verification();
     println!();
     
@@ -89,13 +240,22 @@ pub fn run_complete_benchmark() -> BenchmarkResults {
     
     let final_performance = PerformanceMetrics {
         total_runtime_ms: total_runtime,
+        sentences_per_sec: throughput_results.sentences_per_sec,
+        p50_latency_us: throughput_results.p50_latency_us,
+        p95_latency_us: throughput_results.p95_latency_us,
         ..performance_results
     };
     
     let results = BenchmarkResults {
         agreement: agreement_results,
+        crosslinguistic: crosslinguistic_results,
         colorless_green: colorless_green_results,
+        extraction: extraction_results,
+        ecp: ecp_results,
+        synthesized: synthesized_results,
+        chains: chain_results,
         performance: final_performance,
+        scaling: scaling_results,
         overall_score,
     };
     
@@ -124,28 +284,17 @@ fn run_performance_tests() -> PerformanceMetrics {
     
     for sentence in &test_sentences {
         let start = Instant::now();
-        
-        // Create workspace for memory tracking
-        let mut workspace = Workspace::new(4096);
         let tokens: Vec<&str> = sentence.split_whitespace().collect();
         
-        // Add tokens to workspace
-        for token in &tokens {
-            if let Some(lex_item) = lexicon.iter().find(|item| item.phon == *token) {
-                workspace.add_lex(lex_item);
-            }
-        }
-        
-        // Track memory usage
-        let memory_usage = workspace.memory_usage();
-        peak_memory = peak_memory.max(memory_usage);
-        
-        // Attempt parsing
-        let result = parse_sentence(sentence, &lexicon);
+        // Attempt parsing, tracking the memory high-water mark of the search
+        let outcome = parse_profiled(sentence, &lexicon, &GrammarOptions::default());
         let parse_time = start.elapsed().as_micros() as f64;
         parse_times.push(parse_time);
         
-        if result.is_ok() {
+        let memory_usage = outcome.peak_memory;
+        peak_memory = peak_memory.max(memory_usage);
+        
+        if outcome.result.is_ok() {
             successful_parses += 1;
             println!("  ✅ '{}' - {:.1}μs, {}B memory", sentence, parse_time, memory_usage);
         } else {
@@ -166,6 +315,9 @@ fn run_performance_tests() -> PerformanceMetrics {
         peak_memory_bytes: peak_memory,
         parse_success_rate: success_rate,
         max_recursive_depth: max_depth,
+        sentences_per_sec: 0.0, // Set by the throughput phase
+        p50_latency_us: 0.0,
+        p95_latency_us: 0.0,
     }
 }
 
@@ -199,12 +351,16 @@ fn print_performance_analysis(results: &PerformanceMetrics) {
 fn run_recursive_verification() {
     println!("♾️  RECURSIVE CAPABILITY VERIFICATION:");
     
-    // Test aⁿbⁿ generation
-    println!("\n1. aⁿbⁿ Generation Test:");
-    for n in 0..=8 {
-        if let Ok(pattern) = generate_pattern("an_bn", n) {
-            let display = if pattern.is_empty() { "ε".to_string() } else { pattern };
-            println!("  n={}: {}", n, display);
+    // Test generation of every registered pattern
+    println!("\n1. Pattern Generation Test:");
+    let patterns = PatternRegistry::new();
+    for pattern in patterns.iter() {
+        println!("  {} ({}):", pattern.description(), pattern.complexity_class());
+        for n in 0..=8 {
+            let member = pattern.generate(n);
+            let mark = if pattern.recognize(&member) { "✅" } else { "❌" };
+            let display = if member.is_empty() { "ε".to_string() } else { member };
+            println!("    {} n={}: {}", mark, n, display);
         }
     }
     
@@ -232,11 +388,17 @@ fn run_recursive_verification() {
     // Test mathematical properties
     println!("\n3. Mathematical Property Verification:");
     
-    // Non-regularity demonstration
-    println!("  • Non-regularity: aⁿbⁿ generation ✅");
+    // Position in the hierarchy, backed by checked certificates
+    for pattern in patterns.iter() {
+        let certificates: Vec<Certificate> = (1..=5).flat_map(|p| pattern.certificates(p)).collect();
+        let verified = certificates.iter().filter(|certificate| certificate.verify(pattern).is_ok()).count();
+        let mark = if !certificates.is_empty() && verified == certificates.len() { "✅" } else { "❌" };
+        println!("  • {} is {}: {}/{} certificates verified {}",
+                 pattern.name(), pattern.complexity_class(), verified, certificates.len(), mark);
+    }
     
     // Closure under recursion
-    let closure_test = (0..=5).all(|n| can_generate("an_bn", n));
+    let closure_test = patterns.iter().all(|pattern| (0..=5).all(|n| pattern.recognize(&pattern.generate(n))));
     if closure_test {
         println!("  • Closure under recursion ✅");
     } else {
@@ -288,15 +450,32 @@ fn calculate_overall_score(
 /// Print final benchmark summary
 fn print_final_summary(results: &BenchmarkResults) {
     println!("\n🏆 FINAL BENCHMARK SUMMARY");
-    println!("=" .repeat(50));
+    println!("{}", "=".repeat(50));
     println!("Overall Score: {:.1}%", results.overall_score * 100.0);
     println!("Total Runtime: {:.1}ms", results.performance.total_runtime_ms);
     
     println!("\n📊 Component Scores:");
     println!("  Agreement Tests: {:.1}%", results.agreement.accuracy * 100.0);
+    for (language, agreement) in &results.crosslinguistic.by_language {
+        println!("  {} Agreement: {:.1}%", language, agreement.accuracy * 100.0);
+    }
     println!("  Colorless Green: {:.1}%", results.colorless_green.accuracy * 100.0);
+    println!("  Extraction Tests: {:.1}%", results.extraction.accuracy * 100.0);
+    println!("  ECP Tests: {:.1}%", results.ecp.accuracy * 100.0);
+    println!("  Synthesized Tests: {:.1}%", results.synthesized.accuracy * 100.0);
+    let chains = results.chains.default_score();
+    println!("  Movement Chains: {:.1}% precision, {:.1}% recall", chains.precision() * 100.0, chains.recall() * 100.0);
     println!("  Performance: {:.1}μs avg", results.performance.avg_parse_time_us);
     println!("  Memory Usage: {}B peak", results.performance.peak_memory_bytes);
+    println!(
+        "  Throughput: {:.0} sentences/sec (p50 {:.1}μs, p95 {:.1}μs)",
+        results.performance.sentences_per_sec,
+        results.performance.p50_latency_us,
+        results.performance.p95_latency_us,
+    );
+    if let Some(fit) = &results.scaling.fit {
+        println!("  Scaling: t ∝ n^{:.2} over 11-99 tokens", fit.exponent);
+    }
     
     println!("\n🎯 Key Achievements:");
     
@@ -312,6 +491,10 @@ fn print_final_summary(results: &BenchmarkResults) {
         println!("  ✅ Fast parsing performance");
     }
     
+    if let Some(fit) = results.scaling.fit.filter(|fit| fit.r_squared >= 0.9 && fit.is_bounded_by(3.0)) {
+        println!("  ✅ Polynomial-time parsing (n^{:.2})", fit.exponent);
+    }
+    
     if results.performance.peak_memory_bytes < 2048 {
         println!("  ✅ Efficient memory usage");
     }
@@ -0,0 +1,224 @@
+//! Noise Robustness
+//!
+//! Corrupts grammatical sentences at controlled rates and measures how
+//! parsing degrades. Each token is corrupted independently with the given
+//! probability, by one kind of noise per run:
+//!
+//! - swap: the token trades places with the next one
+//! - deletion: the token is dropped (one token always survives)
+//! - typo: one letter of the token is replaced
+//!
+//! Every corrupted sentence is parsed strictly and in skip mode
+//! (`parse_skipping_unknown`), which sets unknown tokens aside and falls
+//! back to the largest parsable fragments when the rest does not parse.
+//! Typos cost skip mode only the mistyped tokens; swaps and deletions,
+//! whose words are all known, leave it fragments. The coverage of its
+//! analyses shows how much of the input survives.
+
+use super::throughput::generate_throughput_sentences;
+use atomic_lang_model::determinism::xorshift;
+use atomic_lang_model::*;
+
+/// Corruption rates measured, per token
+pub const RATES: [f64; 5] = [0.0, 0.1, 0.2, 0.3, 0.5];
+
+/// Distinct clauses of the throughput generator, each corrupted once per level
+pub const SENTENCES: usize = 54;
+
+/// Penalty per skipped token in skip mode
+const SKIP_PENALTY: f64 = 1.0;
+
+/// Kind of noise applied to a sentence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Corruption {
+    /// Adjacent tokens swapped
+    Swap,
+    /// Tokens dropped
+    Deletion,
+    /// A letter replaced within a token
+    Typo,
+}
+
+impl Corruption {
+    /// Every kind of noise
+    pub const ALL: [Corruption; 3] = [Corruption::Swap, Corruption::Deletion, Corruption::Typo];
+}
+
+/// Parse rates at one corruption kind and rate
+#[derive(Debug, Clone)]
+pub struct NoiseLevel {
+    /// Kind of noise
+    pub corruption: Corruption,
+    /// Probability of corrupting each token
+    pub rate: f64,
+    /// Sentences parsed
+    pub sentences: usize,
+    /// Sentences left unchanged by the corruption
+    pub unchanged: usize,
+    /// Sentences accepted by the strict parser
+    pub strict: usize,
+    /// Sentences analysed in skip mode
+    pub skipping: usize,
+    /// Share of tokens covered by the skip-mode analyses, over those analysed
+    pub coverage: f64,
+}
+
+impl NoiseLevel {
+    /// Share of sentences the strict parser accepts
+    pub fn strict_rate(&self) -> f64 {
+        self.strict as f64 / self.sentences.max(1) as f64
+    }
+
+    /// Share of sentences skip mode analyses
+    pub fn skipping_rate(&self) -> f64 {
+        self.skipping as f64 / self.sentences.max(1) as f64
+    }
+}
+
+/// Results of the noise-robustness phase
+#[derive(Debug, Clone)]
+pub struct NoiseResults {
+    /// One level per corruption kind and rate, kinds in `Corruption::ALL` order
+    pub levels: Vec<NoiseLevel>,
+}
+
+/// Deterministic xorshift generator, so every run corrupts alike
+struct Noise(u64);
+
+impl Noise {
+    fn next(&mut self) -> u64 {
+        xorshift(&mut self.0)
+    }
+
+    /// Uniform sample in [0, 1)
+    fn chance(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Corrupt each token of `sentence` with probability `rate`
+fn corrupt(sentence: &str, corruption: Corruption, rate: f64, noise: &mut Noise) -> String {
+    let mut tokens: Vec<String> = sentence.split_whitespace().map(String::from).collect();
+    match corruption {
+        Corruption::Swap => {
+            for i in 0..tokens.len().saturating_sub(1) {
+                if noise.chance() < rate {
+                    tokens.swap(i, i + 1);
+                }
+            }
+        }
+        Corruption::Deletion => {
+            let mut kept: Vec<String> = tokens.iter().filter(|_| noise.chance() >= rate).cloned().collect();
+            if kept.is_empty() {
+                kept.push(tokens.swap_remove(noise.below(tokens.len())));
+            }
+            tokens = kept;
+        }
+        Corruption::Typo => {
+            for token in &mut tokens {
+                if noise.chance() < rate {
+                    let mut letters: Vec<char> = token.chars().collect();
+                    let at = noise.below(letters.len());
+                    // Shift within the alphabet so the letter always changes;
+                    // anything but a lowercase letter becomes one
+                    let offset = match letters[at] {
+                        letter @ 'a'..='z' => (letter as u8 - b'a' + 1 + noise.below(25) as u8) % 26,
+                        _ => noise.below(26) as u8,
+                    };
+                    letters[at] = (b'a' + offset) as char;
+                    *token = letters.into_iter().collect();
+                }
+            }
+        }
+    }
+    tokens.join(" ")
+}
+
+/// Parse `count` clauses under every corruption kind and rate
+pub fn run_noise_with(count: usize, rates: &[f64]) -> NoiseResults {
+    let options = GrammarOptions::default();
+    let lexicon = options.lexicon();
+    let sentences = generate_throughput_sentences(count);
+    let mut noise = Noise(0x2545_F491_4F6C_DD1D);
+
+    let mut levels = Vec::new();
+    for corruption in Corruption::ALL {
+        for &rate in rates {
+            let mut level = NoiseLevel { corruption, rate, sentences: sentences.len(), unchanged: 0, strict: 0, skipping: 0, coverage: 0.0 };
+            let mut covered = 0.0;
+            for sentence in &sentences {
+                let corrupted = corrupt(sentence, corruption, rate, &mut noise);
+                level.unchanged += (corrupted == *sentence) as usize;
+                level.strict += parse_with_options(&corrupted, &lexicon, &options).is_ok() as usize;
+                if let Ok(analysis) = parse_skipping_unknown(&corrupted, &lexicon, &options, SKIP_PENALTY) {
+                    let tokens = corrupted.split_whitespace().count();
+                    let skipped: usize = analysis.skipped.iter().map(|run| run.span.len()).sum();
+                    level.skipping += 1;
+                    covered += (tokens - skipped) as f64 / tokens as f64;
+                }
+            }
+            level.coverage = covered / level.skipping.max(1) as f64;
+            levels.push(level);
+        }
+    }
+    NoiseResults { levels }
+}
+
+/// Run the noise-robustness phase
+pub fn run_noise() -> NoiseResults {
+    run_noise_with(SENTENCES, &RATES)
+}
+
+/// Print parse rate against corruption rate for each kind of noise
+pub fn print_noise_analysis(results: &NoiseResults) {
+    println!("📉 NOISE ROBUSTNESS (parse rate vs corruption rate):");
+    for corruption in Corruption::ALL {
+        println!("  {:?}:", corruption);
+        for level in results.levels.iter().filter(|level| level.corruption == corruption) {
+            println!(
+                "    {:>3.0}%: strict {:>5.1}%, skip mode {:>5.1}% (coverage {:>5.1}%), {} unchanged",
+                level.rate * 100.0,
+                level.strict_rate() * 100.0,
+                level.skipping_rate() * 100.0,
+                level.coverage * 100.0,
+                level.unchanged
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corruption_kinds() {
+        let mut noise = Noise(1);
+        assert_eq!(corrupt("the student left", Corruption::Swap, 1.0, &mut noise), "student left the");
+        assert_eq!(corrupt("the student left", Corruption::Deletion, 1.0, &mut noise).split_whitespace().count(), 1);
+        let typo = corrupt("the student left", Corruption::Typo, 1.0, &mut noise);
+        assert!(typo.split_whitespace().zip("the student left".split_whitespace()).all(|(a, b)| a != b && a.len() == b.len()));
+        assert_eq!(corrupt("the student left", Corruption::Typo, 0.0, &mut noise), "the student left");
+        assert_ne!(corrupt("MARY 42", Corruption::Typo, 1.0, &mut noise), "MARY 42");
+    }
+
+    #[test]
+    fn test_degradation() {
+        let results = run_noise_with(SENTENCES, &[0.0, 0.5]);
+        assert_eq!(results.levels.len(), 6);
+        for pair in results.levels.chunks(2) {
+            let (clean, noisy) = (&pair[0], &pair[1]);
+            assert_eq!(clean.strict, clean.sentences);
+            assert!(noisy.strict < clean.strict, "{:?}", noisy.corruption);
+        }
+
+        // Skip mode recovers some typos the strict parser rejects
+        let typos = &results.levels[5];
+        assert!(typos.skipping > typos.strict);
+        assert!(typos.coverage < 1.0);
+    }
+}
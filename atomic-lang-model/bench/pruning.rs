@@ -0,0 +1,139 @@
+//! Forest Pruning
+//!
+//! Judges the agreement and extraction minimal pairs with the pruned
+//! forest search at increasing thresholds, and reports how accuracy,
+//! runtime and the number of dropped constituents change. Threshold 0 is
+//! the exact search. Pruning can only lose analyses, which can cost a
+//! grammatical sentence its parse but can also reject an ungrammatical one
+//! the exact search accepts, so accuracy may move either way; the two are
+//! counted separately.
+
+use crate::agreement_suite::{agreement_lexicon, generate_agreement_tests};
+use crate::extraction_suite::{extraction_lexicon, extraction_options, generate_extraction_tests};
+use atomic_lang_model::*;
+use std::time::Instant;
+
+/// Thresholds compared, exact search first
+pub const THRESHOLDS: [f64; 5] = [0.0, 0.5, 0.7, 0.9, 1.0];
+
+/// Weight of every local tree: compact analyses score higher
+pub const RULE_WEIGHT: f64 = 0.8;
+
+/// Outcome of one threshold
+#[derive(Debug, Clone)]
+pub struct PruningSample {
+    /// Fraction of the best score in a span below which constituents drop
+    pub threshold: f64,
+    /// Correct judgments over both suites
+    pub accuracy: f64,
+    /// Grammatical sentences pruning left without an analysis
+    pub lost: usize,
+    /// Ungrammatical sentences the exact search accepts that pruning
+    /// left without an analysis
+    pub rejected: usize,
+    /// Constituents dropped
+    pub pruned: usize,
+    /// Time to judge every sentence (microseconds)
+    pub time_us: f64,
+}
+
+/// Results of the pruning phase
+#[derive(Debug, Clone)]
+pub struct PruningResults {
+    /// One sample per threshold, in the order given
+    pub samples: Vec<PruningSample>,
+}
+
+/// Judge the minimal pairs at each threshold
+pub fn run_pruning_with(thresholds: &[f64]) -> PruningResults {
+    let agreement = (
+        agreement_lexicon(),
+        GrammarOptions::default(),
+        generate_agreement_tests().into_iter().map(|test| (test.grammatical, test.ungrammatical)).collect::<Vec<_>>(),
+    );
+    let extraction = (
+        extraction_lexicon(),
+        extraction_options(),
+        generate_extraction_tests().into_iter().map(|test| (test.grammatical, test.ungrammatical)).collect::<Vec<_>>(),
+    );
+    let weights = RuleWeights::new(RULE_WEIGHT);
+    let parsable: Vec<Vec<(bool, bool)>> = [&agreement, &extraction].iter()
+        .map(|(lexicon, options, pairs)| {
+            let parses = |sentence: &str| parse_with_options(sentence, lexicon, options).is_ok();
+            pairs.iter().map(|(grammatical, ungrammatical)| (parses(grammatical), parses(ungrammatical))).collect()
+        })
+        .collect();
+
+    let samples = thresholds.iter()
+        .map(|&threshold| {
+            let pruning = Pruning { score: &weights, threshold };
+            let (mut correct, mut total, mut lost, mut rejected_ungrammatical, mut pruned) = (0, 0, 0, 0, 0);
+            let start = Instant::now();
+            for ((lexicon, options, pairs), parsable) in [&agreement, &extraction].into_iter().zip(&parsable) {
+                for ((grammatical, ungrammatical), &(exact, overgenerated)) in pairs.iter().zip(parsable) {
+                    let accepted = parse_forest_pruned(grammatical, lexicon, options, &Unranked, pruning);
+                    let rejected = parse_forest_pruned(ungrammatical, lexicon, options, &Unranked, pruning);
+                    lost += usize::from(exact && accepted.parses.is_err());
+                    rejected_ungrammatical += usize::from(overgenerated && rejected.parses.is_err());
+                    correct += usize::from(accepted.parses.is_ok()) + usize::from(rejected.parses.is_err());
+                    total += 2;
+                    pruned += accepted.pruned + rejected.pruned;
+                }
+            }
+            PruningSample {
+                threshold,
+                accuracy: correct as f64 / total as f64,
+                lost,
+                rejected: rejected_ungrammatical,
+                pruned,
+                time_us: start.elapsed().as_secs_f64() * 1e6,
+            }
+        })
+        .collect();
+    PruningResults { samples }
+}
+
+/// Run the pruning phase
+pub fn run_pruning() -> PruningResults {
+    run_pruning_with(&THRESHOLDS)
+}
+
+/// Print accuracy against the exact search
+pub fn print_pruning_analysis(results: &PruningResults) {
+    println!("✂️  FOREST PRUNING (uniform rule weight {}):", RULE_WEIGHT);
+    let exact = results.samples.first().map_or(0.0, |sample| sample.accuracy);
+    for sample in &results.samples {
+        let mark = if sample.lost == 0 { "✅" } else { "⚠️ " };
+        println!(
+            "  {} threshold {:.2} - accuracy {:.1}% ({:+.1} points), {} lost, {} newly rejected, {} pruned, {:.0}μs",
+            mark,
+            sample.threshold,
+            sample.accuracy * 100.0,
+            (sample.accuracy - exact) * 100.0,
+            sample.lost,
+            sample.rejected,
+            sample.pruned,
+            sample.time_us,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pruning_only_loses_analyses() {
+        let results = run_pruning();
+        let exact = &results.samples[0];
+        assert_eq!(exact.threshold, 0.0);
+        assert_eq!((exact.lost, exact.rejected, exact.pruned), (0, 0, 0));
+
+        // Every change in accuracy is a sentence pruning left unparsed
+        let total = (generate_agreement_tests().len() + generate_extraction_tests().len()) as f64 * 2.0;
+        for sample in &results.samples[1..] {
+            let change = (sample.rejected as f64 - sample.lost as f64) / total;
+            assert!((sample.accuracy - exact.accuracy - change).abs() < 1e-9);
+        }
+    }
+}
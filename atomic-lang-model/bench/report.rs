@@ -0,0 +1,438 @@
+//! HTML Benchmark Report
+//!
+//! Renders `BenchmarkResults` as one self-contained HTML file: accuracy
+//! tables and bar charts for each suite's breakdowns (depth, attractors,
+//! complexity, category, constraint, environment), performance figures and
+//! the long-sentence timing curve as inline SVG. The report embeds its
+//! metrics as tab-separated values, so a saved report can serve as the
+//! baseline of a later one, which then tabulates the change in every
+//! metric and flags regressions.
+
+use super::scaling::ScalingSample;
+use super::BenchmarkResults;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// Change for the worse a metric tolerates before it counts as a regression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// Fraction of the baseline value
+    Relative(f64),
+    /// Amount in the metric's own units
+    Absolute(f64),
+}
+
+impl Tolerance {
+    /// Largest change from `baseline` tolerated
+    fn margin(self, baseline: f64) -> f64 {
+        match self {
+            Tolerance::Relative(fraction) => baseline.abs() * fraction,
+            Tolerance::Absolute(amount) => amount,
+        }
+    }
+}
+
+/// Accuracies are deterministic, so any drop is a regression
+pub const ACCURACY_TOLERANCE: Tolerance = Tolerance::Absolute(0.0);
+
+/// Timings and throughput, loose enough that timing noise is not flagged
+pub const TIME_TOLERANCE: Tolerance = Tolerance::Relative(0.10);
+
+/// Peak memory, which varies far less between runs than time does
+pub const MEMORY_TOLERANCE: Tolerance = Tolerance::Relative(0.02);
+
+/// Scaling exponent: a log-log slope, so compared in absolute terms; an
+/// increase of 0.1 takes `n^2` to `n^2.1` whatever the baseline exponent
+pub const SCALING_TOLERANCE: Tolerance = Tolerance::Absolute(0.1);
+
+/// One named figure of a benchmark run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    /// Dotted name, e.g. `agreement.depth.1`
+    pub name: String,
+    /// Measured value
+    pub value: f64,
+    /// Whether an increase is an improvement (accuracy) or not (time, memory)
+    pub higher_is_better: bool,
+    /// Change for the worse tolerated before it is a regression
+    pub tolerance: Tolerance,
+}
+
+/// Change in one metric from the baseline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    /// Metric name
+    pub name: String,
+    /// Baseline value
+    pub baseline: f64,
+    /// Current value
+    pub current: f64,
+    /// Whether the change is a regression
+    pub regression: bool,
+}
+
+/// Every metric of a run, in report order
+pub fn metrics(results: &BenchmarkResults) -> Vec<Metric> {
+    let mut metrics = Vec::new();
+    let mut push = |name: String, value: f64, higher_is_better: bool, tolerance: Tolerance| {
+        metrics.push(Metric { name, value, higher_is_better, tolerance });
+    };
+    push("overall".to_string(), results.overall_score, true, ACCURACY_TOLERANCE);
+    for (suite, accuracy, breakdowns) in breakdowns(results) {
+        push(format!("{}.accuracy", suite), accuracy, true, ACCURACY_TOLERANCE);
+        for (breakdown, rows) in breakdowns {
+            for (key, value) in rows {
+                push(format!("{}.{}.{}", suite, breakdown, key), value, true, ACCURACY_TOLERANCE);
+            }
+        }
+    }
+    for (language, agreement) in &results.crosslinguistic.by_language {
+        let suite = format!("crosslinguistic.{}", language.to_lowercase());
+        push(format!("{}.accuracy", suite), agreement.accuracy, true, ACCURACY_TOLERANCE);
+        for (attractors, value) in sorted(&agreement.by_attractors) {
+            push(format!("{}.attractors.{}", suite, attractors), value, true, ACCURACY_TOLERANCE);
+        }
+    }
+    for (analysis, score) in &results.chains.by_analysis {
+        push(format!("chains.{:?}.precision", analysis), score.precision(), true, ACCURACY_TOLERANCE);
+        push(format!("chains.{:?}.recall", analysis), score.recall(), true, ACCURACY_TOLERANCE);
+    }
+    let performance = &results.performance;
+    push("performance.avg_parse_time_us".to_string(), performance.avg_parse_time_us, false, TIME_TOLERANCE);
+    push("performance.peak_memory_bytes".to_string(), performance.peak_memory_bytes as f64, false, MEMORY_TOLERANCE);
+    push("performance.sentences_per_sec".to_string(), performance.sentences_per_sec, true, TIME_TOLERANCE);
+    push("performance.p50_latency_us".to_string(), performance.p50_latency_us, false, TIME_TOLERANCE);
+    push("performance.p95_latency_us".to_string(), performance.p95_latency_us, false, TIME_TOLERANCE);
+    if let Some(fit) = &results.scaling.fit {
+        push("scaling.exponent".to_string(), fit.exponent, false, SCALING_TOLERANCE);
+    }
+    metrics
+}
+
+/// Changes from a baseline's metrics, for the metrics both runs have
+pub fn deltas(current: &[Metric], baseline: &[(String, f64)]) -> Vec<Delta> {
+    current.iter()
+        .filter_map(|metric| {
+            let &(_, before) = baseline.iter().find(|(name, _)| *name == metric.name)?;
+            let regression = if metric.higher_is_better {
+                metric.value < before - metric.tolerance.margin(before) - 1e-9
+            } else {
+                metric.value > before + metric.tolerance.margin(before)
+            };
+            Some(Delta { name: metric.name.clone(), baseline: before, current: metric.value, regression })
+        })
+        .collect()
+}
+
+/// Metrics embedded in a report written by `render_report`
+pub fn read_baseline(html: &str) -> Option<Vec<(String, f64)>> {
+    let start = html.find(METRICS_OPEN)? + METRICS_OPEN.len();
+    let end = start + html[start..].find("</script>")?;
+    Some(
+        html[start..end].lines()
+            .filter_map(|line| {
+                let (name, value) = line.split_once('\t')?;
+                Some((name.to_string(), value.parse().ok()?))
+            })
+            .collect(),
+    )
+}
+
+const METRICS_OPEN: &str = "<script type=\"text/tab-separated-values\" id=\"metrics\">";
+
+/// Accuracy breakdowns of a suite, sorted by key
+type Breakdowns = Vec<(&'static str, Vec<(String, f64)>)>;
+
+/// Each suite's accuracy and breakdowns
+fn breakdowns(results: &BenchmarkResults) -> Vec<(&'static str, f64, Breakdowns)> {
+    vec![
+        ("agreement", results.agreement.accuracy, vec![
+            ("depth", sorted(&results.agreement.by_depth)),
+            ("attractors", sorted(&results.agreement.by_attractors)),
+        ]),
+        ("colorless_green", results.colorless_green.accuracy, vec![
+            ("complexity", sorted(&results.colorless_green.by_complexity)),
+            ("category", sorted(&results.colorless_green.by_category)),
+        ]),
+        ("extraction", results.extraction.accuracy, vec![("constraint", sorted(&results.extraction.by_constraint))]),
+        ("ecp", results.ecp.accuracy, vec![("environment", sorted(&results.ecp.by_environment))]),
+        ("synthesized", results.synthesized.accuracy, vec![("construction", sorted(&results.synthesized.by_construction))]),
+    ]
+}
+
+fn sorted<K: Display + Ord>(map: &HashMap<K, f64>) -> Vec<(String, f64)> {
+    let mut rows: Vec<(&K, &f64)> = map.iter().collect();
+    rows.sort_by(|a, b| a.0.cmp(b.0));
+    rows.into_iter().map(|(key, value)| (key.to_string(), *value)).collect()
+}
+
+/// Render a run, with changes from `baseline` if given, as an HTML page
+pub fn render_report(results: &BenchmarkResults, baseline: Option<&[(String, f64)]>) -> String {
+    let mut body = String::new();
+    body.push_str(&format!(
+        "<h1>Atomic Language Model Benchmark</h1>\n<p class=\"score\">Overall score: {}</p>\n",
+        percent(results.overall_score)
+    ));
+
+    let metrics = metrics(results);
+    if let Some(baseline) = baseline {
+        let deltas = deltas(&metrics, baseline);
+        let regressions = deltas.iter().filter(|delta| delta.regression).count();
+        body.push_str(&format!("<h2>Change from baseline</h2>\n<p>{} regression(s) in {} metrics</p>\n", regressions, deltas.len()));
+        let rows: Vec<Vec<String>> = deltas.iter()
+            .map(|delta| vec![
+                escape(&delta.name),
+                format!("{:.4}", delta.baseline),
+                format!("{:.4}", delta.current),
+                format!("{:+.4}", delta.current - delta.baseline),
+                if delta.regression { "<span class=\"bad\">regression</span>".to_string() } else { String::new() },
+            ])
+            .collect();
+        body.push_str(&table(&["Metric", "Baseline", "Current", "Δ", ""], &rows));
+    }
+
+    body.push_str("<h2>Accuracy</h2>\n");
+    let suites = breakdowns(results);
+    let summary: Vec<(String, f64)> = suites.iter().map(|(suite, accuracy, _)| (suite.to_string(), *accuracy)).collect();
+    body.push_str(&bar_chart(&summary));
+    for (suite, _, breakdowns) in &suites {
+        for (breakdown, rows) in breakdowns.iter().filter(|(_, rows)| !rows.is_empty()) {
+            body.push_str(&format!("<h3>{} by {}</h3>\n", escape(suite), breakdown));
+            body.push_str(&bar_chart(rows));
+            let rows: Vec<Vec<String>> = rows.iter().map(|(key, value)| vec![escape(key), percent(*value)]).collect();
+            body.push_str(&table(&[breakdown, "Accuracy"], &rows));
+        }
+    }
+
+    let performance = &results.performance;
+    body.push_str("<h2>Performance</h2>\n");
+    body.push_str(&table(&["Measure", "Value"], &[
+        vec!["Total runtime".to_string(), format!("{:.1} ms", performance.total_runtime_ms)],
+        vec!["Average parse time".to_string(), format!("{:.1} μs", performance.avg_parse_time_us)],
+        vec!["Peak memory".to_string(), format!("{} B", performance.peak_memory_bytes)],
+        vec!["Parse success rate".to_string(), percent(performance.parse_success_rate)],
+        vec!["Throughput".to_string(), format!("{:.0} sentences/s", performance.sentences_per_sec)],
+        vec!["Latency p50 / p95".to_string(), format!("{:.1} / {:.1} μs", performance.p50_latency_us, performance.p95_latency_us)],
+    ]));
+
+    body.push_str("<h2>Long-sentence timing</h2>\n");
+    if let Some(fit) = &results.scaling.fit {
+        body.push_str(&format!("<p>t ∝ n<sup>{:.2}</sup> (R² = {:.3})</p>\n", fit.exponent, fit.r_squared));
+    }
+    body.push_str(&line_chart(&results.scaling.samples));
+    let rows: Vec<Vec<String>> = results.scaling.samples.iter()
+        .map(|sample| vec![
+            sample.tokens.to_string(),
+            format!("{:.1}", sample.time_us),
+            if sample.parsed { "✅" } else { "❌" }.to_string(),
+        ])
+        .collect();
+    body.push_str(&table(&["Tokens", "Median time (μs)", "Parsed"], &rows));
+
+    let embedded: String = metrics.iter().map(|metric| format!("{}\t{}\n", metric.name, metric.value)).collect();
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Atomic Language Model Benchmark</title>\n<style>{}</style>\n</head>\n<body>\n{}{}\n{}</script>\n</body>\n</html>\n",
+        STYLE, body, METRICS_OPEN, embedded
+    )
+}
+
+const STYLE: &str = "body{font-family:sans-serif;max-width:56em;margin:2em auto;color:#222}\
+table{border-collapse:collapse;margin:.5em 0 1.5em}td,th{border:1px solid #ccc;padding:.2em .6em;text-align:left}\
+.score{font-size:1.4em}.bad{color:#b00;font-weight:bold}svg{display:block;margin:.5em 0}";
+
+fn percent(value: f64) -> String {
+    format!("{:.1}%", value * 100.0)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let cells = |row: &[String], tag: &str| -> String {
+        row.iter().map(|cell| format!("<{tag}>{cell}</{tag}>")).collect()
+    };
+    let header: Vec<String> = header.iter().map(|h| escape(h)).collect();
+    let mut html = format!("<table>\n<tr>{}</tr>\n", cells(&header, "th"));
+    for row in rows {
+        html.push_str(&format!("<tr>{}</tr>\n", cells(row, "td")));
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+/// Horizontal bars for accuracies between 0 and 1
+fn bar_chart(bars: &[(String, f64)]) -> String {
+    const ROW: usize = 22;
+    const WIDTH: f64 = 300.0;
+    let mut svg = format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"520\" height=\"{}\">\n", bars.len() * ROW + 4);
+    for (i, (label, value)) in bars.iter().enumerate() {
+        let y = i * ROW + 2;
+        svg.push_str(&format!(
+            "<text x=\"156\" y=\"{}\" font-size=\"12\" text-anchor=\"end\">{}</text>\
+<rect x=\"160\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"#4a7ebb\"/>\
+<text x=\"{:.1}\" y=\"{}\" font-size=\"12\">{}</text>\n",
+            y + 15, escape(label), y, value.clamp(0.0, 1.0) * WIDTH, ROW - 4, 164.0 + value.clamp(0.0, 1.0) * WIDTH, y + 15, percent(*value)
+        ));
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Parse time against sentence length
+fn line_chart(samples: &[ScalingSample]) -> String {
+    const LEFT: f64 = 60.0;
+    const TOP: f64 = 10.0;
+    const WIDTH: f64 = 420.0;
+    const HEIGHT: f64 = 200.0;
+    let Some(max_tokens) = samples.iter().map(|s| s.tokens).max() else {
+        return String::new();
+    };
+    let min_tokens = samples.iter().map(|s| s.tokens).min().unwrap_or(0);
+    let max_time = samples.iter().map(|s| s.time_us).fold(0.0, f64::max).max(1.0);
+    let x = |tokens: usize| LEFT + (tokens - min_tokens) as f64 / (max_tokens - min_tokens).max(1) as f64 * WIDTH;
+    let y = |time: f64| TOP + HEIGHT - time / max_time * HEIGHT;
+    let points: Vec<String> = samples.iter().map(|s| format!("{:.1},{:.1}", x(s.tokens), y(s.time_us))).collect();
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"520\" height=\"250\">\n\
+<line x1=\"{LEFT}\" y1=\"{bottom}\" x2=\"{right}\" y2=\"{bottom}\" stroke=\"#888\"/>\
+<line x1=\"{LEFT}\" y1=\"{TOP}\" x2=\"{LEFT}\" y2=\"{bottom}\" stroke=\"#888\"/>\n\
+<text x=\"{LEFT}\" y=\"{label}\" font-size=\"12\">{min_tokens}</text>\
+<text x=\"{right}\" y=\"{label}\" font-size=\"12\" text-anchor=\"end\">{max_tokens} tokens</text>\
+<text x=\"{axis}\" y=\"{top_label}\" font-size=\"12\" text-anchor=\"end\">{max_time:.0} μs</text>\
+<text x=\"{axis}\" y=\"{bottom}\" font-size=\"12\" text-anchor=\"end\">0</text>\n\
+<polyline points=\"{points}\" fill=\"none\" stroke=\"#4a7ebb\" stroke-width=\"2\"/>\n</svg>\n",
+        bottom = TOP + HEIGHT,
+        right = LEFT + WIDTH,
+        label = TOP + HEIGHT + 16.0,
+        axis = LEFT - 4.0,
+        top_label = TOP + 10.0,
+        points = points.join(" "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::{
+        AgreementResults, ChainResults, ColorlessGreenResults, CrossLinguisticResults, EcpResults, ExtractionResults,
+        PerformanceMetrics, ScalingResults, SynthesizedResults,
+    };
+
+    fn results() -> BenchmarkResults {
+        BenchmarkResults {
+            agreement: AgreementResults {
+                total: 4,
+                correct_grammatical: 4,
+                correct_ungrammatical: 3,
+                accuracy: 0.875,
+                by_depth: HashMap::from([(0, 1.0), (1, 0.75)]),
+                by_attractors: HashMap::from([(0, 1.0), (1, 0.75)]),
+                forced_choice_accuracy: Some(0.75),
+            },
+            crosslinguistic: CrossLinguisticResults {
+                by_language: vec![(
+                    "Hindi",
+                    AgreementResults {
+                        total: 2,
+                        correct_grammatical: 1,
+                        correct_ungrammatical: 1,
+                        accuracy: 1.0,
+                        by_depth: HashMap::from([(1, 1.0)]),
+                        by_attractors: HashMap::from([(1, 1.0)]),
+                        forced_choice_accuracy: None,
+                    },
+                )],
+            },
+            colorless_green: ColorlessGreenResults {
+                total: 2,
+                correct_grammatical: 2,
+                correct_ungrammatical: 2,
+                accuracy: 1.0,
+                complexity_penalty: 0.0,
+                by_complexity: HashMap::from([(1, 1.0)]),
+                by_category: HashMap::from([("<relative>".to_string(), 1.0)]),
+                forced_choice_accuracy: 1.0,
+            },
+            extraction: ExtractionResults {
+                total: 1,
+                correct_grammatical: 1,
+                correct_ungrammatical: 1,
+                accuracy: 1.0,
+                by_constraint: HashMap::from([("that-trace", 1.0)]),
+            },
+            ecp: EcpResults { total: 1, correct: 1, accuracy: 1.0, by_environment: HashMap::new() },
+            synthesized: SynthesizedResults {
+                total: 0,
+                correct: 0,
+                accuracy: 1.0,
+                constructions: 0,
+                unsynthesized: Vec::new(),
+                by_construction: HashMap::new(),
+            },
+            chains: ChainResults {
+                by_analysis: vec![(
+                    atomic_lang_model::RelativeClauseAnalysis::OperatorMovement,
+                    atomic_lang_model::ChainScore { gold: 2, predicted: 2, correct: 2, landings: 2, attachments: 2, unparsed: 0 },
+                )],
+            },
+            performance: PerformanceMetrics {
+                total_runtime_ms: 12.0,
+                avg_parse_time_us: 100.0,
+                peak_memory_bytes: 512,
+                parse_success_rate: 1.0,
+                max_recursive_depth: 2,
+                sentences_per_sec: 1000.0,
+                p50_latency_us: 90.0,
+                p95_latency_us: 150.0,
+            },
+            scaling: ScalingResults {
+                samples: vec![
+                    ScalingSample { tokens: 11, time_us: 50.0, parsed: true },
+                    ScalingSample { tokens: 15, time_us: 80.0, parsed: true },
+                ],
+                fit: None,
+            },
+            overall_score: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_report_is_self_contained() {
+        let html = render_report(&results(), None);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h3>agreement by attractors</h3>"));
+        assert!(html.contains("&lt;relative&gt;"));
+        assert!(html.contains("<polyline points=\"60.0,85.0 480.0,10.0\""));
+        assert!(!html.contains("Change from baseline"));
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+
+    #[test]
+    fn test_embedded_metrics_serve_as_baseline() {
+        let baseline = read_baseline(&render_report(&results(), None)).unwrap();
+        assert_eq!(baseline.len(), metrics(&results()).len());
+        assert!(baseline.contains(&("agreement.depth.1".to_string(), 0.75)));
+        assert!(baseline.contains(&("crosslinguistic.hindi.attractors.1".to_string(), 1.0)));
+
+        let mut current = results();
+        current.agreement.by_depth.insert(1, 0.5);
+        current.performance.avg_parse_time_us = 105.0;
+        current.performance.p95_latency_us = 200.0;
+        current.performance.sentences_per_sec = 950.0;
+        let regressions: Vec<String> = deltas(&metrics(&current), &baseline).into_iter()
+            .filter(|delta| delta.regression)
+            .map(|delta| delta.name)
+            .collect();
+        assert_eq!(regressions, ["agreement.depth.1", "performance.p95_latency_us"]);
+        assert!(render_report(&current, Some(&baseline)).contains("2 regression(s)"));
+        assert_eq!(read_baseline("<html></html>"), None);
+
+        // A slope is compared in absolute terms, so a steeper curve is
+        // flagged however steep the baseline was
+        let exponent = |value| Metric { name: "scaling.exponent".to_string(), value, higher_is_better: false, tolerance: SCALING_TOLERANCE };
+        let baseline = [("scaling.exponent".to_string(), 2.0)];
+        assert!(deltas(&[exponent(2.15)], &baseline)[0].regression);
+        assert!(!deltas(&[exponent(2.05)], &baseline)[0].regression);
+    }
+}
@@ -0,0 +1,130 @@
+//! Long-Sentence Scaling
+//!
+//! Parses sentences of increasing length (10–100 tokens) from a coverage
+//! grammar of stacked clausal complements ("the student said that the
+//! teacher said that … the tutor left"), fits the runtime curve to a power
+//! law t = c·nᵏ with `complexity::fit_growth`, and reports the empirical
+//! exponent k as evidence for polynomial-time parsing.
+
+use atomic_lang_model::complexity::{embedding_lexicon, embedding_sentence, fit_growth};
+use atomic_lang_model::*;
+use std::time::Instant;
+
+/// Embedded clauses in the shortest and longest benchmark sentences
+/// (each clause adds four tokens to a three-token base: 11 to 99 tokens)
+pub const MIN_CLAUSES: usize = 2;
+/// See `MIN_CLAUSES`
+pub const MAX_CLAUSES: usize = 24;
+
+/// Timed parses per length; the median is kept
+pub const REPETITIONS: usize = 5;
+
+/// Runtime of one sentence length
+#[derive(Debug, Clone)]
+pub struct ScalingSample {
+    /// Sentence length in tokens
+    pub tokens: usize,
+    /// Median parse time (microseconds)
+    pub time_us: f64,
+    /// Whether the sentence parsed
+    pub parsed: bool,
+}
+
+/// Results of the scaling phase
+#[derive(Debug, Clone)]
+pub struct ScalingResults {
+    /// One sample per sentence length, shortest first
+    pub samples: Vec<ScalingSample>,
+    /// Fitted runtime curve t = c·nᵏ (`None` if too few lengths parsed)
+    pub fit: Option<ComplexityEstimate>,
+}
+
+/// Time parses of every clause count in `clauses` and fit the curve
+pub fn run_scaling_with(clauses: impl IntoIterator<Item = usize>) -> ScalingResults {
+    let lexicon = embedding_lexicon();
+
+    let samples: Vec<ScalingSample> = clauses.into_iter()
+        .map(|k| {
+            let sentence = embedding_sentence(k);
+            let mut times = Vec::with_capacity(REPETITIONS);
+            let mut parsed = true;
+            for _ in 0..REPETITIONS {
+                let start = Instant::now();
+                parsed &= parse_sentence(&sentence, &lexicon).is_ok();
+                times.push(start.elapsed().as_secs_f64() * 1e6);
+            }
+            times.sort_by(|a, b| a.total_cmp(b));
+            ScalingSample {
+                tokens: sentence.split_whitespace().count(),
+                time_us: times[times.len() / 2],
+                parsed,
+            }
+        })
+        .collect();
+
+    let points: Vec<(usize, f64)> = samples.iter()
+        .filter(|sample| sample.parsed)
+        .map(|sample| (sample.tokens, sample.time_us))
+        .collect();
+    let fit = fit_growth(&points);
+
+    ScalingResults { samples, fit }
+}
+
+/// Run the scaling phase over 11 to 99 tokens
+pub fn run_scaling() -> ScalingResults {
+    run_scaling_with((MIN_CLAUSES..=MAX_CLAUSES).step_by(2))
+}
+
+/// Print scaling analysis
+pub fn print_scaling_analysis(results: &ScalingResults) {
+    println!("📈 LONG-SENTENCE SCALING:");
+    for sample in &results.samples {
+        let mark = if sample.parsed { "✅" } else { "❌" };
+        println!("  {} {:>3} tokens - {:.1}μs", mark, sample.tokens, sample.time_us);
+    }
+    let fit = match &results.fit {
+        Some(fit) => fit,
+        None => {
+            println!("❌ Too few sentences parsed to fit a curve");
+            return;
+        }
+    };
+    println!("Fitted curve: t ∝ {}", fit);
+
+    if results.samples.iter().any(|sample| !sample.parsed) {
+        println!("❌ Some sentences failed to parse; the fit covers parsed lengths only");
+    } else if fit.r_squared < 0.9 {
+        println!("⚠️  Noisy measurements; rerun in release mode on an idle machine");
+    } else if fit.is_bounded_by(3.0) {
+        println!("✅ Polynomial-time parsing (exponent ≤ 3)");
+    } else {
+        println!("⚠️  Steep growth (exponent > 3)");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scaling_sentences_parse() {
+        let lexicon = embedding_lexicon();
+        assert_eq!(embedding_sentence(0), "the tutor left");
+        assert_eq!(embedding_sentence(MIN_CLAUSES).split_whitespace().count(), 11);
+        assert_eq!(embedding_sentence(MAX_CLAUSES).split_whitespace().count(), 99);
+
+        for k in [MIN_CLAUSES, 8] {
+            let sentence = embedding_sentence(k);
+            assert_eq!(parse_sentence(&sentence, &lexicon).unwrap().linearize(), sentence);
+        }
+    }
+
+    #[test]
+    fn test_scaling_phase_runs() {
+        let results = run_scaling_with([2, 4, 6]);
+        assert_eq!(results.samples.len(), 3);
+        assert!(results.samples.iter().all(|sample| sample.parsed));
+        assert!(results.fit.is_some_and(|fit| fit.exponent.is_finite()));
+    }
+}
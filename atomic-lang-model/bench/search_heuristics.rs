@@ -0,0 +1,120 @@
+//! Search Heuristics
+//!
+//! Parses the same sentences with best-first search under each built-in
+//! cost function and with no estimate at all, and compares how many
+//! workspaces each expands before converging. Admissible estimates should
+//! expand fewer states and still find derivations of the same length.
+
+use atomic_lang_model::*;
+
+/// Sentences compared, from the default grammar
+pub const SENTENCES: [&str; 4] = [
+    "the student left",
+    "the student thinks the teacher",
+    "the student who left smiled",
+    "the student who left thinks the teacher",
+];
+
+/// Expansions of one cost function on one sentence
+#[derive(Debug, Clone)]
+pub struct HeuristicSample {
+    /// Cost function name
+    pub heuristic: &'static str,
+    /// Sentence parsed
+    pub sentence: &'static str,
+    /// Workspaces expanded
+    pub expansions: usize,
+    /// Length of the derivation found (`None` if the sentence failed)
+    pub steps: Option<usize>,
+}
+
+/// Results of the heuristics phase
+#[derive(Debug, Clone)]
+pub struct HeuristicResults {
+    /// One sample per cost function and sentence
+    pub samples: Vec<HeuristicSample>,
+}
+
+impl HeuristicResults {
+    /// Total expansions of a cost function over all sentences
+    pub fn total(&self, heuristic: &str) -> usize {
+        self.samples.iter()
+            .filter(|sample| sample.heuristic == heuristic)
+            .map(|sample| sample.expansions)
+            .sum()
+    }
+}
+
+/// Compare the cost functions on `sentences`
+pub fn run_heuristics_with(sentences: &[&'static str]) -> HeuristicResults {
+    let options = GrammarOptions::default();
+    let lexicon = options.lexicon();
+    let configs = [
+        ("uninformed", ParserConfig::new(BestFirst::new(Uninformed))),
+        ("unchecked features", ParserConfig::new(BestFirst::new(UncheckedFeatures))),
+        ("uncovered tokens", ParserConfig::new(BestFirst::new(UncoveredTokens))),
+    ];
+
+    let mut samples = Vec::new();
+    for (heuristic, config) in &configs {
+        let heuristic = *heuristic;
+        for &sentence in sentences {
+            let (parse, expansions) = parse_counting_expansions(sentence, &lexicon, &options, config);
+            samples.push(HeuristicSample {
+                heuristic,
+                sentence,
+                expansions,
+                steps: parse.ok().map(|parse| parse.derivation.len()),
+            });
+        }
+    }
+    HeuristicResults { samples }
+}
+
+/// Run the heuristics phase
+pub fn run_heuristics() -> HeuristicResults {
+    run_heuristics_with(&SENTENCES)
+}
+
+/// Print expansion counts
+pub fn print_heuristics_analysis(results: &HeuristicResults) {
+    println!("🧭 SEARCH HEURISTICS (best-first expansions):");
+    for sample in &results.samples {
+        let mark = if sample.steps.is_some() { "✅" } else { "❌" };
+        println!("  {} {:<18} {:>5} - '{}'", mark, sample.heuristic, sample.expansions, sample.sentence);
+    }
+    let uninformed = results.total("uninformed");
+    for heuristic in ["unchecked features", "uncovered tokens"] {
+        let total = results.total(heuristic);
+        println!(
+            "{}: {} expansions vs {} uninformed ({:.1}% fewer)",
+            heuristic,
+            total,
+            uninformed,
+            100.0 * (1.0 - total as f64 / uninformed.max(1) as f64),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristics_expand_fewer_states() {
+        let results = run_heuristics();
+        assert!(results.samples.iter().all(|sample| sample.steps.is_some()));
+        for heuristic in ["unchecked features", "uncovered tokens"] {
+            assert!(results.total(heuristic) < results.total("uninformed"));
+        }
+
+        // Admissible estimates find derivations as short as uninformed search
+        for sentence in SENTENCES {
+            let steps: Vec<_> = results.samples.iter()
+                .filter(|sample| sample.sentence == sentence)
+                .map(|sample| sample.steps)
+                .collect();
+            assert!(steps.windows(2).all(|pair| pair[0] == pair[1]), "{}: {:?}", sentence, steps);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! aⁿbⁿ Stress Benchmark
+//!
+//! Generates and recognizes aⁿbⁿ for n from 10⁵ to 10⁶ on all cores (the
+//! `parallel` feature), fits recognition time to verify it grows linearly,
+//! and checks that streaming verification holds only a fixed-size counter
+//! however long the input.
+
+use atomic_lang_model::complexity::estimate_complexity;
+use atomic_lang_model::stress::*;
+use atomic_lang_model::*;
+use std::time::Instant;
+
+/// Sizes the stress phase runs
+pub const STRESS_SIZES: [usize; 4] = [100_000, 250_000, 500_000, 1_000_000];
+
+/// Timings for one n
+#[derive(Debug, Clone)]
+pub struct StressSample {
+    /// Pattern size
+    pub n: usize,
+    /// Parallel generation time (milliseconds)
+    pub generate_ms: f64,
+    /// Parallel recognition time (milliseconds)
+    pub recognize_ms: f64,
+    /// Streaming verification time, without materializing the string (milliseconds)
+    pub stream_ms: f64,
+    /// Whether every recognizer accepted the generated string
+    pub accepted: bool,
+}
+
+/// Results of the stress phase
+#[derive(Debug, Clone)]
+pub struct StressResults {
+    /// One sample per size
+    pub samples: Vec<StressSample>,
+    /// Fitted growth of parallel recognition time
+    pub recognition: Option<ComplexityEstimate>,
+    /// State held by the streaming verifier, whatever the input length (bytes)
+    pub streaming_state_bytes: usize,
+}
+
+/// Run the stress phase over the given sizes
+pub fn run_stress_with(sizes: &[usize]) -> StressResults {
+    let threads = default_threads();
+    let elapsed_ms = |start: Instant| start.elapsed().as_secs_f64() * 1e3;
+
+    let samples = sizes.iter()
+        .map(|&n| {
+            let start = Instant::now();
+            let generated = generate_an_bn_parallel(n, threads);
+            let generate_ms = elapsed_ms(start);
+
+            let start = Instant::now();
+            let recognized = is_an_bn_parallel(&generated, threads);
+            let recognize_ms = elapsed_ms(start);
+            drop(generated);
+
+            let start = Instant::now();
+            let streamed = verify_an_bn_stream(an_bn_bytes(n));
+            let stream_ms = elapsed_ms(start);
+
+            StressSample { n, generate_ms, recognize_ms, stream_ms, accepted: recognized && streamed }
+        })
+        .collect();
+
+    let recognition = estimate_complexity(
+        |n| {
+            let generated = generate_an_bn_parallel(n, threads);
+            let start = Instant::now();
+            std::hint::black_box(is_an_bn_parallel(&generated, threads));
+            start.elapsed()
+        },
+        sizes,
+    );
+
+    StressResults {
+        samples,
+        recognition,
+        streaming_state_bytes: std::mem::size_of::<AnBn>(),
+    }
+}
+
+/// Run the stress phase for n from 10⁵ to 10⁶
+pub fn run_stress() -> StressResults {
+    run_stress_with(&STRESS_SIZES)
+}
+
+/// Print stress analysis
+pub fn print_stress_analysis(results: &StressResults) {
+    println!("🔥 aⁿbⁿ STRESS TEST ({} threads):", default_threads());
+    for sample in &results.samples {
+        let mark = if sample.accepted { "✅" } else { "❌" };
+        println!(
+            "  {} n={:>9}: generate {:.2}ms, recognize {:.2}ms, stream {:.2}ms",
+            mark, sample.n, sample.generate_ms, sample.recognize_ms, sample.stream_ms
+        );
+    }
+    println!("Streaming verifier state: {} bytes for every n", results.streaming_state_bytes);
+
+    match &results.recognition {
+        Some(fit) if fit.is_bounded_by(1.5) => println!("✅ Linear-time recognition: t ∝ {}", fit),
+        Some(fit) => println!("⚠️  Recognition grows faster than linear: t ∝ {}", fit),
+        None => println!("❌ Too few sizes to fit recognition time"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stress_phase_runs() {
+        let results = run_stress_with(&[1_000, 10_000, 100_000]);
+        assert_eq!(results.samples.len(), 3);
+        assert!(results.samples.iter().all(|sample| sample.accepted));
+        assert!(results.recognition.is_some_and(|fit| fit.exponent.is_finite()));
+        assert_eq!(results.streaming_state_bytes, std::mem::size_of::<AnBn>());
+    }
+}
@@ -0,0 +1,140 @@
+//! Synthesized Test Suite
+//!
+//! Sentences generated from each suite lexicon's constructions (see
+//! `atomic_lang_model::synthesis`), so every head a lexicon adds is tested
+//! without a hand-written case. Grammatical sentences must parse, and
+//! sentences missing an argument or host must be rejected.
+
+use crate::agreement_suite::agreement_lexicon;
+use crate::extraction_suite::{extraction_lexicon, extraction_options};
+use atomic_lang_model::*;
+use std::collections::HashMap;
+
+/// Results of the synthesized suite
+#[derive(Debug, Clone)]
+pub struct SynthesizedResults {
+    /// Total test cases
+    pub total: usize,
+    /// Correct judgments
+    pub correct: usize,
+    /// Accuracy score
+    pub accuracy: f64,
+    /// Constructions found in the lexicon
+    pub constructions: usize,
+    /// Constructions no sentence could be synthesized for
+    pub unsynthesized: Vec<String>,
+    /// Results by construction
+    pub by_construction: HashMap<String, f64>,
+}
+
+/// Run the synthesized suite over the agreement and extraction lexicons
+pub fn run_synthesized_suite() -> SynthesizedResults {
+    let agreement = run_synthesized_suite_with(&agreement_lexicon(), &GrammarOptions::default());
+    let extraction = run_synthesized_suite_with(&extraction_lexicon(), &extraction_options());
+
+    let total = agreement.total + extraction.total;
+    let correct = agreement.correct + extraction.correct;
+    let mut by_construction = agreement.by_construction;
+    by_construction.extend(extraction.by_construction);
+    SynthesizedResults {
+        total,
+        correct,
+        accuracy: correct as f64 / total as f64,
+        constructions: agreement.constructions + extraction.constructions,
+        unsynthesized: [agreement.unsynthesized, extraction.unsynthesized].concat(),
+        by_construction,
+    }
+}
+
+/// Run the synthesized suite for a given lexicon and grammar options
+pub fn run_synthesized_suite_with(lexicon: &[LexItem], options: &GrammarOptions) -> SynthesizedResults {
+    let constructions = synthesize_benchmark(lexicon);
+
+    let mut total = 0;
+    let mut correct = 0;
+    let mut unsynthesized = Vec::new();
+    let mut by_construction = HashMap::new();
+
+    println!("🧪 Running Synthesized Test Suite");
+    println!("{}", "=".repeat(60));
+
+    for construction in &constructions {
+        println!("{}", construction);
+        if construction.cases.is_empty() {
+            println!("   ⚠️  no derivation found");
+            unsynthesized.push(construction.to_string());
+            continue;
+        }
+
+        let mut construction_correct = 0;
+        for case in &construction.cases {
+            let parsed = parse_with_options(&case.sentence, lexicon, options).is_ok();
+            let ok = parsed == case.grammatical();
+            construction_correct += ok as usize;
+
+            let mark = if ok { "✅" } else { "❌" };
+            match &case.omitted {
+                None => println!("{} GRAM: {}", mark, case.sentence),
+                Some(feature) => println!("{} UNGRAM: *{} (without {})", mark, case.sentence, feature),
+            }
+        }
+        total += construction.cases.len();
+        correct += construction_correct;
+        by_construction.insert(construction.to_string(), construction_correct as f64 / construction.cases.len() as f64);
+        println!();
+    }
+
+    SynthesizedResults {
+        total,
+        correct,
+        accuracy: if total == 0 { 0.0 } else { correct as f64 / total as f64 },
+        constructions: constructions.len(),
+        unsynthesized,
+        by_construction,
+    }
+}
+
+/// Print detailed results analysis
+pub fn print_synthesized_analysis(results: &SynthesizedResults) {
+    println!("\n📊 SYNTHESIZED TEST RESULTS");
+    println!("{}", "=".repeat(40));
+    println!("Constructions: {} ({} without sentences)", results.constructions, results.unsynthesized.len());
+    println!("Total test cases: {}", results.total);
+    println!("Correct judgments: {}/{}", results.correct, results.total);
+    println!("Overall accuracy: {:.1}%", results.accuracy * 100.0);
+
+    println!("\n📈 ACCURACY BY CONSTRUCTION:");
+    let mut constructions: Vec<_> = results.by_construction.iter().collect();
+    constructions.sort_by(|a, b| a.0.cmp(b.0));
+    for (construction, accuracy) in constructions {
+        println!("  {}: {:.1}%", construction, accuracy * 100.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suite_tracks_the_lexicon() {
+        let mut lexicon = agreement_lexicon();
+        let before = run_synthesized_suite_with(&lexicon, &GrammarOptions::default());
+
+        // A new ditransitive-like head brings its own construction and cases
+        lexicon.push(LexItem::new("gave", &[Feature::Cat(Category::V), Feature::Sel(Category::P), Feature::Sel(Category::DP), Feature::Spec(Category::D)]));
+        let after = run_synthesized_suite_with(&lexicon, &GrammarOptions::default());
+
+        assert_eq!(after.constructions, before.constructions + 1);
+        assert!(after.total > before.total);
+    }
+
+    #[test]
+    fn test_synthesized_suite_runs() {
+        let results = run_synthesized_suite();
+
+        assert!(results.total > 0);
+        assert_eq!(results.accuracy, 1.0);
+
+        print_synthesized_analysis(&results);
+    }
+}
@@ -0,0 +1,162 @@
+//! Throughput Benchmark
+//!
+//! Sustained parsing load: thousands of sentences generated from the core
+//! lexicon are parsed back to back, and the run reports sentences per second
+//! together with median and tail (p95) latency per sentence.
+//!
+//! The same sentences are then parsed again with `parse_batch`, which
+//! shares one `WorkspacePool` across the batch, to measure the speedup of
+//! reusing workspace buffers over allocating them per sentence.
+
+use atomic_lang_model::*;
+use std::time::Instant;
+
+/// Sentences parsed by the benchmark phase
+pub const THROUGHPUT_SENTENCES: usize = 2000;
+
+/// Results of a throughput run
+#[derive(Debug, Clone)]
+pub struct ThroughputResults {
+    /// Sentences parsed
+    pub sentences: usize,
+    /// Sentences that parsed successfully
+    pub parsed: usize,
+    /// Wall-clock time for the whole run (milliseconds)
+    pub elapsed_ms: f64,
+    /// Sustained throughput
+    pub sentences_per_sec: f64,
+    /// Median latency per sentence (microseconds)
+    pub p50_latency_us: f64,
+    /// 95th-percentile latency per sentence (microseconds)
+    pub p95_latency_us: f64,
+    /// Throughput of `parse_batch` with a shared workspace pool
+    pub pooled_sentences_per_sec: f64,
+    /// Pooled over per-sentence throughput
+    pub pooling_speedup: f64,
+}
+
+/// Generate `count` sentences, cycling through every intransitive
+/// (`D N V`) and transitive (`D N thinks D N`) clause of the test lexicon
+pub fn generate_throughput_sentences(count: usize) -> Vec<String> {
+    let determiners = ["the", "a"];
+    let nouns = ["student", "tutor", "teacher"];
+    let intransitives = ["left", "smiled", "arrived"];
+
+    let mut clauses = Vec::new();
+    for det in determiners {
+        for noun in nouns {
+            for verb in intransitives {
+                clauses.push(format!("{} {} {}", det, noun, verb));
+            }
+            for object_det in determiners {
+                for object in nouns {
+                    clauses.push(format!("{} {} thinks {} {}", det, noun, object_det, object));
+                }
+            }
+        }
+    }
+
+    clauses.iter().cycle().take(count).cloned().collect()
+}
+
+/// Value at percentile `p` (0–100) of sorted samples, by nearest rank
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Parse `count` generated sentences and measure throughput
+pub fn run_throughput(count: usize) -> ThroughputResults {
+    let lexicon = test_lexicon();
+    let sentences = generate_throughput_sentences(count);
+
+    let mut latencies = Vec::with_capacity(sentences.len());
+    let mut parsed = 0;
+
+    let start = Instant::now();
+    for sentence in &sentences {
+        let parse_start = Instant::now();
+        let result = parse_sentence(sentence, &lexicon);
+        latencies.push(parse_start.elapsed().as_secs_f64() * 1e6);
+        parsed += result.is_ok() as usize;
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+
+    latencies.sort_by(|a, b| a.total_cmp(b));
+
+    let mut pool = WorkspacePool::new();
+    let start = Instant::now();
+    let batch = parse_batch(sentences.iter().map(String::as_str), &lexicon, &GrammarOptions::default(), &mut pool);
+    let pooled_elapsed = start.elapsed().as_secs_f64();
+    debug_assert_eq!(batch.iter().filter(|result| result.is_ok()).count(), parsed);
+
+    let rate = |elapsed: f64| if elapsed > 0.0 { sentences.len() as f64 / elapsed } else { 0.0 };
+    let sentences_per_sec = rate(elapsed);
+    let pooled_sentences_per_sec = rate(pooled_elapsed);
+
+    ThroughputResults {
+        sentences: sentences.len(),
+        parsed,
+        elapsed_ms: elapsed * 1e3,
+        sentences_per_sec,
+        p50_latency_us: percentile(&latencies, 50.0),
+        p95_latency_us: percentile(&latencies, 95.0),
+        pooled_sentences_per_sec,
+        pooling_speedup: if sentences_per_sec > 0.0 { pooled_sentences_per_sec / sentences_per_sec } else { 0.0 },
+    }
+}
+
+/// Print throughput analysis
+pub fn print_throughput_analysis(results: &ThroughputResults) {
+    println!("⚡ THROUGHPUT ANALYSIS:");
+    println!("Sentences parsed: {}/{}", results.parsed, results.sentences);
+    println!("Elapsed: {:.1} ms", results.elapsed_ms);
+    println!("Throughput: {:.0} sentences/sec", results.sentences_per_sec);
+    println!("Latency p50: {:.1} μs", results.p50_latency_us);
+    println!("Latency p95: {:.1} μs", results.p95_latency_us);
+    println!(
+        "Pooled batch: {:.0} sentences/sec ({:.2}x)",
+        results.pooled_sentences_per_sec, results.pooling_speedup
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_sentences_parse() {
+        let sentences = generate_throughput_sentences(100);
+        assert_eq!(sentences.len(), 100);
+        assert_eq!(sentences[0], "the student left");
+        assert_eq!(sentences[54], sentences[0], "54 distinct clauses, then the cycle repeats");
+
+        let lexicon = test_lexicon();
+        for sentence in &sentences[..54] {
+            assert!(parse_sentence(sentence, &lexicon).is_ok(), "{}", sentence);
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        let samples: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&samples, 50.0), 10.0);
+        assert_eq!(percentile(&samples, 95.0), 19.0);
+        assert_eq!(percentile(&samples, 100.0), 20.0);
+        assert_eq!(percentile(&samples, 0.0), 1.0);
+        assert_eq!(percentile(&[], 50.0), 0.0);
+    }
+
+    #[test]
+    fn test_throughput_run() {
+        let results = run_throughput(200);
+        assert_eq!(results.sentences, 200);
+        assert_eq!(results.parsed, 200);
+        assert!(results.sentences_per_sec > 0.0);
+        assert!(results.p50_latency_us <= results.p95_latency_us);
+        assert!(results.pooled_sentences_per_sec > 0.0 && results.pooling_speedup > 0.0);
+    }
+}
@@ -0,0 +1,167 @@
+//! Graded Acceptability
+//!
+//! The parser's verdict is binary, but minimal-pair evaluations ask which
+//! member of a pair is better, and both members often parse (or both
+//! fail). `acceptability` grades a sentence between 0 and 1 by combining
+//! three components, each between 0 and 1:
+//!
+//! - grammaticality: 1 if the sentence parses, otherwise how far the
+//!   furthest dead end got (see `explain`), as the share of tokens already
+//!   combined into larger objects
+//! - economy: the fewest operations any derivation could take, one per
+//!   token joined, over the operations of the most economical analysis
+//! - probability: the best inside score of an analysis under a model
+//!
+//! Economy and probability are zero for a sentence that does not parse, so
+//! with the grammaticality weight at least as large as the other two
+//! combined, every sentence that parses outscores every one that does not.
+
+use crate::explain::Reason;
+use crate::{explain, parse_chart, GrammarOptions, InsideScore, LexItem, SyntacticObject};
+
+/// Components of a graded judgment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Judgment {
+    /// Whether the sentence parses
+    pub parsed: bool,
+    /// 1 for a parse, else the share of tokens combined at the furthest dead end
+    pub grammaticality: f64,
+    /// Minimal operation count over the most economical analysis's
+    pub economy: f64,
+    /// Best inside score of an analysis
+    pub probability: f64,
+    /// Weighted sum of the components
+    pub score: f64,
+}
+
+/// Graded acceptability scorer
+#[derive(Clone, Copy)]
+pub struct Acceptability<'a> {
+    /// Probability of an analysis's tree
+    pub model: &'a dyn InsideScore,
+    /// Weight of grammaticality
+    pub grammaticality: f64,
+    /// Weight of derivation economy
+    pub economy: f64,
+    /// Weight of the model's probability
+    pub probability: f64,
+}
+
+/// Every tree equally probable
+fn uniform(_: &SyntacticObject) -> f64 {
+    1.0
+}
+
+impl Default for Acceptability<'_> {
+    fn default() -> Self {
+        Self::new(&uniform)
+    }
+}
+
+impl<'a> Acceptability<'a> {
+    /// Scorer with `model` and the default weights (0.6, 0.2, 0.2)
+    pub fn new(model: &'a dyn InsideScore) -> Self {
+        Self { model, grammaticality: 0.6, economy: 0.2, probability: 0.2 }
+    }
+
+    /// Grade `sentence`, between 0 and 1 when the weights sum to 1
+    pub fn score(&self, sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> f64 {
+        self.judge(sentence, lexicon, options).score
+    }
+
+    /// Grade `sentence`, keeping the components
+    ///
+    /// The analyses come from the chart parser, which builds each shared
+    /// constituent once; a rejected sentence is then explained to find how
+    /// far its derivation got.
+    pub fn judge(&self, sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Judgment {
+        let tokens = options.normalizer.apply(sentence, lexicon).split_whitespace().count();
+        let (parsed, grammaticality, economy, probability) = match parse_chart(sentence, lexicon, options) {
+            Ok(trees) => {
+                let economy = match trees.iter().map(operations).min() {
+                    None | Some(0) => 1.0,
+                    Some(operations) => (tokens.saturating_sub(1) as f64 / operations as f64).min(1.0),
+                };
+                let probability = trees.iter().map(|tree| self.model.inside(tree)).fold(0.0, f64::max);
+                (true, 1.0, economy, probability)
+            }
+            Err(_) => {
+                let objects = match explain(sentence, lexicon, options) {
+                    // Converged, but filtered out
+                    Some(explanation) if matches!(explanation.reasons.as_slice(), [Reason::Constraint(_)] | [Reason::WordOrder { .. }]) => 1,
+                    Some(explanation) if !explanation.stuck.is_empty() => explanation.stuck.len(),
+                    _ => tokens,
+                };
+                (false, tokens.saturating_sub(objects) as f64 / tokens.max(1) as f64, 0.0, 0.0)
+            }
+        };
+        let score = self.grammaticality * grammaticality + self.economy * economy + self.probability * probability;
+        Judgment { parsed, grammaticality, economy, probability, score }
+    }
+}
+
+/// Operations that derived a tree: every Merge, Adjoin and Move builds one
+/// of its phrases
+fn operations(tree: &SyntacticObject) -> usize {
+    match tree.children.as_slice() {
+        [] => 0,
+        children => 1 + children.iter().map(operations).sum::<usize>(),
+    }
+}
+
+/// Grade `sentence` between 0 and 1 with the default scorer
+pub fn acceptability(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> f64 {
+    Acceptability::default().score(sentence, lexicon, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_forest, test_lexicon, Category, FewestOperations, RuleWeights};
+
+    #[test]
+    fn test_parsed_sentences_outscore_rejected_ones() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+
+        let good = Acceptability::default().judge("the student left", &lexicon, &options);
+        assert!(good.parsed);
+        assert_eq!(good.economy, 1.0);
+        assert_eq!(good.score, 1.0);
+
+        // A dead end two objects short of convergence gets partial credit
+        let partial = Acceptability::default().judge("the student the teacher", &lexicon, &options);
+        assert!(!partial.parsed);
+        assert_eq!(partial.grammaticality, 0.5);
+        assert_eq!(partial.score, 0.3);
+
+        assert_eq!(acceptability("the student zorp", &lexicon, &options), 0.0);
+        assert!(acceptability("the student left", &lexicon, &options) > acceptability("the student the teacher", &lexicon, &options));
+    }
+
+    #[test]
+    fn test_economy_counts_the_fewest_operations() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        for sentence in ["the student left", "the student who left smiled", "the student thinks the teacher"] {
+            let trees = parse_chart(sentence, &lexicon, &options).unwrap();
+            let fewest = parse_forest(sentence, &lexicon, &options, &FewestOperations).unwrap()[0].derivation.len();
+            assert_eq!(trees.iter().map(operations).min(), Some(fewest), "{}", sentence);
+        }
+    }
+
+    #[test]
+    fn test_model_grades_parsed_sentences() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+
+        // Disfavour verbs with a DP complement
+        let weights = RuleWeights::new(1.0).rule(Category::VP, Category::V, Category::DP, 0.5);
+        let scorer = Acceptability::new(&weights);
+        let simple = scorer.judge("the student left", &lexicon, &options);
+        let embedded = scorer.judge("the student thinks the teacher", &lexicon, &options);
+        assert!(simple.parsed && embedded.parsed);
+        assert!(embedded.probability < simple.probability);
+        assert!(embedded.score < simple.score);
+    }
+}
@@ -0,0 +1,261 @@
+//! Agreement Features
+//!
+//! `Feature::Agr` carries person, number and gender, each optional. Where it
+//! sits in a bundle decides its role:
+//!
+//! - directly after a selector it is a probe: whatever that selector
+//!   selects must agree with it, and it is checked and discharged together
+//!   with the selector (`is :: V =P D= [sg]` wants a singular subject)
+//! - anywhere else it describes the item itself, and like other unchecked
+//!   features it passes up through every projection containing the item
+//!   (`student :: N [3.sg]`)
+//!
+//! A phrase's own agreement is the first descriptive feature in its bundle,
+//! which is its head's: agreement passed up from adjuncts and arguments goes
+//! after the head's own but ahead of any selector the head has left, so in
+//! "the student near the teachers" the noun's singular comes before the
+//! attractor's plural, and an object's plural never becomes the probe of
+//! the subject selector after it. Two bundles agree
+//! when no attribute both specify has different values; an unspecified
+//! attribute agrees with anything. Agreement features are interpretable, so
+//! they never keep a derivation from converging.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::phi::{Phi, PhiNode};
+use crate::Feature;
+
+/// Grammatical person
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Person {
+    /// Speaker
+    First,
+    /// Addressee
+    Second,
+    /// Neither
+    Third,
+}
+
+/// Grammatical number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Number {
+    /// Singular
+    Singular,
+    /// Plural
+    Plural,
+}
+
+/// Grammatical gender
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Gender {
+    /// Feminine
+    Feminine,
+    /// Masculine
+    Masculine,
+    /// Neuter
+    Neuter,
+}
+
+/// Person, number and gender, each possibly unspecified
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AgrBundle {
+    /// Person, if specified
+    pub person: Option<Person>,
+    /// Number, if specified
+    pub number: Option<Number>,
+    /// Gender, if specified
+    pub gender: Option<Gender>,
+}
+
+impl AgrBundle {
+    /// Bundle specifying number only
+    pub const fn number(number: Number) -> Self {
+        Self { person: None, number: Some(number), gender: None }
+    }
+
+    /// Third person bundle of the given number
+    pub const fn third(number: Number) -> Self {
+        Self { person: Some(Person::Third), number: Some(number), gender: None }
+    }
+
+    /// Check whether no attribute specified by both has different values
+    pub fn agrees(&self, other: &AgrBundle) -> bool {
+        fn compatible<T: PartialEq>(a: Option<T>, b: Option<T>) -> bool {
+            a.zip(b).is_none_or(|(a, b)| a == b)
+        }
+        compatible(self.person, other.person) && compatible(self.number, other.number) && compatible(self.gender, other.gender)
+    }
+
+    /// Six bits: person, number and gender, two bits each (0 unspecified)
+    pub const fn bits(&self) -> u8 {
+        let person = match self.person {
+            None => 0,
+            Some(Person::First) => 1,
+            Some(Person::Second) => 2,
+            Some(Person::Third) => 3,
+        };
+        let number = match self.number {
+            None => 0,
+            Some(Number::Singular) => 1,
+            Some(Number::Plural) => 2,
+        };
+        let gender = match self.gender {
+            None => 0,
+            Some(Gender::Feminine) => 1,
+            Some(Gender::Masculine) => 2,
+            Some(Gender::Neuter) => 3,
+        };
+        person << 4 | number << 2 | gender
+    }
+
+    /// Bundle from `bits`, if they encode one
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        let person = match bits >> 4 {
+            0 => None,
+            1 => Some(Person::First),
+            2 => Some(Person::Second),
+            3 => Some(Person::Third),
+            _ => return None,
+        };
+        let number = match bits >> 2 & 3 {
+            0 => None,
+            1 => Some(Number::Singular),
+            2 => Some(Number::Plural),
+            _ => return None,
+        };
+        let gender = match bits & 3 {
+            0 => None,
+            1 => Some(Gender::Feminine),
+            2 => Some(Gender::Masculine),
+            _ => Some(Gender::Neuter),
+        };
+        Some(Self { person, number, gender })
+    }
+
+    /// Read the notation inside `[...]`: dot-separated `1`, `2` or `3`,
+    /// `sg` or `pl`, and `f`, `m` or `n`, in any order
+    pub fn parse(notation: &str) -> Option<Self> {
+        let mut bundle = AgrBundle::default();
+        for part in notation.split('.').filter(|part| !part.is_empty()) {
+            match part {
+                "1" => bundle.person = Some(Person::First),
+                "2" => bundle.person = Some(Person::Second),
+                "3" => bundle.person = Some(Person::Third),
+                "sg" => bundle.number = Some(Number::Singular),
+                "pl" => bundle.number = Some(Number::Plural),
+                "f" => bundle.gender = Some(Gender::Feminine),
+                "m" => bundle.gender = Some(Gender::Masculine),
+                "n" => bundle.gender = Some(Gender::Neuter),
+                _ => return None,
+            }
+        }
+        Some(bundle)
+    }
+}
+
+impl fmt::Display for AgrBundle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let person = self.person.map(|person| match person {
+            Person::First => "1",
+            Person::Second => "2",
+            Person::Third => "3",
+        });
+        let number = self.number.map(|number| match number {
+            Number::Singular => "sg",
+            Number::Plural => "pl",
+        });
+        let gender = self.gender.map(|gender| match gender {
+            Gender::Feminine => "f",
+            Gender::Masculine => "m",
+            Gender::Neuter => "n",
+        });
+        let parts: Vec<&str> = [person, number, gender].into_iter().flatten().collect();
+        write!(f, "[{}]", parts.join("."))
+    }
+}
+
+impl From<AgrBundle> for Phi {
+    /// The bundle's nodes in the feature geometry; third person is the
+    /// absence of Participant
+    fn from(bundle: AgrBundle) -> Phi {
+        let mut nodes = Vec::new();
+        match bundle.person {
+            Some(Person::First) => nodes.push(PhiNode::Speaker),
+            Some(Person::Second) => nodes.push(PhiNode::Addressee),
+            _ => {}
+        }
+        match bundle.number {
+            Some(Number::Singular) => nodes.push(PhiNode::Minimal),
+            Some(Number::Plural) => nodes.push(PhiNode::Group),
+            None => {}
+        }
+        match bundle.gender {
+            Some(Gender::Feminine) => nodes.push(PhiNode::Feminine),
+            Some(Gender::Masculine) => nodes.push(PhiNode::Masculine),
+            Some(Gender::Neuter) => nodes.push(PhiNode::Neuter),
+            None => {}
+        }
+        Phi::new(&nodes)
+    }
+}
+
+/// Probe of a bundle's first selector: the agreement feature directly after it
+pub fn probe(features: &[Feature]) -> Option<&AgrBundle> {
+    let selector = features.iter().position(Feature::is_selector)?;
+    match features.get(selector + 1) {
+        Some(Feature::Agr(bundle)) => Some(bundle),
+        _ => None,
+    }
+}
+
+/// A bundle's own agreement: its first agreement feature that is not a probe
+pub fn agreement(features: &[Feature]) -> Option<&AgrBundle> {
+    features.iter().enumerate().find_map(|(i, feature)| match feature {
+        Feature::Agr(bundle) if i == 0 || !features[i - 1].is_selector() => Some(bundle),
+        _ => None,
+    })
+}
+
+/// Check whether a selectee with features `selectee` satisfies the probe
+/// of the first selector in `selector`
+pub fn satisfies_probe(selector: &[Feature], selectee: &[Feature]) -> bool {
+    probe(selector).zip(agreement(selectee)).is_none_or(|(probe, goal)| probe.agrees(goal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Category;
+
+    #[test]
+    fn test_agreement_and_notation() {
+        let singular = AgrBundle::third(Number::Singular);
+        let plural = AgrBundle::number(Number::Plural);
+        assert!(singular.agrees(&AgrBundle::number(Number::Singular)));
+        assert!(!singular.agrees(&plural));
+        assert!(AgrBundle::default().agrees(&plural));
+
+        let feminine = AgrBundle { gender: Some(Gender::Feminine), ..singular };
+        assert_eq!(feminine.to_string(), "[3.sg.f]");
+        assert_eq!(AgrBundle::parse("f.3.sg"), Some(feminine));
+        assert_eq!(AgrBundle::parse("dual"), None);
+        assert_eq!(AgrBundle::from_bits(feminine.bits()), Some(feminine));
+        assert_eq!(Phi::from(feminine), Phi::THIRD_SINGULAR.with(PhiNode::Feminine));
+    }
+
+    #[test]
+    fn test_probes_and_goals() {
+        let is = [Feature::Cat(Category::V), Feature::Sel(Category::P), Feature::Spec(Category::D), Feature::Agr(AgrBundle::number(Number::Singular))];
+        assert_eq!(probe(&is), None);
+        assert_eq!(probe(&is[2..]), Some(&AgrBundle::number(Number::Singular)));
+        assert_eq!(agreement(&is), None);
+
+        let dp = [Feature::Cat(Category::D), Feature::Agr(AgrBundle::third(Number::Singular)), Feature::Agr(AgrBundle::third(Number::Plural))];
+        assert_eq!(agreement(&dp), Some(&AgrBundle::third(Number::Singular)));
+        assert!(satisfies_probe(&is[2..], &dp));
+        assert!(!satisfies_probe(&[Feature::Spec(Category::D), Feature::Agr(AgrBundle::number(Number::Plural))], &dp));
+    }
+}
@@ -0,0 +1,188 @@
+//! Derivation Animation
+//!
+//! Replays a derivation one operation at a time and exports each state as a
+//! JSON frame (workspace contents, operation applied, resulting object), so
+//! a web front end can animate how a sentence is built.
+//!
+//! ```json
+//! {
+//!   "sentence": "the student left",
+//!   "empty_heads": [...],
+//!   "frames": [
+//!     { "step": 0, "operation": null, "result": null, "workspace": [...] },
+//!     { "step": 1, "operation": { "type": "merge", "selector": 0, "selectee": 1 }, ... }
+//!   ]
+//! }
+//! ```
+//!
+//! Trees are objects with `label`, `phon` (`null` for traces and internal
+//! nodes), `features`, and `children`. The `head` of a `merge_empty`
+//! operation indexes `empty_heads`; every other index refers to the previous
+//! frame's workspace.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+
+use crate::lexicon::json_string;
+use crate::{
+    apply_operation, parse_derivation, replay_workspace, DerivationError, GrammarOptions, LexItem,
+    Operation, SyntacticObject,
+};
+
+/// Workspace state after one derivation step
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// Number of operations applied so far
+    pub step: usize,
+    /// Operation that produced this state (`None` for the initial workspace)
+    pub operation: Option<Operation>,
+    /// Object built by the operation
+    pub result: Option<SyntacticObject>,
+    /// Workspace contents in surface order
+    pub workspace: Vec<SyntacticObject>,
+}
+
+/// Frame-by-frame record of a derivation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// Sentence being derived
+    pub sentence: String,
+    /// Silent heads available to `MergeEmpty`
+    pub empty_heads: Vec<SyntacticObject>,
+    /// States from the initial workspace to the finished tree
+    pub frames: Vec<Frame>,
+}
+
+impl Animation {
+    /// Replay a derivation from the sentence's initial workspace
+    pub fn replay(sentence: &str, lexicon: &[LexItem], derivation: &[Operation]) -> Result<Self, DerivationError> {
+        let tokens: Vec<&str> = sentence.split_whitespace().collect();
+        let mut workspace = replay_workspace(&tokens, lexicon, derivation)?;
+
+        let mut frames = vec![Frame {
+            step: 0,
+            operation: None,
+            result: None,
+            workspace: workspace.items().to_vec(),
+        }];
+        for (step, &op) in derivation.iter().enumerate() {
+            apply_operation(&mut workspace, op)?;
+            frames.push(Frame {
+                step: step + 1,
+                operation: Some(op),
+                result: Some(workspace.items()[op.result_index()].clone()),
+                workspace: workspace.items().to_vec(),
+            });
+        }
+
+        Ok(Self {
+            sentence: sentence.to_string(),
+            empty_heads: workspace.empty_heads,
+            frames,
+        })
+    }
+
+    /// Export frames as JSON, one frame per line
+    pub fn to_json(&self) -> String {
+        let frames: Vec<String> = self.frames.iter()
+            .map(|frame| {
+                let workspace: Vec<String> = frame.workspace.iter().map(tree_json).collect();
+                format!(
+                    "    {{ \"step\": {}, \"operation\": {}, \"result\": {}, \"workspace\": [{}] }}",
+                    frame.step,
+                    frame.operation.as_ref().map_or("null".to_string(), operation_json),
+                    frame.result.as_ref().map_or("null".to_string(), tree_json),
+                    workspace.join(", ")
+                )
+            })
+            .collect();
+
+        let empty_heads: Vec<String> = self.empty_heads.iter().map(tree_json).collect();
+
+        format!(
+            "{{\n  \"sentence\": {},\n  \"empty_heads\": [{}],\n  \"frames\": [\n{}\n  ]\n}}\n",
+            json_string(&self.sentence),
+            empty_heads.join(", "),
+            frames.join(",\n")
+        )
+    }
+}
+
+/// Parse a sentence and record its derivation for animation
+pub fn animate(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Animation, DerivationError> {
+    let parse = parse_derivation(sentence, lexicon, options)?;
+    Animation::replay(sentence, lexicon, &parse.derivation)
+}
+
+/// Encode an operation as a JSON object
+fn operation_json(op: &Operation) -> String {
+    match *op {
+        Operation::Merge(i, j) => format!("{{ \"type\": \"merge\", \"selector\": {}, \"selectee\": {} }}", i, j),
+        Operation::Adjoin(i, j) => format!("{{ \"type\": \"adjoin\", \"host\": {}, \"adjunct\": {} }}", i, j),
+        Operation::MergeEmpty(h, i) => format!("{{ \"type\": \"merge_empty\", \"head\": {}, \"selectee\": {} }}", h, i),
+        Operation::Move(i) => format!("{{ \"type\": \"move\", \"target\": {} }}", i),
+    }
+}
+
+/// Encode a syntactic object as a JSON tree
+fn tree_json(obj: &SyntacticObject) -> String {
+    let features: Vec<String> = obj.features.iter().map(|f| json_string(&f.to_string())).collect();
+    let children: Vec<String> = obj.children.iter().map(tree_json).collect();
+    format!(
+        "{{ \"label\": {}, \"phon\": {}, \"features\": [{}], \"children\": [{}] }}",
+        json_string(&obj.label.to_string()),
+        obj.phon.as_deref().map_or("null".to_string(), json_string),
+        features.join(", "),
+        children.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon};
+
+    #[test]
+    fn test_frames_follow_derivation() {
+        let lexicon = test_lexicon();
+        let animation = animate("the student left", &lexicon, &GrammarOptions::default()).unwrap();
+        let frames = &animation.frames;
+
+        // Initial workspace holds one leaf per token
+        assert_eq!(frames[0].operation, None);
+        assert_eq!(frames[0].workspace.len(), 3);
+
+        // Each step applies one operation and shrinks or restructures the workspace
+        for pair in frames.windows(2) {
+            assert_eq!(pair[1].step, pair[0].step + 1);
+            assert!(pair[1].workspace.len() <= pair[0].workspace.len());
+        }
+
+        let last = frames.last().unwrap();
+        assert_eq!(last.workspace, vec![parse_sentence("the student left", &lexicon).unwrap()]);
+        assert_eq!(last.result.as_ref(), Some(&last.workspace[0]));
+    }
+
+    #[test]
+    fn test_json_export() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let json = animate("the student who left", &lexicon, &GrammarOptions::default()).unwrap().to_json();
+
+        assert!(json.starts_with("{\n  \"sentence\": \"the student who left\""));
+        assert!(json.contains("\"operation\": null"));
+        assert!(json.contains("\"empty_heads\": [{ \"label\": \"C\", \"phon\": \"\""));
+        assert!(json.contains("{ \"type\": \"move\", \"target\": "));
+        assert!(json.contains("{ \"label\": \"D\", \"phon\": null, \"features\": [], \"children\": [] }"));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+    }
+
+    #[test]
+    fn test_replay_rejects_invalid_derivation() {
+        let lexicon = test_lexicon();
+        assert!(Animation::replay("the student left", &lexicon, &[Operation::Move(0)]).is_err());
+        assert_eq!(
+            Animation::replay("the robot left", &lexicon, &[]),
+            Err(DerivationError::InvalidOperation)
+        );
+    }
+}
@@ -0,0 +1,815 @@
+//! Proof Assistant Export
+//!
+//! Renders a checked `Proof` as a Lean 4 or Coq source file, so a derivation
+//! can be certified by a proof assistant instead of by this crate. The file
+//! opens with a prelude encoding the rules as the engine implements them
+//! (Stabler's Merge and Move over feature lists, plus Adjoin, agreement
+//! probes and the Shortest Move Constraint), then lists the lexical items used and the
+//! steps, and closes with a theorem stating that the steps check and derive
+//! the sentence as a complete projection. The theorem is proved by
+//! evaluation (`decide` in Lean, `vm_compute` in Coq).
+//!
+//! The prelude works on judgments (category, unchecked features, yield)
+//! rather than trees, so each Move step carries the mover's judgment and
+//! the number of words preceding it as witnesses. The assistant checks the
+//! feature bookkeeping and word order these imply; that the mover is a
+//! constituent of its premise is established by `Proof::check`, which the
+//! export runs first. The prelude is self-contained, so the files check
+//! against a stock Lean 4 or Coq install with no libraries.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::proof::{Proof, ProofError, Rule};
+use crate::{adjoin, find_mover_path, merge, move_operation, Category, DerivationError, Feature, LexItem, SyntacticObject};
+
+/// Proof assistant to export to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Assistant {
+    /// Coq (`.v`)
+    Coq,
+    /// Lean 4 (`.lean`)
+    Lean,
+}
+
+impl Assistant {
+    /// File extension for exported proofs
+    pub fn extension(self) -> &'static str {
+        match self {
+            Assistant::Coq => "v",
+            Assistant::Lean => "lean",
+        }
+    }
+
+    fn prelude(self) -> &'static str {
+        match self {
+            Assistant::Coq => COQ_PRELUDE,
+            Assistant::Lean => LEAN_PRELUDE,
+        }
+    }
+
+    fn category(self, cat: &Category) -> String {
+        match self {
+            Assistant::Coq => format!("c{:?}", cat),
+            Assistant::Lean => format!(".{:?}", cat),
+        }
+    }
+
+    fn feature(self, feature: &Feature) -> String {
+        let (constructor, argument) = match feature {
+            Feature::Cat(cat) => ("cat", self.category(cat)),
+            Feature::Sel(cat) => ("sel", self.category(cat)),
+            Feature::Spec(cat) => ("spec", self.category(cat)),
+            Feature::Adjoin(cat) => ("adj", self.category(cat)),
+            Feature::Pos(idx) => ("pos", idx.to_string()),
+            Feature::Neg(idx) => ("neg", idx.to_string()),
+            Feature::Agr(bundle) => ("agr", bundle.bits().to_string()),
+        };
+        match self {
+            Assistant::Coq => format!("F{}{} {}", &constructor[..1].to_uppercase(), &constructor[1..], argument),
+            Assistant::Lean => format!(".{} {}", constructor, argument),
+        }
+    }
+
+    fn string(self, s: &str) -> String {
+        match self {
+            Assistant::Coq => format!("\"{}\"", s.replace('"', "\"\"")),
+            Assistant::Lean => format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
+        }
+    }
+
+    fn list(self, items: impl IntoIterator<Item = String>) -> String {
+        let separator = match self {
+            Assistant::Coq => "; ",
+            Assistant::Lean => ", ",
+        };
+        format!("[{}]", items.into_iter().collect::<Vec<_>>().join(separator))
+    }
+
+    fn features(self, features: &[Feature]) -> String {
+        self.list(features.iter().map(|f| self.feature(f)))
+    }
+
+    fn judgment(self, obj: &SyntacticObject) -> String {
+        let label = self.category(&obj.label);
+        let features = self.features(&obj.features);
+        let words = self.list(obj.linearize().split_whitespace().map(|w| self.string(w)));
+        match self {
+            Assistant::Coq => format!("{{| label := {}; features := {}; yield := {} |}}", label, features, words),
+            Assistant::Lean => format!("{{ label := {}, features := {}, yield := {} }}", label, features, words),
+        }
+    }
+
+    fn entry(self, item: &LexItem) -> String {
+        format!("({}, {})", self.string(&item.phon), self.features(&item.feats))
+    }
+
+    fn step(self, rule: Rule, premises: &[usize], witness: Option<(&SyntacticObject, usize)>, item: Option<&LexItem>) -> String {
+        let name = match (self, rule) {
+            (Assistant::Coq, Rule::Lexical) => "Lexical",
+            (Assistant::Coq, Rule::Merge) => "Merge",
+            (Assistant::Coq, Rule::Adjoin) => "Adjoin",
+            (Assistant::Coq, Rule::Move) => "Move",
+            (Assistant::Lean, Rule::Lexical) => ".lexical",
+            (Assistant::Lean, Rule::Merge) => ".merge",
+            (Assistant::Lean, Rule::Adjoin) => ".adjoin",
+            (Assistant::Lean, Rule::Move) => ".move",
+        };
+        let mut arguments: Vec<String> = premises.iter().map(usize::to_string).collect();
+        if let Some(item) = item {
+            arguments.push(self.string(&item.phon));
+            arguments.push(self.features(&item.feats));
+        }
+        if let Some((mover, before)) = witness {
+            arguments.push(self.judgment(mover));
+            arguments.push(before.to_string());
+        }
+        format!("{} {}", name, arguments.join(" "))
+    }
+
+    fn comment(self, text: &str) -> String {
+        match self {
+            Assistant::Coq => format!("(* {} *)", text),
+            Assistant::Lean => format!("-- {}", text),
+        }
+    }
+}
+
+impl fmt::Display for Assistant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Assistant::Coq => "coq",
+            Assistant::Lean => "lean",
+        })
+    }
+}
+
+impl Proof {
+    /// Check the proof against `lexicon` and render it for `assistant`
+    pub fn export(&self, lexicon: &[LexItem], assistant: Assistant) -> Result<String, ProofError> {
+        let tree = self.check(lexicon)?;
+
+        // Replay once more, keeping every conclusion to read Move witnesses off
+        let mut objects: Vec<SyntacticObject> = Vec::with_capacity(self.steps.len());
+        let mut entries: Vec<&LexItem> = Vec::new();
+        let mut steps = Vec::with_capacity(self.steps.len());
+        for (index, step) in self.steps.iter().enumerate() {
+            let premise = |k: usize| objects[step.premises[k]].clone();
+            let mut witness = None;
+            let result = match step.rule {
+                Rule::Lexical => {
+                    let item = step.item.as_ref().expect("lexical steps checked above");
+                    if !entries.contains(&item) {
+                        entries.push(item);
+                    }
+                    Ok(SyntacticObject::from_lex(item))
+                }
+                Rule::Merge => merge(premise(0), premise(1)),
+                Rule::Adjoin => adjoin(premise(0), premise(1)),
+                Rule::Move => {
+                    let target = premise(0);
+                    witness = Some(mover_witness(&target).ok_or(ProofError::Rule(index, DerivationError::NoValidOperations))?);
+                    move_operation(target)
+                }
+            };
+            let obj = result.map_err(|e| ProofError::Rule(index, e))?;
+            let witness = witness.as_ref().map(|(mover, before)| (mover, *before));
+            // Separators go before the comment, which runs to the end of the line in Lean
+            let separator = match (assistant, index + 1 == self.steps.len()) {
+                (_, true) => "",
+                (Assistant::Coq, false) => ";",
+                (Assistant::Lean, false) => ",",
+            };
+            steps.push(format!(
+                "  {}{} {}",
+                assistant.step(step.rule, &step.premises, witness, step.item.as_ref()),
+                separator,
+                assistant.comment(&format!("{}: {}", index, obj.linearize())),
+            ));
+            objects.push(obj);
+        }
+
+        let lexicon = assistant.list(entries.iter().map(|item| assistant.entry(item)));
+        let steps = format!("[\n{}\n]", steps.join("\n"));
+        let conclusion = assistant.judgment(&tree);
+        let header = assistant.comment(&format!("Derivation of {} exported by atomic-lang-model", assistant.string(&self.sentence)));
+
+        Ok(match assistant {
+            Assistant::Coq => format!(
+                "{}\n\n{}\nDefinition lexicon : list (string * list feature) :=\n  {}.\n\n\
+                 Definition steps : list step := {}.\n\n\
+                 Theorem derivation_checks :\n  check lexicon steps = Some {}.\nProof. vm_compute. reflexivity. Qed.\n",
+                header, assistant.prelude(), lexicon, steps, conclusion
+            ),
+            Assistant::Lean => format!(
+                "{}\n\n{}\nopen AtomicLM\n\ndef lexicon : List (String × List Feature) :=\n  {}\n\n\
+                 def steps : List Step := {}\n\n\
+                 theorem derivation_checks :\n    check lexicon steps = some {} := by decide\n",
+                header, assistant.prelude(), lexicon, steps, conclusion
+            ),
+        })
+    }
+}
+
+/// The constituent Move would attract in `obj`, and the number of words before it
+fn mover_witness(obj: &SyntacticObject) -> Option<(SyntacticObject, usize)> {
+    let idx = obj.features.iter().find(|f| f.is_positive()).and_then(Feature::movement_index)?;
+    let path = find_mover_path(obj, idx)?;
+
+    let mut before = 0;
+    let mut node = obj;
+    for &i in &path {
+        before += node.children[..i].iter().map(|child| child.linearize().split_whitespace().count()).sum::<usize>();
+        node = &node.children[i];
+    }
+    Some((node.clone(), before))
+}
+
+const LEAN_PRELUDE: &str = r##"namespace AtomicLM
+
+inductive Cat where
+  | N | V | D | C | P | S | NP | VP | DP | CP | PP
+  deriving DecidableEq, Repr
+
+def Cat.head : Cat → Cat
+  | .NP => .N
+  | .VP => .V
+  | .DP => .D
+  | .CP => .C
+  | .PP => .P
+  | c => c
+
+def Cat.phrase : Cat → Cat
+  | .N => .NP
+  | .V => .VP
+  | .D => .DP
+  | .C => .CP
+  | .P => .PP
+  | c => c
+
+inductive Feature where
+  | cat (c : Cat)
+  | sel (c : Cat)
+  | spec (c : Cat)
+  | adj (c : Cat)
+  | pos (i : Nat)
+  | neg (i : Nat)
+  | agr (bits : Nat)
+  deriving DecidableEq, Repr
+
+def Feature.isSelector : Feature → Bool
+  | .sel _ => true
+  | .spec _ => true
+  | _ => false
+
+def Feature.isCat : Feature → Bool
+  | .cat _ => true
+  | _ => false
+
+def Feature.isNeg : Feature → Bool
+  | .neg _ => true
+  | _ => false
+
+/-- Features a complete projection may keep -/
+def Feature.isInterpretable : Feature → Bool
+  | .cat _ => true
+  | .agr _ => true
+  | _ => false
+
+/-- Features left unchecked on a selectee or adjunct -/
+def Feature.survives : Feature → Bool
+  | .cat _ => false
+  | .adj _ => false
+  | _ => true
+
+def Feature.isAgr : Feature → Bool
+  | .agr _ => true
+  | _ => false
+
+/-- Features heading a bundle: its category and its own agreement -/
+def Feature.describes : Feature → Bool
+  | .cat _ => true
+  | .agr _ => true
+  | _ => false
+
+/-- Add the surviving features of a selectee or adjunct: agreement right
+    after the phrase's category and agreement, so it is never read as a
+    remaining selector's probe, and the rest at the end -/
+def passFeatures (fs src : List Feature) : List Feature :=
+  let kept := src.filter Feature.survives
+  fs.takeWhile Feature.describes ++ kept.filter Feature.isAgr ++
+    fs.dropWhile Feature.describes ++ kept.filter (fun f => !f.isAgr)
+
+structure Judgment where
+  label : Cat
+  features : List Feature
+  yield : List String
+  deriving DecidableEq, Repr
+
+def category : List Feature → Option Cat
+  | [] => none
+  | .cat c :: _ => some c
+  | _ :: fs => category fs
+
+def firstSelector : List Feature → Option Feature
+  | [] => none
+  | f :: fs => if f.isSelector then some f else firstSelector fs
+
+def firstPos : List Feature → Option Nat
+  | [] => none
+  | .pos i :: _ => some i
+  | _ :: fs => firstPos fs
+
+def removeFirst (g : Feature) : List Feature → List Feature
+  | [] => []
+  | f :: fs => if f = g then fs else f :: removeFirst g fs
+
+/-- Drop the first selector and the agreement probe right after it -/
+def removeSelector : List Feature → List Feature
+  | [] => []
+  | f :: fs =>
+    if f.isSelector then
+      (match fs with
+       | .agr _ :: rest => rest
+       | _ => fs)
+    else f :: removeSelector fs
+
+/-- Agreement probe right after the first selector -/
+def probe : List Feature → Option Nat
+  | [] => none
+  | f :: fs =>
+    if f.isSelector then
+      (match fs with
+       | .agr p :: _ => some p
+       | _ => none)
+    else probe fs
+
+/-- First agreement feature not right after a selector (`afterSelector`) -/
+def goalAfter : Bool → List Feature → Option Nat
+  | _, [] => none
+  | false, .agr g :: _ => some g
+  | _, f :: fs => goalAfter f.isSelector fs
+
+/-- First agreement feature that is not a probe -/
+def goal (fs : List Feature) : Option Nat := goalAfter false fs
+
+/-- Person, number and gender (two bits each, 0 unspecified) clash nowhere -/
+def agrees (a b : Nat) : Bool :=
+  let field (shift : Nat) := a / shift % 4 == 0 || b / shift % 4 == 0 || a / shift % 4 == b / shift % 4
+  field 16 && field 4 && field 1
+
+/-- Shortest Move Constraint: no licensee of `b` is already pending in `a` -/
+def smcNeg (a : List Feature) : Feature → Bool
+  | .neg i => !(a.contains (.neg i))
+  | _ => true
+
+def smc (a b : List Feature) : Bool := b.all (smcNeg a)
+
+def selects (c : Cat) (a b : Judgment) : Bool :=
+  !(b.features.any Feature.isSelector) &&
+    (match category b.features with
+     | some c' => c'.head == c.head
+     | none => false) &&
+    (match probe a.features, goal b.features with
+     | some p, some g => agrees p g
+     | _, _ => true) &&
+    smc a.features b.features
+
+def projects (a : Judgment) (c : Cat) : Cat :=
+  match category a.features with
+  | some h => h.phrase
+  | none => c
+
+def merge (a b : Judgment) : Option Judgment :=
+  let features := passFeatures (removeSelector a.features) b.features
+  match firstSelector a.features with
+  | some (.sel c) =>
+    if selects c a b then
+      some { label := projects a c, features := features, yield := a.yield ++ b.yield }
+    else none
+  | some (.spec c) =>
+    if selects c a b then
+      some { label := projects a c, features := features, yield := b.yield ++ a.yield }
+    else none
+  | _ => none
+
+def adjoinsTo (c : Cat) : Feature → Bool
+  | .adj t => t.head == c.head
+  | _ => false
+
+def fits (host adjunct : Judgment) : Bool :=
+  match category host.features with
+  | some c => adjunct.features.any (adjoinsTo c)
+  | none => false
+
+def adjoin (host adjunct : Judgment) : Option Judgment :=
+  if !(host.features.any Feature.isSelector) && !(adjunct.features.any Feature.isSelector) &&
+      fits host adjunct && smc host.features adjunct.features then
+    some { label := host.label,
+           features := passFeatures host.features adjunct.features,
+           yield := host.yield ++ adjunct.yield }
+  else none
+
+/-- Move `mover`, whose yield starts `before` words into the premise's yield -/
+def move (obj mover : Judgment) (before : Nat) : Option Judgment :=
+  match firstPos obj.features with
+  | some i =>
+    let licensees := mover.features.filter Feature.isNeg
+    let remnant := licensees.foldl (fun fs l => removeFirst l fs) obj.features
+    let rest := obj.yield.drop (before + mover.yield.length)
+    if obj.features.contains (.neg i) && licensees.contains (.neg i) &&
+        obj.yield.take before ++ mover.yield ++ rest == obj.yield then
+      some { label := obj.label,
+             features := removeFirst (.pos i) remnant ++ removeFirst (.neg i) licensees,
+             yield := mover.yield ++ obj.yield.take before ++ rest }
+    else none
+  | none => none
+
+inductive Step where
+  | lexical (phon : String) (features : List Feature)
+  | merge (selector selectee : Nat)
+  | adjoin (host adjunct : Nat)
+  | move (target : Nat) (mover : Judgment) (before : Nat)
+
+def Step.premises : Step → List Nat
+  | .lexical _ _ => []
+  | .merge i j => [i, j]
+  | .adjoin i j => [i, j]
+  | .move i _ _ => [i]
+
+def leaf (phon : String) (features : List Feature) : Judgment :=
+  { label := (category features).getD .N, features := features,
+    yield := if phon = "" then [] else [phon] }
+
+def applyStep (lexicon : List (String × List Feature)) (built : List Judgment) : Step → Option Judgment
+  | .lexical phon features =>
+    if lexicon.contains (phon, features) then some (leaf phon features) else none
+  | .merge i j => do merge (← built[i]?) (← built[j]?)
+  | .adjoin i j => do adjoin (← built[i]?) (← built[j]?)
+  | .move i mover before => do move (← built[i]?) mover before
+
+def run (lexicon : List (String × List Feature)) : List Judgment → List Step → Option (List Judgment)
+  | built, [] => some built
+  | built, step :: rest => do
+    let j ← applyStep lexicon built step
+    run lexicon (built ++ [j]) rest
+
+/-- Premises name earlier steps -/
+def premisesBefore : Nat → List Step → Bool
+  | _, [] => true
+  | k, step :: rest => step.premises.all (fun p => Nat.blt p k) && premisesBefore (k + 1) rest
+
+def noDup : List Nat → Bool
+  | [] => true
+  | n :: ns => !(ns.contains n) && noDup ns
+
+/-- Every step but the last is consumed exactly once -/
+def linear (steps : List Step) : Bool :=
+  let used := steps.foldr (fun step acc => step.premises ++ acc) []
+  premisesBefore 0 steps && noDup used && used.length + 1 == steps.length
+
+def check (lexicon : List (String × List Feature)) (steps : List Step) : Option Judgment := do
+  let built ← run lexicon [] steps
+  let last ← built.getLast?
+  if linear steps && last.features.all Feature.isInterpretable then some last else none
+
+end AtomicLM
+"##;
+
+const COQ_PRELUDE: &str = r##"From Coq Require Import List String Bool Arith.
+Import ListNotations.
+Open Scope string_scope.
+Open Scope list_scope.
+
+Inductive cat := cN | cV | cD | cC | cP | cS | cNP | cVP | cDP | cCP | cPP.
+
+Definition cat_eqb (a b : cat) : bool :=
+  match a, b with
+  | cN, cN | cV, cV | cD, cD | cC, cC | cP, cP | cS, cS
+  | cNP, cNP | cVP, cVP | cDP, cDP | cCP, cCP | cPP, cPP => true
+  | _, _ => false
+  end.
+
+Definition head (c : cat) : cat :=
+  match c with cNP => cN | cVP => cV | cDP => cD | cCP => cC | cPP => cP | _ => c end.
+
+Definition phrase (c : cat) : cat :=
+  match c with cN => cNP | cV => cVP | cD => cDP | cC => cCP | cP => cPP | _ => c end.
+
+Inductive feature :=
+| FCat (c : cat) | FSel (c : cat) | FSpec (c : cat) | FAdj (c : cat) | FPos (i : nat) | FNeg (i : nat)
+| FAgr (bits : nat).
+
+Definition feature_eqb (f g : feature) : bool :=
+  match f, g with
+  | FCat a, FCat b | FSel a, FSel b | FSpec a, FSpec b | FAdj a, FAdj b => cat_eqb a b
+  | FPos i, FPos j | FNeg i, FNeg j | FAgr i, FAgr j => Nat.eqb i j
+  | _, _ => false
+  end.
+
+Fixpoint list_eqb {A} (eqb : A -> A -> bool) (l m : list A) : bool :=
+  match l, m with
+  | [], [] => true
+  | x :: l', y :: m' => eqb x y && list_eqb eqb l' m'
+  | _, _ => false
+  end.
+
+Definition is_selector (f : feature) : bool := match f with FSel _ | FSpec _ => true | _ => false end.
+Definition is_cat (f : feature) : bool := match f with FCat _ => true | _ => false end.
+Definition is_neg (f : feature) : bool := match f with FNeg _ => true | _ => false end.
+
+(* Features a complete projection may keep *)
+Definition is_interpretable (f : feature) : bool := match f with FCat _ | FAgr _ => true | _ => false end.
+
+(* Features left unchecked on a selectee or adjunct *)
+Definition survives (f : feature) : bool := match f with FCat _ | FAdj _ => false | _ => true end.
+
+Definition is_agr (f : feature) : bool := match f with FAgr _ => true | _ => false end.
+
+(* Features heading a bundle: its category and its own agreement *)
+Definition describes (f : feature) : bool := match f with FCat _ | FAgr _ => true | _ => false end.
+
+Fixpoint take_while (p : feature -> bool) (fs : list feature) : list feature :=
+  match fs with [] => [] | f :: fs' => if p f then f :: take_while p fs' else [] end.
+
+Fixpoint drop_while (p : feature -> bool) (fs : list feature) : list feature :=
+  match fs with [] => [] | f :: fs' => if p f then drop_while p fs' else fs end.
+
+(* Add the surviving features of a selectee or adjunct: agreement right after
+   the phrase's category and agreement, so it is never read as a remaining
+   selector's probe, and the rest at the end *)
+Definition pass_features (fs src : list feature) : list feature :=
+  let kept := filter survives src in
+  take_while describes fs ++ filter is_agr kept ++ drop_while describes fs ++ filter (fun f => negb (is_agr f)) kept.
+
+Record judgment := { label : cat; features : list feature; yield : list string }.
+
+Fixpoint category (fs : list feature) : option cat :=
+  match fs with [] => None | FCat c :: _ => Some c | _ :: fs' => category fs' end.
+
+Fixpoint first_selector (fs : list feature) : option feature :=
+  match fs with [] => None | f :: fs' => if is_selector f then Some f else first_selector fs' end.
+
+Fixpoint first_pos (fs : list feature) : option nat :=
+  match fs with [] => None | FPos i :: _ => Some i | _ :: fs' => first_pos fs' end.
+
+Fixpoint remove_first (g : feature) (fs : list feature) : list feature :=
+  match fs with [] => [] | f :: fs' => if feature_eqb f g then fs' else f :: remove_first g fs' end.
+
+(* Drop the first selector and the agreement probe right after it *)
+Fixpoint remove_selector (fs : list feature) : list feature :=
+  match fs with
+  | [] => []
+  | f :: fs' =>
+      if is_selector f then match fs' with FAgr _ :: rest => rest | _ => fs' end
+      else f :: remove_selector fs'
+  end.
+
+(* Agreement probe right after the first selector *)
+Fixpoint probe (fs : list feature) : option nat :=
+  match fs with
+  | [] => None
+  | f :: fs' =>
+      if is_selector f then match fs' with FAgr p :: _ => Some p | _ => None end
+      else probe fs'
+  end.
+
+(* First agreement feature not right after a selector (after_selector) *)
+Fixpoint goal_after (after_selector : bool) (fs : list feature) : option nat :=
+  match after_selector, fs with
+  | _, [] => None
+  | false, FAgr g :: _ => Some g
+  | _, f :: fs' => goal_after (is_selector f) fs'
+  end.
+
+(* First agreement feature that is not a probe *)
+Definition goal (fs : list feature) : option nat := goal_after false fs.
+
+(* Person, number and gender (two bits each, 0 unspecified) clash nowhere *)
+Definition agrees (a b : nat) : bool :=
+  let field shift := let x := Nat.modulo (Nat.div a shift) 4 in let y := Nat.modulo (Nat.div b shift) 4 in
+    Nat.eqb x 0 || Nat.eqb y 0 || Nat.eqb x y in
+  field 16 && field 4 && field 1.
+
+Definition mem (f : feature) (fs : list feature) : bool := existsb (feature_eqb f) fs.
+
+(* Shortest Move Constraint: no licensee of b is already pending in a *)
+Definition smc (a b : list feature) : bool :=
+  forallb (fun f => match f with FNeg i => negb (mem (FNeg i) a) | _ => true end) b.
+
+Definition selects (c : cat) (a b : judgment) : bool :=
+  negb (existsb is_selector (features b))
+  && (match category (features b) with Some c' => cat_eqb (head c') (head c) | None => false end)
+  && (match probe (features a), goal (features b) with Some p, Some g => agrees p g | _, _ => true end)
+  && smc (features a) (features b).
+
+Definition projects (a : judgment) (c : cat) : cat :=
+  match category (features a) with Some h => phrase h | None => c end.
+
+Definition merge (a b : judgment) : option judgment :=
+  let fs := pass_features (remove_selector (features a)) (features b) in
+  match first_selector (features a) with
+  | Some (FSel c) =>
+      if selects c a b
+      then Some {| label := projects a c; features := fs; yield := yield a ++ yield b |}
+      else None
+  | Some (FSpec c) =>
+      if selects c a b
+      then Some {| label := projects a c; features := fs; yield := yield b ++ yield a |}
+      else None
+  | _ => None
+  end.
+
+Definition adjoins_to (c : cat) (f : feature) : bool :=
+  match f with FAdj t => cat_eqb (head t) (head c) | _ => false end.
+
+Definition fits (h a : judgment) : bool :=
+  match category (features h) with Some c => existsb (adjoins_to c) (features a) | None => false end.
+
+Definition adjoin (h a : judgment) : option judgment :=
+  if negb (existsb is_selector (features h)) && negb (existsb is_selector (features a))
+     && fits h a && smc (features h) (features a)
+  then Some {| label := label h; features := pass_features (features h) (features a);
+               yield := yield h ++ yield a |}
+  else None.
+
+(* Move m, whose yield starts before words into the premise's yield *)
+Definition move (o m : judgment) (before : nat) : option judgment :=
+  match first_pos (features o) with
+  | Some i =>
+      let licensees := filter is_neg (features m) in
+      let remnant := fold_left (fun fs l => remove_first l fs) licensees (features o) in
+      let rest := skipn (before + List.length (yield m)) (yield o) in
+      if mem (FNeg i) (features o) && mem (FNeg i) licensees
+         && list_eqb String.eqb (firstn before (yield o) ++ yield m ++ rest) (yield o)
+      then Some {| label := label o;
+                   features := remove_first (FPos i) remnant ++ remove_first (FNeg i) licensees;
+                   yield := yield m ++ firstn before (yield o) ++ rest |}
+      else None
+  | None => None
+  end.
+
+Inductive step :=
+| Lexical (phon : string) (fs : list feature)
+| Merge (selector selectee : nat)
+| Adjoin (host adjunct : nat)
+| Move (target : nat) (mover : judgment) (before : nat).
+
+Definition premises (s : step) : list nat :=
+  match s with Lexical _ _ => [] | Merge i j | Adjoin i j => [i; j] | Move i _ _ => [i] end.
+
+Definition leaf (phon : string) (fs : list feature) : judgment :=
+  {| label := match category fs with Some c => c | None => cN end;
+     features := fs;
+     yield := if String.eqb phon "" then [] else [phon] |}.
+
+Definition entry_eqb (e f : string * list feature) : bool :=
+  String.eqb (fst e) (fst f) && list_eqb feature_eqb (snd e) (snd f).
+
+Definition apply_step (lexicon : list (string * list feature)) (built : list judgment) (s : step)
+  : option judgment :=
+  match s with
+  | Lexical phon fs => if existsb (entry_eqb (phon, fs)) lexicon then Some (leaf phon fs) else None
+  | Merge i j =>
+      match nth_error built i, nth_error built j with Some a, Some b => merge a b | _, _ => None end
+  | Adjoin i j =>
+      match nth_error built i, nth_error built j with Some a, Some b => adjoin a b | _, _ => None end
+  | Move i m before => match nth_error built i with Some o => move o m before | None => None end
+  end.
+
+Fixpoint run (lexicon : list (string * list feature)) (built : list judgment) (steps : list step)
+  : option (list judgment) :=
+  match steps with
+  | [] => Some built
+  | s :: rest =>
+      match apply_step lexicon built s with
+      | Some j => run lexicon (built ++ [j]) rest
+      | None => None
+      end
+  end.
+
+(* Premises name earlier steps *)
+Fixpoint premises_before (k : nat) (steps : list step) : bool :=
+  match steps with
+  | [] => true
+  | s :: rest => forallb (fun p => Nat.ltb p k) (premises s) && premises_before (S k) rest
+  end.
+
+Fixpoint no_dup (ns : list nat) : bool :=
+  match ns with [] => true | n :: ns' => negb (existsb (Nat.eqb n) ns') && no_dup ns' end.
+
+(* Every step but the last is consumed exactly once *)
+Definition linear (steps : list step) : bool :=
+  let used := flat_map premises steps in
+  premises_before 0 steps && no_dup used && Nat.eqb (S (List.length used)) (List.length steps).
+
+Definition check (lexicon : list (string * list feature)) (steps : list step) : option judgment :=
+  match run lexicon [] steps with
+  | Some built =>
+      match rev built with
+      | final :: _ => if linear steps && forallb is_interpretable (features final) then Some final else None
+      | [] => None
+      end
+  | None => None
+  end.
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::{Judgment, Step};
+    use crate::{prove, test_lexicon, GrammarOptions};
+
+    #[test]
+    fn test_exports_one_step_per_line() {
+        let lexicon = test_lexicon();
+        let proof = prove("the student left", &lexicon, &GrammarOptions::default()).unwrap();
+
+        for assistant in [Assistant::Coq, Assistant::Lean] {
+            let source = proof.export(&lexicon, assistant).unwrap();
+            assert!(source.contains("derivation_checks"), "{}", assistant);
+            assert!(source.contains("\"the\""));
+            let open = match assistant {
+                Assistant::Coq => "(* ",
+                Assistant::Lean => "-- ",
+            };
+            for index in 0..proof.steps.len() {
+                assert!(source.contains(&format!("{}{}: ", open, index)), "{}: {}", assistant, source);
+            }
+        }
+
+        let lean = proof.export(&lexicon, Assistant::Lean).unwrap();
+        assert!(lean.contains(".lexical \"the\" [.cat .D, .sel .N]"), "{}", lean);
+        assert!(lean.contains("some { label := .VP, features := [.cat .V], yield := [\"the\", \"student\", \"left\"] }"));
+        let coq = proof.export(&lexicon, Assistant::Coq).unwrap();
+        assert!(coq.contains("Lexical \"the\" [FCat cD; FSel cN]"), "{}", coq);
+        assert!(coq.ends_with("Proof. vm_compute. reflexivity. Qed.\n"));
+    }
+
+    /// Agreeing nouns, and a verb whose specifier selector has no probe
+    fn agreeing_lexicon() -> Vec<LexItem> {
+        use crate::{AgrBundle, Number};
+        let noun = |phon, number| LexItem::new(phon, &[Feature::Cat(Category::N), Feature::Agr(AgrBundle::third(number))]);
+        vec![
+            LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+            noun("students", Number::Plural),
+            noun("teacher", Number::Singular),
+            LexItem::new("thinks", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+        ]
+    }
+
+    #[test]
+    fn test_object_agreement_is_not_a_probe() {
+        // The object's number passes up ahead of the subject selector, as
+        // in the engine, so the plural subject is not checked against it
+        let lexicon = agreeing_lexicon();
+        let proof = prove("the students thinks the teacher", &lexicon, &GrammarOptions::default()).unwrap();
+        for assistant in [Assistant::Coq, Assistant::Lean] {
+            let source = proof.export(&lexicon, assistant).unwrap();
+            let merge = match assistant {
+                Assistant::Coq => "pass_features (remove_selector (features a)) (features b)",
+                Assistant::Lean => "passFeatures (removeSelector a.features) b.features",
+            };
+            assert!(source.contains(merge), "{}", assistant);
+            assert!(source.contains("\"students\""));
+        }
+    }
+
+    #[test]
+    fn test_move_steps_carry_witnesses() {
+        // One derivation with Move, driven outside the parser
+        let who = LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(1)]);
+        let saw = LexItem::new("saw", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Pos(1)]);
+        let leaf = |item: &LexItem| SyntacticObject::from_lex(item);
+        let merged = merge(leaf(&saw), leaf(&who)).unwrap();
+        let (mover, before) = mover_witness(&merged).unwrap();
+        assert_eq!((mover.linearize().as_str(), before), ("who", 1));
+
+        let step = |rule, premises: Vec<usize>, item: Option<&LexItem>, obj: &SyntacticObject| Step {
+            rule,
+            premises,
+            item: item.cloned(),
+            conclusion: Judgment::of(obj),
+        };
+        let moved = move_operation(merged.clone()).unwrap();
+        let proof = Proof {
+            sentence: "who saw".to_string(),
+            steps: vec![
+                step(Rule::Lexical, vec![], Some(&saw), &leaf(&saw)),
+                step(Rule::Lexical, vec![], Some(&who), &leaf(&who)),
+                step(Rule::Merge, vec![0, 1], None, &merged),
+                step(Rule::Move, vec![2], None, &moved),
+            ],
+        };
+        let lexicon = [saw, who];
+        let lean = proof.export(&lexicon, Assistant::Lean).unwrap();
+        assert!(lean.contains(".move 2 { label := .D, features := [.cat .D, .neg 1], yield := [\"who\"] } 1"), "{}", lean);
+        let coq = proof.export(&lexicon, Assistant::Coq).unwrap();
+        assert!(coq.contains("Move 2 {| label := cD; features := [FCat cD; FNeg 1]; yield := [\"who\"] |} 1"), "{}", coq);
+
+        // Rejected proofs are not exported
+        assert_eq!(proof.export(&lexicon[..1], Assistant::Lean), Err(ProofError::UnknownItem(1)));
+    }
+}
@@ -0,0 +1,130 @@
+//! Bare Phrase Structure
+//!
+//! Merge in bare phrase structure (Chomsky 1995) forms a set of two objects
+//! and adds no label: what a constituent is follows from the head that
+//! projects it. `BareObject` keeps only lexical items, traces and merged
+//! pairs with their unchecked features, and computes a label when asked:
+//! the host's label for an adjunction, otherwise the phrase the head
+//! projects. The labels so computed are the ones Merge, Adjoin and Move
+//! stipulate on a `SyntacticObject`, so the two representations convert
+//! into each other without loss.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{parse_with_options, Category, DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Constituent without stipulated labels
+#[derive(Debug, Clone, PartialEq)]
+pub enum BareObject {
+    /// Lexical item, overt or silent, with its features as listed
+    Item(LexItem),
+    /// Trace of a moved constituent
+    Trace(Category),
+    /// Two constituents in linear order, with the features left unchecked
+    Merged {
+        /// Unchecked features of the pair
+        features: Vec<Feature>,
+        /// The constituents
+        children: Box<[BareObject; 2]>,
+    },
+}
+
+impl BareObject {
+    /// Label, computed from the projecting head
+    pub fn label(&self) -> Category {
+        self.dressed().label
+    }
+
+    /// Labeled derived tree
+    pub fn dressed(&self) -> SyntacticObject {
+        match self {
+            BareObject::Item(item) => SyntacticObject::from_lex(item),
+            BareObject::Trace(label) => SyntacticObject::trace(label.clone()),
+            BareObject::Merged { features, children } => {
+                let children = children.iter().map(BareObject::dressed).collect();
+                let mut obj = SyntacticObject::internal(Category::S, features.clone(), children);
+                obj.label = projected_label(&obj);
+                obj
+            }
+        }
+    }
+}
+
+impl From<&SyntacticObject> for BareObject {
+    /// The tree with its internal labels dropped
+    fn from(obj: &SyntacticObject) -> Self {
+        match (&obj.phon, obj.children.as_slice()) {
+            (Some(phon), _) => BareObject::Item(LexItem::new(phon, &obj.features)),
+            (None, [first, second]) => BareObject::Merged {
+                features: obj.features.clone(),
+                children: Box::new([BareObject::from(first), BareObject::from(second)]),
+            },
+            (None, _) => BareObject::Trace(obj.label.clone()),
+        }
+    }
+}
+
+/// Label of a constituent, computed from its head rather than read off it
+///
+/// A leaf is labeled by its category feature and a trace by its stored
+/// label. An adjunction is labeled like its host; any other pair projects
+/// its head, whose category becomes phrasal, and a head without a category
+/// projects the category it selects.
+pub fn projected_label(obj: &SyntacticObject) -> Category {
+    let Some(head) = obj.head_index().and_then(|i| obj.children.get(i)) else {
+        return obj.category().cloned().unwrap_or_else(|| obj.label.clone());
+    };
+    let adjunction = obj.children.iter().enumerate()
+        .any(|(i, child)| obj.head_index() != Some(i) && child.features.iter().any(|f| matches!(f, Feature::Adjoin(_))))
+        && !head.features.iter().any(|f| f.is_selector() || f.is_positive());
+    if adjunction {
+        return projected_label(head);
+    }
+    match (head.category(), head.next_selector()) {
+        (Some(category), _) => category.phrase(),
+        (None, Some(Feature::Sel(selected) | Feature::Spec(selected))) => selected.clone(),
+        (None, _) => projected_label(head),
+    }
+}
+
+/// Parse a sentence into bare phrase structure
+pub fn parse_bare(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<BareObject, DerivationError> {
+    parse_with_options(sentence, lexicon, options).map(|tree| BareObject::from(&tree))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RelativeClauseAnalysis;
+
+    fn stipulated_labels_agree(obj: &SyntacticObject) -> bool {
+        projected_label(obj) == obj.label && obj.children.iter().all(stipulated_labels_agree)
+    }
+
+    #[test]
+    fn test_labels_follow_from_heads() {
+        for relative_clauses in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let options = GrammarOptions { relative_clauses, ..GrammarOptions::default() };
+            let lexicon = options.lexicon();
+            for sentence in ["the student left", "the student thinks the teacher", "the student who left smiled"] {
+                let tree = parse_with_options(sentence, &lexicon, &options).unwrap();
+                assert!(stipulated_labels_agree(&tree), "{}", tree.bracketed());
+
+                // Dropping the labels loses nothing
+                let bare = parse_bare(sentence, &lexicon, &options).unwrap();
+                assert_eq!(bare.dressed(), tree);
+                assert_eq!(bare.label(), tree.label);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bare_structure() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let bare = parse_bare("the student", &lexicon, &GrammarOptions::default()).unwrap();
+        let BareObject::Merged { children, .. } = &bare else { panic!("expected a merged pair") };
+        assert!(matches!(&children[0], BareObject::Item(item) if item.phon == "the"));
+        assert_eq!(bare.label(), Category::DP);
+    }
+}
@@ -2,30 +2,715 @@
 //! 
 //! Command-line interface demonstrating recursive language generation and parsing
 //! with provable mathematical properties.
+//!
+//! Usage:
+//! - `atomic-lm` runs the demo
+//! - `atomic-lm lexicon diff OLD.json NEW.json` audits a lexicon edit
+//! - `atomic-lm parse [--animate FRAMES.json | --proof PROOF.json] SENTENCE`
+//!   shows a derivation, optionally exporting animation frames or a proof,
+//!   or explains why the sentence is rejected
+//! - `atomic-lm timeline [--allocations] SENTENCE STACKS.txt` times each
+//!   step of a sentence's derivation and writes the steps as collapsed
+//!   stacks for a flame graph, weighted by wall time or heap allocations;
+//!   allocations are only counted in a build with `--features count-allocations`
+//! - `atomic-lm tree dot|latex|penn SENTENCE` prints the derived tree as
+//!   Graphviz DOT or LaTeX (`tikz-qtree`), with arrows for movement chains,
+//!   or as a Penn Treebank bracketing
+//! - `atomic-lm check-proof PROOF.json` re-checks an exported proof against
+//!   the default lexicon, exiting 1 if it is rejected
+//! - `atomic-lm export-proof coq|lean PROOF.json` prints a checked proof as a
+//!   Coq or Lean 4 file
+//! - `atomic-lm induce CORPUS.txt [CLUSTERS]` clusters a corpus's words by
+//!   distribution and prints entries proposed for the unknown ones as a lexicon
+//! - `atomic-lm frames TREEBANK.mrg` extracts verbs' complement frames from
+//!   a bracketed treebank and prints them as a candidate lexicon
+//! - `atomic-lm coverage CORPUS.txt [LEXICON.json]` reports how much of a
+//!   corpus (one sentence per line) parses, against the default lexicon if
+//!   none is given
+//! - `atomic-lm overgeneration N JUDGMENTS.txt [LEXICON.json]` generates
+//!   every string of up to N words the grammar derives and lists those the
+//!   judgment file stars as ungrammatical
+//! - `atomic-lm parse-text TEXT.txt [LEXICON.json]` splits running text into
+//!   sentences and parses each, printing its byte offsets and result
+//! - `atomic-lm discourse TEXT.txt [LEXICON.json]` parses running text and
+//!   prints its coreference chains as JSON, resolving pronouns across sentences
+//! - `atomic-lm triage CORPUS.txt [LEXICON.json]` clusters the corpus
+//!   sentences that fail by where their derivations get stuck, largest first
+//! - `atomic-lm synthesize [LEXICON.json]` prints test sentences for each
+//!   construction in a lexicon (the default one if none is given), starring
+//!   those expected to fail
+//! - `atomic-lm sample N SEED [LEXICON.json]` prints N random sentences
+//!   the lexicon derives, one per seed from SEED up, as a synthetic corpus
+//! - `atomic-lm bench --report REPORT.html [--baseline OLD.html]` runs the
+//!   benchmark suite and writes an HTML report, with changes from an earlier
+//!   report if one is given
+//! - `atomic-lm self-check` verifies the build, exiting 1 on failure
+//! - `atomic-lm size BINARY [BUDGET]` prints the sizes of an ELF binary's
+//!   loaded sections, exiting 1 if the file exceeds BUDGET bytes (by
+//!   default the 50kB budget `alm-tiny` is held to)
+//! - `atomic-lm patterns` lists the recursive patterns; `atomic-lm pattern
+//!   NAME N` generates the member of size N
+//! - `atomic-lm repl [LEXICON.json]` parses sentences interactively;
+//!   `:debug SENTENCE` steps through a derivation. A lexicon file given is
+//!   reloaded whenever it changes on disk
 
+use atomic_lang_model::animation::animate;
+use atomic_lang_model::lexicon::status_changes;
+use atomic_lang_model::reload::POLL_INTERVAL;
 use atomic_lang_model::*;
+#[cfg(feature = "count-allocations")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::sync::Arc;
+
+/// The system allocator, reporting each allocation for derivation timelines
+///
+/// Installed only under the `count-allocations` feature, so that every
+/// other command allocates at the system allocator's speed.
+#[cfg(feature = "count-allocations")]
+struct CountingAllocator;
+
+#[cfg(feature = "count-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count_allocation();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_allocation();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Benchmark suites, compiled in for their test sentences and `bench`
+#[allow(dead_code)]
+#[path = "../../bench/mod.rs"]
+mod bench;
+use bench::{agreement_suite, colorless_green, ecp_suite, extraction_suite};
 
 fn main() {
-    println!("🧬 Atomic Language Model - Recursive Grammar Demo");
-    println!("=" .repeat(60));
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    
+    match args.as_slice() {
+        [] => demo(),
+        ["lexicon", "diff", old, new] => lexicon_diff(old, new),
+        ["repl"] => repl(None),
+        ["repl", path] => repl(Some(path)),
+        ["self-check"] => {
+            let report = self_check();
+            print!("{}", report);
+            if !report.passed() {
+                process::exit(1);
+            }
+        }
+        ["size", path] => report_size(path, &binary_size::BUDGET.to_string()),
+        ["size", path, budget] => report_size(path, budget),
+        ["patterns"] => list_patterns(),
+        ["pattern", name, n] => generate(name, n),
+        ["parse", sentence] => parse(sentence, None, None),
+        ["parse", "--animate", path, sentence] | ["parse", sentence, "--animate", path] => {
+            parse(sentence, Some(path), None)
+        }
+        ["parse", "--proof", path, sentence] | ["parse", sentence, "--proof", path] => {
+            parse(sentence, None, Some(path))
+        }
+        ["timeline", sentence, path] => timeline(sentence, path, TimelineMetric::WallTime),
+        ["timeline", "--allocations", sentence, path] => timeline(sentence, path, TimelineMetric::Allocations),
+        ["tree", "dot", sentence] => print_tree(sentence, to_dot),
+        ["tree", "latex", sentence] => print_tree(sentence, to_latex),
+        ["tree", "penn", sentence] => print_tree(sentence, |tree| tree.penn_bracketed() + "\n"),
+        ["check-proof", path] => check_proof(path),
+        ["export-proof", "coq", path] => export_proof(path, Assistant::Coq),
+        ["export-proof", "lean", path] => export_proof(path, Assistant::Lean),
+        ["induce", path] => induce(path, None),
+        ["induce", path, clusters] => induce(path, Some(clusters)),
+        ["frames", path] => frames(path),
+        ["coverage", corpus] => report_coverage(corpus, &GrammarOptions::default().lexicon()),
+        ["coverage", corpus, path] => report_coverage(corpus, &read_lexicon(path).items),
+        ["overgeneration", n, judgments] => report_overgeneration(n, judgments, &GrammarOptions::default().lexicon()),
+        ["overgeneration", n, judgments, path] => report_overgeneration(n, judgments, &read_lexicon(path).items),
+        ["parse-text", text] => parse_paragraphs(text, &GrammarOptions::default().lexicon()),
+        ["parse-text", text, path] => parse_paragraphs(text, &read_lexicon(path).items),
+        ["discourse", text] => discourse(text, &GrammarOptions::default().lexicon()),
+        ["discourse", text, path] => discourse(text, &read_lexicon(path).items),
+        ["sample", n, seed] => sample(n, seed, &GrammarOptions::default().lexicon()),
+        ["sample", n, seed, path] => sample(n, seed, &read_lexicon(path).items),
+        ["bench", "--report", path] => bench_report(path, None),
+        ["bench", "--report", path, "--baseline", baseline] => bench_report(path, Some(baseline)),
+        ["triage", corpus] => report_triage(corpus, &GrammarOptions::default().lexicon()),
+        ["triage", corpus, path] => report_triage(corpus, &read_lexicon(path).items),
+        ["synthesize"] => synthesize(&GrammarOptions::default().lexicon()),
+        ["synthesize", path] => synthesize(&read_lexicon(path).items),
+        _ => {
+            eprintln!("usage: atomic-lm [lexicon diff OLD.json NEW.json | parse [--animate FRAMES.json | --proof PROOF.json] SENTENCE | timeline [--allocations] SENTENCE STACKS.txt | tree dot|latex|penn SENTENCE | check-proof PROOF.json | export-proof coq|lean PROOF.json | induce CORPUS.txt [CLUSTERS] | frames TREEBANK.mrg | coverage CORPUS.txt [LEXICON.json] | overgeneration N JUDGMENTS.txt [LEXICON.json] | parse-text TEXT.txt [LEXICON.json] | discourse TEXT.txt [LEXICON.json] | triage CORPUS.txt [LEXICON.json] | synthesize [LEXICON.json] | sample N SEED [LEXICON.json] | bench --report REPORT.html [--baseline OLD.html] | patterns | pattern NAME N | repl [LEXICON.json] | self-check | size BINARY [BUDGET]]");
+            process::exit(2);
+        }
+    }
+}
+
+/// Print a binary's section sizes and check it against a budget in bytes
+fn report_size(path: &str, budget: &str) {
+    let Ok(budget) = budget.parse::<u64>() else {
+        eprintln!("❌ BUDGET must be a number of bytes, got '{}'", budget);
+        process::exit(2);
+    };
+    let report = SizeReport::read(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    println!("{}", report);
+    match report.check(budget) {
+        Ok(()) => println!("✅ within the {}-byte budget", budget),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// List the registered recursive patterns
+fn list_patterns() {
+    for pattern in PatternRegistry::new().iter() {
+        println!("{:<10} {:<26} {}", pattern.name(), pattern.complexity_class(), pattern.description());
+    }
+}
+
+/// Generate and verify one member of a registered pattern
+fn generate(name: &str, n: &str) {
+    let registry = PatternRegistry::new();
+    let Some(pattern) = registry.get(name) else {
+        eprintln!("❌ unknown pattern '{}' (see `atomic-lm patterns`)", name);
+        process::exit(2);
+    };
+    let Ok(n) = n.parse::<usize>() else {
+        eprintln!("❌ N must be a non-negative integer, got '{}'", n);
+        process::exit(2);
+    };
+    let member = pattern.generate(n);
+    let mark = if pattern.recognize(&member) { "✅" } else { "❌" };
+    println!("{} {}", mark, if member.is_empty() { "ε" } else { &member });
+}
+
+/// Deepest phrase nesting in sampled sentences
+const SAMPLE_DEPTH: usize = 5;
+
+/// Print random sentences from consecutive seeds, one per line
+fn sample(n: &str, seed: &str, lexicon: &[LexItem]) {
+    let (Ok(n), Ok(seed)) = (n.parse::<u64>(), seed.parse::<u64>()) else {
+        eprintln!("❌ N and SEED must be non-negative integers, got '{}' and '{}'", n, seed);
+        process::exit(2);
+    };
+    for seed in seed..seed.saturating_add(n) {
+        let sentence = generate_random(lexicon, SAMPLE_DEPTH, seed);
+        if !sentence.is_empty() {
+            println!("{}", sentence);
+        }
+    }
+}
+
+/// Read a lexicon file, exiting with a message on failure
+fn read_lexicon(path: &str) -> Lexicon {
+    let json = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    Lexicon::from_json(&json).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    })
+}
+
+/// Every sentence of the benchmark suites
+fn benchmark_sentences() -> Vec<String> {
+    let mut sentences = Vec::new();
+    for test in agreement_suite::generate_agreement_tests() {
+        sentences.extend([test.grammatical, test.ungrammatical]);
+    }
+    for test in colorless_green::generate_colorless_green_tests() {
+        sentences.extend([test.grammatical, test.ungrammatical]);
+    }
+    for test in extraction_suite::generate_extraction_tests() {
+        sentences.extend([test.grammatical, test.ungrammatical]);
+    }
+    for test in ecp_suite::generate_ecp_tests() {
+        sentences.extend([test.object, test.subject]);
+    }
+    sentences.sort();
+    sentences.dedup();
+    sentences
+}
+
+/// Report lexical changes and the benchmark sentences they affect
+fn lexicon_diff(old_path: &str, new_path: &str) {
+    let old = read_lexicon(old_path);
+    let new = read_lexicon(new_path);
+    
+    let diff = old.diff(&new);
+    println!("📖 {}", diff.to_string().trim_end());
+    println!(
+        "{} added, {} removed, {} changed",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.changed.len()
+    );
+    
+    let sentences = benchmark_sentences();
+    let sentences: Vec<&str> = sentences.iter().map(String::as_str).collect();
+    let changes = status_changes(&old.items, &new.items, &sentences);
+    
+    println!("\n🔍 Parse status changes ({} benchmark sentences)", sentences.len());
+    println!("{}", "-".repeat(40));
+    for change in &changes {
+        let status = |parses: bool| if parses { "parses" } else { "fails" };
+        println!("{} → {}: {}", status(change.before), status(change.after), change.sentence);
+    }
+    if changes.is_empty() {
+        println!("No benchmark sentence changes status");
+    }
+}
+
+/// Derive a sentence step by step, optionally writing animation frames
+fn parse(sentence: &str, animate_path: Option<&str>, proof_path: Option<&str>) {
+    let options = GrammarOptions::default();
+    let lexicon = options.lexicon();
+    
+    let animation = animate(sentence, &lexicon, &options).unwrap_or_else(|e| {
+        eprintln!("❌ '{}' → Error: {}", sentence, e);
+        if let Some(explanation) = explain(sentence, &lexicon, &options) {
+            eprintln!("   {}", explanation);
+        }
+        process::exit(1);
+    });
+    
+    println!("🔍 Derivation of '{}'", sentence);
+    println!("{}", "-".repeat(40));
+    for frame in &animation.frames[1..] {
+        if let (Some(op), Some(result)) = (&frame.operation, &frame.result) {
+            println!("{:>3}. {:?} → {:?} '{}'", frame.step, op, result.label, result.linearize());
+        }
+    }
+    if let Some(tree) = animation.frames.last().and_then(|frame| frame.workspace.first()) {
+        if let Ok(prosody) = phrasing(tree) {
+            println!("Prosody: {}", prosody);
+        }
+        if let Some(term) = transfer(tree).ok().and_then(|output| output.interpret()) {
+            println!("LF: {}", term);
+        }
+    }
+    
+    if let Some(path) = animate_path {
+        std::fs::write(path, animation.to_json()).unwrap_or_else(|e| {
+            eprintln!("❌ {}: {}", path, e);
+            process::exit(1);
+        });
+        println!("\n🎞️  Wrote {} frames to {}", animation.frames.len(), path);
+    }
+    
+    if let Some(path) = proof_path {
+        let derivation: Vec<Operation> = animation.frames.iter().filter_map(|frame| frame.operation).collect();
+        let proof = Proof::from_derivation(sentence, &lexicon, &derivation).unwrap_or_else(|e| {
+            eprintln!("❌ '{}' → Error: {}", sentence, e);
+            process::exit(1);
+        });
+        std::fs::write(path, proof.to_json()).unwrap_or_else(|e| {
+            eprintln!("❌ {}: {}", path, e);
+            process::exit(1);
+        });
+        println!("\n📜 Wrote a {}-step proof to {}", proof.steps.len(), path);
+    }
+}
+
+/// Print the cost of each operation the search for a sentence's derivation
+/// tried and write them as collapsed stacks
+fn timeline(sentence: &str, path: &str, metric: TimelineMetric) {
+    if metric == TimelineMetric::Allocations && !cfg!(feature = "count-allocations") {
+        eprintln!("❌ counting allocations needs a build with --features count-allocations");
+        process::exit(2);
+    }
+    let options = GrammarOptions::default();
+    let trace = trace_derivation(sentence, &options.lexicon(), &options).unwrap_or_else(|e| {
+        eprintln!("❌ '{}' → Error: {}", sentence, e);
+        process::exit(1);
+    });
+    
+    println!("⏱️  Timeline of '{}'", sentence);
+    println!("{}", "-".repeat(40));
+    for step in &trace.steps {
+        let operation = step.operation.map_or("none".to_string(), |op| format!("{:?}", op));
+        let failed = if step.outcome.is_err() { " (failed)" } else { "" };
+        println!("{:>3}. {:<16} {:>9.1}µs {:>5} allocations{}", step.step, operation, step.elapsed.as_secs_f64() * 1e6, step.allocations, failed);
+    }
+    
+    std::fs::write(path, trace.collapsed_stacks(metric)).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    println!("\n🔥 Wrote {} stacks to {}", trace.len(), path);
+}
+
+/// Print a sentence's derived tree in an export format
+fn print_tree(sentence: &str, render: fn(&SyntacticObject) -> String) {
+    let options = GrammarOptions::default();
+    match parse_with_options(sentence, &options.lexicon(), &options) {
+        Ok(tree) => print!("{}", render(&tree)),
+        Err(e) => {
+            eprintln!("❌ '{}' → Error: {}", sentence, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Re-check an exported proof against the default lexicon
+fn check_proof(path: &str) {
+    let json = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let lexicon = GrammarOptions::default().lexicon();
+    match Proof::from_json(&json).and_then(|proof| proof.check(&lexicon).map(|tree| (proof, tree))) {
+        Ok((proof, tree)) => println!("✅ {} steps check: '{}' → {}", proof.steps.len(), proof.sentence, tree.bracketed()),
+        Err(e) => {
+            eprintln!("❌ {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Print an exported proof as a Coq or Lean file
+fn export_proof(path: &str, assistant: Assistant) {
+    let json = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let lexicon = GrammarOptions::default().lexicon();
+    match Proof::from_json(&json).and_then(|proof| proof.export(&lexicon, assistant)) {
+        Ok(source) => print!("{}", source),
+        Err(e) => {
+            eprintln!("❌ {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Cluster a corpus by distribution and print proposed entries as a lexicon
+fn induce(path: &str, clusters: Option<&str>) {
+    let mut options = InductionOptions::default();
+    if let Some(clusters) = clusters {
+        let Ok(clusters) = clusters.parse() else {
+            eprintln!("❌ CLUSTERS must be a non-negative integer, got '{}'", clusters);
+            process::exit(2);
+        };
+        options.clusters = clusters;
+    }
+    let corpus = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let lexicon = GrammarOptions::default().lexicon();
+    let induction = induce_categories(&corpus, options, &lexicon);
+    for cluster in &induction.clusters {
+        let features = cluster.features.as_ref().map_or("?".to_string(), |features| {
+            features.iter().map(Feature::to_string).collect::<Vec<_>>().join(" ")
+        });
+        eprintln!("[{}] {}", features, cluster.words.join(" "));
+    }
+    print!("{}", Lexicon::new("0.1.0", induction.proposals(&lexicon)).to_json());
+}
+
+/// Extract subcategorization frames and print them as a candidate lexicon
+fn frames(path: &str) {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let trees = parse_treebank(&text).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let frames = subcategorization(&trees);
+    for frame in &frames {
+        eprintln!("{:>5}  {}", frame.count, frame.item());
+    }
+    print!("{}", Lexicon::new("0.1.0", frames.iter().map(SubcatFrame::item).collect()).to_json());
+}
+
+/// Print a corpus coverage report
+fn report_coverage(path: &str, lexicon: &[LexItem]) {
+    let corpus = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    print!("{}", coverage(&corpus, lexicon));
+}
+
+/// Print the grammar's outputs up to `n` words that the judgments reject
+fn report_overgeneration(n: &str, path: &str, lexicon: &[LexItem]) {
+    let Ok(n) = n.parse::<usize>() else {
+        eprintln!("❌ N must be a non-negative integer, got '{}'", n);
+        process::exit(2);
+    };
+    let judgments = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    print!("{}", overgeneration(lexicon, &GrammarOptions::default(), n, &Judgments::parse(&judgments)));
+}
+
+/// Segment a text file into sentences and print each one's parse result
+fn parse_paragraphs(path: &str, lexicon: &[LexItem]) {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let options = GrammarOptions { normalizer: Normalizer::raw_text(), ..GrammarOptions::default() };
+    for outcome in parse_text(&text, lexicon, &options) {
+        let sentence = &text[outcome.span.clone()];
+        match outcome.result {
+            Ok(_) => println!("✅ {:>5}..{:<5} {}", outcome.span.start, outcome.span.end, sentence),
+            Err(e) => println!("❌ {:>5}..{:<5} {}  ({})", outcome.span.start, outcome.span.end, sentence, e),
+        }
+    }
+}
+
+/// Parse a text file's sentences and print their coreference chains as JSON
+fn discourse(path: &str, lexicon: &[LexItem]) {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let options = GrammarOptions { normalizer: Normalizer::raw_text(), ..GrammarOptions::default() };
+    let mut discourse = Discourse::default();
+    discourse.add_outcomes(&parse_text(&text, lexicon, &options));
+    print!("{}", discourse.to_json());
+}
+
+/// Print the corpus's parse failures clustered by signature
+fn report_triage(path: &str, lexicon: &[LexItem]) {
+    let corpus = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    let sentences = corpus.lines().map(str::trim).filter(|line| !line.is_empty());
+    print!("{}", triage(sentences, lexicon, &GrammarOptions::default()));
+}
+
+/// Print the sentences synthesized for each construction in a lexicon
+fn synthesize(lexicon: &[LexItem]) {
+    for construction in synthesize_benchmark(lexicon) {
+        println!("{}", construction);
+        if construction.cases.is_empty() {
+            println!("  (no derivation found)");
+        }
+        for case in &construction.cases {
+            match &case.omitted {
+                None => println!("   {}", case.sentence),
+                Some(feature) => println!("  *{}  (without {})", case.sentence, feature),
+            }
+        }
+    }
+}
+
+/// Run the benchmark suite and write its HTML report
+fn bench_report(path: &str, baseline: Option<&str>) {
+    let baseline = baseline.map(|baseline| {
+        let html = std::fs::read_to_string(baseline).unwrap_or_else(|e| {
+            eprintln!("❌ {}: {}", baseline, e);
+            process::exit(1);
+        });
+        bench::report::read_baseline(&html).unwrap_or_else(|| {
+            eprintln!("❌ {}: not a benchmark report", baseline);
+            process::exit(1);
+        })
+    });
+    let results = bench::run_complete_benchmark();
+    let html = bench::report::render_report(&results, baseline.as_deref());
+    std::fs::write(path, html).unwrap_or_else(|e| {
+        eprintln!("❌ {}: {}", path, e);
+        process::exit(1);
+    });
+    println!("\n📄 Report written to {}", path);
+}
+
+/// Print a prompt and read one trimmed line (`None` at end of input)
+fn prompt(lines: &mut impl Iterator<Item = io::Result<String>>, text: &str) -> Option<String> {
+    print!("{}", text);
+    io::stdout().flush().ok()?;
+    lines.next()?.ok().map(|line| line.trim().to_string())
+}
+
+/// Parse sentences line by line until `:quit`
+fn repl(path: Option<&str>) {
+    let options = GrammarOptions::default();
+    let default = Arc::new(Lexicon::new("default", options.lexicon()));
+    let watcher = path.map(|path| {
+        LexiconWatcher::watch(path, POLL_INTERVAL).unwrap_or_else(|e| {
+            eprintln!("❌ {}: {}", path, e);
+            process::exit(1);
+        })
+    });
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
     
-    // Demonstrate aⁿbⁿ generation (proof of recursion)
-    println!("\n📐 Mathematical Proof: aⁿbⁿ Generation");
-    println!("-".repeat(40));
+    println!("🧬 Atomic Language Model REPL (:debug SENTENCE, :quit)");
+    if let Some(path) = path {
+        println!("👀 Watching {} for changes", path);
+    }
+    while let Some(line) = prompt(&mut lines, "alm> ") {
+        // Each line parses against one snapshot, whatever reloads meanwhile
+        let lexicon = match &watcher {
+            Some(watcher) => {
+                for event in watcher.events() {
+                    match event {
+                        ReloadEvent::Reloaded(lexicon) => {
+                            println!("🔄 Reloaded lexicon {} ({} items)", lexicon.version, lexicon.items.len())
+                        }
+                        ReloadEvent::Failed(e) => println!("⚠️  Kept the previous lexicon: {}", e),
+                    }
+                }
+                watcher.lexicon()
+            }
+            None => Arc::clone(&default),
+        };
+        let lexicon = &lexicon.items;
+        match line.split_once(' ') {
+            _ if line.is_empty() => {}
+            _ if line == ":quit" => break,
+            Some((":debug", sentence)) => match Debugger::for_sentence(sentence, lexicon, &options) {
+                Ok(debugger) => debug(debugger, &mut lines),
+                Err(e) => println!("❌ Error: {}", e),
+            },
+            _ if line.starts_with(':') => println!("Unknown command: {}", line),
+            _ => match parse_with_options(&line, lexicon, &options) {
+                Ok(tree) => println!("✅ {:?}: {}", tree.label, tree.linearize()),
+                Err(e) => {
+                    println!("❌ Error: {}", e);
+                    if let Some(explanation) = explain(&line, lexicon, &options) {
+                        println!("   {}", explanation);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Interactive debugger session for the REPL's `:debug` command
+fn debug(mut debugger: Debugger, lines: &mut impl Iterator<Item = io::Result<String>>) {
+    println!("🐞 s(tep) | n(ext phrase) | c(ontinue) | r(ewind) | i N | b CAT|FEATURE | w(orkspace) | q(uit)");
+    show_workspace(&debugger);
     
-    for n in 0..=5 {
-        match generate_pattern("an_bn", n) {
-            Ok(pattern) => {
-                let display = if pattern.is_empty() { "ε (empty)" } else { &pattern };
-                println!("n={}: {}", n, display);
+    while let Some(line) = prompt(lines, "debug> ") {
+        let mut words = line.split_whitespace();
+        let result = match (words.next(), words.next()) {
+            (Some("s"), None) => debugger.step_into().map(|op| report(op.into_iter().collect())),
+            (Some("n"), None) => debugger.step_over().map(report),
+            (Some("c"), None) => debugger.resume().map(|hit| match hit {
+                Some(breakpoint) => println!("⏸️  Breakpoint: {:?}", breakpoint),
+                None => println!("🏁 Derivation finished"),
+            }),
+            (Some("r"), None) => {
+                match debugger.rewind() {
+                    Some(op) => println!("⏪ Undid {:?}", op),
+                    None => println!("At the start of the derivation"),
+                }
+                Ok(())
+            }
+            (Some("i"), Some(i)) => {
+                match i.parse().ok().and_then(|i| debugger.inspect(i)) {
+                    Some(obj) => {
+                        let features: Vec<String> = obj.features.iter().map(Feature::to_string).collect();
+                        let children: Vec<String> = obj.children.iter().map(|c| format!("{:?}", c.label)).collect();
+                        println!("{:?} :: {} '{}'", obj.label, features.join(" "), obj.linearize());
+                        if !children.is_empty() {
+                            println!("  children: {}", children.join(", "));
+                        }
+                    }
+                    None => println!("No object at {}", i),
+                }
+                Ok(())
+            }
+            (Some("b"), Some(target)) => {
+                let breakpoint = target.parse().map(Breakpoint::Category)
+                    .or_else(|_| target.parse().map(Breakpoint::Feature));
+                match breakpoint {
+                    Ok(breakpoint) => debugger.add_breakpoint(breakpoint),
+                    Err(e) => println!("❌ {}", e),
+                }
+                Ok(())
             }
-            Err(e) => println!("n={}: Error - {}", n, e),
+            (Some("w"), None) => {
+                show_workspace(&debugger);
+                Ok(())
+            }
+            (Some("q"), None) => return,
+            _ => {
+                println!("Unknown debugger command: {}", line);
+                Ok(())
+            }
+        };
+        
+        if let Err(e) = result {
+            println!("❌ Error: {}", e);
+        }
+    }
+}
+
+/// Print operations applied by a debugger step
+fn report(applied: Vec<Operation>) {
+    if applied.is_empty() {
+        println!("🏁 Derivation finished");
+    }
+    for op in applied {
+        println!("▶️  {:?}", op);
+    }
+}
+
+/// Print the debugger's workspace and next operation
+fn show_workspace(debugger: &Debugger) {
+    for (i, obj) in debugger.workspace().items().iter().enumerate() {
+        println!("  [{}] {:?}: {}", i, obj.label, obj.linearize());
+    }
+    if let Some(op) = debugger.next_operation() {
+        println!("  next: {:?}", op);
+    }
+}
+
+/// Demonstrate generation, parsing, and formal properties
+fn demo() {
+    println!("🧬 Atomic Language Model - Recursive Grammar Demo");
+    println!("{}", "=".repeat(60));
+    
+    // Demonstrate pattern generation (proof of recursion)
+    let patterns = PatternRegistry::new();
+    for pattern in patterns.iter() {
+        println!("\n📐 Mathematical Proof: {} ({})", pattern.description(), pattern.complexity_class());
+        println!("{}", "-".repeat(40));
+        
+        for n in 0..=5 {
+            let member = pattern.generate(n);
+            let display = if member.is_empty() { "ε (empty)" } else { &member };
+            println!("n={}: {}", n, display);
         }
     }
     
     // Test recursive parsing capability
     println!("\n🔍 Parsing Test: Recursive Structures");
-    println!("-".repeat(40));
+    println!("{}", "-".repeat(40));
     
     let lexicon = test_lexicon();
     let test_sentences = vec![
@@ -48,7 +733,7 @@ fn main() {
     
     // Memory and performance metrics
     println!("\n📊 Performance Metrics");
-    println!("-".repeat(40));
+    println!("{}", "-".repeat(40));
     
     let mut workspace = Workspace::new(1024);
     workspace.add_lex(&lexicon[0]); // "the"
@@ -56,12 +741,12 @@ fn main() {
     workspace.add_lex(&lexicon[8]); // "left"
     
     println!("Memory usage: {} bytes", workspace.memory_usage());
-    println!("Objects in workspace: {}", workspace.items.len());
+    println!("Objects in workspace: {}", workspace.items().len());
     println!("Binary optimized for: <50kB total size");
     
     // Demonstrate unbounded recursion principle
     println!("\n♾️  Unbounded Recursion Demonstration");
-    println!("-".repeat(40));
+    println!("{}", "-".repeat(40));
     
     println!("Generating increasingly complex patterns...");
     for n in 6..=10 {
@@ -78,7 +763,7 @@ fn main() {
     
     // Show formal properties
     println!("\n🧮 Formal Properties Verified");
-    println!("-".repeat(40));
+    println!("{}", "-".repeat(40));
     println!("✅ Non-regular language generation (aⁿbⁿ)");
     println!("✅ Context-free parsing capability");
     println!("✅ Minimalist Grammar operations (Merge/Move)");
@@ -0,0 +1,232 @@
+//! Binary Size Budget
+//!
+//! Reads the section headers of an ELF executable and checks its size
+//! against a budget, so the size claim is measured rather than asserted.
+//! The `alm-tiny` binary, a freestanding `compiled::recognize` for the
+//! bundled English lexicon, built with
+//!
+//! ```text
+//! cargo build --manifest-path tiny/Cargo.toml --profile min-size
+//! ```
+//!
+//! is held to `BUDGET` by `tests/size_budget.rs`; `atomic-lm size BINARY`
+//! prints the same report for any build. Both 32- and 64-bit ELF files of
+//! either byte order are read; other executable formats are rejected.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+/// Size budget for the `alm-tiny` binary, in bytes
+pub const BUDGET: u64 = 50 * 1024;
+
+/// `sh_flags` bit of sections occupying memory at run time
+const SHF_ALLOC: u64 = 0x2;
+
+/// `sh_type` of sections with no data in the file (`.bss`)
+const SHT_NOBITS: u32 = 8;
+
+/// Errors reading or checking a binary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeError {
+    /// The file could not be read
+    Unreadable,
+    /// The file is not an ELF executable
+    NotElf,
+    /// A header or section name lies outside the file
+    Truncated,
+    /// The binary exceeds its budget
+    OverBudget {
+        /// Size of the file in bytes
+        size: u64,
+        /// Budget in bytes
+        budget: u64,
+    },
+}
+
+impl fmt::Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SizeError::Unreadable => write!(f, "Binary could not be read"),
+            SizeError::NotElf => write!(f, "Not an ELF binary"),
+            SizeError::Truncated => write!(f, "ELF headers lie outside the file"),
+            SizeError::OverBudget { size, budget } => {
+                write!(f, "Binary is {} bytes, over its budget of {} bytes", size, budget)
+            }
+        }
+    }
+}
+
+/// One section of a binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Section name, e.g. `.text`
+    pub name: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Whether the section is loaded into memory at run time
+    pub loaded: bool,
+    /// Whether the section takes up space in the file (`.bss` does not)
+    pub in_file: bool,
+}
+
+/// Sizes of a binary and its sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeReport {
+    /// Size of the whole file in bytes
+    pub file_size: u64,
+    /// Sections in header order, without the null section
+    pub sections: Vec<Section>,
+}
+
+/// Fixed-width reads in the file's byte order, failing past its end
+struct Reader<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl Reader<'_> {
+    fn uint(&self, offset: u64, width: usize) -> Result<u64, SizeError> {
+        let start = usize::try_from(offset).map_err(|_| SizeError::Truncated)?;
+        let end = start.checked_add(width).ok_or(SizeError::Truncated)?;
+        let field = self.bytes.get(start..end).ok_or(SizeError::Truncated)?;
+        let fold = |value: u64, &byte: &u8| value << 8 | byte as u64;
+        Ok(if self.little_endian { field.iter().rev().fold(0, fold) } else { field.iter().fold(0, fold) })
+    }
+}
+
+impl SizeReport {
+    /// Read the section headers of an ELF file
+    pub fn from_elf(bytes: &[u8]) -> Result<Self, SizeError> {
+        if bytes.get(..4) != Some(b"\x7fELF") {
+            return Err(SizeError::NotElf);
+        }
+        let wide = match bytes.get(4) {
+            Some(1) => false,
+            Some(2) => true,
+            _ => return Err(SizeError::NotElf),
+        };
+        let little_endian = match bytes.get(5) {
+            Some(1) => true,
+            Some(2) => false,
+            _ => return Err(SizeError::NotElf),
+        };
+        let reader = Reader { bytes, little_endian };
+        // Address-sized fields, and the header offsets that depend on them
+        let word = if wide { 8 } else { 4 };
+        let (shoff, shentsize) = if wide { (0x28, 0x3a) } else { (0x20, 0x2e) };
+
+        let table = reader.uint(shoff, word)?;
+        let entry = reader.uint(shentsize, 2)?;
+        let count = reader.uint(shentsize + 2, 2)?;
+        let names = reader.uint(shentsize + 4, 2)?;
+        // Offsets come from the file, so a corrupt one must not overflow
+        let field = |i: u64, at: u64, width: usize| {
+            let offset = i.checked_mul(entry).and_then(|header| header.checked_add(table)?.checked_add(at));
+            reader.uint(offset.ok_or(SizeError::Truncated)?, width)
+        };
+        // sh_name, sh_type, sh_flags, then sh_addr, sh_offset and sh_size
+        let flags = |i: u64| field(i, 8, word);
+        let offset = |i: u64| field(i, 8 + 2 * word as u64, word);
+        let size = |i: u64| field(i, 8 + 3 * word as u64, word);
+
+        let strings = offset(names)?;
+        let name = |i: u64| -> Result<String, SizeError> {
+            let start = strings.checked_add(field(i, 0, 4)?).ok_or(SizeError::Truncated)?;
+            let start = usize::try_from(start).map_err(|_| SizeError::Truncated)?;
+            let tail = bytes.get(start..).ok_or(SizeError::Truncated)?;
+            let end = tail.iter().position(|&b| b == 0).ok_or(SizeError::Truncated)?;
+            Ok(String::from_utf8_lossy(&tail[..end]).into_owned())
+        };
+
+        let sections = (1..count)
+            .map(|i| {
+                Ok(Section {
+                    name: name(i)?,
+                    size: size(i)?,
+                    loaded: flags(i)? & SHF_ALLOC != 0,
+                    in_file: field(i, 4, 4)? as u32 != SHT_NOBITS,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { file_size: bytes.len() as u64, sections })
+    }
+
+    /// Read an ELF file from disk
+    #[cfg(feature = "std")]
+    pub fn read(path: impl AsRef<std::path::Path>) -> Result<Self, SizeError> {
+        Self::from_elf(&std::fs::read(path).map_err(|_| SizeError::Unreadable)?)
+    }
+
+    /// The section with the given name
+    pub fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    /// Bytes the sections loaded at run time occupy
+    pub fn loaded_size(&self) -> u64 {
+        self.sections.iter().filter(|section| section.loaded).map(|section| section.size).sum()
+    }
+
+    /// Check the file against a budget in bytes
+    pub fn check(&self, budget: u64) -> Result<(), SizeError> {
+        if self.file_size > budget {
+            return Err(SizeError::OverBudget { size: self.file_size, budget });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for section in self.sections.iter().filter(|section| section.loaded) {
+            writeln!(f, "{:<20} {:>8}", section.name, section.size)?;
+        }
+        writeln!(f, "{:<20} {:>8}", "loaded", self.loaded_size())?;
+        write!(f, "{:<20} {:>8}", "file", self.file_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_other_formats() {
+        assert_eq!(SizeReport::from_elf(b"MZ\x90\x00"), Err(SizeError::NotElf));
+        assert_eq!(SizeReport::from_elf(b"\x7fELF\x03\x01"), Err(SizeError::NotElf));
+        assert_eq!(SizeReport::from_elf(b"\x7fELF\x02\x01\x01"), Err(SizeError::Truncated));
+    }
+
+    #[test]
+    fn test_corrupt_offsets_are_truncated() {
+        // A 64-bit little-endian header whose section table lies past the
+        // end of the address space
+        let mut header = [0; 0x40];
+        header[..6].copy_from_slice(b"\x7fELF\x02\x01");
+        header[0x28..0x30].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        header[0x3a..0x40].copy_from_slice(&[0x40, 0, 2, 0, 1, 0]);
+        assert_eq!(SizeReport::from_elf(&header), Err(SizeError::Truncated));
+
+        // Or whose entries are too large to index
+        header[0x28..0x30].copy_from_slice(&0x40u64.to_le_bytes());
+        header[0x3a..0x3c].copy_from_slice(&u16::MAX.to_le_bytes());
+        header[0x3c..0x3e].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(SizeReport::from_elf(&header), Err(SizeError::Truncated));
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_os = "linux"))]
+    fn test_reads_own_executable() {
+        let report = SizeReport::read(std::env::current_exe().unwrap()).unwrap();
+        let text = report.section(".text").unwrap();
+        assert!(text.loaded && text.in_file && text.size > 0);
+        assert!(report.section(".bss").is_none_or(|bss| !bss.in_file));
+        assert!(report.loaded_size() >= text.size);
+
+        assert_eq!(report.check(report.file_size), Ok(()));
+        assert_eq!(report.check(1024), Err(SizeError::OverBudget { size: report.file_size, budget: 1024 }));
+        assert!(report.to_string().contains(".text"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Tree Builder
+//!
+//! Expected trees in tests and examples, written without struct literals:
+//! `TreeBuilder` assembles a `SyntacticObject` step by step, and the
+//! `tree!` macro writes one as a labeled bracketing,
+//! `tree!{ DP => [ D "the", N "student" ] }`. Leaves are `Label "phon"`
+//! (`""` for a silent head), traces `Label t`, and phrases
+//! `Label => [ children ]`; a leaf or phrase may list its unchecked
+//! features in brackets, `D "the" [Feature::Sel(Category::N)]`.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec::Vec};
+
+use crate::{Category, Feature, SyntacticObject};
+
+/// Incremental constructor for a syntactic object
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeBuilder {
+    tree: SyntacticObject,
+}
+
+impl TreeBuilder {
+    /// Phrase with the given label and no children yet
+    pub fn phrase(label: Category) -> Self {
+        Self { tree: SyntacticObject::internal(label, Vec::new(), Vec::new()) }
+    }
+
+    /// Leaf pronounced `phon` (silent if empty)
+    pub fn leaf(label: Category, phon: &str) -> Self {
+        Self { tree: SyntacticObject { phon: Some(phon.to_string()), ..SyntacticObject::trace(label) } }
+    }
+
+    /// Trace of a moved constituent
+    pub fn trace(label: Category) -> Self {
+        Self { tree: SyntacticObject::trace(label) }
+    }
+
+    /// The object with its unchecked features set
+    pub fn features(mut self, features: &[Feature]) -> Self {
+        self.tree.features = features.to_vec();
+        self
+    }
+
+    /// The phrase with a child appended
+    pub fn child(mut self, child: impl Into<SyntacticObject>) -> Self {
+        self.tree.children.push(child.into());
+        self
+    }
+
+    /// The finished object
+    pub fn build(self) -> SyntacticObject {
+        self.tree
+    }
+}
+
+impl From<TreeBuilder> for SyntacticObject {
+    fn from(builder: TreeBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Build a `SyntacticObject` from a labeled bracketing
+///
+/// ```
+/// use atomic_lang_model::{tree, parse_sentence, test_lexicon};
+///
+/// let expected = tree!{ VP => [ DP => [ D "the", N "student" ], V "left" ] };
+/// let parsed = parse_sentence("the student left", &test_lexicon()).unwrap();
+/// assert_eq!(expected.bracketed(), parsed.bracketed());
+/// ```
+#[macro_export]
+macro_rules! tree {
+    (@children $builder:expr ;) => { $builder };
+    (@children $builder:expr ; $label:ident $([ $($feature:expr),* $(,)? ])? => [ $($inner:tt)* ] $(, $($rest:tt)*)?) => {
+        $crate::tree!(@children $builder.child($crate::tree!($label $([ $($feature),* ])? => [ $($inner)* ])) ; $($($rest)*)?)
+    };
+    (@children $builder:expr ; $label:ident t $(, $($rest:tt)*)?) => {
+        $crate::tree!(@children $builder.child($crate::tree!($label t)) ; $($($rest)*)?)
+    };
+    (@children $builder:expr ; $label:ident $phon:literal $([ $($feature:expr),* $(,)? ])? $(, $($rest:tt)*)?) => {
+        $crate::tree!(@children $builder.child($crate::tree!($label $phon $([ $($feature),* ])?)) ; $($($rest)*)?)
+    };
+    ($label:ident $([ $($feature:expr),* $(,)? ])? => [ $($children:tt)* ]) => {
+        $crate::tree!(@children $crate::builder::TreeBuilder::phrase($crate::Category::$label)
+            $(.features(&[$($feature),*]))? ; $($children)*).build()
+    };
+    ($label:ident t) => {
+        $crate::builder::TreeBuilder::trace($crate::Category::$label).build()
+    };
+    ($label:ident $phon:literal $([ $($feature:expr),* $(,)? ])?) => {
+        $crate::builder::TreeBuilder::leaf($crate::Category::$label, $phon)
+            $(.features(&[$($feature),*]))?.build()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, GrammarOptions};
+
+    #[test]
+    fn test_macro_matches_builder() {
+        let built = TreeBuilder::phrase(Category::DP)
+            .child(TreeBuilder::leaf(Category::D, "the").features(&[Feature::Cat(Category::D), Feature::Sel(Category::N)]))
+            .child(TreeBuilder::leaf(Category::N, "student"))
+            .build();
+        let written = tree!{ DP => [ D "the" [Feature::Cat(Category::D), Feature::Sel(Category::N)], N "student" ] };
+        assert_eq!(written, built);
+        assert_eq!(written.linearize(), "the student");
+    }
+
+    #[test]
+    fn test_expected_tree_with_trace_and_silent_head() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let parsed = parse_sentence("the student who left", &lexicon).unwrap();
+        let expected = tree!{
+            DP => [
+                D "the",
+                N => [
+                    N "student",
+                    CP => [ D "who", CP => [ C "", VP => [ D t, V "left" ] ] ],
+                ],
+            ]
+        };
+        assert_eq!(expected.bracketed(), parsed.bracketed());
+        assert!(tree!(D t).is_trace());
+    }
+}
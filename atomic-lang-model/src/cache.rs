@@ -0,0 +1,193 @@
+//! Derivation Caching
+//!
+//! Corpora repeat themselves: headlines, boilerplate, and the short
+//! sentences of child-directed speech recur many times, and each repeat
+//! costs a full derivation search. A `DerivationCache` remembers the
+//! outcome of the last `capacity` distinct sentences, least recently used
+//! evicted first. Sentences are keyed by the `Vocabulary` IDs of their
+//! normalized tokens, so spacing and case variants that normalize alike
+//! share an entry and keys stay small. A sentence with a word missing from
+//! the lexicon fails before any search and is not cached, so the cache
+//! holds no more than `capacity` keys whatever the input.
+//!
+//! A cache is only valid for the lexicon and options it was filled with;
+//! `clear` it after changing either.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::fmt;
+
+use crate::{parse_derivation, DerivationError, GrammarOptions, LexItem, Parse, Vocabulary};
+
+/// Cached outcome and when it was last used
+#[derive(Debug, Clone)]
+struct Entry {
+    result: Result<Parse, DerivationError>,
+    last_used: u64,
+}
+
+/// Cache effectiveness so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: usize,
+    /// Lookups that had to derive
+    pub misses: usize,
+    /// Entries dropped to make room
+    pub evictions: usize,
+    /// Entries currently held
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// Share of lookups answered from the cache, 0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} evictions, {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.evictions,
+            self.entries
+        )
+    }
+}
+
+/// Least-recently-used cache of parse outcomes keyed by token sequence
+#[derive(Debug, Clone, Default)]
+pub struct DerivationCache {
+    capacity: usize,
+    entries: BTreeMap<Vec<u32>, Entry>,
+    /// Keys by time of last use, oldest first
+    recency: BTreeMap<u64, Vec<u32>>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl DerivationCache {
+    /// Cache holding up to `capacity` sentences; zero caches nothing
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, ..Self::default() }
+    }
+
+    /// Parse `sentence` as `parse_derivation` does, reusing the outcome for
+    /// a sentence with the same normalized tokens
+    pub fn parse(&mut self, sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Parse, DerivationError> {
+        let Some(key) = Self::key(sentence, lexicon, options) else {
+            self.stats.misses += 1;
+            return parse_derivation(sentence, lexicon, options);
+        };
+        self.clock += 1;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            self.stats.hits += 1;
+            self.recency.remove(&entry.last_used);
+            entry.last_used = self.clock;
+            self.recency.insert(self.clock, key);
+            return entry.result.clone();
+        }
+
+        self.stats.misses += 1;
+        let result = parse_derivation(sentence, lexicon, options);
+        if self.capacity == 0 {
+            return result;
+        }
+        if self.entries.len() == self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+        self.entries.insert(key.clone(), Entry { result: result.clone(), last_used: self.clock });
+        self.recency.insert(self.clock, key);
+        self.stats.entries = self.entries.len();
+        result
+    }
+
+    /// Vocabulary IDs of a sentence's normalized tokens, or `None` if one
+    /// is missing from the lexicon
+    fn key(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Option<Vec<u32>> {
+        Vocabulary::new(lexicon).encode(&options.normalizer.apply(sentence, lexicon)).ok()
+    }
+
+    /// Check whether a sentence's outcome is cached, without counting a lookup
+    pub fn contains(&self, sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> bool {
+        Self::key(sentence, lexicon, options).is_some_and(|key| self.entries.contains_key(&key))
+    }
+
+    /// Cache effectiveness so far
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Drop every entry and reset the statistics, e.g. after a lexicon edit
+    pub fn clear(&mut self) {
+        *self = Self::new(self.capacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    #[test]
+    fn test_repeats_hit() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        let mut cache = DerivationCache::new(8);
+
+        let first = cache.parse("the student left", &lexicon, &options);
+        assert_eq!(cache.parse(" the  student left", &lexicon, &options), first);
+        assert!(cache.parse("the student the teacher", &lexicon, &options).is_err());
+        assert!(cache.parse("the student the teacher", &lexicon, &options).is_err());
+        assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 2, evictions: 0, entries: 2 });
+        assert_eq!(cache.stats().hit_rate(), 0.5);
+
+        // Unknown words fail without taking up an entry
+        assert!(cache.parse("the student zorped", &lexicon, &options).is_err());
+        assert!(!cache.contains("the student zorped", &lexicon, &options));
+        assert_eq!(cache.stats().entries, 2);
+
+        cache.clear();
+        assert_eq!(cache.stats(), CacheStats::default());
+        assert!(!cache.contains("the student left", &lexicon, &options));
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        let mut cache = DerivationCache::new(2);
+
+        cache.parse("the student left", &lexicon, &options).unwrap();
+        cache.parse("the tutor left", &lexicon, &options).unwrap();
+        // Touch the first so the second is the oldest
+        cache.parse("the student left", &lexicon, &options).unwrap();
+        cache.parse("the teacher left", &lexicon, &options).unwrap();
+
+        assert!(cache.contains("the student left", &lexicon, &options));
+        assert!(!cache.contains("the tutor left", &lexicon, &options));
+        assert!(cache.contains("the teacher left", &lexicon, &options));
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cache.stats().entries, 2);
+
+        let mut disabled = DerivationCache::new(0);
+        disabled.parse("the student left", &lexicon, &options).unwrap();
+        disabled.parse("the student left", &lexicon, &options).unwrap();
+        assert_eq!(disabled.stats().hits, 0);
+    }
+}
@@ -0,0 +1,359 @@
+//! Proof Certificates
+//!
+//! Executable evidence for where a pattern sits in the Chomsky hierarchy.
+//! A certificate is plain data: a search builds it, and `Certificate::verify`
+//! checks it using nothing but the pattern's recognizer. Trusting a claim
+//! therefore means trusting the small checker, not the search that found
+//! the argument.
+//!
+//! Pumping certificates refute the pumping lemma for one pumping length p.
+//! They name a member with at least p symbols and, for every admissible
+//! decomposition of it, an exponent that pumps it out of the language.
+//! Certificates exist for every p, and callers verify a range of them.
+//! Derivation certificates carry a convergent Minimalist Grammar tree over
+//! a fragment's lexicon whose yield is a member.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::fmt;
+
+use crate::patterns::RecursivePattern;
+use crate::{LexItem, SyntacticObject};
+
+/// Pumping lemma a certificate refutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lemma {
+    /// w = xyz with |xy| ≤ p and |y| ≥ 1; refuting it shows non-regularity
+    Regular,
+    /// w = uvxyz with |vxy| ≤ p and |vy| ≥ 1; refuting it shows the
+    /// language is not context-free
+    ContextFree,
+}
+
+impl Lemma {
+    /// Indices of the pieces that are repeated when pumping
+    fn pumped_pieces(self) -> &'static [usize] {
+        match self {
+            Lemma::Regular => &[1],
+            Lemma::ContextFree => &[1, 3],
+        }
+    }
+
+    /// Every admissible decomposition of a word of `len` symbols, as piece lengths
+    fn decompositions(self, len: usize, p: usize) -> Vec<Vec<usize>> {
+        let mut decompositions = Vec::new();
+        match self {
+            Lemma::Regular => {
+                for x in 0..p.min(len) {
+                    for y in 1..=(p - x).min(len - x) {
+                        decompositions.push(vec![x, y, len - x - y]);
+                    }
+                }
+            }
+            Lemma::ContextFree => {
+                for u in 0..len {
+                    let window = p.min(len - u);
+                    for v in 0..=window {
+                        for x in 0..=window - v {
+                            for y in (if v == 0 { 1 } else { 0 })..=window - v - x {
+                                decompositions.push(vec![u, v, x, y, len - u - v - x - y]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        decompositions
+    }
+
+    /// The word with its pumped pieces repeated `exponent` times
+    fn pump(self, symbols: &[&str], pieces: &[usize], exponent: usize) -> String {
+        let mut pumped: Vec<&str> = Vec::new();
+        let mut start = 0;
+        for (i, &len) in pieces.iter().enumerate() {
+            let piece = &symbols[start..start + len];
+            let times = if self.pumped_pieces().contains(&i) { exponent } else { 1 };
+            for _ in 0..times {
+                pumped.extend_from_slice(piece);
+            }
+            start += len;
+        }
+        pumped.join(" ")
+    }
+}
+
+impl fmt::Display for Lemma {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Lemma::Regular => "not regular",
+            Lemma::ContextFree => "not context-free",
+        })
+    }
+}
+
+/// One decomposition of the witness and the exponent that pumps it out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pump {
+    /// Piece lengths, three for `Lemma::Regular` and five for `Lemma::ContextFree`
+    pub pieces: Vec<usize>,
+    /// Times the pumped pieces are repeated
+    pub exponent: usize,
+}
+
+/// Refutation of a pumping lemma for one pumping length
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PumpingCertificate {
+    /// Lemma refuted
+    pub lemma: Lemma,
+    /// Pumping length refuted
+    pub pumping_length: usize,
+    /// Member with at least `pumping_length` symbols
+    pub witness: String,
+    /// One pump per admissible decomposition of the witness
+    pub pumps: Vec<Pump>,
+}
+
+/// Convergent derivation of a member in a grammar fragment
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationCertificate {
+    /// Lexicon of the fragment
+    pub lexicon: Vec<LexItem>,
+    /// Derived tree
+    pub tree: SyntacticObject,
+}
+
+/// Machine-checkable evidence about a pattern
+#[derive(Debug, Clone, PartialEq)]
+pub enum Certificate {
+    /// The pattern is not regular, or not context-free
+    Pumping {
+        /// Name of the pattern
+        pattern: &'static str,
+        /// The refutation
+        certificate: PumpingCertificate,
+    },
+    /// A Minimalist Grammar fragment derives members of the pattern
+    Derivation {
+        /// Name of the pattern
+        pattern: &'static str,
+        /// The derivation
+        certificate: DerivationCertificate,
+    },
+}
+
+/// Reason a certificate was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CertificateError {
+    /// The certificate is about another pattern
+    WrongPattern,
+    /// The witness or derived yield is not in the language
+    NotAMember,
+    /// The witness is shorter than the pumping length
+    WitnessTooShort,
+    /// A pump names a decomposition the lemma does not admit
+    InadmissibleDecomposition,
+    /// An admissible decomposition has no pump
+    MissingDecomposition,
+    /// Pumping leaves the word in the language
+    StillAMember,
+    /// The derived tree has unchecked features
+    NotConvergent,
+    /// A leaf of the derived tree is not an item of the fragment's lexicon
+    ForeignLeaf,
+}
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CertificateError::WrongPattern => "certificate is about another pattern",
+            CertificateError::NotAMember => "witness is not in the language",
+            CertificateError::WitnessTooShort => "witness is shorter than the pumping length",
+            CertificateError::InadmissibleDecomposition => "pump uses an inadmissible decomposition",
+            CertificateError::MissingDecomposition => "decomposition left unrefuted",
+            CertificateError::StillAMember => "pumped word is still in the language",
+            CertificateError::NotConvergent => "derivation did not converge",
+            CertificateError::ForeignLeaf => "derivation uses an item outside the lexicon",
+        })
+    }
+}
+
+impl Certificate {
+    /// Name of the pattern the certificate is about
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Certificate::Pumping { pattern, .. } | Certificate::Derivation { pattern, .. } => pattern,
+        }
+    }
+
+    /// Check the certificate against the pattern's recognizer
+    pub fn verify(&self, pattern: &dyn RecursivePattern) -> Result<(), CertificateError> {
+        if self.pattern() != pattern.name() {
+            return Err(CertificateError::WrongPattern);
+        }
+        match self {
+            Certificate::Pumping { certificate, .. } => verify_pumping(certificate, pattern),
+            Certificate::Derivation { certificate, .. } => verify_derivation(certificate, pattern),
+        }
+    }
+}
+
+impl fmt::Display for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Certificate::Pumping { pattern, certificate } => write!(
+                f,
+                "{}: {} (pumping length {}, {} decompositions refuted)",
+                pattern,
+                certificate.lemma,
+                certificate.pumping_length,
+                certificate.pumps.len()
+            ),
+            Certificate::Derivation { pattern, certificate } => {
+                write!(f, "{}: derived by a Minimalist Grammar ({})", pattern, certificate.tree.linearize())
+            }
+        }
+    }
+}
+
+fn verify_pumping(certificate: &PumpingCertificate, pattern: &dyn RecursivePattern) -> Result<(), CertificateError> {
+    let symbols: Vec<&str> = certificate.witness.split_whitespace().collect();
+    if !pattern.recognize(&certificate.witness) {
+        return Err(CertificateError::NotAMember);
+    }
+    if symbols.len() < certificate.pumping_length {
+        return Err(CertificateError::WitnessTooShort);
+    }
+
+    let admissible = certificate.lemma.decompositions(symbols.len(), certificate.pumping_length);
+    let mut exponents = BTreeMap::new();
+    for pump in &certificate.pumps {
+        if !admissible.contains(&pump.pieces) {
+            return Err(CertificateError::InadmissibleDecomposition);
+        }
+        exponents.insert(&pump.pieces, pump.exponent);
+    }
+
+    for pieces in &admissible {
+        let exponent = *exponents.get(pieces).ok_or(CertificateError::MissingDecomposition)?;
+        if exponent == 1 || pattern.recognize(&certificate.lemma.pump(&symbols, pieces, exponent)) {
+            return Err(CertificateError::StillAMember);
+        }
+    }
+    Ok(())
+}
+
+fn verify_derivation(
+    certificate: &DerivationCertificate,
+    pattern: &dyn RecursivePattern,
+) -> Result<(), CertificateError> {
+    fn leaves_in_lexicon(node: &SyntacticObject, lexicon: &[LexItem]) -> bool {
+        match &node.phon {
+            Some(phon) => lexicon.iter().any(|item| item.phon == *phon && item.feats == node.features),
+            None => node.children.iter().all(|child| leaves_in_lexicon(child, lexicon)),
+        }
+    }
+
+    let tree = &certificate.tree;
+    if !tree.is_complete() {
+        return Err(CertificateError::NotConvergent);
+    }
+    if !leaves_in_lexicon(tree, &certificate.lexicon) {
+        return Err(CertificateError::ForeignLeaf);
+    }
+    if !pattern.recognize(&tree.linearize()) {
+        return Err(CertificateError::NotAMember);
+    }
+    Ok(())
+}
+
+/// Search for a refutation of `lemma` at pumping length `p` using `witness`
+///
+/// Returns `None` if the witness is not a long enough member, or if some
+/// decomposition stays in the language for every exponent tried (0, 2, 3).
+pub fn certify_pumping(
+    pattern: &dyn RecursivePattern,
+    lemma: Lemma,
+    p: usize,
+    witness: String,
+) -> Option<Certificate> {
+    let symbols: Vec<&str> = witness.split_whitespace().collect();
+    if symbols.len() < p || !pattern.recognize(&witness) {
+        return None;
+    }
+
+    let pumps = lemma.decompositions(symbols.len(), p)
+        .into_iter()
+        .map(|pieces| {
+            let exponent = [0, 2, 3].into_iter()
+                .find(|&exponent| !pattern.recognize(&lemma.pump(&symbols, &pieces, exponent)))?;
+            Some(Pump { pieces, exponent })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let certificate = PumpingCertificate { lemma, pumping_length: p, witness, pumps };
+    Some(Certificate::Pumping { pattern: pattern.name(), certificate })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{AnBn, AnBnCn, ComplexityClass, Dyck, PatternRegistry};
+
+    #[test]
+    fn test_builtin_certificates_verify() {
+        for pattern in PatternRegistry::new().iter() {
+            for p in 1..=5 {
+                let certificates = pattern.certificates(p);
+                assert!(!certificates.is_empty(), "{} p={}", pattern.name(), p);
+                for certificate in &certificates {
+                    assert_eq!(certificate.verify(pattern), Ok(()), "{}", certificate);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_certificates_match_complexity_class() {
+        let registry = PatternRegistry::new();
+        for pattern in registry.iter() {
+            let lemmas: Vec<Lemma> = pattern.certificates(4)
+                .into_iter()
+                .filter_map(|certificate| match certificate {
+                    Certificate::Pumping { certificate, .. } => Some(certificate.lemma),
+                    Certificate::Derivation { .. } => None,
+                })
+                .collect();
+            let expected = match pattern.complexity_class() {
+                ComplexityClass::ContextFree => Lemma::Regular,
+                _ => Lemma::ContextFree,
+            };
+            assert_eq!(lemmas, [expected], "{}", pattern.name());
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_certificates() {
+        // aⁿbⁿ is context-free: pumping a's and b's together stays inside
+        assert!(certify_pumping(&AnBn::default(), Lemma::ContextFree, 3, AnBn::default().generate(3)).is_none());
+        // Dyck words (ab)ⁿ pump within the language
+        assert!(certify_pumping(&Dyck::default(), Lemma::Regular, 2, "a b a b".into()).is_none());
+
+        let mut certificate = certify_pumping(&AnBn::default(), Lemma::Regular, 3, AnBn::default().generate(3)).unwrap();
+        assert_eq!(certificate.verify(&AnBnCn::default()), Err(CertificateError::WrongPattern));
+        if let Certificate::Pumping { certificate, .. } = &mut certificate {
+            certificate.pumps.pop();
+        }
+        assert_eq!(certificate.verify(&AnBn::default()), Err(CertificateError::MissingDecomposition));
+        if let Certificate::Pumping { certificate, .. } = &mut certificate {
+            certificate.pumps[0].exponent = 1;
+        }
+        assert_eq!(certificate.verify(&AnBn::default()), Err(CertificateError::StillAMember));
+        if let Certificate::Pumping { certificate, .. } = &mut certificate {
+            certificate.witness = "a a b".into();
+        }
+        assert_eq!(certificate.verify(&AnBn::default()), Err(CertificateError::NotAMember));
+    }
+}
@@ -0,0 +1,216 @@
+//! Movement Chain Evaluation
+//!
+//! Scores the movement chains the parser reconstructs against gold trees
+//! whose traces are coindexed with their antecedents, as read by
+//! `parse_indexed_treebank`:
+//!
+//! ```text
+//! (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left)))))
+//! ```
+//!
+//! Treebank labels and derived-tree labels do not line up, so a chain is
+//! compared by where it sits in the sentence: the words of the moved phrase
+//! (its landing site) and the number of words before its trace (where it
+//! was attached before moving). A parsed chain is correct when it matches a
+//! gold chain at both sites; each gold chain is matched at most once.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::constraints::{chains, node_at};
+use crate::treebank::PtbTree;
+use crate::{parse_with_options, GrammarOptions, LexItem, SyntacticObject};
+
+/// Movement chain located by word positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SurfaceChain {
+    /// Word span of the moved phrase, end exclusive
+    pub landing: (usize, usize),
+    /// Words before the trace
+    pub attachment: usize,
+}
+
+/// Chain precision and recall over a set of sentences
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChainScore {
+    /// Chains in the gold trees
+    pub gold: usize,
+    /// Chains in the parses
+    pub predicted: usize,
+    /// Parsed chains matching a gold chain at both sites
+    pub correct: usize,
+    /// Parsed chains whose landing site matches a gold chain's
+    pub landings: usize,
+    /// Parsed chains whose attachment site matches a gold chain's
+    pub attachments: usize,
+    /// Gold sentences the grammar could not parse
+    pub unparsed: usize,
+}
+
+impl ChainScore {
+    /// Share of parsed chains that are correct (1 when none were parsed)
+    pub fn precision(&self) -> f64 {
+        ratio(self.correct, self.predicted)
+    }
+
+    /// Share of gold chains recovered (1 when there were none)
+    pub fn recall(&self) -> f64 {
+        ratio(self.correct, self.gold)
+    }
+
+    /// Harmonic mean of precision and recall
+    pub fn f1(&self) -> f64 {
+        let (p, r) = (self.precision(), self.recall());
+        if p + r == 0.0 { 0.0 } else { 2.0 * p * r / (p + r) }
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 1.0 } else { part as f64 / whole as f64 }
+}
+
+impl core::ops::AddAssign for ChainScore {
+    fn add_assign(&mut self, other: Self) {
+        self.gold += other.gold;
+        self.predicted += other.predicted;
+        self.correct += other.correct;
+        self.landings += other.landings;
+        self.attachments += other.attachments;
+        self.unparsed += other.unparsed;
+    }
+}
+
+/// Overt words of a derived constituent
+fn word_count(node: &SyntacticObject) -> usize {
+    match &node.phon {
+        Some(phon) if node.children.is_empty() => phon.split_whitespace().count(),
+        _ => node.children.iter().map(word_count).sum(),
+    }
+}
+
+/// Words before the node at `path`
+fn words_before(tree: &SyntacticObject, path: &[usize]) -> usize {
+    let mut before = 0;
+    let mut node = tree;
+    for &i in path {
+        before += node.children[..i].iter().map(word_count).sum::<usize>();
+        node = &node.children[i];
+    }
+    before
+}
+
+/// Movement chains of a derived tree
+pub fn derived_chains(tree: &SyntacticObject) -> Vec<SurfaceChain> {
+    chains(tree).iter()
+        .map(|chain| {
+            // The moved phrase is the landing node's first child
+            let mut mover = chain.landing.clone();
+            mover.push(0);
+            let start = words_before(tree, &mover);
+            SurfaceChain {
+                landing: (start, start + word_count(node_at(tree, &mover))),
+                attachment: words_before(tree, &chain.trace),
+            }
+        })
+        .collect()
+}
+
+/// Movement chains of a gold tree: each coindexed empty element (`*T*-1`,
+/// `*-1`) with the overt constituent bearing its index
+pub fn gold_chains(tree: &PtbTree) -> Vec<SurfaceChain> {
+    // (index, span) of antecedents and (index, words before) of traces
+    fn collect(node: &PtbTree, before: &mut usize, antecedents: &mut Vec<(usize, (usize, usize))>, traces: &mut Vec<(usize, usize)>) {
+        match node {
+            PtbTree::Leaf(_, word) if node.is_empty_element() => {
+                if let (Some(index), true) = (node.index(), word.starts_with('*')) {
+                    traces.push((index, *before));
+                }
+            }
+            PtbTree::Leaf(..) => *before += 1,
+            PtbTree::Node(_, children) => {
+                let start = *before;
+                children.iter().for_each(|child| collect(child, before, antecedents, traces));
+                if let Some(index) = node.index().filter(|_| *before > start) {
+                    antecedents.push((index, (start, *before)));
+                }
+            }
+        }
+    }
+
+    let (mut antecedents, mut traces) = (Vec::new(), Vec::new());
+    collect(tree, &mut 0, &mut antecedents, &mut traces);
+    traces.iter()
+        .filter_map(|&(index, attachment)| {
+            let &(_, landing) = antecedents.iter().find(|(antecedent, _)| *antecedent == index)?;
+            Some(SurfaceChain { landing, attachment })
+        })
+        .collect()
+}
+
+/// Score parsed chains against gold chains for one sentence
+pub fn score_chains(gold: &[SurfaceChain], predicted: &[SurfaceChain]) -> ChainScore {
+    let mut unmatched = gold.to_vec();
+    let mut score = ChainScore { gold: gold.len(), predicted: predicted.len(), ..ChainScore::default() };
+    for chain in predicted {
+        score.landings += gold.iter().any(|g| g.landing == chain.landing) as usize;
+        score.attachments += gold.iter().any(|g| g.attachment == chain.attachment) as usize;
+        if let Some(i) = unmatched.iter().position(|g| g == chain) {
+            unmatched.swap_remove(i);
+            score.correct += 1;
+        }
+    }
+    score
+}
+
+/// Parse the words of every gold tree and score the chains of the first
+/// analysis found; a sentence that fails to parse contributes only its
+/// gold chains
+pub fn evaluate_chains(gold: &[PtbTree], lexicon: &[LexItem], options: &GrammarOptions) -> ChainScore {
+    let mut total = ChainScore::default();
+    for tree in gold {
+        let expected = gold_chains(tree);
+        let sentence: String = tree.words().join(" ");
+        total += match parse_with_options(&sentence, lexicon, options) {
+            Ok(parse) => score_chains(&expected, &derived_chains(&parse)),
+            Err(_) => ChainScore { gold: expected.len(), unparsed: 1, ..ChainScore::default() },
+        };
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_indexed_treebank, parse_sentence, RelativeClauseAnalysis};
+
+    const GOLD: &str = "
+        (S (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left))))) (VP (VBD smiled)))
+        (NP (NP (DT the) (NN student)) (SBAR (WHNP-1 (WP who)) (S (NP-SBJ (DT the) (NN teacher)) (VP (VBZ thinks) (NP (-NONE- *T*-1))))))
+    ";
+
+    #[test]
+    fn test_gold_and_derived_chains_line_up() {
+        let trees = parse_indexed_treebank(GOLD).unwrap();
+        let subject_gap = SurfaceChain { landing: (2, 3), attachment: 3 };
+        assert_eq!(gold_chains(&trees[0]), [subject_gap]);
+        assert_eq!(gold_chains(&trees[1]), [SurfaceChain { landing: (2, 3), attachment: 6 }]);
+
+        let lexicon = GrammarOptions::default().lexicon();
+        let tree = parse_sentence("the student who left smiled", &lexicon).unwrap();
+        assert_eq!(derived_chains(&tree), [subject_gap]);
+
+        let score = evaluate_chains(&trees, &lexicon, &GrammarOptions::default());
+        assert_eq!((score.gold, score.predicted, score.correct, score.unparsed), (2, 2, 2, 0));
+        assert_eq!(score.f1(), 1.0);
+    }
+
+    #[test]
+    fn test_promotion_adds_a_chain() {
+        // Raising the head noun is a second chain the gold trees do not have
+        let options = GrammarOptions { relative_clauses: RelativeClauseAnalysis::Promotion, ..Default::default() };
+        let score = evaluate_chains(&parse_indexed_treebank(GOLD).unwrap(), &options.lexicon(), &options);
+        assert_eq!(score.gold, 2);
+        assert!(score.predicted > score.gold);
+        assert!(score.precision() < 1.0);
+    }
+}
@@ -0,0 +1,445 @@
+//! Interpreter for compiled blobs
+//!
+//! Uses nothing from the rest of the crate and never allocates, so a
+//! freestanding build can include this file alone: `alm-tiny` does, and
+//! the size budget measures the recognizer without the library around it.
+
+use core::fmt;
+
+/// Magic bytes and format version at the start of a blob
+pub const MAGIC: [u8; 4] = *b"ALM\x01";
+
+/// Features an item (or a mover's licensees) can carry
+pub const MAX_FEATURES: usize = 8;
+
+/// Pending movers an item can contain
+pub const MAX_MOVERS: usize = 2;
+
+/// Longest sentence the interpreter accepts, in tokens
+pub const MAX_TOKENS: usize = 254;
+
+pub(super) const CAT: u8 = 0;
+pub(super) const SEL: u8 = 1;
+pub(super) const SPEC: u8 = 2;
+pub(super) const ADJOIN: u8 = 3;
+pub(super) const POS: u8 = 4;
+pub(super) const NEG: u8 = 5;
+pub(super) const AGR: u8 = 6;
+
+/// Kind of an encoded feature
+fn kind(feature: u8) -> u8 {
+    (feature >> 5).min(AGR)
+}
+
+/// Check whether bundles encoded as `AgrBundle::bits` agree
+fn agrees(a: u8, b: u8) -> bool {
+    [4, 2, 0].iter().all(|shift| {
+        let (x, y) = (a >> shift & 3, b >> shift & 3);
+        x == 0 || y == 0 || x == y
+    })
+}
+
+/// Why the interpreter could not decide a sentence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecognizeError {
+    /// The blob is not a compiled lexicon
+    Malformed,
+    /// The sentence has more than `MAX_TOKENS` tokens
+    TooLong,
+    /// The chart outgrew its capacity, or an item its feature or mover
+    /// limits, before the sentence was recognized
+    CapacityExceeded,
+}
+
+impl fmt::Display for RecognizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecognizeError::Malformed => write!(f, "not a compiled lexicon"),
+            RecognizeError::TooLong => write!(f, "sentence longer than {} tokens", MAX_TOKENS),
+            RecognizeError::CapacityExceeded => write!(f, "recognizer capacity exceeded"),
+        }
+    }
+}
+
+/// Entries of a well-formed blob as (pronunciation, features)
+fn entries(blob: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let count = u16::from_le_bytes([blob[4], blob[5]]);
+    let mut at = 6;
+    (0..count).map(move |_| {
+        let phon = &blob[at + 1..at + 1 + blob[at] as usize];
+        at += 1 + phon.len();
+        let features = &blob[at + 1..at + 1 + blob[at] as usize];
+        at += 1 + features.len();
+        (phon, features)
+    })
+}
+
+fn validate(blob: &[u8]) -> Result<(), RecognizeError> {
+    if blob.len() < 6 || blob[..4] != MAGIC {
+        return Err(RecognizeError::Malformed);
+    }
+    let mut at = 6;
+    for _ in 0..u16::from_le_bytes([blob[4], blob[5]]) {
+        let phon = *blob.get(at).ok_or(RecognizeError::Malformed)? as usize;
+        at += 1 + phon;
+        let features = *blob.get(at).ok_or(RecognizeError::Malformed)? as usize;
+        if features > MAX_FEATURES {
+            return Err(RecognizeError::Malformed);
+        }
+        at += 1 + features;
+    }
+    if at == blob.len() { Ok(()) } else { Err(RecognizeError::Malformed) }
+}
+
+/// Up to `MAX_FEATURES` encoded features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Bundle {
+    bytes: [u8; MAX_FEATURES],
+    len: u8,
+}
+
+impl Bundle {
+    const EMPTY: Bundle = Bundle { bytes: [0; MAX_FEATURES], len: 0 };
+
+    fn from(features: &[u8]) -> Self {
+        let mut bundle = Self::EMPTY;
+        bundle.bytes[..features.len()].copy_from_slice(features);
+        bundle.len = features.len() as u8;
+        bundle
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    fn push(&mut self, feature: u8) -> Option<()> {
+        *self.bytes.get_mut(self.len as usize)? = feature;
+        self.len += 1;
+        Some(())
+    }
+
+    fn remove_first(&mut self, feature: u8) {
+        if let Some(pos) = self.as_slice().iter().position(|&f| f == feature) {
+            self.bytes.copy_within(pos + 1..self.len as usize, pos);
+            self.len -= 1;
+        }
+    }
+
+    fn first(&self, kinds: &[u8]) -> Option<u8> {
+        self.as_slice().iter().copied().find(|&f| kinds.contains(&kind(f)))
+    }
+
+    fn has(&self, kind: u8) -> bool {
+        self.first(&[kind]).is_some()
+    }
+
+    fn selector(&self) -> Option<usize> {
+        self.as_slice().iter().position(|&f| matches!(kind(f), SEL | SPEC))
+    }
+
+    /// Agreement probe of the first selector, as `agreement::probe` finds it
+    fn probe(&self) -> Option<u8> {
+        let next = *self.as_slice().get(self.selector()? + 1)?;
+        (kind(next) == AGR).then_some(next & 0x3f)
+    }
+
+    /// The bundle's own agreement, as `agreement::agreement` finds it
+    fn agreement(&self) -> Option<u8> {
+        let features = self.as_slice();
+        (0..features.len())
+            .find(|&i| kind(features[i]) == AGR && (i == 0 || !matches!(kind(features[i - 1]), SEL | SPEC)))
+            .map(|i| features[i] & 0x3f)
+    }
+
+    /// Drop the first selector and its probe
+    fn remove_selector(&mut self) -> Option<()> {
+        let at = self.selector()?;
+        let width = 1 + self.probe().is_some() as usize;
+        self.bytes.copy_within(at + width..self.len as usize, at);
+        self.len -= width as u8;
+        Some(())
+    }
+
+    /// The features of `other` a merge or adjunction passes on, agreement
+    /// placed as `pass_features` places it
+    fn extend_passed(&mut self, other: &Bundle) -> Option<()> {
+        let mut at = self.as_slice().iter().position(|&f| kind(f) != CAT && kind(f) != AGR).unwrap_or(self.len as usize);
+        for &f in other.as_slice().iter().filter(|&&f| kind(f) != CAT && kind(f) != ADJOIN) {
+            self.push(f)?;
+            if kind(f) == AGR {
+                self.bytes[at..self.len as usize].rotate_right(1);
+                at += 1;
+            }
+        }
+        Some(())
+    }
+}
+
+/// Token positions `[start, end)`; `SILENT` for none
+type Span = (u8, u8);
+
+const SILENT: Span = (u8::MAX, u8::MAX);
+
+fn concatenate(left: Span, right: Span) -> Option<Span> {
+    match (left == SILENT, right == SILENT) {
+        (true, _) => Some(right),
+        (_, true) => Some(left),
+        _ if left.1 == right.0 => Some((left.0, right.1)),
+        _ => None,
+    }
+}
+
+/// Constituent set aside until its licensees are checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Mover {
+    licensees: Bundle,
+    span: Span,
+}
+
+/// Constituent description, `parser::Chart`'s item without label or steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Item {
+    features: Bundle,
+    span: Span,
+    movers: [Mover; MAX_MOVERS],
+    mover_count: u8,
+}
+
+impl Item {
+    const EMPTY: Item = Item {
+        features: Bundle::EMPTY,
+        span: SILENT,
+        movers: [Mover { licensees: Bundle::EMPTY, span: SILENT }; MAX_MOVERS],
+        mover_count: 0,
+    };
+
+    fn movers(&self) -> &[Mover] {
+        &self.movers[..self.mover_count as usize]
+    }
+
+    fn add_mover(&mut self, mover: Mover) -> Option<()> {
+        *self.movers.get_mut(self.mover_count as usize)? = mover;
+        self.mover_count += 1;
+        // Keep movers in span order so equal items compare equal
+        self.movers[..self.mover_count as usize].sort_unstable_by_key(|mover| mover.span);
+        Some(())
+    }
+
+    fn own_licensees(&self) -> Bundle {
+        let mut own = Bundle::EMPTY;
+        for &f in self.features.as_slice().iter().filter(|&&f| kind(f) == NEG) {
+            own.push(f);
+        }
+        for mover in self.movers() {
+            for &f in mover.licensees.as_slice() {
+                own.remove_first(f);
+            }
+        }
+        own
+    }
+
+    fn covers(&self) -> impl Iterator<Item = Span> + '_ {
+        core::iter::once(self.span).chain(self.movers().iter().map(|mover| mover.span)).filter(|&span| span != SILENT)
+    }
+
+    fn complete(&self) -> bool {
+        self.features.as_slice().iter().all(|&f| kind(f) == CAT || kind(f) == AGR)
+    }
+}
+
+/// `a` can select `b`, as `can_merge` decides
+fn selects(a: &Bundle, b: &Bundle) -> bool {
+    let Some(selector) = a.first(&[SEL, SPEC]) else {
+        return false;
+    };
+    !b.has(SEL) && !b.has(SPEC) && !b.has(POS)
+        && b.first(&[CAT]).is_some_and(|cat| cat & 31 == selector & 31)
+        && a.probe().zip(b.agreement()).is_none_or(|(probe, goal)| agrees(probe, goal))
+        && smc(a, b)
+}
+
+/// `adjunct` can adjoin to `host`, as `can_adjoin` decides
+fn hosts(host: &Bundle, adjunct: &Bundle) -> bool {
+    if host.has(SEL) || host.has(SPEC) || adjunct.has(SEL) || adjunct.has(SPEC) {
+        return false;
+    }
+    host.first(&[CAT]).is_some_and(|cat| {
+        adjunct.as_slice().iter().any(|&f| kind(f) == ADJOIN && f & 31 == cat & 31)
+    }) && smc(host, adjunct)
+}
+
+/// Shortest Move Constraint
+fn smc(a: &Bundle, b: &Bundle) -> bool {
+    !b.as_slice().iter().any(|&f| kind(f) == NEG && a.as_slice().contains(&f))
+}
+
+struct Chart<'b, const CAPACITY: usize> {
+    blob: &'b [u8],
+    items: [Item; CAPACITY],
+    len: usize,
+    overflow: bool,
+}
+
+impl<const CAPACITY: usize> Chart<'_, CAPACITY> {
+    fn add(&mut self, item: Option<Item>) {
+        let Some(item) = item else {
+            self.overflow = true;
+            return;
+        };
+        if self.items[..self.len].contains(&item) {
+            return;
+        }
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                *slot = item;
+                self.len += 1;
+            }
+            None => self.overflow = true,
+        }
+    }
+
+    /// Add the result of combining `a` and `b` into `features`, `b` on the
+    /// left if `left`, or merged in place as a mover if it has licensees of
+    /// its own; `None` features mean the bundle overflowed
+    fn attach(&mut self, a: &Item, b: &Item, left: bool, features: Option<Bundle>) {
+        let Some(features) = features else {
+            return self.add(None);
+        };
+        let mut item = Item { features, ..*a };
+        for &mover in b.movers() {
+            if item.add_mover(mover).is_none() {
+                return self.add(None);
+            }
+        }
+        let own = b.own_licensees();
+        if own.len > 0 {
+            let mover = item.add_mover(Mover { licensees: own, span: b.span });
+            return self.add(mover.map(|()| item));
+        }
+        let joined = if left { concatenate(b.span, a.span) } else { concatenate(a.span, b.span) };
+        if let Some(span) = joined {
+            self.add(Some(Item { span, ..item }));
+        }
+    }
+
+    /// Features of `a` merged with `b`, as `merge` computes them
+    fn merged(a: &Bundle, b: &Bundle) -> Option<Bundle> {
+        let mut features = *a;
+        features.remove_selector()?;
+        features.extend_passed(b)?;
+        Some(features)
+    }
+
+    /// Merge and adjoin `a` (selector or host) with `b`
+    fn combine(&mut self, a: Item, b: Item) {
+        let overlap = a.covers().any(|(start, end)| {
+            b.covers().any(|(other_start, other_end)| start < other_end && other_start < end)
+        });
+        if overlap {
+            return;
+        }
+        if selects(&a.features, &b.features) {
+            let specifier = a.features.first(&[SEL, SPEC]).is_some_and(|f| kind(f) == SPEC);
+            self.attach(&a, &b, specifier, Self::merged(&a.features, &b.features));
+        }
+        if hosts(&a.features, &b.features) {
+            let mut features = a.features;
+            let features = features.extend_passed(&b.features).map(|()| features);
+            self.attach(&a, &b, false, features);
+        }
+    }
+
+    /// Merge the silent head with features `head` with `x`
+    fn merge_empty(&mut self, head: &[u8], x: Item) {
+        let head = Item { features: Bundle::from(head), ..Item::EMPTY };
+        if selects(&head.features, &x.features) {
+            self.attach(&head, &x, false, Self::merged(&head.features, &x.features));
+        }
+    }
+
+    /// Move the mover `x`'s first positive feature attracts
+    fn move_within(&mut self, x: Item) {
+        let Some(trigger) = x.features.first(&[POS]) else {
+            return;
+        };
+        let licensee = NEG << 5 | (trigger & 31);
+        let Some(m) = x.movers().iter().position(|mover| mover.licensees.as_slice().contains(&licensee)) else {
+            return;
+        };
+
+        let mut item = Item { mover_count: 0, ..x };
+        let mut mover = x.movers()[m];
+        for (i, &other) in x.movers().iter().enumerate() {
+            if i != m {
+                item.add_mover(other);
+            }
+        }
+        for &f in mover.licensees.as_slice() {
+            item.features.remove_first(f);
+        }
+        item.features.remove_first(trigger);
+        mover.licensees.remove_first(licensee);
+        if item.features.extend_passed(&mover.licensees).is_none() {
+            self.add(None);
+            return;
+        }
+
+        // A mover with licensees left stays set aside; otherwise it lands at the left edge
+        if mover.licensees.len > 0 {
+            item.add_mover(mover);
+            self.add(Some(item));
+        } else if let Some(span) = concatenate(mover.span, x.span) {
+            self.add(Some(Item { span, ..item }));
+        }
+    }
+}
+
+/// Decide whether `sentence` is in the language of the lexicon compiled
+/// into `blob`, with a chart of at most `CAPACITY` items
+///
+/// A word missing from the lexicon makes the sentence unrecognized rather
+/// than an error.
+pub fn recognize<const CAPACITY: usize>(blob: &[u8], sentence: &str) -> Result<bool, RecognizeError> {
+    validate(blob)?;
+    let mut chart: Chart<CAPACITY> = Chart { blob, items: [Item::EMPTY; CAPACITY], len: 0, overflow: false };
+
+    let mut length = 0;
+    for (position, token) in sentence.split_whitespace().enumerate() {
+        if position >= MAX_TOKENS {
+            return Err(RecognizeError::TooLong);
+        }
+        let span = (position as u8, position as u8 + 1);
+        let mut known = false;
+        for (_, features) in entries(blob).filter(|(phon, _)| *phon == token.as_bytes()) {
+            known = true;
+            chart.add(Some(Item { features: Bundle::from(features), span, ..Item::EMPTY }));
+        }
+        if !known {
+            return Ok(false);
+        }
+        length = position + 1;
+    }
+    if length == 0 {
+        return Ok(false);
+    }
+
+    let goal = |item: &Item| item.span == (0, length as u8) && item.mover_count == 0 && item.complete();
+    let mut next = 0;
+    while next < chart.len {
+        let x = chart.items[next];
+        next += 1;
+        for (_, head) in entries(chart.blob).filter(|(phon, _)| phon.is_empty()) {
+            chart.merge_empty(head, x);
+        }
+        chart.move_within(x);
+        for y in 0..next - 1 {
+            let other = chart.items[y];
+            chart.combine(x, other);
+            chart.combine(other, x);
+        }
+        if chart.items[..chart.len].iter().any(goal) {
+            return Ok(true);
+        }
+    }
+    if chart.overflow { Err(RecognizeError::CapacityExceeded) } else { Ok(false) }
+}
@@ -0,0 +1,155 @@
+//! Compiled Recognizer
+//!
+//! For recognition-only embedded use, a fixed lexicon is compiled ahead of
+//! time into a compact byte blob, and a small interpreter decides whether a
+//! sentence is in the language without building trees, allocating, or
+//! linking the rest of the crate. The interpreter runs the deduction of
+//! `parser::Chart` over feature bundles alone: an item is a bundle, the span
+//! of its pronounced material and the spans of its pending movers, stored
+//! in a fixed array of `CAPACITY` items on the stack, so its memory use is
+//! known at compile time. Grammar constraints (`that_trace`, `subjacency`,
+//! `superiority`, `ecp`) filter trees and are not applied.
+//!
+//! Each feature is one byte, its kind in the top three bits and its
+//! category (by head) or movement index in the low five; an agreement
+//! feature is `11` followed by `AgrBundle::bits`:
+//!
+//! ```text
+//! "ALM" 1                      magic and format version
+//! count: u16 (little-endian)   number of entries
+//! per entry:
+//!   len: u8, phon: [u8; len]   pronunciation, empty for a silent head
+//!   n: u8, features: [u8; n]   feature bundle
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::{Category, Feature, LexItem};
+
+mod interpreter;
+
+pub use interpreter::{recognize, RecognizeError, MAGIC, MAX_FEATURES, MAX_MOVERS, MAX_TOKENS};
+
+use interpreter::{ADJOIN, AGR, CAT, NEG, POS, SEL, SPEC};
+
+/// Why a lexicon cannot be compiled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileError {
+    /// More entries than the format's 16-bit count
+    TooManyEntries,
+    /// Entry whose pronunciation is longer than 255 bytes
+    LongPhon(usize),
+    /// Entry with more than `MAX_FEATURES` features
+    LongBundle(usize),
+    /// Entry with a movement index above 31
+    MovementIndex(usize),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompileError::TooManyEntries => write!(f, "more than {} entries", u16::MAX),
+            CompileError::LongPhon(i) => write!(f, "entry {} has a pronunciation over 255 bytes", i),
+            CompileError::LongBundle(i) => write!(f, "entry {} has more than {} features", i, MAX_FEATURES),
+            CompileError::MovementIndex(i) => write!(f, "entry {} has a movement index above 31", i),
+        }
+    }
+}
+
+fn category_code(category: &Category) -> u8 {
+    match category.head() {
+        Category::N => 0,
+        Category::V => 1,
+        Category::D => 2,
+        Category::C => 3,
+        Category::P => 4,
+        _ => 5,
+    }
+}
+
+/// Compile `lexicon` into a blob for `recognize`
+pub fn compile(lexicon: &[LexItem]) -> Result<Vec<u8>, CompileError> {
+    let count = u16::try_from(lexicon.len()).map_err(|_| CompileError::TooManyEntries)?;
+    let mut blob = Vec::from(MAGIC);
+    blob.extend_from_slice(&count.to_le_bytes());
+    for (i, item) in lexicon.iter().enumerate() {
+        let phon = u8::try_from(item.phon.len()).map_err(|_| CompileError::LongPhon(i))?;
+        if item.feats.len() > MAX_FEATURES {
+            return Err(CompileError::LongBundle(i));
+        }
+        blob.push(phon);
+        blob.extend_from_slice(item.phon.as_bytes());
+        blob.push(item.feats.len() as u8);
+        for feature in &item.feats {
+            let (kind, value) = match feature {
+                Feature::Cat(cat) => (CAT, category_code(cat)),
+                Feature::Sel(cat) => (SEL, category_code(cat)),
+                Feature::Spec(cat) => (SPEC, category_code(cat)),
+                Feature::Adjoin(cat) => (ADJOIN, category_code(cat)),
+                Feature::Pos(index) => (POS, *index),
+                Feature::Neg(index) => (NEG, *index),
+                Feature::Agr(bundle) => {
+                    blob.push(AGR << 5 | bundle.bits());
+                    continue;
+                }
+            };
+            if value > 31 {
+                return Err(CompileError::MovementIndex(i));
+            }
+            blob.push(kind << 5 | value);
+        }
+    }
+    Ok(blob)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, Chart, GrammarOptions, RelativeClauseAnalysis};
+
+    #[test]
+    fn test_agrees_with_chart() {
+        for analysis in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let lexicon = GrammarOptions { relative_clauses: analysis, ..GrammarOptions::default() }.lexicon();
+            let blob = compile(&lexicon).unwrap();
+            for sentence in [
+                "the student left",
+                "the student thinks the teacher",
+                "the student who left smiled",
+                "the student who the teacher thinks left smiled",
+                "who the student left",
+                "the student the teacher",
+                "left the student",
+                "student",
+            ] {
+                let tokens: Vec<&str> = sentence.split_whitespace().collect();
+                let expected = Chart::build(&tokens, &lexicon, true).unwrap().recognizes();
+                assert_eq!(recognize::<512>(&blob, sentence), Ok(expected), "{}", sentence);
+            }
+        }
+
+        let blob = compile(&test_lexicon()).unwrap();
+        assert_eq!(recognize::<64>(&blob, "the student left"), Ok(parse_sentence("the student left", &test_lexicon()).is_ok()));
+        assert_eq!(recognize::<64>(&blob, "the zorp left"), Ok(false));
+        assert_eq!(recognize::<64>(&blob, ""), Ok(false));
+    }
+
+    #[test]
+    fn test_blob_and_limits() {
+        let lexicon = test_lexicon();
+        let blob = compile(&lexicon).unwrap();
+        let size: usize = lexicon.iter().map(|item| 2 + item.phon.len() + item.feats.len()).sum();
+        assert_eq!(blob.len(), 6 + size);
+
+        assert_eq!(recognize::<64>(b"nope", "the student left"), Err(RecognizeError::Malformed));
+        assert_eq!(recognize::<64>(&blob[..blob.len() - 1], "the student left"), Err(RecognizeError::Malformed));
+        assert_eq!(recognize::<2>(&blob, "the student thinks the teacher"), Err(RecognizeError::CapacityExceeded));
+
+        let mut bad = lexicon;
+        bad[0].feats.push(Feature::Pos(40));
+        assert_eq!(compile(&bad), Err(CompileError::MovementIndex(0)));
+    }
+}
@@ -0,0 +1,249 @@
+//! Empirical Complexity Estimation
+//!
+//! Fits measured growth to a power law y = c·nᵏ by least squares in log-log
+//! space and reports the exponent k with a 95% confidence interval. The
+//! general entry points take any measurement function, so external
+//! benchmarks can reuse the fit; `estimate_parse_complexity` and
+//! `estimate_generation_complexity` measure the engine itself.
+//!
+//! Requires the `std` feature (clocks and floating-point logarithms).
+
+use core::fmt;
+use std::time::{Duration, Instant};
+
+use crate::{generate_an_bn, parse_sentence, Category, Feature, LexItem, Workspace};
+
+/// Calls per `n` when timing; the median is kept
+pub const REPETITIONS: usize = 3;
+
+/// Two-sided 95% critical values of Student's t for 1–30 degrees of freedom
+const T_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228,
+    2.201, 2.179, 2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086,
+    2.080, 2.074, 2.069, 2.064, 2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+];
+
+/// Fitted power law y = c·nᵏ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComplexityEstimate {
+    /// Fitted exponent k
+    pub exponent: f64,
+    /// Fitted coefficient c
+    pub coefficient: f64,
+    /// 95% confidence interval for the exponent
+    pub confidence: (f64, f64),
+    /// Goodness of the log-log fit
+    pub r_squared: f64,
+    /// Measurements the fit used
+    pub samples: usize,
+}
+
+impl ComplexityEstimate {
+    /// Check if growth is bounded by nᵈ, i.e. the whole confidence
+    /// interval lies at or below `degree`
+    pub fn is_bounded_by(&self, degree: f64) -> bool {
+        self.confidence.1 <= degree
+    }
+}
+
+impl fmt::Display for ComplexityEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "n^{:.2} (95% CI {:.2}–{:.2}, r² = {:.3}, {} samples)",
+            self.exponent, self.confidence.0, self.confidence.1, self.r_squared, self.samples
+        )
+    }
+}
+
+/// Time and memory growth of one workload
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthEstimate {
+    /// Runtime growth
+    pub time: ComplexityEstimate,
+    /// Memory growth
+    pub memory: ComplexityEstimate,
+}
+
+/// Fit y = c·nᵏ to `(n, y)` measurements
+///
+/// Points with non-positive n or y are ignored. Returns `None` when fewer
+/// than three points with at least two distinct sizes remain.
+pub fn fit_growth(points: &[(usize, f64)]) -> Option<ComplexityEstimate> {
+    let logs: Vec<(f64, f64)> = points.iter()
+        .filter(|&&(n, y)| n > 0 && y > 0.0)
+        .map(|&(n, y)| ((n as f64).ln(), y.ln()))
+        .collect();
+    if logs.len() < 3 {
+        return None;
+    }
+
+    let count = logs.len() as f64;
+    let mean_x = logs.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = logs.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let sxx: f64 = logs.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = logs.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let syy: f64 = logs.iter().map(|&(_, y)| (y - mean_y).powi(2)).sum();
+    if sxx == 0.0 {
+        return None;
+    }
+
+    let exponent = sxy / sxx;
+    let residual = (syy - exponent * sxy).max(0.0);
+    let df = logs.len() - 2;
+    let margin = T_95.get(df - 1).copied().unwrap_or(1.96) * (residual / df as f64 / sxx).sqrt();
+
+    Some(ComplexityEstimate {
+        exponent,
+        coefficient: (mean_y - exponent * mean_x).exp(),
+        confidence: (exponent - margin, exponent + margin),
+        r_squared: if syy == 0.0 { 1.0 } else { sxy * sxy / (sxx * syy) },
+        samples: logs.len(),
+    })
+}
+
+/// Estimate how the runtime of `f` grows with `n`
+///
+/// `f` is called `REPETITIONS` times per size and the median duration is
+/// fitted, which damps scheduler noise.
+pub fn estimate_complexity(f: impl Fn(usize) -> Duration, ns: &[usize]) -> Option<ComplexityEstimate> {
+    let points: Vec<(usize, f64)> = ns.iter()
+        .map(|&n| {
+            let mut times: Vec<Duration> = (0..REPETITIONS).map(|_| f(n)).collect();
+            times.sort();
+            (n, times[REPETITIONS / 2].as_secs_f64())
+        })
+        .collect();
+    fit_growth(&points)
+}
+
+/// Estimate how a memory measurement (any unit) grows with `n`
+pub fn estimate_memory_complexity(f: impl Fn(usize) -> usize, ns: &[usize]) -> Option<ComplexityEstimate> {
+    let points: Vec<(usize, f64)> = ns.iter().map(|&n| (n, f(n) as f64)).collect();
+    fit_growth(&points)
+}
+
+/// Coverage grammar of stacked clausal complements: determiners, nouns, a
+/// clause-embedding verb, a complementizer, and an intransitive verb
+pub fn embedding_lexicon() -> Vec<LexItem> {
+    vec![
+        LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+        LexItem::new("student", &[Feature::Cat(Category::N)]),
+        LexItem::new("teacher", &[Feature::Cat(Category::N)]),
+        LexItem::new("tutor", &[Feature::Cat(Category::N)]),
+        LexItem::new("said", &[Feature::Cat(Category::V), Feature::Sel(Category::C), Feature::Spec(Category::D)]),
+        LexItem::new("that", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+        LexItem::new("left", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+    ]
+}
+
+/// Sentence with `clauses` embedding clauses above "the tutor left"
+/// (4 · clauses + 3 tokens)
+pub fn embedding_sentence(clauses: usize) -> String {
+    let mut sentence = String::new();
+    for i in 0..clauses {
+        let subject = if i % 2 == 0 { "student" } else { "teacher" };
+        sentence.push_str(&format!("the {} said that ", subject));
+    }
+    sentence.push_str("the tutor left");
+    sentence
+}
+
+/// Estimate parsing growth over embedding sentences of about `ns` tokens
+///
+/// Each n is rounded down to the nearest sentence length the coverage
+/// grammar produces. Memory is the parsed tree's workspace estimate.
+/// Returns `None` if a sentence fails to parse or too few sizes remain.
+pub fn estimate_parse_complexity(ns: &[usize]) -> Option<GrowthEstimate> {
+    let lexicon = embedding_lexicon();
+    let sentences: Vec<String> = ns.iter().map(|&n| embedding_sentence(n.saturating_sub(3) / 4)).collect();
+    let tokens = |sentence: &String| sentence.split_whitespace().count();
+
+    let mut time = Vec::with_capacity(sentences.len());
+    let mut memory = Vec::with_capacity(sentences.len());
+    for sentence in &sentences {
+        let mut times = Vec::with_capacity(REPETITIONS);
+        let mut tree = None;
+        for _ in 0..REPETITIONS {
+            let start = Instant::now();
+            tree = Some(parse_sentence(sentence, &lexicon).ok()?);
+            times.push(start.elapsed());
+        }
+        times.sort();
+
+        let mut workspace = Workspace::new(usize::MAX);
+        workspace.items_mut().extend(tree);
+        time.push((tokens(sentence), times[REPETITIONS / 2].as_secs_f64()));
+        memory.push((tokens(sentence), workspace.memory_usage() as f64));
+    }
+
+    Some(GrowthEstimate { time: fit_growth(&time)?, memory: fit_growth(&memory)? })
+}
+
+/// Estimate aⁿbⁿ generation growth; memory is the generated string's length
+pub fn estimate_generation_complexity(ns: &[usize]) -> Option<GrowthEstimate> {
+    let time = estimate_complexity(
+        |n| {
+            let start = Instant::now();
+            core::hint::black_box(generate_an_bn(n));
+            start.elapsed()
+        },
+        ns,
+    )?;
+    let memory = estimate_memory_complexity(|n| generate_an_bn(n).len(), ns)?;
+    Some(GrowthEstimate { time, memory })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_recovers_exponent() {
+        let quadratic: Vec<(usize, f64)> = (1..=10).map(|n| (n, 3.0 * (n * n) as f64)).collect();
+        let estimate = fit_growth(&quadratic).unwrap();
+        assert!((estimate.exponent - 2.0).abs() < 1e-9);
+        assert!((estimate.coefficient - 3.0).abs() < 1e-9);
+        assert!((estimate.r_squared - 1.0).abs() < 1e-9);
+        assert!(estimate.confidence.1 - estimate.confidence.0 < 1e-6);
+        assert!(estimate.is_bounded_by(2.0 + 1e-6));
+        assert_eq!(estimate.samples, 10);
+
+        // Too few points, or no spread in n
+        assert_eq!(fit_growth(&[(1, 1.0), (2, 2.0)]), None);
+        assert_eq!(fit_growth(&[(4, 1.0), (4, 2.0), (4, 3.0)]), None);
+        assert_eq!(fit_growth(&[(0, 1.0), (2, 2.0), (3, 0.0)]), None);
+    }
+
+    #[test]
+    fn test_noise_widens_interval() {
+        let noisy: Vec<(usize, f64)> = (1..=8)
+            .map(|n| (n, (n as f64).powi(2) * if n % 2 == 0 { 1.3 } else { 0.7 }))
+            .collect();
+        let estimate = fit_growth(&noisy).unwrap();
+        assert!(estimate.confidence.0 < estimate.exponent && estimate.exponent < estimate.confidence.1);
+        assert!(estimate.r_squared < 1.0);
+    }
+
+    #[test]
+    fn test_measurement_functions() {
+        let linear = estimate_memory_complexity(|n| 5 * n, &[1, 2, 4, 8]).unwrap();
+        assert!((linear.exponent - 1.0).abs() < 1e-9);
+
+        let timed = estimate_complexity(|n| Duration::from_micros((n * n) as u64), &[10, 20, 40]).unwrap();
+        assert!((timed.exponent - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_canned_estimates() {
+        assert_eq!(embedding_sentence(2).split_whitespace().count(), 11);
+
+        let parsing = estimate_parse_complexity(&[11, 19, 27]).unwrap();
+        assert_eq!(parsing.time.samples, 3);
+        assert!(parsing.time.exponent.is_finite());
+        assert!((parsing.memory.exponent - 1.0).abs() < 0.1, "{}", parsing.memory);
+
+        let generation = estimate_generation_complexity(&[8, 16, 32]).unwrap();
+        assert!((generation.memory.exponent - 1.0).abs() < 0.05);
+    }
+}
@@ -0,0 +1,353 @@
+//! Derivational Constraints
+//!
+//! Filters applied to convergent derivations. A constraint inspects the
+//! finished tree (including traces left by movement) and rules it out even
+//! though every feature was checked, as with the that-trace effect,
+//! Subjacency or Superiority.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+
+use crate::{Category, DerivationError, SyntacticObject};
+
+/// Movement chain recovered from a derived tree, as child-index paths
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chain {
+    /// Path to the node created by movement (the moved phrase is its first child)
+    pub landing: Vec<usize>,
+    /// Path to the trace left in the base position
+    pub trace: Vec<usize>,
+}
+
+/// Node at a child-index path
+pub fn node_at<'a>(tree: &'a SyntacticObject, path: &[usize]) -> &'a SyntacticObject {
+    path.iter().fold(tree, |node, &i| &node.children[i])
+}
+
+/// Lexical head of a constituent, found by following its projecting children
+pub(crate) fn head_leaf(mut node: &SyntacticObject) -> &SyntacticObject {
+    while let Some(head) = node.head_index() {
+        node = &node.children[head];
+    }
+    node
+}
+
+/// Check whether a clause's edge is free: no specifier, and not headed by a
+/// complementizer that itself occupies the edge
+pub(crate) fn has_open_edge(node: &SyntacticObject, wh_complementizers: &[String]) -> bool {
+    let has_specifier = node.children.len() == 2 && node.head_index() == Some(1);
+    let wh_head = head_leaf(node).phon.as_ref().is_some_and(|phon| wh_complementizers.contains(phon));
+    !has_specifier && !wh_head
+}
+
+/// Check whether a node was created by movement: the remnant projects and a
+/// positive feature was checked without a selector being consumed
+fn is_movement_node(node: &SyntacticObject) -> bool {
+    let [_, remnant] = node.children.as_slice() else {
+        return false;
+    };
+    let triggers = |obj: &SyntacticObject| obj.features.iter().filter(|f| f.is_positive()).count();
+    node.head_index() == Some(1) && triggers(remnant) > triggers(node)
+}
+
+/// Recover the movement chains of a derived tree
+///
+/// Each movement node binds the first trace of the moved phrase's label in
+/// its remnant that no lower movement has already bound.
+pub fn chains(tree: &SyntacticObject) -> Vec<Chain> {
+    fn collect(node: &SyntacticObject, path: &mut Vec<usize>, chains: &mut Vec<Chain>) -> Vec<(Vec<usize>, Category)> {
+        if node.is_trace() {
+            return vec![(path.clone(), node.label.clone())];
+        }
+        
+        let mut unbound: Vec<Vec<(Vec<usize>, Category)>> = Vec::new();
+        for (i, child) in node.children.iter().enumerate() {
+            path.push(i);
+            unbound.push(collect(child, path, chains));
+            path.pop();
+        }
+        
+        if is_movement_node(node) {
+            let label = &node.children[0].label;
+            if let Some(k) = unbound[1].iter().position(|(_, trace)| trace == label) {
+                let (trace, _) = unbound[1].remove(k);
+                chains.push(Chain { landing: path.clone(), trace });
+            }
+        }
+        unbound.into_iter().flatten().collect()
+    }
+    
+    let mut chains = Vec::new();
+    collect(tree, &mut Vec::new(), &mut chains);
+    chains
+}
+
+/// Terminal in the output of a derivation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Terminal<'a> {
+    /// Pronounced lexical item
+    Overt(&'a str),
+    /// Lower copy of a moved constituent
+    Trace,
+}
+
+/// Collect overt terminals and traces in surface order, skipping silent heads
+fn terminals(obj: &SyntacticObject) -> Vec<Terminal<'_>> {
+    match obj.phon.as_deref() {
+        Some("") => Vec::new(),
+        Some(phon) => vec![Terminal::Overt(phon)],
+        None if obj.is_trace() => vec![Terminal::Trace],
+        None => obj.children.iter().flat_map(terminals).collect(),
+    }
+}
+
+/// That-trace filter: `*[C that] t` (Chomsky & Lasnik 1977)
+///
+/// An overt complementizer may not be immediately followed by a trace, so
+/// subjects cannot be extracted across `that` while objects can.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThatTraceFilter {
+    /// Overt complementizers that trigger the filter
+    pub complementizers: Vec<String>,
+}
+
+impl ThatTraceFilter {
+    /// Name reported in constraint violations
+    pub const NAME: &'static str = "that-trace";
+
+    /// English setting: the filter applies to `that`
+    pub fn english() -> Self {
+        Self {
+            complementizers: vec!["that".to_string()],
+        }
+    }
+
+    /// Check whether the tree contains a complementizer-trace sequence
+    pub fn violated_by(&self, tree: &SyntacticObject) -> bool {
+        terminals(tree).windows(2).any(|pair| match pair {
+            [Terminal::Overt(word), Terminal::Trace] => {
+                self.complementizers.iter().any(|c| c == word)
+            }
+            _ => false,
+        })
+    }
+
+    /// Reject trees that violate the filter
+    pub fn check(&self, tree: &SyntacticObject) -> Result<(), DerivationError> {
+        if self.violated_by(tree) {
+            Err(DerivationError::ConstraintViolation(Self::NAME))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Subjacency: movement may cross at most `limit` bounding nodes per step
+/// (Chomsky 1973)
+///
+/// Movement is successive-cyclic: a clause whose edge is free serves as an
+/// escape hatch, and crossings are counted from the last one. Clauses whose
+/// edge is filled by a specifier or by a wh-complementizer are islands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subjacency {
+    /// Categories whose maximal projections are bounding nodes
+    pub bounding_nodes: Vec<Category>,
+    /// Bounding nodes one movement step may cross
+    pub limit: usize,
+    /// Category whose free edge provides an intermediate landing site
+    pub escape_hatch: Option<Category>,
+    /// Complementizers that occupy the edge of their clause
+    pub wh_complementizers: Vec<String>,
+}
+
+impl Subjacency {
+    /// Name reported in constraint violations
+    pub const NAME: &'static str = "subjacency";
+
+    /// English setting: DP and the clause bound movement
+    ///
+    /// Clauses project no T layer here, so the clausal bounding node (S) is
+    /// the verb's maximal projection.
+    pub fn english() -> Self {
+        Self {
+            bounding_nodes: vec![Category::D, Category::V],
+            limit: 1,
+            escape_hatch: Some(Category::C),
+            wh_complementizers: vec!["whether".to_string(), "if".to_string()],
+        }
+    }
+
+    /// Italian setting: DP and CP bound movement (Rizzi 1982)
+    pub fn italian() -> Self {
+        Self {
+            bounding_nodes: vec![Category::D, Category::C],
+            ..Self::english()
+        }
+    }
+
+    /// Check whether a node is a bounding node
+    fn is_bounding(&self, node: &SyntacticObject) -> bool {
+        self.bounding_nodes.contains(&node.label.head())
+    }
+
+    /// Check whether a clause offers its edge as an intermediate landing site
+    fn is_escape_hatch(&self, node: &SyntacticObject) -> bool {
+        self.escape_hatch.as_ref() == Some(&node.label.head())
+            && has_open_edge(node, &self.wh_complementizers)
+    }
+
+    /// Most bounding nodes a chain crosses in a single movement step
+    pub fn crossings(&self, tree: &SyntacticObject, chain: &Chain) -> usize {
+        let mut max = 0;
+        let mut count = 0;
+        
+        // Climb from the trace to the landing site's projection
+        for depth in (chain.landing.len() + 2..chain.trace.len()).rev() {
+            let node = node_at(tree, &chain.trace[..depth]);
+            let parent = node_at(tree, &chain.trace[..depth - 1]);
+            if parent.head_index() == Some(chain.trace[depth - 1]) {
+                continue; // not a maximal projection
+            }
+            if self.is_escape_hatch(node) {
+                count = 0;
+            }
+            if self.is_bounding(node) {
+                count += 1;
+                max = max.max(count);
+            }
+        }
+        max
+    }
+
+    /// Reject trees with a movement step crossing too many bounding nodes
+    pub fn check(&self, tree: &SyntacticObject) -> Result<(), DerivationError> {
+        if chains(tree).iter().any(|chain| self.crossings(tree, chain) > self.limit) {
+            Err(DerivationError::ConstraintViolation(Self::NAME))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Superiority: a wh-phrase may not move across a higher wh-phrase left in
+/// situ (Chomsky 1973)
+///
+/// The attracting head must take the closest wh-phrase, so `who saw what`
+/// is fine while `*what did who see` moves the object over the subject.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Superiority {
+    /// Words heading wh-phrases
+    pub wh_words: Vec<String>,
+}
+
+impl Superiority {
+    /// Name reported in constraint violations
+    pub const NAME: &'static str = "superiority";
+
+    /// English setting: the wh-pronouns and determiners
+    pub fn english() -> Self {
+        Self {
+            wh_words: ["who", "whom", "what", "which", "where", "when"]
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+        }
+    }
+
+    /// Check whether a constituent is a pronounced wh-phrase
+    fn is_wh_phrase(&self, node: &SyntacticObject) -> bool {
+        head_leaf(node).phon.as_ref().is_some_and(|phon| self.wh_words.contains(phon))
+    }
+
+    /// Check whether a wh-phrase left in situ c-commands the chain's trace
+    ///
+    /// The sisters of the nodes between the landing site and the trace are
+    /// exactly the constituents the moved phrase crossed.
+    pub fn crossed_by(&self, tree: &SyntacticObject, chain: &Chain) -> bool {
+        (chain.landing.len() + 1..chain.trace.len()).any(|depth| {
+            let parent = node_at(tree, &chain.trace[..depth]);
+            parent.children.iter().enumerate().any(|(i, sister)| {
+                i != chain.trace[depth] && self.is_wh_phrase(sister)
+            })
+        })
+    }
+
+    /// Reject trees where a wh-phrase moved over a higher one
+    pub fn check(&self, tree: &SyntacticObject) -> Result<(), DerivationError> {
+        if chains(tree).iter().any(|chain| self.crossed_by(tree, chain)) {
+            Err(DerivationError::ConstraintViolation(Self::NAME))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(phon: &str) -> SyntacticObject {
+        SyntacticObject {
+            label: Category::C,
+            features: Vec::new(),
+            children: Vec::new(),
+            phon: Some(phon.to_string()),
+            entry: None,
+        }
+    }
+
+    fn node(children: Vec<SyntacticObject>) -> SyntacticObject {
+        SyntacticObject {
+            label: Category::CP,
+            features: Vec::new(),
+            children,
+            phon: None,
+            entry: None,
+        }
+    }
+
+    #[test]
+    fn test_complementizer_before_trace() {
+        let filter = ThatTraceFilter::english();
+        let subject_gap = node(vec![leaf("that"), node(vec![SyntacticObject::trace(Category::D), leaf("left")])]);
+        assert!(filter.violated_by(&subject_gap));
+        assert_eq!(
+            filter.check(&subject_gap),
+            Err(DerivationError::ConstraintViolation(ThatTraceFilter::NAME))
+        );
+    }
+
+    #[test]
+    fn test_object_gap_and_silent_complementizer() {
+        let filter = ThatTraceFilter::english();
+        let object_gap = node(vec![leaf("that"), leaf("Mary"), leaf("saw"), SyntacticObject::trace(Category::D)]);
+        assert!(filter.check(&object_gap).is_ok());
+
+        // A silent C does not trigger the filter
+        let silent = node(vec![leaf(""), SyntacticObject::trace(Category::D), leaf("left")]);
+        assert!(!filter.violated_by(&silent));
+    }
+
+    #[test]
+    fn test_chain_recovery() {
+        use crate::{parse_sentence, GrammarOptions, RelativeClauseAnalysis};
+
+        for (analysis, expected) in [
+            (RelativeClauseAnalysis::OperatorMovement, 1),
+            (RelativeClauseAnalysis::Promotion, 2),
+        ] {
+            let options = GrammarOptions { relative_clauses: analysis, ..Default::default() };
+            let tree = parse_sentence("the student who left", &options.lexicon()).unwrap();
+            let chains = chains(&tree);
+            assert_eq!(chains.len(), expected);
+
+            for chain in &chains {
+                let landing = node_at(&tree, &chain.landing);
+                assert!(node_at(&tree, &chain.trace).is_trace());
+                assert!(chain.trace.starts_with(&chain.landing));
+                assert_eq!(node_at(&tree, &chain.trace).label, landing.children[0].label);
+                assert_eq!(Subjacency::english().crossings(&tree, chain), 1);
+            }
+        }
+    }
+}
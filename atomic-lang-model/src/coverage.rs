@@ -0,0 +1,180 @@
+//! Corpus Coverage
+//!
+//! Measures how much of a corpus (one sentence per line) a grammar covers:
+//! the share of sentences that parse, the share of tokens missing from the
+//! lexicon, failures bucketed by derivation error, and the word sequences
+//! that occur in failed sentences but never in parsed ones, most frequent
+//! first. Those n-grams point at the constructions to add next.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, format, string::{String, ToString}, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::fmt;
+
+use crate::{parse_batch, DerivationError, GrammarOptions, LexItem, WorkspacePool};
+
+/// Longest n-gram counted
+pub const MAX_NGRAM: usize = 3;
+
+/// N-grams shown when a report is displayed
+pub const SHOWN_NGRAMS: usize = 10;
+
+/// Coverage of a corpus by a grammar
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CoverageReport {
+    /// Non-empty lines
+    pub sentences: usize,
+    /// Sentences that parse
+    pub parsed: usize,
+    /// Tokens in the corpus
+    pub tokens: usize,
+    /// Tokens missing from the lexicon
+    pub oov_tokens: usize,
+    /// Words missing from the lexicon with their counts, most frequent first
+    pub oov_words: Vec<(String, usize)>,
+    /// Failed sentences per derivation error, most frequent first
+    pub failures: Vec<(DerivationError, usize)>,
+    /// N-grams (2 to `MAX_NGRAM` words) found only in failed sentences,
+    /// with their counts, most frequent first
+    pub unparsed_ngrams: Vec<(String, usize)>,
+}
+
+impl CoverageReport {
+    /// Share of sentences that parse
+    pub fn parse_rate(&self) -> f64 {
+        ratio(self.parsed, self.sentences)
+    }
+
+    /// Share of tokens missing from the lexicon
+    pub fn oov_rate(&self) -> f64 {
+        ratio(self.oov_tokens, self.tokens)
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 { 0.0 } else { part as f64 / whole as f64 }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "parsed {}/{} sentences ({:.1}%)", self.parsed, self.sentences, self.parse_rate() * 100.0)?;
+        writeln!(f, "out of vocabulary: {}/{} tokens ({:.1}%)", self.oov_tokens, self.tokens, self.oov_rate() * 100.0)?;
+        if !self.oov_words.is_empty() {
+            let words: Vec<String> = self.oov_words.iter().map(|(word, count)| format!("{} ×{}", word, count)).collect();
+            writeln!(f, "  {}", words.join(", "))?;
+        }
+        if !self.failures.is_empty() {
+            writeln!(f, "failures:")?;
+            for (error, count) in &self.failures {
+                writeln!(f, "  {:>5}  {}", count, error)?;
+            }
+        }
+        if !self.unparsed_ngrams.is_empty() {
+            writeln!(f, "n-grams only in failed sentences:")?;
+            for (ngram, count) in self.unparsed_ngrams.iter().take(SHOWN_NGRAMS) {
+                writeln!(f, "  {:>5}  {}", count, ngram)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Coverage of `corpus` (one sentence per line) under the default options
+pub fn coverage(corpus: &str, lexicon: &[LexItem]) -> CoverageReport {
+    coverage_with_options(corpus, lexicon, &GrammarOptions::default())
+}
+
+/// Coverage of `corpus` (one sentence per line) under the given options
+pub fn coverage_with_options(corpus: &str, lexicon: &[LexItem], options: &GrammarOptions) -> CoverageReport {
+    let sentences: Vec<&str> = corpus.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+    let results = parse_batch(sentences.iter().copied(), lexicon, options, &mut WorkspacePool::new());
+
+    let mut report = CoverageReport { sentences: sentences.len(), ..Default::default() };
+    let mut oov: BTreeMap<String, usize> = BTreeMap::new();
+    let mut parsed_ngrams: BTreeMap<String, usize> = BTreeMap::new();
+    let mut failed_ngrams: BTreeMap<String, usize> = BTreeMap::new();
+
+    for (sentence, result) in sentences.iter().zip(&results) {
+        let normalized = options.normalizer.apply(sentence, lexicon);
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        report.tokens += tokens.len();
+        for &token in tokens.iter().filter(|&&token| !lexicon.iter().any(|item| item.phon == token)) {
+            report.oov_tokens += 1;
+            *oov.entry(token.to_string()).or_default() += 1;
+        }
+
+        let ngrams = match result {
+            Ok(_) => {
+                report.parsed += 1;
+                &mut parsed_ngrams
+            }
+            Err(error) => {
+                match report.failures.iter_mut().find(|(bucket, _)| bucket == error) {
+                    Some((_, count)) => *count += 1,
+                    None => report.failures.push((*error, 1)),
+                }
+                &mut failed_ngrams
+            }
+        };
+        for n in 2..=MAX_NGRAM {
+            for window in tokens.windows(n) {
+                *ngrams.entry(window.join(" ")).or_default() += 1;
+            }
+        }
+    }
+
+    report.oov_words = ranked(oov.into_iter());
+    report.failures.sort_by_key(|&(_, count)| core::cmp::Reverse(count));
+    report.unparsed_ngrams = ranked(failed_ngrams.into_iter().filter(|(ngram, _)| !parsed_ngrams.contains_key(ngram)));
+    report
+}
+
+/// Counts sorted most frequent first, ties in alphabetical order
+fn ranked(counts: impl Iterator<Item = (String, usize)>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    const CORPUS: &str = "the student left\n\
+                          the teacher smiled\n\
+                          \n\
+                          the student thinks\n\
+                          the student thinks\n\
+                          the teacher thinks\n\
+                          the robot left\n";
+
+    #[test]
+    fn test_rates_and_buckets() {
+        let report = coverage(CORPUS, &test_lexicon());
+
+        assert_eq!((report.parsed, report.sentences), (2, 6));
+        assert_eq!(report.parse_rate(), 2.0 / 6.0);
+        assert_eq!((report.oov_tokens, report.tokens), (1, 18));
+        assert_eq!(report.oov_words, [("robot".to_string(), 1)]);
+        assert_eq!(report.failures, [
+            (DerivationError::NoValidOperations, 3),
+            (DerivationError::InvalidOperation, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_ngrams_only_in_failures() {
+        let report = coverage(CORPUS, &test_lexicon());
+        let ngram = |text: &str| report.unparsed_ngrams.iter().find(|(ngram, _)| ngram == text).map(|&(_, count)| count);
+
+        // 'the student' also occurs in a parsed sentence, so it is not to blame
+        assert_eq!(ngram("the student"), None);
+        assert_eq!(report.unparsed_ngrams[0], ("student thinks".to_string(), 2));
+        assert_eq!(ngram("the robot left"), Some(1));
+        assert!(report.to_string().starts_with("parsed 2/6 sentences (33.3%)\nout of vocabulary: 1/18 tokens (5.6%)\n"));
+    }
+}
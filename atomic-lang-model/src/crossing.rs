@@ -0,0 +1,183 @@
+//! Crossing Dependencies
+//!
+//! A Minimalist Grammar fragment for aⁿbᵐcⁿdᵐ, where the a–c and b–d
+//! dependencies cross. No context-free grammar generates the language; the
+//! fragment derives it with Merge and Move alone, which is the mild
+//! context-sensitivity MGs add over phrase structure grammars.
+//!
+//! The d's are collected into one constituent that stays pending as a
+//! mover (`-D_BLOCK`) while the b's are counted off against them. Each new
+//! b/d pair re-merges the pending block under a fresh d and evacuates the
+//! b's already counted (`-B_REMNANT`), so the two counts grow in lockstep.
+//! The a–c pairs then nest around the b's like aⁿbⁿ, and a final head
+//! pulls the block of d's out and rolls the rest of the clause over it.
+//!
+//! Every silent head selects an argument with overt material at its root,
+//! so the fragment needs at least one b/d pair (m ≥ 1); n may be 0.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::{merge, move_operation, Category, DerivationError, Feature, LexItem, SyntacticObject};
+
+/// Movement index for the pending block of d's
+pub const D_BLOCK: u8 = 4;
+
+/// Movement index for the b's evacuated from a block being extended
+pub const B_REMNANT: u8 = 5;
+
+/// Movement index for the a–c nest rolled over the block of d's
+pub const NEST: u8 = 6;
+
+fn a() -> LexItem {
+    LexItem::new("a", &[Feature::Cat(Category::N)])
+}
+
+fn b() -> LexItem {
+    LexItem::new("b", &[Feature::Cat(Category::V)])
+}
+
+/// Takes the inner nest, then the matching a, both to its left
+fn c() -> LexItem {
+    LexItem::new("c", &[Feature::Cat(Category::S), Feature::Spec(Category::S), Feature::Spec(Category::N)])
+}
+
+fn d() -> LexItem {
+    LexItem::new("d", &[Feature::Cat(Category::D)])
+}
+
+/// Marks a block of d's as pending
+fn block() -> LexItem {
+    LexItem::new("", &[Feature::Cat(Category::P), Feature::Sel(Category::D), Feature::Neg(D_BLOCK)])
+}
+
+/// First b/d pair: b in the specifier, the block of one d as complement
+fn first_pair() -> LexItem {
+    LexItem::new("", &[Feature::Cat(Category::S), Feature::Sel(Category::P), Feature::Spec(Category::V)])
+}
+
+/// Marks the b's counted so far for evacuation
+fn remnant() -> LexItem {
+    LexItem::new("", &[Feature::Cat(Category::S), Feature::Sel(Category::S), Feature::Neg(B_REMNANT)])
+}
+
+/// Attracts the pending block and adds a d to it
+fn extend_block() -> LexItem {
+    LexItem::new("", &[
+        Feature::Cat(Category::D),
+        Feature::Sel(Category::S),
+        Feature::Pos(D_BLOCK),
+        Feature::Spec(Category::D),
+    ])
+}
+
+/// Attracts the evacuated b's out of the extended block and adds a b
+fn next_pair() -> LexItem {
+    LexItem::new("", &[
+        Feature::Cat(Category::S),
+        Feature::Sel(Category::P),
+        Feature::Pos(B_REMNANT),
+        Feature::Spec(Category::V),
+    ])
+}
+
+/// Marks the a–c nest for movement over the block of d's
+fn nest() -> LexItem {
+    LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::S), Feature::Neg(NEST)])
+}
+
+/// Lands the block of d's, then the nest to its left
+fn clause() -> LexItem {
+    LexItem::new("", &[
+        Feature::Cat(Category::C),
+        Feature::Sel(Category::C),
+        Feature::Pos(D_BLOCK),
+        Feature::Pos(NEST),
+    ])
+}
+
+/// Lexical items of the crossing-dependency fragment, overt items first
+pub fn crossing_lexicon() -> Vec<LexItem> {
+    vec![
+        a(),
+        b(),
+        c(),
+        d(),
+        block(),
+        first_pair(),
+        remnant(),
+        extend_block(),
+        next_pair(),
+        nest(),
+        clause(),
+    ]
+}
+
+/// Merge a silent or overt head with its argument
+fn head(item: LexItem, argument: SyntacticObject) -> Result<SyntacticObject, DerivationError> {
+    merge(SyntacticObject::from_lex(&item), argument)
+}
+
+/// Derive aⁿbᵐcⁿdᵐ in the crossing fragment
+///
+/// The derivation is driven directly rather than found by search: the
+/// parser only merges non-adjacent items that still have to move, and the
+/// nested b's are not adjacent to the c that takes them. Fails with
+/// `InvalidOperation` for m = 0.
+pub fn derive_crossing(n: usize, m: usize) -> Result<SyntacticObject, DerivationError> {
+    if m == 0 {
+        return Err(DerivationError::InvalidOperation);
+    }
+    let leaf = |item: LexItem| SyntacticObject::from_lex(&item);
+
+    // b with the block of one d pending: yield b, mover d
+    let pair = head(first_pair(), head(block(), leaf(d()))?)?;
+    let mut pairs = merge(pair, leaf(b()))?;
+
+    // Each pair: extend the block with a d, evacuate the b's, add a b
+    for _ in 1..m {
+        let counted = head(remnant(), pairs)?;
+        let extended = move_operation(head(extend_block(), counted)?)?;
+        let extended = merge(extended, leaf(d()))?;
+        let evacuated = move_operation(head(next_pair(), head(block(), extended)?)?)?;
+        pairs = merge(evacuated, leaf(b()))?;
+    }
+
+    // a–c pairs nest around the b's
+    let mut nested = pairs;
+    for _ in 0..n {
+        nested = merge(merge(leaf(c()), nested)?, leaf(a()))?;
+    }
+
+    // Land the d's, then roll the nest over them
+    let landed = move_operation(head(clause(), head(nest(), nested)?)?)?;
+    move_operation(landed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{AnBmCnDm, RecursivePattern};
+
+    #[test]
+    fn test_derives_crossing_dependencies() {
+        for n in 0..=4 {
+            for m in 1..=4 {
+                let tree = derive_crossing(n, m).unwrap();
+                assert!(tree.is_complete(), "n={} m={}: {:?}", n, m, tree.features);
+                assert_eq!(tree.linearize(), AnBmCnDm::member(n, m), "n={} m={}", n, m);
+                assert!(AnBmCnDm::default().recognize(&tree.linearize()));
+            }
+        }
+        assert_eq!(derive_crossing(2, 0), Err(DerivationError::InvalidOperation));
+    }
+
+    #[test]
+    fn test_blocks_are_constituents() {
+        // The d's land as one constituent, to the right of the rolled-up nest
+        let tree = derive_crossing(1, 3).unwrap();
+        assert_eq!(tree.children[0].linearize(), "a b b b c");
+        assert_eq!(tree.children[1].children[0].linearize(), "d d d");
+        assert!(crossing_lexicon().iter().filter(|item| !item.phon.is_empty()).count() == 4);
+    }
+}
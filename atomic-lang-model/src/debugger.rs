@@ -0,0 +1,220 @@
+//! Derivation Debugger
+//!
+//! Steps through a derivation one operation at a time, keeping every
+//! intermediate workspace so steps can be rewound. Breakpoints pause
+//! `resume` when an operation builds an object of a given category or
+//! carrying a given feature.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{
+    apply_operation, begin_step, parse_derivation, replay_workspace, Category, DerivationError, Feature,
+    GrammarOptions, LexItem, Operation, SyntacticObject, Workspace,
+};
+
+/// Condition that pauses a running derivation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Breakpoint {
+    /// Break when an operation builds a projection of the category
+    Category(Category),
+    /// Break when an operation builds an object carrying the feature
+    Feature(Feature),
+}
+
+impl Breakpoint {
+    /// Check whether an operation's result triggers the breakpoint
+    pub fn hit_by(&self, obj: &SyntacticObject) -> bool {
+        match self {
+            Breakpoint::Category(cat) => cat.matches(&obj.label),
+            Breakpoint::Feature(feature) => obj.features.contains(feature),
+        }
+    }
+}
+
+/// Workspace stepped through a planned derivation
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    /// Workspaces before each applied operation, oldest first
+    history: Vec<Workspace>,
+    /// Current workspace
+    workspace: Workspace,
+    /// Operations to apply, in order
+    plan: Vec<Operation>,
+    /// Active breakpoints
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl Debugger {
+    /// Wrap a workspace with the operations to step through
+    pub fn new(workspace: Workspace, plan: Vec<Operation>) -> Self {
+        Self {
+            history: Vec::new(),
+            workspace,
+            plan,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    /// Debug the derivation found for a sentence
+    pub fn for_sentence(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Self, DerivationError> {
+        let parse = parse_derivation(sentence, lexicon, options)?;
+        let tokens: Vec<&str> = sentence.split_whitespace().collect();
+        Ok(Self::new(replay_workspace(&tokens, lexicon, &parse.derivation)?, parse.derivation))
+    }
+
+    /// Current workspace
+    pub fn workspace(&self) -> &Workspace {
+        &self.workspace
+    }
+
+    /// Number of operations applied so far
+    pub fn position(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Operation the next step applies
+    pub fn next_operation(&self) -> Option<Operation> {
+        self.plan.get(self.position()).copied()
+    }
+
+    /// Check if every planned operation has been applied
+    pub fn is_finished(&self) -> bool {
+        self.next_operation().is_none()
+    }
+
+    /// Object at a workspace index
+    pub fn inspect(&self, i: usize) -> Option<&SyntacticObject> {
+        self.workspace.items().get(i)
+    }
+
+    /// Add a breakpoint
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.push(breakpoint);
+    }
+
+    /// Active breakpoints
+    pub fn breakpoints(&self) -> &[Breakpoint] {
+        &self.breakpoints
+    }
+
+    /// Remove every breakpoint
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Apply the next operation, returning it (`None` once finished)
+    pub fn step_into(&mut self) -> Result<Option<Operation>, DerivationError> {
+        let op = match self.next_operation() {
+            Some(op) => op,
+            None => return Ok(None),
+        };
+
+        let mut next = self.workspace.clone();
+        begin_step(&mut next)?;
+        apply_operation(&mut next, op)?;
+        self.history.push(core::mem::replace(&mut self.workspace, next));
+        Ok(Some(op))
+    }
+
+    /// Apply operations until one completes a phrase: its result has no
+    /// selectors or licensors left. Returns the operations applied.
+    pub fn step_over(&mut self) -> Result<Vec<Operation>, DerivationError> {
+        let mut applied = Vec::new();
+        while let Some(op) = self.step_into()? {
+            applied.push(op);
+            let result = &self.workspace.items()[op.result_index()];
+            if result.next_selector().is_none() && !result.features.iter().any(Feature::is_positive) {
+                break;
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Apply operations until a breakpoint is hit or the plan is finished,
+    /// returning the breakpoint that paused the derivation
+    pub fn resume(&mut self) -> Result<Option<&Breakpoint>, DerivationError> {
+        while let Some(op) = self.step_into()? {
+            let result = &self.workspace.items()[op.result_index()];
+            if let Some(i) = self.breakpoints.iter().position(|b| b.hit_by(result)) {
+                return Ok(Some(&self.breakpoints[i]));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Undo the last operation, returning it (`None` at the start)
+    pub fn rewind(&mut self) -> Option<Operation> {
+        let previous = self.history.pop()?;
+        self.workspace = previous;
+        self.plan.get(self.position()).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon};
+
+    fn debugger(sentence: &str) -> Debugger {
+        Debugger::for_sentence(sentence, &GrammarOptions::default().lexicon(), &GrammarOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn test_step_and_rewind() {
+        let mut debugger = debugger("the student left");
+        assert_eq!(debugger.workspace().items().len(), 3);
+        assert_eq!(debugger.inspect(2).and_then(|obj| obj.phon.as_deref()), Some("left"));
+
+        let first = debugger.step_into().unwrap();
+        assert_eq!(first, Some(Operation::Merge(0, 1)));
+        assert_eq!(debugger.inspect(0).unwrap().linearize(), "the student");
+        assert_eq!(debugger.workspace().step_count, 1);
+
+        assert_eq!(debugger.rewind(), first);
+        assert_eq!(debugger.position(), 0);
+        assert_eq!(debugger.workspace().items().len(), 3);
+        assert_eq!(debugger.rewind(), None);
+
+        while debugger.step_into().unwrap().is_some() {}
+        assert!(debugger.is_finished());
+        assert_eq!(
+            debugger.inspect(0),
+            Some(&parse_sentence("the student left", &test_lexicon()).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_step_over_completes_phrase() {
+        let mut debugger = debugger("the student left");
+
+        // The DP is complete after one merge
+        assert_eq!(debugger.step_over().unwrap().len(), 1);
+
+        // The verb then takes the DP as its subject
+        let applied = debugger.step_over().unwrap();
+        assert!(!applied.is_empty());
+        assert!(debugger.is_finished());
+        assert_eq!(debugger.inspect(0).unwrap().linearize(), "the student left");
+        assert!(debugger.step_over().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let mut debugger = debugger("the student who left");
+        debugger.add_breakpoint(Breakpoint::Category(Category::C));
+        debugger.add_breakpoint(Breakpoint::Feature(Feature::Adjoin(Category::N)));
+
+        // The silent relative complementizer projects a CP carrying ~N
+        assert_eq!(debugger.resume().unwrap(), Some(&Breakpoint::Category(Category::C)));
+        assert_eq!(debugger.next_operation(), Some(Operation::Move(2)));
+
+        // Movement checks +wh on the CP, which still adjoins to N
+        assert!(debugger.resume().unwrap().is_some());
+        assert_eq!(debugger.inspect(2).unwrap().linearize(), "who left");
+
+        debugger.clear_breakpoints();
+        assert_eq!(debugger.resume().unwrap(), None);
+        assert!(debugger.is_finished());
+    }
+}
@@ -0,0 +1,247 @@
+//! Derivation Trees
+//!
+//! A `SyntacticObject` is the derived tree: phrase structure with movers at
+//! their landing sites and traces left behind. The derivation tree records
+//! how it was built instead: leaves are lexical items and internal nodes are
+//! operations (Merge, Adjoin, Move), the object MCFG compilation and
+//! compositional semantics usually work on. `derived` evaluates a
+//! derivation tree to its derived tree; `replay` builds one from a parse's
+//! operation sequence, and `of` recovers one from a derived tree by reading
+//! the operation that built each of its nodes.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use core::fmt;
+
+use crate::{
+    adjoin, apply_operation, merge, move_operation, Category, DerivationError, Feature, LexItem, Operation, SyntacticObject,
+    Workspace,
+};
+
+/// Derivation as a tree of operations over lexical items
+#[derive(Debug, Clone, PartialEq)]
+pub enum DerivationTree {
+    /// Lexical item, overt or silent
+    Lexical(LexItem),
+    /// Merge of a selector (first) with its selectee (second)
+    Merge(Box<DerivationTree>, Box<DerivationTree>),
+    /// Adjunction of the second derivation to the first, its host
+    Adjoin(Box<DerivationTree>, Box<DerivationTree>),
+    /// Movement within the derived object
+    Move(Box<DerivationTree>),
+}
+
+impl DerivationTree {
+    /// Evaluate to the derived tree
+    pub fn derived(&self) -> Result<SyntacticObject, DerivationError> {
+        match self {
+            DerivationTree::Lexical(item) => Ok(SyntacticObject::from_lex(item)),
+            DerivationTree::Merge(selector, selectee) => merge(selector.derived()?, selectee.derived()?),
+            DerivationTree::Adjoin(host, adjunct) => adjoin(host.derived()?, adjunct.derived()?),
+            DerivationTree::Move(obj) => move_operation(obj.derived()?),
+        }
+    }
+
+    /// Derivation tree of `operations` applied to `workspace`
+    pub fn replay(workspace: &Workspace, operations: &[Operation]) -> Result<Self, DerivationError> {
+        let mut workspace = workspace.clone();
+        let mut nodes: Vec<DerivationTree> = workspace.items().iter().map(|item| DerivationTree::Lexical(lexical(item))).collect();
+        for &op in operations {
+            apply_operation(&mut workspace, op)?;
+            match op {
+                Operation::Merge(_, j) | Operation::Adjoin(_, j) => {
+                    let b = Box::new(nodes.remove(j));
+                    let a = Box::new(nodes.remove(op.result_index()));
+                    let node = if let Operation::Merge(..) = op { DerivationTree::Merge(a, b) } else { DerivationTree::Adjoin(a, b) };
+                    nodes.insert(op.result_index(), node);
+                }
+                Operation::MergeEmpty(h, i) => {
+                    let head = Box::new(DerivationTree::Lexical(lexical(&workspace.empty_heads[h])));
+                    let node = DerivationTree::Merge(head, Box::new(nodes.remove(i)));
+                    nodes.insert(i, node);
+                }
+                Operation::Move(i) => {
+                    let obj = core::mem::replace(&mut nodes[i], DerivationTree::Lexical(LexItem::new("", &[])));
+                    nodes[i] = DerivationTree::Move(Box::new(obj));
+                }
+            }
+        }
+        match <[DerivationTree; 1]>::try_from(nodes) {
+            Ok([tree]) => Ok(tree),
+            Err(_) => Err(DerivationError::NoValidOperations),
+        }
+    }
+
+    /// Derivation tree of a derived tree
+    ///
+    /// The derivation is read off the tree a node at a time, so no lexicon
+    /// is needed and the work stays polynomial in the size of the tree
+    /// however ambiguous its words are. A node is a Merge if a daughter has
+    /// a selector pending, an Adjoin if its right daughter is an adjunct,
+    /// and a Move if its right daughter has a trigger pending; a Move is
+    /// read further from the tree before it, with the mover back in place
+    /// of its trace and its licensee restored. Each reading is kept only if
+    /// it derives the node's structure again; fails with `NoValidOperations`
+    /// if no reading derives the whole tree.
+    pub fn of(tree: &SyntacticObject) -> Result<Self, DerivationError> {
+        let derivation = read(tree).ok_or(DerivationError::NoValidOperations)?;
+        match derivation.derived() {
+            Ok(derived) if derived == *tree => Ok(derivation),
+            Ok(_) => Err(DerivationError::NoValidOperations),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lexical items at the leaves, in derivation order
+    pub fn items(&self) -> Vec<&LexItem> {
+        match self {
+            DerivationTree::Lexical(item) => Vec::from([item]),
+            DerivationTree::Merge(a, b) | DerivationTree::Adjoin(a, b) => {
+                let mut items = a.items();
+                items.extend(b.items());
+                items
+            }
+            DerivationTree::Move(obj) => obj.items(),
+        }
+    }
+}
+
+impl fmt::Display for DerivationTree {
+    /// Bracketing with operations at the nodes and `ε` for silent heads
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivationTree::Lexical(item) if item.phon.is_empty() => write!(f, "ε"),
+            DerivationTree::Lexical(item) => write!(f, "{}", item.phon),
+            DerivationTree::Merge(a, b) => write!(f, "[Merge {} {}]", a, b),
+            DerivationTree::Adjoin(a, b) => write!(f, "[Adjoin {} {}]", a, b),
+            DerivationTree::Move(obj) => write!(f, "[Move {}]", obj),
+        }
+    }
+}
+
+/// Lexical item a leaf was built from
+fn lexical(leaf: &SyntacticObject) -> LexItem {
+    LexItem::new(leaf.phon.as_deref().unwrap_or(""), &leaf.features)
+}
+
+/// Derivation of a node, trying each operation that could have built it
+fn read(node: &SyntacticObject) -> Option<DerivationTree> {
+    let [left, right] = node.children.as_slice() else {
+        return node.phon.is_some().then(|| DerivationTree::Lexical(lexical(node)));
+    };
+    let derives = |derivation: &DerivationTree| derivation.derived().is_ok_and(|derived| same_shape(&derived, node));
+
+    let complement = matches!(left.next_selector(), Some(Feature::Sel(_)));
+    let specifier = matches!(right.next_selector(), Some(Feature::Spec(_)));
+    let adjunct = right.features.iter().any(|f| matches!(f, Feature::Adjoin(_)));
+    // A daughter with a trace has no reading until the Move above it
+    let pair = || Some((Box::new(read(left)?), Box::new(read(right)?)));
+    if let Some((left, right)) = (complement || specifier || adjunct).then(pair).flatten() {
+        let readings = [
+            complement.then(|| DerivationTree::Merge(left.clone(), right.clone())),
+            specifier.then(|| DerivationTree::Merge(right.clone(), left.clone())),
+            adjunct.then(|| DerivationTree::Adjoin(left.clone(), right.clone())),
+        ];
+        if let Some(reading) = readings.into_iter().flatten().find(derives) {
+            return Some(reading);
+        }
+    }
+
+    let index = right.features.iter().find(|f| f.is_positive())?.movement_index()?;
+    let mut mover = left.clone();
+    let at = mover.features.iter().position(Feature::is_negative).unwrap_or(mover.features.len());
+    mover.features.insert(at, Feature::Neg(index));
+    traces(right, &left.label, &mut Vec::new()).into_iter().find_map(|path| {
+        let mut before = right.clone();
+        *path.iter().fold(&mut before, |node, &i| &mut node.children[i]) = mover.clone();
+        Some(DerivationTree::Move(Box::new(read(&before)?))).filter(derives)
+    })
+}
+
+/// Whether two trees have the same labels, words and leaf features,
+/// whatever the features of their phrases
+///
+/// The tree before a Move is rebuilt from the tree after it, which has
+/// lost the mover's licensees along its path, so phrases are compared by
+/// their shape alone; `of` compares the whole derivation exactly.
+fn same_shape(a: &SyntacticObject, b: &SyntacticObject) -> bool {
+    a.label == b.label
+        && a.phon == b.phon
+        && a.children.len() == b.children.len()
+        && (!a.children.is_empty() || a.features == b.features)
+        && a.children.iter().zip(&b.children).all(|(a, b)| same_shape(a, b))
+}
+
+/// Paths to the traces labeled `label` in `obj`
+fn traces(obj: &SyntacticObject, label: &Category, path: &mut Vec<usize>) -> Vec<Vec<usize>> {
+    if obj.is_trace() {
+        return if obj.label == *label { vec![path.clone()] } else { Vec::new() };
+    }
+    let mut found = Vec::new();
+    for (i, child) in obj.children.iter().enumerate() {
+        path.push(i);
+        found.extend(traces(child, label, path));
+        path.pop();
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_chart, parse_derivation, replay_workspace, GrammarOptions, RelativeClauseAnalysis};
+
+    #[test]
+    fn test_round_trip() {
+        for (sentence, relative_clauses) in [
+            ("the student left", RelativeClauseAnalysis::OperatorMovement),
+            ("the student who left smiled", RelativeClauseAnalysis::OperatorMovement),
+            ("the student who left smiled", RelativeClauseAnalysis::Promotion),
+        ] {
+            let options = GrammarOptions { relative_clauses, ..GrammarOptions::default() };
+            let lexicon = options.lexicon();
+            let parse = parse_derivation(sentence, &lexicon, &options).unwrap();
+
+            // Replaying the parse's operations and recovering them from the
+            // derived tree give derivations that evaluate back to it
+            let tokens: Vec<&str> = sentence.split_whitespace().collect();
+            let replayed = DerivationTree::replay(&replay_workspace(&tokens, &lexicon, &parse.derivation).unwrap(), &parse.derivation).unwrap();
+            assert_eq!(replayed.derived(), Ok(parse.tree.clone()));
+            let recovered = DerivationTree::of(&parse.tree).unwrap();
+            assert_eq!(recovered.derived(), Ok(parse.tree.clone()));
+        }
+    }
+
+    #[test]
+    fn test_operations_at_the_nodes() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let parse = parse_derivation("the student left", &lexicon, &GrammarOptions::default()).unwrap();
+        let tree = DerivationTree::of(&parse.tree).unwrap();
+        assert_eq!(tree.to_string(), "[Merge left [Merge the student]]");
+        let phons: Vec<&str> = tree.items().iter().map(|item| item.phon.as_str()).collect();
+        assert_eq!(phons, ["left", "the", "student"]);
+
+        // Movement is a node of its own; the mover is merged in its base position
+        let parse = parse_derivation("the student who left", &lexicon, &GrammarOptions::default()).unwrap();
+        let tree = DerivationTree::of(&parse.tree).unwrap();
+        assert_eq!(tree.to_string(), "[Merge the [Adjoin student [Move [Merge ε [Merge left who]]]]]");
+    }
+
+    #[test]
+    fn test_ambiguous_trees_are_read_directly() {
+        let mut lexicon = GrammarOptions::default().lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        let sentence = "the student who left smiled near the teacher near the tutor near the student near the teacher";
+        let trees = parse_chart(sentence, &lexicon, &GrammarOptions::default()).unwrap();
+        assert!(trees.len() > 10);
+        for tree in &trees {
+            assert_eq!(DerivationTree::of(tree).unwrap().derived().as_ref(), Ok(tree));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Derivation-Order Determinism
+//!
+//! The search tries operations in the order `available_operations` lists
+//! them (merges first, then adjunction, silent heads and movement), and a
+//! single `step` simply takes the first. Neither order is part of the
+//! grammar, so which analyses a sentence receives, and which one a ranker
+//! prefers, should not depend on it. `check_order_independence` reruns the
+//! full search with the lexicon's silent heads and the operations at every
+//! branch shuffled from a seed, and reports the first seed whose outcome
+//! differs from the unshuffled search.
+//!
+//! Overt items keep their lexicon order: each token maps to the first item
+//! with its phonology, so reordering them would change the input rather
+//! than the search.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+use crate::{forest, DerivationError, GrammarOptions, LexItem, Parse, ParseRanker};
+
+/// How a shuffled search departed from the unshuffled one
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// One search converged and the other failed, or they failed differently
+    Outcome {
+        /// Outcome in the default order
+        expected: Result<usize, DerivationError>,
+        /// Outcome in the shuffled order
+        found: Result<usize, DerivationError>,
+    },
+    /// The searches found different analyses (bracketed)
+    Analyses {
+        /// Found only in the default order
+        missing: Vec<String>,
+        /// Found only in the shuffled order
+        extra: Vec<String>,
+    },
+    /// The ranker's top-scoring analyses differ (bracketed, in found order)
+    Best {
+        /// Best analyses in the default order
+        expected: Vec<String>,
+        /// Best analyses in the shuffled order
+        found: Vec<String>,
+    },
+}
+
+/// Seed whose search order changed the result
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderDependence {
+    /// Seed the orders were shuffled from
+    pub seed: u64,
+    /// What changed
+    pub divergence: Divergence,
+}
+
+impl fmt::Display for OrderDependence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "seed {}: ", self.seed)?;
+        match &self.divergence {
+            Divergence::Outcome { expected, found } => {
+                write!(f, "search outcome {:?} became {:?}", expected, found)
+            }
+            Divergence::Analyses { missing, extra } => {
+                write!(f, "lost analyses {:?}, gained {:?}", missing, extra)
+            }
+            Divergence::Best { expected, found } => {
+                write!(f, "best analyses {:?} became {:?}", expected, found)
+            }
+        }
+    }
+}
+
+/// Check that the analyses of `sentence`, and the ones `ranker` scores
+/// best, are the same whatever order the search tries operations in
+///
+/// Returns the number of analyses on success. Every seed is checked against
+/// the unshuffled search; ties for the best score are compared as sets.
+pub fn check_order_independence(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    ranker: &dyn ParseRanker,
+    seeds: impl IntoIterator<Item = u64>,
+) -> Result<usize, OrderDependence> {
+    let expected = forest(sentence, lexicon, options, ranker, None);
+
+    for seed in seeds {
+        // Zero is a fixed point of xorshift
+        let mut state = seed.max(1);
+        let mut shuffled: Vec<LexItem> = lexicon.to_vec();
+        let silent: Vec<usize> = (0..shuffled.len()).filter(|&i| shuffled[i].phon.is_empty()).collect();
+        let mut order = silent.clone();
+        shuffle(&mut order, &mut state);
+        for (&slot, &item) in silent.iter().zip(&order) {
+            shuffled[slot] = lexicon[item].clone();
+        }
+
+        let found = forest(sentence, &shuffled, options, ranker, Some(state));
+        if let Some(divergence) = compare(&expected, &found) {
+            return Err(OrderDependence { seed, divergence });
+        }
+    }
+    expected.map_or(Ok(0), |parses| Ok(parses.len()))
+}
+
+fn compare(expected: &Result<Vec<Parse>, DerivationError>, found: &Result<Vec<Parse>, DerivationError>) -> Option<Divergence> {
+    let (expected, found) = match (expected, found) {
+        (Ok(expected), Ok(found)) => (expected, found),
+        (Err(a), Err(b)) if a == b => return None,
+        _ => {
+            return Some(Divergence::Outcome {
+                expected: expected.as_ref().map(Vec::len).map_err(|e| *e),
+                found: found.as_ref().map(Vec::len).map_err(|e| *e),
+            })
+        }
+    };
+
+    let missing = only_in(expected, found);
+    let extra = only_in(found, expected);
+    if !missing.is_empty() || !extra.is_empty() {
+        return Some(Divergence::Analyses { missing, extra });
+    }
+
+    let (best_expected, best_found) = (best(expected), best(found));
+    let same = best_expected.len() == best_found.len()
+        && best_expected.iter().all(|parse| best_found.iter().any(|other| other.tree == parse.tree));
+    if !same {
+        let bracketed = |parses: Vec<&Parse>| parses.iter().map(|parse| parse.tree.bracketed()).collect();
+        return Some(Divergence::Best { expected: bracketed(best_expected), found: bracketed(best_found) });
+    }
+    None
+}
+
+/// Analyses in `parses` whose tree is not in `others`, bracketed
+fn only_in(parses: &[Parse], others: &[Parse]) -> Vec<String> {
+    parses.iter()
+        .filter(|parse| !others.iter().any(|other| other.tree == parse.tree))
+        .map(|parse| parse.tree.bracketed())
+        .collect()
+}
+
+/// Analyses sharing the top score (`parse_forest` sorts best first)
+fn best(parses: &[Parse]) -> Vec<&Parse> {
+    parses.iter().take_while(|parse| parse.score == parses[0].score).collect()
+}
+
+/// Fisher–Yates shuffle driven by a xorshift generator
+pub fn shuffle<T>(items: &mut [T], state: &mut u64) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, (xorshift(state) % (i as u64 + 1)) as usize);
+    }
+}
+
+/// Scramble a seed with the splitmix64 finalizer, so consecutive seeds
+/// start a generator in unrelated states
+pub fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Advance a xorshift generator, whose state must not be zero
+pub fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category, Feature, FewestOperations, Operation, RelativeClauseAnalysis, SyntacticObject, Unranked};
+
+    #[test]
+    fn test_search_is_order_independent() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        for sentence in ["the student left", "the student thinks the teacher", "the left"] {
+            for ranker in [&Unranked as &dyn ParseRanker, &FewestOperations] {
+                let checked = check_order_independence(sentence, &lexicon, &options, ranker, 1..=8);
+                assert!(checked.is_ok(), "{}: {}", sentence, checked.unwrap_err());
+            }
+        }
+
+        for analysis in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let options = GrammarOptions { relative_clauses: analysis, ..GrammarOptions::default() };
+            let checked = check_order_independence(
+                "the student who left smiled",
+                &options.lexicon(),
+                &options,
+                &FewestOperations,
+                1..=4,
+            );
+            assert!(checked.is_ok_and(|analyses| analyses > 0), "{:?}", analysis);
+        }
+    }
+
+    #[test]
+    fn test_reports_order_dependent_rankers() {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        let sentence = "the student thinks the teacher near the tutor";
+        let options = GrammarOptions::default();
+        assert_eq!(check_order_independence(sentence, &lexicon, &options, &Unranked, 1..=8), Ok(2));
+
+        // Scoring the derivation the search happened to keep, not the tree
+        let eager = |_: &SyntacticObject, trace: &[Operation]| match trace.first() {
+            Some(&Operation::Merge(i, _)) => i as f64,
+            _ => 0.0,
+        };
+        let dependence = check_order_independence(sentence, &lexicon, &options, &eager, 1..=16).unwrap_err();
+        assert!(matches!(dependence.divergence, Divergence::Best { .. }), "{}", dependence);
+
+        let mut shuffled = [1, 2, 3, 4, 5];
+        shuffle(&mut shuffled, &mut 7);
+        shuffled.sort();
+        assert_eq!(shuffled, [1, 2, 3, 4, 5]);
+    }
+}
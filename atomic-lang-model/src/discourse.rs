@@ -0,0 +1,266 @@
+//! Discourse Reference
+//!
+//! An optional layer over a batch of parses that carries referential
+//! indices from sentence to sentence. Every maximal DP is a mention. A full
+//! DP introduces a new referent; a pronoun takes the index of the most
+//! salient earlier mention whose phi-features it agrees with, or a new one
+//! if there is none. Salience follows simple rules: a mention in a more
+//! recent sentence beats an older one, a subject beats a non-subject, and
+//! an earlier mention beats a later one in the same sentence. Only earlier
+//! sentences supply antecedents, so binding within a sentence is left to
+//! the grammar.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use core::cmp::Reverse;
+
+use crate::lexicon::json_string;
+use crate::{Category, ParseOutcome, Phi, PhiNode, SyntacticObject};
+
+/// A DP in the discourse
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mention {
+    /// Position of its sentence in the batch
+    pub sentence: usize,
+    /// The DP as pronounced
+    pub text: String,
+    /// Referential index, shared by coreferent mentions (1-based)
+    pub index: usize,
+    /// Whether the DP is a listed pronoun
+    pub pronoun: bool,
+    /// Whether the DP is the specifier of a verb
+    pub subject: bool,
+    /// Agreement features
+    pub phi: Phi,
+}
+
+/// Mentions sharing a referential index, in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain {
+    /// The shared index
+    pub index: usize,
+    /// The coreferent mentions
+    pub mentions: Vec<Mention>,
+}
+
+/// Referents of a batch of sentences, added in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct Discourse {
+    pronouns: Vec<(String, Phi)>,
+    nouns: Vec<(String, Phi)>,
+    mentions: Vec<Mention>,
+    sentences: usize,
+}
+
+impl Default for Discourse {
+    /// English third-person pronouns, every noun third person singular
+    fn default() -> Self {
+        let singular = |gender| Phi::THIRD_SINGULAR.with(gender);
+        Self::new()
+            .pronoun("he", singular(PhiNode::Masculine))
+            .pronoun("him", singular(PhiNode::Masculine))
+            .pronoun("she", singular(PhiNode::Feminine))
+            .pronoun("her", singular(PhiNode::Feminine))
+            .pronoun("it", singular(PhiNode::Neuter))
+            .pronoun("they", Phi::plural(3))
+            .pronoun("them", Phi::plural(3))
+    }
+}
+
+impl Discourse {
+    /// Empty discourse with no pronouns listed
+    pub fn new() -> Self {
+        Self { pronouns: Vec::new(), nouns: Vec::new(), mentions: Vec::new(), sentences: 0 }
+    }
+
+    /// The discourse with a pronoun's features set
+    pub fn pronoun(mut self, form: &str, phi: Phi) -> Self {
+        self.pronouns.retain(|(listed, _)| listed != form);
+        self.pronouns.push((form.to_string(), phi));
+        self
+    }
+
+    /// The discourse with the features of DPs headed by the noun `word`
+    /// set (unlisted DPs are third person singular)
+    pub fn noun(mut self, word: &str, phi: Phi) -> Self {
+        self.nouns.retain(|(listed, _)| listed != word);
+        self.nouns.push((word.to_string(), phi));
+        self
+    }
+
+    /// Add the next sentence's analysis (`None` if it failed to parse),
+    /// returning its mentions
+    pub fn add(&mut self, tree: Option<&SyntacticObject>) -> &[Mention] {
+        let sentence = self.sentences;
+        self.sentences += 1;
+        let first = self.mentions.len();
+        let mut found = Vec::new();
+        if let Some(tree) = tree {
+            dps(tree, false, &mut found);
+        }
+        for (dp, subject) in found {
+            let text = dp.linearize();
+            if text.is_empty() {
+                continue;
+            }
+            let pronoun = self.pronouns.iter().find(|(form, _)| *form == text).map(|(_, phi)| *phi);
+            let phi = pronoun.unwrap_or_else(|| {
+                head_noun(dp)
+                    .and_then(|noun| self.nouns.iter().find(|(listed, _)| listed == noun))
+                    .map_or(Phi::THIRD_SINGULAR, |(_, phi)| *phi)
+            });
+            let antecedent = pronoun.and_then(|_| {
+                self.mentions[..first].iter()
+                    .enumerate()
+                    .filter(|(_, m)| m.phi.subsumes(&phi) || phi.subsumes(&m.phi))
+                    .max_by_key(|&(i, m)| (m.sentence, m.subject, Reverse(i)))
+                    .map(|(_, m)| m.index)
+            });
+            let index = antecedent.unwrap_or_else(|| self.mentions.iter().map(|m| m.index).max().unwrap_or(0) + 1);
+            self.mentions.push(Mention { sentence, text, index, pronoun: pronoun.is_some(), subject, phi });
+        }
+        &self.mentions[first..]
+    }
+
+    /// Add the analyses of a batch, failed parses included as sentences
+    /// without mentions
+    pub fn add_outcomes(&mut self, outcomes: &[ParseOutcome]) {
+        for outcome in outcomes {
+            self.add(outcome.result.as_ref().ok().map(|parse| &parse.tree));
+        }
+    }
+
+    /// Every mention so far, in order
+    pub fn mentions(&self) -> &[Mention] {
+        &self.mentions
+    }
+
+    /// Coreference chains, by index
+    pub fn chains(&self) -> Vec<Chain> {
+        let mut chains: Vec<Chain> = Vec::new();
+        for mention in &self.mentions {
+            match chains.iter_mut().find(|chain| chain.index == mention.index) {
+                Some(chain) => chain.mentions.push(mention.clone()),
+                None => chains.push(Chain { index: mention.index, mentions: Vec::from([mention.clone()]) }),
+            }
+        }
+        chains.sort_by_key(|chain| chain.index);
+        chains
+    }
+
+    /// Export the coreference chains as JSON, one mention per line
+    pub fn to_json(&self) -> String {
+        let chains: Vec<String> = self.chains().iter()
+            .map(|chain| {
+                let mentions: Vec<String> = chain.mentions.iter()
+                    .map(|m| format!(
+                        "      {{ \"sentence\": {}, \"text\": {}, \"pronoun\": {} }}",
+                        m.sentence,
+                        json_string(&m.text),
+                        m.pronoun
+                    ))
+                    .collect();
+                format!("    {{ \"index\": {}, \"mentions\": [\n{}\n    ] }}", chain.index, mentions.join(",\n"))
+            })
+            .collect();
+        format!("{{\n  \"sentences\": {},\n  \"chains\": [\n{}\n  ]\n}}\n", self.sentences, chains.join(",\n"))
+    }
+}
+
+/// Maximal DPs in linear order, with whether each is a verb's specifier
+fn dps<'t>(node: &'t SyntacticObject, subject: bool, out: &mut Vec<(&'t SyntacticObject, bool)>) {
+    if node.label.head() == Category::D {
+        out.push((node, subject));
+        return;
+    }
+    let verbal = node.label.head() == Category::V;
+    for (i, child) in node.children.iter().enumerate() {
+        dps(child, verbal && i == 0 && node.children.len() == 2, out);
+    }
+}
+
+/// The noun heading a DP: the head of the determiner's complement,
+/// followed down the projecting daughters past adjuncts and specifiers
+fn head_noun(dp: &SyntacticObject) -> Option<&str> {
+    let mut node = dp;
+    while !node.children.is_empty() {
+        let head = node.head_index()?;
+        node = match &node.children[head] {
+            determiner if determiner.children.is_empty() && determiner.label.head() == Category::D => node.children.get(1 - head)?,
+            projection => projection,
+        };
+    }
+    node.phon.as_deref().filter(|_| node.label.head() == Category::N)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, Feature, LexItem};
+
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        for pronoun in ["he", "she", "they"] {
+            lexicon.push(LexItem::new(pronoun, &[Feature::Cat(Category::D)]));
+        }
+        lexicon.push(LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]));
+        lexicon
+    }
+
+    fn track(discourse: &mut Discourse, sentences: &[&str]) {
+        let lexicon = lexicon();
+        for sentence in sentences {
+            discourse.add(Some(&parse_sentence(sentence, &lexicon).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_pronouns_resolve_by_salience_and_agreement() {
+        // The most recent subject wins
+        let mut discourse = Discourse::default();
+        track(&mut discourse, &["the student left", "the teacher thinks the tutor", "he smiled"]);
+        let he = discourse.mentions().last().unwrap();
+        assert!(he.pronoun);
+        assert_eq!(he.index, discourse.mentions()[1].index);
+        assert_eq!(discourse.mentions()[1].text, "the teacher");
+
+        // Gender rules the subject out, and recency picks the object
+        let mut discourse = Discourse::default().noun("teacher", Phi::THIRD_SINGULAR.with(PhiNode::Feminine));
+        track(&mut discourse, &["the student left", "the teacher thinks the tutor", "he smiled"]);
+        assert_eq!(discourse.mentions().last().unwrap().index, discourse.mentions()[2].index);
+
+        // Nothing agrees with a plural, so it starts a referent of its own
+        let mut discourse = Discourse::default();
+        track(&mut discourse, &["the student left", "they smiled"]);
+        assert_eq!(discourse.mentions()[1].index, 2);
+    }
+
+    #[test]
+    fn test_features_come_from_the_head_noun() {
+        let feminine = Phi::THIRD_SINGULAR.with(PhiNode::Feminine);
+        let mut discourse = Discourse::default().noun("teacher", feminine);
+        track(&mut discourse, &["the student near the teacher left", "the teacher near the student left"]);
+        let texts: Vec<(&str, Phi)> = discourse.mentions().iter()
+            .filter(|m| m.subject)
+            .map(|m| (m.text.as_str(), m.phi))
+            .collect();
+        assert_eq!(texts, [("the student near the teacher", Phi::THIRD_SINGULAR), ("the teacher near the student", feminine)]);
+    }
+
+    #[test]
+    fn test_chains_export_as_json() {
+        let mut discourse = Discourse::default();
+        track(&mut discourse, &["the student left", "he thinks the teacher"]);
+        discourse.add(None);
+        track(&mut discourse, &["he smiled"]);
+
+        let chains = discourse.chains();
+        assert_eq!(chains.len(), 2);
+        let texts: Vec<(usize, &str)> = chains[0].mentions.iter().map(|m| (m.sentence, m.text.as_str())).collect();
+        assert_eq!(texts, [(0, "the student"), (1, "he"), (3, "he")]);
+        assert!(discourse.to_json().starts_with(
+            "{\n  \"sentences\": 4,\n  \"chains\": [\n    { \"index\": 1, \"mentions\": [\n      { \"sentence\": 0, \"text\": \"the student\", \"pronoun\": false },"
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+//! Empty Category Principle
+//!
+//! Traces must be properly governed (Chomsky 1981, Lasnik & Saito 1984):
+//! either lexically, as the complement of a lexical head, or by their
+//! antecedent through a chain of free clause edges. Objects satisfy the first
+//! clause and subjects depend on the second, which yields the subject/object
+//! asymmetries of extraction out of `that`-clauses and wh-islands.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+
+use crate::constraints::{chains, has_open_edge, head_leaf, node_at, Chain};
+use crate::{Category, DerivationError, SyntacticObject};
+
+/// Empty Category Principle with configurable proper government
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ecp {
+    /// Heads that lexically govern their complements
+    pub lexical_governors: Vec<Category>,
+    /// Whether antecedent government licenses traces that are not lexically governed
+    pub antecedent_government: bool,
+    /// Category whose maximal projections are barriers unless their edge is free
+    pub clause: Category,
+    /// Complementizers that block antecedent government of the subject they govern
+    pub blocking_complementizers: Vec<String>,
+    /// Complementizers that occupy the edge of their clause
+    pub wh_complementizers: Vec<String>,
+}
+
+impl Ecp {
+    /// Name reported in constraint violations
+    pub const NAME: &'static str = "ECP";
+
+    /// Standard English setting: V and P govern lexically, and an overt
+    /// `that` intervenes between a subject trace and its antecedent
+    pub fn english() -> Self {
+        Self {
+            lexical_governors: vec![Category::V, Category::P],
+            antecedent_government: true,
+            clause: Category::C,
+            blocking_complementizers: vec!["that".to_string()],
+            wh_complementizers: vec!["whether".to_string(), "if".to_string()],
+        }
+    }
+
+    /// Check whether the trace is the complement of a lexical governor
+    pub fn lexically_governed(&self, tree: &SyntacticObject, trace: &[usize]) -> bool {
+        let Some((&index, parent)) = trace.split_last() else {
+            return false;
+        };
+        let parent = node_at(tree, parent);
+        parent.head_index().is_some_and(|head| {
+            let governor = &parent.children[head];
+            head < index
+                && governor.children.is_empty()
+                && self.lexical_governors.contains(&governor.label.head())
+        })
+    }
+
+    /// Check whether the trace is the subject of the complement of a
+    /// blocking complementizer, a closer governor than the antecedent
+    fn governed_by_blocking_head(&self, tree: &SyntacticObject, trace: &[usize]) -> bool {
+        let [.., complement, 0] = trace else {
+            return false;
+        };
+        let clause = node_at(tree, &trace[..trace.len() - 1]);
+        let selector = node_at(tree, &trace[..trace.len() - 2]);
+        clause.head_index() == Some(1)
+            && selector.head_index().is_some_and(|head| head < *complement)
+            && selector.label.head() == self.clause
+            && head_leaf(selector).phon.as_ref().is_some_and(|phon| self.blocking_complementizers.contains(phon))
+    }
+
+    /// Check whether the antecedent governs the trace through free clause edges
+    pub fn antecedent_governed(&self, tree: &SyntacticObject, chain: &Chain) -> bool {
+        if self.governed_by_blocking_head(tree, &chain.trace) {
+            return false;
+        }
+
+        // Every clause between the trace and the landing site must offer its
+        // edge to an intermediate trace
+        (chain.landing.len() + 2..chain.trace.len()).all(|depth| {
+            let node = node_at(tree, &chain.trace[..depth]);
+            let parent = node_at(tree, &chain.trace[..depth - 1]);
+            let maximal = parent.head_index() != Some(chain.trace[depth - 1]);
+            !maximal || node.label.head() != self.clause || has_open_edge(node, &self.wh_complementizers)
+        })
+    }
+
+    /// Check whether a chain's trace is properly governed
+    pub fn properly_governed(&self, tree: &SyntacticObject, chain: &Chain) -> bool {
+        self.lexically_governed(tree, &chain.trace)
+            || (self.antecedent_government && self.antecedent_governed(tree, chain))
+    }
+
+    /// Reject trees containing a trace that is not properly governed
+    pub fn check(&self, tree: &SyntacticObject) -> Result<(), DerivationError> {
+        if chains(tree).iter().all(|chain| self.properly_governed(tree, chain)) {
+            Ok(())
+        } else {
+            Err(DerivationError::ConstraintViolation(Self::NAME))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_with_options, GrammarOptions};
+
+    #[test]
+    fn test_relative_subject_trace() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let tree = crate::parse_sentence("the student who left", &lexicon).unwrap();
+        let chain = &chains(&tree)[0];
+
+        // The subject trace is not lexically governed but its antecedent is local
+        let ecp = Ecp::english();
+        assert!(!ecp.lexically_governed(&tree, &chain.trace));
+        assert!(ecp.antecedent_governed(&tree, chain));
+        assert!(ecp.check(&tree).is_ok());
+
+        let lexical_only = GrammarOptions {
+            ecp: Some(Ecp { antecedent_government: false, ..Ecp::english() }),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_with_options("the student who left", &lexicon, &lexical_only),
+            Err(DerivationError::ConstraintViolation(Ecp::NAME))
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! Rejection Explanations
+//!
+//! Says why a sentence was rejected, in terms a student can act on: which
+//! word needed what, and what it found instead. The derivation search is
+//! rerun to find its furthest dead end (the workspace with the fewest
+//! objects left when no operation applies), and each object's first
+//! unchecked feature is read off as a reason:
+//!
+//! - a selector (`=DP`, `D=`) whose argument is missing or of the wrong category
+//! - an adjunct (`~N`) with no host of its category
+//! - a licensor (`+1`) with no phrase to attract, or a licensee (`-1`) that
+//!   nothing attracts
+//! - a complete phrase that nothing selects
+//!
+//! Derivations that converge but are filtered out are explained by the
+//! constraint that filtered them, or by the word order they yield.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeSet, format, string::{String, ToString}, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use core::fmt;
+
+use crate::constraints::head_leaf;
+use crate::{
+    apply_operation, available_operations, begin_step, find_mover_path, parse_with_options, sentence_workspaces, Category,
+    DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject, Workspace,
+};
+
+/// Derivation steps explored before a branch is abandoned, as in parsing
+const MAX_STEPS: usize = 100;
+
+/// One cause of a rejection
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    /// A word is not in the lexicon
+    UnknownWord(String),
+    /// A head's selector found no argument of its category where it looks
+    MissingArgument {
+        /// Head word (empty for a silent head)
+        head: String,
+        /// Category of the head
+        label: Category,
+        /// Selector left unchecked
+        feature: Feature,
+        /// Neighbouring phrase on the selector's side, with its category
+        nearest: Option<(String, Category)>,
+    },
+    /// An adjunct found no host of its category before it
+    UnattachedAdjunct {
+        /// The adjunct phrase
+        phrase: String,
+        /// Adjunct feature left unchecked
+        feature: Feature,
+    },
+    /// A licensor found no phrase with a matching licensee to attract
+    NothingToAttract {
+        /// Head word (empty for a silent head)
+        head: String,
+        /// Category of the head
+        label: Category,
+        /// Licensor left unchecked
+        feature: Feature,
+    },
+    /// A phrase with a licensee was never attracted
+    StrandedMover {
+        /// The phrase that had to move
+        phrase: String,
+        /// Licensee left unchecked
+        feature: Feature,
+    },
+    /// A complete phrase that no head selects
+    Unselected {
+        /// The phrase
+        phrase: String,
+        /// Its category
+        label: Category,
+    },
+    /// Every word combines, but only in another order
+    WordOrder {
+        /// The order the derivation yields
+        derived: String,
+    },
+    /// A convergent derivation was ruled out by a grammar constraint
+    Constraint(&'static str),
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Reason::UnknownWord(word) => write!(f, "'{}' is not in the lexicon", word),
+            Reason::MissingArgument { head, label, feature, nearest } => {
+                let (cat, role, side) = match feature {
+                    Feature::Spec(cat) => (cat, "specifier", "left"),
+                    Feature::Sel(cat) => (cat, "complement", "right"),
+                    _ => return write!(f, "{} cannot check {}", word(head, label), feature),
+                };
+                write!(f, "{} requires {} {} {} to its {} ({})", word(head, label), article(cat), cat, role, side, feature)?;
+                match nearest {
+                    Some((phrase, found)) => write!(f, "; nearest candidate '{}' is {} {}", phrase, article(found), found),
+                    None if side == "left" => write!(f, "; nothing precedes it"),
+                    None => write!(f, "; nothing follows it"),
+                }
+            }
+            Reason::UnattachedAdjunct { phrase, feature } => match feature {
+                Feature::Adjoin(cat) => write!(f, "'{}' modifies {} {} ({}), but none precedes it", phrase, article(cat), cat, feature),
+                _ => write!(f, "'{}' cannot check {}", phrase, feature),
+            },
+            Reason::NothingToAttract { head, label, feature } => {
+                write!(f, "{} attracts a phrase marked -{} ({}), but none is pending", word(head, label), index(feature), feature)
+            }
+            Reason::StrandedMover { phrase, feature } => {
+                write!(f, "'{}' must move ({}), but nothing attracts it", phrase, feature)
+            }
+            Reason::Unselected { phrase, label } => {
+                write!(f, "'{}' is a complete {}, but nothing selects it", phrase, label)
+            }
+            Reason::WordOrder { derived } => write!(f, "the words only combine in the order '{}'", derived),
+            Reason::Constraint(name) => write!(f, "every derivation violates the {} constraint", name),
+        }
+    }
+}
+
+/// Quoted word, or a description of a silent head
+fn word(head: &str, label: &Category) -> String {
+    if head.is_empty() {
+        format!("a silent {}", label.head())
+    } else {
+        format!("'{}'", head)
+    }
+}
+
+fn article(cat: &Category) -> &'static str {
+    match cat.head() {
+        Category::N | Category::S => "an",
+        _ => "a",
+    }
+}
+
+fn index(feature: &Feature) -> u8 {
+    feature.movement_index().unwrap_or_default()
+}
+
+/// Why a sentence was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    /// Error the parser reported
+    pub error: DerivationError,
+    /// Causes, in surface order
+    pub reasons: Vec<Reason>,
+    /// Objects left in the furthest dead end, empty if no branch got stuck
+    /// short of a convergent derivation
+    pub stuck: Vec<SyntacticObject>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.reasons.is_empty() {
+            return write!(f, "{}", self.error);
+        }
+        let reasons: Vec<String> = self.reasons.iter().map(Reason::to_string).collect();
+        write!(f, "{}", reasons.join("; "))
+    }
+}
+
+/// Explain why `sentence` is rejected, or `None` if it parses
+pub fn explain(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Option<Explanation> {
+    let error = parse_with_options(sentence, lexicon, options).err()?;
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+
+    let unknown: Vec<Reason> = tokens.iter()
+        .filter(|&&token| !lexicon.iter().any(|item| item.phon == token))
+        .map(|&token| Reason::UnknownWord(token.to_string()))
+        .collect();
+    if !unknown.is_empty() {
+        return Some(Explanation { error, reasons: unknown, stuck: Vec::new() });
+    }
+
+    let Ok(workspaces) = sentence_workspaces(&tokens, lexicon) else {
+        return Some(Explanation { error, reasons: Vec::new(), stuck: Vec::new() });
+    };
+    let mut search = DeadEnds { tokens: &tokens, options, visited: BTreeSet::new(), stuck: None, derived: None, constraint: None };
+    workspaces.for_each(|workspace| search.explore(&workspace));
+
+    let stuck = search.stuck.unwrap_or_default();
+    let reasons = if let Some(name) = search.constraint {
+        vec![Reason::Constraint(name)]
+    } else if let Some(derived) = search.derived {
+        vec![Reason::WordOrder { derived }]
+    } else {
+        reasons(&stuck)
+    };
+    Some(Explanation { error, reasons, stuck })
+}
+
+/// Exhaustive search recording where derivations stop
+struct DeadEnds<'a> {
+    tokens: &'a [&'a str],
+    options: &'a GrammarOptions,
+    visited: BTreeSet<String>,
+    /// Dead end with the fewest objects left
+    stuck: Option<Vec<SyntacticObject>>,
+    /// Yield of the first convergent derivation in the wrong order
+    derived: Option<String>,
+    /// First constraint that ruled out a convergent derivation
+    constraint: Option<&'static str>,
+}
+
+impl DeadEnds<'_> {
+    fn explore(&mut self, workspace: &Workspace) {
+        if workspace.is_successful() {
+            let tree = &workspace.items()[0];
+            let linearized = tree.linearize();
+            if !linearized.split_whitespace().eq(self.tokens.iter().copied()) {
+                self.derived.get_or_insert(linearized);
+            } else if let Err(DerivationError::ConstraintViolation(name)) = self.options.check(tree) {
+                self.constraint.get_or_insert(name);
+            }
+            return;
+        }
+        if workspace.step_count >= MAX_STEPS || !self.visited.insert(format!("{:?}", workspace.items())) {
+            return;
+        }
+
+        let mut extended = false;
+        for op in available_operations(workspace) {
+            if !self.options.operations.allows(&op) {
+                continue;
+            }
+            let mut next = workspace.clone();
+            if begin_step(&mut next).and_then(|()| apply_operation(&mut next, op)).is_ok() {
+                extended = true;
+                self.explore(&next);
+            }
+        }
+        if !extended && self.stuck.as_ref().is_none_or(|stuck| workspace.items().len() < stuck.len()) {
+            self.stuck = Some(workspace.items().to_vec());
+        }
+    }
+}
+
+/// Reasons a dead-end workspace cannot go on, one per stuck object
+fn reasons(items: &[SyntacticObject]) -> Vec<Reason> {
+    let mut reasons = Vec::new();
+    let mut root = false;
+    for (i, item) in items.iter().enumerate() {
+        let leaf = head_leaf(item);
+        let head = leaf.phon.clone().unwrap_or_default();
+        let pending = item.features.iter().find(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_)));
+        let reason = match pending {
+            Some(feature @ (Feature::Sel(_) | Feature::Spec(_))) => {
+                let neighbour = match feature {
+                    Feature::Sel(_) => items.get(i + 1),
+                    _ => i.checked_sub(1).and_then(|j| items.get(j)),
+                };
+                Reason::MissingArgument {
+                    head,
+                    label: leaf.label.clone(),
+                    feature: feature.clone(),
+                    nearest: neighbour.map(|n| (n.linearize(), n.label.clone())),
+                }
+            }
+            Some(feature @ Feature::Pos(_)) => Reason::NothingToAttract { head, label: leaf.label.clone(), feature: feature.clone() },
+            Some(feature @ Feature::Neg(i)) => {
+                let path = find_mover_path(item, *i).unwrap_or_default();
+                let mover = path.iter().fold(item, |node, &j| &node.children[j]);
+                Reason::StrandedMover { phrase: mover.linearize(), feature: feature.clone() }
+            }
+            Some(feature @ Feature::Adjoin(_)) => Reason::UnattachedAdjunct { phrase: item.linearize(), feature: feature.clone() },
+            // The first complete object can be the root; the others need selecting
+            Some(Feature::Cat(_) | Feature::Agr(_)) | None if !root => {
+                root = true;
+                continue;
+            }
+            Some(Feature::Cat(_) | Feature::Agr(_)) | None => Reason::Unselected { phrase: item.linearize(), label: item.label.clone() },
+        };
+        reasons.push(reason);
+    }
+    reasons
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Ecp};
+
+    fn because(sentence: &str, lexicon: &[LexItem]) -> String {
+        explain(sentence, lexicon, &GrammarOptions::default()).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_grammatical_sentences_need_no_explanation() {
+        assert_eq!(explain("the student left", &test_lexicon(), &GrammarOptions::default()), None);
+    }
+
+    #[test]
+    fn test_missing_and_mismatched_arguments() {
+        let lexicon = test_lexicon();
+        assert_eq!(
+            because("left", &lexicon),
+            "'left' requires a D specifier to its left (D=); nothing precedes it"
+        );
+        assert_eq!(
+            because("the student thinks", &lexicon),
+            "'thinks' requires a DP complement to its right (=DP); nothing follows it"
+        );
+        assert_eq!(
+            because("the left", &lexicon),
+            "'the' requires an N complement to its right (=N); nearest candidate 'left' is a V; \
+             'left' requires a D specifier to its left (D=); nearest candidate 'the' is a D"
+        );
+        assert_eq!(because("the robot left", &lexicon), "'robot' is not in the lexicon");
+
+        let explanation = explain("the student the teacher", &lexicon, &GrammarOptions::default()).unwrap();
+        assert_eq!(explanation.error, DerivationError::NoValidOperations);
+        assert_eq!(explanation.reasons, [Reason::Unselected { phrase: "the teacher".to_string(), label: Category::DP }]);
+    }
+
+    #[test]
+    fn test_movement_and_adjunction() {
+        let mut lexicon = test_lexicon();
+        lexicon.extend([
+            LexItem::new("whom", &[Feature::Cat(Category::D), Feature::Neg(1)]),
+            LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]),
+        ]);
+        assert_eq!(
+            because("the student said the teacher", &lexicon),
+            "'said' attracts a phrase marked -1 (+1), but none is pending"
+        );
+        assert_eq!(because("whom left", &lexicon), "'whom' must move (-1), but nothing attracts it");
+        let silent = Reason::NothingToAttract { head: String::new(), label: Category::CP, feature: Feature::Pos(2) };
+        assert_eq!(silent.to_string(), "a silent C attracts a phrase marked -2 (+2), but none is pending");
+        assert_eq!(
+            because("near the teacher the student left", &lexicon),
+            "'near the teacher' modifies an N (~N), but none precedes it"
+        );
+    }
+
+    #[test]
+    fn test_filtered_derivations() {
+        let options = GrammarOptions { ecp: Some(Ecp::english()), ..Default::default() };
+        let lexicon = vec![
+            LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(1)]),
+            LexItem::new("do", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(1)]),
+            LexItem::new("that", &[Feature::Cat(Category::C), Feature::Sel(Category::V)]),
+            LexItem::new("you", &[Feature::Cat(Category::D)]),
+            LexItem::new("think", &[Feature::Cat(Category::V), Feature::Sel(Category::C), Feature::Spec(Category::D)]),
+            LexItem::new("left", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        ];
+        let explanation = explain("who do you think that left", &lexicon, &options).unwrap();
+        assert_eq!(explanation.reasons, [Reason::Constraint(Ecp::NAME)]);
+    }
+}
@@ -0,0 +1,201 @@
+//! Bitset Feature Checking
+//!
+//! `can_merge` and `can_adjoin` scan feature vectors, and the Shortest Move
+//! Constraint compares every licensee of one object with every feature of
+//! the other. Categories and movement indices are closed classes, so a
+//! bundle can be summarized once as bitsets and every later check against
+//! it becomes a few mask operations. The summary pays off where the same
+//! constituent is checked many times: the items of a workspace scanned for
+//! mergeable pairs, and the items of `parser::Chart`, which are combined
+//! with every other item and never change after they are added.
+//!
+//! A summary describes the bundle it was built from; rebuild it after
+//! editing an object's features.
+
+use crate::{agreement, AgrBundle, Category, Feature, SyntacticObject};
+
+/// Set of movement indices
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Indices([u64; 4]);
+
+impl Indices {
+    /// Add an index
+    pub fn insert(&mut self, index: u8) {
+        self.0[index as usize / 64] |= 1 << (index % 64);
+    }
+
+    /// Check whether an index is in the set
+    pub fn contains(&self, index: u8) -> bool {
+        self.0[index as usize / 64] & 1 << (index % 64) != 0
+    }
+
+    /// Check whether the two sets share an index
+    pub fn intersects(&self, other: &Indices) -> bool {
+        self.0.iter().zip(&other.0).any(|(a, b)| a & b != 0)
+    }
+
+    /// Check whether the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.0 == [0; 4]
+    }
+}
+
+/// Bit of a category's head
+fn head_bit(category: &Category) -> u8 {
+    1 << category.head() as u8
+}
+
+/// Feature bundle summarized for constant-time feature checks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FeatureSet {
+    /// Head of the first category feature, as a bit (0 for none)
+    category: u8,
+    /// Head selected by the first selector, as a bit (0 for none)
+    selector: u8,
+    /// Whether the first selector is a specifier selector
+    specifier: bool,
+    /// Heads of adjunct features' targets
+    adjoins: u8,
+    /// First positive feature's index, which triggers movement
+    trigger: Option<u8>,
+    /// Indices of positive features
+    licensors: Indices,
+    /// Indices of negative features
+    licensees: Indices,
+    /// Agreement probe of the first selector
+    probe: Option<AgrBundle>,
+    /// The bundle's own agreement features
+    agreement: Option<AgrBundle>,
+}
+
+impl FeatureSet {
+    /// Summarize a feature bundle
+    pub fn new(features: &[Feature]) -> Self {
+        let mut set = FeatureSet::default();
+        for feature in features {
+            match feature {
+                Feature::Cat(cat) if set.category == 0 => set.category = head_bit(cat),
+                Feature::Cat(_) => {}
+                Feature::Sel(cat) | Feature::Spec(cat) if set.selector == 0 => {
+                    set.selector = head_bit(cat);
+                    set.specifier = matches!(feature, Feature::Spec(_));
+                }
+                Feature::Sel(_) | Feature::Spec(_) => {}
+                Feature::Adjoin(cat) => set.adjoins |= head_bit(cat),
+                Feature::Pos(index) => {
+                    set.trigger.get_or_insert(*index);
+                    set.licensors.insert(*index);
+                }
+                Feature::Neg(index) => set.licensees.insert(*index),
+                Feature::Agr(_) => {}
+            }
+        }
+        set.probe = agreement::probe(features).copied();
+        set.agreement = agreement::agreement(features).copied();
+        set
+    }
+
+    /// Summarize an object's unchecked features
+    pub fn of(obj: &SyntacticObject) -> Self {
+        Self::new(&obj.features)
+    }
+
+    /// Position of the first category's head among the head bits
+    pub fn category_head(&self) -> Option<usize> {
+        (self.category != 0).then(|| self.category.trailing_zeros() as usize)
+    }
+
+    /// Position of the head the first selector selects among the head bits
+    pub fn selector_head(&self) -> Option<usize> {
+        (self.selector != 0).then(|| self.selector.trailing_zeros() as usize)
+    }
+
+    /// Check whether a selector is left
+    pub fn has_selector(&self) -> bool {
+        self.selector != 0
+    }
+
+    /// Check whether the first selector is a specifier selector
+    pub fn selects_specifier(&self) -> bool {
+        self.specifier
+    }
+
+    /// Index of the first positive feature
+    pub fn trigger(&self) -> Option<u8> {
+        self.trigger
+    }
+
+    /// Check whether a licensee `-index` is pending
+    pub fn has_licensee(&self, index: u8) -> bool {
+        self.licensees.contains(index)
+    }
+
+    /// Check whether any licensee is pending
+    pub fn has_licensees(&self) -> bool {
+        !self.licensees.is_empty()
+    }
+
+    /// Shortest Move Constraint: `other` brings no licensee `self` has
+    fn smc(&self, other: &FeatureSet) -> bool {
+        !self.licensees.intersects(&other.licensees)
+    }
+
+    /// Check whether the bundle can merge with `other`, as `can_merge` does
+    pub fn can_merge(&self, other: &FeatureSet) -> bool {
+        self.selector != 0
+            && other.selector == 0
+            && other.licensors.is_empty()
+            && other.category == self.selector
+            && self.probe.zip(other.agreement).is_none_or(|(probe, goal)| probe.agrees(&goal))
+            && self.smc(other)
+    }
+
+    /// Check whether `adjunct` can adjoin to the bundle, as `can_adjoin` does
+    pub fn can_adjoin(&self, adjunct: &FeatureSet) -> bool {
+        self.selector == 0
+            && adjunct.selector == 0
+            && self.category & adjunct.adjoins != 0
+            && self.smc(adjunct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::determinism::xorshift;
+    use crate::fuzz::FuzzInput;
+    use crate::{can_adjoin, can_merge, test_lexicon, LexItem};
+
+    #[test]
+    fn test_agrees_with_vector_checks() {
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let data: Vec<u8> = (0..4096).map(|_| xorshift(&mut state) as u8).collect();
+        let mut input = FuzzInput::new(&data);
+        let objects: Vec<SyntacticObject> = (0..60).map(|_| input.object(2)).collect();
+
+        let mut merges = 0;
+        for a in &objects {
+            for b in &objects {
+                let (set, other) = (FeatureSet::of(a), FeatureSet::of(b));
+                assert_eq!(set.can_merge(&other), can_merge(a, b), "{:?} {:?}", a.features, b.features);
+                assert_eq!(set.can_adjoin(&other), can_adjoin(a, b), "{:?} {:?}", a.features, b.features);
+                merges += set.can_merge(&other) as usize;
+            }
+        }
+        assert!(merges > 0);
+    }
+
+    #[test]
+    fn test_summary() {
+        let said = FeatureSet::new(&test_lexicon()[7].feats);
+        assert!(said.has_selector() && !said.selects_specifier());
+        assert_eq!(said.trigger(), Some(1));
+
+        let mover = FeatureSet::of(&SyntacticObject::from_lex(&LexItem::new(
+            "who",
+            &[Feature::Cat(Category::D), Feature::Neg(200), Feature::Neg(3)],
+        )));
+        assert!(mover.has_licensee(200) && mover.has_licensee(3) && !mover.has_licensee(4));
+        assert!(!FeatureSet::default().has_licensees());
+    }
+}
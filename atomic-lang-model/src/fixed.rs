@@ -0,0 +1,289 @@
+//! Fixed-Point Scoring
+//!
+//! Rankers score analyses with `f64`, whose results can differ between
+//! targets (x87 extended precision, fused multiply-add, soft-float on
+//! microcontrollers) and which some embedded targets lack entirely. This
+//! module mirrors the scoring API in Q32.32 fixed point: `Fixed` values are
+//! plain `i64`s, products round toward negative infinity, and every
+//! operation saturates, so a ranking is bit-for-bit the same everywhere.
+//!
+//! - `FixedRanker` is the fixed-point `ParseRanker`
+//! - `FixedRuleWeights` is the fixed-point `RuleWeights` inside score
+//! - `Economy` ranks by the fewest operations a derivation could take over
+//!   the operations it took
+//!
+//! `parse_forest_fixed` orders analyses by a `FixedRanker`; ties keep the
+//! order the search found them in, as in `parse_forest`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::ops::{Add, Mul, Neg, Sub};
+
+use crate::{
+    forest, Category, DerivationError, FewestOperations, GrammarOptions, LexItem, Operation, Parse, SyntacticObject,
+    Unranked,
+};
+
+/// Q32.32 fixed-point number
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Fixed(pub i64);
+
+impl Fixed {
+    /// Fractional bits
+    pub const FRACTION_BITS: u32 = 32;
+    /// Zero
+    pub const ZERO: Fixed = Fixed(0);
+    /// One
+    pub const ONE: Fixed = Fixed(1 << Self::FRACTION_BITS);
+
+    /// The integer `n`, saturating outside ±2³¹
+    pub const fn from_int(n: i64) -> Self {
+        Fixed(n.saturating_mul(1 << Self::FRACTION_BITS))
+    }
+
+    /// `numerator / denominator`, rounded toward negative infinity; a zero
+    /// denominator saturates by the numerator's sign
+    pub fn from_ratio(numerator: i64, denominator: i64) -> Self {
+        if denominator == 0 {
+            return match numerator.signum() {
+                1 => Fixed(i64::MAX),
+                -1 => Fixed(i64::MIN),
+                _ => Fixed::ZERO,
+            };
+        }
+        let (scaled, denominator) = ((numerator as i128) << Self::FRACTION_BITS, denominator as i128);
+        let quotient = scaled / denominator;
+        let inexact = scaled % denominator != 0;
+        saturate(if inexact && (scaled < 0) != (denominator < 0) { quotient - 1 } else { quotient })
+    }
+
+    /// Nearest `f64`, for display on targets that have one
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / Self::ONE.0 as f64
+    }
+}
+
+fn saturate(value: i128) -> Fixed {
+    Fixed(value.clamp(i64::MIN as i128, i64::MAX as i128) as i64)
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_add(other.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed(self.0.saturating_sub(other.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, other: Fixed) -> Fixed {
+        saturate((self.0 as i128 * other.0 as i128) >> Self::FRACTION_BITS)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+        Fixed(self.0.saturating_neg())
+    }
+}
+
+/// Decimal with four places, computed without floating point
+impl fmt::Display for Fixed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let magnitude = self.0.unsigned_abs();
+        let whole = magnitude >> Self::FRACTION_BITS;
+        let fraction = ((magnitude & (Self::ONE.0 as u64 - 1)) as u128 * 10_000) >> Self::FRACTION_BITS;
+        let sign = if self.0 < 0 { "-" } else { "" };
+        write!(f, "{}{}.{:04}", sign, whole, fraction)
+    }
+}
+
+/// Scores convergent analyses in fixed point; higher scores rank first
+pub trait FixedRanker {
+    /// Score a derived tree together with the operations that derived it
+    fn score(&self, tree: &SyntacticObject, trace: &[Operation]) -> Fixed;
+}
+
+impl<F: Fn(&SyntacticObject, &[Operation]) -> Fixed> FixedRanker for F {
+    fn score(&self, tree: &SyntacticObject, trace: &[Operation]) -> Fixed {
+        self(tree, trace)
+    }
+}
+
+impl FixedRanker for Unranked {
+    fn score(&self, _tree: &SyntacticObject, _trace: &[Operation]) -> Fixed {
+        Fixed::ZERO
+    }
+}
+
+impl FixedRanker for FewestOperations {
+    fn score(&self, _tree: &SyntacticObject, trace: &[Operation]) -> Fixed {
+        -Fixed::from_int(trace.len() as i64)
+    }
+}
+
+/// Prefers economical derivations, scoring the fewest operations any
+/// derivation of the tree's words needs (one per word joined) over the
+/// operations taken, at most one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Economy;
+
+impl FixedRanker for Economy {
+    fn score(&self, tree: &SyntacticObject, trace: &[Operation]) -> Fixed {
+        let needed = tree.linearize().split_whitespace().count().saturating_sub(1);
+        match trace.len() {
+            0 => Fixed::ONE,
+            taken => Fixed::from_ratio(needed.min(taken) as i64, taken as i64),
+        }
+    }
+}
+
+/// Fixed-point inside scores from weighted local trees: the product of the
+/// weights of the (parent, left, right) label triples a tree contains
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedRuleWeights {
+    rules: Vec<(Category, Category, Category, Fixed)>,
+    /// Weight of a triple with no weight of its own
+    pub default: Fixed,
+}
+
+impl FixedRuleWeights {
+    /// Weights with every triple at `default`
+    pub fn new(default: Fixed) -> Self {
+        Self { rules: Vec::new(), default }
+    }
+
+    /// The weights with one triple's weight set
+    pub fn rule(mut self, parent: Category, left: Category, right: Category, weight: Fixed) -> Self {
+        self.rules.retain(|(p, l, r, _)| (p, l, r) != (&parent, &left, &right));
+        self.rules.push((parent, left, right, weight));
+        self
+    }
+
+    /// Weight of a local tree
+    pub fn weight(&self, parent: &Category, left: &Category, right: &Category) -> Fixed {
+        self.rules.iter()
+            .find(|(p, l, r, _)| (p, l, r) == (parent, left, right))
+            .map_or(self.default, |rule| rule.3)
+    }
+
+    /// Inside score of a constituent
+    pub fn inside(&self, constituent: &SyntacticObject) -> Fixed {
+        match constituent.children.as_slice() {
+            [left, right] => {
+                self.weight(&constituent.label, &left.label, &right.label) * self.inside(left) * self.inside(right)
+            }
+            _ => Fixed::ONE,
+        }
+    }
+}
+
+impl FixedRanker for FixedRuleWeights {
+    fn score(&self, tree: &SyntacticObject, _trace: &[Operation]) -> Fixed {
+        self.inside(tree)
+    }
+}
+
+/// Analysis with its fixed-point score
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedParse {
+    /// The analysis (its `score` is left at zero)
+    pub parse: Parse,
+    /// Score assigned by the ranker
+    pub score: Fixed,
+}
+
+/// Enumerate every analysis of a sentence, best first according to a
+/// fixed-point `ranker`
+pub fn parse_forest_fixed(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    ranker: &dyn FixedRanker,
+) -> Result<Vec<FixedParse>, DerivationError> {
+    let mut parses: Vec<FixedParse> = forest(sentence, lexicon, options, &Unranked, None)?
+        .into_iter()
+        .map(|parse| FixedParse { score: ranker.score(&parse.tree, &parse.derivation), parse })
+        .collect();
+    // Stable, so ties keep the search order
+    parses.sort_by_key(|parse| core::cmp::Reverse(parse.score));
+    Ok(parses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_forest, test_lexicon, Feature};
+
+    #[test]
+    fn test_arithmetic() {
+        let half = Fixed::from_ratio(1, 2);
+        assert_eq!(half + half, Fixed::ONE);
+        assert_eq!(half * half, Fixed::from_ratio(1, 4));
+        assert_eq!(Fixed::ONE - half, half);
+        assert_eq!(Fixed::from_ratio(-1, 3), -Fixed::from_ratio(1, 3) - Fixed(1));
+        assert_eq!(Fixed::from_ratio(1, -3), Fixed::from_ratio(-1, 3));
+        assert_eq!(Fixed::from_ratio(1, 0), Fixed(i64::MAX));
+        assert_eq!(Fixed(i64::MAX) + Fixed::ONE, Fixed(i64::MAX));
+        assert_eq!(Fixed::from_int(1 << 40) * Fixed::from_int(1 << 40), Fixed(i64::MAX));
+
+        assert_eq!(Fixed::from_ratio(3, 4).to_string(), "0.7500");
+        assert_eq!(Fixed::from_ratio(-5, 2).to_string(), "-2.5000");
+        assert_eq!(Fixed::from_ratio(2, 3), Fixed(2_863_311_530));
+        assert_eq!(Fixed::from_ratio(3, 4).to_f64(), 0.75);
+    }
+
+    #[test]
+    fn test_fixed_ranking_matches_float_ranking() {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        let sentence = "the student thinks the teacher near the tutor";
+        let options = GrammarOptions::default();
+
+        // Prefer high attachment: the PP as a sister of the clause
+        let high = |tree: &SyntacticObject, _: &[Operation]| {
+            if tree.children[1].label == Category::PP { Fixed::ONE } else { Fixed::ZERO }
+        };
+        let fixed = parse_forest_fixed(sentence, &lexicon, &options, &high).unwrap();
+        let float = parse_forest(sentence, &lexicon, &options, &|tree: &SyntacticObject, trace: &[Operation]| {
+            high(tree, trace).to_f64()
+        }).unwrap();
+        assert_eq!(fixed.len(), 2);
+        assert!(fixed.iter().zip(&float).all(|(fixed, float)| fixed.parse.tree == float.tree));
+        assert_eq!(fixed[0].score, Fixed::ONE);
+
+        // Ties keep the search order
+        let unranked = parse_forest_fixed(sentence, &lexicon, &options, &Unranked).unwrap();
+        let order = parse_forest(sentence, &lexicon, &options, &Unranked).unwrap();
+        assert!(unranked.iter().zip(&order).all(|(fixed, float)| fixed.parse.tree == float.tree));
+    }
+
+    #[test]
+    fn test_weights_and_economy() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        let tree = crate::parse_sentence("the student thinks the teacher", &lexicon).unwrap();
+
+        let weights = FixedRuleWeights::new(Fixed::ONE).rule(Category::VP, Category::V, Category::DP, Fixed::from_ratio(1, 2));
+        assert_eq!(weights.inside(&tree), Fixed::from_ratio(1, 2));
+
+        let parses = parse_forest_fixed("the student thinks the teacher", &lexicon, &options, &Economy).unwrap();
+        assert_eq!(parses[0].score, Fixed::ONE);
+        assert_eq!(Economy.score(&tree, &[Operation::Move(0); 8]), Fixed::from_ratio(1, 2));
+    }
+}
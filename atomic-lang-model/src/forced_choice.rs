@@ -0,0 +1,119 @@
+//! Forced-Choice Evaluation
+//!
+//! Linzen- and Gulordava-style evaluations of language models do not ask
+//! whether the ungrammatical member of a minimal pair is rejected outright,
+//! only whether the model prefers the grammatical one. `forced_choice`
+//! grades both members with an `Acceptability` scorer and reports which it
+//! prefers. Accuracy counts a tie as half right, the expected score of a
+//! coin flip.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{Acceptability, GrammarOptions, Judgment, LexItem};
+
+/// Member of a minimal pair the scorer prefers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preference {
+    /// The grammatical member scores higher
+    Grammatical,
+    /// The ungrammatical member scores higher
+    Ungrammatical,
+    /// Both members score the same
+    Tie,
+}
+
+/// Graded judgments of a minimal pair
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Choice {
+    /// Judgment of the grammatical member
+    pub grammatical: Judgment,
+    /// Judgment of the ungrammatical member
+    pub ungrammatical: Judgment,
+    /// Member preferred
+    pub preference: Preference,
+}
+
+/// Forced-choice results over a set of minimal pairs
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForcedChoice {
+    /// One choice per pair, in the order given
+    pub choices: Vec<Choice>,
+    /// Share of pairs where the grammatical member is preferred, ties
+    /// counting half
+    pub accuracy: f64,
+}
+
+impl ForcedChoice {
+    /// Pairs where the scorer preferred `preference`
+    pub fn count(&self, preference: Preference) -> usize {
+        self.choices.iter().filter(|choice| choice.preference == preference).count()
+    }
+}
+
+/// Report which member of each (grammatical, ungrammatical) pair `scorer` prefers
+pub fn forced_choice<'p>(
+    pairs: impl IntoIterator<Item = (&'p str, &'p str)>,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    scorer: &Acceptability,
+) -> ForcedChoice {
+    let choices: Vec<Choice> = pairs.into_iter()
+        .map(|(grammatical, ungrammatical)| {
+            let grammatical = scorer.judge(grammatical, lexicon, options);
+            let ungrammatical = scorer.judge(ungrammatical, lexicon, options);
+            let preference = if grammatical.score > ungrammatical.score {
+                Preference::Grammatical
+            } else if grammatical.score < ungrammatical.score {
+                Preference::Ungrammatical
+            } else {
+                Preference::Tie
+            };
+            Choice { grammatical, ungrammatical, preference }
+        })
+        .collect();
+
+    let credit: f64 = choices.iter()
+        .map(|choice| match choice.preference {
+            Preference::Grammatical => 1.0,
+            Preference::Tie => 0.5,
+            Preference::Ungrammatical => 0.0,
+        })
+        .sum();
+    let accuracy = if choices.is_empty() { 0.0 } else { credit / choices.len() as f64 };
+    ForcedChoice { choices, accuracy }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    #[test]
+    fn test_preferences() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        let pairs = [
+            // Only the grammatical member parses
+            ("the student left", "the student the teacher"),
+            // Both fail, but the "ungrammatical" member gets further
+            ("student zorp", "the student the teacher"),
+            // Both parse equally well
+            ("the student left", "the teacher left"),
+        ];
+        let results = forced_choice(pairs, &lexicon, &options, &Acceptability::default());
+
+        let preferences: Vec<Preference> = results.choices.iter().map(|choice| choice.preference).collect();
+        assert_eq!(preferences, [Preference::Grammatical, Preference::Ungrammatical, Preference::Tie]);
+        assert!(results.choices[0].grammatical.parsed && !results.choices[0].ungrammatical.parsed);
+        assert_eq!(results.count(Preference::Tie), 1);
+        assert_eq!(results.accuracy, 0.5);
+    }
+
+    #[test]
+    fn test_no_pairs() {
+        let results = forced_choice([], &test_lexicon(), &GrammarOptions::default(), &Acceptability::default());
+        assert!(results.choices.is_empty());
+        assert_eq!(results.accuracy, 0.0);
+    }
+}
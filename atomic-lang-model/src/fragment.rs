@@ -0,0 +1,253 @@
+//! Lexicon Fragments
+//!
+//! Large grammars are easier to maintain as a core lexicon plus
+//! domain-specific fragments (medical vocabulary, a dialect, a test
+//! grammar) than as one file. `Composition::compose` concatenates named
+//! fragments in order and remembers which fragment each entry came from, so
+//! a surprising analysis can be traced back to the file that licensed it.
+//! Fragment names double as namespaces: `core::left` names the entries for
+//! "left" contributed by the `core` fragment.
+//!
+//! Composition never fails, but reports conflicts for review:
+//!
+//! - two fragments with the same name, which makes namespaces ambiguous
+//! - an entry repeated in several fragments, which would double every
+//!   analysis using it; it is kept once, attributed to the first fragment
+//! - a word given different bundles by different fragments, which is often
+//!   intended (a domain sense of a core word) but changes the core's parses
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::{LexItem, Lexicon};
+
+/// Separator between a fragment name and a word
+pub const NAMESPACE_SEPARATOR: &str = "::";
+
+/// Named lexicon fragment
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconFragment {
+    /// Name, used as the namespace of its entries
+    pub name: String,
+    /// The fragment's entries
+    pub lexicon: Lexicon,
+}
+
+impl LexiconFragment {
+    /// Create a named fragment
+    pub fn new(name: &str, lexicon: Lexicon) -> Self {
+        Self { name: name.to_string(), lexicon }
+    }
+}
+
+/// Problem found while composing fragments
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+    /// Several fragments share a name
+    DuplicateName(String),
+    /// The same entry in several fragments, in order
+    Duplicate {
+        /// The repeated entry
+        item: LexItem,
+        /// Fragments defining it
+        fragments: Vec<String>,
+    },
+    /// A word with different bundles in different fragments
+    Ambiguity {
+        /// The word
+        phon: String,
+        /// Fragments with an entry for it, in order
+        fragments: Vec<String>,
+    },
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Conflict::DuplicateName(name) => write!(f, "fragment name {} used more than once", name),
+            Conflict::Duplicate { item, fragments } => {
+                write!(f, "{} defined in {}", item, fragments.join(", "))
+            }
+            Conflict::Ambiguity { phon, fragments } => {
+                write!(f, "{} has different entries in {}", phon, fragments.join(", "))
+            }
+        }
+    }
+}
+
+/// Lexicon composed from fragments, with the provenance of each entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct Composition {
+    /// Entries of every fragment in order, duplicates removed
+    pub items: Vec<LexItem>,
+    /// Fragment of each entry, as an index into `fragments`
+    provenance: Vec<usize>,
+    /// Fragment names and versions, in order
+    fragments: Vec<(String, String)>,
+    /// Conflicts found, in order of discovery
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Composition {
+    /// Concatenate `fragments` in order, reporting conflicts
+    pub fn compose(fragments: &[LexiconFragment]) -> Self {
+        let mut composition = Composition {
+            items: Vec::new(),
+            provenance: Vec::new(),
+            fragments: Vec::new(),
+            conflicts: Vec::new(),
+        };
+
+        for (f, fragment) in fragments.iter().enumerate() {
+            if fragments[..f].iter().any(|earlier| earlier.name == fragment.name)
+                && !composition.conflicts.contains(&Conflict::DuplicateName(fragment.name.clone()))
+            {
+                composition.conflicts.push(Conflict::DuplicateName(fragment.name.clone()));
+            }
+            composition.fragments.push((fragment.name.clone(), fragment.lexicon.version.clone()));
+
+            for item in &fragment.lexicon.items {
+                // Repeats within a fragment are the fragment's own business
+                match composition.items.iter().position(|other| other == item) {
+                    Some(i) if composition.provenance[i] != f => {
+                        composition.report_duplicate(item, i, &fragment.name);
+                        continue;
+                    }
+                    _ => {}
+                }
+                composition.items.push(item.clone());
+                composition.provenance.push(f);
+            }
+        }
+
+        composition.report_ambiguities();
+        composition
+    }
+
+    fn report_duplicate(&mut self, item: &LexItem, first: usize, name: &str) {
+        for conflict in &mut self.conflicts {
+            if let Conflict::Duplicate { item: repeated, fragments } = conflict {
+                if repeated == item {
+                    fragments.push(name.to_string());
+                    return;
+                }
+            }
+        }
+        let fragments = Vec::from([self.fragments[self.provenance[first]].0.clone(), name.to_string()]);
+        self.conflicts.push(Conflict::Duplicate { item: item.clone(), fragments });
+    }
+
+    fn report_ambiguities(&mut self) {
+        let mut seen: Vec<&str> = Vec::new();
+        let mut ambiguities = Vec::new();
+        for item in &self.items {
+            if seen.contains(&item.phon.as_str()) {
+                continue;
+            }
+            seen.push(&item.phon);
+
+            let entries: Vec<usize> = (0..self.items.len()).filter(|&i| self.items[i].phon == item.phon).collect();
+            let mut sources: Vec<usize> = entries.iter().map(|&i| self.provenance[i]).collect();
+            sources.dedup();
+            if sources.len() > 1 && entries.iter().any(|&i| self.items[i].feats != item.feats) {
+                let fragments = sources.iter().map(|&f| self.fragments[f].0.clone()).collect();
+                ambiguities.push(Conflict::Ambiguity { phon: item.phon.clone(), fragments });
+            }
+        }
+        self.conflicts.extend(ambiguities);
+    }
+
+    /// Check whether the fragments composed without conflicts
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Name of the fragment entry `i` came from
+    pub fn provenance(&self, i: usize) -> Option<&str> {
+        self.provenance.get(i).map(|&f| self.fragments[f].0.as_str())
+    }
+
+    /// Entries with the name of the fragment each came from
+    pub fn entries(&self) -> impl Iterator<Item = (&LexItem, &str)> {
+        self.items.iter().zip(&self.provenance).map(|(item, &f)| (item, self.fragments[f].0.as_str()))
+    }
+
+    /// Entries contributed by the fragment `name`
+    pub fn fragment(&self, name: &str) -> Vec<&LexItem> {
+        self.entries().filter(|(_, fragment)| *fragment == name).map(|(item, _)| item).collect()
+    }
+
+    /// Entries named by a qualified word such as `core::left`, or every
+    /// entry for an unqualified word
+    pub fn resolve(&self, word: &str) -> Vec<&LexItem> {
+        match word.split_once(NAMESPACE_SEPARATOR) {
+            Some((name, phon)) => self.entries()
+                .filter(|(item, fragment)| *fragment == name && item.phon == phon)
+                .map(|(item, _)| item)
+                .collect(),
+            None => self.items.iter().filter(|item| item.phon == word).collect(),
+        }
+    }
+
+    /// The composed entries as a lexicon, versioned by its fragments, e.g.
+    /// `core@1.0+medical@0.3`
+    pub fn lexicon(&self) -> Lexicon {
+        let version: Vec<String> = self.fragments.iter()
+            .map(|(name, version)| if version.is_empty() { name.clone() } else { [name.as_str(), version].join("@") })
+            .collect();
+        Lexicon::new(&version.join("+"), self.items.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, Category, Feature};
+
+    fn medical() -> LexiconFragment {
+        LexiconFragment::new("medical", Lexicon::new("0.3", Vec::from([
+            LexItem::new("nurse", &[Feature::Cat(Category::N)]),
+            LexItem::new("student", &[Feature::Cat(Category::N)]),
+            LexItem::new("discharged", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("left", &[Feature::Cat(Category::N)]),
+        ])))
+    }
+
+    #[test]
+    fn test_provenance_and_namespaces() {
+        let core = LexiconFragment::new("core", Lexicon::new("1.0", test_lexicon()));
+        let composition = Composition::compose(&[core, medical()]);
+
+        assert_eq!(composition.items.len(), test_lexicon().len() + 3);
+        assert_eq!(composition.provenance(0), Some("core"));
+        assert_eq!(composition.provenance(composition.items.len() - 1), Some("medical"));
+        assert_eq!(composition.fragment("medical").len(), 3);
+        assert_eq!(composition.resolve("medical::left"), [&LexItem::new("left", &[Feature::Cat(Category::N)])]);
+        assert_eq!(composition.resolve("left").len(), 2);
+        assert!(composition.resolve("core::nurse").is_empty());
+
+        let lexicon = composition.lexicon();
+        assert_eq!(lexicon.version, "core@1.0+medical@0.3");
+        assert!(parse_sentence("the nurse discharged the student", &lexicon.items).is_ok());
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let core = LexiconFragment::new("core", Lexicon::new("1.0", test_lexicon()));
+        let composition = Composition::compose(&[core.clone(), medical(), LexiconFragment::new("medical", Lexicon::new("", Vec::new()))]);
+        assert!(!composition.is_clean());
+        assert_eq!(composition.conflicts, [
+            Conflict::Duplicate {
+                item: LexItem::new("student", &[Feature::Cat(Category::N)]),
+                fragments: Vec::from(["core".to_string(), "medical".to_string()]),
+            },
+            Conflict::DuplicateName("medical".to_string()),
+            Conflict::Ambiguity { phon: "left".to_string(), fragments: Vec::from(["core".to_string(), "medical".to_string()]) },
+        ]);
+        assert_eq!(composition.conflicts[0].to_string(), "student :: N defined in core, medical");
+
+        assert!(Composition::compose(&[core]).is_clean());
+    }
+}
@@ -0,0 +1,223 @@
+//! Fuzzing Support
+//!
+//! Decodes arbitrary bytes into token sequences, lexicons, feature bundles,
+//! and syntactic objects, and exposes one harness per fuzz target in
+//! `fuzz/fuzz_targets`. Each harness checks that the engine neither panics
+//! nor violates basic invariants on the decoded input.
+//!
+//! Decoding never fails: exhausted input reads as zero bytes, and sizes are
+//! capped so every input terminates quickly.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{merge, move_operation, parse_sentence, AgrBundle, Category, Feature, LexItem, SyntacticObject};
+
+/// Phonological forms drawn on by decoded lexicons and sentences
+const WORDS: [&str; 8] = ["the", "a", "student", "teacher", "who", "that", "left", "saw"];
+
+/// Most items in a decoded lexicon
+pub const MAX_LEXICON: usize = 8;
+/// Most silent heads in a decoded lexicon
+pub const MAX_EMPTY_HEADS: usize = 2;
+/// Most tokens in a decoded sentence
+pub const MAX_TOKENS: usize = 6;
+/// Most features in a decoded bundle
+pub const MAX_FEATURES: usize = 5;
+/// Deepest decoded syntactic object
+pub const MAX_DEPTH: usize = 5;
+
+/// Cursor over fuzzer-provided bytes
+#[derive(Debug, Clone)]
+pub struct FuzzInput<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> FuzzInput<'a> {
+    /// Start decoding at the beginning of `data`
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    /// Check if every byte has been consumed
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Next byte, or zero once exhausted
+    pub fn byte(&mut self) -> u8 {
+        match self.data.split_first() {
+            Some((&b, rest)) => {
+                self.data = rest;
+                b
+            }
+            None => 0,
+        }
+    }
+
+    /// Value in `0..n` (`n` must be non-zero)
+    pub fn below(&mut self, n: usize) -> usize {
+        self.byte() as usize % n
+    }
+
+    /// Decode a category
+    pub fn category(&mut self) -> Category {
+        const CATEGORIES: [Category; 11] = [
+            Category::N, Category::V, Category::D, Category::C, Category::P, Category::S,
+            Category::NP, Category::VP, Category::DP, Category::CP, Category::PP,
+        ];
+        CATEGORIES[self.below(CATEGORIES.len())].clone()
+    }
+
+    /// Decode a feature; movement indices are kept small so licensors and licensees meet
+    pub fn feature(&mut self) -> Feature {
+        match self.below(7) {
+            0 => Feature::Cat(self.category()),
+            1 => Feature::Sel(self.category()),
+            2 => Feature::Spec(self.category()),
+            3 => Feature::Adjoin(self.category()),
+            4 => Feature::Pos(self.below(3) as u8 + 1),
+            5 => Feature::Neg(self.below(3) as u8 + 1),
+            _ => Feature::Agr(AgrBundle::from_bits(self.byte() & 0x3f).unwrap_or_default()),
+        }
+    }
+
+    /// Decode a feature bundle, led by a category feature like a lexical entry
+    pub fn features(&mut self) -> Vec<Feature> {
+        let mut features = vec![Feature::Cat(self.category())];
+        for _ in 0..self.below(MAX_FEATURES) {
+            features.push(self.feature());
+        }
+        features
+    }
+
+    /// Decode a lexicon over `WORDS`, with up to `MAX_EMPTY_HEADS` silent heads
+    pub fn lexicon(&mut self) -> Vec<LexItem> {
+        let mut lexicon = Vec::new();
+        let mut empty_heads = 0;
+        for _ in 0..=self.below(MAX_LEXICON) {
+            let word = self.below(WORDS.len() + 1);
+            let phon = if word < WORDS.len() {
+                WORDS[word]
+            } else if empty_heads < MAX_EMPTY_HEADS {
+                empty_heads += 1;
+                ""
+            } else {
+                continue;
+            };
+            let features = self.features();
+            lexicon.push(LexItem::new(phon, &features));
+        }
+        lexicon
+    }
+
+    /// Decode a sentence of up to `MAX_TOKENS` words from `WORDS`
+    pub fn tokens(&mut self) -> Vec<&'static str> {
+        (0..=self.below(MAX_TOKENS)).map(|_| WORDS[self.below(WORDS.len())]).collect()
+    }
+
+    /// Decode a syntactic object no deeper than `depth`
+    pub fn object(&mut self, depth: usize) -> SyntacticObject {
+        match self.below(4) {
+            0 => SyntacticObject::trace(self.category()),
+            1 | 2 if depth > 0 => {
+                let label = self.category();
+                let features = self.features();
+                let children = (0..=self.below(2)).map(|_| self.object(depth - 1)).collect();
+                SyntacticObject::internal(label, features, children)
+            }
+            _ => {
+                let phon = WORDS[self.below(WORDS.len())];
+                SyntacticObject::from_lex(&LexItem::new(phon, &self.features()))
+            }
+        }
+    }
+}
+
+/// Pronounced words of an object, sorted
+fn words(obj: &SyntacticObject) -> Vec<String> {
+    let mut words: Vec<String> = obj.linearize().split_whitespace().map(String::from).collect();
+    words.sort();
+    words
+}
+
+/// Parse a decoded sentence against a decoded lexicon
+pub fn fuzz_parse_sentence(data: &[u8]) {
+    let mut input = FuzzInput::new(data);
+    let lexicon = input.lexicon();
+    let sentence = input.tokens().join(" ");
+
+    if let Ok(tree) = parse_sentence(&sentence, &lexicon) {
+        assert_eq!(tree.linearize(), sentence, "parse must yield the input");
+    }
+}
+
+/// Merge two decoded objects
+pub fn fuzz_merge(data: &[u8]) {
+    let mut input = FuzzInput::new(data);
+    let a = input.object(MAX_DEPTH);
+    let b = input.object(MAX_DEPTH);
+    let selectors = a.features.iter().filter(|f| f.is_selector()).count();
+
+    if let Ok(merged) = merge(a, b) {
+        assert_eq!(merged.children.len(), 2);
+        assert_eq!(
+            merged.features.iter().filter(|f| f.is_selector()).count(),
+            selectors - 1,
+            "merge checks exactly one selector"
+        );
+    }
+}
+
+/// Apply movement within a decoded object
+pub fn fuzz_move_operation(data: &[u8]) {
+    let mut input = FuzzInput::new(data);
+    let obj = input.object(MAX_DEPTH);
+    let before = words(&obj);
+
+    if let Ok(moved) = move_operation(obj) {
+        assert_eq!(words(&moved), before, "movement must not add or drop words");
+        assert_eq!(moved.children.len(), 2, "the mover re-merges with its remnant");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::determinism::xorshift;
+
+    /// Deterministic pseudo-random inputs (xorshift)
+    fn inputs(count: usize, len: usize) -> Vec<Vec<u8>> {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        (0..count)
+            .map(|_| {
+                (0..len)
+                    .map(|_| xorshift(&mut state) as u8)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decoding_is_total() {
+        let mut input = FuzzInput::new(&[]);
+        assert!(input.is_empty());
+        assert_eq!(input.byte(), 0);
+        assert_eq!(input.features(), vec![Feature::Cat(Category::N)]);
+        assert_eq!(input.tokens(), vec!["the"]);
+        assert!(input.object(MAX_DEPTH).is_trace());
+
+        // Eight silent heads requested, each with a bare category
+        let lexicon = FuzzInput::new(&[7, 8, 0, 0, 8, 0, 0, 8, 0, 0, 8, 0, 0]).lexicon();
+        assert_eq!(lexicon.iter().filter(|item| item.phon.is_empty()).count(), MAX_EMPTY_HEADS);
+    }
+
+    #[test]
+    fn test_targets_survive_random_inputs() {
+        for data in inputs(1000, 48) {
+            fuzz_parse_sentence(&data);
+            fuzz_merge(&data);
+            fuzz_move_operation(&data);
+        }
+    }
+}
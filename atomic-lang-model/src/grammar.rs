@@ -0,0 +1,231 @@
+//! Grammar Options
+//!
+//! Selectable analyses for constructions where the syntactic literature offers
+//! competing derivations. Each option contributes lexical items (overt and
+//! silent) layered over the standard test lexicon, so the same engine derives
+//! the construction either way. Operations can also be switched off for
+//! ablation: without Move, the engine is a pure Merge grammar, weakly
+//! equivalent to a context-free grammar.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::constraints::{Subjacency, Superiority, ThatTraceFilter};
+use crate::ecp::Ecp;
+use crate::normalize::Normalizer;
+use crate::spellout::SpellOut;
+use crate::transfer::transfer;
+use crate::valuation::Valuation;
+use crate::{test_lexicon, Category, DerivationError, Feature, LexItem, Operation, SyntacticObject};
+
+/// Movement index for relative operators (`+rel` / `-rel`)
+pub const REL: u8 = 2;
+
+/// Movement index for the raised head noun of a promotion relative
+pub const HEAD: u8 = 3;
+
+/// Analysis of relative clauses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RelativeClauseAnalysis {
+    /// Relative operator moves to the edge of a CP adjoined to the head noun
+    #[default]
+    OperatorMovement,
+    /// Head noun raises out of the relativized DP (Kayne 1994, Bianchi 1999)
+    Promotion,
+}
+
+/// Structure-building operations the derivation search may use
+///
+/// External Merge (including Merge of silent heads and adjunction) is
+/// always available; the rest can be disabled to measure what they add.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operations {
+    /// Move (internal Merge)
+    pub movement: bool,
+}
+
+impl Operations {
+    /// Every operation the engine implements
+    pub const ALL: Self = Self { movement: true };
+
+    /// External Merge only: the context-free fragment
+    pub const MERGE_ONLY: Self = Self { movement: false };
+
+    /// Whether the search may apply `op`
+    pub fn allows(&self, op: &Operation) -> bool {
+        match op {
+            Operation::Move(_) => self.movement,
+            Operation::Merge(..) | Operation::MergeEmpty(..) | Operation::Adjoin(..) => true,
+        }
+    }
+}
+
+impl Default for Operations {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Grammar-level analysis options
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GrammarOptions {
+    /// Analysis used for relative clauses
+    pub relative_clauses: RelativeClauseAnalysis,
+    /// That-trace filter, if the language enforces it
+    pub that_trace: Option<ThatTraceFilter>,
+    /// Bounding-node limit on movement, if enforced
+    pub subjacency: Option<Subjacency>,
+    /// Closest-attract condition on wh-movement, if enforced
+    pub superiority: Option<Superiority>,
+    /// Empty Category Principle, if enforced
+    pub ecp: Option<Ecp>,
+    /// Operations available to the derivation
+    pub operations: Operations,
+    /// Morphophonological rules applied after linearization
+    pub spell_out: SpellOut,
+    /// Rewrites applied to input text before tokenization
+    pub normalizer: Normalizer,
+    /// Defaults for features left unvalued by context
+    pub valuation: Valuation,
+}
+
+impl GrammarOptions {
+    /// Test lexicon with the selected analyses' items taking precedence
+    pub fn lexicon(&self) -> Vec<LexItem> {
+        let mut lexicon = relative_clause_lexicon(self.relative_clauses);
+        let base: Vec<LexItem> = test_lexicon()
+            .into_iter()
+            .filter(|item| !lexicon.iter().any(|own| own.phon == item.phon))
+            .collect();
+        lexicon.extend(base);
+        lexicon
+    }
+    
+    /// Apply the enabled constraints to a convergent derivation
+    pub fn check(&self, tree: &SyntacticObject) -> Result<(), DerivationError> {
+        if let Some(filter) = &self.that_trace {
+            filter.check(tree)?;
+        }
+        if let Some(subjacency) = &self.subjacency {
+            subjacency.check(tree)?;
+        }
+        if let Some(superiority) = &self.superiority {
+            superiority.check(tree)?;
+        }
+        if let Some(ecp) = &self.ecp {
+            ecp.check(tree)?;
+        }
+        Ok(())
+    }
+    
+    /// Pronounce a convergent derivation with the grammar's spell-out
+    /// rules, once its unvalued features are resolved
+    pub fn pronounce(&self, tree: &SyntacticObject) -> Result<String, DerivationError> {
+        let (tree, _) = self.valuation.resolve(tree);
+        Ok(self.spell_out.pronounce(&transfer(&tree)?.pf))
+    }
+}
+
+/// Lexical items deriving relative clauses under the given analysis
+pub fn relative_clause_lexicon(analysis: RelativeClauseAnalysis) -> Vec<LexItem> {
+    match analysis {
+        RelativeClauseAnalysis::OperatorMovement => vec![
+            // Relative pronoun: a D operator that must reach the clause edge
+            LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(REL)]),
+            // Silent relative C attracts the operator; the CP adjoins to the head noun
+            LexItem::new("", &[
+                Feature::Cat(Category::C),
+                Feature::Sel(Category::V),
+                Feature::Pos(REL),
+                Feature::Adjoin(Category::N),
+            ]),
+        ],
+        RelativeClauseAnalysis::Promotion => vec![
+            // Relative determiner taking the head noun as its complement
+            LexItem::new("who", &[Feature::Cat(Category::D), Feature::Sel(Category::N), Feature::Neg(REL)]),
+            // Silent head marking the head noun for raising
+            LexItem::new("", &[Feature::Cat(Category::N), Feature::Sel(Category::N), Feature::Neg(HEAD)]),
+            // Silent relative C attracts the relativized DP
+            LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(REL)]),
+            // Silent nominal head over the clause; the raised noun relabels it as NP
+            LexItem::new("", &[Feature::Cat(Category::N), Feature::Sel(Category::C), Feature::Pos(HEAD)]),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, parse_with_options, SyntacticObject};
+
+    fn trace_labels(obj: &SyntacticObject) -> Vec<Category> {
+        if obj.is_trace() {
+            return vec![obj.label.clone()];
+        }
+        obj.children.iter().flat_map(trace_labels).collect()
+    }
+
+    fn parse(sentence: &str, analysis: RelativeClauseAnalysis) -> SyntacticObject {
+        let options = GrammarOptions { relative_clauses: analysis, ..Default::default() };
+        parse_sentence(sentence, &options.lexicon()).expect("relative clause should parse")
+    }
+
+    #[test]
+    fn test_operator_movement_structure() {
+        let tree = parse("the student who left", RelativeClauseAnalysis::OperatorMovement);
+        assert_eq!(tree.linearize(), "the student who left");
+        assert_eq!(tree.label, Category::DP);
+
+        // The head noun is base-generated outside the relative clause
+        let noun_phrase = &tree.children[1];
+        assert_eq!(noun_phrase.children[0].phon.as_deref(), Some("student"));
+
+        // Only the operator moved, to the edge of the adjoined CP
+        let relative = &noun_phrase.children[1];
+        assert_eq!(relative.label, Category::CP);
+        assert_eq!(relative.children[0].phon.as_deref(), Some("who"));
+        assert_eq!(trace_labels(&tree), vec![Category::D]);
+    }
+
+    #[test]
+    fn test_promotion_structure() {
+        let tree = parse("the student who left", RelativeClauseAnalysis::Promotion);
+        assert_eq!(tree.linearize(), "the student who left");
+        assert_eq!(tree.label, Category::DP);
+
+        // The head noun raised out of the clause, to the edge of the nominal
+        // head that takes the relative CP as its complement
+        let noun_phrase = &tree.children[1];
+        assert_eq!(noun_phrase.children[0].linearize(), "student");
+        let relative = &noun_phrase.children[1].children[1];
+        assert_eq!(relative.label, Category::CP);
+        assert_eq!(relative.children[0].linearize(), "who");
+
+        // Two chains: the relativized DP and the noun raised out of it
+        let mut traces = trace_labels(&tree);
+        traces.sort_by_key(|cat| format!("{:?}", cat));
+        assert_eq!(traces, vec![Category::DP, Category::NP]);
+    }
+
+    #[test]
+    fn test_analyses_agree_on_strings() {
+        for analysis in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let tree = parse("the student who left smiled", analysis);
+            assert_eq!(tree.linearize(), "the student who left smiled");
+            assert_eq!(tree.label, Category::VP);
+        }
+    }
+
+    #[test]
+    fn test_merge_only_fragment() {
+        let options = GrammarOptions { operations: Operations::MERGE_ONLY, ..Default::default() };
+        let lexicon = options.lexicon();
+
+        // Clauses without displacement still derive; relatives need Move
+        let tree = parse_with_options("the student left", &lexicon, &options).unwrap();
+        assert_eq!(trace_labels(&tree), Vec::new());
+        assert!(parse_with_options("the student who left smiled", &lexicon, &options).is_err());
+        assert!(!Operations::MERGE_ONLY.allows(&Operation::Move(0)));
+        assert!(Operations::default().allows(&Operation::Move(0)));
+    }
+}
@@ -0,0 +1,35 @@
+//! English
+//!
+//! Head-initial and SVO: determiners, verbs and prepositions precede their
+//! complements, subjects are specifiers, prepositional phrases and
+//! relative clauses follow the noun they modify.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::Fragment;
+use crate::{Category, Feature, GrammarOptions, LexItem};
+
+/// Benchmark sentences, with whether each is grammatical
+pub const BENCHMARK: &[(&str, bool)] = &[
+    ("the student left", true),
+    ("the student thinks the teacher", true),
+    ("the student near the teacher smiled", true),
+    ("the student who left smiled", true),
+    ("student the left", false),
+    ("the student the teacher thinks", false),
+    ("the student thinks", false),
+    ("the student near smiled", false),
+];
+
+/// Test lexicon with operator-movement relatives and a preposition
+pub fn lexicon() -> Vec<LexItem> {
+    let mut lexicon = GrammarOptions::default().lexicon();
+    lexicon.push(LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]));
+    lexicon
+}
+
+/// The English fragment
+pub fn fragment() -> Fragment {
+    Fragment { language: "English", lexicon: lexicon(), options: GrammarOptions::default(), benchmark: BENCHMARK, agreement: &[] }
+}
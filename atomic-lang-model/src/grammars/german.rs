@@ -0,0 +1,75 @@
+//! German
+//!
+//! Verb phrases are head-final (OV): the participle takes its object and
+//! subject to its left. The finite auxiliary takes the verb phrase to its
+//! right and attracts the subject, so it ends up second. Case is checked by
+//! movement: `der` marks its noun phrase as nominative (`-nom`), which only
+//! the auxiliary checks, and `den` as accusative (`-acc`), which only a
+//! transitive participle checks. Verb-final subordinate clauses would need
+//! the finite verb to move (head movement), which the engine does not do,
+//! so the fragment covers main clauses only.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::Fragment;
+use crate::{Category, Feature, GrammarOptions, LexItem};
+
+/// Movement index for nominative case
+pub const NOM: u8 = 8;
+
+/// Movement index for accusative case
+pub const ACC: u8 = 9;
+
+/// Benchmark sentences, with whether each is grammatical
+pub const BENCHMARK: &[(&str, bool)] = &[
+    ("der Student ist gekommen", true),
+    ("der Student hat den Lehrer gesehen", true),
+    ("der Lehrer hat den Hund gesehen", true),
+    ("den Student hat den Lehrer gesehen", false),
+    ("der Student hat der Lehrer gesehen", false),
+    ("der Student hat gesehen den Lehrer", false),
+    ("hat der Student den Lehrer gesehen", false),
+    ("der Student den Lehrer gesehen hat", false),
+    ("der Student ist den Lehrer gekommen", false),
+];
+
+/// Case-marked articles, nouns, participles and auxiliaries
+pub fn lexicon() -> Vec<LexItem> {
+    let noun = |phon| LexItem::new(phon, &[Feature::Cat(Category::N)]);
+    vec![
+        LexItem::new("der", &[Feature::Cat(Category::D), Feature::Sel(Category::N), Feature::Neg(NOM)]),
+        LexItem::new("den", &[Feature::Cat(Category::D), Feature::Sel(Category::N), Feature::Neg(ACC)]),
+        noun("Student"),
+        noun("Lehrer"),
+        noun("Hund"),
+        // Participles: seen, come
+        LexItem::new("gesehen", &[Feature::Cat(Category::V), Feature::Spec(Category::D), Feature::Pos(ACC), Feature::Spec(Category::D)]),
+        LexItem::new("gekommen", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        // Finite auxiliaries: has, is
+        LexItem::new("hat", &[Feature::Cat(Category::V), Feature::Sel(Category::V), Feature::Pos(NOM)]),
+        LexItem::new("ist", &[Feature::Cat(Category::V), Feature::Sel(Category::V), Feature::Pos(NOM)]),
+    ]
+}
+
+/// The German fragment
+pub fn fragment() -> Fragment {
+    Fragment { language: "German", lexicon: lexicon(), options: GrammarOptions::default(), benchmark: BENCHMARK, agreement: &[] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_with_options;
+
+    #[test]
+    fn test_subject_moves_past_the_auxiliary() {
+        let fragment = fragment();
+        let tree = parse_with_options("der Student hat den Lehrer gesehen", &fragment.lexicon, &fragment.options).unwrap();
+
+        // The subject sits at the auxiliary's edge, its trace inside the verb phrase
+        assert_eq!(tree.children[0].linearize(), "der Student");
+        assert_eq!(tree.children[1].children[0].phon.as_deref(), Some("hat"));
+        assert_eq!(tree.children[1].children[1].linearize(), "den Lehrer gesehen");
+    }
+}
@@ -0,0 +1,126 @@
+//! Hindi
+//!
+//! Head-final and SOV, with gender and number agreement: every head takes
+//! its arguments as specifiers to its left, as in the Japanese fragment.
+//! Intransitive perfective verbs agree with their subject in both (`larka
+//! aaya`, `larki aayi`). The possessive particle is a postposition after
+//! the possessor, and agrees with the possessed noun that follows it, not
+//! with the possessor before it (`larkon ka dost`, the boys' friend): the
+//! possessed noun takes the particle's phrase as its specifier and probes
+//! its gender and number. The possessor is the attractor: its features
+//! differ from the head noun's, but only the head's reach the verb. Plural
+//! possessors take their oblique forms, modelled as a separate category so
+//! they cannot stand as subjects; romanization is plain ASCII.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::Fragment;
+use crate::{AgrBundle, Category, Feature, Gender, GrammarOptions, LexItem, Number, Person};
+
+/// Benchmark sentences, with whether each is grammatical
+pub const BENCHMARK: &[(&str, bool)] = &[
+    ("larka aaya", true),
+    ("larkiyan aayin", true),
+    ("larkon ka dost aaya", true),
+    ("larkiyon ki behen aayi", true),
+    ("larka aayi", false),
+    ("aaya larka", false),
+    ("larkon aaye", false),
+    ("larkiyon ka behen aayi", false),
+    ("ka larkon dost aaya", false),
+];
+
+/// Agreement pairs: grammatical, ungrammatical, attractors between subject and verb
+pub const AGREEMENT: &[(&str, &str, usize)] = &[
+    ("larka aaya", "larka aaye", 0),
+    ("larke aaye", "larke aaya", 0),
+    ("larki aayi", "larki aaya", 0),
+    ("larkiyan aayin", "larkiyan aayi", 0),
+    ("larkon ka dost aaya", "larkon ka dost aaye", 1),
+    ("larkon ki behen aayi", "larkon ki behen aaya", 1),
+    ("larkiyon ka dost aaya", "larkiyon ka dost aayin", 1),
+    ("larki ka dost aaya", "larki ka dost aayi", 1),
+];
+
+fn agr(gender: Gender, number: Number) -> Feature {
+    Feature::Agr(AgrBundle { person: Some(Person::Third), number: Some(number), gender: Some(gender) })
+}
+
+/// Gender and number, with person left open
+fn phi(gender: Gender, number: Option<Number>) -> Feature {
+    Feature::Agr(AgrBundle { person: None, number, gender: Some(gender) })
+}
+
+/// Nouns, oblique possessors, possessive particles and verbs
+pub fn lexicon() -> Vec<LexItem> {
+    use Gender::{Feminine as F, Masculine as M};
+    use Number::{Plural as Pl, Singular as Sg};
+    let noun = |phon, gender, number| LexItem::new(phon, &[Feature::Cat(Category::N), agr(gender, number)]);
+    // A possessed noun: the possessive phrase to its left, probed for the noun's own features
+    let possessed = |phon, gender, number| LexItem::new(phon, &[
+        Feature::Cat(Category::N),
+        agr(gender, number),
+        Feature::Spec(Category::P),
+        phi(gender, Some(number)),
+    ]);
+    let oblique = |phon, gender, number| LexItem::new(phon, &[Feature::Cat(Category::D), agr(gender, number)]);
+    // Possessive particle: the possessor to its left, marked for the possessed noun
+    let possessive = |phon, gender, number| LexItem::new(phon, &[Feature::Cat(Category::P), phi(gender, number), Feature::Spec(Category::D)]);
+    // Perfective "came", agreeing with its subject
+    let came = |phon, gender, number| LexItem::new(phon, &[Feature::Cat(Category::V), Feature::Spec(Category::N), phi(gender, Some(number))]);
+    vec![
+        // boy, boys, girl, girls, friend, sister
+        noun("larka", M, Sg),
+        noun("larke", M, Pl),
+        noun("larki", F, Sg),
+        noun("larkiyan", F, Pl),
+        noun("dost", M, Sg),
+        noun("behen", F, Sg),
+        possessed("dost", M, Sg),
+        possessed("behen", F, Sg),
+        oblique("larkon", M, Pl),
+        oblique("larkiyon", F, Pl),
+        oblique("larki", F, Sg),
+        possessive("ka", M, Some(Sg)),
+        possessive("ke", M, Some(Pl)),
+        possessive("ki", F, None),
+        came("aaya", M, Sg),
+        came("aaye", M, Pl),
+        came("aayi", F, Sg),
+        came("aayin", F, Pl),
+    ]
+}
+
+/// The Hindi fragment
+pub fn fragment() -> Fragment {
+    Fragment { language: "Hindi", lexicon: lexicon(), options: GrammarOptions::default(), benchmark: BENCHMARK, agreement: AGREEMENT }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_with_options, SyntacticObject};
+
+    #[test]
+    fn test_verb_is_final_and_agrees_with_the_possessee() {
+        let fragment = fragment();
+        let tree = parse_with_options("larkon ki behen aayi", &fragment.lexicon, &fragment.options).unwrap();
+        assert_eq!(tree.children[0].linearize(), "larkon ki behen");
+        assert_eq!(tree.children[1].phon.as_deref(), Some("aayi"));
+    }
+
+    #[test]
+    fn test_every_head_is_final() {
+        fn heads_are_final(node: &SyntacticObject) -> bool {
+            node.head_index().is_none_or(|head| head == node.children.len() - 1)
+                && node.children.iter().all(heads_are_final)
+        }
+
+        let fragment = fragment();
+        for (sentence, _) in BENCHMARK.iter().filter(|(_, grammatical)| *grammatical) {
+            let tree = parse_with_options(sentence, &fragment.lexicon, &fragment.options).unwrap();
+            assert!(heads_are_final(&tree), "{}", tree.bracketed());
+        }
+    }
+}
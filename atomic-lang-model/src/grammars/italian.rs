@@ -0,0 +1,92 @@
+//! Italian
+//!
+//! Head-initial and SVO, with rich agreement: finite verbs inflect for
+//! all six person and number combinations (`dormo`, `dormi`, `dorme`,
+//! `dormiamo`, `dormite`, `dormono`), and determiners agree with their
+//! noun in gender and number (`il ragazzo`, `le ragazze`). Prepositional
+//! phrases adjoined to the subject's noun are attractors: their nouns may
+//! differ in every attribute, but only the head noun's features reach the
+//! determiner and the verb. Postverbal subjects, which Italian allows, are
+//! left out.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::Fragment;
+use crate::{AgrBundle, Category, Feature, Gender, GrammarOptions, LexItem, Number, Person};
+
+/// Benchmark sentences, with whether each is grammatical
+pub const BENCHMARK: &[(&str, bool)] = &[
+    ("io dormo", true),
+    ("voi dormite", true),
+    ("le ragazze dormono", true),
+    ("la ragazza con i ragazzi dorme", true),
+    ("io dorme", false),
+    ("il ragazza dorme", false),
+    ("ragazzo il dorme", false),
+    ("la ragazza con il ragazzi dorme", false),
+];
+
+/// Agreement pairs: grammatical, ungrammatical, attractors between subject and verb
+pub const AGREEMENT: &[(&str, &str, usize)] = &[
+    ("io dormo", "io dormi", 0),
+    ("tu dormi", "tu dorme", 0),
+    ("lei dorme", "lei dormiamo", 0),
+    ("noi dormiamo", "noi dormite", 0),
+    ("voi dormite", "voi dormono", 0),
+    ("loro dormono", "loro dormo", 0),
+    ("il ragazzo dorme", "il ragazzo dormono", 0),
+    ("la ragazza dorme", "il ragazza dorme", 0),
+    ("il ragazzo con le ragazze dorme", "il ragazzo con le ragazze dormono", 1),
+    ("le ragazze con il professore dormono", "le ragazze con il professore dorme", 1),
+    ("la ragazza con i ragazzi dorme", "i ragazza con i ragazzi dorme", 1),
+    ("il professore con i ragazzi con la ragazza dorme", "il professore con i ragazzi con la ragazza dormono", 2),
+];
+
+fn agr(person: Option<Person>, number: Number, gender: Option<Gender>) -> Feature {
+    Feature::Agr(AgrBundle { person, number: Some(number), gender })
+}
+
+/// Pronouns, determiners, nouns, a preposition and the verb "sleep"
+pub fn lexicon() -> Vec<LexItem> {
+    use Gender::{Feminine as F, Masculine as M};
+    use Number::{Plural as Pl, Singular as Sg};
+    use Person::{First, Second, Third};
+    let pronoun = |phon, person, number, gender| LexItem::new(phon, &[Feature::Cat(Category::D), agr(Some(person), number, gender)]);
+    let noun = |phon, number, gender| LexItem::new(phon, &[Feature::Cat(Category::N), agr(Some(Third), number, Some(gender))]);
+    let determiner = |phon, number, gender| LexItem::new(phon, &[Feature::Cat(Category::D), Feature::Sel(Category::N), agr(None, number, Some(gender))]);
+    let sleeps = |phon, person, number| LexItem::new(phon, &[Feature::Cat(Category::V), Feature::Spec(Category::D), agr(Some(person), number, None)]);
+    vec![
+        pronoun("io", First, Sg, None),
+        pronoun("tu", Second, Sg, None),
+        pronoun("lui", Third, Sg, Some(M)),
+        pronoun("lei", Third, Sg, Some(F)),
+        pronoun("noi", First, Pl, None),
+        pronoun("voi", Second, Pl, None),
+        pronoun("loro", Third, Pl, None),
+        determiner("il", Sg, M),
+        determiner("la", Sg, F),
+        determiner("i", Pl, M),
+        determiner("le", Pl, F),
+        // boy, girl, teacher
+        noun("ragazzo", Sg, M),
+        noun("ragazzi", Pl, M),
+        noun("ragazza", Sg, F),
+        noun("ragazze", Pl, F),
+        noun("professore", Sg, M),
+        noun("professori", Pl, M),
+        // "with": a PP modifying a noun
+        LexItem::new("con", &[Feature::Cat(Category::P), Feature::Sel(Category::D), Feature::Adjoin(Category::N)]),
+        sleeps("dormo", First, Sg),
+        sleeps("dormi", Second, Sg),
+        sleeps("dorme", Third, Sg),
+        sleeps("dormiamo", First, Pl),
+        sleeps("dormite", Second, Pl),
+        sleeps("dormono", Third, Pl),
+    ]
+}
+
+/// The Italian fragment
+pub fn fragment() -> Fragment {
+    Fragment { language: "Italian", lexicon: lexicon(), options: GrammarOptions::default(), benchmark: BENCHMARK, agreement: AGREEMENT }
+}
@@ -0,0 +1,68 @@
+//! Japanese
+//!
+//! Head-final and SOV: every head takes its arguments as specifiers to its
+//! left. Case particles head their phrase after the noun, nominative `ga`
+//! projecting a DP and accusative `o` a PP, so a verb's frame fixes which
+//! case each argument bears. Complementizers close their clause, and
+//! question words stay in situ under clause-final `ka`.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use super::Fragment;
+use crate::{Category, Feature, GrammarOptions, LexItem};
+
+/// Benchmark sentences, with whether each is grammatical
+pub const BENCHMARK: &[(&str, bool)] = &[
+    ("Taro ga hashitta", true),
+    ("Taro ga sushi o tabeta", true),
+    ("Hanako ga Taro ga sushi o tabeta to itta", true),
+    ("Taro ga nani o tabeta ka", true),
+    ("Taro ga tabeta sushi o", false),
+    ("Taro o sushi ga tabeta", false),
+    ("ga Taro sushi o tabeta", false),
+    ("Hanako ga itta to Taro ga sushi o tabeta", false),
+    ("Taro ga sushi o", false),
+];
+
+/// Nouns, case particles, verbs and complementizers
+pub fn lexicon() -> Vec<LexItem> {
+    let noun = |phon| LexItem::new(phon, &[Feature::Cat(Category::N)]);
+    vec![
+        noun("Taro"),
+        noun("Hanako"),
+        noun("sushi"),
+        noun("nani"),
+        // Nominative and accusative particles
+        LexItem::new("ga", &[Feature::Cat(Category::D), Feature::Spec(Category::N)]),
+        LexItem::new("o", &[Feature::Cat(Category::P), Feature::Spec(Category::N)]),
+        // ran, ate, said
+        LexItem::new("hashitta", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("tabeta", &[Feature::Cat(Category::V), Feature::Spec(Category::P), Feature::Spec(Category::D)]),
+        LexItem::new("itta", &[Feature::Cat(Category::V), Feature::Spec(Category::C), Feature::Spec(Category::D)]),
+        // Quotative and question complementizers
+        LexItem::new("to", &[Feature::Cat(Category::C), Feature::Spec(Category::V)]),
+        LexItem::new("ka", &[Feature::Cat(Category::C), Feature::Spec(Category::V)]),
+    ]
+}
+
+/// The Japanese fragment
+pub fn fragment() -> Fragment {
+    Fragment { language: "Japanese", lexicon: lexicon(), options: GrammarOptions::default(), benchmark: BENCHMARK, agreement: &[] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_with_options;
+
+    #[test]
+    fn test_head_final_structure() {
+        let fragment = fragment();
+        let tree = parse_with_options("Taro ga sushi o tabeta", &fragment.lexicon, &fragment.options).unwrap();
+
+        // The verb is the last word and heads the clause
+        assert_eq!(tree.label, Category::VP);
+        assert_eq!(tree.children[1].children[1].phon.as_deref(), Some("tabeta"));
+    }
+}
@@ -0,0 +1,105 @@
+//! Bundled Example Grammars
+//!
+//! Small grammar fragments for typologically distinct languages, run by the
+//! same engine with nothing but a different lexicon: English (head-initial,
+//! SVO), Japanese (head-final, SOV, case particles), German (OV verb
+//! phrases under a second-position auxiliary, case checked by movement),
+//! Hindi (head-final, gender and number agreement) and Italian (rich
+//! person, number and gender agreement). Each fragment is behind its own
+//! feature (`grammars-english`, `grammars-japanese`, `grammars-german`,
+//! `grammars-hindi`, `grammars-italian`; `grammars` enables them all) and
+//! carries a mini benchmark of grammatical and ungrammatical sentences.
+//! Fragments with agreement also carry minimal pairs differing only in
+//! agreement, with the attractors standing between controller and target.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{parse_with_options, GrammarOptions, LexItem};
+
+#[cfg(feature = "grammars-english")]
+pub mod english;
+#[cfg(feature = "grammars-german")]
+pub mod german;
+#[cfg(feature = "grammars-hindi")]
+pub mod hindi;
+#[cfg(feature = "grammars-italian")]
+pub mod italian;
+#[cfg(feature = "grammars-japanese")]
+pub mod japanese;
+
+/// A language's grammar fragment and its benchmark
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fragment {
+    /// Language name
+    pub language: &'static str,
+    /// Lexicon of the fragment
+    pub lexicon: Vec<LexItem>,
+    /// Grammar options the fragment is parsed with
+    pub options: GrammarOptions,
+    /// Benchmark sentences, with whether each is grammatical
+    pub benchmark: &'static [(&'static str, bool)],
+    /// Agreement pairs: grammatical, ungrammatical, and the number of
+    /// attractors between the agreement controller and its target
+    pub agreement: &'static [(&'static str, &'static str, usize)],
+}
+
+impl Fragment {
+    /// Benchmark sentences the fragment judges wrongly
+    pub fn misjudged(&self) -> Vec<&'static str> {
+        self.benchmark.iter()
+            .filter(|&&(sentence, grammatical)| parse_with_options(sentence, &self.lexicon, &self.options).is_ok() != grammatical)
+            .map(|&(sentence, _)| sentence)
+            .collect()
+    }
+
+    /// Agreement pair members the fragment judges wrongly: grammatical
+    /// sentences it rejects and ungrammatical ones it accepts
+    pub fn misjudged_agreement(&self) -> Vec<&'static str> {
+        let parses = |sentence| parse_with_options(sentence, &self.lexicon, &self.options).is_ok();
+        self.agreement.iter()
+            .flat_map(|&(grammatical, ungrammatical, _)| [(grammatical, true), (ungrammatical, false)])
+            .filter(|&(sentence, grammatical)| parses(sentence) != grammatical)
+            .map(|(sentence, _)| sentence)
+            .collect()
+    }
+
+    /// Share of benchmark sentences judged correctly
+    pub fn accuracy(&self) -> f64 {
+        if self.benchmark.is_empty() {
+            return 0.0;
+        }
+        1.0 - self.misjudged().len() as f64 / self.benchmark.len() as f64
+    }
+}
+
+/// Fragments of the enabled languages
+pub fn fragments() -> Vec<Fragment> {
+    [
+        #[cfg(feature = "grammars-english")]
+        english::fragment(),
+        #[cfg(feature = "grammars-japanese")]
+        japanese::fragment(),
+        #[cfg(feature = "grammars-german")]
+        german::fragment(),
+        #[cfg(feature = "grammars-hindi")]
+        hindi::fragment(),
+        #[cfg(feature = "grammars-italian")]
+        italian::fragment(),
+    ]
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmarks() {
+        for fragment in fragments() {
+            assert_eq!(fragment.misjudged(), Vec::<&str>::new(), "{}", fragment.language);
+            assert_eq!(fragment.misjudged_agreement(), Vec::<&str>::new(), "{}", fragment.language);
+            assert_eq!(fragment.accuracy(), 1.0, "{}", fragment.language);
+        }
+    }
+}
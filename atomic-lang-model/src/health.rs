@@ -0,0 +1,155 @@
+//! Library Self-Check
+//!
+//! `self_check` runs a fast battery over the engine (aⁿbⁿ generation and
+//! recognition, pattern certificates, a handful of parses, structural
+//! invariants) and reports each result, so embedders can verify a build on
+//! the target device. Check names are static and the report allocates only
+//! its list of results, which keeps it usable on no_std targets.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::{
+    generate_pattern, is_an_bn_pattern, merge, parse_sentence, test_lexicon, Category, DerivationError, Feature,
+    GrammarOptions, LexItem, PatternRegistry, RelativeClauseAnalysis, SyntacticObject,
+};
+
+/// Largest n for which aⁿbⁿ is generated and recognized
+pub const MAX_AN_BN: usize = 5;
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// What was checked
+    pub name: &'static str,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Derivation error behind a failure, if any
+    pub error: Option<DerivationError>,
+}
+
+/// Results of the self-check battery
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SelfCheckReport {
+    /// Checks in the order they ran
+    pub checks: Vec<Check>,
+}
+
+impl SelfCheckReport {
+    /// Check if every check passed
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Checks that failed
+    pub fn failures(&self) -> impl Iterator<Item = &Check> {
+        self.checks.iter().filter(|check| !check.passed)
+    }
+
+    fn record(&mut self, name: &'static str, passed: bool) {
+        self.checks.push(Check { name, passed, error: None });
+    }
+
+    fn record_parse(&mut self, name: &'static str, sentence: &str, lexicon: &[LexItem]) -> Option<SyntacticObject> {
+        match parse_sentence(sentence, lexicon) {
+            Ok(tree) => {
+                let passed = tree.linearize() == sentence && tree.is_complete();
+                self.record(name, passed);
+                Some(tree)
+            }
+            Err(e) => {
+                self.checks.push(Check { name, passed: false, error: Some(e) });
+                None
+            }
+        }
+    }
+}
+
+impl fmt::Display for SelfCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for check in &self.checks {
+            write!(f, "{} {}", if check.passed { "ok  " } else { "FAIL" }, check.name)?;
+            if let Some(error) = &check.error {
+                write!(f, " ({})", error)?;
+            }
+            writeln!(f)?;
+        }
+        let passed = self.checks.iter().filter(|check| check.passed).count();
+        writeln!(f, "{}/{} checks passed", passed, self.checks.len())
+    }
+}
+
+/// Run the self-check battery
+pub fn self_check() -> SelfCheckReport {
+    let mut report = SelfCheckReport::default();
+
+    // Recursion: aⁿbⁿ is generated and recognized, and near misses are rejected
+    let generated = (0..=MAX_AN_BN).all(|n| generate_pattern("an_bn", n).is_ok_and(|s| is_an_bn_pattern(&s)));
+    report.record("aⁿbⁿ generation up to n=5", generated);
+    report.record("aⁿbⁿ rejects a a b and b a", !is_an_bn_pattern("a a b") && !is_an_bn_pattern("b a"));
+    let certified = PatternRegistry::new().iter().all(|pattern| {
+        let certificates = pattern.certificates(MAX_AN_BN);
+        !certificates.is_empty() && certificates.iter().all(|certificate| certificate.verify(pattern).is_ok())
+    });
+    report.record("pattern certificates verify at p=5", certified);
+
+    // Parsing: simple clauses and both relative clause analyses
+    let lexicon = test_lexicon();
+    let clause = report.record_parse("parse: the student left", "the student left", &lexicon);
+    report.record_parse("parse: the student thinks the teacher", "the student thinks the teacher", &lexicon);
+    for (name, analysis) in [
+        ("parse: relative clause (operator movement)", RelativeClauseAnalysis::OperatorMovement),
+        ("parse: relative clause (promotion)", RelativeClauseAnalysis::Promotion),
+    ] {
+        let options = GrammarOptions { relative_clauses: analysis, ..Default::default() };
+        report.record_parse(name, "the student who left smiled", &options.lexicon());
+    }
+    report.record("reject: the left", parse_sentence("the left", &lexicon).is_err());
+    report.record(
+        "reject: unknown word",
+        parse_sentence("the robot left", &lexicon) == Err(DerivationError::InvalidOperation),
+    );
+
+    // Invariants: merge checks one selector, and the tree keeps every word
+    let det = SyntacticObject::from_lex(&LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]));
+    let noun = SyntacticObject::from_lex(&LexItem::new("student", &[Feature::Cat(Category::N)]));
+    let merged = merge(det, noun);
+    report.record(
+        "merge checks exactly one selector",
+        merged.is_ok_and(|dp| dp.label == Category::DP && dp.next_selector().is_none() && dp.children.len() == 2),
+    );
+    report.record(
+        "linearization keeps every word",
+        clause.is_some_and(|tree| tree.linearize().split_whitespace().count() == 3),
+    );
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_check_passes() {
+        let report = self_check();
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.failures().count(), 0);
+        assert!(report.checks.len() >= 8);
+    }
+
+    #[test]
+    fn test_report_display() {
+        let mut report = SelfCheckReport::default();
+        report.record("fine", true);
+        report.checks.push(Check { name: "broken", passed: false, error: Some(DerivationError::NoValidOperations) });
+
+        assert!(!report.passed());
+        assert_eq!(
+            report.to_string(),
+            "ok   fine\nFAIL broken (No valid operations available)\n1/2 checks passed\n"
+        );
+    }
+}
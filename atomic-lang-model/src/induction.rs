@@ -0,0 +1,251 @@
+//! Distributional Category Induction
+//!
+//! Bootstraps lexical entries from plain text. Words that occur in the same
+//! contexts tend to share a category (Harris 1954; Redington et al. 1998),
+//! so each word is described by counts of its left and right neighbours and
+//! words are clustered bottom-up by the cosine similarity of those counts.
+//! A seed lexicon then names the clusters: each takes the feature bundle
+//! most common among its known members, and its unknown members are
+//! proposed as entries with that bundle.
+//!
+//! Text is normalized as `Normalizer::raw_text` does, so a word is
+//! lowercased only where the seed lexicon spells it that way. Similarities
+//! between clusters wait in a priority queue, and a merge computes only
+//! those of the merged cluster, so `n` words cluster in `O(n² log n)`
+//! comparisons.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::{BTreeMap, BinaryHeap}, string::{String, ToString}, vec, vec::Vec};
+
+use core::cmp::Ordering;
+
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BinaryHeap};
+
+use crate::{Feature, LexItem, Normalizer};
+
+/// Sentence boundary in context counts
+const BOUNDARY: &str = "#";
+
+/// Clustering parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InductionOptions {
+    /// Number of clusters to stop at
+    pub clusters: usize,
+    /// Occurrences a word needs to be clustered
+    pub min_count: usize,
+}
+
+impl Default for InductionOptions {
+    fn default() -> Self {
+        Self { clusters: 8, min_count: 2 }
+    }
+}
+
+/// Words grouped by shared distribution
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordCluster {
+    /// Members, alphabetically
+    pub words: Vec<String>,
+    /// Feature bundle most common among members the seed lexicon knows
+    pub features: Option<Vec<Feature>>,
+}
+
+/// Result of category induction
+#[derive(Debug, Clone, PartialEq)]
+pub struct Induction {
+    /// Clusters, in order of their first member
+    pub clusters: Vec<WordCluster>,
+    /// Words below the frequency threshold, left unclustered
+    pub rare: Vec<String>,
+}
+
+impl Induction {
+    /// Entries for clustered words the seed lexicon lacks
+    pub fn proposals(&self, seed: &[LexItem]) -> Vec<LexItem> {
+        self.clusters.iter()
+            .filter_map(|cluster| Some((cluster, cluster.features.as_ref()?)))
+            .flat_map(|(cluster, features)| {
+                cluster.words.iter()
+                    .filter(|word| !seed.iter().any(|item| item.phon == **word))
+                    .map(|word| LexItem::new(word, features))
+            })
+            .collect()
+    }
+}
+
+/// Context counts of a word or cluster
+type Contexts = BTreeMap<(bool, String), f64>;
+
+/// Similarity of two clusters when it was computed, and the version of
+/// each cluster it was computed for
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    similarity: f64,
+    pair: (usize, usize),
+    versions: (usize, usize),
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    /// Most similar first, then the pair of earliest clusters
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.similarity.total_cmp(&other.similarity).then_with(|| other.pair.cmp(&self.pair))
+    }
+}
+
+/// Cluster the words of `corpus` (one sentence per line) by distribution
+/// and name the clusters from `seed`
+pub fn induce_categories(corpus: &str, options: InductionOptions, seed: &[LexItem]) -> Induction {
+    let normalizer = Normalizer::raw_text();
+    let mut counts: BTreeMap<String, (usize, Contexts)> = BTreeMap::new();
+    for line in corpus.lines() {
+        let normalized = normalizer.apply(line, seed);
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            let left = if i == 0 { BOUNDARY } else { tokens[i - 1] };
+            let right = tokens.get(i + 1).copied().unwrap_or(BOUNDARY);
+            let (count, contexts) = counts.entry(token.to_string()).or_default();
+            *count += 1;
+            *contexts.entry((false, left.to_string())).or_default() += 1.0;
+            *contexts.entry((true, right.to_string())).or_default() += 1.0;
+        }
+    }
+
+    let (frequent, rare): (Vec<_>, Vec<_>) = counts.into_iter().partition(|(_, (count, _))| *count >= options.min_count);
+    // Merged clusters stay in place, emptied, so indices never shift
+    let mut clusters: Vec<Option<(Vec<String>, Contexts)>> = frequent.into_iter()
+        .map(|(word, (_, contexts))| Some((Vec::from([word]), contexts)))
+        .collect();
+    let mut versions = vec![0; clusters.len()];
+    let candidate = |clusters: &[Option<(Vec<String>, Contexts)>], versions: &[usize], i: usize, j: usize| {
+        let (a, b) = (clusters[i].as_ref()?, clusters[j].as_ref()?);
+        Some(Candidate { similarity: squared_cosine(&a.1, &b.1), pair: (i, j), versions: (versions[i], versions[j]) })
+    };
+    let mut queue: BinaryHeap<Candidate> = (0..clusters.len())
+        .flat_map(|i| (i + 1..clusters.len()).map(move |j| (i, j)))
+        .filter_map(|(i, j)| candidate(&clusters, &versions, i, j))
+        .collect();
+
+    let mut remaining = clusters.len();
+    while remaining > options.clusters.max(1) {
+        let Some(Candidate { pair: (i, j), versions: (vi, vj), .. }) = queue.pop() else {
+            break;
+        };
+        // Skip similarities computed before either cluster last changed
+        if clusters[i].is_none() || clusters[j].is_none() || (vi, vj) != (versions[i], versions[j]) {
+            continue;
+        }
+        let (words, contexts) = clusters[j].take().expect("checked above");
+        let merged = clusters[i].as_mut().expect("checked above");
+        merged.0.extend(words);
+        for (context, count) in contexts {
+            *merged.1.entry(context).or_default() += count;
+        }
+        versions[i] += 1;
+        remaining -= 1;
+        for k in (0..clusters.len()).filter(|&k| k != i) {
+            let pair = if k < i { (k, i) } else { (i, k) };
+            queue.extend(candidate(&clusters, &versions, pair.0, pair.1));
+        }
+    }
+
+    let clusters = clusters.into_iter()
+        .flatten()
+        .map(|(mut words, _)| {
+            words.sort();
+            let features = majority_features(&words, seed);
+            WordCluster { words, features }
+        })
+        .collect();
+    Induction { clusters, rare: rare.into_iter().map(|(word, _)| word).collect() }
+}
+
+/// Squared cosine similarity, which orders pairs like the cosine since
+/// counts are non-negative (and needs no square root on no_std)
+fn squared_cosine(a: &Contexts, b: &Contexts) -> f64 {
+    let norm = |v: &Contexts| v.values().map(|x| x * x).sum::<f64>();
+    let dot: f64 = a.iter().filter_map(|(context, x)| Some(x * b.get(context)?)).sum();
+    dot * dot / (norm(a) * norm(b)).max(f64::MIN_POSITIVE)
+}
+
+/// Most frequent bundle among the seed entries of `words` (first found on ties)
+fn majority_features(words: &[String], seed: &[LexItem]) -> Option<Vec<Feature>> {
+    let known: Vec<&Vec<Feature>> = words.iter()
+        .filter_map(|word| seed.iter().find(|item| item.phon == *word))
+        .map(|item| &item.feats)
+        .collect();
+    let votes = |features: &Vec<Feature>| known.iter().filter(|other| **other == features).count();
+    known.iter()
+        .fold(None, |best: Option<&Vec<Feature>>, features| match best {
+            Some(best) if votes(best) >= votes(features) => Some(best),
+            _ => Some(features),
+        })
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category};
+
+    const CORPUS: &str = "The student left.\n\
+        The teacher smiled.\n\
+        A student arrived.\n\
+        The professor left.\n\
+        A tutor smiled.\n\
+        The tutor arrived.\n\
+        A professor smiled.\n\
+        A teacher left.\n\
+        The student smiled.\n\
+        Zebras bloom.";
+
+    #[test]
+    fn test_clusters_follow_distribution() {
+        let induction = induce_categories(CORPUS, InductionOptions { clusters: 3, min_count: 2 }, &test_lexicon());
+        let words: Vec<Vec<&str>> = induction.clusters.iter()
+            .map(|cluster| cluster.words.iter().map(String::as_str).collect())
+            .collect();
+        assert_eq!(words, vec![
+            vec!["a", "the"],
+            vec!["arrived", "left", "smiled"],
+            vec!["professor", "student", "teacher", "tutor"],
+        ]);
+        // The seed lexicon lists neither spelling of "zebras", so it keeps its case
+        assert_eq!(induction.rare, ["Zebras", "bloom"]);
+    }
+
+    #[test]
+    fn test_casing_follows_the_seed_lexicon() {
+        let induction = induce_categories("The student left.\nI left.", InductionOptions { clusters: 1, min_count: 1 }, &test_lexicon());
+        let words = &induction.clusters[0].words;
+        assert!(words.iter().any(|word| word == "the"));
+        assert!(words.iter().any(|word| word == "I"));
+    }
+
+    #[test]
+    fn test_seed_lexicon_names_clusters() {
+        let lexicon = test_lexicon();
+        let induction = induce_categories(CORPUS, InductionOptions { clusters: 3, ..InductionOptions::default() }, &lexicon);
+        let proposals = induction.proposals(&lexicon);
+        assert_eq!(proposals, vec![LexItem::new("professor", &[Feature::Cat(Category::N)])]);
+
+        // Without a seed the clusters stay unnamed
+        let unnamed = induce_categories(CORPUS, InductionOptions { clusters: 3, min_count: 2 }, &[]);
+        assert!(unnamed.clusters.iter().all(|cluster| cluster.features.is_none()));
+        assert!(unnamed.proposals(&[]).is_empty());
+    }
+}
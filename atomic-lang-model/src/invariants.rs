@@ -0,0 +1,187 @@
+//! Well-Formedness Invariants
+//!
+//! Properties every object built by Merge, Adjoin and Move satisfies,
+//! whatever the lexicon: binary branching, lexical leaves without children,
+//! featureless traces, at most one category feature per node (matching its
+//! label), no licensee pending twice (the SMC), and no orphan features, i.e.
+//! every feature on a phrase is inherited from one of its children. A
+//! violation points at an engine bug rather than a bad sentence.
+//!
+//! With the `invariants` feature, debug builds check the result of every
+//! operation and panic on the first violation, so a new or modified
+//! operation fails at the step that broke the structure instead of somewhere
+//! downstream. Release builds compile the checks out.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::fmt;
+
+use crate::{Feature, SyntacticObject};
+
+/// Invariant an object can violate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Invariant {
+    /// Phrase with more than two children
+    Branching,
+    /// Lexical leaf with children
+    LexicalChildren,
+    /// Trace carrying features
+    FeaturefulTrace,
+    /// Node with more than one category feature
+    Categories,
+    /// Category feature that does not match the node's label
+    Label,
+    /// Licensee pending twice on one node
+    Smc,
+    /// Feature on a phrase that none of its children carries
+    Orphan,
+}
+
+impl fmt::Display for Invariant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Invariant::Branching => "more than two children",
+            Invariant::LexicalChildren => "lexical leaf with children",
+            Invariant::FeaturefulTrace => "trace with features",
+            Invariant::Categories => "more than one category feature",
+            Invariant::Label => "category feature does not match the label",
+            Invariant::Smc => "licensee pending twice",
+            Invariant::Orphan => "feature not inherited from a child",
+        })
+    }
+}
+
+/// Violated invariant and the child-index path to the offending node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Invariant violated
+    pub invariant: Invariant,
+    /// Path from the root to the node
+    pub path: Vec<usize>,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {:?}", self.invariant, self.path)
+    }
+}
+
+/// Check every node of `obj`, reporting the first violation in pre-order
+pub fn check_invariants(obj: &SyntacticObject) -> Result<(), Violation> {
+    let mut path = Vec::new();
+    check_node(obj, &mut path)
+}
+
+fn check_node(node: &SyntacticObject, path: &mut Vec<usize>) -> Result<(), Violation> {
+    if let Some(invariant) = node_violation(node) {
+        return Err(Violation { invariant, path: path.clone() });
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        check_node(child, path)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+fn node_violation(node: &SyntacticObject) -> Option<Invariant> {
+    if node.children.len() > 2 {
+        return Some(Invariant::Branching);
+    }
+    if node.phon.is_some() && !node.children.is_empty() {
+        return Some(Invariant::LexicalChildren);
+    }
+    if node.is_trace() && !node.features.is_empty() {
+        return Some(Invariant::FeaturefulTrace);
+    }
+
+    let mut categories = node.features.iter().filter_map(|f| match f {
+        Feature::Cat(cat) => Some(cat),
+        _ => None,
+    });
+    if let Some(cat) = categories.next() {
+        if categories.next().is_some() {
+            return Some(Invariant::Categories);
+        }
+        if !node.label.matches(cat) {
+            return Some(Invariant::Label);
+        }
+    }
+
+    let licensees: Vec<&Feature> = node.features.iter().filter(|f| f.is_negative()).collect();
+    if licensees.iter().enumerate().any(|(i, f)| licensees[..i].contains(f)) {
+        return Some(Invariant::Smc);
+    }
+
+    // Each feature of a phrase, counted with multiplicity, comes from a child
+    if !node.children.is_empty() {
+        let count = |features: &[Feature], f: &Feature| features.iter().filter(|g| *g == f).count();
+        let orphan = node.features.iter().any(|f| {
+            count(&node.features, f) > node.children.iter().map(|child| count(&child.features, f)).sum()
+        });
+        if orphan {
+            return Some(Invariant::Orphan);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crossing::derive_crossing;
+    use crate::{parse_sentence, Category, GrammarOptions};
+
+    #[test]
+    fn test_derivations_are_well_formed() {
+        let lexicon = GrammarOptions::default().lexicon();
+        for sentence in ["the student left", "the student thinks the teacher", "the student who left smiled"] {
+            let tree = parse_sentence(sentence, &lexicon).expect(sentence);
+            assert_eq!(check_invariants(&tree), Ok(()), "{}", sentence);
+        }
+        for m in 1..=3 {
+            assert_eq!(check_invariants(&derive_crossing(2, m).unwrap()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_violations_are_located() {
+        let leaf = |phon: &str, features: &[Feature]| SyntacticObject::from_lex(&crate::LexItem::new(phon, features));
+        let the = leaf("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]);
+        let student = leaf("student", &[Feature::Cat(Category::N), Feature::Neg(1)]);
+
+        // A licensee that appears from nowhere
+        let orphan = SyntacticObject::internal(Category::DP, vec![Feature::Cat(Category::D), Feature::Neg(2)], vec![
+            the.clone(),
+            student.clone(),
+        ]);
+        assert_eq!(check_invariants(&orphan), Err(Violation { invariant: Invariant::Orphan, path: vec![] }));
+
+        let mut trace = SyntacticObject::trace(Category::N);
+        trace.features.push(Feature::Neg(1));
+        let nested = SyntacticObject::internal(Category::DP, vec![Feature::Cat(Category::D)], vec![the.clone(), trace]);
+        assert_eq!(
+            check_invariants(&nested).map_err(|v| (v.invariant, v.path)),
+            Err((Invariant::FeaturefulTrace, vec![1]))
+        );
+
+        let mislabeled = leaf("left", &[Feature::Cat(Category::V)]);
+        let mislabeled = SyntacticObject { label: Category::N, ..mislabeled };
+        assert_eq!(check_invariants(&mislabeled).unwrap_err().invariant, Invariant::Label);
+
+        let twice = leaf("who", &[Feature::Cat(Category::D), Feature::Neg(1), Feature::Neg(1)]);
+        assert_eq!(check_invariants(&twice).unwrap_err().invariant, Invariant::Smc);
+        let flat = SyntacticObject::internal(Category::DP, Vec::new(), vec![the.clone(), student.clone(), the]);
+        assert_eq!(check_invariants(&flat).unwrap_err().to_string(), "more than two children at []");
+    }
+
+    #[test]
+    #[cfg(all(feature = "invariants", debug_assertions))]
+    #[should_panic(expected = "merge broke an invariant: more than two children at []")]
+    fn test_debug_check_panics_on_violation() {
+        let the = SyntacticObject::from_lex(&crate::LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]));
+        let flat = SyntacticObject::internal(Category::DP, Vec::new(), vec![the.clone(), the.clone(), the]);
+        debug_invariants!(flat, "merge", true);
+    }
+}
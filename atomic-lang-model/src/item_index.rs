@@ -0,0 +1,171 @@
+//! Workspace Item Index
+//!
+//! Finding mergeable pairs by checking every item against every other costs
+//! O(n²) comparisons per derivation step, although only pairs whose
+//! categories line up can ever merge. An `ItemIndex` keeps the workspace's
+//! items filed by the category their first selector needs and by the
+//! category they provide once saturated, so the candidates for a merge are
+//! read off the index. The workspace updates it as operations replace
+//! items, which costs O(n) position shifts per step instead of O(n²)
+//! feature checks.
+//!
+//! Positions are workspace positions, so the index describes the item list
+//! it was last updated with; the workspace rebuilds it after its items are
+//! edited through `Workspace::items_mut`.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::{FeatureSet, SyntacticObject};
+
+/// Head categories, one slot per head bit of a `FeatureSet`
+const HEADS: usize = 8;
+
+/// Workspace items filed by the categories they need and provide
+#[derive(Debug, Clone, Default)]
+pub struct ItemIndex {
+    /// Summary of each item, in workspace order
+    sets: Vec<FeatureSet>,
+    /// Positions of items whose first selector selects each head, ascending
+    needs: [Vec<usize>; HEADS],
+    /// Positions of saturated items of each head's category, ascending
+    provides: [Vec<usize>; HEADS],
+}
+
+impl ItemIndex {
+    /// Index a list of items
+    pub fn new(items: &[SyntacticObject]) -> Self {
+        let mut index = Self::default();
+        index.rebuild(items);
+        index
+    }
+
+    /// Re-index a list of items, keeping the buffers
+    pub fn rebuild(&mut self, items: &[SyntacticObject]) {
+        self.clear();
+        for (pos, item) in items.iter().enumerate() {
+            self.insert(pos, item);
+        }
+    }
+
+    /// Forget every item
+    pub fn clear(&mut self) {
+        self.sets.clear();
+        self.needs.iter_mut().chain(&mut self.provides).for_each(Vec::clear);
+    }
+
+    /// Number of items indexed
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Check whether no items are indexed
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+
+    /// Summary of the item at `pos`
+    pub fn set(&self, pos: usize) -> &FeatureSet {
+        &self.sets[pos]
+    }
+
+    /// Index `item` at `pos`, shifting later items up
+    pub fn insert(&mut self, pos: usize, item: &SyntacticObject) {
+        for list in self.needs.iter_mut().chain(&mut self.provides) {
+            list.iter_mut().filter(|p| **p >= pos).for_each(|p| *p += 1);
+        }
+        let set = FeatureSet::of(item);
+        self.sets.insert(pos, set);
+        self.file(pos, set);
+    }
+
+    /// Drop the item at `pos`, shifting later items down
+    pub fn remove(&mut self, pos: usize) {
+        let set = self.sets.remove(pos);
+        self.unfile(pos, set);
+        for list in self.needs.iter_mut().chain(&mut self.provides) {
+            list.iter_mut().filter(|p| **p > pos).for_each(|p| *p -= 1);
+        }
+    }
+
+    /// Re-index the item at `pos` after it changed in place
+    pub fn replace(&mut self, pos: usize, item: &SyntacticObject) {
+        let set = FeatureSet::of(item);
+        self.unfile(pos, self.sets[pos]);
+        self.sets[pos] = set;
+        self.file(pos, set);
+    }
+
+    /// Pairs (selector, selectee) whose categories line up and whose
+    /// positions allow a merge: the selectee is adjacent on the selector's
+    /// side, or is a mover
+    pub fn candidates(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.needs.iter().zip(&self.provides).flat_map(move |(needs, provides)| {
+            needs.iter().flat_map(move |&i| {
+                let adjacent = match self.sets[i].selects_specifier() {
+                    false => i.checked_add(1),
+                    true => i.checked_sub(1),
+                };
+                provides.iter()
+                    .filter(move |&&j| j != i && (Some(j) == adjacent || self.sets[j].has_licensees()))
+                    .map(move |&j| (i, j))
+            })
+        })
+    }
+
+    /// Add `pos` to the lists its summary belongs in
+    fn file(&mut self, pos: usize, set: FeatureSet) {
+        let lists = [
+            set.selector_head().map(|head| &mut self.needs[head]),
+            set.category_head().filter(|_| !set.has_selector()).map(|head| &mut self.provides[head]),
+        ];
+        for list in lists.into_iter().flatten() {
+            let at = list.partition_point(|&p| p < pos);
+            list.insert(at, pos);
+        }
+    }
+
+    /// Remove `pos` from the lists its summary belongs in
+    fn unfile(&mut self, pos: usize, set: FeatureSet) {
+        let lists = [
+            set.selector_head().map(|head| &mut self.needs[head]),
+            set.category_head().filter(|_| !set.has_selector()).map(|head| &mut self.provides[head]),
+        ];
+        for list in lists.into_iter().flatten() {
+            if let Ok(at) = list.binary_search(&pos) {
+                list.remove(at);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category, Feature, LexItem};
+
+    #[test]
+    fn test_updates_match_rebuild() {
+        let lexicon = test_lexicon();
+        let items: Vec<SyntacticObject> = [0, 2, 7, 0, 3].iter().map(|&i| SyntacticObject::from_lex(&lexicon[i])).collect();
+        let mut index = ItemIndex::new(&items[..2]);
+        for (pos, item) in items.iter().enumerate().skip(2) {
+            index.insert(pos, item);
+        }
+        let candidates: Vec<_> = index.candidates().collect();
+        assert_eq!(candidates, ItemIndex::new(&items).candidates().collect::<Vec<_>>());
+        // "the" selects the adjacent noun only; "said" needs a DP and none is saturated yet
+        assert!(candidates.contains(&(0, 1)) && candidates.contains(&(3, 4)));
+        assert_eq!(candidates.len(), 2);
+
+        // Removing the first pair shifts the second down
+        index.remove(1);
+        index.remove(0);
+        assert_eq!(index.candidates().collect::<Vec<_>>(), [(1, 2)]);
+
+        // A mover is a candidate wherever it sits
+        let who = LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(1)]);
+        index.replace(2, &SyntacticObject::from_lex(&who));
+        assert_eq!(index.candidates().collect::<Vec<_>>(), [(0, 2)]);
+    }
+}
@@ -0,0 +1,709 @@
+//! Versioned Lexicons
+//!
+//! JSON storage for lexicons and a diff between two versions, so grammar
+//! edits can be audited. Features are written in directional MG notation:
+//!
+//! | Feature      | Notation |
+//! |--------------|----------|
+//! | `Cat(D)`     | `D`      |
+//! | `Sel(N)`     | `=N`     |
+//! | `Spec(D)`    | `D=`     |
+//! | `Adjoin(V)`  | `~V`     |
+//! | `Pos(1)`     | `+1`     |
+//! | `Neg(1)`     | `-1`     |
+//!
+//! ```json
+//! {
+//!   "version": "1.0.0",
+//!   "items": [
+//!     { "phon": "the", "features": ["D", "=N"] }
+//!   ]
+//! }
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{parse_sentence, AgrBundle, Category, Feature, LexItem};
+
+/// Errors reading a lexicon
+///
+/// Errors are `Copy` and never allocate, so they can be built and reported
+/// on no_std targets without a heap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LexiconError {
+    /// Malformed JSON at a byte offset
+    Syntax {
+        /// Byte offset of the error
+        position: usize,
+        /// What was expected
+        message: &'static str,
+    },
+    /// Well-formed JSON that does not describe a lexicon
+    Schema(&'static str),
+    /// Unrecognized feature or category notation
+    Feature(Notation),
+}
+
+/// Offending notation kept inline in an error, truncated to `Notation::CAPACITY` bytes
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Notation {
+    bytes: [u8; Notation::CAPACITY],
+    len: u8,
+}
+
+impl Notation {
+    /// Bytes kept from the offending notation
+    pub const CAPACITY: usize = 16;
+
+    /// Copy `s`, truncated to the last character boundary that fits
+    pub const fn new(s: &str) -> Self {
+        let src = s.as_bytes();
+        let mut len = if src.len() < Self::CAPACITY { src.len() } else { Self::CAPACITY };
+        // Back up over UTF-8 continuation bytes so the copy stays valid
+        while len < src.len() && (src[len] as i8) < -0x40 {
+            len -= 1;
+        }
+
+        let mut bytes = [0; Self::CAPACITY];
+        let mut i = 0;
+        while i < len {
+            bytes[i] = src[i];
+            i += 1;
+        }
+        Self { bytes, len: len as u8 }
+    }
+
+    /// The (possibly truncated) notation
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len as usize]).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for LexiconError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LexiconError::Syntax { position, message } => {
+                write!(f, "JSON syntax error at byte {}: {}", position, message)
+            }
+            LexiconError::Schema(message) => write!(f, "Invalid lexicon: {}", message),
+            LexiconError::Feature(notation) => write!(f, "Unknown feature: {}", notation),
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+impl FromStr for Category {
+    type Err = LexiconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "N" => Category::N,
+            "V" => Category::V,
+            "D" => Category::D,
+            "C" => Category::C,
+            "P" => Category::P,
+            "S" => Category::S,
+            "NP" => Category::NP,
+            "VP" => Category::VP,
+            "DP" => Category::DP,
+            "CP" => Category::CP,
+            "PP" => Category::PP,
+            _ => return Err(LexiconError::Feature(Notation::new(s))),
+        })
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feature::Cat(cat) => write!(f, "{}", cat),
+            Feature::Sel(cat) => write!(f, "={}", cat),
+            Feature::Spec(cat) => write!(f, "{}=", cat),
+            Feature::Adjoin(cat) => write!(f, "~{}", cat),
+            Feature::Pos(i) => write!(f, "+{}", i),
+            Feature::Neg(i) => write!(f, "-{}", i),
+            Feature::Agr(bundle) => write!(f, "{}", bundle),
+        }
+    }
+}
+
+impl FromStr for Feature {
+    type Err = LexiconError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let index = |digits: &str| digits.parse().map_err(|_| LexiconError::Feature(Notation::new(s)));
+        let category = |name: &str| name.parse().map_err(|_| LexiconError::Feature(Notation::new(s)));
+
+        if let Some(digits) = s.strip_prefix('+') {
+            Ok(Feature::Pos(index(digits)?))
+        } else if let Some(digits) = s.strip_prefix('-') {
+            Ok(Feature::Neg(index(digits)?))
+        } else if let Some(name) = s.strip_prefix('=') {
+            Ok(Feature::Sel(category(name)?))
+        } else if let Some(name) = s.strip_suffix('=') {
+            Ok(Feature::Spec(category(name)?))
+        } else if let Some(name) = s.strip_prefix('~') {
+            Ok(Feature::Adjoin(category(name)?))
+        } else if let Some(notation) = s.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+            AgrBundle::parse(notation).map(Feature::Agr).ok_or(LexiconError::Feature(Notation::new(s)))
+        } else {
+            Ok(Feature::Cat(category(s)?))
+        }
+    }
+}
+
+impl fmt::Display for LexItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let phon = if self.phon.is_empty() { "ε" } else { &self.phon };
+        write!(f, "{} ::", phon)?;
+        for feat in &self.feats {
+            write!(f, " {}", feat)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lexicon with a version label
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lexicon {
+    /// Version label (free-form, e.g. semantic version or date)
+    pub version: String,
+    /// Lexical items, in lookup order
+    pub items: Vec<LexItem>,
+}
+
+impl Lexicon {
+    /// Create a versioned lexicon
+    pub fn new(version: &str, items: Vec<LexItem>) -> Self {
+        Self {
+            version: version.to_string(),
+            items,
+        }
+    }
+
+    /// Read a lexicon from JSON
+    pub fn from_json(json: &str) -> Result<Self, LexiconError> {
+        let value = parse_json(json)?;
+        let schema = LexiconError::Schema;
+        let version = match value.field("version") {
+            Some(Json::String(version)) => version.clone(),
+            Some(_) => return Err(schema("\"version\" must be a string")),
+            None => String::new(),
+        };
+        let Some(Json::Array(entries)) = value.field("items") else {
+            return Err(schema("expected an \"items\" array"));
+        };
+
+        let items = entries.iter().map(item_from_json).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { version, items })
+    }
+
+    /// Write the lexicon as JSON, one item per line
+    pub fn to_json(&self) -> String {
+        let items: Vec<String> = self.items.iter().map(|item| format!("    {}", item_json(item))).collect();
+
+        format!(
+            "{{\n  \"version\": {},\n  \"items\": [\n{}\n  ]\n}}\n",
+            json_string(&self.version),
+            items.join(",\n")
+        )
+    }
+
+    /// Items added, removed, and changed going from `self` to `newer`
+    ///
+    /// Items are compared whole; a removed and an added item with the same
+    /// phonology are reported as one changed item.
+    pub fn diff(&self, newer: &Lexicon) -> LexiconDiff {
+        let mut removed: Vec<LexItem> = multiset_difference(&self.items, &newer.items);
+        let mut added: Vec<LexItem> = multiset_difference(&newer.items, &self.items);
+
+        let mut changed = Vec::new();
+        let mut i = 0;
+        while i < removed.len() {
+            if let Some(j) = added.iter().position(|item| item.phon == removed[i].phon) {
+                let new = added.remove(j);
+                let old = removed.remove(i);
+                changed.push(LexicalChange { phon: old.phon, old: old.feats, new: new.feats });
+            } else {
+                i += 1;
+            }
+        }
+
+        LexiconDiff {
+            from_version: self.version.clone(),
+            to_version: newer.version.clone(),
+            added,
+            removed,
+            changed,
+        }
+    }
+}
+
+/// Items of `a` not matched by an equal item of `b`, counting duplicates
+fn multiset_difference(a: &[LexItem], b: &[LexItem]) -> Vec<LexItem> {
+    let mut unmatched: Vec<&LexItem> = b.iter().collect();
+    a.iter()
+        .filter(|item| match unmatched.iter().position(|other| other == item) {
+            Some(k) => {
+                unmatched.remove(k);
+                false
+            }
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Item whose feature bundle changed between versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexicalChange {
+    /// Phonological form shared by both versions
+    pub phon: String,
+    /// Features before the change
+    pub old: Vec<Feature>,
+    /// Features after the change
+    pub new: Vec<Feature>,
+}
+
+/// Differences between two lexicon versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconDiff {
+    /// Version diffed from
+    pub from_version: String,
+    /// Version diffed to
+    pub to_version: String,
+    /// Items only in the newer version
+    pub added: Vec<LexItem>,
+    /// Items only in the older version
+    pub removed: Vec<LexItem>,
+    /// Items whose features changed
+    pub changed: Vec<LexicalChange>,
+}
+
+impl LexiconDiff {
+    /// Check if the versions contain the same items
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl fmt::Display for LexiconDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Lexicon {} -> {}", self.from_version, self.to_version)?;
+        for item in &self.added {
+            writeln!(f, "+ {}", item)?;
+        }
+        for item in &self.removed {
+            writeln!(f, "- {}", item)?;
+        }
+        for change in &self.changed {
+            let old = LexItem { phon: change.phon.clone(), feats: change.old.clone(), weight: None };
+            let new: Vec<String> = change.new.iter().map(|feat| feat.to_string()).collect();
+            writeln!(f, "~ {} => {}", old, new.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Sentence whose parse status differs between lexicon versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    /// The sentence
+    pub sentence: String,
+    /// Whether it parsed with the older lexicon
+    pub before: bool,
+    /// Whether it parses with the newer lexicon
+    pub after: bool,
+}
+
+/// Sentences that parse under exactly one of the two lexicons
+pub fn status_changes(old: &[LexItem], new: &[LexItem], sentences: &[&str]) -> Vec<StatusChange> {
+    sentences.iter()
+        .map(|&sentence| StatusChange {
+            sentence: sentence.to_string(),
+            before: parse_sentence(sentence, old).is_ok(),
+            after: parse_sentence(sentence, new).is_ok(),
+        })
+        .filter(|change| change.before != change.after)
+        .collect()
+}
+
+/// Quote a string as a JSON string literal
+pub(crate) fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Encode a lexical item as `{ "phon": ..., "features": [...] }`, with a
+/// `"weight"` if it has one
+pub(crate) fn item_json(item: &LexItem) -> String {
+    let features: Vec<String> = item.feats.iter().map(|feat| json_string(&feat.to_string())).collect();
+    let weight = item.weight.map(|weight| format!(", \"weight\": {}", weight)).unwrap_or_default();
+    format!("{{ \"phon\": {}, \"features\": [{}]{} }}", json_string(&item.phon), features.join(", "), weight)
+}
+
+/// Decode a lexical item written by `item_json`
+pub(crate) fn item_from_json(entry: &Json) -> Result<LexItem, LexiconError> {
+    let schema = LexiconError::Schema;
+    let Some(Json::String(phon)) = entry.field("phon") else {
+        return Err(schema("item without a \"phon\" string"));
+    };
+    let Some(Json::Array(features)) = entry.field("features") else {
+        return Err(schema("item without a \"features\" array"));
+    };
+    let feats = features.iter()
+        .map(|feature| match feature {
+            Json::String(notation) => notation.parse(),
+            _ => Err(schema("features must be strings")),
+        })
+        .collect::<Result<Vec<Feature>, _>>()?;
+    let weight = match entry.field("weight") {
+        None => None,
+        Some(&Json::Number(weight)) if (0.0..=1.0).contains(&weight) => Some(weight),
+        Some(_) => return Err(schema("weight must be a number between 0 and 1")),
+    };
+    Ok(LexItem { phon: phon.clone(), feats, weight })
+}
+
+/// Parse a complete JSON document
+pub(crate) fn parse_json(json: &str) -> Result<Json, LexiconError> {
+    let mut parser = JsonParser { bytes: json.as_bytes(), pos: 0, depth: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != json.len() {
+        return Err(parser.error("end of input"));
+    }
+    Ok(value)
+}
+
+/// Parsed JSON value (only what lexicon and proof files need)
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Look up an object field
+    pub(crate) fn field(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(key, _)| key == name).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects the parser accepts, so hostile
+/// input fails with a syntax error instead of overflowing the stack
+const MAX_JSON_DEPTH: usize = 128;
+
+/// Recursive-descent JSON parser
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Arrays and objects open at `pos`
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &'static str) -> LexiconError {
+        LexiconError::Syntax { position: self.pos, message }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8, message: &'static str) -> Result<(), LexiconError> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, LexiconError> {
+        if self.bytes[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("a JSON value"))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, LexiconError> {
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error("a JSON value")),
+        }
+    }
+
+    /// Parse an array or object one level deeper
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<Json, LexiconError>) -> Result<Json, LexiconError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error("at most 128 levels of nesting"));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json, LexiconError> {
+        self.expect(b'{', "'{'")?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':', "':'")?;
+            fields.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, LexiconError> {
+        self.expect(b'[', "'['")?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) == Some(&b']') {
+            self.pos += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return Err(self.error("',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, LexiconError> {
+        if self.bytes.get(self.pos) != Some(&b'"') {
+            return Err(self.error("a string"));
+        }
+        self.pos += 1;
+
+        let mut out = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.bytes.get(self.pos), None | Some(b'"' | b'\\')) {
+                self.pos += 1;
+            }
+            // Input is a &str and we only stop at ASCII bytes, so this slice is valid UTF-8
+            out.push_str(core::str::from_utf8(&self.bytes[start..self.pos]).map_err(|_| self.error("UTF-8"))?);
+
+            match self.bytes.get(self.pos) {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let escaped = match self.bytes.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let code = self.hex4()?;
+                            let code = if (0xD800..0xDC00).contains(&code) {
+                                // High surrogate: combine with the following low surrogate
+                                if !self.bytes[self.pos + 1..].starts_with(b"\\u") {
+                                    return Err(self.error("a low surrogate"));
+                                }
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                0x10000 + ((code - 0xD800) << 10) + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                code
+                            };
+                            char::from_u32(code).ok_or_else(|| self.error("a valid code point"))?
+                        }
+                        _ => return Err(self.error("an escape sequence")),
+                    };
+                    out.push(escaped);
+                    self.pos += 1;
+                }
+                _ => return Err(self.error("closing '\"'")),
+            }
+        }
+    }
+
+    /// Read the four hex digits after `\u`, leaving `pos` on the last one
+    fn hex4(&mut self) -> Result<u32, LexiconError> {
+        let digits = self.bytes.get(self.pos + 1..self.pos + 5).ok_or_else(|| self.error("four hex digits"))?;
+        let code = core::str::from_utf8(digits).ok()
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("four hex digits"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn number(&mut self) -> Result<Json, LexiconError> {
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| b.is_ascii_digit() || b"+-.eE".contains(b)) {
+            self.pos += 1;
+        }
+        core::str::from_utf8(&self.bytes[start..self.pos]).ok()
+            .and_then(|number| number.parse().ok())
+            .map(Json::Number)
+            .ok_or(LexiconError::Syntax { position: start, message: "a number" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    #[test]
+    fn test_feature_notation_round_trip() {
+        for item in test_lexicon() {
+            for feat in &item.feats {
+                assert_eq!(feat.to_string().parse::<Feature>().as_ref(), Ok(feat));
+            }
+        }
+        assert_eq!("~V".parse(), Ok(Feature::Adjoin(Category::V)));
+        assert_eq!("-2".parse(), Ok(Feature::Neg(2)));
+        assert_eq!("=Q".parse::<Feature>(), Err(LexiconError::Feature(Notation::new("=Q"))));
+    }
+
+    #[test]
+    fn test_notation_truncates_inline() {
+        let error = "=VeryLongCategoryName".parse::<Feature>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown feature: =VeryLongCategor");
+
+        // Truncation never splits a character
+        let accented = Notation::new("=ééééééééé");
+        assert_eq!(accented.as_str(), "=ééééééé");
+        assert_eq!(format!("{:?}", accented), "\"=ééééééé\"");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut items = test_lexicon();
+        items.push(LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(2)]));
+        items.push(LexItem::new("say \"cheese\"", &[Feature::Cat(Category::V)]));
+        items.push(LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]).weighted(0.35));
+        let lexicon = Lexicon::new("1.2.0", items);
+
+        assert_eq!(Lexicon::from_json(&lexicon.to_json()), Ok(lexicon));
+    }
+
+    #[test]
+    fn test_json_errors() {
+        assert!(matches!(Lexicon::from_json("{\"items\": [}"), Err(LexiconError::Syntax { position: 11, .. })));
+        assert!(matches!(Lexicon::from_json("{\"version\": \"1\"}"), Err(LexiconError::Schema(_))));
+
+        let unknown = r#"{"items": [{"phon": "x", "features": ["X"]}]}"#;
+        assert_eq!(Lexicon::from_json(unknown), Err(LexiconError::Feature(Notation::new("X"))));
+
+        let escaped = r#"{"items": [{"phon": "été", "features": []}]}"#;
+        assert_eq!(Lexicon::from_json(escaped).unwrap().items[0].phon, "été");
+
+        let heavy = r#"{"items": [{"phon": "x", "features": [], "weight": 1.5}]}"#;
+        assert!(matches!(Lexicon::from_json(heavy), Err(LexiconError::Schema(_))));
+
+        // Deep nesting is an error, not a stack overflow
+        let deep = "[".repeat(100_000);
+        assert!(matches!(parse_json(&deep), Err(LexiconError::Syntax { position: 128, .. })));
+        let nested = format!("{}{}", "[".repeat(MAX_JSON_DEPTH), "]".repeat(MAX_JSON_DEPTH));
+        assert!(parse_json(&nested).is_ok());
+    }
+
+    #[test]
+    fn test_diff_and_status_changes() {
+        let old = Lexicon::new("1", test_lexicon());
+        let mut items = test_lexicon();
+        items.retain(|item| item.phon != "tutor");
+        items[0] = LexItem::new("the", &[Feature::Cat(Category::D)]);
+        items.push(LexItem::new("pupil", &[Feature::Cat(Category::N)]));
+        let new = Lexicon::new("2", items);
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.added, vec![LexItem::new("pupil", &[Feature::Cat(Category::N)])]);
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].phon, "tutor");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].phon, "the");
+        assert!(old.diff(&old).is_empty());
+        assert!(diff.to_string().contains("~ the :: D =N => D"));
+
+        let changes = status_changes(&old.items, &new.items, &["the student left", "the tutor smiled", "student left"]);
+        assert_eq!(changes, vec![
+            StatusChange { sentence: "the student left".to_string(), before: true, after: false },
+            StatusChange { sentence: "the tutor smiled".to_string(), before: true, after: false },
+        ]);
+    }
+}
@@ -0,0 +1,167 @@
+//! Logical Form
+//!
+//! The meaning side of a derivation. LF keeps the derived tree's hierarchy
+//! but not its phonology or features: each binary node records how its
+//! daughters compose, which transfer reads off the syntax. A selecting head
+//! applies to its argument, an adjunct is conjoined with its host, and a
+//! moved phrase takes scope over its remnant, binding the variable its trace
+//! leaves behind. `interpret` composes a `Term` from the result.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::constraints::chains;
+use crate::{Category, Feature, SyntacticObject};
+
+/// Constituent at LF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LfNode {
+    /// Lexical head, `None` for a silent (semantically vacuous) head
+    Head(Category, Option<String>),
+    /// Trace bound by the movement with this (1-based) chain index
+    Variable(usize),
+    /// Selecting head and its argument
+    Apply(Box<LfNode>, Box<LfNode>),
+    /// Host and adjunct
+    Modify(Box<LfNode>, Box<LfNode>),
+    /// Moved phrase scoping over the remnant it binds a variable in
+    Abstract(usize, Box<LfNode>, Box<LfNode>),
+}
+
+/// Build the LF representation of a derived tree
+pub fn lower(tree: &SyntacticObject) -> LfNode {
+    let chains = chains(tree);
+    let mut path = Vec::new();
+    lower_node(tree, &mut path, &|path: &[usize], landing: bool| {
+        chains.iter()
+            .position(|chain| if landing { chain.landing == path } else { chain.trace == path })
+            .map(|i| i + 1)
+    })
+}
+
+fn lower_node(node: &SyntacticObject, path: &mut Vec<usize>, chain: &dyn Fn(&[usize], bool) -> Option<usize>) -> LfNode {
+    if let Some(phon) = &node.phon {
+        let word = Some(phon.to_string()).filter(|phon| !phon.is_empty());
+        return LfNode::Head(node.label.clone(), word);
+    }
+    if node.children.is_empty() {
+        // A trace no movement binds contributes nothing
+        return chain(path, false).map_or(LfNode::Head(node.label.clone(), None), LfNode::Variable);
+    }
+
+    let landing = chain(path, true);
+    let mut lower_child = |i: usize| {
+        path.push(i);
+        let child = lower_node(&node.children[i], path, chain);
+        path.pop();
+        Box::new(child)
+    };
+    if node.children.len() == 1 {
+        return *lower_child(0);
+    }
+    if let Some(index) = landing {
+        return LfNode::Abstract(index, lower_child(0), lower_child(1));
+    }
+
+    let head = node.head_index().unwrap_or(0);
+    let (function, argument) = (lower_child(head), lower_child(1 - head));
+    let adjunct = &node.children[1 - head];
+    if adjunct.features.iter().any(|f| matches!(f, Feature::Adjoin(_))) && node.children[head].next_selector().is_none() {
+        LfNode::Modify(function, argument)
+    } else {
+        LfNode::Apply(function, argument)
+    }
+}
+
+/// Logical term composed from an LF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    /// Meaning of an overt word
+    Const(String),
+    /// Variable bound by the chain with this index
+    Var(usize),
+    /// Function application
+    App(Box<Term>, Box<Term>),
+    /// Abstraction over a variable
+    Lambda(usize, Box<Term>),
+    /// Conjunction of predicates
+    And(Box<Term>, Box<Term>),
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let grouped = |term: &Term, f: &mut fmt::Formatter| match term {
+            Term::Lambda(..) | Term::And(..) => write!(f, "({})", term),
+            _ => write!(f, "{}", term),
+        };
+        match self {
+            Term::Const(word) => f.write_str(word),
+            Term::Var(i) => write!(f, "x{}", i),
+            Term::App(function, argument) => {
+                grouped(function, f)?;
+                write!(f, "({})", argument)
+            }
+            Term::Lambda(i, body) => write!(f, "λx{}. {}", i, body),
+            Term::And(left, right) => {
+                grouped(left, f)?;
+                f.write_str(" ∧ ")?;
+                grouped(right, f)
+            }
+        }
+    }
+}
+
+impl LfNode {
+    /// Compose the meaning of the constituent
+    ///
+    /// Silent heads are vacuous: a phrase they head means what its other
+    /// daughter means. Returns `None` if nothing in the constituent is overt.
+    pub fn interpret(&self) -> Option<Term> {
+        let both = |a: &LfNode, b: &LfNode, combine: fn(Box<Term>, Box<Term>) -> Term| {
+            match (a.interpret(), b.interpret()) {
+                (Some(a), Some(b)) => Some(combine(Box::new(a), Box::new(b))),
+                (a, b) => a.or(b),
+            }
+        };
+        match self {
+            LfNode::Head(_, word) => word.clone().map(Term::Const),
+            LfNode::Variable(i) => Some(Term::Var(*i)),
+            LfNode::Apply(function, argument) => both(function, argument, Term::App),
+            LfNode::Modify(host, adjunct) => both(host, adjunct, Term::And),
+            LfNode::Abstract(i, moved, remnant) => {
+                let body = remnant.interpret().map(|body| Term::Lambda(*i, Box::new(body)));
+                match (moved.interpret(), body) {
+                    (Some(moved), Some(body)) => Some(Term::App(Box::new(moved), Box::new(body))),
+                    (moved, body) => moved.or(body),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, GrammarOptions, RelativeClauseAnalysis};
+
+    #[test]
+    fn test_selection_is_application() {
+        let tree = parse_sentence("the student left", &test_lexicon()).unwrap();
+        let term = lower(&tree).interpret().unwrap();
+        assert_eq!(term.to_string(), "left(the(student))");
+    }
+
+    #[test]
+    fn test_movement_binds_its_trace() {
+        let options = GrammarOptions { relative_clauses: RelativeClauseAnalysis::OperatorMovement, ..GrammarOptions::default() };
+        let tree = parse_sentence("the student who left smiled", &options.lexicon()).unwrap();
+        let term = lower(&tree).interpret().unwrap();
+        assert_eq!(term.to_string(), "smiled(the(student ∧ who(λx1. left(x1))))");
+
+        let lambda = Term::Lambda(1, Box::new(Term::Var(1)));
+        let applied = Term::App(Box::new(lambda), Box::new(Term::Const("who".into())));
+        assert_eq!(applied.to_string(), "(λx1. x1)(who)");
+    }
+}
@@ -9,6 +9,15 @@
 //! - Zero runtime dependencies
 //! - Polynomial-time parsing with bounded memory
 //! - Token-level linguistic evaluation
+//!
+//! # Pipeline
+//! A derivation runs in phases, each in its own module:
+//! 1. Narrow syntax (this module): Merge, Adjoin and Move build a `SyntacticObject`
+//! 2. Transfer (`transfer`): a convergent object is handed to both interfaces
+//! 3. PF (`pf`): the featureless tree that linearization reads, with
+//!    morphophonological rules applied by `spellout` and prosodic phrasing
+//!    annotated by `prosody`
+//! 4. LF (`lf`): composition of the meaning, with moved phrases binding their traces
 
 #![cfg_attr(feature = "no_std", no_std)]
 #![forbid(unsafe_code)]
@@ -21,16 +30,238 @@ extern crate std;
 extern crate alloc;
 
 #[cfg(not(feature = "std"))]
-use alloc::{vec::Vec, string::String, format};
+use alloc::{vec, vec::Vec, string::{String, ToString}, format, collections::BTreeSet};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
 
+use core::borrow::Borrow;
+use core::cell::Cell;
 use core::fmt;
 
+/// Debug-level diagnostic, routed to the `log` facade under the `logging` feature
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::debug!(target: "atomic_lang_model", $($arg)*);
+    };
+}
+
+/// Trace-level diagnostic, routed to the `log` facade under the `logging` feature
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "logging")]
+        log::trace!(target: "atomic_lang_model", $($arg)*);
+    };
+}
+
+/// Enter a `tracing` span for a parse phase under the `tracing` feature
+///
+/// Evaluates to a guard that closes the span when dropped.
+macro_rules! phase_span {
+    ($name:literal $(, $($fields:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(target: "atomic_lang_model", $name $(, $($fields)*)?).entered();
+        #[cfg(not(feature = "tracing"))]
+        let span = $crate::NoSpan;
+        span
+    }};
+}
+
+/// Stand-in guard for `phase_span!` without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+struct NoSpan;
+
+/// Record a field on a span opened by `phase_span!` under the `tracing` feature
+macro_rules! record_field {
+    ($span:expr, $field:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        $span.record($field, $value);
+        #[cfg(not(feature = "tracing"))]
+        let _ = (&$span, || $value);
+    };
+}
+
+/// Whether an operation's inputs satisfy the well-formedness invariants
+/// (always false unless debug builds have the `invariants` feature)
+macro_rules! invariants_hold {
+    ($($input:expr),+) => {{
+        #[cfg(all(feature = "invariants", debug_assertions))]
+        let held = $($crate::invariants::check_invariants(&$input).is_ok())&&+;
+        #[cfg(not(all(feature = "invariants", debug_assertions)))]
+        let held = false;
+        held
+    }};
+}
+
+/// Panic if an operation broke a well-formedness invariant its inputs held
+macro_rules! debug_invariants {
+    ($obj:expr, $operation:literal, $held:expr) => {
+        #[cfg(all(feature = "invariants", debug_assertions))]
+        if $held {
+            if let Err(violation) = $crate::invariants::check_invariants(&$obj) {
+                panic!("{} broke an invariant: {}\n{}", $operation, violation, $obj.bracketed());
+            }
+        }
+        #[cfg(not(all(feature = "invariants", debug_assertions)))]
+        let _ = $held;
+    };
+}
+
+pub mod acceptability;
+pub mod agreement;
+pub mod animation;
+pub mod assistant;
+pub mod bare;
+pub mod binary_size;
+pub mod builder;
+pub mod cache;
+pub mod certificate;
+pub mod chain_eval;
+pub mod compiled;
+#[cfg(feature = "std")]
+pub mod complexity;
+pub mod constraints;
+pub mod coverage;
+pub mod crossing;
+pub mod debugger;
+pub mod derivation;
+pub mod determinism;
+pub mod discourse;
+pub mod ecp;
+pub mod explain;
+pub mod feature_set;
+pub mod fixed;
+pub mod forced_choice;
+pub mod fragment;
+pub mod fuzz;
+pub mod grammar;
+pub mod grammars;
+pub mod health;
+pub mod induction;
+pub mod invariants;
+pub mod item_index;
+pub mod lexicon;
+pub mod lf;
+pub mod minimal_pairs;
+pub mod nanosyntax;
+pub mod nlg;
+pub mod nonce;
+pub mod normalize;
+pub mod overgeneration;
+pub mod paraphrase;
+pub mod parser;
+pub mod patterns;
+pub mod pf;
+pub mod phi;
+pub mod pool;
+pub mod profile;
+pub mod proof;
+pub mod prosody;
+pub mod pruning;
+pub mod ranking;
+#[cfg(feature = "std")]
+pub mod reload;
+pub mod robust;
+pub mod sampling;
+pub mod search;
+pub mod segment;
+pub mod simplify;
+pub mod spellout;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+#[cfg(feature = "parallel")]
+pub mod stress;
+pub mod supertag;
+pub mod synthesis;
+pub mod token_ids;
+pub mod trace;
+pub mod transfer;
+pub mod treebank;
+pub mod triage;
+pub mod typed;
+pub mod valuation;
+pub mod visualize;
+pub mod vocabulary;
+
+pub use acceptability::{acceptability, Acceptability, Judgment};
+pub use agreement::{AgrBundle, Gender, Number, Person};
+pub use assistant::Assistant;
+pub use bare::{parse_bare, projected_label, BareObject};
+pub use binary_size::{SizeError, SizeReport};
+pub use builder::TreeBuilder;
+pub use cache::{CacheStats, DerivationCache};
+pub use certificate::{Certificate, CertificateError};
+pub use chain_eval::{evaluate_chains, ChainScore, SurfaceChain};
+#[cfg(feature = "std")]
+pub use complexity::{estimate_complexity, ComplexityEstimate};
+pub use constraints::{Subjacency, Superiority, ThatTraceFilter};
+pub use coverage::{coverage, coverage_with_options, CoverageReport};
+pub use debugger::{Breakpoint, Debugger};
+pub use derivation::DerivationTree;
+pub use determinism::{check_order_independence, Divergence, OrderDependence};
+pub use discourse::{Chain, Discourse, Mention};
+pub use ecp::Ecp;
+pub use explain::{explain, Explanation, Reason};
+pub use feature_set::{FeatureSet, Indices};
+pub use fixed::{parse_forest_fixed, Economy, Fixed, FixedParse, FixedRanker, FixedRuleWeights};
+pub use forced_choice::{forced_choice, Choice, ForcedChoice, Preference};
+pub use fragment::{Composition, Conflict, LexiconFragment};
+pub use grammar::{GrammarOptions, Operations, RelativeClauseAnalysis};
+pub use health::{self_check, SelfCheckReport};
+pub use induction::{induce_categories, Induction, InductionOptions, WordCluster};
+pub use invariants::{check_invariants, Invariant, Violation};
+pub use item_index::ItemIndex;
+pub use lexicon::{Lexicon, LexiconDiff, LexiconError};
+pub use lf::{LfNode, Term};
+pub use minimal_pairs::{minimal_pairs, MinimalPair, Perturbation, PerturbationSpec};
+pub use nanosyntax::Lexicalization;
+pub use nlg::{generate, Generated, Skeleton, Slot};
+pub use nonce::{infer_nonce, NonceCandidate, NonceError};
+pub use normalize::{Case, Normalizer};
+pub use overgeneration::{enumerate_language, generate_up_to, overgeneration, Judgments, Language, OvergenerationReport};
+pub use paraphrase::{paraphrase, paraphrases, Alternation, Paraphrase};
+pub use parser::{constituent_marginals, parse_chart, parse_token_ids, Chart, Marginals};
+pub use patterns::{AnBmCnDm, AnBn, AnBnCn, ComplexityClass, Dyck, PatternRegistry, Recognizer, RecursivePattern};
+pub use pf::PfNode;
+pub use phi::{Phi, PhiNode};
+pub use pool::WorkspacePool;
+pub use profile::{allocation_count, count_allocation, OperationCounts, ParseOutcome};
+pub use proof::{prove, Proof, ProofError};
+pub use prosody::{phrasing, Break, Prosodic};
+pub use pruning::{parse_forest_pruned, InsideScore, PrunedForest, Pruning, RuleWeights};
+pub use ranking::{derivation_probability, FewestOperations, LexicalWeights, ParseRanker, Unranked};
+#[cfg(feature = "std")]
+pub use ranking::log_derivation_probability;
+#[cfg(feature = "std")]
+pub use reload::{LexiconWatcher, ReloadError, ReloadEvent};
+pub use robust::{parse_skipping_unknown, FragmentParse, SkipParse, Skipped};
+pub use sampling::generate_random;
+pub use search::{
+    parse_counting_expansions, parse_with_config, Backtracking, Beam, BestFirst, CostFn, Goal, Greedy, IterativeDeepening,
+    ParserConfig, SearchStrategy, UncheckedFeatures, UncoveredTokens, Uninformed,
+};
+pub use segment::{parse_text, segment};
+pub use simplify::{prune_adjuncts, simplifications, simplify};
+pub use spellout::{Agreement, Allomorph, ClusterHost, Contraction, PfRule, SpellOut};
+pub use supertag::{supertag, LexiconOrder, SelectionRules, Supertagger, TagWeights};
+pub use synthesis::{synthesize_benchmark, BenchmarkCase, Construction};
+pub use token_ids::Vocabulary;
+pub use trace::{trace_derivation, DerivationTrace, TimelineMetric, TraceStep};
+pub use transfer::{transfer, Transfer};
+pub use treebank::{parse_indexed_treebank, parse_treebank, subcategorization, PtbTree, SubcatFrame, TreebankError};
+pub use triage::{triage, FailureCluster, FailureSignature, TriageReport};
+pub use typed::{CategoryLike, FeatureLike, Grammar, PackedFeature};
+pub use valuation::{Resolution, Valuation};
+pub use visualize::{to_dot, to_latex};
+pub use vocabulary::{Terminal, VocabularyItem};
+
 // ============================================================================
 // Core Data Types
 // ============================================================================
 
 /// Syntactic category labels
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Category {
     /// Noun
     N,
@@ -40,6 +271,8 @@ pub enum Category {
     D,
     /// Complementizer
     C,
+    /// Preposition
+    P,
     /// Sentence
     S,
     /// Noun Phrase
@@ -50,19 +283,59 @@ pub enum Category {
     DP,
     /// Complementizer Phrase
     CP,
+    /// Prepositional Phrase
+    PP,
+}
+
+impl Category {
+    /// Head category a label projects from (phrasal labels select like their heads)
+    pub fn head(&self) -> Category {
+        match self {
+            Category::NP => Category::N,
+            Category::VP => Category::V,
+            Category::DP => Category::D,
+            Category::CP => Category::C,
+            Category::PP => Category::P,
+            other => other.clone(),
+        }
+    }
+
+    /// Phrasal category a head projects (heads without a phrasal label project themselves)
+    pub fn phrase(&self) -> Category {
+        match self {
+            Category::N => Category::NP,
+            Category::V => Category::VP,
+            Category::D => Category::DP,
+            Category::C => Category::CP,
+            Category::P => Category::PP,
+            other => other.clone(),
+        }
+    }
+    
+    /// Check if two categories match for feature checking
+    pub fn matches(&self, other: &Category) -> bool {
+        self.head() == other.head()
+    }
 }
 
 /// Feature types for Minimalist Grammar
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Feature {
     /// Basic category feature
     Cat(Category),
-    /// Selector feature (requires merge with category)
+    /// Selector feature (requires merge with category, complement to the right)
     Sel(Category),
+    /// Specifier selector (requires merge with category, specifier to the left)
+    Spec(Category),
+    /// Adjunct feature (adjoins to a constituent of the category)
+    Adjoin(Category),
     /// Positive feature (triggers movement)
     Pos(u8),
     /// Negative feature (target for movement)
     Neg(u8),
+    /// Agreement features: a probe on the selector it follows, otherwise
+    /// the item's own person, number and gender (see `agreement`)
+    Agr(AgrBundle),
 }
 
 impl Feature {
@@ -76,6 +349,11 @@ impl Feature {
         matches!(self, Feature::Neg(_))
     }
     
+    /// Check if feature selects an argument (complement or specifier)
+    pub fn is_selector(&self) -> bool {
+        matches!(self, Feature::Sel(_) | Feature::Spec(_))
+    }
+    
     /// Get movement feature index if applicable
     pub fn movement_index(&self) -> Option<u8> {
         match self {
@@ -92,6 +370,9 @@ pub struct LexItem {
     pub phon: String,
     /// Feature bundle
     pub feats: Vec<Feature>,
+    /// Probability of choosing the item, which `parse_best` ranks analyses
+    /// by; an unweighted item counts as certain
+    pub weight: Option<f64>,
 }
 
 impl LexItem {
@@ -100,12 +381,27 @@ impl LexItem {
         Self {
             phon: phon.to_string(),
             feats: feats.to_vec(),
+            weight: None,
         }
     }
+    
+    /// The item with a probability attached
+    pub fn weighted(mut self, weight: f64) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+    
+    /// Probability of the item, 1 if it is unweighted
+    pub fn probability(&self) -> f64 {
+        self.weight.unwrap_or(1.0)
+    }
 }
 
 /// Syntactic object in derivation
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Objects compare by structure: the entry recorded on a leaf indexes one
+/// particular lexicon, so it is left out of equality.
+#[derive(Debug, Clone)]
 pub struct SyntacticObject {
     /// Category label
     pub label: Category,
@@ -115,6 +411,16 @@ pub struct SyntacticObject {
     pub children: Vec<SyntacticObject>,
     /// Phonological content (for leaves)
     pub phon: Option<String>,
+    /// Lexicon index of the entry a leaf was built from, if the derivation
+    /// recorded it (see `from_entry`)
+    pub entry: Option<usize>,
+}
+
+impl PartialEq for SyntacticObject {
+    fn eq(&self, other: &Self) -> bool {
+        self.label == other.label && self.features == other.features
+            && self.phon == other.phon && self.children == other.children
+    }
 }
 
 impl SyntacticObject {
@@ -132,9 +438,15 @@ impl SyntacticObject {
             features: item.feats.clone(),
             children: Vec::new(),
             phon: Some(item.phon.clone()),
+            entry: None,
         }
     }
     
+    /// Create leaf node from the lexicon entry at `index`, recording the index
+    pub fn from_entry(lexicon: &[LexItem], index: usize) -> Self {
+        Self { entry: Some(index), ..Self::from_lex(&lexicon[index]) }
+    }
+    
     /// Create internal node with children
     pub fn internal(label: Category, features: Vec<Feature>, children: Vec<SyntacticObject>) -> Self {
         Self {
@@ -142,25 +454,111 @@ impl SyntacticObject {
             features,
             children,
             phon: None,
+            entry: None,
         }
     }
     
-    /// Check if object has no unchecked features
+    /// Check if object has no unchecked features (its own category aside)
     pub fn is_complete(&self) -> bool {
-        self.features.is_empty()
+        self.features.iter().all(|f| matches!(f, Feature::Cat(_) | Feature::Agr(_)))
     }
     
-    /// Get linearized string representation
-    pub fn linearize(&self) -> String {
-        if let Some(ref phon) = self.phon {
-            phon.clone()
-        } else {
-            self.children.iter()
-                .map(|child| child.linearize())
-                .collect::<Vec<_>>()
-                .join(" ")
+    /// Get the category feature this object projects, if any
+    pub fn category(&self) -> Option<&Category> {
+        self.features.iter().find_map(|f| match f {
+            Feature::Cat(cat) => Some(cat),
+            _ => None,
+        })
+    }
+    
+    /// Check if object still carries licensee features (it has yet to move)
+    pub fn has_licensees(&self) -> bool {
+        self.features.iter().any(Feature::is_negative)
+    }
+    
+    /// Get the next selector feature awaiting an argument
+    pub fn next_selector(&self) -> Option<&Feature> {
+        self.features.iter().find(|f| f.is_selector())
+    }
+    
+    /// Create trace left behind by a moved constituent
+    pub fn trace(label: Category) -> Self {
+        Self {
+            label,
+            features: Vec::new(),
+            children: Vec::new(),
+            phon: None,
+            entry: None,
         }
     }
+    
+    /// Check if object is a trace (silent leaf without lexical content)
+    pub fn is_trace(&self) -> bool {
+        self.phon.is_none() && self.children.is_empty()
+    }
+    
+    /// Index of the projecting child: the selector or movement trigger, else the adjunction host
+    pub fn head_index(&self) -> Option<usize> {
+        let has = |pred: fn(&Feature) -> bool| -> Option<usize> {
+            let matching: Vec<usize> = (0..self.children.len())
+                .filter(|&i| self.children[i].features.iter().any(pred))
+                .collect();
+            if matching.len() == 1 { Some(matching[0]) } else { None }
+        };
+        
+        if self.children.is_empty() {
+            return None;
+        }
+        
+        has(Feature::is_selector)
+            .or_else(|| has(Feature::is_positive))
+            .or_else(|| {
+                (0..self.children.len()).find(|&i| {
+                    !self.children[i].features.iter().any(|f| matches!(f, Feature::Adjoin(_)))
+                })
+            })
+            .or(Some(0))
+    }
+    
+    /// Selector the projecting child still had to check, with the indices
+    /// of the children it selected: the node's arguments
+    ///
+    /// `None` if the head selected nothing, so adjuncts and moved phrases
+    /// are not arguments.
+    pub(crate) fn arguments(&self) -> Option<(&Feature, Vec<usize>)> {
+        let head = self.head_index()?;
+        let selector = self.children[head].next_selector()?;
+        Some((selector, (0..self.children.len()).filter(|&i| i != head).collect()))
+    }
+    
+    /// Get linearized string representation (silent heads and traces are skipped)
+    pub fn linearize(&self) -> String {
+        pf::spell_out(self).linearize()
+    }
+    
+    /// Labeled bracketing, e.g. `[VP [DP [D the] [N student]] [V left]]`
+    ///
+    /// Silent heads are written `ε` and traces `t`.
+    pub fn bracketed(&self) -> String {
+        pf::spell_out(self).bracketed()
+    }
+    
+    /// Penn Treebank bracketing, e.g. `(VP (DP (D the) (N student)) (V left))`
+    ///
+    /// Silent heads and traces are written as the treebank writes empty
+    /// elements (see `visualize`), so `parse_indexed_treebank` reads the
+    /// output back.
+    pub fn penn_bracketed(&self) -> String {
+        visualize::to_penn(self, false)
+    }
+    
+    /// `penn_bracketed` with each node's features after its label: the
+    /// features a phrase has left unchecked, and a word's full lexical
+    /// bundle, e.g.
+    /// `(VP[V] (DP[D] (D[D =N] the) (N[N] student)) (V[V D=] left))`
+    pub fn penn_with_features(&self) -> String {
+        visualize::to_penn(self, true)
+    }
 }
 
 // ============================================================================
@@ -168,43 +566,123 @@ impl SyntacticObject {
 // ============================================================================
 
 /// Workspace for managing derivation state
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Workspace {
-    /// Active syntactic objects
-    pub items: Vec<SyntacticObject>,
+    /// Active syntactic objects, read through `items` and edited through
+    /// `items_mut` or the workspace's operations
+    items: Vec<SyntacticObject>,
     /// Maximum memory usage allowed
     pub memory_limit: usize,
     /// Step counter for derivation
     pub step_count: usize,
+    /// Silent (phonologically empty) heads available to the derivation
+    pub empty_heads: Vec<SyntacticObject>,
+    /// Operations tallied since the counts were last taken
+    counts: Cell<OperationCounts>,
+    /// Highest memory usage recorded at a derivation step
+    peak: usize,
+    /// Items filed by the categories they need and provide
+    index: ItemIndex,
+    /// Whether `items` was handed out for editing since `index` was built
+    stale: bool,
+    /// Steps recorded by `step`, if recording
+    trace: Option<DerivationTrace>,
+}
+
+impl Clone for Workspace {
+    /// Clone the derivation state; the copy starts with fresh counts
+    fn clone(&self) -> Self {
+        self.tally(|counts| counts.clones += 1);
+        Self {
+            items: self.items.clone(),
+            memory_limit: self.memory_limit,
+            step_count: self.step_count,
+            empty_heads: self.empty_heads.clone(),
+            counts: Cell::default(),
+            peak: self.peak,
+            index: self.index.clone(),
+            stale: self.stale,
+            trace: self.trace.clone(),
+        }
+    }
+    
+    /// Copy the derivation state into existing buffers
+    fn clone_from(&mut self, source: &Self) {
+        source.tally(|counts| counts.clones += 1);
+        self.items.clone_from(&source.items);
+        self.memory_limit = source.memory_limit;
+        self.step_count = source.step_count;
+        self.empty_heads.clone_from(&source.empty_heads);
+        self.counts.take();
+        self.peak = source.peak;
+        self.index.clone_from(&source.index);
+        self.stale = source.stale;
+        self.trace.clone_from(&source.trace);
+    }
 }
 
 /// Errors that can occur during derivation
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Errors are `Copy` and never allocate, so they can be built and reported
+/// on no_std targets without a heap.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DerivationError {
     /// No valid operations available
     NoValidOperations,
     /// Memory limit exceeded
-    MemoryLimitExceeded,
+    ///
+    /// A workspace over the limit cannot be derived further. Searches that
+    /// hold several hypotheses first drop their worst ones to stay within
+    /// the limit, and fail with this error only if no derivation converged
+    /// from what they kept.
+    MemoryLimitExceeded {
+        /// Hypotheses dropped to stay within the limit before failing
+        pruned: usize,
+    },
     /// Feature mismatch in operation
     FeatureMismatch,
     /// Empty workspace
     EmptyWorkspace,
     /// Invalid operation sequence
     InvalidOperation,
+    /// Convergent derivation ruled out by a grammar constraint
+    ConstraintViolation(&'static str),
 }
 
 impl fmt::Display for DerivationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DerivationError::NoValidOperations => write!(f, "No valid operations available"),
-            DerivationError::MemoryLimitExceeded => write!(f, "Memory limit exceeded"),
+            DerivationError::MemoryLimitExceeded { pruned: 0 } => write!(f, "Memory limit exceeded"),
+            DerivationError::MemoryLimitExceeded { pruned } => {
+                write!(f, "Memory limit exceeded ({} hypotheses pruned)", pruned)
+            }
             DerivationError::FeatureMismatch => write!(f, "Feature mismatch"),
             DerivationError::EmptyWorkspace => write!(f, "Empty workspace"),
             DerivationError::InvalidOperation => write!(f, "Invalid operation"),
+            DerivationError::ConstraintViolation(name) => write!(f, "Constraint violated: {}", name),
         }
     }
 }
 
+// Compile-time guarantee that error construction never allocates: `Copy`
+// rules out heap-owning fields, and every variant is constructible in const
+// context
+const _: () = {
+    const fn assert_copy<T: Copy>() {}
+    assert_copy::<DerivationError>();
+    assert_copy::<LexiconError>();
+};
+const _: [DerivationError; 2] = [
+    DerivationError::NoValidOperations,
+    DerivationError::ConstraintViolation("that-trace"),
+];
+const _: [LexiconError; 3] = [
+    LexiconError::Syntax { position: 0, message: "a value" },
+    LexiconError::Schema("expected an \"items\" array"),
+    LexiconError::Feature(lexicon::Notation::new("=Q")),
+];
+
 impl Workspace {
     /// Create new workspace with memory limit
     pub fn new(memory_limit: usize) -> Self {
@@ -212,15 +690,117 @@ impl Workspace {
             items: Vec::new(),
             memory_limit,
             step_count: 0,
+            empty_heads: Vec::new(),
+            counts: Cell::default(),
+            peak: 0,
+            index: ItemIndex::default(),
+            stale: false,
+            trace: None,
+        }
+    }
+    
+    /// Operations tallied so far
+    pub fn counts(&self) -> OperationCounts {
+        self.counts.get()
+    }
+    
+    /// Return the tallied operations and reset the counts
+    pub fn take_counts(&self) -> OperationCounts {
+        self.counts.take()
+    }
+    
+    /// Update the operation counts (through `&self`, so lookups can tally too)
+    fn tally(&self, update: impl FnOnce(&mut OperationCounts)) {
+        let mut counts = self.counts.get();
+        update(&mut counts);
+        self.counts.set(counts);
+    }
+    
+    /// Empty the workspace for a new derivation, keeping its buffers and
+    /// memory limit
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.empty_heads.clear();
+        self.step_count = 0;
+        self.counts.take();
+        self.peak = 0;
+        self.index.clear();
+        self.stale = false;
+        if let Some(trace) = &mut self.trace {
+            trace.steps.clear();
+        }
+    }
+    
+    /// Start recording the steps `step` and `derive` take, discarding any
+    /// earlier recording
+    pub fn record_trace(&mut self) {
+        self.trace = Some(DerivationTrace::default());
+    }
+    
+    /// Steps recorded since `record_trace`, or `None` if not recording
+    pub fn trace(&self) -> Option<&DerivationTrace> {
+        self.trace.as_ref()
+    }
+    
+    /// Stop recording and return the steps recorded
+    pub fn take_trace(&mut self) -> Option<DerivationTrace> {
+        self.trace.take()
+    }
+    
+    /// Active syntactic objects, in surface order
+    pub fn items(&self) -> &[SyntacticObject] {
+        &self.items
+    }
+    
+    /// Edit the active objects directly
+    ///
+    /// The item index cannot follow edits made through the returned
+    /// vector, so it is rebuilt before it is next used.
+    pub fn items_mut(&mut self) -> &mut Vec<SyntacticObject> {
+        self.stale = true;
+        &mut self.items
+    }
+    
+    /// Rebuild the item index now rather than when it is next used
+    pub fn reindex(&mut self) {
+        self.index.rebuild(&self.items);
+        self.stale = false;
+    }
+    
+    /// Item index, rebuilt first if `items_mut` handed out the items
+    fn index_mut(&mut self) -> &mut ItemIndex {
+        if self.stale {
+            self.reindex();
         }
+        &mut self.index
     }
     
     /// Add lexical item to workspace
     pub fn add_lex(&mut self, item: &LexItem) {
-        let obj = SyntacticObject::from_lex(item);
+        self.add_object(SyntacticObject::from_lex(item));
+    }
+    
+    /// Add the lexicon entry at `index`, recording the index on its leaf
+    pub fn add_entry(&mut self, lexicon: &[LexItem], index: usize) {
+        self.add_object(SyntacticObject::from_entry(lexicon, index));
+    }
+    
+    fn add_object(&mut self, obj: SyntacticObject) {
+        let at = self.items.len();
+        self.index_mut().insert(at, &obj);
         self.items.push(obj);
     }
     
+    /// Make a silent head available for merging anywhere in the derivation
+    pub fn add_empty_head(&mut self, item: &LexItem) {
+        self.empty_heads.push(SyntacticObject::from_lex(item));
+    }
+    
+    /// `add_empty_head` for the lexicon entry at `index`, recording the index
+    pub fn add_empty_entry(&mut self, lexicon: &[LexItem], index: usize) {
+        self.empty_heads.push(SyntacticObject::from_entry(lexicon, index));
+    }
+    
     /// Check if derivation is successful (single complete object)
     pub fn is_successful(&self) -> bool {
         self.items.len() == 1 && self.items[0].is_complete()
@@ -234,6 +814,12 @@ impl Workspace {
             .sum()
     }
     
+    /// Highest memory usage reached so far in the derivation, including
+    /// the current state
+    pub fn peak_usage(&self) -> usize {
+        self.peak.max(self.memory_usage())
+    }
+    
     fn object_size(&self, obj: &SyntacticObject) -> usize {
         1 + obj.children.iter().map(|child| self.object_size(child)).sum::<usize>()
     }
@@ -244,64 +830,158 @@ impl Workspace {
 // ============================================================================
 
 /// Attempt to merge two syntactic objects
+///
+/// The first selector of `a` is checked against the category of `b`, which must
+/// have no selectors of its own left. A complement (`Sel`) is linearized to the
+/// right of the selecting object, a specifier (`Spec`) to its left.
 pub fn merge(a: SyntacticObject, b: SyntacticObject) -> Result<SyntacticObject, DerivationError> {
-    // Check if first object has selector feature matching second object's category
-    if let Some(sel_feature) = a.features.iter().find(|f| matches!(f, Feature::Sel(_))) {
-        if let Feature::Sel(required_cat) = sel_feature {
-            if let Some(cat_feature) = b.features.iter().find(|f| matches!(f, Feature::Cat(_))) {
-                if let Feature::Cat(actual_cat) = cat_feature {
-                    if required_cat == actual_cat {
-                        // Successful merge: create new object
-                        let mut new_features = a.features.clone();
-                        new_features.retain(|f| !matches!(f, Feature::Sel(_)));
-                        
-                        let mut b_features = b.features.clone();
-                        b_features.retain(|f| !matches!(f, Feature::Cat(_)));
-                        new_features.extend(b_features);
-                        
-                        return Ok(SyntacticObject::internal(
-                            required_cat.clone(),
-                            new_features,
-                            vec![a, b],
-                        ));
-                    }
-                }
-            }
-        }
+    let (required_cat, is_specifier) = match a.next_selector() {
+        Some(Feature::Sel(cat)) => (cat.clone(), false),
+        Some(Feature::Spec(cat)) => (cat.clone(), true),
+        _ => return Err(DerivationError::FeatureMismatch),
+    };
+    
+    if !can_merge(&a, &b) {
+        log_trace!("merge: {:?} cannot check {:?} against {:?}", a.label, a.next_selector(), b.category());
+        return Err(DerivationError::FeatureMismatch);
     }
+    log_trace!("merge: {:?} checks {:?} against {:?}", a.label, a.next_selector(), b.label);
+    let well_formed = invariants_hold!(a, b);
     
-    Err(DerivationError::FeatureMismatch)
+    // Check the selector (with its probe) on `a` and the category (plus any
+    // adjunct features) on `b`
+    let mut new_features = a.features.clone();
+    discharge_selector(&mut new_features);
+    pass_features(&mut new_features, &b.features);
+    
+    // The selecting head projects; a bare selector takes the selectee's category
+    let label = a.category().map(Category::phrase).unwrap_or(required_cat);
+    
+    let children = if is_specifier { vec![b, a] } else { vec![a, b] };
+    let merged = SyntacticObject::internal(label, new_features, children);
+    debug_invariants!(merged, "merge", well_formed);
+    Ok(merged)
 }
 
-/// Find pairs of objects that can merge
-pub fn find_mergeable_pairs(workspace: &Workspace) -> Vec<(usize, usize)> {
-    let mut pairs = Vec::new();
-    
-    for i in 0..workspace.items.len() {
-        for j in 0..workspace.items.len() {
-            if i != j {
-                if can_merge(&workspace.items[i], &workspace.items[j]) {
-                    pairs.push((i, j));
-                }
-            }
+/// Remove the first selector and the agreement probe right after it
+pub(crate) fn discharge_selector(features: &mut Vec<Feature>) {
+    if let Some(pos) = features.iter().position(Feature::is_selector) {
+        let probe = agreement::probe(features).is_some();
+        features.drain(pos..pos + 1 + probe as usize);
+    }
+}
+
+/// Pass the unchecked features of an argument or adjunct up to the phrase
+/// with `features`, all but its category and adjunction features
+///
+/// Agreement features go right after the phrase's category and its own
+/// agreement, ahead of any remaining selector, so they keep describing and
+/// are never read as that selector's probe.
+pub(crate) fn pass_features(features: &mut Vec<Feature>, from: &[Feature]) {
+    let mut at = features.iter()
+        .position(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_)))
+        .unwrap_or(features.len());
+    for feature in from.iter().filter(|f| !matches!(f, Feature::Cat(_) | Feature::Adjoin(_))) {
+        if matches!(feature, Feature::Agr(_)) {
+            features.insert(at, feature.clone());
+            at += 1;
+        } else {
+            features.push(feature.clone());
         }
     }
+}
+
+/// Find pairs of objects that can merge, as (selector, selectee) indices
+///
+/// Selectees must be adjacent on the selector's side, except for movers
+/// (objects with licensee features), which are pronounced at their landing
+/// site rather than where they are merged.
+///
+/// Only the pairs the workspace's item index files under the same category
+/// are checked. An index left stale by `Workspace::items_mut` is rebuilt
+/// for the call.
+pub fn find_mergeable_pairs(workspace: &Workspace) -> Vec<(usize, usize)> {
+    let rebuilt;
+    let index = if workspace.stale {
+        rebuilt = ItemIndex::new(&workspace.items);
+        &rebuilt
+    } else {
+        &workspace.index
+    };
     
+    let mut pairs: Vec<(usize, usize)> = index.candidates()
+        .filter(|&(i, j)| {
+            workspace.tally(|counts| counts.feature_comparisons += 1);
+            index.set(i).can_merge(index.set(j))
+        })
+        .collect();
+    pairs.sort_unstable();
     pairs
 }
 
 /// Check if two objects can merge
 pub fn can_merge(a: &SyntacticObject, b: &SyntacticObject) -> bool {
-    // Check if a has selector feature matching b's category
-    a.features.iter().any(|feat| {
-        if let Feature::Sel(required_cat) = feat {
-            b.features.iter().any(|b_feat| {
-                matches!(b_feat, Feature::Cat(actual_cat) if actual_cat == required_cat)
-            })
-        } else {
-            false
-        }
-    })
+    // a's next selector must match b's category, and b must be saturated,
+    // with no movement trigger left pending for the mover to strand
+    let required_cat = match a.next_selector() {
+        Some(Feature::Sel(cat)) | Some(Feature::Spec(cat)) => cat,
+        _ => return false,
+    };
+    
+    b.next_selector().is_none()
+        && !b.features.iter().any(Feature::is_positive)
+        && b.category().is_some_and(|cat| cat.matches(required_cat))
+        && agreement::satisfies_probe(&a.features, &b.features)
+        && satisfies_smc(a, b)
+}
+
+/// Shortest Move Constraint: no two pending movers may share a licensee
+fn satisfies_smc(a: &SyntacticObject, b: &SyntacticObject) -> bool {
+    !b.features.iter()
+        .filter_map(|f| if f.is_negative() { f.movement_index() } else { None })
+        .any(|idx| a.features.contains(&Feature::Neg(idx)))
+}
+
+// ============================================================================
+// Core Operations: Adjunction
+// ============================================================================
+
+/// Adjoin a saturated adjunct to the right of its host
+///
+/// The host keeps its label and features; the adjunct's category and adjunct
+/// features are discharged.
+pub fn adjoin(host: SyntacticObject, adjunct: SyntacticObject) -> Result<SyntacticObject, DerivationError> {
+    if !can_adjoin(&host, &adjunct) {
+        return Err(DerivationError::FeatureMismatch);
+    }
+    let well_formed = invariants_hold!(host, adjunct);
+    
+    let mut new_features = host.features.clone();
+    pass_features(&mut new_features, &adjunct.features);
+    
+    let adjoined = SyntacticObject::internal(host.label.clone(), new_features, vec![host, adjunct]);
+    debug_invariants!(adjoined, "adjoin", well_formed);
+    Ok(adjoined)
+}
+
+/// Check if `adjunct` can adjoin to `host`
+pub fn can_adjoin(host: &SyntacticObject, adjunct: &SyntacticObject) -> bool {
+    if host.next_selector().is_some() || adjunct.next_selector().is_some() {
+        return false;
+    }
+    
+    host.category().is_some_and(|cat| {
+        adjunct.features.iter().any(|f| matches!(f, Feature::Adjoin(target) if target.matches(cat)))
+    }) && satisfies_smc(host, adjunct)
+}
+
+/// Find pairs of adjacent objects that can adjoin, as (host, adjunct) indices
+pub fn find_adjoinable_pairs(workspace: &Workspace) -> Vec<(usize, usize)> {
+    workspace.tally(|counts| counts.feature_comparisons += workspace.items.len().saturating_sub(1));
+    (1..workspace.items.len())
+        .filter(|&j| can_adjoin(&workspace.items[j - 1], &workspace.items[j]))
+        .map(|j| (j - 1, j))
+        .collect()
 }
 
 // ============================================================================
@@ -309,105 +989,494 @@ pub fn can_merge(a: &SyntacticObject, b: &SyntacticObject) -> bool {
 // ============================================================================
 
 /// Apply movement operation to syntactic object
-pub fn move_operation(obj: SyntacticObject) -> Result<SyntacticObject, DerivationError> {
+///
+/// The first positive feature attracts the constituent whose matching negative
+/// feature is pending; it is re-merged at the left edge, leaving a trace behind.
+pub fn move_operation(mut obj: SyntacticObject) -> Result<SyntacticObject, DerivationError> {
+    move_in_place(&mut obj)?;
+    Ok(obj)
+}
+
+/// Apply movement to an object where it stands, leaving it untouched on failure
+///
+/// Only the path down to the mover is visited: the mover and the remnant
+/// are relinked under a new root rather than copied, so movement inside a
+/// workspace costs O(depth) instead of O(size).
+pub fn move_in_place(obj: &mut SyntacticObject) -> Result<(), DerivationError> {
     // Find positive feature that triggers movement
-    if let Some(pos_feature) = obj.features.iter().find(|f| f.is_positive()) {
-        if let Some(movement_idx) = pos_feature.movement_index() {
-            // Search for matching negative feature in embedded structure
-            if let Some(target) = find_movement_target(&obj, movement_idx) {
-                return extract_and_move(obj, target, movement_idx);
-            }
+    if let Some(movement_idx) = obj.features.iter().find(|f| f.is_positive()).and_then(Feature::movement_index) {
+        // Search for matching negative feature in embedded structure
+        if let Some(path) = find_mover_path(obj, movement_idx) {
+            log_trace!("move: +{} in {:?} attracts the mover at {:?}", movement_idx, obj.label, path);
+            let remnant = core::mem::replace(obj, SyntacticObject::trace(obj.label.clone()));
+            *obj = extract_and_move(remnant, &path, movement_idx);
+            return Ok(());
         }
+        log_trace!("move: +{} in {:?} finds no matching licensee", movement_idx, obj.label);
     }
     
     Err(DerivationError::NoValidOperations)
 }
 
-/// Find constituent with matching negative feature
-fn find_movement_target(obj: &SyntacticObject, movement_idx: u8) -> Option<SyntacticObject> {
-    // Check current object
-    if obj.features.iter().any(|f| matches!(f, Feature::Neg(idx) if *idx == movement_idx)) {
-        return Some(obj.clone());
+/// Check if object has a movement trigger with a matching target
+pub fn can_move(obj: &SyntacticObject) -> bool {
+    obj.features.iter()
+        .find(|f| f.is_positive())
+        .and_then(Feature::movement_index)
+        .is_some_and(|idx| find_mover_path(obj, idx).is_some())
+}
+
+/// Find the child-index path to the maximal constituent carrying a pending `-idx`
+///
+/// Licensees percolate up through the projections containing a mover, so the
+/// search follows the carrier of the feature down to the item that introduced
+/// it. The mover is the maximal projection of that item: the constituent
+/// entered by the last step into a non-head child.
+pub(crate) fn find_mover_path(obj: &SyntacticObject, movement_idx: u8) -> Option<Vec<usize>> {
+    let licensee = Feature::Neg(movement_idx);
+    if !obj.features.contains(&licensee) {
+        return None;
     }
     
-    // Recursively search children
-    for child in &obj.children {
-        if let Some(target) = find_movement_target(child, movement_idx) {
-            return Some(target);
+    let mut path = Vec::new();
+    let mut maximal = 0;
+    let mut node = obj;
+    while let Some(carrier) = node.children.iter().position(|child| child.features.contains(&licensee)) {
+        path.push(carrier);
+        if node.head_index() != Some(carrier) {
+            maximal = path.len();
         }
+        node = &node.children[carrier];
     }
     
-    None
+    // A licensee projected by the root's own head cannot move into itself
+    path.truncate(maximal);
+    if path.is_empty() { None } else { Some(path) }
 }
 
-/// Extract target and adjoin to edge position
-fn extract_and_move(
-    obj: SyntacticObject, 
-    target: SyntacticObject, 
-    movement_idx: u8
-) -> Result<SyntacticObject, DerivationError> {
-    // Remove positive feature from trigger
-    let mut new_features = obj.features.clone();
-    new_features.retain(|f| !matches!(f, Feature::Pos(idx) if *idx == movement_idx));
+/// Extract the mover at `path` and re-merge it at the edge
+fn extract_and_move(obj: SyntacticObject, path: &[usize], movement_idx: u8) -> SyntacticObject {
+    let well_formed = invariants_hold!(obj);
     
-    // Remove negative feature from target
-    let mut target_features = target.features.clone();
-    target_features.retain(|f| !matches!(f, Feature::Neg(idx) if *idx == movement_idx));
+    // Licensees travel with the mover, not the remnant it leaves behind
+    let mover = path.iter().fold(&obj, |node, &i| &node.children[i]);
+    let licensees: Vec<Feature> = mover.features.iter().filter(|f| f.is_negative()).cloned().collect();
     
-    let moved_target = SyntacticObject {
-        features: target_features,
-        ..target
-    };
+    let mut remnant = obj;
+    let mut moved = extract_mover(&mut remnant, path, &licensees);
+    
+    // Check the positive feature on the trigger and the negative one on the mover
+    let mut new_features = remnant.features.clone();
+    remove_first(&mut new_features, &Feature::Pos(movement_idx));
+    remove_first(&mut moved.features, &Feature::Neg(movement_idx));
+    new_features.extend(moved.features.iter().filter(|f| f.is_negative()).cloned());
     
-    // Create new structure with moved element adjoined
-    Ok(SyntacticObject::internal(
-        obj.label.clone(),
-        new_features,
-        vec![moved_target, obj],
-    ))
+    // Create new structure with moved element at the left edge
+    let result = SyntacticObject::internal(remnant.label.clone(), new_features, vec![moved, remnant]);
+    debug_invariants!(result, "move", well_formed);
+    result
+}
+
+/// Replace the constituent at `path` with a trace, stripping its licensees along the way
+fn extract_mover(node: &mut SyntacticObject, path: &[usize], licensees: &[Feature]) -> SyntacticObject {
+    for licensee in licensees {
+        remove_first(&mut node.features, licensee);
+    }
+    
+    let (&first, rest) = path.split_first().expect("mover path is never empty");
+    if rest.is_empty() {
+        let label = node.children[first].label.clone();
+        core::mem::replace(&mut node.children[first], SyntacticObject::trace(label))
+    } else {
+        extract_mover(&mut node.children[first], rest, licensees)
+    }
+}
+
+/// Remove the first occurrence of a feature
+pub(crate) fn remove_first(features: &mut Vec<Feature>, feature: &Feature) {
+    if let Some(pos) = features.iter().position(|f| f == feature) {
+        features.remove(pos);
+    }
 }
 
 // ============================================================================
 // Derivation Engine
 // ============================================================================
 
-/// Single derivation step
+/// Operation applicable to a workspace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Merge the selector at the first index with the selectee at the second
+    Merge(usize, usize),
+    /// Adjoin the adjunct at the second index to the host at the first
+    Adjoin(usize, usize),
+    /// Merge the empty head at the first index with the object at the second
+    MergeEmpty(usize, usize),
+    /// Apply movement within the object at the index
+    Move(usize),
+}
+
+impl Operation {
+    /// Workspace index where the operation's result is placed
+    pub fn result_index(&self) -> usize {
+        match *self {
+            Operation::Merge(i, j) | Operation::Adjoin(i, j) => if j < i { i - 1 } else { i },
+            Operation::MergeEmpty(_, i) | Operation::Move(i) => i,
+        }
+    }
+}
+
+/// List operations applicable to the workspace, merges first
+pub fn available_operations(workspace: &Workspace) -> Vec<Operation> {
+    let mut ops: Vec<Operation> = find_mergeable_pairs(workspace)
+        .into_iter()
+        .map(|(i, j)| Operation::Merge(i, j))
+        .collect();
+    
+    ops.extend(find_adjoinable_pairs(workspace).into_iter().map(|(i, j)| Operation::Adjoin(i, j)));
+    for (h, head) in workspace.empty_heads.iter().enumerate() {
+        workspace.tally(|counts| counts.feature_comparisons += workspace.items.len());
+        ops.extend(
+            (0..workspace.items.len())
+                .filter(|&i| can_merge(head, &workspace.items[i]))
+                .map(|i| Operation::MergeEmpty(h, i)),
+        );
+    }
+    workspace.tally(|counts| counts.feature_comparisons += workspace.items.len());
+    ops.extend(
+        (0..workspace.items.len())
+            .filter(|&i| can_move(&workspace.items[i]))
+            .map(Operation::Move),
+    );
+    
+    ops
+}
+
+/// Apply a single operation, keeping workspace items in surface order
+pub fn apply_operation(workspace: &mut Workspace, op: Operation) -> Result<(), DerivationError> {
+    apply(workspace, op)?;
+    workspace.peak = workspace.peak_usage();
+    Ok(())
+}
+
+fn apply(workspace: &mut Workspace, op: Operation) -> Result<(), DerivationError> {
+    if workspace.stale {
+        workspace.reindex();
+    }
+    let len = workspace.items.len();
+    match op {
+        Operation::Merge(i, j) | Operation::Adjoin(i, j) => {
+            if i >= len || j >= len || i == j {
+                return Err(DerivationError::InvalidOperation);
+            }
+            workspace.tally(|counts| {
+                counts.feature_comparisons += 1;
+                if let Operation::Merge(..) = op {
+                    counts.merges_attempted += 1;
+                }
+            });
+            let applicable = match op {
+                Operation::Merge(..) => can_merge(&workspace.items[i], &workspace.items[j]),
+                _ => can_adjoin(&workspace.items[i], &workspace.items[j]),
+            };
+            if !applicable {
+                return Err(DerivationError::FeatureMismatch);
+            }
+            
+            // The result takes the selector's (or host's) place in the workspace
+            let b = workspace.items.remove(j);
+            let at = op.result_index();
+            let a = workspace.items.remove(at);
+            workspace.index.remove(j);
+            workspace.index.remove(at);
+            
+            let combined = match op {
+                Operation::Merge(..) => merge(a, b)?,
+                _ => adjoin(a, b)?,
+            };
+            workspace.index.insert(at, &combined);
+            workspace.items.insert(at, combined);
+            workspace.tally(|counts| match op {
+                Operation::Merge(..) => counts.merges_succeeded += 1,
+                _ => counts.adjunctions += 1,
+            });
+            Ok(())
+        }
+        Operation::MergeEmpty(h, i) => {
+            if h >= workspace.empty_heads.len() || i >= len {
+                return Err(DerivationError::InvalidOperation);
+            }
+            let head = workspace.empty_heads[h].clone();
+            workspace.tally(|counts| {
+                counts.feature_comparisons += 1;
+                counts.merges_attempted += 1;
+            });
+            if !can_merge(&head, &workspace.items[i]) {
+                return Err(DerivationError::FeatureMismatch);
+            }
+            
+            let b = workspace.items.remove(i);
+            workspace.items.insert(i, merge(head, b)?);
+            workspace.index.replace(i, &workspace.items[i]);
+            workspace.tally(|counts| counts.merges_succeeded += 1);
+            Ok(())
+        }
+        Operation::Move(i) => {
+            if i >= len {
+                return Err(DerivationError::InvalidOperation);
+            }
+            move_in_place(&mut workspace.items[i])?;
+            workspace.index.replace(i, &workspace.items[i]);
+            workspace.tally(|counts| counts.moves += 1);
+            Ok(())
+        }
+    }
+}
+
+/// Count a derivation step and enforce the memory limit
+fn begin_step(workspace: &mut Workspace) -> Result<(), DerivationError> {
+    workspace.step_count += 1;
+    
+    let usage = workspace.memory_usage();
+    workspace.peak = workspace.peak.max(usage);
+    if usage > workspace.memory_limit {
+        return Err(DerivationError::MemoryLimitExceeded { pruned: 0 });
+    }
+    
+    Ok(())
+}
+
+/// Single derivation step, recorded in the workspace's trace if recording
 pub fn step(workspace: &mut Workspace) -> Result<(), DerivationError> {
     if workspace.items.is_empty() {
         return Err(DerivationError::EmptyWorkspace);
     }
     
-    workspace.step_count += 1;
+    // Merge operations take priority, then adjunction, empty heads and movement
+    let meter = workspace.trace.is_some().then(trace::Meter::start);
+    let chosen = begin_step(workspace)
+        .and_then(|()| available_operations(workspace).first().copied().ok_or(DerivationError::NoValidOperations));
+    apply_recorded(workspace, chosen, meter)
+}
+
+/// Apply a given operation as one derivation step, recorded in the
+/// workspace's trace like a step `step` chose
+pub fn step_with(workspace: &mut Workspace, op: Operation) -> Result<(), DerivationError> {
+    let meter = workspace.trace.is_some().then(trace::Meter::start);
+    let chosen = begin_step(workspace).map(|()| op);
+    apply_recorded(workspace, chosen, meter)
+}
+
+/// Apply the operation chosen for a step, recording it if `meter` is timing it
+fn apply_recorded(
+    workspace: &mut Workspace,
+    chosen: Result<Operation, DerivationError>,
+    meter: Option<trace::Meter>,
+) -> Result<(), DerivationError> {
+    let Some(mut meter) = meter else {
+        return chosen.and_then(|op| apply_operation(workspace, op));
+    };
+    // Snapshotting the inputs for the trace is not part of the step's cost
+    meter.pause();
+    let entry = TraceStep::before(workspace, chosen.ok());
+    meter.resume();
+    let outcome = chosen.and_then(|op| apply_operation(workspace, op));
+    let entry = entry.after(workspace, outcome, meter);
+    if let Some(trace) = &mut workspace.trace {
+        trace.steps.push(entry);
+    }
+    outcome
+}
+
+/// Convergent analysis of a sentence
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parse {
+    /// Derived tree
+    pub tree: SyntacticObject,
+    /// Operations that derived the tree, in order
+    pub derivation: Vec<Operation>,
+    /// Score assigned by the ranker consulted during enumeration
+    pub score: f64,
+}
+
+/// Depth-first enumeration of convergent derivations yielding `tokens`
+///
+/// Backtracks over every available operation, so a locally plausible merge
+/// that dead-ends (e.g. an attractor noun taken as the subject) is undone.
+/// Convergent trees must also pass `accept`, which applies grammar constraints.
+struct Search<'a> {
+    tokens: &'a [&'a str],
+    max_steps: usize,
+    accept: &'a dyn Fn(&SyntacticObject) -> Result<(), DerivationError>,
+    /// Stop once this many distinct trees are found
+    limit: usize,
+    visited: BTreeSet<String>,
+    derivation: Vec<Operation>,
+    parses: Vec<Parse>,
+    /// Most informative failure seen so far
+    error: DerivationError,
+    /// Operations performed across every branch
+    counts: OperationCounts,
+    /// Highest memory usage reached on any branch
+    peak: usize,
+    /// Spare workspaces for branch copies
+    pool: WorkspacePool,
+    /// Generator state for trying operations in shuffled order
+    shuffle: Option<u64>,
+    /// Operations the grammar allows
+    operations: Operations,
+    /// Drops constituents that score poorly within their span
+    pruning: Option<pruning::SpanBeam<'a>>,
+    /// Records every operation tried, dead ends included, if tracing
+    trace: Option<&'a mut DerivationTrace>,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        tokens: &'a [&'a str],
+        max_steps: usize,
+        accept: &'a dyn Fn(&SyntacticObject) -> Result<(), DerivationError>,
+        limit: usize,
+        pool: WorkspacePool,
+    ) -> Self {
+        Self {
+            tokens,
+            max_steps,
+            accept,
+            limit,
+            visited: BTreeSet::new(),
+            derivation: Vec::new(),
+            parses: Vec::new(),
+            error: DerivationError::NoValidOperations,
+            counts: OperationCounts::default(),
+            peak: 0,
+            pool,
+            shuffle: None,
+            operations: Operations::ALL,
+            pruning: None,
+            trace: None,
+        }
+    }
+    
+    /// Record a failure, preferring a constraint that ruled out an otherwise
+    /// convergent derivation, then resource exhaustion
+    fn fail(&mut self, error: DerivationError) {
+        match error {
+            DerivationError::ConstraintViolation(_) => self.error = error,
+            DerivationError::MemoryLimitExceeded { .. }
+                if !matches!(self.error, DerivationError::ConstraintViolation(_)) =>
+            {
+                self.error = error
+            }
+            _ => {}
+        }
+    }
     
-    // Check memory limit
-    if workspace.memory_usage() > workspace.memory_limit {
-        return Err(DerivationError::MemoryLimitExceeded);
+    fn done(&self) -> bool {
+        self.parses.len() >= self.limit
     }
     
-    // Try merge operations first
-    let mergeable_pairs = find_mergeable_pairs(workspace);
-    if let Some((i, j)) = mergeable_pairs.first() {
-        let a = workspace.items.remove(*i.max(j));
-        let b = workspace.items.remove(*i.min(j));
+    /// Explore from `workspace`, collecting the operations it tallied
+    fn run(&mut self, workspace: &Workspace) {
+        self.explore(workspace);
+        self.counts += workspace.take_counts();
+        self.peak = self.peak.max(workspace.peak_usage());
+    }
+    
+    fn explore(&mut self, workspace: &Workspace) {
+        if workspace.is_successful() {
+            let tree = &workspace.items[0];
+            let linearized = {
+                let _span = phase_span!("linearize");
+                tree.linearize()
+            };
+            if !linearized.split_whitespace().eq(self.tokens.iter().copied()) {
+                return;
+            }
+            match (self.accept)(tree) {
+                Ok(()) if !self.parses.iter().any(|parse| parse.tree == *tree) => {
+                    log_debug!("converged after {} operations: {:?}", self.derivation.len(), self.derivation);
+                    self.parses.push(Parse {
+                        tree: tree.clone(),
+                        derivation: self.derivation.clone(),
+                        score: 0.0,
+                    })
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    log_debug!("convergent derivation rejected: {}", e);
+                    self.fail(e)
+                }
+            }
+            return;
+        }
+        
+        if workspace.step_count >= self.max_steps || !self.visited.insert(format!("{:?}", workspace.items)) {
+            return;
+        }
         
-        match merge(a, b) {
-            Ok(merged) => {
-                workspace.items.push(merged);
-                return Ok(());
+        let mut ops = available_operations(workspace);
+        ops.retain(|op| self.operations.allows(op));
+        if let Some(state) = &mut self.shuffle {
+            determinism::shuffle(&mut ops, state);
+        }
+        for op in ops {
+            log_trace!("step {}: applying {:?}", workspace.step_count + 1, op);
+            let mut next = self.pool.acquire_copy(workspace);
+            let outcome = match &mut self.trace {
+                Some(trace) => {
+                    next.record_trace();
+                    let outcome = step_with(&mut next, op);
+                    trace.steps.extend(next.take_trace().into_iter().flat_map(|recorded| recorded.steps));
+                    outcome
+                }
+                None => begin_step(&mut next).and_then(|()| apply_operation(&mut next, op)),
+            };
+            match outcome {
+                Ok(()) if self.pruning.as_mut().is_some_and(|beam| !beam.admits(&next.items, op.result_index())) => {
+                    log_trace!("step {}: {:?} pruned", workspace.step_count + 1, op);
+                }
+                Ok(()) => {
+                    self.derivation.push(op);
+                    self.run(&next);
+                    self.derivation.pop();
+                }
+                Err(e) => {
+                    log_trace!("step {}: {:?} failed: {}", workspace.step_count + 1, op, e);
+                    self.counts += next.take_counts();
+                    self.fail(e)
+                }
+            }
+            self.pool.release(next);
+            if self.done() {
+                return;
             }
-            Err(e) => return Err(e),
         }
     }
     
-    // Try move operations
-    for i in 0..workspace.items.len() {
-        if let Ok(moved) = move_operation(workspace.items[i].clone()) {
-            workspace.items[i] = moved;
-            return Ok(());
-        }
+    /// Enumerate from `workspace`, failing if no derivation converges
+    fn parses(&mut self, workspace: &Workspace) -> Result<Vec<Parse>, DerivationError> {
+        self.parses_from([workspace])
     }
     
-    Err(DerivationError::NoValidOperations)
+    /// Enumerate from each of `workspaces` in turn until the limit is
+    /// reached, failing if no derivation converges from any
+    fn parses_from<W: Borrow<Workspace>>(&mut self, workspaces: impl IntoIterator<Item = W>) -> Result<Vec<Parse>, DerivationError> {
+        for workspace in workspaces {
+            if self.done() {
+                break;
+            }
+            let workspace = workspace.borrow();
+            log_debug!("searching derivations for {:?} ({} items)", self.tokens, workspace.items.len());
+            self.run(workspace);
+        }
+        if self.parses.is_empty() {
+            log_debug!("no derivation converged for {:?}: {}", self.tokens, self.error);
+            Err(self.error)
+        } else {
+            Ok(core::mem::take(&mut self.parses))
+        }
+    }
 }
 
 /// Run complete derivation
@@ -427,6 +1496,26 @@ pub fn derive(workspace: &mut Workspace, max_steps: usize) -> Result<SyntacticOb
     }
 }
 
+/// Every complete analysis of a workspace's items, in the order found
+///
+/// Unlike `derive`, which commits to the first available operation, this
+/// backtracks through every operation at every step, so an ambiguous
+/// workspace yields each licensed tree once. Only trees pronouncing the
+/// items' words in their workspace order count; constraints are not
+/// applied. The workspace itself is left as it was, with the operations of
+/// every branch added to its counts.
+pub fn derive_all(workspace: &mut Workspace, max_steps: usize) -> Vec<SyntacticObject> {
+    let words: Vec<String> = workspace.items.iter().map(SyntacticObject::linearize).collect();
+    let words = words.join(" ");
+    let tokens: Vec<&str> = words.split_whitespace().collect();
+    let accept = |_: &SyntacticObject| Ok(());
+    let mut search = Search::new(&tokens, workspace.step_count.saturating_add(max_steps), &accept, usize::MAX, WorkspacePool::new());
+    search.explore(workspace);
+    let counts = search.counts;
+    workspace.tally(|total| *total += counts);
+    search.parses.into_iter().map(|parse| parse.tree).collect()
+}
+
 // ============================================================================
 // Lexicon and Grammar
 // ============================================================================
@@ -434,94 +1523,320 @@ pub fn derive(workspace: &mut Workspace, max_steps: usize) -> Result<SyntacticOb
 /// Standard test lexicon for recursive patterns
 pub fn test_lexicon() -> Vec<LexItem> {
     vec![
-        LexItem::new("the", &[Feature::Cat(Category::D)]),
-        LexItem::new("a", &[Feature::Cat(Category::D)]),
+        LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+        LexItem::new("a", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
         LexItem::new("student", &[Feature::Cat(Category::N)]),
         LexItem::new("tutor", &[Feature::Cat(Category::N)]),
         LexItem::new("teacher", &[Feature::Cat(Category::N)]),
         LexItem::new("who", &[Feature::Cat(Category::C), Feature::Sel(Category::S)]),
         LexItem::new("that", &[Feature::Cat(Category::C), Feature::Sel(Category::S)]),
-        LexItem::new("said", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Pos(1)]),
-        LexItem::new("thinks", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
-        LexItem::new("left", &[Feature::Cat(Category::V)]),
-        LexItem::new("smiled", &[Feature::Cat(Category::V)]),
-        LexItem::new("arrived", &[Feature::Cat(Category::V)]),
+        LexItem::new("said", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D), Feature::Pos(1)]),
+        LexItem::new("thinks", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+        LexItem::new("left", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("smiled", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+        LexItem::new("arrived", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
     ]
 }
 
 /// Generate aⁿbⁿ pattern for testing recursion
 pub fn generate_an_bn(n: usize) -> String {
-    if n == 0 {
-        String::new()
-    } else {
-        format!("{} {}", "a".repeat(n), "b".repeat(n))
-    }
+    let mut tokens = vec!["a"; n];
+    tokens.extend(vec!["b"; n]);
+    tokens.join(" ")
 }
 
-/// Test if string matches aⁿbⁿ pattern
+/// Test if string matches aⁿbⁿ pattern (whitespace between symbols is ignored)
+///
+/// Runs the `patterns::AnBn` counter automaton, so no copy of the input is made.
 pub fn is_an_bn_pattern(s: &str) -> bool {
-    let tokens: Vec<&str> = s.split_whitespace().collect();
-    if tokens.is_empty() {
-        return true; // ε case
+    AnBn::recognize_bytes(s.bytes())
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Parse sentence using Minimalist Grammar
+///
+/// Tokens enter the workspace in surface order and the derivation search
+/// backtracks until it finds an analysis whose linearization is the input.
+pub fn parse_sentence(sentence: &str, lexicon: &[LexItem]) -> Result<SyntacticObject, DerivationError> {
+    parse_with_options(sentence, lexicon, &GrammarOptions::default())
+}
+
+/// Parse sentence, rejecting analyses that violate the options' constraints
+pub fn parse_with_options(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+) -> Result<SyntacticObject, DerivationError> {
+    parse_derivation(sentence, lexicon, options).map(|parse| parse.tree)
+}
+
+/// Parse sentence, keeping the operations that derived the first analysis
+pub fn parse_derivation(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+) -> Result<Parse, DerivationError> {
+    let mut parses = search(sentence, lexicon, options, 1, &mut WorkspacePool::new(), None, None).0?;
+    Ok(parses.remove(0))
+}
+
+/// Parse sentence, counting the operations the search performed
+///
+/// The counts cover every branch explored, including dead ends, so they
+/// measure the cost of finding the analysis rather than of the analysis itself.
+pub fn parse_profiled(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> ParseOutcome {
+    let (parses, counts, peak_memory) = search(sentence, lexicon, options, 1, &mut WorkspacePool::new(), None, None);
+    ParseOutcome {
+        result: parses.map(|mut parses| parses.remove(0)),
+        counts,
+        peak_memory,
+        span: 0..sentence.len(),
     }
-    
-    let n = tokens.len() / 2;
-    if tokens.len() != 2 * n {
-        return false;
+}
+
+/// Parse a batch of sentences, reusing workspaces from `pool` across the
+/// whole batch instead of allocating fresh ones per sentence and branch
+pub fn parse_batch<'s>(
+    sentences: impl IntoIterator<Item = &'s str>,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    pool: &mut WorkspacePool,
+) -> Vec<Result<Parse, DerivationError>> {
+    sentences.into_iter()
+        .map(|sentence| search(sentence, lexicon, options, 1, pool, None, None).0.map(|mut parses| parses.remove(0)))
+        .collect()
+}
+
+/// Enumerate every analysis of a sentence, best first according to `ranker`
+///
+/// Analyses the ranker scores equally keep the order in which the search
+/// found them, so the first parse matches `parse_with_options` by default.
+pub fn parse_forest(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    ranker: &dyn ParseRanker,
+) -> Result<Vec<Parse>, DerivationError> {
+    forest(sentence, lexicon, options, ranker, None)
+}
+
+/// Most probable analysis of a sentence, with its probability: the Viterbi
+/// parse under the lexical items' weights (see `LexicalWeights`)
+///
+/// The parse is read off the packed chart (see `Chart::best`) with scores
+/// summed as logs, so long sentences do not underflow to a tie. If the
+/// grammar's constraints rule that parse out, the admitted analyses are
+/// unpacked and the most probable of them is returned instead. Fails as
+/// `parse_chart` does, or with `InvalidOperation` if an item's weight is
+/// not a probability between 0 and 1. Needs `std` for the logarithms.
+#[cfg(feature = "std")]
+pub fn parse_best(sentence: &str, lexicon: &[LexItem]) -> Result<(SyntacticObject, f64), DerivationError> {
+    if lexicon.iter().any(|item| item.weight.is_some_and(|weight| !(0.0..=1.0).contains(&weight))) {
+        return Err(DerivationError::InvalidOperation);
     }
-    
-    // Check first n tokens are 'a'
-    for i in 0..n {
-        if tokens[i] != "a" {
-            return false;
+    let options = GrammarOptions::default();
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let chart = Chart::build(&tokens, lexicon, options.operations.movement)?;
+    if let Some((tree, score)) = chart.best() {
+        if options.check(&tree).is_ok() && tree.linearize().split_whitespace().eq(tokens.iter().copied()) {
+            return Ok((tree, score.exp()));
         }
     }
+    let best = parse_chart(sentence, lexicon, &options)?
+        .into_iter()
+        .map(|tree| {
+            let score = log_derivation_probability(&tree, lexicon);
+            (tree, score)
+        })
+        .fold(None, |best: Option<(SyntacticObject, f64)>, (tree, score)| match best {
+            Some(best) if best.1 >= score => Some(best),
+            _ => Some((tree, score)),
+        })
+        .ok_or(DerivationError::NoValidOperations)?;
+    Ok((best.0, best.1.exp()))
+}
+
+/// `parse_forest`, optionally trying operations in an order shuffled from `seed`
+pub(crate) fn forest(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    ranker: &dyn ParseRanker,
+    seed: Option<u64>,
+) -> Result<Vec<Parse>, DerivationError> {
+    let mut parses = search(sentence, lexicon, options, usize::MAX, &mut WorkspacePool::new(), seed, None).0?;
+    for parse in &mut parses {
+        parse.score = ranker.score(&parse.tree, &parse.derivation);
+    }
+    parses.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(core::cmp::Ordering::Equal));
+    Ok(parses)
+}
+
+/// Tokenize, look up, and derive a sentence, keeping up to `limit` analyses
+/// along with the operations the search performed and its peak memory usage
+///
+/// With a `shuffle` seed, operations are tried in a pseudo-random order
+/// rather than merges first (see `determinism`). With a `trace`, every
+/// operation tried is recorded in it, including those that fail.
+///
+/// Each phase runs in its own `tracing` span under the `tracing` feature.
+fn search(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    limit: usize,
+    pool: &mut WorkspacePool,
+    shuffle: Option<u64>,
+    trace: Option<&mut DerivationTrace>,
+) -> (Result<Vec<Parse>, DerivationError>, OperationCounts, usize) {
+    let _parse = phase_span!("parse", sentence_length = sentence.len());
+    
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = {
+        let _span = phase_span!("tokenize");
+        normalized.split_whitespace().collect()
+    };
     
-    // Check last n tokens are 'b'
-    for i in n..2*n {
-        if tokens[i] != "b" {
-            return false;
+    let workspaces = {
+        let span = phase_span!("lookup", tokens = tokens.len(), memory = tracing::field::Empty);
+        let entries = match sentence_entries(&tokens, lexicon) {
+            Ok(entries) => entries,
+            Err(e) => return (Err(e), OperationCounts::default(), 0),
+        };
+        let mut workspaces = entry_workspaces(entries, lexicon).peekable();
+        if let Some(first) = workspaces.peek() {
+            record_field!(span, "memory", first.memory_usage());
         }
-    }
+        workspaces
+    };
     
-    true
+    let span = phase_span!("derive", steps = tracing::field::Empty, analyses = tracing::field::Empty);
+    let accept = |tree: &SyntacticObject| options.check(tree);
+    let mut search = Search::new(&tokens, 100, &accept, limit, core::mem::take(pool)); // Max 100 derivation steps
+    search.shuffle = shuffle;
+    search.operations = options.operations;
+    search.trace = trace;
+    let parses = search.parses_from(workspaces);
+    if let Ok(parses) = &parses {
+        record_field!(span, "steps", parses[0].derivation.len());
+        record_field!(span, "analyses", parses.len());
+    }
+    *pool = search.pool;
+    (parses, search.counts, search.peak)
 }
 
-// ============================================================================
-// Public API
-// ============================================================================
+/// Every lexicon entry pronounced `token`, in lexicon order
+pub fn lookup<'l>(lexicon: &'l [LexItem], token: &str) -> Vec<&'l LexItem> {
+    lexicon.iter().filter(|item| item.phon == token).collect()
+}
 
-/// Parse sentence using Minimalist Grammar
-pub fn parse_sentence(sentence: &str, lexicon: &[LexItem]) -> Result<SyntacticObject, DerivationError> {
-    let tokens: Vec<&str> = sentence.split_whitespace().collect();
-    let mut workspace = Workspace::new(1024); // 1KB memory limit
-    
-    // Add tokens to workspace
-    for token in tokens {
-        if let Some(lex_item) = lexicon.iter().find(|item| item.phon == token) {
-            workspace.add_lex(lex_item);
-        } else {
-            return Err(DerivationError::InvalidOperation);
+/// Candidate entries of each token, failing on an unknown word or an
+/// empty sentence
+pub(crate) fn sentence_entries<'l>(tokens: &[&str], lexicon: &'l [LexItem]) -> Result<Vec<Vec<&'l LexItem>>, DerivationError> {
+    if tokens.is_empty() {
+        return Err(DerivationError::EmptyWorkspace);
+    }
+    tokens.iter()
+        .map(|&token| {
+            let entries = lookup(lexicon, token);
+            if entries.is_empty() {
+                log_debug!("unknown token {:?}", token);
+                return Err(DerivationError::InvalidOperation);
+            }
+            Ok(entries)
+        })
+        .collect()
+}
+
+/// Every choice of one alternative per position, lazily, the last
+/// position varying fastest; none if some position has no alternatives
+pub(crate) fn choices<T: Clone>(alternatives: Vec<Vec<T>>) -> impl Iterator<Item = Vec<T>> {
+    let mut choice = alternatives.iter().all(|options| !options.is_empty()).then(|| vec![0; alternatives.len()]);
+    core::iter::from_fn(move || {
+        let current = choice.take()?;
+        let chosen = alternatives.iter().zip(&current).map(|(options, &i)| options[i].clone()).collect();
+        // Advance the odometer; done once every position has wrapped
+        if let Some(position) = (0..current.len()).rev().find(|&p| current[p] + 1 < alternatives[p].len()) {
+            let mut next = current;
+            next[position] += 1;
+            next[position + 1..].iter_mut().for_each(|i| *i = 0);
+            choice = Some(next);
+        }
+        Some(chosen)
+    })
+}
+
+/// Initial workspaces for a tokenized sentence, one per choice among the
+/// entries of its ambiguous words, first entries first, built as they are
+/// needed (see `supertag::MAX_TAG_SEQUENCES`)
+pub(crate) fn sentence_workspaces<'l>(
+    tokens: &[&str],
+    lexicon: &'l [LexItem],
+) -> Result<impl Iterator<Item = Workspace> + 'l, DerivationError> {
+    Ok(entry_workspaces(sentence_entries(tokens, lexicon)?, lexicon))
+}
+
+/// Initial workspaces for each choice among the candidate entries of a
+/// sentence's tokens
+fn entry_workspaces<'l>(entries: Vec<Vec<&'l LexItem>>, lexicon: &'l [LexItem]) -> impl Iterator<Item = Workspace> + 'l {
+    supertag::tag_sequences(entries).map(move |entries| {
+        let mut workspace = Workspace::new(1024); // 1KB memory limit
+        load_entries(&mut workspace, &entries, lexicon);
+        workspace
+    })
+}
+
+/// The initial workspace from which `derivation` derives the sentence: the
+/// first choice of entries on which every operation applies and the
+/// derivation converges
+pub(crate) fn replay_workspace(tokens: &[&str], lexicon: &[LexItem], derivation: &[Operation]) -> Result<Workspace, DerivationError> {
+    let converges = |workspace: &Workspace| {
+        let mut workspace = workspace.clone();
+        derivation.iter().all(|&op| apply_operation(&mut workspace, op).is_ok()) && workspace.is_successful()
+    };
+    let mut first = None;
+    for workspace in sentence_workspaces(tokens, lexicon)? {
+        if converges(&workspace) {
+            return Ok(workspace);
+        }
+        first.get_or_insert(workspace);
+    }
+    Ok(first.expect("a sentence has at least one choice of entries"))
+}
+
+/// Fill an empty workspace with chosen entries and the lexicon's silent
+/// heads, recording on each leaf the entry it was built from
+pub(crate) fn load_entries(workspace: &mut Workspace, entries: &[&LexItem], lexicon: &[LexItem]) {
+    // Silent heads can enter the derivation anywhere
+    for index in (0..lexicon.len()).filter(|&i| lexicon[i].phon.is_empty()) {
+        workspace.add_empty_entry(lexicon, index);
+    }
+    for &item in entries {
+        match lexicon.iter().position(|entry| core::ptr::eq(entry, item)) {
+            Some(index) => workspace.add_entry(lexicon, index),
+            None => workspace.add_lex(item),
         }
     }
-    
-    derive(&mut workspace, 100) // Max 100 derivation steps
 }
 
 /// Generate string of specified pattern
+///
+/// Looks `pattern` up in the built-in `PatternRegistry`; use a registry
+/// directly to generate user-defined patterns.
 pub fn generate_pattern(pattern: &str, n: usize) -> Result<String, DerivationError> {
-    match pattern {
-        "an_bn" => Ok(generate_an_bn(n)),
-        _ => Err(DerivationError::InvalidOperation),
-    }
+    PatternRegistry::new()
+        .get(pattern)
+        .map(|pattern| pattern.generate(n))
+        .ok_or(DerivationError::InvalidOperation)
 }
 
 /// Check if grammar can generate given string
 pub fn can_generate(pattern: &str, n: usize) -> bool {
-    match generate_pattern(pattern, n) {
-        Ok(_) => true,
-        Err(_) => false,
-    }
+    generate_pattern(pattern, n).is_ok()
 }
 
 #[cfg(test)]
@@ -546,8 +1861,8 @@ mod tests {
     fn test_an_bn_generation() {
         assert_eq!(generate_an_bn(0), "");
         assert_eq!(generate_an_bn(1), "a b");
-        assert_eq!(generate_an_bn(2), "aa bb");
-        assert_eq!(generate_an_bn(3), "aaa bbb");
+        assert_eq!(generate_an_bn(2), "a a b b");
+        assert_eq!(generate_an_bn(3), "a a a b b b");
     }
 
     #[test]
@@ -584,10 +1899,218 @@ mod tests {
             ..det
         };
         
+        // Category mismatch should fail
+        assert!(merge(det_sel.clone(), verb).is_err());
+        
         // This should succeed
-        assert!(merge(det_sel, noun).is_ok());
+        assert!(merge(det_sel.clone(), noun.clone()).is_ok());
+
+        // A selectee with a pending movement trigger would strand it when it moves
+        let trigger = SyntacticObject {
+            features: vec![Feature::Cat(Category::N), Feature::Pos(1)],
+            ..noun
+        };
+        assert!(merge(det_sel, trigger).is_err());
     }
 
+    #[test]
+    fn test_move_in_place() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let mut debugger = Debugger::for_sentence("the student who left", &lexicon, &options).unwrap();
+        debugger.add_breakpoint(Breakpoint::Category(Category::C));
+        debugger.resume().unwrap();
+        let cp = debugger.inspect(2).unwrap().clone();
+
+        // Relinking in place matches consuming the object
+        let mut moved = cp.clone();
+        move_in_place(&mut moved).unwrap();
+        assert_eq!(moved, move_operation(cp).unwrap());
+        assert_eq!(moved.linearize(), "who left");
+        assert!(moved.bracketed().contains(" t]"), "the mover leaves a trace");
+
+        // A failed movement leaves the object as it was
+        let mut leaf = SyntacticObject::from_lex(&lexicon[0]);
+        let before = leaf.clone();
+        assert_eq!(move_in_place(&mut leaf), Err(DerivationError::NoValidOperations));
+        assert_eq!(leaf, before);
+    }
+
+    #[test]
+    fn test_pp_adjunction() {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        
+        // NP adjunct inside the subject
+        let tree = parse_sentence("the student near the tutor left", &lexicon).unwrap();
+        assert_eq!(tree.children[0].linearize(), "the student near the tutor");
+        
+        // VP adjunct after the subject has merged
+        let tree = parse_sentence("the student left near the tutor", &lexicon).unwrap();
+        assert_eq!(tree.label, Category::VP);
+        assert_eq!(tree.children[1].label, Category::PP);
+        
+        // A preposition still missing its object cannot adjoin
+        let near = SyntacticObject::from_lex(&lexicon[12]);
+        let student = SyntacticObject::from_lex(&lexicon[2]);
+        assert!(!can_adjoin(&student, &near));
+        assert!(parse_sentence("the student near left", &lexicon).is_err());
+    }
+
+    #[test]
+    fn test_ambiguous_tokens() {
+        // `that` is a complementizer first and a determiner second
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("that", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]));
+        assert_eq!(lookup(&lexicon, "that").len(), 2);
+
+        let tree = parse_sentence("that student left", &lexicon).unwrap();
+        assert_eq!(tree.bracketed(), "[VP [DP [D that] [N student]] [V left]]");
+
+        // Replaying the derivation starts from the determiner reading
+        let mut debugger = Debugger::for_sentence("that student left", &lexicon, &GrammarOptions::default()).unwrap();
+        assert_eq!(debugger.resume().unwrap(), None);
+        assert_eq!(debugger.workspace().items()[0], tree);
+        assert!(parse_sentence("that left", &lexicon).is_err());
+    }
+
+    #[test]
+    fn test_bracketed() {
+        let tree = parse_sentence("the student left", &test_lexicon()).unwrap();
+        assert_eq!(tree.bracketed(), "[VP [DP [D the] [N student]] [V left]]");
+        
+        let lexicon = GrammarOptions::default().lexicon();
+        let tree = parse_sentence("the student who left", &lexicon).unwrap();
+        assert!(tree.bracketed().contains("[CP [D who] [CP [C ε] [VP [D t] [V left]]]]"));
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_logging_reports_derivation() {
+        use std::sync::Mutex;
+        
+        struct Capture(Mutex<Vec<String>>);
+        
+        impl log::Log for Capture {
+            fn enabled(&self, metadata: &log::Metadata) -> bool {
+                metadata.target() == "atomic_lang_model"
+            }
+            fn log(&self, record: &log::Record) {
+                if self.enabled(record.metadata()) {
+                    self.0.lock().unwrap().push(format!("{} {}", record.level(), record.args()));
+                }
+            }
+            fn flush(&self) {}
+        }
+        
+        static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+        log::set_logger(&CAPTURE).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        
+        parse_sentence("the student left", &test_lexicon()).unwrap();
+        assert!(parse_sentence("the robot left", &test_lexicon()).is_err());
+        
+        let records = CAPTURE.0.lock().unwrap();
+        assert!(records.iter().any(|r| r.starts_with("TRACE step 1: applying Merge(0, 1)")));
+        assert!(records.iter().any(|r| r.starts_with("TRACE merge: D checks Some(Sel(N))")));
+        assert!(records.iter().any(|r| r.starts_with("DEBUG converged after 2 operations")));
+        assert!(records.iter().any(|r| r == "DEBUG unknown token \"robot\""));
+    }
+    
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_spans_parse_phases() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        
+        type SpanFields = Vec<(String, u64)>;
+        
+        /// Records span names in creation order, plus their numeric fields
+        #[derive(Default)]
+        struct Spans(Mutex<Vec<(String, SpanFields)>>);
+        
+        struct Fields<'a>(&'a mut SpanFields);
+        
+        impl Visit for Fields<'_> {
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                self.0.push((field.name().to_string(), value));
+            }
+            fn record_debug(&mut self, _field: &Field, _value: &dyn fmt::Debug) {}
+        }
+        
+        impl tracing::Subscriber for Spans {
+            fn enabled(&self, _metadata: &tracing::Metadata) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes) -> Id {
+                let mut spans = self.0.lock().unwrap();
+                let mut fields = Vec::new();
+                span.record(&mut Fields(&mut fields));
+                spans.push((span.metadata().name().to_string(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+            fn record(&self, span: &Id, values: &Record) {
+                let mut spans = self.0.lock().unwrap();
+                values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1].1));
+            }
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+            fn event(&self, _event: &tracing::Event) {}
+            fn enter(&self, _span: &Id) {}
+            fn exit(&self, _span: &Id) {}
+        }
+        
+        let subscriber = Arc::new(Spans::default());
+        tracing::subscriber::with_default(subscriber.clone(), || {
+            parse_sentence("the student left", &test_lexicon()).unwrap();
+        });
+        
+        let spans = subscriber.0.lock().unwrap();
+        let names: Vec<&str> = spans.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(&names[..4], ["parse", "tokenize", "lookup", "derive"]);
+        assert!(names[4..].iter().all(|&name| name == "linearize"));
+        
+        let field = |span: usize, name: &str| spans[span].1.iter().find(|(f, _)| f == name).map(|&(_, v)| v);
+        assert_eq!(field(0, "sentence_length"), Some(16));
+        assert_eq!(field(2, "tokens"), Some(3));
+        assert!(field(2, "memory").is_some());
+        assert_eq!(field(3, "steps"), Some(2));
+        assert_eq!(field(3, "analyses"), Some(1));
+    }
+    
+    #[test]
+    fn test_derive_all_backtracks() {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        let tokens = ["the", "student", "thinks", "the", "teacher", "near", "the", "tutor"];
+        let mut workspace = sentence_workspaces(&tokens, &lexicon).unwrap().next().unwrap();
+        let before = workspace.items().to_vec();
+
+        // The PP attaches to "teacher" or to the verb phrase
+        let trees = derive_all(&mut workspace, 100);
+        assert_eq!(trees.len(), 2);
+        assert_ne!(trees[0], trees[1]);
+        assert!(trees.iter().all(|tree| tree.linearize() == tokens.join(" ")));
+        assert_eq!(workspace.items(), before);
+        assert!(workspace.take_counts().merges_succeeded > 0);
+        // A limit past the steps already taken saturates
+        workspace.step_count = 1;
+        assert_eq!(derive_all(&mut workspace, usize::MAX).len(), 2);
+
+        let mut stuck = sentence_workspaces(&["the", "student", "the"], &lexicon).unwrap().next().unwrap();
+        assert!(derive_all(&mut stuck, 100).is_empty());
+    }
+    
     #[test]
     fn test_workspace_operations() {
         let mut workspace = Workspace::new(1024);
@@ -596,7 +2119,25 @@ mod tests {
         workspace.add_lex(&lexicon[0]); // "the"
         workspace.add_lex(&lexicon[2]); // "student"
         
-        assert_eq!(workspace.items.len(), 2);
+        assert_eq!(workspace.items().len(), 2);
         assert!(!workspace.is_successful());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_editing_items_invalidates_the_index() {
+        let mut workspace = Workspace::new(1024);
+        let lexicon = test_lexicon();
+        workspace.add_lex(&lexicon[0]); // "the"
+        workspace.add_lex(&lexicon[2]); // "student"
+        assert_eq!(find_mergeable_pairs(&workspace), [(0, 1)]);
+        
+        // Same length, different items: the old index would still pair them
+        workspace.items_mut()[1] = SyntacticObject::from_lex(&lexicon[0]);
+        assert!(find_mergeable_pairs(&workspace).is_empty());
+        
+        workspace.items_mut()[1] = SyntacticObject::from_lex(&lexicon[2]);
+        workspace.add_lex(&lexicon[9]); // "left"
+        assert_eq!(apply_operation(&mut workspace, Operation::Merge(0, 1)), Ok(()));
+        assert_eq!(find_mergeable_pairs(&workspace), [(1, 0)]);
+    }
+}
@@ -0,0 +1,247 @@
+//! Minimal-Pair Generation
+//!
+//! Expands a handful of seed sentences into many grammatical/ungrammatical
+//! pairs. Seeds are first varied by substituting words with identical
+//! feature bundles, and a variant counts as grammatical only if the grammar
+//! derives it. Each perturbation then yields its ungrammatical twin:
+//!
+//! - `SwapNumber`: the main verb (the lexical head of the clause) takes its
+//!   other-number form
+//! - `SwapOrder`: two adjacent words with different bundles trade places
+//! - `DeleteArgument`: a selected complement or specifier is dropped
+//!
+//! Ungrammaticality comes from the perturbation, not from the grammar, so
+//! the pairs also test grammars (and models) that over-generate, such as
+//! one without agreement features.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeSet, string::{String, ToString}, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use crate::constraints::head_leaf;
+use crate::{choices, parse_with_options, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Way of breaking a grammatical sentence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Perturbation {
+    /// Replace the main verb with its other-number form
+    SwapNumber,
+    /// Swap adjacent words with different feature bundles
+    SwapOrder,
+    /// Delete a selected argument
+    DeleteArgument,
+}
+
+/// Which pairs to generate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerturbationSpec {
+    /// Perturbations to apply to every grammatical variant
+    pub perturbations: Vec<Perturbation>,
+    /// Singular and plural forms; these are never substituted, so variants
+    /// keep the seed's agreement
+    pub number_pairs: Vec<(String, String)>,
+    /// Variants tried per seed, the seed itself included
+    pub variants: usize,
+}
+
+impl PerturbationSpec {
+    /// Every perturbation, with the given number pairs
+    pub fn new(number_pairs: &[(&str, &str)]) -> Self {
+        Self {
+            perturbations: vec![Perturbation::SwapNumber, Perturbation::SwapOrder, Perturbation::DeleteArgument],
+            number_pairs: number_pairs.iter().map(|&(sg, pl)| (sg.to_string(), pl.to_string())).collect(),
+            variants: 64,
+        }
+    }
+
+    fn other_number(&self, word: &str) -> Option<&str> {
+        self.number_pairs.iter().find_map(|(sg, pl)| match word {
+            w if w == sg => Some(pl.as_str()),
+            w if w == pl => Some(sg.as_str()),
+            _ => None,
+        })
+    }
+}
+
+/// Grammatical sentence and its perturbed twin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinimalPair {
+    /// Sentence the grammar derives
+    pub grammatical: String,
+    /// Sentence broken by the perturbation
+    pub ungrammatical: String,
+    /// How it was broken
+    pub perturbation: Perturbation,
+    /// Index of the seed it was varied from
+    pub seed: usize,
+}
+
+/// Generate minimal pairs from `seeds`, dropping seeds the grammar cannot derive
+pub fn minimal_pairs(
+    seeds: &[&str],
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    spec: &PerturbationSpec,
+) -> Vec<MinimalPair> {
+    let mut seen = BTreeSet::new();
+    let mut pairs = Vec::new();
+    for (seed, sentence) in seeds.iter().enumerate() {
+        for variant in variants(sentence, lexicon, spec) {
+            let Ok(tree) = parse_with_options(&variant, lexicon, options) else {
+                continue;
+            };
+            let words: Vec<&str> = variant.split_whitespace().collect();
+            for &perturbation in &spec.perturbations {
+                for ungrammatical in perturb(&words, &tree, lexicon, spec, perturbation) {
+                    if seen.insert((variant.clone(), ungrammatical.clone())) {
+                        pairs.push(MinimalPair { grammatical: variant.clone(), ungrammatical, perturbation, seed });
+                    }
+                }
+            }
+        }
+    }
+    pairs
+}
+
+/// Feature bundle the parser would use for `word`
+fn bundle<'a>(word: &str, lexicon: &'a [LexItem]) -> Option<&'a [Feature]> {
+    lexicon.iter().find(|item| item.phon == word).map(|item| item.feats.as_slice())
+}
+
+/// The seed and its substitution variants, up to `spec.variants`
+fn variants(sentence: &str, lexicon: &[LexItem], spec: &PerturbationSpec) -> Vec<String> {
+    let alternatives: Vec<Vec<&str>> = sentence.split_whitespace()
+        .map(|word| {
+            let mut words = vec![word];
+            if spec.other_number(word).is_some() {
+                return words;
+            }
+            for item in lexicon {
+                let substitutable = !item.phon.is_empty()
+                    && !words.contains(&item.phon.as_str())
+                    && spec.other_number(&item.phon).is_none()
+                    && bundle(&item.phon, lexicon) == Some(item.feats.as_slice())
+                    && bundle(word, lexicon) == Some(item.feats.as_slice());
+                if substitutable {
+                    words.push(&item.phon);
+                }
+            }
+            words
+        })
+        .collect();
+
+    // The seed comes first, since each word is its own first alternative
+    choices(alternatives).take(spec.variants).map(|words| words.join(" ")).collect()
+}
+
+fn perturb(
+    words: &[&str],
+    tree: &SyntacticObject,
+    lexicon: &[LexItem],
+    spec: &PerturbationSpec,
+    perturbation: Perturbation,
+) -> Vec<String> {
+    match perturbation {
+        Perturbation::SwapNumber => {
+            let verb = head_leaf(tree).phon.as_deref().unwrap_or_default();
+            let (Some(other), Some(i)) = (spec.other_number(verb), words.iter().rposition(|w| *w == verb)) else {
+                return Vec::new();
+            };
+            let mut swapped = words.to_vec();
+            swapped[i] = other;
+            vec![swapped.join(" ")]
+        }
+        Perturbation::SwapOrder => (0..words.len().saturating_sub(1))
+            .filter(|&i| bundle(words[i], lexicon) != bundle(words[i + 1], lexicon))
+            .map(|i| {
+                let mut swapped = words.to_vec();
+                swapped.swap(i, i + 1);
+                swapped.join(" ")
+            })
+            .collect(),
+        Perturbation::DeleteArgument => {
+            let mut spans = Vec::new();
+            arguments(tree, &mut 0, &mut spans);
+            spans.into_iter()
+                .filter(|&(_, len)| len > 0 && len < words.len())
+                .map(|(start, len)| [&words[..start], &words[start + len..]].concat().join(" "))
+                .collect()
+        }
+    }
+}
+
+/// Word spans `(start, len)` of the arguments in a tree (see
+/// `SyntacticObject::arguments`), in pre-order
+fn arguments(node: &SyntacticObject, offset: &mut usize, spans: &mut Vec<(usize, usize)>) {
+    if node.phon.is_some() {
+        *offset += node.linearize().split_whitespace().count();
+        return;
+    }
+    let selected = node.arguments().map_or(Vec::new(), |(_, arguments)| arguments);
+    for (i, child) in node.children.iter().enumerate() {
+        let start = *offset;
+        let mut inner = Vec::new();
+        arguments(child, offset, &mut inner);
+        if selected.contains(&i) {
+            spans.push((start, *offset - start));
+        }
+        spans.extend(inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category};
+
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        lexicon.extend([
+            LexItem::new("students", &[Feature::Cat(Category::N)]),
+            LexItem::new("leave", &[Feature::Cat(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("thinks", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("think", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+        ]);
+        lexicon
+    }
+
+    #[test]
+    fn test_pairs_from_a_seed() {
+        let lexicon = lexicon();
+        let spec = PerturbationSpec::new(&[("student", "students"), ("thinks", "think"), ("left", "leave")]);
+        let pairs = minimal_pairs(&["the student left"], &lexicon, &GrammarOptions::default(), &spec);
+
+        let of = |perturbation| -> Vec<(&str, &str)> {
+            pairs.iter()
+                .filter(|pair| pair.perturbation == perturbation && pair.grammatical == "the student left")
+                .map(|pair| (pair.grammatical.as_str(), pair.ungrammatical.as_str()))
+                .collect()
+        };
+        assert_eq!(of(Perturbation::SwapNumber), [("the student left", "the student leave")]);
+        assert_eq!(of(Perturbation::SwapOrder), [
+            ("the student left", "student the left"),
+            ("the student left", "the left student"),
+        ]);
+        assert_eq!(of(Perturbation::DeleteArgument), [("the student left", "left"), ("the student left", "the left")]);
+
+        // 'the' varies with 'a'; the number-marked noun and verb never vary
+        assert!(pairs.iter().any(|pair| pair.grammatical == "a student left"));
+        assert!(pairs.iter().all(|pair| pair.grammatical.contains("student left") && pair.seed == 0));
+    }
+
+    #[test]
+    fn test_seeds_expand() {
+        let lexicon = lexicon();
+        let spec = PerturbationSpec {
+            perturbations: vec![Perturbation::SwapNumber],
+            ..PerturbationSpec::new(&[("thinks", "think"), ("student", "students")])
+        };
+        let seeds = ["the student thinks the teacher", "the student thinks"];
+        let pairs = minimal_pairs(&seeds, &lexicon, &GrammarOptions::default(), &spec);
+        // Two determiners each, and 'teacher' or 'tutor'; the second seed does not parse
+        assert_eq!(pairs.len(), 8);
+        assert!(pairs.iter().all(|pair| pair.ungrammatical.contains(" think ") && pair.seed == 0));
+    }
+}
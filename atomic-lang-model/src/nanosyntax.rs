@@ -0,0 +1,171 @@
+//! Nanosyntax
+//!
+//! A lexicalist grammar lists words with their feature bundles. Nanosyntax
+//! (Starke 2009, Caha 2009) instead merges one feature per head, so the
+//! derivation runs over atomic morphemes (`-ed go`), and lexicalizes
+//! phrases after syntax: an entry stores a span of heads and spells out
+//! any constituent its span contains (Superset Principle). Among entries
+//! that fit, the one with the fewest unused heads wins (Elsewhere
+//! Condition), and a match at a larger constituent overrides the matches
+//! inside it, so one exponent can realize several heads (`went`). Selected
+//! per grammar as the `PfRule::PhrasalSpellOut` rule; the same engine then
+//! derives both analyses from different lexicons.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use core::ops::Range;
+
+use crate::pf::PfNode;
+
+/// Exponent stored with the span of heads it lexicalizes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexicalization {
+    /// Realized form, e.g. `went`
+    pub exponent: String,
+    /// Heads, in linear order with the lowest last, e.g. `-ed go`
+    pub span: Vec<String>,
+}
+
+impl Lexicalization {
+    /// Entry realizing the heads of `span` as `exponent`
+    pub fn new(exponent: &str, span: &[&str]) -> Self {
+        Self { exponent: exponent.to_string(), span: span.iter().map(|head| head.to_string()).collect() }
+    }
+
+    /// Whether the stored span contains a constituent with these heads
+    ///
+    /// Constituents of a right-branching span are its lower parts, so the
+    /// span must end with `heads`. A lone head is a word of its own, and
+    /// only an entry for exactly that head spells it out: otherwise `went`
+    /// would realize the `go` of `-s go` and leave `-s` to hop onto it.
+    pub fn matches(&self, heads: &[&str]) -> bool {
+        match heads.len() {
+            0 => false,
+            1 => self.span.len() == 1 && self.span[0] == heads[0],
+            width => self.span.len() >= width
+                && self.span[self.span.len() - width..].iter().zip(heads).all(|(stored, head)| stored == head),
+        }
+    }
+}
+
+/// Entry spelling out a constituent with these heads: the matching entry
+/// with the fewest unused heads, the first listed on a tie
+pub fn lexicalize<'a>(entries: &'a [Lexicalization], heads: &[&str]) -> Option<&'a Lexicalization> {
+    entries.iter().filter(|entry| entry.matches(heads)).min_by_key(|entry| entry.span.len())
+}
+
+/// Largest lexicalized constituents of a PF, as ranges over its words
+///
+/// Constituents are tried top-down, so a match overrides those below it;
+/// words no entry covers are left to the other spell-out rules.
+pub fn phrasal_spell_out<'a>(pf: &PfNode, entries: &'a [Lexicalization]) -> Vec<(Range<usize>, &'a str)> {
+    let mut spans = Vec::new();
+    spell(pf, entries, 0, &mut spans);
+    spans
+}
+
+/// Lexicalize `node`, whose first word is at `start`; returns its width
+fn spell<'a>(node: &PfNode, entries: &'a [Lexicalization], start: usize, spans: &mut Vec<(Range<usize>, &'a str)>) -> usize {
+    let heads = node.words();
+    if let Some(entry) = lexicalize(entries, &heads) {
+        spans.push((start..start + heads.len(), entry.exponent.as_str()));
+    } else if let PfNode::Phrase(_, children) = node {
+        children.iter().fold(start, |at, child| at + spell(child, entries, at, spans));
+    }
+    heads.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pf::spell_out;
+    use crate::{parse_sentence, parse_with_options, Category, Feature, GrammarOptions, LexItem, PfRule, SpellOut};
+
+    /// Cumulative case (Caha 2009): accusative is built on top of nominative
+    fn pronouns() -> Vec<LexItem> {
+        vec![
+            LexItem::new("-acc", &[Feature::Cat(Category::D), Feature::Sel(Category::D)]),
+            LexItem::new("-nom", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+            LexItem::new("3m", &[Feature::Cat(Category::N)]),
+            LexItem::new("3n", &[Feature::Cat(Category::N)]),
+        ]
+    }
+
+    #[test]
+    fn test_superset_and_elsewhere() {
+        let entries = [
+            Lexicalization::new("him", &["-acc", "-nom", "3m"]),
+            Lexicalization::new("he", &["-nom", "3m"]),
+            Lexicalization::new("it", &["-acc", "-nom", "3n"]),
+        ];
+        let spelled = |morphemes: &str| {
+            let pf = spell_out(&parse_sentence(morphemes, &pronouns()).unwrap());
+            phrasal_spell_out(&pf, &entries).into_iter().map(|(_, exponent)| exponent).collect::<Vec<_>>()
+        };
+
+        // Both 'he' and 'him' contain the nominative; 'he' leaves nothing unused
+        assert_eq!(spelled("-nom 3m"), ["he"]);
+        assert_eq!(spelled("-acc -nom 3m"), ["him"]);
+        // One neuter entry covers both cases (syncretism)
+        assert_eq!(spelled("-nom 3n"), ["it"]);
+        assert_eq!(spelled("-acc -nom 3n"), ["it"]);
+
+        assert!(entries[0].matches(&["-nom", "3m"]));
+        assert!(!entries[0].matches(&["3m"]));
+        assert!(!entries[1].matches(&["-acc", "-nom", "3m"]));
+        assert!(!entries[1].matches(&["-nom"]));
+    }
+
+    #[test]
+    fn test_lexicalist_and_nanosyntactic_analyses_agree() {
+        // Lexicalist: 'went' is listed with its bundle
+        let mut lexicalist = vec![LexItem::new("went", &[Feature::Cat(Category::V), Feature::Spec(Category::D)])];
+        lexicalist.extend(GrammarOptions::default().lexicon());
+        let options = GrammarOptions::default();
+        let tree = parse_with_options("the student went", &lexicalist, &options).unwrap();
+        assert_eq!(options.pronounce(&tree), Ok("the student went".to_string()));
+
+        // Nanosyntactic: tense and the root are separate heads, and 'went'
+        // lexicalizes the phrase they form; '-ed' and 'walk' are left to
+        // hopping and allomorphy
+        let mut nanosyntactic = vec![
+            LexItem::new("-ed", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("go", &[Feature::Cat(Category::V)]),
+            LexItem::new("walk", &[Feature::Cat(Category::V)]),
+        ];
+        nanosyntactic.extend(GrammarOptions::default().lexicon());
+        let mut spell_out = SpellOut::english();
+        spell_out.rules.insert(0, PfRule::PhrasalSpellOut(vec![Lexicalization::new("went", &["-ed", "go"])]));
+        let options = GrammarOptions { spell_out, ..GrammarOptions::default() };
+        for (morphemes, pronounced) in [("the student -ed go", "the student went"), ("the student -ed walk", "the student walked")] {
+            let tree = parse_with_options(morphemes, &nanosyntactic, &options).unwrap();
+            assert_eq!(options.pronounce(&tree), Ok(pronounced.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_lexicalization_stays_within_its_span() {
+        // 'went' lexicalizes '-ed go' only; the root alone under another
+        // tense head is not part of it
+        let mut lexicon = vec![
+            LexItem::new("-ed", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("-s", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("go", &[Feature::Cat(Category::V)]),
+        ];
+        lexicon.extend(GrammarOptions::default().lexicon());
+        let entries = vec![Lexicalization::new("went", &["-ed", "go"])];
+
+        let tree = parse_sentence("the student -s go", &lexicon).unwrap();
+        assert!(phrasal_spell_out(&spell_out(&tree), &entries).is_empty());
+        assert!(!entries[0].matches(&["go"]));
+
+        let mut spell_out = SpellOut::english();
+        spell_out.rules.insert(0, PfRule::PhrasalSpellOut(entries));
+        let options = GrammarOptions { spell_out, ..GrammarOptions::default() };
+        let tree = parse_with_options("the student -s go", &lexicon, &options).unwrap();
+        assert_eq!(options.pronounce(&tree), Ok("the student goes".to_string()));
+        let tree = parse_with_options("the student -ed go", &lexicon, &options).unwrap();
+        assert_eq!(options.pronounce(&tree), Ok("the student went".to_string()));
+    }
+}
@@ -0,0 +1,231 @@
+//! Template-Based Generation
+//!
+//! A skeleton fixes the shape of a derivation, which constituent merges
+//! with which, and leaves some leaves open as named slots (`SUBJ`, `VERB`,
+//! `OBJ`) constrained by a feature bundle. Generation fills every slot
+//! with each lexical item carrying those features and keeps the fillings
+//! that converge. A slot may also require phi-features, read from the
+//! grammar's agreement rule, and spell-out then makes the verb agree, so a
+//! template yields agreeing sentences in bulk for data augmentation.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::{String, ToString}, vec, vec::Vec};
+
+use crate::paraphrase::has_frame;
+use crate::phi::Phi;
+use crate::spellout::PfRule;
+use crate::{adjoin, choices, merge, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Open leaf of a skeleton
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    /// Name the filler is reported under
+    pub name: String,
+    /// Feature bundle of its fillers; selected categories match at head
+    /// or phrase level, and agreement features are left to `phi`
+    pub features: Vec<Feature>,
+    /// Phi-features its fillers must have, if constrained; nodes left
+    /// unspecified here are free
+    pub phi: Option<Phi>,
+}
+
+impl Slot {
+    /// Whether the item has the slot's category and selectors
+    fn fits(&self, item: &LexItem) -> bool {
+        let category = |features: &[Feature]| features.iter().find(|f| matches!(f, Feature::Cat(_))).cloned();
+        let frame: Vec<Feature> = self.features.iter().filter(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_))).cloned().collect();
+        category(&item.feats) == category(&self.features) && has_frame(&item.feats, &frame)
+    }
+}
+
+/// Shape of a derivation with open slots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Skeleton {
+    /// Fixed word, the lexicon's first entry for it
+    Word(String),
+    /// Slot filled from the lexicon
+    Slot(Slot),
+    /// Selecting constituent and its argument
+    Merge(Box<Skeleton>, Box<Skeleton>),
+    /// Host and adjunct
+    Adjoin(Box<Skeleton>, Box<Skeleton>),
+}
+
+impl Skeleton {
+    /// Fixed word
+    pub fn word(word: &str) -> Self {
+        Skeleton::Word(word.to_string())
+    }
+
+    /// Slot for items with the feature bundle
+    pub fn slot(name: &str, features: &[Feature]) -> Self {
+        Skeleton::Slot(Slot { name: name.to_string(), features: features.to_vec(), phi: None })
+    }
+
+    /// Slot for items with the feature bundle and phi-features
+    pub fn slot_with_phi(name: &str, features: &[Feature], phi: Phi) -> Self {
+        Skeleton::Slot(Slot { name: name.to_string(), features: features.to_vec(), phi: Some(phi) })
+    }
+
+    /// This constituent selecting `argument`
+    pub fn merge(self, argument: Skeleton) -> Self {
+        Skeleton::Merge(Box::new(self), Box::new(argument))
+    }
+
+    /// This constituent hosting `adjunct`
+    pub fn adjoin(self, adjunct: Skeleton) -> Self {
+        Skeleton::Adjoin(Box::new(self), Box::new(adjunct))
+    }
+
+    /// Slots in left-to-right order of the skeleton
+    pub fn slots(&self) -> Vec<&Slot> {
+        match self {
+            Skeleton::Word(_) => Vec::new(),
+            Skeleton::Slot(slot) => vec![slot],
+            Skeleton::Merge(a, b) | Skeleton::Adjoin(a, b) => [a.slots(), b.slots()].concat(),
+        }
+    }
+
+    /// Derive the skeleton with the given fillers, in slot order
+    fn build(&self, lexicon: &[LexItem], fillers: &mut impl Iterator<Item = SyntacticObject>) -> Option<SyntacticObject> {
+        match self {
+            Skeleton::Word(word) => lexicon.iter().find(|item| item.phon == *word).map(SyntacticObject::from_lex),
+            Skeleton::Slot(_) => fillers.next(),
+            Skeleton::Merge(a, b) => {
+                let (a, b) = (a.build(lexicon, fillers)?, b.build(lexicon, fillers)?);
+                merge(a, b).ok()
+            }
+            Skeleton::Adjoin(a, b) => {
+                let (a, b) = (a.build(lexicon, fillers)?, b.build(lexicon, fillers)?);
+                adjoin(a, b).ok()
+            }
+        }
+    }
+}
+
+/// A sentence generated from a skeleton
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    /// Pronounced sentence
+    pub sentence: String,
+    /// Slot names with their fillers, in slot order
+    pub fillers: Vec<(String, String)>,
+}
+
+/// Phi-features of a filler: as listed by the grammar's agreement rule,
+/// otherwise third person singular
+fn phi(word: &str, options: &GrammarOptions) -> Phi {
+    options.spell_out.rules.iter()
+        .find_map(|rule| match rule {
+            PfRule::Agreement(agreement) => agreement.listed(word),
+            _ => None,
+        })
+        .unwrap_or(Phi::THIRD_SINGULAR)
+}
+
+/// Sentences the skeleton derives, at most `limit`
+///
+/// Fillings are tried in lexicon order, the last slot varying fastest.
+/// A filling is kept if it converges and passes the grammar's constraints.
+pub fn generate(skeleton: &Skeleton, lexicon: &[LexItem], options: &GrammarOptions, limit: usize) -> Vec<Generated> {
+    let slots = skeleton.slots();
+    let candidates: Vec<Vec<&LexItem>> = slots.iter()
+        .map(|slot| {
+            lexicon.iter()
+                .filter(|item| slot.fits(item))
+                .filter(|item| slot.phi.is_none_or(|required| required.subsumes(&phi(&item.phon, options))))
+                .collect()
+        })
+        .collect();
+
+    let mut generated = Vec::new();
+    for items in choices(candidates) {
+        if generated.len() == limit {
+            break;
+        }
+        let tree = skeleton.build(lexicon, &mut items.iter().map(|item| SyntacticObject::from_lex(item)));
+        if let Some(tree) = tree.filter(|tree| tree.is_complete() && options.check(tree).is_ok()) {
+            if let Ok(sentence) = options.pronounce(&tree) {
+                let fillers = slots.iter().zip(&items).map(|(slot, item)| (slot.name.clone(), item.phon.clone())).collect();
+                generated.push(Generated { sentence, fillers });
+            }
+        }
+    }
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spellout::SpellOut;
+    use crate::{test_lexicon, Category};
+
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        lexicon.extend([
+            LexItem::new("-pres", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("watch", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
+            LexItem::new("leave", &[Feature::Cat(Category::V)]),
+            LexItem::new("students", &[Feature::Cat(Category::N)]),
+        ]);
+        lexicon
+    }
+
+    fn english() -> GrammarOptions {
+        let mut spell_out = SpellOut::english();
+        if let PfRule::Agreement(agreement) = &mut spell_out.rules[0] {
+            agreement.nominals.push(("students".to_string(), Phi::plural(3)));
+        }
+        GrammarOptions { spell_out, ..GrammarOptions::default() }
+    }
+
+    /// `the SUBJ -pres VERB the OBJ`
+    fn transitive(subject: Skeleton) -> Skeleton {
+        let object = Skeleton::word("the").merge(Skeleton::slot("OBJ", &[Feature::Cat(Category::N)]));
+        let verb = Skeleton::slot("VERB", &[Feature::Cat(Category::V), Feature::Sel(Category::D)]).merge(object);
+        Skeleton::word("-pres").merge(verb).merge(Skeleton::word("the").merge(subject))
+    }
+
+    #[test]
+    fn test_slots_are_filled_by_features() {
+        let skeleton = transitive(Skeleton::slot("SUBJ", &[Feature::Cat(Category::N)]));
+        let names: Vec<&str> = skeleton.slots().iter().map(|slot| slot.name.as_str()).collect();
+        assert_eq!(names, ["VERB", "OBJ", "SUBJ"]);
+
+        // 'thinks' also selects a DP but wants a specifier too, so it does not fit
+        let generated = generate(&skeleton, &lexicon(), &english(), usize::MAX);
+        assert_eq!(generated.len(), 16);
+        assert!(generated.iter().all(|g| g.fillers[0] == ("VERB".to_string(), "watch".to_string())));
+        assert_eq!(generated[0].sentence, "the student watches the student");
+    }
+
+    #[test]
+    fn test_agreement_follows_the_subject() {
+        let skeleton = transitive(Skeleton::slot_with_phi("SUBJ", &[Feature::Cat(Category::N)], Phi::plural(3)));
+        let generated = generate(&skeleton, &lexicon(), &english(), usize::MAX);
+        let sentences: Vec<&str> = generated.iter().map(|g| g.sentence.as_str()).collect();
+        assert_eq!(sentences, [
+            "the students watch the student",
+            "the students watch the tutor",
+            "the students watch the teacher",
+            "the students watch the students",
+        ]);
+    }
+
+    #[test]
+    fn test_limit_and_convergence() {
+        let skeleton = transitive(Skeleton::slot("SUBJ", &[Feature::Cat(Category::N)]));
+        assert_eq!(generate(&skeleton, &lexicon(), &english(), 3).len(), 3);
+
+        // A bare noun cannot fill the D= specifier, so nothing converges
+        let intransitive = Skeleton::word("-pres")
+            .merge(Skeleton::slot("VERB", &[Feature::Cat(Category::V)]))
+            .merge(Skeleton::slot("SUBJ", &[Feature::Cat(Category::N)]));
+        assert!(generate(&intransitive, &lexicon(), &english(), usize::MAX).is_empty());
+        let sentence = Skeleton::word("-pres")
+            .merge(Skeleton::slot("VERB", &[Feature::Cat(Category::V)]))
+            .merge(Skeleton::word("the").merge(Skeleton::word("students")));
+        let generated = generate(&sentence, &lexicon(), &english(), usize::MAX);
+        assert_eq!(generated[0].sentence, "the students leave");
+    }
+}
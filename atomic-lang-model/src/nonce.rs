@@ -0,0 +1,133 @@
+//! Nonce-Word Category Inference
+//!
+//! Berko's (1958) wug test shows that speakers assign a novel word a
+//! category from its context alone: "this is a wug" makes "wug" a noun.
+//! `infer_nonce` does the same for a sentence with one unknown word. Every
+//! feature bundle the lexicon attests is tried as an entry for the word, and
+//! the bundles under which the sentence parses are returned as candidate
+//! entries, most economical derivation first. Among equally economical
+//! candidates, bundles shared by more words come first, since a new word is
+//! more likely to join a large class than a small one.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::{lookup, parse_forest, Feature, FewestOperations, GrammarOptions, LexItem};
+
+/// Why a sentence cannot be used to infer a nonce word's entry
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonceError {
+    /// Every word is in the lexicon
+    NoUnknownWord,
+    /// More than one distinct word is missing from the lexicon
+    SeveralUnknownWords(Vec<String>),
+}
+
+impl fmt::Display for NonceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NonceError::NoUnknownWord => write!(f, "every word is in the lexicon"),
+            NonceError::SeveralUnknownWords(words) => write!(f, "several unknown words: {}", words.join(", ")),
+        }
+    }
+}
+
+/// Entry under which a sentence with a nonce word parses
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonceCandidate {
+    /// The nonce word with an attested feature bundle
+    pub item: LexItem,
+    /// Operations in the most economical derivation
+    pub operations: usize,
+    /// Analyses of the sentence with this entry
+    pub analyses: usize,
+    /// Lexicon entries with the same bundle
+    pub attested: usize,
+}
+
+/// Infer entries for the one unknown word of `sentence`, best first
+///
+/// Returns an empty list when no attested bundle lets the sentence parse.
+/// The word may occur more than once; every occurrence gets the same entry.
+pub fn infer_nonce(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+) -> Result<Vec<NonceCandidate>, NonceError> {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let mut unknown: Vec<String> = Vec::new();
+    for token in normalized.split_whitespace() {
+        if lookup(lexicon, token).is_empty() && !unknown.iter().any(|word| word == token) {
+            unknown.push(token.to_string());
+        }
+    }
+    let word = match unknown.as_slice() {
+        [] => return Err(NonceError::NoUnknownWord),
+        [word] => word.clone(),
+        _ => return Err(NonceError::SeveralUnknownWords(unknown)),
+    };
+
+    // Distinct bundles in lexicon order, with the number of entries bearing each
+    let mut bundles: Vec<(&[Feature], usize)> = Vec::new();
+    for item in lexicon {
+        match bundles.iter_mut().find(|(feats, _)| *feats == item.feats.as_slice()) {
+            Some((_, count)) => *count += 1,
+            None => bundles.push((&item.feats, 1)),
+        }
+    }
+
+    let mut extended = lexicon.to_vec();
+    extended.push(LexItem::new(&word, &[]));
+    let mut candidates: Vec<NonceCandidate> = bundles.into_iter()
+        .filter_map(|(feats, attested)| {
+            let item = LexItem::new(&word, feats);
+            *extended.last_mut()? = item.clone();
+            let parses = parse_forest(&normalized, &extended, options, &FewestOperations).ok()?;
+            let operations = parses.first()?.derivation.len();
+            Some(NonceCandidate { item, operations, analyses: parses.len(), attested })
+        })
+        .collect();
+    // Stable, so ties keep lexicon order
+    candidates.sort_by_key(|candidate| (candidate.operations, core::cmp::Reverse(candidate.attested)));
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category};
+
+    #[test]
+    fn test_wug_is_a_noun() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+
+        let candidates = infer_nonce("the wug left", &lexicon, &options).unwrap();
+        assert!(!candidates.is_empty());
+        assert_eq!(candidates[0].item.phon, "wug");
+        assert_eq!(candidates[0].item.feats, [Feature::Cat(Category::N)]);
+        assert!(candidates.windows(2).all(|pair| pair[0].operations <= pair[1].operations));
+
+        // Same frame, different slot
+        let candidates = infer_nonce("the student wugged", &lexicon, &options).unwrap();
+        assert!(candidates[0].item.feats.contains(&Feature::Cat(Category::V)));
+        assert!(candidates.iter().all(|candidate| !candidate.item.feats.contains(&Feature::Cat(Category::N))));
+    }
+
+    #[test]
+    fn test_needs_exactly_one_unknown_word() {
+        let lexicon = test_lexicon();
+        let options = GrammarOptions::default();
+        assert_eq!(infer_nonce("the student left", &lexicon, &options), Err(NonceError::NoUnknownWord));
+        assert_eq!(
+            infer_nonce("the wug daxed", &lexicon, &options),
+            Err(NonceError::SeveralUnknownWords(Vec::from(["wug".to_string(), "daxed".to_string()])))
+        );
+        // Repeated occurrences are one word
+        assert!(infer_nonce("the wug thinks the wug", &lexicon, &options).is_ok());
+        // No bundle helps a sentence that fails for other reasons
+        assert_eq!(infer_nonce("the the wug", &lexicon, &options), Ok(Vec::new()));
+    }
+}
@@ -0,0 +1,250 @@
+//! Text Normalization
+//!
+//! The parser matches whitespace-separated tokens against the lexicon
+//! verbatim, so `The student left.` fails on `The` and `left.`. A
+//! `Normalizer` runs before tokenization and rewrites raw web or ASR text
+//! into the lexicon's spelling: Unicode composition (NFC), typographic
+//! quotes to ASCII, punctuation stripped from word edges, digits spelled
+//! out, and a lowercasing policy. Every stage is off by default, and a
+//! normalizer in `GrammarOptions` applies to every parse.
+
+#[cfg(not(feature = "std"))]
+use alloc::{borrow::Cow, string::{String, ToString}, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+use crate::LexItem;
+
+/// Lowercasing policy
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Case {
+    /// Leave words as written
+    #[default]
+    Preserve,
+    /// Lowercase every word
+    Lower,
+    /// Lowercase a word only if the lexicon lists its lowercase form but
+    /// not the word as written: `The` becomes `the`, `Mary` and `I` stay
+    Lexicon,
+}
+
+/// Configurable pre-tokenization normalizer
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    /// Lowercasing policy
+    pub case: Case,
+    /// Spell out digit strings (`21` → `twenty-one`, `1,000` → `one thousand`)
+    pub numbers: bool,
+    /// Compose base letters and combining marks (Unicode NFC, Latin letters)
+    pub nfc: bool,
+    /// Map typographic quotes to ASCII `'` and `"`
+    pub quotes: bool,
+    /// Strip punctuation from the edges of words, dropping tokens that are
+    /// only punctuation; a leading hyphen marks an affix and stays
+    pub punctuation: bool,
+}
+
+impl Normalizer {
+    /// Every stage on, lowercasing by the lexicon: for raw web and ASR text
+    pub fn raw_text() -> Self {
+        Self { case: Case::Lexicon, numbers: true, nfc: true, quotes: true, punctuation: true }
+    }
+
+    /// Normalize text for tokenization, borrowing it if no stage is on
+    pub fn apply<'s>(&self, text: &'s str, lexicon: &[LexItem]) -> Cow<'s, str> {
+        if *self == Self::default() {
+            return Cow::Borrowed(text);
+        }
+        let mut text = Cow::Borrowed(text);
+        if self.nfc {
+            text = Cow::Owned(to_nfc(&text));
+        }
+        if self.quotes {
+            text = Cow::Owned(text.chars().map(ascii_quote).collect());
+        }
+
+        let words: Vec<String> = text.split_whitespace()
+            .filter_map(|token| {
+                let token = if self.punctuation { strip_punctuation(token) } else { token };
+                let word = match parse_number(token) {
+                    Some(n) if self.numbers => number_words(n),
+                    _ => token.to_string(),
+                };
+                (!word.is_empty()).then(|| self.recase(word, lexicon))
+            })
+            .collect();
+        Cow::Owned(words.join(" "))
+    }
+
+    fn recase(&self, word: String, lexicon: &[LexItem]) -> String {
+        let listed = |word: &str| lexicon.iter().any(|item| item.phon == word);
+        match self.case {
+            Case::Preserve => word,
+            Case::Lower => word.to_lowercase(),
+            Case::Lexicon => {
+                let lower = word.to_lowercase();
+                if lower != word && !listed(&word) && listed(&lower) { lower } else { word }
+            }
+        }
+    }
+}
+
+/// Precomposed Latin letters by combining mark: bases and their
+/// compositions, position for position
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    ('\u{0300}', "AEIOUaeiou", "ÀÈÌÒÙàèìòù"), // grave
+    ('\u{0301}', "AEIOUYaeiouyCcLlNnRrSsZz", "ÁÉÍÓÚÝáéíóúýĆćĹĺŃńŔŕŚśŹź"), // acute
+    ('\u{0302}', "AEIOUaeiouCcGgHhJjSsWwYy", "ÂÊÎÔÛâêîôûĈĉĜĝĤĥĴĵŜŝŴŵŶŷ"), // circumflex
+    ('\u{0303}', "ANOanoIiUu", "ÃÑÕãñõĨĩŨũ"), // tilde
+    ('\u{0304}', "AaEeIiOoUu", "ĀāĒēĪīŌōŪū"), // macron
+    ('\u{0306}', "AaEeGgIiOoUu", "ĂăĔĕĞğĬĭŎŏŬŭ"), // breve
+    ('\u{0307}', "CcEeGgIZz", "ĊċĖėĠġİŻż"), // dot above
+    ('\u{0308}', "AEIOUaeiouyY", "ÄËÏÖÜäëïöüÿŸ"), // diaeresis
+    ('\u{030a}', "AaUu", "ÅåŮů"), // ring above
+    ('\u{030b}', "OoUu", "ŐőŰű"), // double acute
+    ('\u{030c}', "CcDdEeLlNnRrSsTtZz", "ČčĎďĚěĽľŇňŘřŠšŤťŽž"), // caron
+    ('\u{0327}', "CcGgKkLlNnRrSsTt", "ÇçĢģĶķĻļŅņŖŗŞşŢţ"), // cedilla
+    ('\u{0328}', "AaEeIiUu", "ĄąĘęĮįŲų"), // ogonek
+];
+
+/// NFC for the Latin letters in `COMPOSITIONS`: compose each base letter
+/// with a following combining mark where a precomposed letter exists
+fn to_nfc(text: &str) -> String {
+    let mut composed = String::with_capacity(text.len());
+    for c in text.chars() {
+        let precomposed = composed.chars().last().and_then(|base| {
+            let (_, bases, forms) = COMPOSITIONS.iter().find(|(mark, _, _)| *mark == c)?;
+            let i = bases.chars().position(|b| b == base)?;
+            forms.chars().nth(i)
+        });
+        match precomposed {
+            Some(letter) => {
+                composed.pop();
+                composed.push(letter);
+            }
+            None => composed.push(c),
+        }
+    }
+    composed
+}
+
+fn ascii_quote(c: char) -> char {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => '\'',
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' | '\u{00AB}' | '\u{00BB}' => '"',
+        _ => c,
+    }
+}
+
+fn strip_punctuation(token: &str) -> &str {
+    let is_punctuation = |c: char| c.is_ascii_punctuation() || matches!(c, '…' | '–' | '—');
+    token.trim_end_matches(is_punctuation).trim_start_matches(|c| c != '-' && is_punctuation(c))
+}
+
+/// Digits, optionally grouped by commas in threes
+fn parse_number(token: &str) -> Option<u64> {
+    let mut groups = token.split(',');
+    let first = groups.next()?;
+    let grouped = groups.all(|group| group.len() == 3 && group.bytes().all(|b| b.is_ascii_digit()));
+    let digits = first.bytes().all(|b| b.is_ascii_digit()) && !first.is_empty();
+    if !(digits && grouped) || (token.contains(',') && first.len() > 3) {
+        return None;
+    }
+    token.replace(',', "").parse().ok()
+}
+
+/// English cardinal, without `and`: `105` is `one hundred five`
+fn number_words(n: u64) -> String {
+    const ONES: [&str; 20] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+    ];
+    const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+    const SCALES: [&str; 7] = ["", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"];
+
+    let below_thousand = |n: u64| {
+        let mut words = Vec::new();
+        if n >= 100 {
+            words.push(ONES[(n / 100) as usize].to_string());
+            words.push("hundred".to_string());
+        }
+        match n % 100 {
+            0 => {}
+            rest @ 1..=19 => words.push(ONES[rest as usize].to_string()),
+            rest if rest % 10 == 0 => words.push(TENS[(rest / 10) as usize].to_string()),
+            rest => words.push([TENS[(rest / 10) as usize], ONES[(rest % 10) as usize]].join("-")),
+        }
+        words
+    };
+
+    if n == 0 {
+        return ONES[0].to_string();
+    }
+    let mut words = Vec::new();
+    let mut rest = n;
+    for scale in SCALES {
+        let group = rest % 1000;
+        if group > 0 {
+            let mut group_words = below_thousand(group);
+            if !scale.is_empty() {
+                group_words.push(scale.to_string());
+            }
+            words.splice(0..0, group_words);
+        }
+        rest /= 1000;
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_with_options, test_lexicon, GrammarOptions};
+
+    #[test]
+    fn test_numbers() {
+        let spelled = |n| number_words(n);
+        assert_eq!(spelled(0), "zero");
+        assert_eq!(spelled(21), "twenty-one");
+        assert_eq!(spelled(105), "one hundred five");
+        assert_eq!(spelled(1_000_000), "one million");
+        assert_eq!(spelled(2_019), "two thousand nineteen");
+        assert_eq!(parse_number("1,000"), Some(1000));
+        assert_eq!(parse_number("10,00"), None);
+        assert_eq!(parse_number("1234,567"), None);
+        assert_eq!(parse_number("3rd"), None);
+    }
+
+    #[test]
+    fn test_stages() {
+        let lexicon = test_lexicon();
+        let only = |normalizer: Normalizer, text: &str| normalizer.apply(text, &lexicon).into_owned();
+
+        assert_eq!(only(Normalizer { nfc: true, ..Default::default() }, "cafe\u{301} nai\u{308}ve"), "café naïve");
+        assert_eq!(only(Normalizer { quotes: true, ..Default::default() }, "\u{201C}can\u{2019}t\u{201D}"), "\"can't\"");
+        assert_eq!(only(Normalizer { punctuation: true, ..Default::default() }, "(the) student , -ed left..."), "the student -ed left");
+        assert_eq!(only(Normalizer { numbers: true, ..Default::default() }, "at 7 or 1,500"), "at seven or one thousand five hundred");
+        assert_eq!(only(Normalizer { case: Case::Lower, ..Default::default() }, "The Student"), "the student");
+
+        // Words the lexicon lists as written keep their case
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("Mary", &[]));
+        let normalizer = Normalizer { case: Case::Lexicon, ..Default::default() };
+        assert_eq!(normalizer.apply("The Student Mary Robot", &lexicon), "the student Mary Robot");
+
+        assert!(matches!(Normalizer::default().apply("The  student", &lexicon), Cow::Borrowed("The  student")));
+    }
+
+    #[test]
+    fn test_raw_text_parses() {
+        let mut options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let raw = "\u{201C}The student left.\u{201D}";
+        assert!(parse_with_options(raw, &lexicon, &options).is_err());
+
+        options.normalizer = Normalizer::raw_text();
+        let tree = parse_with_options(raw, &lexicon, &options).unwrap();
+        assert_eq!(tree.linearize(), "the student left");
+    }
+}
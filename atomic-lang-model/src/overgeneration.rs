@@ -0,0 +1,382 @@
+//! Overgeneration Audit
+//!
+//! Coverage reports what a grammar fails to derive; this is the complement,
+//! what it derives but should not. The language the grammar generates up
+//! to `n` words is listed exhaustively, and each output is checked against
+//! a reference judgment file. The file has one sentence per line, a
+//! leading `*` marking it ungrammatical as in `synthesize` output; blank
+//! lines and lines starting with `#` are skipped. Outputs the file marks
+//! ungrammatical are overgenerations; outputs it does not list are
+//! reported separately for a linguist to judge. `enumerate_language`
+//! produces the same strings one at a time, for browsing the language
+//! while debugging a grammar.
+//!
+//! Rather than parse every string of the vocabulary up to `n` words, the
+//! language is generated bottom-up: constituents are built from the
+//! lexicon with the chart parser's rules, pronouncing words where a chart
+//! item has a span, shortest first and none longer than `n` words. Only
+//! the strings of complete constituents are parsed, to apply the
+//! grammar's constraints. The cost grows with the number of distinct
+//! constituents up to `n` words, which recursion still makes exponential,
+//! but not with the number of strings the grammar rules out.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::parser::Deduction;
+use crate::{parse_batch, GrammarOptions, LexItem, WorkspacePool};
+
+/// Reference judgments, sentences normalized to single spaces
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Judgments {
+    /// Sentences judged grammatical
+    pub grammatical: Vec<String>,
+    /// Sentences judged ungrammatical
+    pub ungrammatical: Vec<String>,
+}
+
+impl Judgments {
+    /// Read judgments, one sentence per line, `*` marking the ungrammatical
+    pub fn parse(text: &str) -> Self {
+        let mut judgments = Self::default();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            match line.strip_prefix('*') {
+                Some(sentence) => judgments.ungrammatical.push(words(sentence)),
+                None => judgments.grammatical.push(words(line)),
+            }
+        }
+        judgments
+    }
+}
+
+fn words(sentence: &str) -> String {
+    sentence.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Grammar outputs up to a length, checked against judgments
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OvergenerationReport {
+    /// Longest string tried, in words
+    pub max_length: usize,
+    /// Strings tried
+    pub candidates: usize,
+    /// Strings the grammar derives, shortest first, then in vocabulary order
+    pub generated: Vec<String>,
+    /// Outputs judged ungrammatical
+    pub overgenerated: Vec<String>,
+    /// Outputs judged grammatical
+    pub confirmed: usize,
+    /// Outputs the judgments do not mention
+    pub unjudged: Vec<String>,
+}
+
+impl OvergenerationReport {
+    /// Share of judged outputs that are ungrammatical
+    pub fn overgeneration_rate(&self) -> f64 {
+        match self.overgenerated.len() + self.confirmed {
+            0 => 0.0,
+            judged => self.overgenerated.len() as f64 / judged as f64,
+        }
+    }
+}
+
+impl fmt::Display for OvergenerationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "generated {} of {} strings up to {} words",
+            self.generated.len(),
+            self.candidates,
+            self.max_length
+        )?;
+        writeln!(
+            f,
+            "overgenerated {}/{} judged outputs ({:.1}%)",
+            self.overgenerated.len(),
+            self.overgenerated.len() + self.confirmed,
+            self.overgeneration_rate() * 100.0
+        )?;
+        for sentence in &self.overgenerated {
+            writeln!(f, "  *{}", sentence)?;
+        }
+        if !self.unjudged.is_empty() {
+            writeln!(f, "unjudged outputs:")?;
+            for sentence in &self.unjudged {
+                writeln!(f, "   {}", sentence)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every string of 1 to `max_length` words the grammar derives, shortest
+/// first, with the number of candidate strings parsed: what `Language`
+/// produces, collected
+///
+/// Strings of the same length come in vocabulary order (words ordered as
+/// the lexicon first lists them), the last word varying fastest.
+pub fn generate_up_to(lexicon: &[LexItem], options: &GrammarOptions, max_length: usize) -> (Vec<String>, usize) {
+    let mut language = Language::new(lexicon, options.clone(), max_length);
+    let generated = language.by_ref().collect();
+    (generated, language.candidates())
+}
+
+/// Every string of 1 to `max_len` words the lexicon derives under the
+/// default grammar options, produced lazily in `generate_up_to` order
+///
+/// A word with several entries is one word of the vocabulary, so no
+/// string comes up twice.
+pub fn enumerate_language(lexicon: &[LexItem], max_len: usize) -> Language<'_> {
+    Language::new(lexicon, GrammarOptions::default(), max_len)
+}
+
+/// Constituents of up to a bounded number of words, built bottom-up with
+/// the chart's rules (see `parser`) covering words instead of spans, one
+/// length at a time
+struct Generator<'a> {
+    lexicon: &'a [LexItem],
+    movement: bool,
+    /// Constituents built, each once
+    deduction: Deduction<'a, Vec<usize>>,
+    /// Index of the first constituent of each length built so far
+    layers: Vec<usize>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(lexicon: &'a [LexItem], movement: bool) -> Self {
+        Self { lexicon, movement, deduction: Deduction::new(lexicon), layers: Vec::new() }
+    }
+
+    fn layer(&self, length: usize) -> Range<usize> {
+        self.layers[length]..self.layers.get(length + 1).copied().unwrap_or(self.deduction.items.len())
+    }
+
+    /// Build every constituent of the next length, and return the word
+    /// sequences of the complete ones
+    fn grow(&mut self, vocabulary: &[&str]) -> Vec<Vec<usize>> {
+        let length = self.layers.len();
+        let first = self.deduction.items.len();
+        self.layers.push(first);
+        if length <= 1 {
+            for (index, item) in self.lexicon.iter().enumerate() {
+                let words: Vec<usize> = vocabulary.iter().position(|word| *word == item.phon).into_iter().collect();
+                if words.len() == length {
+                    self.deduction.lexical(index, words);
+                }
+            }
+        }
+        // Longer constituents are built from two shorter pronounced ones
+        for left in 1..length {
+            for a in self.layer(left) {
+                for b in self.layer(length - left) {
+                    self.deduction.combine(a, b);
+                }
+            }
+        }
+        // and from one of this length by rules adding no words
+        let silent: Vec<usize> = (0..self.lexicon.len()).filter(|&i| self.lexicon[i].phon.is_empty()).collect();
+        let mut next = first;
+        while next < self.deduction.items.len() {
+            let x = next;
+            next += 1;
+            for &head in &silent {
+                self.deduction.merge_empty(head, x);
+            }
+            if self.movement {
+                self.deduction.move_within(x);
+            }
+            let others = if length == 0 { first..x } else { self.layer(0) };
+            for y in others {
+                self.deduction.combine(x, y);
+                self.deduction.combine(y, x);
+            }
+        }
+
+        let mut complete: Vec<Vec<usize>> = self.deduction.items[first..].iter()
+            .filter(|c| c.movers.is_empty() && !c.covers.is_empty() && c.signature().is_complete())
+            .map(|c| c.covers.clone())
+            .collect();
+        complete.sort();
+        complete.dedup();
+        complete
+    }
+}
+
+/// Strings of a bounded length a grammar derives
+///
+/// Constituents are generated bottom-up from the lexicon, one length at a
+/// time, so only strings some derivation yields are ever considered. Each
+/// is then parsed, which applies the grammar's constraints and confirms
+/// the word order, before it is produced.
+pub struct Language<'a> {
+    lexicon: &'a [LexItem],
+    options: GrammarOptions,
+    vocabulary: Vec<&'a str>,
+    max_length: usize,
+    generator: Generator<'a>,
+    /// Candidates of the current length still to parse, last first
+    pending: Vec<Vec<usize>>,
+    candidates: usize,
+    pool: WorkspacePool,
+}
+
+impl<'a> Language<'a> {
+    /// Enumeration of the strings of 1 to `max_length` words `lexicon` derives
+    pub fn new(lexicon: &'a [LexItem], options: GrammarOptions, max_length: usize) -> Self {
+        let mut vocabulary: Vec<&str> = Vec::new();
+        for item in lexicon.iter().filter(|item| !item.phon.is_empty()) {
+            if !vocabulary.contains(&item.phon.as_str()) {
+                vocabulary.push(&item.phon);
+            }
+        }
+        let generator = Generator::new(lexicon, options.operations.movement);
+        Self { lexicon, options, vocabulary, max_length, generator, pending: Vec::new(), candidates: 0, pool: WorkspacePool::new() }
+    }
+
+    /// Candidate strings parsed so far
+    pub fn candidates(&self) -> usize {
+        self.candidates
+    }
+}
+
+impl Iterator for Language<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            let Some(words) = self.pending.pop() else {
+                // Silent constituents come first, then one length at a time
+                if self.generator.layers.is_empty() {
+                    self.generator.grow(&self.vocabulary);
+                }
+                if self.generator.layers.len() > self.max_length {
+                    return None;
+                }
+                self.pending = self.generator.grow(&self.vocabulary);
+                self.pending.reverse();
+                continue;
+            };
+            let string = words.iter().map(|&i| self.vocabulary[i]).collect::<Vec<_>>().join(" ");
+            self.candidates += 1;
+            let result = parse_batch([string.as_str()], self.lexicon, &self.options, &mut self.pool).remove(0);
+            if result.is_ok() {
+                return Some(string);
+            }
+        }
+    }
+}
+
+/// Audit the grammar's outputs up to `max_length` words against `judgments`,
+/// judging each as `Language` produces it
+pub fn overgeneration(
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    max_length: usize,
+    judgments: &Judgments,
+) -> OvergenerationReport {
+    let mut language = Language::new(lexicon, options.clone(), max_length);
+    let mut report = OvergenerationReport { max_length, ..Default::default() };
+    for sentence in language.by_ref() {
+        if judgments.ungrammatical.contains(&sentence) {
+            report.overgenerated.push(sentence.clone());
+        } else if judgments.grammatical.contains(&sentence) {
+            report.confirmed += 1;
+        } else {
+            report.unjudged.push(sentence.clone());
+        }
+        report.generated.push(sentence);
+    }
+    report.candidates = language.candidates();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, Category, Feature};
+
+    const JUDGMENTS: &str = "# intransitives\n\
+                             the student left\n\
+                             the   tutor left\n\
+                             \n\
+                             *the student thinks the student\n\
+                             *a student left\n";
+
+    #[test]
+    fn test_outputs_are_exhaustive() {
+        let lexicon = test_lexicon();
+        let (generated, candidates) = generate_up_to(&lexicon, &GrammarOptions::default(), 3);
+        // Only strings some derivation yields are parsed, not all 12^3 + 12^2 + 12
+        assert_eq!(candidates, generated.len());
+        // Bare nouns and DPs are complete objects too; two determiners,
+        // three nouns, three intransitive verbs
+        assert_eq!(generated.len(), 3 + 2 * 3 + 2 * 3 * 3);
+        assert_eq!(generated[..4], ["student", "tutor", "teacher", "the student"]);
+        assert!(generated.contains(&"a teacher arrived".to_string()));
+        assert!(!generated.contains(&"the student thinks".to_string()));
+    }
+
+    #[test]
+    fn test_language_is_lazy_and_duplicate_free() {
+        let mut lexicon = test_lexicon();
+        // A second entry for a word, deriving 'left' on its own as well
+        lexicon.push(LexItem::new("left", &[Feature::Cat(Category::V)]));
+
+        let mut language = enumerate_language(&lexicon, 4);
+        assert_eq!(language.by_ref().take(4).collect::<Vec<_>>(), ["student", "tutor", "teacher", "left"]);
+        assert_eq!(language.candidates(), 4);
+
+        let strings: Vec<String> = enumerate_language(&lexicon, 3).collect();
+        let mut unique = strings.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), strings.len());
+        assert!(strings.iter().all(|string| string.split(' ').count() <= 3));
+        assert!(strings.contains(&"the student left".to_string()));
+        assert_eq!(enumerate_language(&lexicon, 0).next(), None);
+    }
+
+    /// Every string of 1 to `max_length` vocabulary words that parses
+    fn brute_force(lexicon: &[LexItem], options: &GrammarOptions, max_length: usize) -> Vec<String> {
+        let vocabulary = Language::new(lexicon, options.clone(), 0).vocabulary;
+        let mut strings: Vec<Vec<&str>> = vec![Vec::new()];
+        let mut derived = Vec::new();
+        for _ in 0..max_length {
+            strings = strings.iter()
+                .flat_map(|string| vocabulary.iter().map(move |word| [&string[..], &[*word]].concat()))
+                .collect();
+            derived.extend(strings.iter()
+                .map(|string| string.join(" "))
+                .filter(|string| crate::parse_with_options(string, lexicon, options).is_ok()));
+        }
+        derived
+    }
+
+    #[test]
+    fn test_generation_matches_filtering_every_string() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let (generated, candidates) = generate_up_to(&lexicon, &options, 3);
+        assert_eq!(generated, brute_force(&lexicon, &options, 3));
+        assert_eq!(candidates, generated.len());
+        // Relative clauses need movement
+        assert!(generated.contains(&"student who left".to_string()));
+    }
+
+    #[test]
+    fn test_judged_outputs() {
+        let judgments = Judgments::parse(JUDGMENTS);
+        assert_eq!(judgments.grammatical, ["the student left", "the tutor left"]);
+        assert_eq!(judgments.ungrammatical.len(), 2);
+
+        let report = overgeneration(&test_lexicon(), &GrammarOptions::default(), 3, &judgments);
+        assert_eq!(report.overgenerated, ["a student left"]);
+        assert_eq!(report.confirmed, 2);
+        assert_eq!(report.unjudged.len(), report.generated.len() - 3);
+        assert_eq!(report.overgeneration_rate(), 1.0 / 3.0);
+        assert!(report.to_string().starts_with("generated 27 of 27 strings up to 3 words\noverge"));
+    }
+}
@@ -0,0 +1,294 @@
+//! Paraphrase Generation
+//!
+//! Alternations relate derivations that assign the same arguments to the
+//! same predicate under a different feature specification: a transitive
+//! verb `V =D D=` has a passive participle `V =P`, a double-object verb
+//! `V =D =D D=` a prepositional frame `V =D =P D=`. Given a parse, the
+//! clause's head and arguments are read off its spine and re-derived with
+//! Merge (and Move, for topicalization) around the alternative entry.
+//!
+//! A variant is licensed when every item it uses, the alternative entry
+//! included, is listed in the lexicon and the result passes the grammar's
+//! constraints. Variants are built here rather than recovered by parsing
+//! their strings, so each is tied to the entry it alternates on.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use core::fmt;
+
+use crate::{merge, move_operation, parse_with_options, Category, DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Movement index for topics (`+top` / `-top`)
+pub const TOP: u8 = 7;
+
+/// Meaning-preserving alternation between feature specifications
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Alternation {
+    /// Active ↔ passive: `the student praised the teacher` ↔ `the teacher
+    /// was praised by the student`
+    Passive {
+        /// Auxiliary heading the passive clause (`V =V D=`)
+        auxiliary: String,
+        /// Preposition introducing the agent (`P =D`)
+        agent_marker: String,
+    },
+    /// Double object ↔ prepositional dative: `gave the student a book` ↔
+    /// `gave a book to the student`
+    Dative {
+        /// Preposition introducing the goal (`P =D`)
+        preposition: String,
+    },
+    /// Object fronted to the specifier of a silent `C =V +top`: `the
+    /// teacher the student praised`
+    Topicalization,
+}
+
+impl Alternation {
+    /// Passive with the given auxiliary and agent preposition
+    pub fn passive(auxiliary: &str, agent_marker: &str) -> Self {
+        Alternation::Passive { auxiliary: auxiliary.into(), agent_marker: agent_marker.into() }
+    }
+
+    /// Dative with the given goal preposition
+    pub fn dative(preposition: &str) -> Self {
+        Alternation::Dative { preposition: preposition.into() }
+    }
+
+    /// Re-derive the clause under the alternation, if it applies
+    fn apply(&self, tree: &SyntacticObject, lexicon: &[LexItem]) -> Option<SyntacticObject> {
+        let clause = Clause::of(tree)?;
+        let verb = clause.head.phon.as_deref()?;
+        let args = &clause.arguments;
+        let d = || Feature::Sel(Category::D);
+        let p = || Feature::Sel(Category::P);
+        let spec_d = || Feature::Spec(Category::D);
+
+        match self {
+            Alternation::Passive { auxiliary, agent_marker } if verb == auxiliary => {
+                // Passive to active: the subject is the theme, the by-phrase the agent
+                let participle = Clause::of(args.first()?)?;
+                let by_phrase = Clause::of(participle.arguments.first()?)?;
+                if by_phrase.head.phon.as_deref() != Some(agent_marker) {
+                    return None;
+                }
+                let active = entry(lexicon, participle.head.phon.as_deref()?, &[d(), spec_d()])?;
+                derive(active, [*args.get(1)?, *by_phrase.arguments.first()?])
+            }
+            Alternation::Passive { auxiliary, agent_marker } if clause.has_frame(&[d(), spec_d()]) => {
+                let participle = entry(lexicon, verb, &[p()])?;
+                let by = entry(lexicon, agent_marker, &[d()])?;
+                let aux = entry(lexicon, auxiliary, &[Feature::Sel(Category::V), spec_d()])?;
+                let by_phrase = derive(by, [args[1]])?;
+                let participle = derive(participle, [&by_phrase])?;
+                derive(aux, [&participle, args[0]])
+            }
+            Alternation::Dative { preposition } if clause.has_frame(&[d(), d(), spec_d()]) => {
+                let prepositional = entry(lexicon, verb, &[d(), p(), spec_d()])?;
+                let to = entry(lexicon, preposition, &[d()])?;
+                let goal = derive(to, [args[0]])?;
+                derive(prepositional, [args[1], &goal, args[2]])
+            }
+            Alternation::Dative { preposition } if clause.has_frame(&[d(), p(), spec_d()]) => {
+                let goal = Clause::of(args[1])?;
+                if goal.head.phon.as_deref() != Some(preposition) {
+                    return None;
+                }
+                let double = entry(lexicon, verb, &[d(), d(), spec_d()])?;
+                derive(double, [*goal.arguments.first()?, args[0], args[2]])
+            }
+            Alternation::Topicalization if tree.label.matches(&Category::V) && args.len() >= 2 => {
+                if !matches!(clause.head.next_selector(), Some(Feature::Sel(cat)) if cat.matches(&Category::D)) {
+                    return None;
+                }
+                let c = lexicon.iter()
+                    .find(|item| item.phon.is_empty() && item.feats == [Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(TOP)])
+                    .map(SyntacticObject::from_lex)?;
+                let mut topic = args[0].clone();
+                topic.features.push(Feature::Neg(TOP));
+                let vp = derive(clause.head.clone(), core::iter::once(&topic).chain(args[1..].iter().copied()))?;
+                move_operation(merge(c, vp).ok()?).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Alternation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Alternation::Passive { .. } => write!(f, "passive"),
+            Alternation::Dative { .. } => write!(f, "dative"),
+            Alternation::Topicalization => write!(f, "topicalization"),
+        }
+    }
+}
+
+/// A licensed variant of a parse
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paraphrase {
+    /// Alternation that produced it
+    pub alternation: Alternation,
+    /// Pronounced variant
+    pub sentence: String,
+    /// Its derivation
+    pub tree: SyntacticObject,
+}
+
+/// A head with the arguments it selected, in merge order
+struct Clause<'t> {
+    head: &'t SyntacticObject,
+    arguments: Vec<&'t SyntacticObject>,
+}
+
+impl<'t> Clause<'t> {
+    /// Walk down the head spine, which must be built by Merge alone
+    fn of(tree: &'t SyntacticObject) -> Option<Self> {
+        let mut arguments = Vec::new();
+        let mut node = tree;
+        while !node.children.is_empty() {
+            let head = node.head_index()?;
+            if node.children.len() != 2 || node.children[head].next_selector().is_none() {
+                return None;
+            }
+            arguments.push(&node.children[1 - head]);
+            node = &node.children[head];
+        }
+        node.phon.as_ref()?;
+        arguments.reverse();
+        Some(Clause { head: node, arguments })
+    }
+
+    /// Whether the head is a verb selecting exactly these arguments
+    fn has_frame(&self, frame: &[Feature]) -> bool {
+        self.head.category().is_some_and(|cat| cat.matches(&Category::V)) && has_frame(&self.head.features, frame)
+    }
+}
+
+/// Whether the features past the category are these selectors, up to
+/// head/phrase level and ignoring agreement
+pub(crate) fn has_frame(features: &[Feature], frame: &[Feature]) -> bool {
+    let rest: Vec<&Feature> = features.iter().filter(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_))).collect();
+    rest.len() == frame.len() && rest.iter().zip(frame).all(|(f, g)| match (f, g) {
+        (Feature::Sel(a), Feature::Sel(b)) | (Feature::Spec(a), Feature::Spec(b)) => a.matches(b),
+        _ => *f == g,
+    })
+}
+
+/// The lexicon's entry for `phon` with the given selectors, at any position
+fn entry(lexicon: &[LexItem], phon: &str, frame: &[Feature]) -> Option<SyntacticObject> {
+    lexicon.iter()
+        .find(|item| item.phon == phon && has_frame(&item.feats, frame))
+        .map(SyntacticObject::from_lex)
+}
+
+/// Merge a head with its arguments in order
+fn derive<'a>(head: SyntacticObject, arguments: impl IntoIterator<Item = &'a SyntacticObject>) -> Option<SyntacticObject> {
+    arguments.into_iter().try_fold(head, |obj, argument| merge(obj, argument.clone()).ok())
+}
+
+/// Licensed variants of a parse under the given alternations
+pub fn paraphrases(
+    tree: &SyntacticObject,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    alternations: &[Alternation],
+) -> Vec<Paraphrase> {
+    let original = options.pronounce(tree).ok();
+    alternations.iter()
+        .filter_map(|alternation| {
+            let tree = alternation.apply(tree, lexicon)?;
+            options.check(&tree).ok()?;
+            let sentence = options.pronounce(&tree).ok()?;
+            (Some(&sentence) != original.as_ref()).then(|| Paraphrase { alternation: alternation.clone(), sentence, tree })
+        })
+        .collect()
+}
+
+/// Parse a sentence and return its licensed variants
+pub fn paraphrase(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    alternations: &[Alternation],
+) -> Result<Vec<Paraphrase>, DerivationError> {
+    parse_with_options(sentence, lexicon, options).map(|tree| paraphrases(&tree, lexicon, options, alternations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check_invariants, test_lexicon};
+
+    fn alternations() -> Vec<Alternation> {
+        vec![Alternation::passive("was", "by"), Alternation::dative("to"), Alternation::Topicalization]
+    }
+
+    /// Active entries first, so the parser reads active clauses
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        lexicon.extend([
+            LexItem::new("book", &[Feature::Cat(Category::N)]),
+            LexItem::new("praised", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("praised", &[Feature::Cat(Category::V), Feature::Sel(Category::P)]),
+            LexItem::new("was", &[Feature::Cat(Category::V), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("by", &[Feature::Cat(Category::P), Feature::Sel(Category::DP)]),
+            LexItem::new("gave", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("gave", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Sel(Category::P), Feature::Spec(Category::D)]),
+            LexItem::new("to", &[Feature::Cat(Category::P), Feature::Sel(Category::DP)]),
+            LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(TOP)]),
+        ]);
+        lexicon
+    }
+
+    fn sentences(paraphrases: &[Paraphrase]) -> Vec<(String, &str)> {
+        paraphrases.iter().map(|p| (p.alternation.to_string(), p.sentence.as_str())).collect()
+    }
+
+    #[test]
+    fn test_transitive_alternations() {
+        let options = GrammarOptions::default();
+        let variants = paraphrase("the student praised the teacher", &lexicon(), &options, &alternations()).unwrap();
+        assert_eq!(sentences(&variants), [
+            ("passive".into(), "the teacher was praised by the student"),
+            ("topicalization".into(), "the teacher the student praised"),
+        ]);
+        for variant in &variants {
+            assert!(variant.tree.is_complete());
+            assert!(check_invariants(&variant.tree).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_dative_alternates_both_ways() {
+        let options = GrammarOptions::default();
+        let lexicon = lexicon();
+        let variants = paraphrase("the student gave the teacher a book", &lexicon, &options, &[Alternation::dative("to")]).unwrap();
+        assert_eq!(sentences(&variants), [("dative".into(), "the student gave a book to the teacher")]);
+
+        let back = paraphrases(&variants[0].tree, &lexicon, &options, &[Alternation::dative("to")]);
+        assert_eq!(sentences(&back), [("dative".into(), "the student gave the teacher a book")]);
+    }
+
+    #[test]
+    fn test_passive_to_active() {
+        // Participle first, so the parser reads the passive
+        let mut lexicon = lexicon();
+        let participle = lexicon.iter().position(|item| item.phon == "praised" && item.feats.len() == 2).unwrap();
+        let item = lexicon.remove(participle);
+        lexicon.insert(0, item);
+
+        let options = GrammarOptions::default();
+        let variants = paraphrase("the teacher was praised by the student", &lexicon, &options, &alternations()).unwrap();
+        assert_eq!(sentences(&variants), [("passive".into(), "the student praised the teacher")]);
+    }
+
+    #[test]
+    fn test_unlicensed_alternations_are_skipped() {
+        // Without the passive and prepositional entries nothing alternates
+        let options = GrammarOptions::default();
+        let lexicon = test_lexicon();
+        assert_eq!(paraphrase("the student thinks the teacher", &lexicon, &options, &alternations()), Ok(Vec::new()));
+        assert_eq!(paraphrase("the student left", &lexicon, &options, &alternations()), Ok(Vec::new()));
+    }
+}
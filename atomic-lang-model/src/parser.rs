@@ -0,0 +1,856 @@
+//! Chart Parsing
+//!
+//! The workspace search explores derivations one operation sequence at a
+//! time, so it rebuilds the same constituent once for every order in which
+//! its parts could have been combined. `Chart` instead follows Harkema's
+//! (2001) CKY recognizer for Minimalist Grammars, run as agenda-driven
+//! deduction: an item is a constituent's label and unchecked features, the
+//! span of its pronounced material, and a span for each mover it contains
+//! that has yet to land. Items with the same description are built once and
+//! share their derivations, so the chart stays polynomial in the sentence
+//! length however many analyses it packs; trees are unpacked only for the
+//! items that cover the whole sentence.
+//!
+//! The rules mirror the workspace operations and build trees with the same
+//! `merge`, `adjoin` and `move_operation`:
+//!
+//! - a complement must start where its selector's span ends, a specifier
+//!   end where it starts, and an adjunct start where its host ends
+//! - a constituent with licensees of its own is merged in place wherever
+//!   it occurs, its span set aside as a mover until Move lands it at the
+//!   left edge of the constituent that attracts it
+//! - silent heads have no span and select anything adjacent to nothing
+//! - no two movers wait for the same licensee (the Shortest Move
+//!   Constraint), which the `FeatureSet` merge and adjunction checks
+//!   enforce as the compiled recognizer's `smc` does
+//!
+//! `parse_chart` returns every tree `parse_forest` finds, without the
+//! derivations, which are a property of the workspace search, and
+//! `parse_token_ids` does the same for a sentence given as `Vocabulary`
+//! token IDs.
+//!
+//! The packed chart also gives each constituent its marginal probability
+//! by the inside–outside algorithm, with derivations weighted like
+//! `derivation_probability` weighs trees: by the product of the weights
+//! of the lexical items they use. `Chart::marginals` maps each span to the
+//! labels of the constituents covering it with their probabilities, and
+//! `Chart::best` finds the most probable tree by the Viterbi algorithm:
+//! the same recursion, maximizing sums of log weights instead of summing
+//! products.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{
+    adjoin, discharge_selector, merge, move_operation, pass_features, remove_first, sentence_entries, Category, DerivationError, Feature,
+    FeatureSet, GrammarOptions, LexItem, SyntacticObject, Vocabulary,
+};
+
+/// Token positions `[start, end)` of pronounced material, `None` if silent
+type Span = Option<(usize, usize)>;
+
+/// What an item pronounces: its span of the input when parsing, or the
+/// words themselves when generating (see `overgeneration`)
+pub(crate) trait Coverage: Clone + Ord {
+    /// Coverage of silent material
+    fn silent() -> Self;
+    /// Coverage of `left` followed immediately by `right`, if they can be
+    /// joined
+    fn concatenate(left: &Self, right: &Self) -> Option<Self>;
+    /// Whether the two cover some of the same material
+    fn overlaps(&self, other: &Self) -> bool;
+}
+
+impl Coverage for Span {
+    fn silent() -> Self {
+        None
+    }
+
+    /// Joined only if they are adjacent
+    fn concatenate(left: &Self, right: &Self) -> Option<Self> {
+        match (*left, *right) {
+            (Some((start, mid)), Some((next, end))) if mid == next => Some(Some((start, end))),
+            (Some(_), Some(_)) => None,
+            (span, None) | (None, span) => Some(span),
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        matches!((self, other), (Some((start, end)), Some((other_start, other_end))) if start < other_end && other_start < end)
+    }
+}
+
+/// Vocabulary indices of the words pronounced, in order; any two word
+/// sequences can be joined
+impl Coverage for Vec<usize> {
+    fn silent() -> Self {
+        Vec::new()
+    }
+
+    fn concatenate(left: &Self, right: &Self) -> Option<Self> {
+        Some([&left[..], &right[..]].concat())
+    }
+
+    fn overlaps(&self, _: &Self) -> bool {
+        false
+    }
+}
+
+/// Constituent merged in place that has yet to reach its landing site
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct Mover<C> {
+    /// Licensees still to check
+    licensees: Vec<Feature>,
+    /// What the mover pronounces where it lands
+    covers: C,
+}
+
+/// Marginal probabilities by span `(start, end)`, with a probability for
+/// each label of a constituent covering the span
+pub type Marginals = BTreeMap<(usize, usize), Vec<(Category, f64)>>;
+
+/// How an item was built
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Step {
+    /// Lexical entry at a lexicon index
+    Lexical(usize),
+    /// Merge of a selector item with a selectee item
+    Merge(usize, usize),
+    /// Merge of the silent head at a lexicon index with an item
+    MergeEmpty(usize, usize),
+    /// Adjunction of an adjunct item to a host item
+    Adjoin(usize, usize),
+    /// Movement within an item
+    Move(usize),
+}
+
+/// Constituent description with every way of building it
+#[derive(Debug, Clone)]
+pub(crate) struct Item<C> {
+    label: Category,
+    features: Vec<Feature>,
+    /// What the item pronounces in place
+    pub(crate) covers: C,
+    pub(crate) movers: Vec<Mover<C>>,
+    steps: Vec<Step>,
+    /// Summary of `features` for the checks against every other item
+    set: FeatureSet,
+}
+
+impl<C: Coverage> Item<C> {
+    /// Childless object with the item's label and features, enough for
+    /// `merged` to compute a result's label and features
+    pub(crate) fn signature(&self) -> SyntacticObject {
+        SyntacticObject::internal(self.label.clone(), self.features.clone(), Vec::new())
+    }
+
+    /// Licensees that belong to the item itself rather than to a mover inside it
+    fn own_licensees(&self) -> Vec<Feature> {
+        let mut own: Vec<Feature> = self.features.iter().filter(|f| f.is_negative()).cloned().collect();
+        for licensee in self.movers.iter().flat_map(|mover| &mover.licensees) {
+            remove_first(&mut own, licensee);
+        }
+        own
+    }
+
+    /// Everything the item pronounces, in place and in its movers
+    fn coverage(&self) -> impl Iterator<Item = &C> + '_ {
+        core::iter::once(&self.covers).chain(self.movers.iter().map(|mover| &mover.covers))
+    }
+}
+
+/// Label, unchecked features, coverage and movers that identify an item
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Key<C> {
+    label: Category,
+    features: Vec<Feature>,
+    covers: C,
+    movers: Vec<Mover<C>>,
+}
+
+/// Items built by the chart's rules, each description once with every way
+/// of building it; the parser's chart covers spans, and the generator in
+/// `overgeneration` covers words
+#[derive(Debug, Clone)]
+pub(crate) struct Deduction<'l, C> {
+    lexicon: &'l [LexItem],
+    pub(crate) items: Vec<Item<C>>,
+    /// Index of each item by its description
+    index: BTreeMap<Key<C>, usize>,
+}
+
+impl<'l, C: Coverage> Deduction<'l, C> {
+    pub(crate) fn new(lexicon: &'l [LexItem]) -> Self {
+        Self { lexicon, items: Vec::new(), index: BTreeMap::new() }
+    }
+
+    /// Add the lexical entry at lexicon index `index`, pronounced as `covers`
+    pub(crate) fn lexical(&mut self, index: usize, covers: C) {
+        let leaf = SyntacticObject::from_entry(self.lexicon, index);
+        self.add(leaf.label, leaf.features, covers, Vec::new(), Step::Lexical(index));
+    }
+
+    /// Record a way of building an item, adding the item if it is new
+    fn add(&mut self, label: Category, features: Vec<Feature>, covers: C, mut movers: Vec<Mover<C>>, step: Step) {
+        movers.sort_by(|a, b| a.covers.cmp(&b.covers));
+        let key = Key { label, features, covers, movers };
+        if let Some(&i) = self.index.get(&key) {
+            let steps = &mut self.items[i].steps;
+            if !steps.contains(&step) {
+                steps.push(step);
+            }
+            return;
+        }
+        let Key { label, features, covers, movers } = key.clone();
+        let set = FeatureSet::new(&features);
+        self.index.insert(key, self.items.len());
+        self.items.push(Item { label, features, covers, movers, steps: vec![step], set });
+    }
+
+    /// Merge and adjoin item `a` (selector or host) with item `b`
+    pub(crate) fn combine(&mut self, a: usize, b: usize) {
+        let (first, second) = (&self.items[a], &self.items[b]);
+        let overlap = first.coverage().any(|covers| second.coverage().any(|other| covers.overlaps(other)));
+        if overlap {
+            return;
+        }
+        let (can_merge, can_adjoin) = (first.set.can_merge(&second.set), first.set.can_adjoin(&second.set));
+
+        if can_merge {
+            let specifier = first.set.selects_specifier();
+            if let Some((label, features)) = merged(&first.signature(), &second.signature()) {
+                self.attach(a, b, specifier, label, features, Step::Merge(a, b));
+            }
+        }
+        if can_adjoin {
+            let (first, second) = (&self.items[a], &self.items[b]);
+            let mut features = first.features.clone();
+            pass_features(&mut features, &second.features);
+            self.attach(a, b, false, first.label.clone(), features, Step::Adjoin(a, b));
+        }
+    }
+
+    /// Add the result of combining items `a` and `b`, `b` on the left if
+    /// `left`, or merged in place as a mover if it has licensees of its own
+    fn attach(&mut self, a: usize, b: usize, left: bool, label: Category, features: Vec<Feature>, step: Step) {
+        let (first, second) = (&self.items[a], &self.items[b]);
+        let mut movers: Vec<Mover<C>> = first.movers.iter().chain(&second.movers).cloned().collect();
+        let own = second.own_licensees();
+        let covers = if !own.is_empty() {
+            movers.push(Mover { licensees: own, covers: second.covers.clone() });
+            first.covers.clone()
+        } else {
+            let joined = if left { C::concatenate(&second.covers, &first.covers) } else { C::concatenate(&first.covers, &second.covers) };
+            match joined {
+                Some(covers) => covers,
+                None => return,
+            }
+        };
+        self.add(label, features, covers, movers, step);
+    }
+
+    /// Merge the silent head at lexicon index `head` with item `x`
+    pub(crate) fn merge_empty(&mut self, head: usize, x: usize) {
+        let leaf = SyntacticObject::from_entry(self.lexicon, head);
+        let item = &self.items[x];
+        if !FeatureSet::new(&leaf.features).can_merge(&item.set) {
+            return;
+        }
+        let other = item.signature();
+        let Some((label, features)) = merged(&leaf, &other) else {
+            return;
+        };
+        let mut movers = item.movers.clone();
+        let own = item.own_licensees();
+        let covers = if own.is_empty() {
+            item.covers.clone()
+        } else {
+            movers.push(Mover { licensees: own, covers: item.covers.clone() });
+            C::silent()
+        };
+        self.add(label, features, covers, movers, Step::MergeEmpty(head, x));
+    }
+
+    /// Move the mover item `x`'s first positive feature attracts
+    pub(crate) fn move_within(&mut self, x: usize) {
+        let item = &self.items[x];
+        let Some(index) = item.set.trigger() else {
+            return;
+        };
+        let licensee = Feature::Neg(index);
+        let Some(m) = item.movers.iter().position(|mover| mover.licensees.contains(&licensee)) else {
+            return;
+        };
+
+        let mut movers = item.movers.clone();
+        let mut mover = movers.remove(m);
+        let mut features = item.features.clone();
+        for licensee in &mover.licensees {
+            remove_first(&mut features, licensee);
+        }
+        remove_first(&mut features, &Feature::Pos(index));
+        remove_first(&mut mover.licensees, &licensee);
+        features.extend(mover.licensees.iter().cloned());
+
+        // A mover with licensees left stays set aside; otherwise it lands at the left edge
+        let covers = if mover.licensees.is_empty() {
+            match C::concatenate(&mover.covers, &item.covers) {
+                Some(covers) => covers,
+                None => return,
+            }
+        } else {
+            movers.push(mover);
+            item.covers.clone()
+        };
+        self.add(item.label.clone(), features, covers, movers, Step::Move(x));
+    }
+}
+
+/// Packed chart of a sentence's constituents
+#[derive(Debug, Clone)]
+pub struct Chart<'l> {
+    length: usize,
+    deduction: Deduction<'l, Span>,
+}
+
+impl<'l> Chart<'l> {
+    /// Fill the chart for `tokens`, with movement only if `movement` is set
+    ///
+    /// Fails if a token is missing from the lexicon or there are no tokens.
+    pub fn build(tokens: &[&str], lexicon: &'l [LexItem], movement: bool) -> Result<Self, DerivationError> {
+        sentence_entries(tokens, lexicon)?;
+        let entries: Vec<Vec<usize>> = tokens.iter()
+            .map(|token| (0..lexicon.len()).filter(|&i| lexicon[i].phon == *token).collect())
+            .collect();
+        Ok(Self::fill(&entries, lexicon, movement))
+    }
+
+    /// Fill the chart for a sentence of token IDs, with movement only if
+    /// `movement` is set; no token is compared as a string
+    ///
+    /// Fails if a token ID is not in the vocabulary or there are none.
+    pub fn build_token_ids(token_ids: &[u32], vocabulary: &Vocabulary<'l>, movement: bool) -> Result<Self, DerivationError> {
+        if token_ids.is_empty() {
+            return Err(DerivationError::EmptyWorkspace);
+        }
+        let entries = token_ids.iter()
+            .map(|&token_id| vocabulary.lookup(token_id).ok_or(DerivationError::InvalidOperation))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::fill(&entries, vocabulary.lexicon(), movement))
+    }
+
+    /// Fill the chart given the lexicon indices of each position's entries
+    fn fill(entries: &[impl AsRef<[usize]>], lexicon: &'l [LexItem], movement: bool) -> Self {
+        let mut chart = Deduction::new(lexicon);
+        for (position, indices) in entries.iter().enumerate() {
+            for &index in indices.as_ref() {
+                chart.lexical(index, Some((position, position + 1)));
+            }
+        }
+
+        let silent: Vec<usize> = (0..lexicon.len()).filter(|&i| lexicon[i].phon.is_empty()).collect();
+        let mut next = 0;
+        while next < chart.items.len() {
+            let x = next;
+            next += 1;
+            for &head in &silent {
+                chart.merge_empty(head, x);
+            }
+            if movement {
+                chart.move_within(x);
+            }
+            for y in 0..x {
+                chart.combine(x, y);
+                chart.combine(y, x);
+            }
+        }
+        Chart { length: entries.len(), deduction: chart }
+    }
+
+    /// Number of distinct items in the chart
+    pub fn len(&self) -> usize {
+        self.deduction.items.len()
+    }
+
+    /// Check if the chart has no items
+    pub fn is_empty(&self) -> bool {
+        self.deduction.items.is_empty()
+    }
+
+    /// Check whether some complete constituent covers the whole sentence
+    pub fn recognizes(&self) -> bool {
+        self.goals().next().is_some()
+    }
+
+    fn goals(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.deduction.items.len()).filter(|&i| {
+            let item = &self.deduction.items[i];
+            item.covers == Some((0, self.length)) && item.movers.is_empty() && item.signature().is_complete()
+        })
+    }
+
+    /// Every tree of a complete constituent covering the whole sentence
+    ///
+    /// Derivations that loop through the same item (silent heads selecting
+    /// their own category) are unpacked without repeating it.
+    pub fn trees(&self) -> Vec<SyntacticObject> {
+        let mut memo = vec![None; self.deduction.items.len()];
+        let mut on_path = vec![false; self.deduction.items.len()];
+        let mut trees: Vec<SyntacticObject> = Vec::new();
+        for goal in self.goals() {
+            for tree in self.unpack(goal, &mut memo, &mut on_path) {
+                if !trees.contains(&tree) {
+                    trees.push(tree);
+                }
+            }
+        }
+        trees
+    }
+
+    fn unpack(&self, i: usize, memo: &mut Vec<Option<Vec<SyntacticObject>>>, on_path: &mut Vec<bool>) -> Vec<SyntacticObject> {
+        if let Some(trees) = &memo[i] {
+            return trees.clone();
+        }
+        if on_path[i] {
+            return Vec::new();
+        }
+        on_path[i] = true;
+
+        let mut trees: Vec<SyntacticObject> = Vec::new();
+        for &step in &self.deduction.items[i].steps {
+            let built: Vec<SyntacticObject> = match step {
+                Step::Lexical(index) => vec![SyntacticObject::from_entry(self.deduction.lexicon, index)],
+                Step::Merge(a, b) | Step::Adjoin(a, b) => {
+                    let (lefts, rights) = (self.unpack(a, memo, on_path), self.unpack(b, memo, on_path));
+                    lefts.iter()
+                        .flat_map(|left| rights.iter().map(move |right| (left.clone(), right.clone())))
+                        .filter_map(|(left, right)| match step {
+                            Step::Merge(..) => merge(left, right).ok(),
+                            _ => adjoin(left, right).ok(),
+                        })
+                        .collect()
+                }
+                Step::MergeEmpty(head, x) => self.unpack(x, memo, on_path)
+                    .into_iter()
+                    .filter_map(|tree| merge(SyntacticObject::from_entry(self.deduction.lexicon, head), tree).ok())
+                    .collect(),
+                Step::Move(x) => self.unpack(x, memo, on_path).into_iter().filter_map(|tree| move_operation(tree).ok()).collect(),
+            };
+            for tree in built {
+                if !trees.contains(&tree) {
+                    trees.push(tree);
+                }
+            }
+        }
+
+        on_path[i] = false;
+        memo[i] = Some(trees.clone());
+        trees
+    }
+
+    /// Probability of each constituent of a complete analysis, given that
+    /// the sentence has one
+    ///
+    /// The probability of a label over a span sums over the chart items
+    /// with that label and span, so it is strictly the expected number of
+    /// such constituents, which is a probability as long as no analysis has
+    /// two of them. Derivations looping through the same item are left out,
+    /// as `trees` leaves them out. Silent constituents have no span and are
+    /// not listed; an empty map means the chart recognizes nothing.
+    pub fn marginals(&self) -> Marginals {
+        let count = self.deduction.items.len();
+        let mut inside = vec![None; count];
+        let mut on_path = vec![false; count];
+        let mut used = vec![Vec::new(); count];
+        let mut order = Vec::new();
+        let goals: Vec<usize> = self.goals().collect();
+        let total: f64 = goals.iter()
+            .map(|&goal| self.inside(goal, &mut inside, &mut on_path, &mut used, &mut order))
+            .sum();
+        let mut marginals = Marginals::new();
+        if total <= 0.0 {
+            return marginals;
+        }
+        let inside: Vec<f64> = inside.into_iter().map(|score| score.unwrap_or(0.0)).collect();
+
+        // Every edge runs from an item to items finished before it, so in
+        // reverse order each item's outside score is complete before it is used
+        let mut outside = vec![0.0; count];
+        for &goal in &goals {
+            outside[goal] += 1.0;
+        }
+        for &i in order.iter().rev() {
+            for &step in &used[i] {
+                let children = children(step);
+                for (k, &child) in children.iter().enumerate() {
+                    let siblings: f64 = children.iter().enumerate().filter(|&(j, _)| j != k).map(|(_, &j)| inside[j]).product();
+                    outside[child] += outside[i] * self.weight(step) * siblings;
+                }
+            }
+        }
+
+        for &i in &order {
+            let (item, probability) = (&self.deduction.items[i], inside[i] * outside[i] / total);
+            let Some(span) = item.covers.filter(|_| probability > 0.0) else {
+                continue;
+            };
+            let labels = marginals.entry(span).or_default();
+            match labels.iter_mut().find(|(label, _)| *label == item.label) {
+                Some((_, sum)) => *sum += probability,
+                None => labels.push((item.label.clone(), probability)),
+            }
+        }
+        marginals
+    }
+
+    /// Inside score of item `i`: the summed weight of its derivations
+    ///
+    /// Records the steps that contribute in `used` and each item in `order`
+    /// once its score is known.
+    fn inside(
+        &self,
+        i: usize,
+        memo: &mut Vec<Option<f64>>,
+        on_path: &mut Vec<bool>,
+        used: &mut Vec<Vec<Step>>,
+        order: &mut Vec<usize>,
+    ) -> f64 {
+        if let Some(score) = memo[i] {
+            return score;
+        }
+        if on_path[i] {
+            return 0.0;
+        }
+        on_path[i] = true;
+
+        let mut score = 0.0;
+        for &step in &self.deduction.items[i].steps {
+            let children = children(step);
+            if children.iter().any(|&child| on_path[child]) {
+                continue;
+            }
+            let built = children.iter().fold(self.weight(step), |product, &child| {
+                product * self.inside(child, memo, on_path, used, order)
+            });
+            if built > 0.0 {
+                used[i].push(step);
+                score += built;
+            }
+        }
+
+        on_path[i] = false;
+        memo[i] = Some(score);
+        order.push(i);
+        score
+    }
+
+    /// Most probable tree of a complete constituent covering the whole
+    /// sentence, with its log probability
+    ///
+    /// Each item keeps only its best way of being built, so the tree is
+    /// found without unpacking the others; of equally probable ways, the
+    /// first found wins. Derivations looping through the same item are left
+    /// out, as `trees` leaves them out. `None` if the chart recognizes
+    /// nothing or every analysis has probability 0. Needs `std` for the
+    /// logarithms.
+    #[cfg(feature = "std")]
+    pub fn best(&self) -> Option<(SyntacticObject, f64)> {
+        let mut memo = vec![None; self.deduction.items.len()];
+        let mut on_path = vec![false; self.deduction.items.len()];
+        let mut best: Option<(usize, f64)> = None;
+        for goal in self.goals().collect::<Vec<_>>() {
+            let score = self.viterbi(goal, &mut memo, &mut on_path);
+            if score > best.map_or(f64::NEG_INFINITY, |(_, best)| best) {
+                best = Some((goal, score));
+            }
+        }
+        let (goal, score) = best?;
+        Some((self.best_tree(goal, &memo)?, score))
+    }
+
+    /// Viterbi score of item `i`: the log weight of its best derivation,
+    /// recording the step that builds it in `memo`
+    #[cfg(feature = "std")]
+    fn viterbi(&self, i: usize, memo: &mut Vec<Option<(f64, Option<Step>)>>, on_path: &mut Vec<bool>) -> f64 {
+        if let Some((score, _)) = memo[i] {
+            return score;
+        }
+        if on_path[i] {
+            return f64::NEG_INFINITY;
+        }
+        on_path[i] = true;
+
+        let mut best = (f64::NEG_INFINITY, None);
+        for &step in &self.deduction.items[i].steps {
+            let children = children(step);
+            if children.iter().any(|&child| on_path[child]) {
+                continue;
+            }
+            let built = children.iter().fold(self.weight(step).ln(), |sum, &child| {
+                sum + self.viterbi(child, memo, on_path)
+            });
+            if built > best.0 {
+                best = (built, Some(step));
+            }
+        }
+
+        on_path[i] = false;
+        memo[i] = Some(best);
+        best.0
+    }
+
+    /// Tree of item `i` along the steps `viterbi` recorded
+    #[cfg(feature = "std")]
+    fn best_tree(&self, i: usize, memo: &[Option<(f64, Option<Step>)>]) -> Option<SyntacticObject> {
+        match memo[i]?.1? {
+            Step::Lexical(index) => Some(SyntacticObject::from_entry(self.deduction.lexicon, index)),
+            Step::Merge(a, b) => merge(self.best_tree(a, memo)?, self.best_tree(b, memo)?).ok(),
+            Step::Adjoin(a, b) => adjoin(self.best_tree(a, memo)?, self.best_tree(b, memo)?).ok(),
+            Step::MergeEmpty(head, x) => merge(SyntacticObject::from_entry(self.deduction.lexicon, head), self.best_tree(x, memo)?).ok(),
+            Step::Move(x) => move_operation(self.best_tree(x, memo)?).ok(),
+        }
+    }
+
+    /// Weight a step contributes to a derivation: the probability of the
+    /// lexical item it introduces, if any
+    fn weight(&self, step: Step) -> f64 {
+        match step {
+            Step::Lexical(index) | Step::MergeEmpty(index, _) => self.deduction.lexicon[index].probability(),
+            _ => 1.0,
+        }
+    }
+}
+
+/// Items a step builds on
+fn children(step: Step) -> Vec<usize> {
+    match step {
+        Step::Lexical(_) => Vec::new(),
+        Step::Merge(a, b) | Step::Adjoin(a, b) => vec![a, b],
+        Step::MergeEmpty(_, x) | Step::Move(x) => vec![x],
+    }
+}
+
+/// Label and features of `a` merged with `b`, as `merge` computes them
+pub(crate) fn merged(a: &SyntacticObject, b: &SyntacticObject) -> Option<(Category, Vec<Feature>)> {
+    let required = match a.next_selector()? {
+        Feature::Sel(cat) | Feature::Spec(cat) => cat.clone(),
+        _ => return None,
+    };
+    let mut features = a.features.clone();
+    discharge_selector(&mut features);
+    pass_features(&mut features, &b.features);
+    Some((a.category().map(Category::phrase).unwrap_or(required), features))
+}
+
+/// Every analysis of a sentence, found by chart parsing
+///
+/// Returns the same trees as `parse_forest`, in chart order. Fails as the
+/// workspace search does: on an unknown word, with the constraint that
+/// ruled out the only convergent analyses, or with `NoValidOperations`.
+pub fn parse_chart(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Vec<SyntacticObject>, DerivationError> {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let chart = Chart::build(&tokens, lexicon, options.operations.movement)?;
+    let trees = chart.trees()
+        .into_iter()
+        .filter(|tree| tree.linearize().split_whitespace().eq(tokens.iter().copied()));
+    admitted(trees, options)
+}
+
+/// Every analysis of a sentence of token IDs, found by chart parsing
+/// without comparing tokens as strings
+///
+/// For callers that already tokenize to IDs. The chart's spans fix each
+/// tree's word order, so unlike `parse_chart` the trees are not
+/// re-linearized; the grammar's constraints still inspect their words.
+/// Fails as `parse_chart` does, with a token ID missing from the
+/// vocabulary counting as an unknown word.
+pub fn parse_token_ids(token_ids: &[u32], vocabulary: &Vocabulary<'_>, options: &GrammarOptions) -> Result<Vec<SyntacticObject>, DerivationError> {
+    let chart = Chart::build_token_ids(token_ids, vocabulary, options.operations.movement)?;
+    admitted(chart.trees().into_iter(), options)
+}
+
+/// The trees the grammar's constraints admit, failing with the violation
+/// that ruled out the last rejected tree, or `NoValidOperations` if there
+/// were none
+fn admitted(trees: impl Iterator<Item = SyntacticObject>, options: &GrammarOptions) -> Result<Vec<SyntacticObject>, DerivationError> {
+    let mut error = DerivationError::NoValidOperations;
+    let trees: Vec<SyntacticObject> = trees
+        .filter(|tree| match options.check(tree) {
+            Ok(()) => true,
+            Err(violation) => {
+                error = violation;
+                false
+            }
+        })
+        .collect();
+    if trees.is_empty() { Err(error) } else { Ok(trees) }
+}
+
+/// Marginal probability of each constituent of a sentence's analyses
+/// (see `Chart::marginals`)
+///
+/// The grammar's constraints are not applied, since they judge whole
+/// trees; fails as `Chart::build` does, or with `NoValidOperations` if the
+/// sentence has no analysis.
+pub fn constituent_marginals(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Marginals, DerivationError> {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let marginals = Chart::build(&tokens, lexicon, options.operations.movement)?.marginals();
+    if marginals.is_empty() { Err(DerivationError::NoValidOperations) } else { Ok(marginals) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_forest, test_lexicon, LexicalWeights, RelativeClauseAnalysis, Unranked};
+
+    /// Check that the chart finds exactly the trees the workspace search finds
+    fn agrees(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> usize {
+        let chart = parse_chart(sentence, lexicon, options);
+        let search = parse_forest(sentence, lexicon, options, &Unranked);
+        match (chart, search) {
+            (Ok(chart), Ok(search)) => {
+                assert_eq!(chart.len(), search.len(), "{}", sentence);
+                assert!(search.iter().all(|parse| chart.contains(&parse.tree)), "{}", sentence);
+                chart.len()
+            }
+            (chart, search) => {
+                assert_eq!(chart.err(), search.err(), "{}", sentence);
+                0
+            }
+        }
+    }
+
+    fn near() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        lexicon
+    }
+
+    #[test]
+    fn test_matches_workspace_search() {
+        let options = GrammarOptions::default();
+        let lexicon = near();
+        assert_eq!(agrees("the student left", &lexicon, &options), 1);
+        assert_eq!(agrees("the student thinks the teacher", &lexicon, &options), 1);
+        assert_eq!(agrees("the student thinks the teacher near the tutor", &lexicon, &options), 2);
+        assert_eq!(agrees("the student the teacher", &lexicon, &options), 0);
+        assert_eq!(agrees("the student zorp", &lexicon, &options), 0);
+    }
+
+    #[test]
+    fn test_movement() {
+        for analysis in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let options = GrammarOptions { relative_clauses: analysis, ..GrammarOptions::default() };
+            let lexicon = options.lexicon();
+            assert_eq!(agrees("the student who left smiled", &lexicon, &options), 1);
+            assert_eq!(agrees("the teacher who the student thinks smiled", &lexicon, &options), 1);
+            assert_eq!(agrees("the student who thinks the teacher who left smiled", &lexicon, &options), 2);
+            // `thinks` selects a DP, not a clause, and a relative clause is no sentence
+            assert_eq!(agrees("the student who the teacher thinks left smiled", &lexicon, &options), 0);
+            assert_eq!(agrees("who the student left", &lexicon, &options), 0);
+        }
+    }
+
+    #[test]
+    fn test_shortest_move_constraint() {
+        let lexicon = vec![
+            LexItem::new("saw", &[Feature::Cat(Category::V), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("mary", &[Feature::Cat(Category::D)]),
+            LexItem::new("her", &[Feature::Cat(Category::D), Feature::Neg(1)]),
+            LexItem::new("him", &[Feature::Cat(Category::D), Feature::Neg(1), Feature::Neg(2)]),
+            LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(1)]),
+            LexItem::new("", &[Feature::Cat(Category::C), Feature::Sel(Category::V), Feature::Pos(1), Feature::Pos(1), Feature::Pos(2)]),
+        ];
+        let options = GrammarOptions::default();
+        assert_eq!(agrees("her mary saw", &lexicon, &options), 1);
+        // Both arguments of `saw` would wait for the same licensor at once
+        assert_eq!(agrees("him her saw", &lexicon, &options), 0);
+        assert!(!Chart::build(&["him", "her", "saw"], &lexicon, true).unwrap().recognizes());
+    }
+
+    #[test]
+    fn test_chart_shares_constituents() {
+        let lexicon = near();
+        let sentence = "the student thinks the teacher near the tutor near the student near the tutor";
+        let tokens: Vec<&str> = sentence.split_whitespace().collect();
+        let chart = Chart::build(&tokens, &lexicon, true).unwrap();
+        assert!(chart.recognizes());
+        // Every attachment of the three PPs, from a chart far smaller than the forest
+        let trees = parse_chart(sentence, &lexicon, &GrammarOptions::default()).unwrap();
+        assert!(trees.len() > 5);
+        assert!(chart.len() < 200, "{} items", chart.len());
+
+        assert!(matches!(Chart::build(&[], &lexicon, true), Err(DerivationError::EmptyWorkspace)));
+    }
+
+    #[test]
+    fn test_marginals_weigh_attachments() {
+        let mut lexicon = test_lexicon();
+        let near = [Feature::Cat(Category::P), Feature::Sel(Category::DP)];
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::N)]].concat()).weighted(0.3));
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::V)]].concat()).weighted(0.9));
+        let options = GrammarOptions::default();
+        let sentence = "the student thinks the teacher near the tutor";
+        let marginals = constituent_marginals(sentence, &lexicon, &options).unwrap();
+        let probability = |span: (usize, usize), label: Category| {
+            marginals.get(&span).and_then(|labels| labels.iter().find(|(l, _)| *l == label)).map_or(0.0, |(_, p)| *p)
+        };
+
+        // Both analyses have the PP and the clause; they split on its host
+        let low = 0.3 / (0.3 + 0.9);
+        assert_eq!(probability((5, 8), Category::PP), 1.0);
+        assert_eq!(probability((0, 8), Category::VP), 1.0);
+        assert!((probability((4, 8), Category::N) - low).abs() < 1e-12);
+        assert!((probability((0, 5), Category::VP) - (1.0 - low)).abs() < 1e-12);
+        assert_eq!(probability((0, 8), Category::DP), 0.0);
+
+        // The same split as normalizing the forest's lexical weights
+        let forest = parse_forest(sentence, &lexicon, &options, &LexicalWeights::new(&lexicon)).unwrap();
+        let total: f64 = forest.iter().map(|parse| parse.score).sum();
+        assert!((forest[1].score / total - low).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_viterbi_parse_in_log_space() {
+        // Weights whose products underflow: only their logs tell the
+        // attachments apart
+        let mut lexicon = test_lexicon();
+        lexicon[0].weight = Some(1e-100);
+        let near = [Feature::Cat(Category::P), Feature::Sel(Category::DP)];
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::N)]].concat()).weighted(1e-200));
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::V)]].concat()).weighted(2e-200));
+        let tokens: Vec<&str> = "the student thinks the teacher near the tutor".split_whitespace().collect();
+        let chart = Chart::build(&tokens, &lexicon, true).unwrap();
+
+        let (tree, score) = chart.best().unwrap();
+        assert_eq!(tree.children[1].label, Category::PP);
+        assert!((score - (3.0 * 1e-100f64.ln() + 2e-200f64.ln())).abs() < 1e-9);
+        assert_eq!(crate::log_derivation_probability(&tree, &lexicon), score);
+        assert_eq!(crate::derivation_probability(&tree, &lexicon), 0.0);
+
+        let tokens = ["the", "student", "the", "teacher"];
+        assert_eq!(Chart::build(&tokens, &lexicon, true).unwrap().best(), None);
+    }
+
+    #[test]
+    fn test_marginals_of_an_unambiguous_sentence() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let marginals = constituent_marginals("the student who left smiled", &lexicon, &options).unwrap();
+        assert!(marginals.values().flatten().all(|(_, probability)| *probability == 1.0));
+        assert_eq!(marginals[&(2, 4)], [(Category::CP, 1.0)]);
+        assert_eq!(marginals.len(), 9);
+
+        assert_eq!(constituent_marginals("the student the teacher", &lexicon, &options), Err(DerivationError::NoValidOperations));
+    }
+}
@@ -0,0 +1,543 @@
+//! Streaming Pattern Recognizers
+//!
+//! Counter automata for the formal languages used to witness recursion.
+//! Each recognizer consumes symbols one at a time and keeps a fixed number
+//! of counters, so arbitrarily long inputs (or inputs embedded in a larger
+//! stream) are checked in O(1) memory without collecting them first.
+//! ASCII whitespace between symbols is ignored.
+//!
+//! Each recognizer is also a `RecursivePattern`, which pairs recognition
+//! with generation, a description, and a complexity class. A
+//! `PatternRegistry` looks patterns up by name for the CLI and the
+//! benchmarks; user-defined witness languages register alongside the
+//! built-in ones.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use core::fmt;
+
+use crate::certificate::{certify_pumping, Certificate, DerivationCertificate, Lemma};
+
+/// Symbol-at-a-time acceptor for a formal language
+pub trait Recognizer: Default {
+    /// Consume one input byte
+    fn push(&mut self, byte: u8);
+
+    /// Check if the input consumed so far is in the language
+    fn accepts(&self) -> bool;
+
+    /// Recognize a stream of bytes
+    fn recognize_bytes(bytes: impl IntoIterator<Item = u8>) -> bool {
+        let mut recognizer = Self::default();
+        bytes.into_iter().for_each(|byte| recognizer.push(byte));
+        recognizer.accepts()
+    }
+
+    /// Recognize a stream of tokens, each a single symbol
+    fn recognize_tokens<'a>(tokens: impl IntoIterator<Item = &'a str>) -> bool {
+        let mut recognizer = Self::default();
+        for token in tokens {
+            match token.as_bytes() {
+                &[symbol] if !symbol.is_ascii_whitespace() => recognizer.push(symbol),
+                _ => return false,
+            }
+        }
+        recognizer.accepts()
+    }
+}
+
+/// aⁿbⁿ: two counters and a flag for out-of-order or foreign symbols
+///
+/// Recognizers of consecutive chunks combine with `then`, so a long input
+/// can be split across threads and the summaries joined in order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnBn {
+    /// `a` symbols seen
+    pub a: usize,
+    /// `b` symbols seen
+    pub b: usize,
+    malformed: bool,
+}
+
+impl AnBn {
+    /// Summary of this input followed by `next`
+    pub fn then(self, next: AnBn) -> AnBn {
+        AnBn {
+            a: self.a + next.a,
+            b: self.b + next.b,
+            malformed: self.malformed || next.malformed || (self.b > 0 && next.a > 0),
+        }
+    }
+}
+
+impl Recognizer for AnBn {
+    fn push(&mut self, byte: u8) {
+        match byte {
+            b'a' if self.b == 0 => self.a += 1,
+            b'b' => self.b += 1,
+            byte if byte.is_ascii_whitespace() => {}
+            _ => self.malformed = true,
+        }
+    }
+
+    fn accepts(&self) -> bool {
+        !self.malformed && self.a == self.b
+    }
+}
+
+/// aⁿbⁿcⁿ: the non-context-free language of three matched blocks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnBnCn {
+    counts: [usize; 3],
+    /// Index of the block being read
+    block: usize,
+    malformed: bool,
+}
+
+impl Recognizer for AnBnCn {
+    fn push(&mut self, byte: u8) {
+        let block = match byte {
+            b'a' => 0,
+            b'b' => 1,
+            b'c' => 2,
+            byte if byte.is_ascii_whitespace() => return,
+            _ => {
+                self.malformed = true;
+                return;
+            }
+        };
+        if block < self.block {
+            self.malformed = true;
+        }
+        self.block = block;
+        self.counts[block] += 1;
+    }
+
+    fn accepts(&self) -> bool {
+        !self.malformed && self.counts[0] == self.counts[1] && self.counts[1] == self.counts[2]
+    }
+}
+
+/// aⁿbᵐcⁿdᵐ: crossing dependencies, a with c and b with d
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnBmCnDm {
+    counts: [usize; 4],
+    /// Index of the block being read
+    block: usize,
+    malformed: bool,
+}
+
+impl AnBmCnDm {
+    /// Member with `n` a's and c's and `m` b's and d's
+    pub fn member(n: usize, m: usize) -> String {
+        blocks(&[('a', n), ('b', m), ('c', n), ('d', m)])
+    }
+}
+
+impl Recognizer for AnBmCnDm {
+    fn push(&mut self, byte: u8) {
+        let block = match byte {
+            b'a' => 0,
+            b'b' => 1,
+            b'c' => 2,
+            b'd' => 3,
+            byte if byte.is_ascii_whitespace() => return,
+            _ => {
+                self.malformed = true;
+                return;
+            }
+        };
+        if block < self.block {
+            self.malformed = true;
+        }
+        self.block = block;
+        self.counts[block] += 1;
+    }
+
+    fn accepts(&self) -> bool {
+        !self.malformed && self.counts[0] == self.counts[2] && self.counts[1] == self.counts[3]
+    }
+}
+
+/// Dyck language D₁: balanced nesting with `a` opening and `b` closing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dyck {
+    depth: usize,
+    malformed: bool,
+}
+
+impl Recognizer for Dyck {
+    fn push(&mut self, byte: u8) {
+        match byte {
+            b'a' => self.depth += 1,
+            b'b' if self.depth > 0 => self.depth -= 1,
+            byte if byte.is_ascii_whitespace() => {}
+            _ => self.malformed = true,
+        }
+    }
+
+    fn accepts(&self) -> bool {
+        !self.malformed && self.depth == 0
+    }
+}
+
+/// Place of a language in the Chomsky hierarchy (with the mildly
+/// context-sensitive class Minimalist Grammars generate)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ComplexityClass {
+    /// Recognized by a finite automaton
+    Regular,
+    /// Needs a stack (one counter suffices for the built-in witnesses)
+    ContextFree,
+    /// Beyond context-free, within reach of Minimalist Grammars
+    MildlyContextSensitive,
+    /// Beyond mildly context-sensitive
+    ContextSensitive,
+}
+
+impl fmt::Display for ComplexityClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            ComplexityClass::Regular => "regular",
+            ComplexityClass::ContextFree => "context-free",
+            ComplexityClass::MildlyContextSensitive => "mildly context-sensitive",
+            ComplexityClass::ContextSensitive => "context-sensitive",
+        })
+    }
+}
+
+/// Formal language used to witness recursion: an indexed family of
+/// members plus a membership test
+pub trait RecursivePattern {
+    /// Registry name (e.g. `an_bn`)
+    fn name(&self) -> &'static str;
+
+    /// One-line description for listings
+    fn description(&self) -> &'static str;
+
+    /// Where the language sits in the hierarchy
+    fn complexity_class(&self) -> ComplexityClass;
+
+    /// Member of size `n`, symbols separated by spaces
+    fn generate(&self, n: usize) -> String;
+
+    /// Check membership (whitespace between symbols is ignored)
+    fn recognize(&self, s: &str) -> bool;
+
+    /// Checkable evidence for the complexity class at one pumping length
+    /// (none by default)
+    fn certificates(&self, pumping_length: usize) -> Vec<Certificate> {
+        let _ = pumping_length;
+        Vec::new()
+    }
+}
+
+/// Space-separated symbol blocks, e.g. `[('a', 2), ('c', 1)]` → `a a c`
+fn blocks(runs: &[(char, usize)]) -> String {
+    let mut out = String::new();
+    for &(symbol, count) in runs {
+        for _ in 0..count {
+            if !out.is_empty() {
+                out.push(' ');
+            }
+            out.push(symbol);
+        }
+    }
+    out
+}
+
+impl RecursivePattern for AnBn {
+    fn name(&self) -> &'static str {
+        "an_bn"
+    }
+
+    fn description(&self) -> &'static str {
+        "aⁿbⁿ: n a's followed by n b's"
+    }
+
+    fn complexity_class(&self) -> ComplexityClass {
+        ComplexityClass::ContextFree
+    }
+
+    fn generate(&self, n: usize) -> String {
+        crate::generate_an_bn(n)
+    }
+
+    fn recognize(&self, s: &str) -> bool {
+        Self::recognize_bytes(s.bytes())
+    }
+
+    /// aᵖbᵖ refutes the regular pumping lemma
+    fn certificates(&self, pumping_length: usize) -> Vec<Certificate> {
+        certify_pumping(self, Lemma::Regular, pumping_length, self.generate(pumping_length)).into_iter().collect()
+    }
+}
+
+impl RecursivePattern for AnBnCn {
+    fn name(&self) -> &'static str {
+        "an_bn_cn"
+    }
+
+    fn description(&self) -> &'static str {
+        "aⁿbⁿcⁿ: three matched blocks"
+    }
+
+    fn complexity_class(&self) -> ComplexityClass {
+        ComplexityClass::MildlyContextSensitive
+    }
+
+    fn generate(&self, n: usize) -> String {
+        blocks(&[('a', n), ('b', n), ('c', n)])
+    }
+
+    fn recognize(&self, s: &str) -> bool {
+        Self::recognize_bytes(s.bytes())
+    }
+
+    /// aᵖbᵖcᵖ refutes the context-free pumping lemma
+    fn certificates(&self, pumping_length: usize) -> Vec<Certificate> {
+        certify_pumping(self, Lemma::ContextFree, pumping_length, self.generate(pumping_length)).into_iter().collect()
+    }
+}
+
+impl RecursivePattern for AnBmCnDm {
+    fn name(&self) -> &'static str {
+        "an_bm_cn_dm"
+    }
+
+    fn description(&self) -> &'static str {
+        "aⁿbᵐcⁿdᵐ: crossing a–c and b–d dependencies"
+    }
+
+    fn complexity_class(&self) -> ComplexityClass {
+        ComplexityClass::MildlyContextSensitive
+    }
+
+    /// Member with n a–c pairs and n + 1 b–d pairs, so the counts differ
+    fn generate(&self, n: usize) -> String {
+        Self::member(n, n + 1)
+    }
+
+    fn recognize(&self, s: &str) -> bool {
+        Self::recognize_bytes(s.bytes())
+    }
+
+    /// aᵖbᵖcᵖdᵖ refutes the context-free pumping lemma, and the crossing
+    /// fragment derives it
+    fn certificates(&self, pumping_length: usize) -> Vec<Certificate> {
+        let p = pumping_length;
+        let derivation = crate::crossing::derive_crossing(p, p.max(1)).ok().map(|tree| Certificate::Derivation {
+            pattern: self.name(),
+            certificate: DerivationCertificate { lexicon: crate::crossing::crossing_lexicon(), tree },
+        });
+        certify_pumping(self, Lemma::ContextFree, p, Self::member(p, p)).into_iter().chain(derivation).collect()
+    }
+}
+
+impl RecursivePattern for Dyck {
+    fn name(&self) -> &'static str {
+        "dyck"
+    }
+
+    fn description(&self) -> &'static str {
+        "D₁: balanced a…b nesting"
+    }
+
+    fn complexity_class(&self) -> ComplexityClass {
+        ComplexityClass::ContextFree
+    }
+
+    /// n pairs: a sequence of n - 1 pairs inside an outer pair
+    fn generate(&self, n: usize) -> String {
+        match n {
+            0 => String::new(),
+            _ => {
+                let mut out = String::from("a");
+                for _ in 1..n {
+                    out.push_str(" a b");
+                }
+                out.push_str(" b");
+                out
+            }
+        }
+    }
+
+    fn recognize(&self, s: &str) -> bool {
+        Self::recognize_bytes(s.bytes())
+    }
+
+    /// aᵖbᵖ refutes the regular pumping lemma
+    fn certificates(&self, pumping_length: usize) -> Vec<Certificate> {
+        let witness = blocks(&[('a', pumping_length), ('b', pumping_length)]);
+        certify_pumping(self, Lemma::Regular, pumping_length, witness).into_iter().collect()
+    }
+}
+
+/// Patterns available by name
+pub struct PatternRegistry {
+    patterns: Vec<Box<dyn RecursivePattern>>,
+}
+
+impl PatternRegistry {
+    /// Registry without any patterns
+    pub fn empty() -> Self {
+        Self { patterns: Vec::new() }
+    }
+
+    /// Registry with the built-in patterns: aⁿbⁿ, aⁿbⁿcⁿ, aⁿbᵐcⁿdᵐ, and D₁
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(AnBn::default());
+        registry.register(AnBnCn::default());
+        registry.register(AnBmCnDm::default());
+        registry.register(Dyck::default());
+        registry
+    }
+
+    /// Add a pattern, replacing any registered under the same name
+    pub fn register(&mut self, pattern: impl RecursivePattern + 'static) {
+        self.patterns.retain(|existing| existing.name() != pattern.name());
+        self.patterns.push(Box::new(pattern));
+    }
+
+    /// Look up a pattern by name
+    pub fn get(&self, name: &str) -> Option<&dyn RecursivePattern> {
+        self.patterns.iter().find(|pattern| pattern.name() == name).map(|pattern| &**pattern)
+    }
+
+    /// Registered patterns, in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &dyn RecursivePattern> {
+        self.patterns.iter().map(|pattern| &**pattern)
+    }
+}
+
+impl Default for PatternRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for PatternRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(|pattern| pattern.name())).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_an_bn() {
+        for s in ["", "a b", "aa bb", "a a a b b b"] {
+            assert!(AnBn::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        for s in ["a", "a a b", "b a", "a b a b", "a b c"] {
+            assert!(!AnBn::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        assert!(AnBn::recognize_tokens(["a", "a", "b", "b"]));
+        assert!(!AnBn::recognize_tokens(["a", "ab", "b"]));
+        assert!(!AnBn::recognize_tokens(["a", " ", "b"]));
+
+        // Chunks combine in order
+        let (mut left, mut right) = (AnBn::default(), AnBn::default());
+        b"a a".iter().for_each(|&b| left.push(b));
+        b"b b".iter().for_each(|&b| right.push(b));
+        assert!(left.then(right).accepts());
+        assert!(!right.then(left).accepts());
+    }
+
+    #[test]
+    fn test_an_bn_cn() {
+        for s in ["", "a b c", "a a b b c c"] {
+            assert!(AnBnCn::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        for s in ["a b", "a a b b c", "a c b", "a b c a b c", "a b b c"] {
+            assert!(!AnBnCn::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_an_bm_cn_dm() {
+        for s in ["", "a c", "b d", "a b c d", "a b b c d d", "a a b c c d"] {
+            assert!(AnBmCnDm::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        for s in ["a b d c", "a b c", "a a b c d", "b a d c", "a b c d d", "a b c d e"] {
+            assert!(!AnBmCnDm::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        assert_eq!(AnBmCnDm::member(1, 2), "a b b c d d");
+    }
+
+    #[test]
+    fn test_dyck() {
+        for s in ["", "a b", "a a b b", "a b a b", "a a b a b b"] {
+            assert!(Dyck::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+        for s in ["a", "b a", "a b b a", "a c b"] {
+            assert!(!Dyck::recognize_bytes(s.bytes()), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_patterns_generate_members() {
+        let registry = PatternRegistry::new();
+        for pattern in registry.iter() {
+            for n in 0..=6 {
+                let member = pattern.generate(n);
+                assert!(pattern.recognize(&member), "{} n={}: {:?}", pattern.name(), n, member);
+            }
+        }
+        assert_eq!(registry.get("an_bn").unwrap().generate(2), "a a b b");
+        assert_eq!(registry.get("an_bn_cn").unwrap().generate(1), "a b c");
+        assert_eq!(registry.get("dyck").unwrap().generate(3), "a a b a b b");
+        assert!(registry.get("an_bn").unwrap().complexity_class() < ComplexityClass::MildlyContextSensitive);
+        assert!(registry.get("copy").is_none());
+    }
+
+    #[test]
+    fn test_user_patterns_register() {
+        /// (ab)ⁿ: a regular language
+        struct Alternating;
+
+        impl RecursivePattern for Alternating {
+            fn name(&self) -> &'static str {
+                "ab_n"
+            }
+            fn description(&self) -> &'static str {
+                "(ab)ⁿ"
+            }
+            fn complexity_class(&self) -> ComplexityClass {
+                ComplexityClass::Regular
+            }
+            fn generate(&self, n: usize) -> String {
+                let mut out = String::new();
+                for _ in 0..n {
+                    out.push_str(if out.is_empty() { "a b" } else { " a b" });
+                }
+                out
+            }
+            fn recognize(&self, s: &str) -> bool {
+                let symbols: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+                symbols.chunks(2).all(|pair| pair == b"ab")
+            }
+        }
+
+        let mut registry = PatternRegistry::empty();
+        registry.register(Alternating);
+        registry.register(Alternating);
+        assert_eq!(registry.iter().count(), 1);
+        let pattern = registry.get("ab_n").unwrap();
+        assert!(pattern.recognize(&pattern.generate(4)));
+        assert_eq!(format!("{:?}", registry), "[\"ab_n\"]");
+    }
+
+    #[test]
+    fn test_streams_without_collecting() {
+        let n = 1_000_000;
+        let stream = core::iter::repeat_n(b'a', n).chain(core::iter::repeat_n(b'b', n));
+        assert!(AnBn::recognize_bytes(stream.clone()));
+        assert!(Dyck::recognize_bytes(stream.clone()));
+        assert!(!AnBn::recognize_bytes(stream.chain(*b"b")));
+    }
+}
@@ -0,0 +1,101 @@
+//! Phonological Form
+//!
+//! The sound side of a derivation: the derived tree with its features
+//! stripped, keeping only labels, overt words, silent heads and traces.
+//! Linearization and bracketing live here rather than on `SyntacticObject`,
+//! so later PF processes work on a `PfNode` and never see unchecked
+//! features. `spell_out` accepts any object, finished or not; `transfer`
+//! is the entry point for convergent derivations.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use crate::{Category, SyntacticObject};
+
+/// Constituent at PF
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PfNode {
+    /// Overt lexical item
+    Word(Category, String),
+    /// Phonologically empty head
+    Silent(Category),
+    /// Base position of a moved phrase
+    Trace(Category),
+    /// Phrase and its constituents, in order
+    Phrase(Category, Vec<PfNode>),
+}
+
+impl PfNode {
+    /// Category label
+    pub fn label(&self) -> &Category {
+        match self {
+            PfNode::Word(label, _) | PfNode::Silent(label) | PfNode::Trace(label) | PfNode::Phrase(label, _) => label,
+        }
+    }
+
+    /// Pronounced words, left to right
+    pub fn words(&self) -> Vec<&str> {
+        let mut words = Vec::new();
+        self.collect_words(&mut words);
+        words
+    }
+
+    fn collect_words<'a>(&'a self, words: &mut Vec<&'a str>) {
+        match self {
+            PfNode::Word(_, word) if !word.is_empty() => words.push(word),
+            PfNode::Phrase(_, children) => children.iter().for_each(|child| child.collect_words(words)),
+            _ => {}
+        }
+    }
+
+    /// Pronounced string (silent heads and traces are skipped)
+    pub fn linearize(&self) -> String {
+        self.words().join(" ")
+    }
+
+    /// Labeled bracketing, with silent heads written `ε` and traces `t`
+    pub fn bracketed(&self) -> String {
+        match self {
+            PfNode::Word(label, word) => format!("[{:?} {}]", label, word),
+            PfNode::Silent(label) => format!("[{:?} ε]", label),
+            PfNode::Trace(label) => format!("[{:?} t]", label),
+            PfNode::Phrase(label, children) => {
+                let children: Vec<String> = children.iter().map(PfNode::bracketed).collect();
+                format!("[{:?} {}]", label, children.join(" "))
+            }
+        }
+    }
+}
+
+/// Strip a syntactic object down to its PF representation
+pub fn spell_out(obj: &SyntacticObject) -> PfNode {
+    let label = obj.label.clone();
+    match &obj.phon {
+        Some(phon) if phon.is_empty() => PfNode::Silent(label),
+        Some(phon) => PfNode::Word(label, phon.to_string()),
+        None if obj.children.is_empty() => PfNode::Trace(label),
+        None => PfNode::Phrase(label, obj.children.iter().map(spell_out).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, GrammarOptions};
+
+    #[test]
+    fn test_spell_out_matches_tree() {
+        let lexicon = GrammarOptions::default().lexicon();
+        for sentence in ["the student left", "the student who left smiled"] {
+            let tree = parse_sentence(sentence, &lexicon).unwrap();
+            let pf = spell_out(&tree);
+            assert_eq!(pf.linearize(), sentence);
+            assert_eq!(pf.words().len(), sentence.split_whitespace().count());
+            assert_eq!(pf.label(), &tree.label);
+        }
+
+        let tree = parse_sentence("the student who left", &lexicon).unwrap();
+        assert!(spell_out(&tree).bracketed().contains("[VP [D t] [V left]]"));
+        assert_eq!(spell_out(&SyntacticObject::trace(Category::D)), PfNode::Trace(Category::D));
+    }
+}
@@ -0,0 +1,255 @@
+//! Phi-Feature Geometry
+//!
+//! Person, number and gender as privative nodes of a feature geometry
+//! (Harley & Ritter 2002): Participant dominates Speaker and Addressee;
+//! Individuation dominates Minimal (singular), Group (plural, with
+//! Augmented below it) and Class (gender). A node entails its ancestors,
+//! and an absent node is unmarked, so third person is the absence of
+//! Participant and a nominal may leave number or gender unspecified.
+//!
+//! Exponents are specified the same way and compete by the Subset
+//! Principle: the most specific exponent whose nodes the target contains
+//! wins, person outranking number and gender. One underspecified exponent
+//! thereby covers several cells (syncretism). Coordination resolves by
+//! markedness: the conjuncts' person nodes add up, the whole is a group,
+//! and only gender shared by every conjunct survives.
+
+/// Node of the phi-feature geometry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PhiNode {
+    /// Discourse participant (first or second person)
+    Participant,
+    /// Speaker (first person)
+    Speaker,
+    /// Addressee (second person)
+    Addressee,
+    /// Individuation (number and gender)
+    Individuation,
+    /// Singular
+    Minimal,
+    /// Plural
+    Group,
+    /// Greater plural
+    Augmented,
+    /// Gender
+    Class,
+    /// Feminine gender
+    Feminine,
+    /// Masculine gender
+    Masculine,
+    /// Neuter gender
+    Neuter,
+}
+
+impl PhiNode {
+    /// Every node, parents before their dependents
+    pub const ALL: [PhiNode; 11] = [
+        PhiNode::Participant,
+        PhiNode::Speaker,
+        PhiNode::Addressee,
+        PhiNode::Individuation,
+        PhiNode::Minimal,
+        PhiNode::Group,
+        PhiNode::Augmented,
+        PhiNode::Class,
+        PhiNode::Feminine,
+        PhiNode::Masculine,
+        PhiNode::Neuter,
+    ];
+
+    /// Node this one depends on
+    pub const fn parent(self) -> Option<PhiNode> {
+        match self {
+            PhiNode::Speaker | PhiNode::Addressee => Some(PhiNode::Participant),
+            PhiNode::Minimal | PhiNode::Group | PhiNode::Class => Some(PhiNode::Individuation),
+            PhiNode::Augmented => Some(PhiNode::Group),
+            PhiNode::Feminine | PhiNode::Masculine | PhiNode::Neuter => Some(PhiNode::Class),
+            PhiNode::Participant | PhiNode::Individuation => None,
+        }
+    }
+
+    /// Whether the node is Participant or below it
+    pub const fn is_person(self) -> bool {
+        matches!(self, PhiNode::Participant | PhiNode::Speaker | PhiNode::Addressee)
+    }
+
+    const fn bit(self) -> u16 {
+        1 << self as u16
+    }
+
+    /// The node with its ancestors
+    const fn closure(self) -> u16 {
+        match self.parent() {
+            Some(parent) => self.bit() | parent.closure(),
+            None => self.bit(),
+        }
+    }
+}
+
+/// Partially specified phi-features: a set of geometry nodes closed under
+/// their ancestors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub struct Phi {
+    nodes: u16,
+}
+
+impl Phi {
+    /// No node specified: third person, number and gender unmarked
+    pub const UNSPECIFIED: Self = Self { nodes: 0 };
+
+    /// Third person singular, the phi-features of an unlisted nominal
+    pub const THIRD_SINGULAR: Self = Self { nodes: PhiNode::Minimal.closure() };
+
+    /// Phi-features with the given nodes and their ancestors
+    pub fn new(nodes: &[PhiNode]) -> Self {
+        nodes.iter().fold(Self::UNSPECIFIED, |phi, &node| phi.with(node))
+    }
+
+    /// Singular of the given person
+    pub fn singular(person: u8) -> Self {
+        Self::person_node(person).with(PhiNode::Minimal)
+    }
+
+    /// Plural of the given person
+    pub fn plural(person: u8) -> Self {
+        Self::person_node(person).with(PhiNode::Group)
+    }
+
+    fn person_node(person: u8) -> Self {
+        match person {
+            1 => Self::new(&[PhiNode::Speaker]),
+            2 => Self::new(&[PhiNode::Addressee]),
+            _ => Self::UNSPECIFIED,
+        }
+    }
+
+    /// These features with a node (and its ancestors) added
+    pub fn with(self, node: PhiNode) -> Self {
+        Self { nodes: self.nodes | node.closure() }
+    }
+
+    /// Whether the node is specified
+    pub fn has(&self, node: PhiNode) -> bool {
+        self.nodes & node.bit() != 0
+    }
+
+    /// Specified nodes, parents before their dependents
+    pub fn nodes(&self) -> impl Iterator<Item = PhiNode> + '_ {
+        PhiNode::ALL.into_iter().filter(|&node| self.has(node))
+    }
+
+    /// Whether every node specified here is specified in `other`
+    pub fn subsumes(&self, other: &Phi) -> bool {
+        self.nodes & !other.nodes == 0
+    }
+
+    /// Number of specified nodes
+    pub fn specificity(&self) -> u32 {
+        self.nodes.count_ones()
+    }
+
+    /// Person, 1 to 3; a speaker-addressee group counts as first person
+    pub fn person(&self) -> u8 {
+        if self.has(PhiNode::Speaker) {
+            1
+        } else if self.has(PhiNode::Addressee) {
+            2
+        } else {
+            3
+        }
+    }
+
+    /// Whether the features are plural
+    pub fn is_plural(&self) -> bool {
+        self.has(PhiNode::Group)
+    }
+
+    /// Phi-features of a coordination of the conjuncts
+    ///
+    /// Person nodes add up (`you and I` is first person), the coordination
+    /// is a group, and gender survives only where all conjuncts share it.
+    pub fn resolve(conjuncts: &[Phi]) -> Phi {
+        let below = |root: PhiNode| PhiNode::ALL.iter().filter(|node| Self::new(&[**node]).has(root)).fold(0, |bits, node| bits | node.bit());
+        let person = conjuncts.iter().fold(0, |bits, phi| bits | phi.nodes) & below(PhiNode::Participant);
+        let gender = conjuncts.iter().fold(below(PhiNode::Class), |bits, phi| bits & phi.nodes);
+        let gender = if conjuncts.is_empty() { 0 } else { gender };
+        Phi { nodes: person | gender }.with(PhiNode::Group)
+    }
+}
+
+/// The exponent realizing `target`, by the Subset Principle
+///
+/// Among exponents whose features `target` contains, the one with more
+/// person nodes wins, then the more specific; an exact tie goes to the
+/// first listed.
+pub fn realize<T>(exponents: &[(Phi, T)], target: Phi) -> Option<&T> {
+    let rank = |phi: &Phi| (phi.nodes().filter(|node| node.is_person()).count(), phi.specificity());
+    let mut best: Option<&(Phi, T)> = None;
+    for entry in exponents.iter().filter(|(phi, _)| phi.subsumes(&target)) {
+        if best.is_none_or(|(phi, _)| rank(&entry.0) > rank(phi)) {
+            best = Some(entry);
+        }
+    }
+    best.map(|(_, exponent)| exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nodes(phi: Phi) -> Vec<PhiNode> {
+        phi.nodes().collect()
+    }
+
+    #[test]
+    fn test_nodes_entail_their_ancestors() {
+        let phi = Phi::new(&[PhiNode::Speaker, PhiNode::Augmented]);
+        assert_eq!(nodes(phi), [PhiNode::Participant, PhiNode::Speaker, PhiNode::Individuation, PhiNode::Group, PhiNode::Augmented]);
+        assert_eq!((phi.person(), phi.is_plural()), (1, true));
+
+        // Partial specification: 'you' need not say whether it is plural
+        let you = Phi::new(&[PhiNode::Addressee]);
+        assert!(you.subsumes(&Phi::singular(2)) && you.subsumes(&Phi::plural(2)));
+        assert!(!Phi::singular(2).subsumes(&you));
+        assert_eq!(Phi::THIRD_SINGULAR, Phi::singular(3));
+    }
+
+    #[test]
+    fn test_subset_principle_yields_syncretism() {
+        // English present: '-s' for third singular, null wherever a
+        // participant is involved, whatever the number
+        let exponents = [
+            (Phi::new(&[PhiNode::Minimal]), "-s"),
+            (Phi::new(&[PhiNode::Participant]), "-"),
+            (Phi::singular(1), "-1sg"),
+        ];
+        let realized = |phi| realize(&exponents, phi).copied();
+        assert_eq!(realized(Phi::singular(3)), Some("-s"));
+        assert_eq!(realized(Phi::singular(2)), Some("-"));
+        assert_eq!(realized(Phi::plural(1)), Some("-"));
+        assert_eq!(realized(Phi::singular(1)), Some("-1sg"));
+        assert_eq!(realized(Phi::plural(3)), None);
+    }
+
+    #[test]
+    fn test_coordination_resolution() {
+        let you_and_i = Phi::resolve(&[Phi::singular(2), Phi::singular(1)]);
+        assert_eq!((you_and_i.person(), you_and_i.is_plural()), (1, true));
+        assert!(!you_and_i.has(PhiNode::Minimal));
+
+        // Spanish-style gender: shared feminine survives, mixed gender
+        // resolves to the unmarked (masculine) form
+        let feminine = Phi::new(&[PhiNode::Minimal, PhiNode::Feminine]);
+        let masculine = Phi::new(&[PhiNode::Minimal, PhiNode::Class]);
+        let exponents = [
+            (Phi::new(&[PhiNode::Minimal]), "-o"),
+            (Phi::new(&[PhiNode::Minimal, PhiNode::Feminine]), "-a"),
+            (Phi::new(&[PhiNode::Group]), "-os"),
+            (Phi::new(&[PhiNode::Group, PhiNode::Feminine]), "-as"),
+        ];
+        let realized = |conjuncts: &[Phi]| realize(&exponents, Phi::resolve(conjuncts)).copied();
+        assert_eq!(realized(&[feminine, feminine]), Some("-as"));
+        assert_eq!(realized(&[feminine, masculine]), Some("-os"));
+        assert_eq!(realized(&[masculine, masculine]), Some("-os"));
+    }
+}
@@ -0,0 +1,104 @@
+//! Workspace Pooling
+//!
+//! The derivation search copies its workspace at every branch, and a batch
+//! of sentences needs a fresh workspace per sentence. A `WorkspacePool`
+//! keeps released workspaces so their item and empty-head buffers are
+//! reused instead of reallocated. `parse_batch` shares one pool across a
+//! whole batch.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::Workspace;
+
+/// Released workspaces waiting to be reused
+#[derive(Debug, Default)]
+pub struct WorkspacePool {
+    free: Vec<Workspace>,
+}
+
+impl WorkspacePool {
+    /// Create an empty pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take an empty workspace with the given memory limit, reusing a
+    /// released one when available
+    pub fn acquire(&mut self, memory_limit: usize) -> Workspace {
+        match self.free.pop() {
+            Some(mut workspace) => {
+                workspace.memory_limit = memory_limit;
+                workspace
+            }
+            None => Workspace::new(memory_limit),
+        }
+    }
+
+    /// Take a copy of `source`, reusing a released workspace's buffers
+    pub fn acquire_copy(&mut self, source: &Workspace) -> Workspace {
+        let mut workspace = self.acquire(source.memory_limit);
+        workspace.clone_from(source);
+        workspace
+    }
+
+    /// Reset a workspace and keep it for reuse
+    pub fn release(&mut self, mut workspace: Workspace) {
+        workspace.reset();
+        self.free.push(workspace);
+    }
+
+    /// Number of workspaces waiting to be reused
+    pub fn len(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Check if no released workspaces are waiting
+    pub fn is_empty(&self) -> bool {
+        self.free.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_batch, parse_sentence, test_lexicon, GrammarOptions, OperationCounts};
+
+    #[test]
+    fn test_release_resets_and_reuses() {
+        let lexicon = test_lexicon();
+        let mut pool = WorkspacePool::new();
+        let mut workspace = pool.acquire(1024);
+        workspace.add_lex(&lexicon[0]);
+        workspace.add_lex(&lexicon[2]);
+        workspace.step_count = 3;
+        let copy = pool.acquire_copy(&workspace);
+        assert_eq!(copy.items(), workspace.items());
+        assert_eq!(workspace.counts().clones, 1);
+
+        let capacity = workspace.items.capacity();
+        pool.release(workspace);
+        pool.release(copy);
+        assert_eq!(pool.len(), 2);
+
+        let reused = pool.acquire(64);
+        assert!(reused.items.is_empty() && reused.items.capacity() >= capacity);
+        assert_eq!((reused.memory_limit, reused.step_count, reused.peak_usage()), (64, 0, 0));
+        assert_eq!(reused.counts(), OperationCounts::default());
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn test_batch_matches_single_parses() {
+        let lexicon = test_lexicon();
+        let sentences = ["the student left", "the left", "a tutor thinks the teacher", "the robot left"];
+        let mut pool = WorkspacePool::new();
+        let batch = parse_batch(sentences, &lexicon, &GrammarOptions::default(), &mut pool);
+
+        assert_eq!(batch.len(), sentences.len());
+        for (sentence, result) in sentences.iter().zip(&batch) {
+            assert_eq!(result.as_ref().map(|parse| &parse.tree), parse_sentence(sentence, &lexicon).as_ref());
+        }
+        assert!(!pool.is_empty());
+    }
+}
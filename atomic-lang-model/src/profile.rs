@@ -0,0 +1,151 @@
+//! Operation Profiling
+//!
+//! Counters for the work a derivation does: merges attempted and succeeded,
+//! adjunctions, movements, feature comparisons, and workspace clones. The
+//! workspace tallies them as operations run; the parser sums them over every
+//! branch of its search and reports the total in a `ParseOutcome`, together
+//! with the workspace's peak memory usage.
+//!
+//! Heap allocations are counted only in a program whose global allocator
+//! reports them through `count_allocation`; derivation traces then record
+//! how many each step made.
+
+use core::fmt;
+use core::ops::{AddAssign, Range};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{DerivationError, Parse};
+
+/// Work done by a derivation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OperationCounts {
+    /// Merge and empty-head merge operations applied or attempted
+    pub merges_attempted: usize,
+    /// Merges that built a new object
+    pub merges_succeeded: usize,
+    /// Adjunctions applied
+    pub adjunctions: usize,
+    /// Movements applied
+    pub moves: usize,
+    /// Selector/category and adjunct/host feature checks
+    pub feature_comparisons: usize,
+    /// Workspace clones (one per branch of the search)
+    pub clones: usize,
+}
+
+impl AddAssign for OperationCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.merges_attempted += other.merges_attempted;
+        self.merges_succeeded += other.merges_succeeded;
+        self.adjunctions += other.adjunctions;
+        self.moves += other.moves;
+        self.feature_comparisons += other.feature_comparisons;
+        self.clones += other.clones;
+    }
+}
+
+impl fmt::Display for OperationCounts {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "merges:              {}/{} succeeded", self.merges_succeeded, self.merges_attempted)?;
+        writeln!(f, "adjunctions:         {}", self.adjunctions)?;
+        writeln!(f, "moves:               {}", self.moves)?;
+        writeln!(f, "feature comparisons: {}", self.feature_comparisons)?;
+        writeln!(f, "workspace clones:    {}", self.clones)
+    }
+}
+
+/// Result of a profiled parse together with the work it took
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOutcome {
+    /// First convergent analysis, or why none was found
+    pub result: Result<Parse, DerivationError>,
+    /// Operations performed across the whole search
+    pub counts: OperationCounts,
+    /// Highest workspace memory usage reached on any branch
+    pub peak_memory: usize,
+    /// Byte range of the sentence in the text it came from
+    pub span: Range<usize>,
+}
+
+/// Allocations reported through `count_allocation`
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Report one heap allocation, for a program's global allocator to call
+///
+/// The library has no unsafe code and so installs no allocator itself;
+/// `atomic-lm` built with the `count-allocations` feature wraps the system
+/// allocator to call this on every allocation and reallocation.
+pub fn count_allocation() {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Allocations reported so far, on every thread
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{derive, parse_profiled, test_lexicon, GrammarOptions, Workspace};
+
+    #[test]
+    fn test_profiled_parse_counts_search() {
+        let outcome = parse_profiled("the student left", &test_lexicon(), &GrammarOptions::default());
+        let parse = outcome.result.unwrap();
+        let counts = outcome.counts;
+
+        // Every operation of the derivation is counted, plus any dead ends
+        assert!(counts.merges_succeeded >= parse.derivation.len());
+        assert!(counts.merges_attempted >= counts.merges_succeeded);
+        assert!(counts.feature_comparisons >= counts.merges_attempted);
+        assert!(counts.clones >= parse.derivation.len());
+        assert_eq!(counts.moves, 0);
+
+        let relative = parse_profiled("the student who left", &GrammarOptions::default().lexicon(), &GrammarOptions::default());
+        assert!(relative.result.is_ok());
+        assert!(relative.counts.moves >= 1);
+        assert!(relative.counts.adjunctions >= 1);
+
+        // The finished tree (five nodes) outweighs the three starting leaves
+        assert_eq!(outcome.peak_memory, 5);
+        assert!(relative.peak_memory > 4);
+    }
+
+    #[test]
+    fn test_failures_are_profiled() {
+        let outcome = parse_profiled("the left", &test_lexicon(), &GrammarOptions::default());
+        assert_eq!(outcome.result, Err(DerivationError::NoValidOperations));
+        assert!(outcome.counts.feature_comparisons > 0);
+
+        let unknown = parse_profiled("the robot left", &test_lexicon(), &GrammarOptions::default());
+        assert_eq!(unknown.counts, OperationCounts::default());
+        assert_eq!(unknown.peak_memory, 0);
+    }
+
+    #[test]
+    fn test_workspace_tallies_greedy_derivation() {
+        let lexicon = test_lexicon();
+        let mut workspace = Workspace::new(1024);
+        workspace.add_lex(&lexicon[0]);
+        workspace.add_lex(&lexicon[2]);
+        workspace.add_lex(&lexicon[9]);
+
+        derive(&mut workspace, 10).unwrap();
+        let counts = workspace.counts();
+        assert_eq!(counts.merges_attempted, 2);
+        assert_eq!(counts.merges_succeeded, 2);
+        assert_eq!(counts.clones, 0);
+        assert_eq!(workspace.peak_usage(), 5);
+
+        // The high-water mark survives the workspace shrinking
+        let tree = workspace.items_mut().pop().unwrap();
+        assert_eq!(workspace.memory_usage(), 0);
+        assert_eq!(workspace.peak_usage(), 5);
+        workspace.items_mut().push(tree);
+
+        let copy = workspace.clone();
+        assert_eq!(workspace.counts().clones, 1);
+        assert_eq!(copy.counts(), OperationCounts::default());
+    }
+}
@@ -0,0 +1,427 @@
+//! Derivation Proof Objects
+//!
+//! A completed derivation written out as a proof term: a numbered list of
+//! steps, each naming a rule (a lexical axiom, Merge, Adjoin, or Move), the
+//! earlier steps it consumes as premises, and the judgment it concludes
+//! (category, unchecked features, yield). Proofs serialize to JSON and are
+//! re-checked by `Proof::check`, which replays each rule on its premises and
+//! compares conclusions. The checker never consults the derivation search,
+//! so a proof shipped with a parse can be trusted without trusting the parser.
+//!
+//! ```json
+//! {
+//!   "sentence": "the student left",
+//!   "steps": [
+//!     { "rule": "lexical", "premises": [], "item": { "phon": "the", "features": ["D", "=N"] },
+//!       "conclusion": { "label": "D", "features": ["D", "=N"], "yield": "the" } },
+//!     { "rule": "merge", "premises": [0, 1], "conclusion": { ... } },
+//!     ...
+//!   ]
+//! }
+//! ```
+//!
+//! Premises are resources: each step is consumed at most once, and every step
+//! but the last must be consumed, as in the linear logic reading of MGs.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+
+use core::fmt;
+
+use crate::lexicon::{item_from_json, item_json, json_string, parse_json, Json};
+use crate::{
+    adjoin, apply_operation, merge, move_operation, parse_derivation, replay_workspace, Category, DerivationError,
+    Feature, GrammarOptions, LexItem, LexiconError, Operation, SyntacticObject,
+};
+
+/// Inference rule of a proof step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rule {
+    /// Axiom introducing a lexical item (no premises)
+    Lexical,
+    /// Merge: selector, then selectee
+    Merge,
+    /// Adjoin: host, then adjunct
+    Adjoin,
+    /// Move within one premise
+    Move,
+}
+
+impl Rule {
+    /// Number of premises the rule consumes
+    pub fn arity(self) -> usize {
+        match self {
+            Rule::Lexical => 0,
+            Rule::Move => 1,
+            Rule::Merge | Rule::Adjoin => 2,
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Rule::Lexical => "lexical",
+            Rule::Merge => "merge",
+            Rule::Adjoin => "adjoin",
+            Rule::Move => "move",
+        })
+    }
+}
+
+/// What a step establishes about the object it builds
+#[derive(Debug, Clone, PartialEq)]
+pub struct Judgment {
+    /// Category label
+    pub label: Category,
+    /// Unchecked features
+    pub features: Vec<Feature>,
+    /// Linearized yield
+    pub yield_: String,
+}
+
+impl Judgment {
+    /// Judgment describing `obj`
+    pub fn of(obj: &SyntacticObject) -> Self {
+        Self {
+            label: obj.label.clone(),
+            features: obj.features.clone(),
+            yield_: obj.linearize(),
+        }
+    }
+}
+
+/// One inference in a proof
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// Rule applied
+    pub rule: Rule,
+    /// Indices of the earlier steps consumed, in rule order
+    pub premises: Vec<usize>,
+    /// Lexical item introduced (lexical steps only)
+    pub item: Option<LexItem>,
+    /// Judgment concluded
+    pub conclusion: Judgment,
+}
+
+/// Proof term for the derivation of a sentence
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proof {
+    /// Sentence derived
+    pub sentence: String,
+    /// Steps in order; the last concludes the derived tree
+    pub steps: Vec<Step>,
+}
+
+/// Reason a proof was rejected
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProofError {
+    /// Malformed JSON or feature notation
+    Format(LexiconError),
+    /// Well-formed JSON that does not describe a proof
+    Schema(&'static str),
+    /// Lexical step without an item from the lexicon
+    UnknownItem(usize),
+    /// Step with the wrong number of premises for its rule
+    Arity(usize),
+    /// Premise that is not an earlier, unconsumed step
+    Premise(usize),
+    /// Rule does not apply to the premises
+    Rule(usize, DerivationError),
+    /// Recorded conclusion differs from the rule's result
+    Conclusion(usize),
+    /// Steps left unconsumed besides the last
+    Unused(usize),
+    /// Final object has unchecked features or the proof is empty
+    Incomplete,
+    /// Final yield is not the sentence
+    Yield,
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProofError::Format(error) => write!(f, "{}", error),
+            ProofError::Schema(message) => write!(f, "Invalid proof: {}", message),
+            ProofError::UnknownItem(step) => write!(f, "Step {}: item not in the lexicon", step),
+            ProofError::Arity(step) => write!(f, "Step {}: wrong number of premises", step),
+            ProofError::Premise(step) => write!(f, "Step {}: premise is not an earlier unused step", step),
+            ProofError::Rule(step, error) => write!(f, "Step {}: {}", step, error),
+            ProofError::Conclusion(step) => write!(f, "Step {}: conclusion does not follow", step),
+            ProofError::Unused(step) => write!(f, "Step {} is never used", step),
+            ProofError::Incomplete => write!(f, "Proof does not conclude a complete derivation"),
+            ProofError::Yield => write!(f, "Proof derives a different sentence"),
+        }
+    }
+}
+
+impl From<LexiconError> for ProofError {
+    fn from(error: LexiconError) -> Self {
+        ProofError::Format(error)
+    }
+}
+
+impl Proof {
+    /// Replay a derivation from the sentence's initial workspace, recording
+    /// each operation as a step
+    pub fn from_derivation(sentence: &str, lexicon: &[LexItem], derivation: &[Operation]) -> Result<Self, DerivationError> {
+        let tokens: Vec<&str> = sentence.split_whitespace().collect();
+        let mut workspace = replay_workspace(&tokens, lexicon, derivation)?;
+
+        // Steps that built each workspace item, in surface order
+        let mut steps: Vec<Step> = Vec::new();
+        let mut built: Vec<usize> = Vec::new();
+        for obj in workspace.items() {
+            built.push(steps.len());
+            steps.push(axiom(obj));
+        }
+
+        for &op in derivation {
+            let step = |i: usize| built.get(i).copied().ok_or(DerivationError::InvalidOperation);
+            let (rule, premises) = match op {
+                Operation::Merge(i, j) => (Rule::Merge, vec![step(i)?, step(j)?]),
+                Operation::Adjoin(i, j) => (Rule::Adjoin, vec![step(i)?, step(j)?]),
+                Operation::MergeEmpty(h, i) => {
+                    let head = workspace.empty_heads.get(h).ok_or(DerivationError::InvalidOperation)?;
+                    let selectee = step(i)?;
+                    steps.push(axiom(head));
+                    (Rule::Merge, vec![steps.len() - 1, selectee])
+                }
+                Operation::Move(i) => (Rule::Move, vec![step(i)?]),
+            };
+            apply_operation(&mut workspace, op)?;
+
+            let at = op.result_index();
+            if let Operation::Merge(_, j) | Operation::Adjoin(_, j) = op {
+                built.remove(j);
+            }
+            built[at] = steps.len();
+            steps.push(Step {
+                rule,
+                premises,
+                item: None,
+                conclusion: Judgment::of(&workspace.items()[at]),
+            });
+        }
+
+        Ok(Self { sentence: sentence.to_string(), steps })
+    }
+
+    /// Re-check every step against `lexicon`, returning the derived tree
+    pub fn check(&self, lexicon: &[LexItem]) -> Result<SyntacticObject, ProofError> {
+        let mut objects: Vec<Option<SyntacticObject>> = Vec::with_capacity(self.steps.len());
+        for (index, step) in self.steps.iter().enumerate() {
+            if step.premises.len() != step.rule.arity() {
+                return Err(ProofError::Arity(index));
+            }
+            let mut premises = Vec::with_capacity(step.premises.len());
+            for &premise in &step.premises {
+                let obj = objects.get_mut(premise).and_then(Option::take).ok_or(ProofError::Premise(index))?;
+                premises.push(obj);
+            }
+
+            let mut premises = premises.into_iter();
+            let mut next = || premises.next().expect("arity checked above");
+            let result = match step.rule {
+                Rule::Lexical => match &step.item {
+                    // Axioms record no weight, so entries match on form and features
+                    Some(item) if lexicon.iter().any(|entry| entry.phon == item.phon && entry.feats == item.feats) => {
+                        Ok(SyntacticObject::from_lex(item))
+                    }
+                    _ => return Err(ProofError::UnknownItem(index)),
+                },
+                Rule::Merge => merge(next(), next()),
+                Rule::Adjoin => adjoin(next(), next()),
+                Rule::Move => move_operation(next()),
+            };
+            let obj = result.map_err(|e| ProofError::Rule(index, e))?;
+            if Judgment::of(&obj) != step.conclusion {
+                return Err(ProofError::Conclusion(index));
+            }
+            objects.push(Some(obj));
+        }
+
+        let tree = objects.pop().flatten().ok_or(ProofError::Incomplete)?;
+        if let Some(unused) = objects.iter().position(Option::is_some) {
+            return Err(ProofError::Unused(unused));
+        }
+        if !tree.is_complete() {
+            return Err(ProofError::Incomplete);
+        }
+        if !tree.linearize().split_whitespace().eq(self.sentence.split_whitespace()) {
+            return Err(ProofError::Yield);
+        }
+        Ok(tree)
+    }
+
+    /// Export the proof as JSON, one step per line
+    pub fn to_json(&self) -> String {
+        let steps: Vec<String> = self.steps.iter()
+            .map(|step| {
+                let premises: Vec<String> = step.premises.iter().map(usize::to_string).collect();
+                let item = step.item.as_ref().map_or(String::new(), |item| format!(", \"item\": {}", item_json(item)));
+                let features: Vec<String> = step.conclusion.features.iter()
+                    .map(|f| json_string(&f.to_string()))
+                    .collect();
+                format!(
+                    "    {{ \"rule\": \"{}\", \"premises\": [{}]{}, \"conclusion\": {{ \"label\": {}, \"features\": [{}], \"yield\": {} }} }}",
+                    step.rule,
+                    premises.join(", "),
+                    item,
+                    json_string(&step.conclusion.label.to_string()),
+                    features.join(", "),
+                    json_string(&step.conclusion.yield_)
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\n  \"sentence\": {},\n  \"steps\": [\n{}\n  ]\n}}\n",
+            json_string(&self.sentence),
+            steps.join(",\n")
+        )
+    }
+
+    /// Read a proof written by `to_json` (the steps are not checked)
+    pub fn from_json(json: &str) -> Result<Self, ProofError> {
+        let value = parse_json(json)?;
+        let schema = ProofError::Schema;
+        let Some(Json::String(sentence)) = value.field("sentence") else {
+            return Err(schema("expected a \"sentence\" string"));
+        };
+        let Some(Json::Array(entries)) = value.field("steps") else {
+            return Err(schema("expected a \"steps\" array"));
+        };
+
+        let steps = entries.iter()
+            .map(|entry| {
+                let rule = match entry.field("rule") {
+                    Some(Json::String(rule)) => match rule.as_str() {
+                        "lexical" => Rule::Lexical,
+                        "merge" => Rule::Merge,
+                        "adjoin" => Rule::Adjoin,
+                        "move" => Rule::Move,
+                        _ => return Err(schema("unknown rule")),
+                    },
+                    _ => return Err(schema("step without a \"rule\" string")),
+                };
+                let Some(Json::Array(premises)) = entry.field("premises") else {
+                    return Err(schema("step without a \"premises\" array"));
+                };
+                let premises = premises.iter()
+                    .map(|premise| match premise {
+                        Json::Number(n) if *n >= 0.0 && (*n as usize) as f64 == *n => Ok(*n as usize),
+                        _ => Err(schema("premises must be step indices")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let item = entry.field("item").map(item_from_json).transpose()?;
+                let conclusion = entry.field("conclusion").ok_or(schema("step without a \"conclusion\""))?;
+                Ok(Step { rule, premises, item, conclusion: judgment_from_json(conclusion)? })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { sentence: sentence.clone(), steps })
+    }
+}
+
+/// Lexical axiom for a leaf of the initial workspace or a silent head
+fn axiom(leaf: &SyntacticObject) -> Step {
+    let item = LexItem {
+        phon: leaf.phon.clone().unwrap_or_default(),
+        feats: leaf.features.clone(),
+        weight: None,
+    };
+    Step {
+        rule: Rule::Lexical,
+        premises: Vec::new(),
+        item: Some(item),
+        conclusion: Judgment::of(leaf),
+    }
+}
+
+fn judgment_from_json(value: &Json) -> Result<Judgment, ProofError> {
+    let schema = ProofError::Schema;
+    let Some(Json::String(label)) = value.field("label") else {
+        return Err(schema("conclusion without a \"label\" string"));
+    };
+    let Some(Json::Array(features)) = value.field("features") else {
+        return Err(schema("conclusion without a \"features\" array"));
+    };
+    let Some(Json::String(yield_)) = value.field("yield") else {
+        return Err(schema("conclusion without a \"yield\" string"));
+    };
+    let features = features.iter()
+        .map(|feature| match feature {
+            Json::String(notation) => notation.parse().map_err(ProofError::Format),
+            _ => Err(schema("features must be strings")),
+        })
+        .collect::<Result<Vec<Feature>, _>>()?;
+    Ok(Judgment { label: label.parse()?, features, yield_: yield_.clone() })
+}
+
+/// Parse a sentence and record its derivation as a proof
+pub fn prove(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<Proof, DerivationError> {
+    let parse = parse_derivation(sentence, lexicon, options)?;
+    Proof::from_derivation(sentence, lexicon, &parse.derivation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relative_clause() -> (Vec<LexItem>, Proof) {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let proof = prove("the student who left smiled", &lexicon, &options).unwrap();
+        (lexicon, proof)
+    }
+
+    #[test]
+    fn test_proof_checks_and_round_trips() {
+        let (lexicon, proof) = relative_clause();
+        assert!(proof.steps.iter().any(|step| step.rule == Rule::Move));
+        let tree = proof.check(&lexicon).unwrap();
+        assert_eq!(tree, parse_derivation(&proof.sentence, &lexicon, &GrammarOptions::default()).unwrap().tree);
+
+        let read = Proof::from_json(&proof.to_json()).unwrap();
+        assert_eq!(read, proof);
+        assert_eq!(read.check(&lexicon), Ok(tree));
+    }
+
+    #[test]
+    fn test_checks_against_weighted_lexicon() {
+        let options = GrammarOptions::default();
+        let lexicon: Vec<LexItem> = options.lexicon().into_iter().map(|item| item.weighted(0.5)).collect();
+        let proof = prove("the student left", &lexicon, &options).unwrap();
+        assert!(proof.check(&lexicon).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_tampered_proofs() {
+        let (lexicon, proof) = relative_clause();
+        let last = proof.steps.len() - 1;
+
+        let mut tampered = proof.clone();
+        tampered.steps[last].conclusion.features.push(Feature::Neg(1));
+        assert_eq!(tampered.check(&lexicon), Err(ProofError::Conclusion(last)));
+
+        let first = proof.steps.iter().position(|step| step.rule == Rule::Merge).unwrap();
+        let later = proof.steps.iter().rposition(|step| step.rule == Rule::Merge).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.steps[later].premises.pop();
+        assert_eq!(tampered.check(&lexicon), Err(ProofError::Arity(later)));
+
+        let mut tampered = proof.clone();
+        tampered.steps[later].premises[0] = proof.steps[first].premises[0];
+        assert_eq!(tampered.check(&lexicon), Err(ProofError::Premise(later)));
+
+        let mut tampered = proof.clone();
+        tampered.sentence = "the student who smiled left".into();
+        assert_eq!(tampered.check(&lexicon), Err(ProofError::Yield));
+
+        assert!(matches!(proof.check(&[]), Err(ProofError::UnknownItem(0))));
+        assert!(Proof::from_derivation(&proof.sentence, &lexicon, &[Operation::Move(9)]).is_err());
+        assert!(matches!(Proof::from_json("{ \"steps\": [] }"), Err(ProofError::Schema(_))));
+    }
+}
@@ -0,0 +1,174 @@
+//! Prosodic Phrasing
+//!
+//! Prosodic constituency read off a derived tree by syntax–prosody mapping
+//! in the style of Match Theory (Selkirk 2011). Clauses (CPs and the root)
+//! map to intonational phrases (ι), other maximal projections to
+//! phonological phrases (φ), and overt words to prosodic words. Binarity
+//! then removes φs with fewer than two words, whose material joins the
+//! enclosing phrase. Silent heads and traces have no prosody.
+//!
+//! Phrasing reads the PF side of `transfer`, so it sees only categories and
+//! words, and a tree that has not converged has no phrasing. The head of a
+//! phrase is the daughter that shares its category; the others are maximal.
+//!
+//! The result is an annotation layer beside the PF string: a prosodic tree,
+//! and a break tier giving the boundary after each word for speech synthesis.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec, vec::Vec};
+
+use core::fmt;
+
+use crate::pf::PfNode;
+use crate::transfer::transfer;
+use crate::{Category, DerivationError, SyntacticObject};
+
+/// Prosodic constituent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prosodic {
+    /// Prosodic word
+    Word(String),
+    /// Phonological phrase (φ)
+    Phrase(Vec<Prosodic>),
+    /// Intonational phrase (ι)
+    Intonational(Vec<Prosodic>),
+}
+
+/// Strength of the prosodic boundary after a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Break {
+    /// Between prosodic words
+    Word,
+    /// At the right edge of a phonological phrase
+    Phonological,
+    /// At the right edge of an intonational phrase
+    Intonational,
+}
+
+impl Prosodic {
+    /// Number of prosodic words in the constituent
+    pub fn word_count(&self) -> usize {
+        match self {
+            Prosodic::Word(_) => 1,
+            Prosodic::Phrase(children) | Prosodic::Intonational(children) => {
+                children.iter().map(Prosodic::word_count).sum()
+            }
+        }
+    }
+
+    /// Each word with the strongest boundary at its right edge
+    pub fn breaks(&self) -> Vec<(&str, Break)> {
+        let mut breaks = Vec::new();
+        self.collect_breaks(&mut breaks);
+        breaks
+    }
+
+    fn collect_breaks<'a>(&'a self, breaks: &mut Vec<(&'a str, Break)>) {
+        let (children, edge) = match self {
+            Prosodic::Word(word) => return breaks.push((word, Break::Word)),
+            Prosodic::Phrase(children) => (children, Break::Phonological),
+            Prosodic::Intonational(children) => (children, Break::Intonational),
+        };
+        children.iter().for_each(|child| child.collect_breaks(breaks));
+        if let Some((_, last)) = breaks.last_mut() {
+            *last = (*last).max(edge);
+        }
+    }
+}
+
+impl fmt::Display for Prosodic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (symbol, children) = match self {
+            Prosodic::Word(word) => return f.write_str(word),
+            Prosodic::Phrase(children) => ("φ", children),
+            Prosodic::Intonational(children) => ("ι", children),
+        };
+        write!(f, "[{}", symbol)?;
+        for child in children {
+            write!(f, " {}", child)?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Prosodic phrasing of a derived tree, rooted in an intonational phrase
+///
+/// Fails with `FeatureMismatch`, as `transfer` does, if the tree has not
+/// converged.
+pub fn phrasing(tree: &SyntacticObject) -> Result<Prosodic, DerivationError> {
+    let output = transfer(tree)?;
+    Ok(match map(&output.pf, true).as_slice() {
+        [Prosodic::Intonational(children)] | [Prosodic::Phrase(children)] => Prosodic::Intonational(children.clone()),
+        constituents => Prosodic::Intonational(constituents.to_vec()),
+    })
+}
+
+/// Prosodic constituents of a node; non-head daughters are maximal projections
+fn map(node: &PfNode, maximal: bool) -> Vec<Prosodic> {
+    let (label, children) = match node {
+        PfNode::Word(_, word) => return vec![Prosodic::Word(word.clone())],
+        PfNode::Silent(_) | PfNode::Trace(_) => return Vec::new(),
+        PfNode::Phrase(label, children) => (label, children),
+    };
+
+    let head = children.iter().position(|child| child.label().head() == label.head());
+    let mut constituents: Vec<Prosodic> = children.iter()
+        .enumerate()
+        .flat_map(|(i, child)| map(child, Some(i) != head))
+        .collect();
+    if !maximal || children.is_empty() {
+        return constituents;
+    }
+
+    // A phrase whose only constituent is already a phrase adds no boundary
+    if let [Prosodic::Phrase(inner)] = constituents.as_mut_slice() {
+        constituents = core::mem::take(inner);
+    }
+    let words: usize = constituents.iter().map(Prosodic::word_count).sum();
+    if label.head() == Category::C && words > 0 {
+        vec![Prosodic::Intonational(constituents)]
+    } else if words >= 2 {
+        vec![Prosodic::Phrase(constituents)]
+    } else {
+        constituents
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, Feature, GrammarOptions, LexItem};
+
+    #[test]
+    fn test_match_phrase_with_binarity() {
+        let lexicon = test_lexicon();
+        let tree = parse_sentence("the student left", &lexicon).unwrap();
+        assert_eq!(phrasing(&tree).unwrap().to_string(), "[ι [φ the student] left]");
+
+        let tree = parse_sentence("the student thinks the teacher", &lexicon).unwrap();
+        let prosody = phrasing(&tree).unwrap();
+        assert_eq!(prosody.to_string(), "[ι [φ the student] thinks [φ the teacher]]");
+        assert_eq!(prosody.word_count(), 5);
+        assert_eq!(prosody.breaks(), vec![
+            ("the", Break::Word),
+            ("student", Break::Phonological),
+            ("thinks", Break::Word),
+            ("the", Break::Word),
+            ("teacher", Break::Intonational),
+        ]);
+    }
+
+    #[test]
+    fn test_match_clause() {
+        let tree = parse_sentence("the student who left smiled", &GrammarOptions::default().lexicon()).unwrap();
+        let prosody = phrasing(&tree).unwrap();
+        assert_eq!(prosody.to_string(), "[ι [φ the [φ student [ι who left]]] smiled]");
+        assert_eq!(prosody.breaks()[3], ("left", Break::Intonational));
+    }
+
+    #[test]
+    fn test_unconverged_tree_has_no_phrasing() {
+        let pending = SyntacticObject::from_lex(&LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(1)]));
+        assert_eq!(phrasing(&pending), Err(DerivationError::FeatureMismatch));
+    }
+}
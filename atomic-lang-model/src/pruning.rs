@@ -0,0 +1,237 @@
+//! Forest Pruning
+//!
+//! The forest search keeps every constituent it can build. With pruning,
+//! each new constituent gets an inside score, the probability of its own
+//! subtree, and is abandoned when that score falls below `threshold` times
+//! the best score seen for its span and label: the token positions it
+//! covers and the category it projects. As in beam pruning of a chart,
+//! this trades exactness for speed: a constituent that scores poorly early
+//! may have been the one a later analysis needed. The best score is the
+//! best found so far, so pruning depends on the order in which the search
+//! meets constituents.
+//!
+//! Pruning only ever removes analyses. A sentence can lose its last one,
+//! whether or not it is grammatical, so measured against judgments pruning
+//! can reject a good sentence and also a bad one the exact search accepts.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{
+    sentence_workspaces, Category, DerivationError, GrammarOptions, LexItem, Parse, ParseRanker, Search,
+    SyntacticObject, WorkspacePool,
+};
+
+/// Probability of a constituent's subtree
+pub trait InsideScore {
+    /// Inside score, between 0 and 1
+    fn inside(&self, constituent: &SyntacticObject) -> f64;
+}
+
+impl<F: Fn(&SyntacticObject) -> f64> InsideScore for F {
+    fn inside(&self, constituent: &SyntacticObject) -> f64 {
+        self(constituent)
+    }
+}
+
+/// Inside scores from weighted local trees: the product of the weights of
+/// the (parent, left, right) label triples a subtree contains
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleWeights {
+    rules: Vec<(Category, Category, Category, f64)>,
+    /// Weight of a triple with no weight of its own
+    pub default: f64,
+}
+
+impl RuleWeights {
+    /// Weights with every triple at `default`
+    pub fn new(default: f64) -> Self {
+        Self { rules: Vec::new(), default }
+    }
+
+    /// The weights with one triple's weight set
+    pub fn rule(mut self, parent: Category, left: Category, right: Category, weight: f64) -> Self {
+        self.rules.retain(|(p, l, r, _)| (p, l, r) != (&parent, &left, &right));
+        self.rules.push((parent, left, right, weight));
+        self
+    }
+
+    /// Weight of a local tree
+    pub fn weight(&self, parent: &Category, left: &Category, right: &Category) -> f64 {
+        self.rules.iter()
+            .find(|(p, l, r, _)| (p, l, r) == (parent, left, right))
+            .map_or(self.default, |rule| rule.3)
+    }
+}
+
+impl InsideScore for RuleWeights {
+    fn inside(&self, constituent: &SyntacticObject) -> f64 {
+        match constituent.children.as_slice() {
+            [left, right] => {
+                self.weight(&constituent.label, &left.label, &right.label) * self.inside(left) * self.inside(right)
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+/// Pruning settings for the forest search
+#[derive(Clone, Copy)]
+pub struct Pruning<'a> {
+    /// Inside score of a constituent
+    pub score: &'a dyn InsideScore,
+    /// Fraction of the best score in a span below which a constituent is
+    /// dropped; 0 keeps everything
+    pub threshold: f64,
+}
+
+/// Best inside score per span and label, as the search meets constituents
+pub(crate) struct SpanBeam<'a> {
+    pruning: Pruning<'a>,
+    /// Best score of each label over a span `(start, end)` of tokens
+    best: BTreeMap<(usize, usize), Vec<(Category, f64)>>,
+    /// Constituents dropped so far
+    pub(crate) pruned: usize,
+}
+
+impl<'a> SpanBeam<'a> {
+    pub(crate) fn new(pruning: Pruning<'a>) -> Self {
+        Self { pruning, best: BTreeMap::new(), pruned: 0 }
+    }
+
+    /// Whether the newly built constituent at `index` among a workspace's
+    /// `items` survives, recording its score
+    ///
+    /// Workspace items are in surface order, so the constituent's span
+    /// starts after the words of the items before it.
+    pub(crate) fn admits(&mut self, items: &[SyntacticObject], index: usize) -> bool {
+        let words = |item: &SyntacticObject| item.linearize().split_whitespace().count();
+        let constituent = &items[index];
+        let start: usize = items[..index].iter().map(words).sum();
+        let span = (start, start + words(constituent));
+
+        let score = self.pruning.score.inside(constituent);
+        let labels = self.best.entry(span).or_default();
+        let best = match labels.iter_mut().find(|(label, _)| *label == constituent.label) {
+            Some((_, best)) => best,
+            None => {
+                labels.push((constituent.label.clone(), score));
+                &mut labels.last_mut().expect("a label was just pushed").1
+            }
+        };
+        if score < self.pruning.threshold * *best {
+            self.pruned += 1;
+            return false;
+        }
+        *best = best.max(score);
+        true
+    }
+}
+
+/// Analyses a pruned forest search kept, and how much it dropped
+#[derive(Debug, Clone)]
+pub struct PrunedForest {
+    /// Analyses, best first according to the ranker
+    pub parses: Result<Vec<Parse>, DerivationError>,
+    /// Constituents dropped
+    pub pruned: usize,
+}
+
+/// `parse_forest`, dropping constituents that score poorly within their span
+pub fn parse_forest_pruned(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    ranker: &dyn ParseRanker,
+    pruning: Pruning<'_>,
+) -> PrunedForest {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let workspaces = match sentence_workspaces(&tokens, lexicon) {
+        Ok(workspaces) => workspaces,
+        Err(e) => return PrunedForest { parses: Err(e), pruned: 0 },
+    };
+    let accept = |tree: &SyntacticObject| options.check(tree);
+    let mut search = Search::new(&tokens, 100, &accept, usize::MAX, WorkspacePool::new());
+    search.operations = options.operations;
+    search.pruning = Some(SpanBeam::new(pruning));
+    let parses = search.parses_from(workspaces).map(|mut parses| {
+        for parse in &mut parses {
+            parse.score = ranker.score(&parse.tree, &parse.derivation);
+        }
+        parses.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(core::cmp::Ordering::Equal));
+        parses
+    });
+    PrunedForest { parses, pruned: search.pruning.map_or(0, |beam| beam.pruned) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_forest, parse_sentence, test_lexicon, Feature, Unranked};
+    use core::cell::Cell;
+
+    fn ambiguous() -> (Vec<LexItem>, &'static str) {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        (lexicon, "the student thinks the teacher near the tutor")
+    }
+
+    #[test]
+    fn test_threshold_zero_is_exact() {
+        let (lexicon, sentence) = ambiguous();
+        let options = GrammarOptions::default();
+        let weights = RuleWeights::new(0.5);
+        let forest = parse_forest_pruned(sentence, &lexicon, &options, &Unranked, Pruning { score: &weights, threshold: 0.0 });
+        assert_eq!(forest.parses.unwrap(), parse_forest(sentence, &lexicon, &options, &Unranked).unwrap());
+        assert_eq!(forest.pruned, 0);
+    }
+
+    #[test]
+    fn test_pruning_drops_unlikely_attachment() {
+        let (lexicon, sentence) = ambiguous();
+        let options = GrammarOptions::default();
+
+        // Attachment inside the object is rare; the search meets the clausal
+        // attachment first and keeps it
+        let weights = RuleWeights::new(0.9).rule(Category::N, Category::N, Category::PP, 0.1);
+        let forest = parse_forest_pruned(sentence, &lexicon, &options, &Unranked, Pruning { score: &weights, threshold: 0.5 });
+        let parses = forest.parses.unwrap();
+        assert_eq!(parses.len(), 1);
+        assert_eq!(parses[0].tree.children[1].label, Category::PP);
+        assert!(forest.pruned > 0);
+
+        // A low threshold keeps both
+        let forest = parse_forest_pruned(sentence, &lexicon, &options, &Unranked, Pruning { score: &weights, threshold: 0.01 });
+        assert_eq!(forest.parses.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_spans_are_token_positions() {
+        let lexicon = test_lexicon();
+        let dp = parse_sentence("the student", &lexicon).unwrap();
+        let thinks = SyntacticObject::from_lex(lexicon.iter().find(|item| item.phon == "thinks").unwrap());
+
+        // Every constituent after the first scores low
+        let calls = Cell::new(0);
+        let score = |_: &SyntacticObject| {
+            calls.set(calls.get() + 1);
+            if calls.get() == 1 { 1.0 } else { 0.4 }
+        };
+        let mut beam = SpanBeam::new(Pruning { score: &score, threshold: 0.5 });
+        assert!(beam.admits(&[dp.clone(), thinks.clone()], 0));
+
+        // The same words later in the sentence do not compete with the first
+        assert!(beam.admits(&[dp.clone(), thinks.clone(), dp.clone()], 2));
+        assert!(!beam.admits(&[dp, thinks], 0));
+        assert_eq!(beam.pruned, 1);
+    }
+}
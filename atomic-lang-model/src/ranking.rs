@@ -0,0 +1,204 @@
+//! Parse Ranking
+//!
+//! Ambiguous sentences yield several convergent analyses. `parse_forest`
+//! consults a `ParseRanker` to order them, so neural or heuristic rerankers
+//! can be supplied without touching the derivation engine.
+
+use crate::{LexItem, Operation, SyntacticObject};
+
+/// Scores convergent analyses; higher scores rank first
+pub trait ParseRanker {
+    /// Score a derived tree together with the operations that derived it
+    fn score(&self, tree: &SyntacticObject, trace: &[Operation]) -> f64;
+}
+
+impl<F> ParseRanker for F
+where
+    F: Fn(&SyntacticObject, &[Operation]) -> f64,
+{
+    fn score(&self, tree: &SyntacticObject, trace: &[Operation]) -> f64 {
+        self(tree, trace)
+    }
+}
+
+/// Leaves analyses in the order the search found them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unranked;
+
+impl ParseRanker for Unranked {
+    fn score(&self, _tree: &SyntacticObject, _trace: &[Operation]) -> f64 {
+        0.0
+    }
+}
+
+/// Prefers economical derivations: fewer operations rank first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FewestOperations;
+
+impl ParseRanker for FewestOperations {
+    fn score(&self, _tree: &SyntacticObject, trace: &[Operation]) -> f64 {
+        -(trace.len() as f64)
+    }
+}
+
+/// Ranks analyses by their probability under the lexicon's item weights
+/// (see `derivation_probability`)
+#[derive(Debug, Clone, Copy)]
+pub struct LexicalWeights<'a> {
+    lexicon: &'a [LexItem],
+}
+
+impl<'a> LexicalWeights<'a> {
+    /// Ranker weighing analyses by the items of `lexicon`
+    pub fn new(lexicon: &'a [LexItem]) -> Self {
+        Self { lexicon }
+    }
+}
+
+impl ParseRanker for LexicalWeights<'_> {
+    fn score(&self, tree: &SyntacticObject, _trace: &[Operation]) -> f64 {
+        derivation_probability(tree, self.lexicon)
+    }
+}
+
+/// Probability of a derived tree: the product of the weights of the lexical
+/// items at its leaves, silent heads included
+///
+/// A leaf counts the weight of the entry the derivation recorded on it (see
+/// `SyntacticObject::from_entry`); a leaf built without one is matched to
+/// the entry with its pronunciation and features. Traces and leaves with no
+/// entry count as certain.
+pub fn derivation_probability(tree: &SyntacticObject, lexicon: &[LexItem]) -> f64 {
+    match tree.children.is_empty() {
+        true => leaf_entry(tree, lexicon).map_or(1.0, LexItem::probability),
+        false => tree.children.iter().map(|child| derivation_probability(child, lexicon)).product(),
+    }
+}
+
+/// `derivation_probability` as a sum of log weights, which does not
+/// underflow however many weighted items a tree has (needs `std` for the
+/// logarithm)
+#[cfg(feature = "std")]
+pub fn log_derivation_probability(tree: &SyntacticObject, lexicon: &[LexItem]) -> f64 {
+    match tree.children.is_empty() {
+        true => leaf_entry(tree, lexicon).map_or(0.0, |item| item.probability().ln()),
+        false => tree.children.iter().map(|child| log_derivation_probability(child, lexicon)).sum(),
+    }
+}
+
+/// Entry a leaf was built from: the one recorded on it, else the entry
+/// with its pronunciation and features; `None` for traces
+fn leaf_entry<'a>(leaf: &SyntacticObject, lexicon: &'a [LexItem]) -> Option<&'a LexItem> {
+    let phon = leaf.phon.as_ref()?;
+    match leaf.entry {
+        Some(index) => lexicon.get(index),
+        None => lexicon.iter().find(|item| item.phon == *phon && item.feats == leaf.features),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_best, parse_forest, parse_sentence, test_lexicon, Category, Feature, GrammarOptions, LexItem};
+
+    fn ambiguous() -> (Vec<LexItem>, &'static str) {
+        let mut lexicon = test_lexicon();
+        lexicon.push(LexItem::new("near", &[
+            Feature::Cat(Category::P),
+            Feature::Sel(Category::DP),
+            Feature::Adjoin(Category::N),
+            Feature::Adjoin(Category::V),
+        ]));
+        (lexicon, "the student thinks the teacher near the tutor")
+    }
+
+    #[test]
+    fn test_forest_enumerates_attachments() {
+        let (lexicon, sentence) = ambiguous();
+        let forest = parse_forest(sentence, &lexicon, &GrammarOptions::default(), &Unranked).unwrap();
+
+        // The PP adjoins to the object noun or to the clause
+        assert_eq!(forest.len(), 2);
+        assert_ne!(forest[0].tree, forest[1].tree);
+        assert_eq!(forest[0].tree, parse_sentence(sentence, &lexicon).unwrap());
+        assert!(forest.iter().all(|parse| parse.tree.linearize() == sentence));
+        assert!(forest.iter().all(|parse| !parse.derivation.is_empty()));
+    }
+
+    #[test]
+    fn test_custom_ranker_reorders_forest() {
+        let (lexicon, sentence) = ambiguous();
+        let options = GrammarOptions::default();
+
+        // Prefer high attachment: the PP as a sister of the clause
+        let high = |tree: &SyntacticObject, _: &[Operation]| {
+            if tree.children[1].label == Category::PP { 1.0 } else { 0.0 }
+        };
+        let forest = parse_forest(sentence, &lexicon, &options, &high).unwrap();
+        assert_eq!(forest[0].score, 1.0);
+        assert_eq!(forest[0].tree.children[1].linearize(), "near the tutor");
+
+        let low = |tree: &SyntacticObject, trace: &[Operation]| -high(tree, trace);
+        let forest = parse_forest(sentence, &lexicon, &options, &low).unwrap();
+        assert_eq!(forest[1].score, -1.0);
+        assert_ne!(forest[0].tree.children[1].label, Category::PP);
+    }
+
+    /// `ambiguous`, with a weighted entry for each attachment of "near"
+    fn weighted(noun: f64, clause: f64) -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        let near = [Feature::Cat(Category::P), Feature::Sel(Category::DP)];
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::N)]].concat()).weighted(noun));
+        lexicon.push(LexItem::new("near", &[&near[..], &[Feature::Adjoin(Category::V)]].concat()).weighted(clause));
+        lexicon
+    }
+
+    #[test]
+    fn test_best_parse_follows_item_weights() {
+        let (_, sentence) = ambiguous();
+
+        let (tree, probability) = parse_best(sentence, &weighted(0.3, 0.7)).unwrap();
+        assert_eq!(probability, 0.7);
+        assert_eq!(tree.children[1].label, Category::PP);
+
+        let (tree, probability) = parse_best(sentence, &weighted(0.7, 0.3)).unwrap();
+        assert_eq!(probability, 0.7);
+        assert_ne!(tree.children[1].label, Category::PP);
+        assert_eq!(tree.linearize(), sentence);
+    }
+
+    #[test]
+    fn test_weights_must_be_probabilities() {
+        let (_, sentence) = ambiguous();
+        for weight in [-0.1, 1.5, f64::NAN] {
+            assert_eq!(parse_best(sentence, &weighted(0.3, weight)), Err(crate::DerivationError::InvalidOperation));
+        }
+        assert!(parse_best(sentence, &weighted(0.0, 1.0)).is_ok());
+    }
+
+    #[test]
+    fn test_unweighted_items_are_certain() {
+        let (lexicon, sentence) = ambiguous();
+        let (tree, probability) = parse_best(sentence, &lexicon).unwrap();
+        assert_eq!(probability, 1.0);
+        assert_eq!(tree, parse_sentence(sentence, &lexicon).unwrap());
+    }
+
+    #[test]
+    fn test_moved_items_keep_their_weight() {
+        // The relative pronoun's leaf gives up its licensee when it moves
+        let mut lexicon = GrammarOptions::default().lexicon();
+        for item in lexicon.iter_mut().filter(|item| item.phon == "who" && item.feats.iter().any(Feature::is_negative)) {
+            item.weight = Some(0.5);
+        }
+        let (tree, probability) = parse_best("the student who left", &lexicon).unwrap();
+        assert_eq!(probability, 0.5);
+        assert_eq!(derivation_probability(&tree, &lexicon), 0.5);
+
+        // A homophone differing only in its licensee keeps its own weight
+        lexicon.insert(0, LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(7)]).weighted(0.1));
+        let forest = parse_forest("the student who left", &lexicon, &GrammarOptions::default(), &LexicalWeights::new(&lexicon)).unwrap();
+        assert_eq!(forest.len(), 1);
+        assert_eq!(forest[0].score, 0.5);
+    }
+}
@@ -0,0 +1,188 @@
+//! Lexicon Hot Reload
+//!
+//! Grammar development is an edit–parse loop. `LexiconWatcher` polls a
+//! lexicon file from a background thread and, whenever its contents
+//! change, parses the new version and swaps it in. Readers take a snapshot
+//! (`lexicon`) per request, so a swap never changes the lexicon under a
+//! parse in progress. An edit that fails to load (often a half-saved file)
+//! leaves the previous lexicon in place and is reported as an event; the
+//! next good save is picked up as usual.
+//!
+//! Requires the `std` feature (threads and the file system).
+
+use core::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::{Lexicon, LexiconError};
+
+/// Default interval between checks of the watched file
+pub const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Why a lexicon file could not be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReloadError {
+    /// The file could not be read
+    Io(io::ErrorKind),
+    /// The file is not a valid lexicon
+    Lexicon(LexiconError),
+}
+
+impl fmt::Display for ReloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReloadError::Io(kind) => write!(f, "cannot read lexicon: {}", io::Error::from(*kind)),
+            ReloadError::Lexicon(e) => write!(f, "invalid lexicon: {}", e),
+        }
+    }
+}
+
+/// Outcome of a change to the watched file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReloadEvent {
+    /// The new version was loaded and swapped in
+    Reloaded(Arc<Lexicon>),
+    /// The new version is not a valid lexicon; the previous one stays in place
+    Failed(ReloadError),
+}
+
+/// Lexicon kept in sync with a file on disk
+pub struct LexiconWatcher {
+    current: Arc<RwLock<Arc<Lexicon>>>,
+    events: Receiver<ReloadEvent>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+fn load(path: &PathBuf) -> Result<(String, Lexicon), ReloadError> {
+    let json = std::fs::read_to_string(path).map_err(|e| ReloadError::Io(e.kind()))?;
+    let lexicon = Lexicon::from_json(&json).map_err(ReloadError::Lexicon)?;
+    Ok((json, lexicon))
+}
+
+impl LexiconWatcher {
+    /// Load the lexicon at `path` and watch it, checking every `interval`
+    ///
+    /// Fails if the initial version cannot be loaded.
+    pub fn watch(path: impl Into<PathBuf>, interval: Duration) -> Result<Self, ReloadError> {
+        let path = path.into();
+        let (mut loaded, lexicon) = load(&path)?;
+        let current = Arc::new(RwLock::new(Arc::new(lexicon)));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, events) = mpsc::channel();
+
+        let thread = {
+            let (current, stop) = (Arc::clone(&current), Arc::clone(&stop));
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(interval);
+                    let event = match std::fs::read_to_string(&path) {
+                        Ok(json) if json == loaded => continue,
+                        Ok(json) => {
+                            let result = Lexicon::from_json(&json);
+                            loaded = json;
+                            match result {
+                                Ok(lexicon) => {
+                                    let lexicon = Arc::new(lexicon);
+                                    *current.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::clone(&lexicon);
+                                    ReloadEvent::Reloaded(lexicon)
+                                }
+                                Err(e) => ReloadEvent::Failed(ReloadError::Lexicon(e)),
+                            }
+                        }
+                        // Editors that save by renaming leave the path briefly missing
+                        Err(_) => continue,
+                    };
+                    if sender.send(event).is_err() {
+                        break;
+                    }
+                }
+            })
+        };
+        Ok(Self { current, events, stop, thread: Some(thread) })
+    }
+
+    /// Snapshot of the current lexicon, unaffected by later reloads
+    pub fn lexicon(&self) -> Arc<Lexicon> {
+        Arc::clone(&self.current.read().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    /// Reload outcomes since the last call, oldest first
+    pub fn events(&self) -> Vec<ReloadEvent> {
+        self.events.try_iter().collect()
+    }
+}
+
+impl Drop for LexiconWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_lexicon, LexItem};
+    use std::time::Instant;
+
+    /// Poll `condition` until it holds, for up to two seconds
+    fn eventually(mut condition: impl FnMut() -> bool) -> bool {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(2) {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        false
+    }
+
+    #[test]
+    fn test_edits_are_swapped_in() {
+        let path = std::env::temp_dir().join(format!("alm-reload-{}.json", std::process::id()));
+        let items = test_lexicon();
+        std::fs::write(&path, Lexicon::new("1", items.clone()).to_json()).unwrap();
+
+        let watcher = LexiconWatcher::watch(&path, Duration::from_millis(5)).unwrap();
+        let before = watcher.lexicon();
+        assert_eq!(before.version, "1");
+
+        // A broken save keeps the previous version
+        std::fs::write(&path, "{ \"version\": ").unwrap();
+        let mut events = Vec::new();
+        assert!(eventually(|| {
+            events.extend(watcher.events());
+            !events.is_empty()
+        }));
+        assert!(matches!(events[0], ReloadEvent::Failed(ReloadError::Lexicon(_))));
+        assert_eq!(watcher.lexicon().version, "1");
+
+        let mut extended = items;
+        extended.push(LexItem::new("tutors", &[crate::Feature::Cat(crate::Category::N)]));
+        std::fs::write(&path, Lexicon::new("2", extended).to_json()).unwrap();
+        assert!(eventually(|| {
+            watcher.events().iter().any(|event| matches!(event, ReloadEvent::Reloaded(lexicon) if lexicon.version == "2"))
+        }));
+        assert_eq!(watcher.lexicon().version, "2");
+        assert!(watcher.lexicon().items.iter().any(|item| item.phon == "tutors"));
+
+        // Snapshots taken earlier are untouched
+        assert_eq!(before.version, "1");
+        drop(watcher);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let result = LexiconWatcher::watch("/nonexistent/lexicon.json", POLL_INTERVAL);
+        assert!(matches!(result, Err(ReloadError::Io(io::ErrorKind::NotFound))));
+    }
+}
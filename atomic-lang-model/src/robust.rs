@@ -0,0 +1,180 @@
+//! Skipping Unknown Words
+//!
+//! The parser rejects a sentence at its first word missing from the
+//! lexicon, but noisy real-world input is full of typos, names and filler.
+//! In skip mode unknown tokens are set aside and the known material is
+//! parsed on its own. If it does not parse as a whole, the analysis falls
+//! back to the largest parsable fragments: the runs of known tokens that
+//! parse by themselves and together cover the most tokens, fewest
+//! fragments first. Each skipped token, unknown or left out of every
+//! fragment, costs a penalty, recorded as each fragment's (negative)
+//! score, and the skipped spans are reported so the caller can tell how
+//! much of the input the analysis covers.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::{String, ToString}, vec::Vec};
+
+use core::cmp::Reverse;
+use core::ops::Range;
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{lookup, parse_derivation, DerivationError, GrammarOptions, LexItem, Parse};
+
+/// Run of consecutive unknown tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct Skipped {
+    /// Token positions in the normalized sentence
+    pub span: Range<usize>,
+    /// The skipped tokens
+    pub tokens: Vec<String>,
+}
+
+/// Analysis of a run of known tokens
+#[derive(Debug, Clone, PartialEq)]
+pub struct FragmentParse {
+    /// Token positions in the normalized sentence, from the first token of
+    /// the fragment to its last
+    pub span: Range<usize>,
+    /// Analysis of the fragment, scored by the penalty
+    pub parse: Parse,
+}
+
+/// Analysis of the known material of a sentence
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkipParse {
+    /// One fragment if the known tokens parse together, otherwise the
+    /// largest parsable fragments, in order
+    pub fragments: Vec<FragmentParse>,
+    /// Spans left out, in order
+    pub skipped: Vec<Skipped>,
+    /// Total penalty for the skipped tokens
+    pub penalty: f64,
+}
+
+/// Parse a sentence, skipping unknown tokens at `penalty` each
+///
+/// Fails if no token is known, or with the error for the known tokens as a
+/// whole if no run of them parses either.
+pub fn parse_skipping_unknown(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    penalty: f64,
+) -> Result<SkipParse, DerivationError> {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let known: Vec<usize> = (0..tokens.len()).filter(|&i| !lookup(lexicon, tokens[i]).is_empty()).collect();
+    if known.is_empty() {
+        return Err(DerivationError::EmptyWorkspace);
+    }
+
+    let fragment = |run: &[usize]| {
+        let words: Vec<&str> = run.iter().map(|&i| tokens[i]).collect();
+        let parse = parse_derivation(&words.join(" "), lexicon, options)?;
+        Ok(FragmentParse { span: run[0]..run[run.len() - 1] + 1, parse })
+    };
+    let mut fragments = match fragment(&known) {
+        Ok(whole) => Vec::from([whole]),
+        Err(e) => largest_fragments(&known, fragment).ok_or(e)?,
+    };
+
+    let covered = |i: usize| fragments.iter().any(|fragment| fragment.span.contains(&i) && known.contains(&i));
+    let mut skipped: Vec<Skipped> = Vec::new();
+    for (i, token) in tokens.iter().enumerate().filter(|&(i, _)| !covered(i)) {
+        if let Some(last) = skipped.last_mut().filter(|last| last.span.end == i) {
+            last.span.end = i + 1;
+            last.tokens.push(token.to_string());
+        } else {
+            skipped.push(Skipped { span: i..i + 1, tokens: Vec::from([token.to_string()]) });
+        }
+    }
+    let penalty = penalty * skipped.iter().map(|run| run.span.len()).sum::<usize>() as f64;
+    fragments.iter_mut().for_each(|fragment| fragment.parse.score = -penalty);
+    Ok(SkipParse { fragments, skipped, penalty })
+}
+
+/// Runs of `known` that parse, covering the most tokens with the fewest
+/// fragments, or `None` if no run parses
+fn largest_fragments(
+    known: &[usize],
+    fragment: impl Fn(&[usize]) -> Result<FragmentParse, DerivationError>,
+) -> Option<Vec<FragmentParse>> {
+    // best[i]: tokens covered and fragments used over the first i known
+    // tokens, with where the last fragment starts (None: token i-1 skipped)
+    let mut best: Vec<(usize, usize, Option<usize>)> = Vec::from([(0, 0, None)]);
+    let mut parses: BTreeMap<(usize, usize), FragmentParse> = BTreeMap::new();
+    for end in 1..=known.len() {
+        let (covered, count, _) = best[end - 1];
+        let mut choice = (covered, count, None);
+        for start in 0..end {
+            let (covered, count, _) = best[start];
+            let candidate = (covered + end - start, count + 1);
+            if (candidate.0, Reverse(candidate.1)) <= (choice.0, Reverse(choice.1)) {
+                continue;
+            }
+            if let Ok(parse) = fragment(&known[start..end]) {
+                parses.insert((start, end), parse);
+                choice = (candidate.0, candidate.1, Some(start));
+            }
+        }
+        best.push(choice);
+    }
+
+    let mut fragments = Vec::new();
+    let mut end = known.len();
+    while end > 0 {
+        match best[end].2 {
+            Some(start) => {
+                fragments.push(parses.remove(&(start, end))?);
+                end = start;
+            }
+            None => end -= 1,
+        }
+    }
+    fragments.reverse();
+    (!fragments.is_empty()).then_some(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_spans_are_skipped() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let result = parse_skipping_unknown("um the student uh er left", &lexicon, &options, 0.5).unwrap();
+        assert_eq!(result.fragments.len(), 1);
+        assert_eq!(result.fragments[0].span, 1..6);
+        assert_eq!(result.fragments[0].parse.tree.linearize(), "the student left");
+        assert_eq!(result.skipped, [
+            Skipped { span: 0..1, tokens: Vec::from(["um".to_string()]) },
+            Skipped { span: 3..5, tokens: Vec::from(["uh".to_string(), "er".to_string()]) },
+        ]);
+        assert_eq!(result.penalty, 1.5);
+        assert_eq!(result.fragments[0].parse.score, -1.5);
+    }
+
+    #[test]
+    fn test_largest_fragments_when_known_material_does_not_parse() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let clean = parse_skipping_unknown("the student left", &lexicon, &options, 1.0).unwrap();
+        assert!(clean.skipped.is_empty());
+        assert_eq!(clean.fragments[0].parse, parse_derivation("the student left", &lexicon, &options).unwrap());
+
+        // Neither order of the two clauses parses whole, but each clause does
+        let result = parse_skipping_unknown("the student left xyzzy the teacher smiled the", &lexicon, &options, 1.0).unwrap();
+        let fragments: Vec<String> = result.fragments.iter().map(|fragment| fragment.parse.tree.linearize()).collect();
+        assert_eq!(fragments, ["the student left", "the teacher smiled"]);
+        assert_eq!(result.fragments[1].span, 4..7);
+        let skipped: Vec<_> = result.skipped.iter().map(|run| run.span.clone()).collect();
+        assert_eq!(skipped, [3..4, 7..8]);
+        assert_eq!(result.penalty, 2.0);
+
+        assert!(parse_skipping_unknown("the xyzzy the", &lexicon, &options, 1.0).is_err());
+        assert_eq!(parse_skipping_unknown("xyzzy", &lexicon, &options, 1.0), Err(DerivationError::EmptyWorkspace));
+    }
+}
@@ -0,0 +1,154 @@
+//! Random Generation
+//!
+//! Samples derivations bottom-up from a lexicon, for synthetic corpora: a
+//! clause head is chosen at random, each of its selectors is filled with a
+//! phrase built the same way, licensors attract their movers, and now and
+//! then an adjunct is built and adjoined. The choices come from a xorshift
+//! generator seeded by the caller, so a seed always yields the same
+//! sentence from the same lexicon. The seed is mixed with splitmix64
+//! first, so consecutive seeds give unrelated sentences.
+//!
+//! A choice that leads nowhere, such as a head whose arguments cannot be
+//! built within the depth left, is undone and another made in its place;
+//! a clause left with a mover nothing attracted is discarded and sampling
+//! starts over, up to `MAX_ATTEMPTS` times.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::determinism::{shuffle, splitmix64, xorshift};
+use crate::{adjoin, can_adjoin, can_merge, merge, move_in_place, Category, Feature, LexItem, SyntacticObject};
+
+/// Clauses sampled before giving up on a seed
+pub const MAX_ATTEMPTS: usize = 64;
+
+/// One phrase in this many gets an adjunct, where the lexicon has one for it
+pub const ADJUNCTION_ODDS: u64 = 4;
+
+/// A random sentence the lexicon derives, nesting phrases at most
+/// `max_depth` deep
+///
+/// Sentences are clauses: phrases projected by a V, C or S head. Silent
+/// heads take part like any other entry. Returns the empty string if no
+/// clause was derived within `MAX_ATTEMPTS` tries.
+pub fn generate_random(lexicon: &[LexItem], max_depth: usize, rng_seed: u64) -> String {
+    // Zero is a fixed point of xorshift
+    let mut sampler = Sampler { lexicon, state: splitmix64(rng_seed).max(1) };
+    let clauses = sampler.heads(|cat| matches!(cat.head(), Category::V | Category::C | Category::S));
+    if clauses.is_empty() {
+        return String::new();
+    }
+    for _ in 0..MAX_ATTEMPTS {
+        let root = clauses[(xorshift(&mut sampler.state) % clauses.len() as u64) as usize];
+        if let Some(clause) = sampler.phrase(root, max_depth).filter(SyntacticObject::is_complete) {
+            return clause.linearize();
+        }
+    }
+    String::new()
+}
+
+struct Sampler<'a> {
+    lexicon: &'a [LexItem],
+    state: u64,
+}
+
+impl Sampler<'_> {
+    /// Indices of the entries whose category satisfies `wanted`, shuffled
+    fn heads(&mut self, wanted: impl Fn(&Category) -> bool) -> Vec<usize> {
+        let mut heads: Vec<usize> = (0..self.lexicon.len())
+            .filter(|&i| SyntacticObject::from_lex(&self.lexicon[i]).category().is_some_and(&wanted))
+            .collect();
+        shuffle(&mut heads, &mut self.state);
+        heads
+    }
+
+    /// A phrase headed by the entry at `index`, with every selector checked
+    /// and every licensor's mover attracted
+    fn phrase(&mut self, index: usize, depth: usize) -> Option<SyntacticObject> {
+        let item = &self.lexicon[index];
+        let mut phrase = SyntacticObject::from_lex(item);
+        for f in &item.feats {
+            if let Feature::Sel(cat) | Feature::Spec(cat) = f {
+                phrase = self.fill(phrase, cat, depth.checked_sub(1)?)?;
+            }
+        }
+        while phrase.features.iter().any(Feature::is_positive) {
+            move_in_place(&mut phrase).ok()?;
+        }
+
+        if depth > 0 && xorshift(&mut self.state).is_multiple_of(ADJUNCTION_ODDS) {
+            if let Some(host) = phrase.category().cloned() {
+                let adjuncts = self.adjuncts(&host);
+                for i in adjuncts {
+                    match self.phrase(i, depth - 1) {
+                        Some(adjunct) if can_adjoin(&phrase, &adjunct) => return adjoin(phrase, adjunct).ok(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(phrase)
+    }
+
+    /// `phrase` with its next selector checked by a phrase of category `cat`
+    fn fill(&mut self, phrase: SyntacticObject, cat: &Category, depth: usize) -> Option<SyntacticObject> {
+        for i in self.heads(|own| own.matches(cat)) {
+            if let Some(filler) = self.phrase(i, depth).filter(|filler| can_merge(&phrase, filler)) {
+                return merge(phrase, filler).ok();
+            }
+        }
+        None
+    }
+
+    /// Indices of the entries adjoining to `host`, shuffled
+    fn adjuncts(&mut self, host: &Category) -> Vec<usize> {
+        let mut adjuncts: Vec<usize> = (0..self.lexicon.len())
+            .filter(|&i| self.lexicon[i].feats.iter().any(|f| matches!(f, Feature::Adjoin(target) if target.matches(host))))
+            .collect();
+        shuffle(&mut adjuncts, &mut self.state);
+        adjuncts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, GrammarOptions};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_seed_fixes_the_sentence() {
+        let lexicon = GrammarOptions::default().lexicon();
+        let sentences: Vec<String> = (0..20).map(|seed| generate_random(&lexicon, 4, seed)).collect();
+        assert_eq!(sentences, (0..20).map(|seed| generate_random(&lexicon, 4, seed)).collect::<Vec<_>>());
+
+        // Consecutive seeds start from unrelated states
+        assert_ne!(sentences[0], sentences[1]);
+        assert_ne!(sentences[1], sentences[2]);
+        assert_ne!(sentences[7], sentences[8]);
+        let samples: Vec<String> = (0..200).map(|seed| generate_random(&lexicon, 5, seed)).collect();
+        let repeats = samples.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        assert!(repeats < 5, "{} neighbouring seeds agree", repeats);
+        assert!(samples.iter().collect::<BTreeSet<_>>().len() > 50);
+    }
+
+    #[test]
+    fn test_samples_parse() {
+        for lexicon in [test_lexicon(), GrammarOptions::default().lexicon()] {
+            for seed in 1..=50 {
+                let sentence = generate_random(&lexicon, 4, seed);
+                assert!(!sentence.is_empty());
+                assert!(parse_sentence(&sentence, &lexicon).is_ok(), "seed {}: {}", seed, sentence);
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth_bounds_nesting() {
+        let lexicon = test_lexicon();
+        // A bare verb has nothing to select, but every verb needs a subject
+        assert_eq!(generate_random(&lexicon, 0, 7), "");
+        assert!((1..=20).all(|seed| generate_random(&lexicon, 2, seed).split(' ').count() <= 6));
+        assert_eq!(generate_random(&[], 4, 7), "");
+    }
+}
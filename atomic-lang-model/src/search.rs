@@ -0,0 +1,641 @@
+//! Search Strategies
+//!
+//! The parser enumerates derivations depth-first with backtracking. That is
+//! one way to explore the space of workspaces; a `SearchStrategy` decides
+//! which operation to apply next and which states to keep, given a `Goal`
+//! that expands workspaces and recognizes convergent ones. Greedy,
+//! backtracking, beam and best-first engines are built in, and a
+//! `ParserConfig` selects among them or a user-supplied strategy.
+//! Iterative deepening repeats a depth-bounded search with a growing bound,
+//! for targets where even a beam does not fit in memory: it keeps only the
+//! current path and its siblings, and is still complete.
+//!
+//! The workspace memory limit bounds each workspace, and Beam and
+//! best-first search also hold the states they keep to it: when their
+//! frontier outgrows the limit they drop its worst states rather than
+//! fail, and report how many they dropped if no derivation converges from
+//! the rest.
+//!
+//! Best-first search orders states by the steps taken plus a `CostFn`
+//! estimate of the steps remaining. The built-in estimates never exceed
+//! the true distance to a convergent workspace, so they prune the search
+//! without changing which derivations are reachable.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeSet, format, string::String, vec::Vec};
+
+use core::cell::Cell;
+
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use crate::supertag::{supertag, tag_sequences, LexiconOrder, Supertagger};
+use crate::{
+    apply_operation, available_operations, begin_step, load_entries, DerivationError, Feature, GrammarOptions, LexItem,
+    Operation, Operations, Parse, Search, SyntacticObject, Workspace, WorkspacePool,
+};
+
+/// What a search is looking for, and the moves available to it
+pub struct Goal<'a> {
+    /// Tokens the derived tree must linearize to
+    pub tokens: &'a [&'a str],
+    /// Check a convergent tree against the grammar's constraints
+    pub accept: &'a dyn Fn(&SyntacticObject) -> Result<(), DerivationError>,
+    /// Operations the grammar allows
+    pub operations: Operations,
+    /// Longest derivation explored
+    pub max_steps: usize,
+    /// Workspaces expanded so far
+    expanded: Cell<usize>,
+}
+
+impl<'a> Goal<'a> {
+    /// Goal of deriving `tokens` within `max_steps` operations
+    pub fn new(
+        tokens: &'a [&'a str],
+        accept: &'a dyn Fn(&SyntacticObject) -> Result<(), DerivationError>,
+        operations: Operations,
+        max_steps: usize,
+    ) -> Self {
+        Self { tokens, accept, operations, max_steps, expanded: Cell::new(0) }
+    }
+
+    /// Workspaces one allowed operation away, in the order the operations
+    /// are listed (merges first)
+    pub fn successors(&self, workspace: &Workspace) -> Vec<(Operation, Workspace)> {
+        if workspace.step_count >= self.max_steps {
+            return Vec::new();
+        }
+        self.expanded.set(self.expanded.get() + 1);
+        available_operations(workspace)
+            .into_iter()
+            .filter(|op| self.operations.allows(op))
+            .filter_map(|op| {
+                let mut next = workspace.clone();
+                begin_step(&mut next).and_then(|()| apply_operation(&mut next, op)).ok()?;
+                Some((op, next))
+            })
+            .collect()
+    }
+
+    /// For a convergent workspace, whether its tree yields the tokens and
+    /// passes the constraints; `None` while the derivation is unfinished
+    pub fn reached(&self, workspace: &Workspace) -> Option<Result<(), DerivationError>> {
+        if !workspace.is_successful() {
+            return None;
+        }
+        let tree = &workspace.items()[0];
+        if !tree.linearize().split_whitespace().eq(self.tokens.iter().copied()) {
+            return Some(Err(DerivationError::NoValidOperations));
+        }
+        Some((self.accept)(tree))
+    }
+
+    /// Number of workspaces whose successors have been generated
+    pub fn expansions(&self) -> usize {
+        self.expanded.get()
+    }
+
+    /// The parse a reached workspace stands for
+    fn parse(workspace: &Workspace, derivation: Vec<Operation>) -> Parse {
+        Parse { tree: workspace.items()[0].clone(), derivation, score: 0.0 }
+    }
+}
+
+/// Explores derivations from an initial workspace
+pub trait SearchStrategy {
+    /// First convergent derivation the strategy finds for `goal`
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError>;
+}
+
+/// Applies the first available operation at every step, never revisiting
+/// a choice; fast but incomplete
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+impl SearchStrategy for Greedy {
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+        let mut workspace = workspace.clone();
+        let mut derivation = Vec::new();
+        loop {
+            if let Some(result) = goal.reached(&workspace) {
+                return result.map(|()| Goal::parse(&workspace, derivation));
+            }
+            let (op, next) = goal.successors(&workspace).into_iter().next().ok_or(DerivationError::NoValidOperations)?;
+            derivation.push(op);
+            workspace = next;
+        }
+    }
+}
+
+/// Depth-first enumeration undoing dead ends: the parser's default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Backtracking;
+
+impl SearchStrategy for Backtracking {
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+        let mut search = Search::new(goal.tokens, goal.max_steps, goal.accept, 1, WorkspacePool::new());
+        search.operations = goal.operations;
+        search.parses(workspace).map(|mut parses| parses.remove(0))
+    }
+}
+
+/// Breadth-first search keeping the `width` states with the fewest
+/// workspace items at each step, fewer if they would exceed the memory
+/// limit; memory-bounded but incomplete
+#[derive(Debug, Clone, Copy)]
+pub struct Beam {
+    /// States kept per step
+    pub width: usize,
+}
+
+impl SearchStrategy for Beam {
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+        let mut error = DerivationError::NoValidOperations;
+        let mut pruned = 0;
+        let mut visited = BTreeSet::new();
+        let mut beam = Vec::from([(workspace.clone(), Vec::new())]);
+        while !beam.is_empty() {
+            let mut next = Vec::new();
+            for (workspace, derivation) in beam {
+                match goal.reached(&workspace) {
+                    Some(Ok(())) => return Ok(Goal::parse(&workspace, derivation)),
+                    Some(Err(e)) => error = preferred(error, e),
+                    None => {
+                        for (op, successor) in goal.successors(&workspace) {
+                            if visited.insert(state(&successor)) {
+                                let mut derivation = derivation.clone();
+                                derivation.push(op);
+                                next.push((successor, derivation));
+                            }
+                        }
+                    }
+                }
+            }
+            next.sort_by_key(|(workspace, _): &(Workspace, Vec<Operation>)| workspace.items().len());
+            next.truncate(self.width);
+            pruned += shed(&mut next, workspace.memory_limit, |(workspace, _)| workspace);
+            beam = next;
+        }
+        Err(exhausted(error, pruned))
+    }
+}
+
+/// Estimate of the operations still needed to reach a convergent workspace
+pub trait CostFn {
+    /// Lower bound on the remaining steps (admissible), or a guess if the
+    /// implementation does not promise one
+    fn cost(&self, workspace: &Workspace, goal: &Goal<'_>) -> usize;
+}
+
+impl<F: Fn(&Workspace) -> usize> CostFn for F {
+    fn cost(&self, workspace: &Workspace, _goal: &Goal<'_>) -> usize {
+        self(workspace)
+    }
+}
+
+/// No estimate: best-first search degenerates to breadth-first
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Uninformed;
+
+impl CostFn for Uninformed {
+    fn cost(&self, _workspace: &Workspace, _goal: &Goal<'_>) -> usize {
+        0
+    }
+}
+
+/// Half the unchecked non-category features, rounded up
+///
+/// A convergent tree keeps only its category. Merge and Adjoin check one
+/// such feature and Move two, while merging a silent head only adds its
+/// own, so no step removes more than two.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UncheckedFeatures;
+
+impl CostFn for UncheckedFeatures {
+    fn cost(&self, workspace: &Workspace, _goal: &Goal<'_>) -> usize {
+        let unchecked = workspace.items().iter()
+            .flat_map(|item| &item.features)
+            .filter(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_)))
+            .count();
+        unchecked.div_ceil(2)
+    }
+}
+
+/// Constituents not yet joined to the rest of the sentence
+///
+/// Tokens in separate workspace items are covered by no common
+/// constituent; Merge and Adjoin join two items, and no other step
+/// reduces their number.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UncoveredTokens;
+
+impl CostFn for UncoveredTokens {
+    fn cost(&self, workspace: &Workspace, _goal: &Goal<'_>) -> usize {
+        workspace.items().len().saturating_sub(1)
+    }
+}
+
+/// Expands the state with the least steps taken plus estimated cost
+/// first, breaking ties by discovery order; complete within the step
+/// bound, and finds a shortest derivation when the estimate is admissible
+///
+/// If the frontier outgrows the memory limit, its states with the highest
+/// estimates are dropped, the latest found first, and the search is no
+/// longer complete.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BestFirst<C = UncheckedFeatures> {
+    /// Estimate of the remaining steps
+    pub cost: C,
+}
+
+impl<C: CostFn> BestFirst<C> {
+    /// Best-first search guided by `cost`
+    pub fn new(cost: C) -> Self {
+        Self { cost }
+    }
+}
+
+impl<C: CostFn> SearchStrategy for BestFirst<C> {
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+        let mut error = DerivationError::NoValidOperations;
+        let mut visited = BTreeSet::from([state(workspace)]);
+        let estimate = |workspace: &Workspace, derivation: &Vec<Operation>| derivation.len() + self.cost.cost(workspace, goal);
+        let mut frontier = Vec::from([(estimate(workspace, &Vec::new()), workspace.clone(), Vec::new())]);
+        let mut held = workspace.memory_usage();
+        let mut pruned = 0;
+        while let Some(best) = (0..frontier.len()).min_by_key(|&i| frontier[i].0) {
+            let (_, workspace, derivation) = frontier.remove(best);
+            held -= workspace.memory_usage();
+            match goal.reached(&workspace) {
+                Some(Ok(())) => return Ok(Goal::parse(&workspace, derivation)),
+                Some(Err(e)) => error = preferred(error, e),
+                None => {
+                    for (op, successor) in goal.successors(&workspace) {
+                        if visited.insert(state(&successor)) {
+                            let mut derivation = derivation.clone();
+                            derivation.push(op);
+                            held += successor.memory_usage();
+                            frontier.push((estimate(&successor, &derivation), successor, derivation));
+                        }
+                    }
+                    if held > workspace.memory_limit {
+                        // Stable, so ties stay in discovery order
+                        frontier.sort_by_key(|(estimate, _, _)| *estimate);
+                        pruned += shed(&mut frontier, workspace.memory_limit, |(_, workspace, _)| workspace);
+                        held = usage(&frontier, |(_, workspace, _)| workspace);
+                    }
+                }
+            }
+        }
+        Err(exhausted(error, pruned))
+    }
+}
+
+/// Depth-first search bounded to `initial` steps, the bound growing by
+/// `increment` whenever some path was cut off; memory stays linear in the
+/// derivation length, and the first derivation found is within `increment`
+/// steps of the shortest
+#[derive(Debug, Clone, Copy)]
+pub struct IterativeDeepening {
+    /// Bound of the first pass
+    pub initial: usize,
+    /// Growth of the bound after a pass that was cut off
+    pub increment: usize,
+}
+
+impl Default for IterativeDeepening {
+    fn default() -> Self {
+        Self { initial: 1, increment: 1 }
+    }
+}
+
+/// State of one bounded pass
+struct Pass<'g, 'a> {
+    goal: &'g Goal<'a>,
+    bound: usize,
+    derivation: Vec<Operation>,
+    cut_off: bool,
+    error: DerivationError,
+}
+
+impl Pass<'_, '_> {
+    /// First derivation below `workspace` within the bound
+    fn search(&mut self, workspace: &Workspace) -> Option<Parse> {
+        match self.goal.reached(workspace) {
+            Some(Ok(())) => return Some(Goal::parse(workspace, self.derivation.clone())),
+            Some(Err(e)) => {
+                self.error = preferred(self.error, e);
+                return None;
+            }
+            None => {}
+        }
+        if self.derivation.len() >= self.bound {
+            self.cut_off = true;
+            return None;
+        }
+        for (op, successor) in self.goal.successors(workspace) {
+            self.derivation.push(op);
+            if let Some(parse) = self.search(&successor) {
+                return Some(parse);
+            }
+            self.derivation.pop();
+        }
+        None
+    }
+}
+
+impl SearchStrategy for IterativeDeepening {
+    fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+        let mut bound = self.initial;
+        loop {
+            let mut pass = Pass { goal, bound, derivation: Vec::new(), cut_off: false, error: DerivationError::NoValidOperations };
+            if let Some(parse) = pass.search(workspace) {
+                return Ok(parse);
+            }
+            // Without a cut-off the whole space was searched
+            if !pass.cut_off || bound >= goal.max_steps {
+                return Err(pass.error);
+            }
+            bound += self.increment.max(1);
+        }
+    }
+}
+
+/// Key identifying a workspace state
+fn state(workspace: &Workspace) -> String {
+    format!("{:?}", workspace.items())
+}
+
+/// Memory held by a set of search states
+fn usage<T>(states: &[T], workspace: impl Fn(&T) -> &Workspace) -> usize {
+    states.iter().map(|state| workspace(state).memory_usage()).sum()
+}
+
+/// Drop states from the end of `states`, which is ordered best first,
+/// until the rest fit in `limit`; returns the number dropped
+fn shed<T>(states: &mut Vec<T>, limit: usize, workspace: impl Fn(&T) -> &Workspace) -> usize {
+    let mut held = usage(states, &workspace);
+    let mut dropped = 0;
+    while held > limit {
+        let Some(worst) = states.pop() else { break };
+        held -= workspace(&worst).memory_usage();
+        dropped += 1;
+    }
+    dropped
+}
+
+/// The failure of a search that dropped `pruned` states to stay within its
+/// memory limit: a constraint violation still wins, but otherwise the
+/// dropped states may have converged
+fn exhausted(error: DerivationError, pruned: usize) -> DerivationError {
+    match pruned {
+        0 => error,
+        pruned => preferred(DerivationError::MemoryLimitExceeded { pruned }, error),
+    }
+}
+
+/// The more informative of two failures: a constraint violation over
+/// resource exhaustion over a dead end, adding up the hypotheses pruned
+fn preferred(current: DerivationError, new: DerivationError) -> DerivationError {
+    match (current, new) {
+        (_, DerivationError::ConstraintViolation(_)) => new,
+        (DerivationError::ConstraintViolation(_), _) => current,
+        (DerivationError::MemoryLimitExceeded { pruned: a }, DerivationError::MemoryLimitExceeded { pruned: b }) => {
+            DerivationError::MemoryLimitExceeded { pruned: a + b }
+        }
+        (_, DerivationError::MemoryLimitExceeded { .. }) => new,
+        _ => current,
+    }
+}
+
+/// Parser settings beyond the grammar: how derivations are searched
+pub struct ParserConfig {
+    /// Search strategy
+    pub strategy: Box<dyn SearchStrategy>,
+    /// Longest derivation explored
+    pub max_steps: usize,
+    /// Workspace memory limit, which also bounds the states a Beam or
+    /// best-first search holds at once
+    pub memory_limit: usize,
+    /// Scores candidate entries of ambiguous words before parsing
+    pub supertagger: Box<dyn Supertagger>,
+    /// Entries kept per word, best-scored first (all by default)
+    pub supertags: usize,
+}
+
+impl ParserConfig {
+    /// Default limits with the given strategy
+    pub fn new(strategy: impl SearchStrategy + 'static) -> Self {
+        Self { strategy: Box::new(strategy), ..Self::default() }
+    }
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            strategy: Box::new(Backtracking),
+            max_steps: 100,
+            memory_limit: 1024,
+            supertagger: Box::new(LexiconOrder),
+            supertags: usize::MAX,
+        }
+    }
+}
+
+/// Parse a sentence with the configured search strategy
+pub fn parse_with_config(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    config: &ParserConfig,
+) -> Result<Parse, DerivationError> {
+    parse_counting_expansions(sentence, lexicon, options, config).0
+}
+
+/// Parse a sentence with the configured search strategy, counting the
+/// workspaces it expanded on the way
+///
+/// Each choice among the supertagger's `supertags` best entries per word
+/// is searched in turn, best-scored first, until one derives the sentence.
+pub fn parse_counting_expansions(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+    config: &ParserConfig,
+) -> (Result<Parse, DerivationError>, usize) {
+    let normalized = options.normalizer.apply(sentence, lexicon);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    let tags = match supertag(&tokens, lexicon, config.supertagger.as_ref(), config.supertags) {
+        Ok(tags) => tags,
+        Err(e) => return (Err(e), 0),
+    };
+    let accept = |tree: &SyntacticObject| options.check(tree);
+    let goal = Goal::new(&tokens, &accept, options.operations, config.max_steps);
+    let mut error = DerivationError::NoValidOperations;
+    for entries in tag_sequences(tags) {
+        let mut workspace = Workspace::new(config.memory_limit);
+        load_entries(&mut workspace, &entries, lexicon);
+        match config.strategy.search(&workspace, &goal) {
+            Ok(parse) => return (Ok(parse), goal.expansions()),
+            Err(e) => error = preferred(error, e),
+        }
+    }
+    (Err(error), goal.expansions())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supertag::SelectionRules;
+    use crate::parse_with_options;
+
+    #[test]
+    fn test_strategies_are_swappable() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let complete: [ParserConfig; 3] = [ParserConfig::new(Backtracking), ParserConfig::new(BestFirst::new(UncheckedFeatures)), ParserConfig::new(Beam { width: 64 })];
+        for sentence in ["the student left", "the student who left smiled", "the student thinks the teacher"] {
+            let expected = parse_with_options(sentence, &lexicon, &options).unwrap();
+            for config in &complete {
+                let parse = parse_with_config(sentence, &lexicon, &options, config).unwrap();
+                assert_eq!(parse.tree.linearize(), sentence);
+                assert_eq!(parse.tree.label, expected.label);
+            }
+        }
+        assert_eq!(parse_with_config("the student left", &lexicon, &options, &ParserConfig::default()).unwrap().tree,
+            parse_with_options("the student left", &lexicon, &options).unwrap());
+        assert!(parse_with_config("student the left", &lexicon, &options, &ParserConfig::new(BestFirst::new(UncheckedFeatures))).is_err());
+    }
+
+    #[test]
+    fn test_greedy_commits_to_its_first_choice() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let greedy = ParserConfig::new(Greedy);
+        assert!(parse_with_config("the student left", &lexicon, &options, &greedy).is_ok());
+
+        // The first merge available builds the relative clause wrongly, and
+        // greedy search cannot undo it
+        let sentence = "the student who left smiled";
+        assert!(parse_with_config(sentence, &lexicon, &options, &greedy).is_err());
+        assert!(parse_with_config(sentence, &lexicon, &options, &ParserConfig::default()).is_ok());
+    }
+
+    #[test]
+    fn test_admissible_heuristics_expand_less() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let run = |config: ParserConfig| {
+            let (parse, expansions) = parse_counting_expansions("the student who left smiled", &lexicon, &options, &config);
+            (parse.unwrap().derivation.len(), expansions)
+        };
+        let (shortest, uninformed) = run(ParserConfig::new(BestFirst::new(Uninformed)));
+        for (steps, expansions) in [
+            run(ParserConfig::new(BestFirst::new(UncheckedFeatures))),
+            run(ParserConfig::new(BestFirst::new(UncoveredTokens))),
+        ] {
+            // Same derivation length: the estimates never overshoot
+            assert_eq!(steps, shortest);
+            assert!(expansions < uninformed, "{} >= {}", expansions, uninformed);
+        }
+
+        // Any function of the workspace serves as an estimate
+        let items = |workspace: &Workspace| workspace.items().len() - 1;
+        assert_eq!(run(ParserConfig::new(BestFirst::new(items))).0, shortest);
+    }
+
+    #[test]
+    fn test_iterative_deepening() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let deepening = ParserConfig::new(IterativeDeepening::default());
+        let shortest = ParserConfig::new(BestFirst::new(Uninformed));
+        for sentence in ["the student left", "the student who left smiled", "the student who left thinks the teacher"] {
+            let parse = parse_with_config(sentence, &lexicon, &options, &deepening).unwrap();
+            assert_eq!(parse.tree.linearize(), sentence);
+            let expected = parse_with_config(sentence, &lexicon, &options, &shortest).unwrap();
+            assert_eq!(parse.derivation.len(), expected.derivation.len());
+        }
+
+        // An exhausted space ends the search before the step limit
+        let (result, expansions) = parse_counting_expansions("student the left", &lexicon, &options, &deepening);
+        assert!(result.is_err());
+        assert!(expansions < 100, "{}", expansions);
+    }
+
+    #[test]
+    fn test_tight_memory_limit_prunes_before_failing() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let sentence = "the student who left smiled";
+        let config = |memory_limit, strategy: Box<dyn SearchStrategy>| ParserConfig { strategy, memory_limit, ..ParserConfig::default() };
+
+        // Best-first search still finds the parse with a pruned frontier
+        let best_first = config(40, Box::new(BestFirst::new(UncheckedFeatures)));
+        assert_eq!(parse_with_config(sentence, &lexicon, &options, &best_first).unwrap().tree.linearize(), sentence);
+
+        // Below that, the error says what was given up
+        for strategy in [Box::new(Beam { width: 64 }) as Box<dyn SearchStrategy>, Box::new(BestFirst::new(UncheckedFeatures))] {
+            match parse_with_config(sentence, &lexicon, &options, &config(20, strategy)) {
+                Err(e @ DerivationError::MemoryLimitExceeded { pruned }) => {
+                    assert!(pruned > 0);
+                    assert!(e.to_string().ends_with(&format!("({} hypotheses pruned)", pruned)));
+                }
+                other => panic!("expected the limit to be exceeded, got {:?}", other),
+            }
+        }
+
+        // Depth-first search holds no frontier to prune
+        let backtracking = config(2, Box::new(Backtracking));
+        assert_eq!(
+            parse_with_config(sentence, &lexicon, &options, &backtracking).map(|parse| parse.tree),
+            Err(DerivationError::MemoryLimitExceeded { pruned: 0 })
+        );
+    }
+
+    #[test]
+    fn test_supertags_widen_lexical_choice() {
+        let options = GrammarOptions::default();
+        let mut lexicon = options.lexicon();
+        lexicon.insert(0, LexItem::new("left", &[Feature::Cat(crate::Category::N)]));
+
+        // Only the first entry: the nominal `left` cannot end a clause
+        let first = ParserConfig { supertags: 1, ..ParserConfig::default() };
+        assert!(parse_with_config("the student left", &lexicon, &options, &first).is_err());
+        let both = ParserConfig::default();
+        assert!(parse_with_config("the student left", &lexicon, &options, &both).is_ok());
+
+        // Context-scored tags find the verb without widening the search
+        let rules = ParserConfig { supertagger: Box::new(SelectionRules), supertags: 1, ..ParserConfig::default() };
+        assert_eq!(
+            parse_with_config("the student left", &lexicon, &options, &rules).unwrap().tree,
+            parse_with_config("the student left", &lexicon, &options, &both).unwrap().tree,
+        );
+    }
+
+    /// A user strategy: try the built-in engines in turn
+    struct Fallback(Vec<Box<dyn SearchStrategy>>);
+
+    impl SearchStrategy for Fallback {
+        fn search(&self, workspace: &Workspace, goal: &Goal<'_>) -> Result<Parse, DerivationError> {
+            let mut error = DerivationError::NoValidOperations;
+            for strategy in &self.0 {
+                match strategy.search(workspace, goal) {
+                    Ok(parse) => return Ok(parse),
+                    Err(e) => error = e,
+                }
+            }
+            Err(error)
+        }
+    }
+
+    #[test]
+    fn test_user_strategy() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let config = ParserConfig::new(Fallback(Vec::from([Box::new(Greedy) as Box<dyn SearchStrategy>, Box::new(Backtracking)])));
+        let parse = parse_with_config("the student who left smiled", &lexicon, &options, &config).unwrap();
+        assert_eq!(parse.tree.linearize(), "the student who left smiled");
+    }
+}
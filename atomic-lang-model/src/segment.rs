@@ -0,0 +1,123 @@
+//! Sentence Segmentation
+//!
+//! The parser takes one sentence at a time. `segment` splits running text
+//! by rule: a sentence ends at `.`, `!` or `?` (with any closing quotes or
+//! brackets) followed by whitespace and a capital, digit or opening quote,
+//! at a blank line, or at the end of the text. A period after a listed
+//! abbreviation or a single-letter initial does not end a sentence.
+//! `parse_text` parses each sentence, reporting where it sits in the text.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use core::ops::Range;
+
+use crate::{parse_profiled, GrammarOptions, LexItem, ParseOutcome};
+
+/// Abbreviations whose period does not end a sentence (compared without case)
+pub const ABBREVIATIONS: [&str; 12] = ["mr", "mrs", "ms", "dr", "prof", "st", "jr", "sr", "vs", "etc", "e.g", "i.e"];
+
+/// Closing punctuation that may follow a sentence terminator
+const CLOSERS: [char; 5] = ['"', '\'', ')', ']', '”'];
+
+/// Byte ranges of the sentences in `text`, trimmed of surrounding whitespace
+pub fn segment(text: &str) -> Vec<Range<usize>> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '.' | '!' | '?' => {
+                let mut end = i + c.len_utf8();
+                while let Some(&(j, closer)) = chars.peek().filter(|(_, next)| CLOSERS.contains(next) || matches!(next, '.' | '!' | '?')) {
+                    end = j + closer.len_utf8();
+                    chars.next();
+                }
+                let rest = text[end..].trim_start();
+                let opens = rest.starts_with(|next: char| {
+                    next.is_uppercase() || next.is_ascii_digit() || matches!(next, '"' | '\'' | '“' | '(')
+                });
+                let boundary = rest.is_empty() || (text[end..].starts_with(char::is_whitespace) && opens);
+                (boundary && !(c == '.' && abbreviated(&text[start..i]))).then_some(end)
+            }
+            '\n' if text[i + 1..].trim_start_matches([' ', '\t', '\r']).starts_with('\n') => Some(i),
+            _ => None,
+        };
+        if let Some(end) = end {
+            push(text, start..end, &mut sentences);
+            start = end;
+        }
+    }
+    push(text, start..text.len(), &mut sentences);
+    sentences
+}
+
+/// Whether the text before a period ends in an abbreviation or an initial
+fn abbreviated(before: &str) -> bool {
+    let word = before.rsplit(char::is_whitespace).next().unwrap_or("").trim_start_matches(['"', '\'', '(', '“']);
+    let lower = word.to_lowercase();
+    ABBREVIATIONS.contains(&lower.as_str()) || (word.chars().count() == 1 && word.chars().all(char::is_uppercase))
+}
+
+/// Record the trimmed range, if it has any text
+fn push(text: &str, range: Range<usize>, sentences: &mut Vec<Range<usize>>) {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    if start < end {
+        sentences.push(start..end);
+    }
+}
+
+/// A sentence without its terminator and closing punctuation
+fn words(sentence: &str) -> &str {
+    sentence.trim_end_matches(|c: char| matches!(c, '.' | '!' | '?') || CLOSERS.contains(&c))
+}
+
+/// Segment `text` and parse each sentence, in order
+///
+/// Each outcome's span locates its sentence in `text`, terminator
+/// included; the terminator itself is not parsed. Sentences keep their
+/// capitals, so running text wants a case-folding normalizer in `options`.
+pub fn parse_text(text: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Vec<ParseOutcome> {
+    segment(text)
+        .into_iter()
+        .map(|span| ParseOutcome { span: span.clone(), ..parse_profiled(words(&text[span]), lexicon, options) })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Normalizer;
+
+    #[test]
+    fn test_segment_by_rule() {
+        let text = "The student left. Dr. Smith smiled! \"Who arrived?\" Mr. J. Doe asked\n\nthe tutor smiled";
+        let sentences: Vec<&str> = segment(text).into_iter().map(|span| &text[span]).collect();
+        assert_eq!(sentences, [
+            "The student left.",
+            "Dr. Smith smiled!",
+            "\"Who arrived?\"",
+            "Mr. J. Doe asked",
+            "the tutor smiled",
+        ]);
+
+        // No split before a lowercase word or inside a number
+        assert_eq!(segment("it cost 3.50 dollars. then it left").len(), 1);
+        assert!(segment("  \n ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_text_reports_offsets() {
+        let options = GrammarOptions { normalizer: Normalizer::raw_text(), ..GrammarOptions::default() };
+        let lexicon = options.lexicon();
+        let text = "The student left.  The teacher smiled!\nStudent the left.";
+        let outcomes = parse_text(text, &lexicon, &options);
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(&text[outcomes[1].span.clone()], "The teacher smiled!");
+        assert_eq!(outcomes[0].result.as_ref().unwrap().tree.linearize(), "the student left");
+        assert!(outcomes[1].result.is_ok());
+        assert!(outcomes[2].result.is_err());
+    }
+}
@@ -0,0 +1,138 @@
+//! Sentence Simplification
+//!
+//! Adjuncts are optional by construction: the host of an adjunction keeps
+//! its label and features, so cutting the adjunct leaves a tree that is
+//! just as well-formed. Pruning adjuncts and re-linearizing therefore
+//! yields simplified paraphrases: `the student near the teacher who left
+//! smiled` becomes `the student smiled`. Relative clauses are pruned
+//! whenever they are adjuncts, as under the operator-movement analysis; a
+//! promotion relative is the complement of its nominal head and stays.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{parse_with_options, DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Index of the adjunct if `node` was built by adjunction
+///
+/// Merge and Move nodes have a head carrying the selector or licensor it
+/// checked; an adjunction node's head is the saturated host, and the other
+/// daughter carries the adjunct feature.
+fn adjunct_index(node: &SyntacticObject) -> Option<usize> {
+    if node.children.len() != 2 {
+        return None;
+    }
+    let head = node.head_index()?;
+    let checked = node.children[head].features.iter().any(|f| f.is_selector() || f.is_positive());
+    let adjunct = 1 - head;
+    let marked = node.children[adjunct].features.iter().any(|f| matches!(f, Feature::Adjoin(_)));
+    (!checked && marked).then_some(adjunct)
+}
+
+/// The tree with every adjunct removed
+pub fn prune_adjuncts(tree: &SyntacticObject) -> SyntacticObject {
+    if let Some(adjunct) = adjunct_index(tree) {
+        return prune_adjuncts(&tree.children[1 - adjunct]);
+    }
+    let mut pruned = tree.clone();
+    pruned.children = tree.children.iter().map(prune_adjuncts).collect();
+    pruned
+}
+
+/// Adjuncts of the tree, outermost first, as child-index paths to their
+/// adjunction nodes
+fn adjunctions(node: &SyntacticObject, path: &mut Vec<usize>, found: &mut Vec<Vec<usize>>) {
+    if adjunct_index(node).is_some() {
+        found.push(path.clone());
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        adjunctions(child, path, found);
+        path.pop();
+    }
+}
+
+/// Simplified paraphrases of a tree, shortest last
+///
+/// Each adjunct is dropped on its own, outermost first, and then all of
+/// them at once; repeated strings and the original are left out.
+pub fn simplifications(tree: &SyntacticObject) -> Vec<String> {
+    let mut paths = Vec::new();
+    adjunctions(tree, &mut Vec::new(), &mut paths);
+
+    let original = tree.linearize();
+    let mut paraphrases: Vec<String> = Vec::new();
+    let candidates = paths.iter()
+        .map(|path| {
+            let mut pruned = tree.clone();
+            let node = path.iter().fold(&mut pruned, |node, &i| &mut node.children[i]);
+            if let Some(adjunct) = adjunct_index(node) {
+                *node = node.children.swap_remove(1 - adjunct);
+            }
+            pruned.linearize()
+        })
+        .chain(core::iter::once(prune_adjuncts(tree).linearize()));
+    for candidate in candidates {
+        if candidate != original && !paraphrases.contains(&candidate) {
+            paraphrases.push(candidate);
+        }
+    }
+    paraphrases
+}
+
+/// Parse a sentence and linearize it without its adjuncts
+pub fn simplify(sentence: &str, lexicon: &[LexItem], options: &GrammarOptions) -> Result<String, DerivationError> {
+    parse_with_options(sentence, lexicon, options).map(|tree| prune_adjuncts(&tree).linearize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{check_invariants, Category, RelativeClauseAnalysis};
+
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = GrammarOptions::default().lexicon();
+        lexicon.extend([
+            LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N), Feature::Adjoin(Category::V)]),
+            LexItem::new("is", &[Feature::Cat(Category::V), Feature::Sel(Category::P), Feature::Spec(Category::D)]),
+        ]);
+        lexicon
+    }
+
+    #[test]
+    fn test_adjuncts_are_pruned() {
+        let lexicon = lexicon();
+        let options = GrammarOptions::default();
+        assert_eq!(simplify("the student near the teacher left", &lexicon, &options), Ok("the student left".into()));
+        assert_eq!(simplify("the student who left smiled", &lexicon, &options), Ok("the student smiled".into()));
+
+        let tree = parse_with_options("the student near the teacher who left smiled", &lexicon, &options).unwrap();
+        let pruned = prune_adjuncts(&tree);
+        assert_eq!(pruned.linearize(), "the student smiled");
+        assert_eq!(pruned.label, tree.label);
+        assert!(check_invariants(&pruned).is_ok());
+    }
+
+    #[test]
+    fn test_complements_are_kept() {
+        // 'near' is an adjunct to nouns but the complement of 'is'
+        let lexicon = lexicon();
+        let options = GrammarOptions::default();
+        assert_eq!(simplify("the student is near the teacher", &lexicon, &options), Ok("the student is near the teacher".into()));
+
+        // A promotion relative is selected, not adjoined
+        let options = GrammarOptions { relative_clauses: RelativeClauseAnalysis::Promotion, ..Default::default() };
+        let sentence = "the student who left smiled";
+        assert_eq!(simplify(sentence, &options.lexicon(), &options), Ok(sentence.into()));
+    }
+
+    #[test]
+    fn test_paraphrases() {
+        let lexicon = lexicon();
+        let tree = parse_with_options("the student near the teacher who left smiled", &lexicon, &GrammarOptions::default()).unwrap();
+        let paraphrases = simplifications(&tree);
+        assert_eq!(paraphrases.last().map(String::as_str), Some("the student smiled"));
+        assert!(paraphrases.iter().all(|paraphrase| paraphrase.ends_with("smiled")));
+        assert!(paraphrases.len() >= 2);
+    }
+}
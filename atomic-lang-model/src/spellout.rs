@@ -0,0 +1,587 @@
+//! Morphophonological Spell-Out
+//!
+//! Lexical items are morphemes, so a derivation pronounced word by word
+//! comes out as `the student -ed not leave`. Spell-out rewrites the
+//! linearized PF with an ordered list of rules, configured per grammar:
+//! subject agreement, affix hopping, do-support for stranded affixes,
+//! allomorph selection, contraction, second-position clitic placement,
+//! phrasal spell-out of decomposed heads (see `nanosyntax`), and late
+//! insertion of exponents for abstract terminals (see `vocabulary`). A
+//! bound morpheme is written with a leading hyphen (`-ed`, `-s`);
+//! allomorph entries use the same hyphen for stem endings.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+
+use crate::nanosyntax::{self, Lexicalization};
+use crate::pf::PfNode;
+use crate::phi::{self, Phi, PhiNode};
+use crate::vocabulary::{self, Terminal, VocabularyItem};
+use crate::Category;
+
+/// Morpheme rewrite rule applied after linearization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PfRule {
+    /// Constituents are lexicalized by stored spans of heads; it reads the
+    /// morphemes as linearized, so it precedes rules that move or fuse them
+    PhrasalSpellOut(Vec<Lexicalization>),
+    /// Abstract terminals receive the exponent winning their competition;
+    /// a null word disappears
+    VocabularyInsertion(Vec<VocabularyItem>),
+    /// An agreement affix is realized by its subject's phi-features
+    Agreement(Agreement),
+    /// An affix attaches to an immediately following verb, unless a listed
+    /// word intervenes (Lasnik's adjacency condition)
+    AffixHopping {
+        /// Words that block adjacency, e.g. `not`
+        blockers: Vec<String>,
+    },
+    /// A stranded affix is supported by a dummy verb
+    DoSupport {
+        /// Supporting verb, e.g. `do`
+        host: String,
+    },
+    /// Stems and affixes are realized as listed, falling back to concatenation
+    Allomorphy(Vec<Allomorph>),
+    /// Adjacent words fuse into a contracted form
+    Contraction(Vec<Contraction>),
+    /// Clitics cluster in second position (Wackernagel's law)
+    SecondPosition {
+        /// Clitics in cluster order
+        clitics: Vec<String>,
+        /// What the cluster follows
+        host: ClusterHost,
+    },
+}
+
+/// Host of a second-position clitic cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterHost {
+    /// The first prosodic word, splitting its phrase if need be
+    FirstWord,
+    /// The first constituent of the clause
+    FirstPhrase,
+}
+
+/// Subject agreement: which affix agrees, and how
+///
+/// The subject is the specifier of the phrase the affix heads, and its
+/// phi-features are those of its first noun or listed nominal (the head
+/// noun, in a head-initial grammar), so nouns inside modifiers do not act
+/// as attractors. A subject split by a coordinator takes the features its
+/// conjuncts resolve to. Exponents compete by the Subset Principle (see
+/// `phi`), so an underspecified exponent covers several cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Agreement {
+    /// Abstract agreement affix, e.g. `-pres`
+    pub affix: String,
+    /// Phi-features of listed nominals; others are third person singular
+    pub nominals: Vec<(String, Phi)>,
+    /// Exponents with the phi-features they realize
+    pub exponents: Vec<(Phi, String)>,
+    /// Words joining conjuncts, e.g. `and`
+    pub coordinators: Vec<String>,
+    /// Affix for combinations without an exponent (`-` is null)
+    pub default: String,
+}
+
+impl Agreement {
+    /// English present tense: `-s` in the third person singular, `-1sg`
+    /// (null except on `be`) in the first, null wherever a participant is
+    /// involved or the subject is plural
+    pub fn english() -> Self {
+        let nominals = [
+            ("I", Phi::singular(1)),
+            ("we", Phi::plural(1)),
+            ("you", Phi::new(&[PhiNode::Addressee])),
+            ("he", Phi::singular(3)),
+            ("she", Phi::singular(3)),
+            ("it", Phi::singular(3)),
+            ("they", Phi::plural(3)),
+        ];
+        Self {
+            affix: "-pres".to_string(),
+            nominals: nominals.iter().map(|&(nominal, phi)| (nominal.to_string(), phi)).collect(),
+            exponents: vec![
+                (Phi::THIRD_SINGULAR, "-s".to_string()),
+                (Phi::new(&[PhiNode::Participant]), "-".to_string()),
+                (Phi::singular(1), "-1sg".to_string()),
+            ],
+            coordinators: vec!["and".to_string()],
+            default: "-".to_string(),
+        }
+    }
+
+    /// Phi-features of a listed nominal
+    pub fn listed(&self, word: &str) -> Option<Phi> {
+        self.nominals.iter().find(|(nominal, _)| nominal == word).map(|&(_, phi)| phi)
+    }
+
+    fn phi(&self, subject: &PfNode) -> Phi {
+        let mut words = Vec::new();
+        collect(subject, &mut words);
+        let conjuncts: Vec<Phi> = words.split(|word| self.coordinators.contains(&word.stem))
+            .filter_map(|conjunct| {
+                conjunct.iter().find_map(|word| self.listed(&word.stem).or((word.label.head() == Category::N).then_some(Phi::THIRD_SINGULAR)))
+            })
+            .collect();
+        match conjuncts.as_slice() {
+            [] => Phi::THIRD_SINGULAR,
+            [phi] => *phi,
+            _ => Phi::resolve(&conjuncts),
+        }
+    }
+
+    fn exponent(&self, phi: Phi) -> &str {
+        phi::realize(&self.exponents, phi).unwrap_or(&self.default)
+    }
+
+    /// Exponents for the agreement affixes of `node`, in linear order
+    fn realize<'a>(&'a self, node: &PfNode, subject: Phi, exponents: &mut Vec<&'a str>) {
+        match node {
+            PfNode::Word(_, word) if *word == self.affix => exponents.push(self.exponent(subject)),
+            PfNode::Phrase(_, children) => {
+                let heads_affix = |child: &PfNode| {
+                    let mut words = Vec::new();
+                    collect(child, &mut words);
+                    words.first().is_some_and(|word| word.stem == self.affix)
+                };
+                let specified = match children.as_slice() {
+                    [specifier, head] if heads_affix(head) => Some(self.phi(specifier)),
+                    _ => None,
+                };
+                for (i, child) in children.iter().enumerate() {
+                    let subject = if i == 1 { specified.unwrap_or(subject) } else { subject };
+                    self.realize(child, subject, exponents);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Realization of a stem with an affix
+///
+/// A stem with a leading hyphen matches any stem with that ending, which
+/// `form` (also hyphenated) replaces; exact stems take precedence, then
+/// longer endings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allomorph {
+    /// Stem, or `-ending`
+    pub stem: String,
+    /// Affix, e.g. `-ed`
+    pub affix: String,
+    /// Realized word, or `-ending` replacing the stem's ending
+    pub form: String,
+}
+
+impl Allomorph {
+    /// Entry realizing `stem` with `affix` as `form`
+    pub fn new(stem: &str, affix: &str, form: &str) -> Self {
+        Self { stem: stem.to_string(), affix: affix.to_string(), form: form.to_string() }
+    }
+}
+
+/// Two adjacent words and their contracted form
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contraction {
+    /// First word, e.g. `did`
+    pub first: String,
+    /// Second word, e.g. `not`
+    pub second: String,
+    /// Contracted form, e.g. `didn't`
+    pub form: String,
+}
+
+impl Contraction {
+    /// Contraction of `first second` to `form`
+    pub fn new(first: &str, second: &str, form: &str) -> Self {
+        Self { first: first.to_string(), second: second.to_string(), form: form.to_string() }
+    }
+}
+
+/// Word under construction: a stem and the affixes attached to it
+#[derive(Debug, Clone)]
+struct Morph {
+    label: Category,
+    /// Clause-level constituent the word was spelled out in
+    constituent: usize,
+    stem: String,
+    affixes: Vec<String>,
+}
+
+impl Morph {
+    fn is_affix(&self) -> bool {
+        self.stem.starts_with('-')
+    }
+
+    fn spelled(&self, table: &[Allomorph]) -> String {
+        self.affixes.iter().fold(self.stem.clone(), |stem, affix| attach(&stem, affix, table))
+    }
+}
+
+/// Ordered PF rules of a grammar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpellOut {
+    /// Rules, applied in order
+    pub rules: Vec<PfRule>,
+}
+
+impl SpellOut {
+    /// English tense morphology: present-tense agreement, hopping blocked
+    /// by `not`, do-support, irregular and orthographic allomorphs, and
+    /// negative contraction
+    pub fn english() -> Self {
+        let allomorphs = [
+            ("be", "-1sg", "am"),
+            ("be", "-s", "is"),
+            ("be", "-", "are"),
+            ("do", "-ed", "did"),
+            ("do", "-s", "does"),
+            ("go", "-ed", "went"),
+            ("go", "-s", "goes"),
+            ("have", "-ed", "had"),
+            ("have", "-s", "has"),
+            ("leave", "-ed", "left"),
+            ("say", "-ed", "said"),
+            ("see", "-ed", "saw"),
+            ("think", "-ed", "thought"),
+            ("-", "-1sg", "-"),
+            ("-e", "-ed", "-ed"),
+            ("-s", "-s", "-ses"),
+            ("-sh", "-s", "-shes"),
+            ("-ch", "-s", "-ches"),
+            ("-x", "-s", "-xes"),
+            ("-z", "-s", "-zes"),
+            ("-y", "-s", "-ies"),
+            ("-y", "-ed", "-ied"),
+            ("-ay", "-s", "-ays"),
+            ("-ay", "-ed", "-ayed"),
+            ("-ey", "-s", "-eys"),
+            ("-ey", "-ed", "-eyed"),
+            ("-oy", "-s", "-oys"),
+            ("-oy", "-ed", "-oyed"),
+        ];
+        let contractions = [
+            ("did", "not", "didn't"),
+            ("does", "not", "doesn't"),
+            ("do", "not", "don't"),
+            ("has", "not", "hasn't"),
+            ("had", "not", "hadn't"),
+            ("is", "not", "isn't"),
+            ("are", "not", "aren't"),
+            ("will", "not", "won't"),
+        ];
+        Self {
+            rules: vec![
+                PfRule::Agreement(Agreement::english()),
+                PfRule::AffixHopping { blockers: vec!["not".to_string()] },
+                PfRule::DoSupport { host: "do".to_string() },
+                PfRule::Allomorphy(allomorphs.iter().map(|&(stem, affix, form)| Allomorph::new(stem, affix, form)).collect()),
+                PfRule::Contraction(contractions.iter().map(|&(first, second, form)| Contraction::new(first, second, form)).collect()),
+            ],
+        }
+    }
+
+    /// Serbo-Croatian clitic cluster: the question particle, auxiliaries,
+    /// dative, accusative and reflexive pronouns, then auxiliary `je`
+    pub fn serbo_croatian(host: ClusterHost) -> Self {
+        let clitics = ["li", "sam", "si", "smo", "ste", "su", "mi", "ti", "mu", "joj", "me", "te", "ga", "se", "je"];
+        let clitics = clitics.iter().map(|clitic| clitic.to_string()).collect();
+        Self { rules: vec![PfRule::SecondPosition { clitics, host }] }
+    }
+
+    /// Spell out the words of a PF, applying each rule in order
+    ///
+    /// Affixes still unattached after the last rule are pronounced as
+    /// plain suffixes of their stem.
+    pub fn apply(&self, pf: &PfNode) -> Vec<String> {
+        let mut morphs = Vec::new();
+        collect(pf, &mut morphs);
+        for rule in &self.rules {
+            match rule {
+                PfRule::PhrasalSpellOut(entries) => {
+                    for (words, exponent) in nanosyntax::phrasal_spell_out(pf, entries).into_iter().rev() {
+                        if words.end > morphs.len() {
+                            continue;
+                        }
+                        // The word keeps the category of its lowest head
+                        let lowest = morphs[words.end - 1].label.clone();
+                        let mut word = morphs.drain(words.clone()).next().expect("lexicalized constituents have words");
+                        word.label = lowest;
+                        word.stem = exponent.to_string();
+                        word.affixes.clear();
+                        morphs.insert(words.start, word);
+                    }
+                }
+                PfRule::VocabularyInsertion(items) => {
+                    let exponents: Vec<Option<String>> = (0..morphs.len())
+                        .map(|i| {
+                            let terminal = Terminal::parse(&morphs[i].stem)?;
+                            let neighbors: Vec<&str> = [i.checked_sub(1), Some(i + 1)].into_iter()
+                                .filter_map(|j| morphs.get(j?))
+                                .map(|morph| morph.stem.as_str())
+                                .collect();
+                            vocabulary::insert(items, &terminal, &neighbors).map(|item| item.exponent.clone())
+                        })
+                        .collect();
+                    for (morph, exponent) in morphs.iter_mut().zip(exponents) {
+                        if let Some(exponent) = exponent {
+                            morph.stem = exponent;
+                        }
+                    }
+                    morphs.retain(|morph| !morph.stem.is_empty());
+                }
+                PfRule::Agreement(agreement) => {
+                    let mut exponents = Vec::new();
+                    agreement.realize(pf, Phi::THIRD_SINGULAR, &mut exponents);
+                    let affixes = morphs.iter_mut().filter(|morph| morph.stem == agreement.affix);
+                    for (morph, exponent) in affixes.zip(exponents) {
+                        morph.stem = exponent.to_string();
+                    }
+                }
+                PfRule::AffixHopping { blockers } => hop(&mut morphs, blockers),
+                PfRule::DoSupport { host } => {
+                    for morph in morphs.iter_mut().filter(|morph| morph.is_affix()) {
+                        let affix = core::mem::replace(&mut morph.stem, host.clone());
+                        morph.label = Category::V;
+                        morph.affixes.insert(0, affix);
+                    }
+                }
+                PfRule::Allomorphy(table) => {
+                    for morph in &mut morphs {
+                        morph.stem = morph.spelled(table);
+                        morph.affixes.clear();
+                    }
+                }
+                PfRule::Contraction(contractions) => contract(&mut morphs, contractions),
+                PfRule::SecondPosition { clitics, host } => second_position(&mut morphs, clitics, *host),
+            }
+        }
+        morphs.iter().map(|morph| morph.spelled(&[])).collect()
+    }
+
+    /// Spelled-out string
+    pub fn pronounce(&self, pf: &PfNode) -> String {
+        self.apply(pf).join(" ")
+    }
+}
+
+/// Overt words of a PF as bare stems, numbering the root's daughters
+fn collect(node: &PfNode, morphs: &mut Vec<Morph>) {
+    fn words(node: &PfNode, constituent: usize, morphs: &mut Vec<Morph>) {
+        match node {
+            PfNode::Word(label, word) if !word.is_empty() => {
+                morphs.push(Morph { label: label.clone(), constituent, stem: word.clone(), affixes: Vec::new() });
+            }
+            PfNode::Phrase(_, children) => children.iter().for_each(|child| words(child, constituent, morphs)),
+            _ => {}
+        }
+    }
+    
+    match node {
+        PfNode::Phrase(_, children) => {
+            children.iter().enumerate().for_each(|(constituent, child)| words(child, constituent, morphs));
+        }
+        _ => words(node, 0, morphs),
+    }
+}
+
+fn hop(morphs: &mut Vec<Morph>, blockers: &[String]) {
+    let mut i = 0;
+    while i + 1 < morphs.len() {
+        let next = &morphs[i + 1];
+        let adjacent_verb = next.label.head() == Category::V && !next.is_affix() && !blockers.contains(&next.stem);
+        if morphs[i].is_affix() && adjacent_verb {
+            let affix = morphs.remove(i).stem;
+            morphs[i].affixes.push(affix);
+        }
+        i += 1;
+    }
+}
+
+fn contract(morphs: &mut Vec<Morph>, contractions: &[Contraction]) {
+    let mut i = 0;
+    while i + 1 < morphs.len() {
+        let (first, second) = (morphs[i].spelled(&[]), morphs[i + 1].spelled(&[]));
+        if let Some(contraction) = contractions.iter().find(|c| c.first == first && c.second == second) {
+            morphs[i].stem = contraction.form.clone();
+            morphs[i].affixes.clear();
+            morphs.remove(i + 1);
+        }
+        i += 1;
+    }
+}
+
+/// Gather the clitics, in cluster order, after the host
+fn second_position(morphs: &mut Vec<Morph>, clitics: &[String], host: ClusterHost) {
+    let rank = |morph: &Morph| clitics.iter().position(|clitic| *clitic == morph.stem);
+    let (mut cluster, rest): (Vec<Morph>, Vec<Morph>) = morphs.drain(..).partition(|morph| rank(morph).is_some());
+    cluster.sort_by_key(rank);
+    
+    let at = match (rest.first(), host) {
+        (None, _) => 0,
+        (Some(_), ClusterHost::FirstWord) => 1,
+        (Some(first), ClusterHost::FirstPhrase) => {
+            rest.iter().take_while(|morph| morph.constituent == first.constituent).count()
+        }
+    };
+    *morphs = rest;
+    morphs.splice(at..at, cluster);
+}
+
+/// Realize `stem` with `affix`: a listed stem, else the longest listed
+/// ending, else concatenation
+fn attach(stem: &str, affix: &str, table: &[Allomorph]) -> String {
+    let entries = table.iter().filter(|entry| entry.affix == affix);
+    if let Some(entry) = entries.clone().find(|entry| entry.stem == stem) {
+        return entry.form.clone();
+    }
+    let ending = entries
+        .filter_map(|entry| Some((entry.stem.strip_prefix('-')?, entry.form.strip_prefix('-')?)))
+        .filter(|(ending, _)| stem.ends_with(ending) && stem.len() > ending.len())
+        .max_by_key(|(ending, _)| ending.len());
+    match ending {
+        Some((ending, form)) => [&stem[..stem.len() - ending.len()], form].concat(),
+        None => [stem, affix.trim_start_matches('-')].concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pf::spell_out;
+    use crate::{parse_sentence, test_lexicon, Feature, GrammarOptions, LexItem};
+
+    /// English fragment with tense as a head selecting the verb phrase
+    fn fragment() -> Vec<LexItem> {
+        let mut lexicon = vec![
+            LexItem::new("-ed", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("-s", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("-pres", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+            LexItem::new("not", &[Feature::Cat(Category::V), Feature::Sel(Category::V)]),
+            LexItem::new("leave", &[Feature::Cat(Category::V)]),
+            LexItem::new("smile", &[Feature::Cat(Category::V)]),
+            LexItem::new("study", &[Feature::Cat(Category::V)]),
+            LexItem::new("watch", &[Feature::Cat(Category::V), Feature::Sel(Category::DP)]),
+            LexItem::new("be", &[Feature::Cat(Category::V)]),
+            LexItem::new("students", &[Feature::Cat(Category::N)]),
+            LexItem::new("teachers", &[Feature::Cat(Category::N)]),
+            LexItem::new("I", &[Feature::Cat(Category::D)]),
+            LexItem::new("they", &[Feature::Cat(Category::D)]),
+            LexItem::new("you", &[Feature::Cat(Category::D)]),
+            LexItem::new("thou", &[Feature::Cat(Category::D)]),
+            LexItem::new("and", &[Feature::Cat(Category::D), Feature::Sel(Category::DP), Feature::Spec(Category::D)]),
+            LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]),
+        ];
+        lexicon.extend(test_lexicon());
+        lexicon
+    }
+
+    fn pronounce(spell_out_rules: &SpellOut, sentence: &str) -> String {
+        let tree = parse_sentence(sentence, &fragment()).unwrap();
+        spell_out_rules.pronounce(&spell_out(&tree))
+    }
+
+    #[test]
+    fn test_english_spell_out() {
+        let english = SpellOut::english();
+        for (morphemes, english_output) in [
+            ("the student -ed leave", "the student left"),
+            ("the student -ed smile", "the student smiled"),
+            ("the student -s watch the teacher", "the student watches the teacher"),
+            ("the tutor -s study", "the tutor studies"),
+            ("the student -ed not leave", "the student didn't leave"),
+            ("the student -s not smile", "the student doesn't smile"),
+        ] {
+            assert_eq!(pronounce(&english, morphemes), english_output);
+        }
+
+        // Without the rules, morphemes are pronounced as linearized
+        let tree = parse_sentence("the student -ed leave", &fragment()).unwrap();
+        assert_eq!(GrammarOptions::default().pronounce(&tree), Ok("the student -ed leave".to_string()));
+        let options = GrammarOptions { spell_out: english, ..GrammarOptions::default() };
+        assert_eq!(options.pronounce(&tree), Ok("the student left".to_string()));
+    }
+
+    #[test]
+    fn test_agreement_realizes_phi_features() {
+        let mut english = SpellOut::english();
+        if let PfRule::Agreement(agreement) = &mut english.rules[0] {
+            for noun in ["students", "teachers"] {
+                agreement.nominals.push((noun.to_string(), Phi::plural(3)));
+            }
+            agreement.nominals.push(("thou".to_string(), Phi::singular(2)));
+        }
+        for (morphemes, english_output) in [
+            ("the student -pres leave", "the student leaves"),
+            ("the students -pres leave", "the students leave"),
+            ("the student -pres watch the teachers", "the student watches the teachers"),
+            ("the students -pres not leave", "the students don't leave"),
+            ("I -pres be", "I am"),
+            ("they -pres be", "they are"),
+            // The head noun agrees, not the nearer attractor
+            ("the student near the teachers -pres be", "the student near the teachers is"),
+            ("the students near the teacher -pres leave", "the students near the teacher leave"),
+            // 'you' is unspecified for number; coordinations resolve to plural
+            ("you -pres be", "you are"),
+            // A singular addressee is a participant, not a third singular
+            ("thou -pres leave", "thou leave"),
+            ("the student and the teacher -pres leave", "the student and the teacher leave"),
+            ("you and I -pres be", "you and I are"),
+        ] {
+            assert_eq!(pronounce(&english, morphemes), english_output);
+        }
+    }
+
+    #[test]
+    fn test_rules_are_configurable() {
+        let hopping = PfRule::AffixHopping { blockers: vec!["not".to_string()] };
+        let support = SpellOut { rules: vec![hopping.clone(), PfRule::DoSupport { host: "do".to_string() }] };
+        assert_eq!(pronounce(&support, "the student -ed not leave"), "the student doed not leave");
+
+        // Without do-support the stranded affix stays bare
+        let stranded = SpellOut { rules: vec![hopping] };
+        assert_eq!(pronounce(&stranded, "the student -ed not leave"), "the student -ed not leave");
+
+        let table = [Allomorph::new("-y", "-s", "-ies"), Allomorph::new("-ay", "-s", "-ays")];
+        assert_eq!(attach("study", "-s", &table), "studies");
+        assert_eq!(attach("play", "-s", &table), "plays");
+        assert_eq!(attach("y", "-s", &table), "ys");
+    }
+
+    /// Serbo-Croatian fragment: an auxiliary head taking the subject and a
+    /// ditransitive participle with a dative clitic in its specifier
+    fn serbo_croatian() -> Vec<LexItem> {
+        vec![
+            LexItem::new("taj", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+            LexItem::new("pesnik", &[Feature::Cat(Category::N)]),
+            LexItem::new("knjigu", &[Feature::Cat(Category::D)]),
+            LexItem::new("mi", &[Feature::Cat(Category::D)]),
+            LexItem::new("dao", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+            LexItem::new("je", &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]),
+        ]
+    }
+
+    #[test]
+    fn test_second_position_clitics() {
+        // 'That poet gave me a book', in the order narrow syntax derives
+        let tree = parse_sentence("taj pesnik je mi dao knjigu", &serbo_croatian()).unwrap();
+        let pf = spell_out(&tree);
+
+        let phrase = SpellOut::serbo_croatian(ClusterHost::FirstPhrase);
+        assert_eq!(phrase.pronounce(&pf), "taj pesnik mi je dao knjigu");
+        // Splitting the subject, as in 'Taj mi je pesnik dao knjigu'
+        let word = SpellOut::serbo_croatian(ClusterHost::FirstWord);
+        assert_eq!(word.pronounce(&pf), "taj mi je pesnik dao knjigu");
+
+        let mut cluster = vec![
+            Morph { label: Category::D, constituent: 0, stem: "ga".to_string(), affixes: Vec::new() },
+            Morph { label: Category::V, constituent: 1, stem: "vidio".to_string(), affixes: Vec::new() },
+            Morph { label: Category::S, constituent: 1, stem: "sam".to_string(), affixes: Vec::new() },
+        ];
+        second_position(&mut cluster, &[String::from("sam"), String::from("ga")], ClusterHost::FirstWord);
+        let stems: Vec<&str> = cluster.iter().map(|morph| morph.stem.as_str()).collect();
+        assert_eq!(stems, ["vidio", "sam", "ga"]);
+    }
+}
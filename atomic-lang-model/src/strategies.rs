@@ -0,0 +1,179 @@
+//! Property-Testing Strategies
+//!
+//! `proptest` strategies for lexical items, feature bundles, small grammars,
+//! and derived syntactic objects, together with the invariants the engine
+//! guarantees. Enabled by the `proptest` feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! atomic-lang-model = { version = "0.1", features = ["proptest"] }
+//! ```
+//!
+//! Grammar developers can combine these with their own lexicons, e.g. to
+//! check that every generated item merges with the items it selects.
+
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+
+use crate::{merge, Category, Feature, LexItem, SyntacticObject};
+
+/// Any category label
+pub fn category() -> impl Strategy<Value = Category> {
+    prop_oneof![
+        Just(Category::N), Just(Category::V), Just(Category::D), Just(Category::C),
+        Just(Category::P), Just(Category::S), Just(Category::NP), Just(Category::VP),
+        Just(Category::DP), Just(Category::CP), Just(Category::PP),
+    ]
+}
+
+/// Movement feature index
+pub fn movement_index() -> impl Strategy<Value = u8> {
+    1u8..=3
+}
+
+/// Any single feature
+pub fn feature() -> impl Strategy<Value = Feature> {
+    prop_oneof![
+        category().prop_map(Feature::Cat),
+        category().prop_map(Feature::Sel),
+        category().prop_map(Feature::Spec),
+        category().prop_map(Feature::Adjoin),
+        movement_index().prop_map(Feature::Pos),
+        movement_index().prop_map(Feature::Neg),
+    ]
+}
+
+/// Well-formed lexical feature bundle: one category, then up to two
+/// selectors, an optional licensor, an optional adjunct feature, and an
+/// optional licensee
+pub fn feature_bundle() -> impl Strategy<Value = Vec<Feature>> {
+    let selector = prop_oneof![
+        category().prop_map(Feature::Sel),
+        category().prop_map(Feature::Spec),
+    ];
+    (
+        category(),
+        vec(selector, 0..=2),
+        option::of(movement_index()),
+        option::of(category()),
+        option::of(movement_index()),
+    )
+        .prop_map(|(cat, selectors, licensor, adjunct, licensee)| {
+            let mut features = vec![Feature::Cat(cat)];
+            features.extend(selectors);
+            features.extend(licensor.map(Feature::Pos));
+            features.extend(adjunct.map(Feature::Adjoin));
+            features.extend(licensee.map(Feature::Neg));
+            features
+        })
+}
+
+/// Lexical item with a lowercase phonological form
+pub fn lex_item() -> impl Strategy<Value = LexItem> {
+    ("[a-z]{1,8}", feature_bundle()).prop_map(|(phon, feats)| LexItem::new(&phon, &feats))
+}
+
+/// Small grammar: up to `size` items with distinct phonological forms
+pub fn grammar(size: usize) -> impl Strategy<Value = Vec<LexItem>> {
+    vec(lex_item(), 1..=size.max(1)).prop_map(|mut items| {
+        let mut seen = Vec::new();
+        items.retain(|item| {
+            let fresh = !seen.contains(&item.phon);
+            seen.push(item.phon.clone());
+            fresh
+        });
+        items
+    })
+}
+
+/// Saturated leaf of the given category
+fn saturated_leaf(cat: Category) -> impl Strategy<Value = SyntacticObject> {
+    "[a-z]{1,8}".prop_map(move |phon| SyntacticObject::from_lex(&LexItem::new(&phon, &[Feature::Cat(cat.clone())])))
+}
+
+/// Selector and selectee that merge: the selectee is a saturated leaf of the
+/// category the selector's first selector asks for
+pub fn mergeable_pair() -> impl Strategy<Value = (SyntacticObject, SyntacticObject)> {
+    (category(), any::<bool>(), "[a-z]{1,8}", feature_bundle()).prop_flat_map(|(cat, spec, phon, rest)| {
+        let selector = if spec { Feature::Spec(cat.clone()) } else { Feature::Sel(cat.clone()) };
+        let mut feats = vec![rest[0].clone(), selector];
+        feats.extend(rest.into_iter().skip(1));
+        let head = SyntacticObject::from_lex(&LexItem::new(&phon, &feats));
+        (Just(head), saturated_leaf(cat))
+    })
+}
+
+/// Saturated object built by repeatedly merging a fresh head with the
+/// object derived so far, as complement or specifier
+pub fn derived_object() -> impl Strategy<Value = SyntacticObject> {
+    saturated_leaf(Category::D).prop_recursive(6, 32, 1, |inner| {
+        (inner, category(), any::<bool>(), "[a-z]{1,8}").prop_map(|(selectee, cat, spec, phon)| {
+            let required = selectee.category().cloned().unwrap_or(Category::D);
+            let selector = if spec { Feature::Spec(required) } else { Feature::Sel(required) };
+            let head = SyntacticObject::from_lex(&LexItem::new(&phon, &[Feature::Cat(cat), selector]));
+            merge(head, selectee).expect("head selects the selectee's category")
+        })
+    })
+}
+
+/// Merge checks exactly one selector of `a`: the result has one fewer
+/// selector than `a` (`b` must be saturated to merge at all)
+pub fn merge_checks_one_selector(a: &SyntacticObject, b: &SyntacticObject) -> bool {
+    let selectors = |obj: &SyntacticObject| obj.features.iter().filter(|f| f.is_selector()).count();
+    match merge(a.clone(), b.clone()) {
+        Ok(merged) => selectors(&merged) + 1 == selectors(a),
+        Err(_) => true,
+    }
+}
+
+/// Linearization pronounces every leaf with phonological content exactly once
+pub fn linearize_matches_leaves(obj: &SyntacticObject) -> bool {
+    fn leaves(obj: &SyntacticObject) -> usize {
+        match &obj.phon {
+            Some(phon) => phon.split_whitespace().count(),
+            None => obj.children.iter().map(leaves).sum(),
+        }
+    }
+    obj.linearize().split_whitespace().count() == leaves(obj)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::can_merge;
+
+    proptest! {
+        #[test]
+        fn prop_bundles_lead_with_one_category(feats in feature_bundle()) {
+            prop_assert!(matches!(feats[0], Feature::Cat(_)));
+            prop_assert_eq!(feats.iter().filter(|f| matches!(f, Feature::Cat(_))).count(), 1);
+        }
+
+        #[test]
+        fn prop_grammar_forms_are_distinct(lexicon in grammar(8)) {
+            for (i, item) in lexicon.iter().enumerate() {
+                prop_assert!(lexicon[i + 1..].iter().all(|other| other.phon != item.phon));
+            }
+        }
+
+        #[test]
+        fn prop_merge_output_has_one_fewer_selector((a, b) in mergeable_pair()) {
+            prop_assert!(can_merge(&a, &b));
+            prop_assert!(merge_checks_one_selector(&a, &b));
+        }
+
+        #[test]
+        fn prop_arbitrary_merges_check_one_selector(a in lex_item(), b in lex_item()) {
+            let a = SyntacticObject::from_lex(&a);
+            let b = SyntacticObject::from_lex(&b);
+            prop_assert!(merge_checks_one_selector(&a, &b));
+        }
+
+        #[test]
+        fn prop_linearize_length_equals_leaf_count(obj in derived_object()) {
+            prop_assert!(obj.is_complete());
+            prop_assert!(linearize_matches_leaves(&obj));
+        }
+    }
+}
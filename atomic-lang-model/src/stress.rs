@@ -0,0 +1,108 @@
+//! aⁿbⁿ Stress Testing
+//!
+//! Generation and recognition of aⁿbⁿ for very large n (10⁵–10⁶), enabled
+//! by the `parallel` feature. The string is produced and checked in chunks
+//! on scoped threads; each chunk is summarized by an `AnBn` recognizer, and
+//! the summaries combine in order, so recognition stays linear in the input.
+//! The same recognizer verifies a stream of bytes in constant memory.
+
+use std::thread;
+
+use crate::patterns::{AnBn, Recognizer};
+
+/// Worker threads to use by default
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Byte at position `p` of the space-separated aⁿbⁿ string
+fn an_bn_byte(n: usize, p: usize) -> u8 {
+    match (p % 2, p / 2 < n) {
+        (1, _) => b' ',
+        (_, true) => b'a',
+        (_, false) => b'b',
+    }
+}
+
+/// Length of the space-separated aⁿbⁿ string
+fn an_bn_len(n: usize) -> usize {
+    (4 * n).saturating_sub(1)
+}
+
+/// Lazily yield the bytes of `generate_an_bn(n)` without building the string
+pub fn an_bn_bytes(n: usize) -> impl Iterator<Item = u8> {
+    (0..an_bn_len(n)).map(move |p| an_bn_byte(n, p))
+}
+
+/// Generate `generate_an_bn(n)` on up to `threads` threads
+pub fn generate_an_bn_parallel(n: usize, threads: usize) -> String {
+    let mut bytes = vec![0u8; an_bn_len(n)];
+    let chunk = bytes.len().div_ceil(threads.max(1)).max(1);
+    thread::scope(|scope| {
+        for (i, part) in bytes.chunks_mut(chunk).enumerate() {
+            scope.spawn(move || {
+                for (offset, byte) in part.iter_mut().enumerate() {
+                    *byte = an_bn_byte(n, i * chunk + offset);
+                }
+            });
+        }
+    });
+    String::from_utf8(bytes).expect("aⁿbⁿ is ASCII")
+}
+
+/// Check membership in aⁿbⁿ on up to `threads` threads (agrees with `is_an_bn_pattern`)
+pub fn is_an_bn_parallel(s: &str, threads: usize) -> bool {
+    let bytes = s.as_bytes();
+    let chunk = bytes.len().div_ceil(threads.max(1)).max(1);
+    let counters: Vec<AnBn> = thread::scope(|scope| {
+        let workers: Vec<_> = bytes.chunks(chunk)
+            .map(|part| scope.spawn(move || {
+                let mut counter = AnBn::default();
+                part.iter().for_each(|&byte| counter.push(byte));
+                counter
+            }))
+            .collect();
+        workers.into_iter().map(|worker| worker.join().expect("recognizer thread panicked")).collect()
+    });
+    counters.into_iter().fold(AnBn::default(), AnBn::then).accepts()
+}
+
+/// Verify a byte stream against aⁿbⁿ in constant memory
+pub fn verify_an_bn_stream(bytes: impl IntoIterator<Item = u8>) -> bool {
+    AnBn::recognize_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_an_bn, is_an_bn_pattern};
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        for n in [0, 1, 2, 7, 100] {
+            for threads in [1, 3, 8] {
+                let generated = generate_an_bn_parallel(n, threads);
+                assert_eq!(generated, generate_an_bn(n));
+                assert!(is_an_bn_parallel(&generated, threads));
+            }
+            assert!(an_bn_bytes(n).eq(generate_an_bn(n).bytes()));
+        }
+
+        for s in ["a a b", "b a", "a b a b", "a b c", "aabb", "  ", "a a b b b"] {
+            for threads in [1, 2, 5] {
+                assert_eq!(is_an_bn_parallel(s, threads), is_an_bn_pattern(s), "{:?}", s);
+            }
+            assert_eq!(verify_an_bn_stream(s.bytes()), is_an_bn_pattern(s), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_large_n() {
+        let n = 100_000;
+        let generated = generate_an_bn_parallel(n, default_threads());
+        assert_eq!(generated.len(), 4 * n - 1);
+        assert!(is_an_bn_parallel(&generated, default_threads()));
+        assert!(verify_an_bn_stream(an_bn_bytes(n)));
+        assert!(!verify_an_bn_stream(an_bn_bytes(n).chain(*b" b")));
+    }
+}
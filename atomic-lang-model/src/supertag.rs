@@ -0,0 +1,203 @@
+//! Supertagging
+//!
+//! A word with several lexical entries multiplies the derivations to
+//! search. A `Supertagger` scores each candidate entry of each token before
+//! parsing, and only the `k` best per token go on to the search, tried in
+//! order of score. `SelectionRules` scores entries by whether their
+//! neighbors can satisfy the features they select and the category they
+//! project; `TagWeights` looks scores up in a weight table. Without a
+//! limit every entry is tried, as in `parse_sentence`; `k = 1` commits to
+//! the best-scored entry of each word. The choices of entries are
+//! generated one at a time, and at most `MAX_TAG_SEQUENCES` are tried, so
+//! a long sentence of ambiguous words does not take time exponential in
+//! its length.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::{Category, DerivationError, Feature, LexItem};
+
+/// Scores candidate lexical entries for a token in context
+pub trait Supertagger {
+    /// Score of `entry` for the token at `position`; higher is better
+    fn score(&self, tokens: &[&str], position: usize, entry: &LexItem, lexicon: &[LexItem]) -> f64;
+}
+
+impl<F: Fn(&[&str], usize, &LexItem) -> f64> Supertagger for F {
+    fn score(&self, tokens: &[&str], position: usize, entry: &LexItem, _lexicon: &[LexItem]) -> f64 {
+        self(tokens, position, entry)
+    }
+}
+
+/// Scores every entry alike, so the lexicon's order decides
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexiconOrder;
+
+impl Supertagger for LexiconOrder {
+    fn score(&self, _tokens: &[&str], _position: usize, _entry: &LexItem, _lexicon: &[LexItem]) -> f64 {
+        0.0
+    }
+}
+
+/// Fraction of an entry's requirements its neighbors can meet
+///
+/// A complement selector needs a next token of the selected category, a
+/// specifier selector an earlier one, and an adjunct an earlier host. The
+/// entry's own category must be selected by the previous token, wanted as
+/// a specifier or host by a later one, or be clausal (a possible root).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SelectionRules;
+
+impl Supertagger for SelectionRules {
+    fn score(&self, tokens: &[&str], position: usize, entry: &LexItem, lexicon: &[LexItem]) -> f64 {
+        let entries: Vec<Vec<&LexItem>> = tokens.iter()
+            .map(|&token| lexicon.iter().filter(|item| item.phon == token).collect())
+            .collect();
+        let categories = |range: &[Vec<&LexItem>]| -> Vec<Category> {
+            range.iter()
+                .flatten()
+                .flat_map(|item| item.feats.iter().filter_map(|f| if let Feature::Cat(c) = f { Some(c.clone()) } else { None }))
+                .collect()
+        };
+        let before = categories(&entries[..position]);
+        let next = categories(entries.get(position + 1..position + 2).unwrap_or(&[]));
+        let previous = &entries[position.saturating_sub(1)..position];
+        let later = &entries[position + 1..];
+
+        let selects = |range: &[Vec<&LexItem>], cat: &Category, wanted: fn(&Feature) -> Option<&Category>| {
+            range.iter().flatten().any(|item| item.feats.iter().filter_map(wanted).any(|c| c.matches(cat)))
+        };
+        let (mut met, mut total) = (0, 0);
+        for feature in &entry.feats {
+            let satisfied = match feature {
+                Feature::Sel(c) => next.iter().any(|n| n.matches(c)),
+                Feature::Spec(c) | Feature::Adjoin(c) => before.iter().any(|b| b.matches(c)),
+                Feature::Cat(c) => {
+                    matches!(c.head(), Category::V | Category::C | Category::S)
+                        || selects(previous, c, |f| if let Feature::Sel(c) = f { Some(c) } else { None })
+                        || selects(later, c, |f| if let Feature::Spec(c) | Feature::Adjoin(c) = f { Some(c) } else { None })
+                }
+                Feature::Pos(_) | Feature::Neg(_) | Feature::Agr(_) => continue,
+            };
+            met += usize::from(satisfied);
+            total += 1;
+        }
+        if total == 0 { 1.0 } else { met as f64 / total as f64 }
+    }
+}
+
+/// Scores from a table of weighted entries
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagWeights {
+    weights: Vec<(LexItem, f64)>,
+    /// Score of an entry the table does not list
+    pub default: f64,
+}
+
+impl TagWeights {
+    /// Empty table scoring every entry `default`
+    pub fn new(default: f64) -> Self {
+        Self { weights: Vec::new(), default }
+    }
+
+    /// The table with an entry's weight set
+    pub fn weight(mut self, entry: LexItem, weight: f64) -> Self {
+        self.weights.retain(|(item, _)| *item != entry);
+        self.weights.push((entry, weight));
+        self
+    }
+}
+
+impl Supertagger for TagWeights {
+    fn score(&self, _tokens: &[&str], _position: usize, entry: &LexItem, _lexicon: &[LexItem]) -> f64 {
+        self.weights.iter().find(|(item, _)| item == entry).map_or(self.default, |(_, weight)| *weight)
+    }
+}
+
+/// The `k` best entries of each token, best first (ties keep lexicon order)
+pub fn supertag<'l>(
+    tokens: &[&str],
+    lexicon: &'l [LexItem],
+    tagger: &dyn Supertagger,
+    k: usize,
+) -> Result<Vec<Vec<&'l LexItem>>, DerivationError> {
+    tokens.iter()
+        .enumerate()
+        .map(|(position, &token)| {
+            let mut scored: Vec<(f64, &LexItem)> = lexicon.iter()
+                .filter(|item| item.phon == token)
+                .map(|item| (tagger.score(tokens, position, item, lexicon), item))
+                .collect();
+            if scored.is_empty() {
+                return Err(DerivationError::InvalidOperation);
+            }
+            scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(core::cmp::Ordering::Equal));
+            Ok(scored.into_iter().take(k.max(1)).map(|(_, item)| item).collect())
+        })
+        .collect()
+}
+
+/// Most choices of entries a sentence is searched with
+pub const MAX_TAG_SEQUENCES: usize = 256;
+
+/// Choices of one entry per token, the best-scored choices first, lazily
+/// and at most `MAX_TAG_SEQUENCES` of them
+pub(crate) fn tag_sequences<'l>(tags: Vec<Vec<&'l LexItem>>) -> impl Iterator<Item = Vec<&'l LexItem>> + 'l {
+    let mut choice = Some(vec![0; tags.len()]);
+    let sequences = core::iter::from_fn(move || {
+        let current = choice.take()?;
+        let sequence = tags.iter().zip(&current).map(|(entries, &i)| entries[i]).collect();
+        if let Some(word) = (0..tags.len()).find(|&i| current[i] + 1 < tags[i].len()) {
+            let mut next = current;
+            next[word] += 1;
+            next[..word].iter_mut().for_each(|i| *i = 0);
+            choice = Some(next);
+        }
+        Some(sequence)
+    });
+    sequences.take(MAX_TAG_SEQUENCES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    fn ambiguous() -> Vec<LexItem> {
+        let mut lexicon = test_lexicon();
+        // The nominal entry of `left` is listed first
+        lexicon.insert(0, LexItem::new("left", &[Feature::Cat(Category::N)]));
+        lexicon
+    }
+
+    #[test]
+    fn test_rules_prefer_entries_the_context_supports() {
+        let lexicon = ambiguous();
+        let tokens = ["the", "student", "left"];
+        let tags = supertag(&tokens, &lexicon, &SelectionRules, 1).unwrap();
+        assert!(tags[2][0].feats.contains(&Feature::Spec(Category::D)));
+        let tags = supertag(&tokens, &lexicon, &LexiconOrder, 1).unwrap();
+        assert_eq!(tags[2][0].feats, [Feature::Cat(Category::N)]);
+
+        // Weights override the lexicon's order
+        let weights = TagWeights::new(0.5).weight(lexicon[0].clone(), 0.1);
+        let tags = supertag(&tokens, &lexicon, &weights, 2).unwrap();
+        assert_eq!(tags[2].len(), 2);
+        assert_ne!(*tags[2][0], lexicon[0]);
+        assert!(supertag(&["the", "dog"], &lexicon, &LexiconOrder, 1).is_err());
+    }
+
+    #[test]
+    fn test_sequences_cover_every_choice() {
+        let lexicon = ambiguous();
+        let tags = supertag(&["left", "the", "left"], &lexicon, &LexiconOrder, 2).unwrap();
+        let sequences: Vec<_> = tag_sequences(tags).collect();
+        assert_eq!(sequences.len(), 4);
+        assert!(sequences[0].iter().all(|item| item.feats == [Feature::Cat(Category::N)] || item.phon == "the"));
+
+        // Twenty ambiguous words have 2^20 choices, of which only the cap is generated
+        let tokens = ["left"; 20];
+        let tags = supertag(&tokens, &lexicon, &LexiconOrder, 2).unwrap();
+        assert_eq!(tag_sequences(tags).count(), MAX_TAG_SEQUENCES);
+    }
+}
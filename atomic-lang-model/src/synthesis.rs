@@ -0,0 +1,332 @@
+//! Benchmark Synthesis
+//!
+//! Reads the constructions a lexicon claims to handle off its feature
+//! bundles and synthesizes test sentences for each, so the benchmark grows
+//! with the grammar instead of lagging behind it. Every head that selects,
+//! adjoins or attracts defines a construction, shared by all heads with the
+//! same bundle. For each head the generator builds a phrase with Merge,
+//! Adjoin and Move alone, filling its selectors with small phrases from the
+//! lexicon:
+//!
+//! - the complete phrase is expected to parse
+//! - the phrase with one of the head's arguments (or an adjunct's host)
+//!   left out is expected to fail
+//!
+//! Expectations come from the lexicon, not from the parser, so a parser
+//! that cannot find a derivation the lexicon licenses shows up as a miss.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::{String, ToString}, vec, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use core::fmt;
+
+use crate::{adjoin, can_adjoin, can_merge, merge, move_in_place, Category, Feature, LexItem, SyntacticObject};
+
+/// Deepest nesting of phrases inside a construction's head
+pub const MAX_DEPTH: usize = 3;
+
+/// Most phrases kept per head and nesting depth
+pub const MAX_PHRASES: usize = 8;
+
+/// Test sentence with its expected outcome
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkCase {
+    /// Sentence to parse
+    pub sentence: String,
+    /// Head of the construction the sentence exercises
+    pub head: String,
+    /// Feature left unchecked by leaving out its argument or host, if any
+    pub omitted: Option<Feature>,
+}
+
+impl BenchmarkCase {
+    /// Whether the sentence is expected to parse
+    pub fn grammatical(&self) -> bool {
+        self.omitted.is_none()
+    }
+}
+
+/// Heads sharing a feature bundle, with the sentences synthesized for them
+#[derive(Debug, Clone, PartialEq)]
+pub struct Construction {
+    /// Feature bundle shared by the heads
+    pub frame: Vec<Feature>,
+    /// Heads in lexicon order (silent heads as the empty string)
+    pub heads: Vec<String>,
+    /// Synthesized sentences; empty if no head could be derived
+    pub cases: Vec<BenchmarkCase>,
+}
+
+impl fmt::Display for Construction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let frame: Vec<String> = self.frame.iter().map(Feature::to_string).collect();
+        let heads: Vec<&str> = self.heads.iter().map(|head| if head.is_empty() { "ε" } else { head }).collect();
+        write!(f, "{} ({})", frame.join(" "), heads.join(", "))
+    }
+}
+
+/// Enumerate the lexicon's constructions and synthesize sentences for each
+///
+/// Every entry of an ambiguous word is exercised, as in parsing. Negative
+/// cases that happen to coincide with a synthesized grammatical sentence
+/// are dropped.
+pub fn synthesize_benchmark(lexicon: &[LexItem]) -> Vec<Construction> {
+    let mut generator = Generator::new(lexicon);
+    let mut constructions: Vec<Construction> = Vec::new();
+    let mut reduced = Vec::new();
+
+    for index in 0..generator.items.len() {
+        let item = generator.items[index];
+        if item.feats.iter().all(|f| matches!(f, Feature::Cat(_) | Feature::Neg(_) | Feature::Agr(_))) {
+            continue;
+        }
+        let position = match constructions.iter().position(|c| c.frame == item.feats) {
+            Some(position) => position,
+            None => {
+                constructions.push(Construction { frame: item.feats.clone(), heads: Vec::new(), cases: Vec::new() });
+                constructions.len() - 1
+            }
+        };
+        constructions[position].heads.push(item.phon.clone());
+
+        for (phrase, omissions) in generator.constructions(index) {
+            let sentence = phrase.linearize();
+            if sentence.is_empty() {
+                continue;
+            }
+            let construction = &mut constructions[position];
+            construction.cases.push(BenchmarkCase { sentence, head: item.phon.clone(), omitted: None });
+            reduced.extend(omissions.into_iter().map(|(feature, sentence)| (position, item.phon.clone(), feature, sentence)));
+        }
+    }
+
+    for (position, head, feature, sentence) in reduced {
+        let duplicate = constructions.iter()
+            .flat_map(|c| &c.cases)
+            .any(|case| case.sentence == sentence);
+        if !sentence.is_empty() && !duplicate {
+            constructions[position].cases.push(BenchmarkCase { sentence, head, omitted: Some(feature) });
+        }
+    }
+    constructions
+}
+
+/// Phrase builder over the entries the parser would use
+struct Generator<'a> {
+    /// Every entry, silent heads included
+    items: Vec<&'a LexItem>,
+    /// Saturated phrases by head index and depth
+    memo: BTreeMap<(usize, usize), Vec<SyntacticObject>>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(lexicon: &'a [LexItem]) -> Self {
+        let items = lexicon.iter().collect();
+        Self { items, memo: BTreeMap::new() }
+    }
+
+    /// One complete phrase exercising the head at `index`, with the
+    /// sentences that leave out each of its arguments or its host
+    fn constructions(&mut self, index: usize) -> Vec<(SyntacticObject, Vec<(Feature, String)>)> {
+        let targets: Vec<Category> = self.items[index].feats.iter()
+            .filter_map(|f| match f {
+                Feature::Adjoin(cat) => Some(cat.clone()),
+                _ => None,
+            })
+            .collect();
+        let phrases = self.phrases(index, MAX_DEPTH);
+
+        if targets.is_empty() {
+            let Some(phrase) = phrases.into_iter().find(SyntacticObject::is_complete) else {
+                return Vec::new();
+            };
+            let omissions = without_arguments(&phrase).into_iter()
+                .map(|(feature, reduced)| (feature, reduced.linearize()))
+                .collect();
+            return vec![(phrase, omissions)];
+        }
+
+        // An adjunct is exercised once per category it adjoins to
+        let mut constructions = Vec::new();
+        for cat in targets {
+            let hosts = self.hosts(&cat);
+            let adjoined = phrases.iter().find_map(|adjunct| {
+                hosts.iter()
+                    .filter(|host| can_adjoin(host, adjunct))
+                    .find_map(|host| Some((adjoin(host.clone(), adjunct.clone()).ok()?, host, adjunct)))
+                    .filter(|(phrase, _, _)| phrase.is_complete())
+            });
+            let Some((phrase, host, adjunct)) = adjoined else {
+                continue;
+            };
+            let mut omissions = vec![(Feature::Adjoin(cat), adjunct.linearize())];
+            omissions.extend(without_arguments(adjunct).into_iter().filter_map(|(feature, reduced)| {
+                Some((feature, adjoin(host.clone(), reduced).ok()?.linearize()))
+            }));
+            constructions.push((phrase, omissions));
+        }
+        constructions
+    }
+
+    /// Indices of the items projecting `cat`
+    fn heads_of(&self, cat: &Category) -> Vec<usize> {
+        (0..self.items.len())
+            .filter(|&i| category(self.items[i]).is_some_and(|own| own.matches(cat)))
+            .collect()
+    }
+
+    /// Complete phrases of category `cat` with nothing left to move
+    fn hosts(&mut self, cat: &Category) -> Vec<SyntacticObject> {
+        self.heads_of(cat).into_iter()
+            .flat_map(|i| self.phrases(i, MAX_DEPTH - 1))
+            .filter(SyntacticObject::is_complete)
+            .take(MAX_PHRASES)
+            .collect()
+    }
+
+    /// Phrases headed by the item at `index` with every selector checked
+    /// and every licensor's mover attracted, nested at most `depth` deep
+    fn phrases(&mut self, index: usize, depth: usize) -> Vec<SyntacticObject> {
+        if let Some(phrases) = self.memo.get(&(index, depth)) {
+            return phrases.clone();
+        }
+
+        let item = self.items[index];
+        let mut phrases = vec![SyntacticObject::from_lex(item)];
+        let selected = item.feats.iter().filter_map(|f| match f {
+            Feature::Sel(cat) | Feature::Spec(cat) => Some(cat),
+            _ => None,
+        });
+        for cat in selected {
+            if depth == 0 {
+                phrases.clear();
+                break;
+            }
+            let fillers: Vec<SyntacticObject> = self.heads_of(cat).into_iter()
+                .flat_map(|i| self.phrases(i, depth - 1))
+                .collect();
+            phrases = phrases.iter()
+                .flat_map(|phrase| {
+                    fillers.iter()
+                        .filter(|filler| can_merge(phrase, filler))
+                        .filter_map(|filler| merge(phrase.clone(), filler.clone()).ok())
+                })
+                .take(MAX_PHRASES)
+                .collect();
+        }
+
+        // Licensors attract their movers once every argument is in place
+        phrases.retain_mut(|phrase| {
+            while phrase.features.iter().any(Feature::is_positive) {
+                if move_in_place(phrase).is_err() {
+                    return false;
+                }
+            }
+            true
+        });
+        self.memo.insert((index, depth), phrases.clone());
+        phrases
+    }
+}
+
+/// Category an entry projects
+fn category(item: &LexItem) -> Option<&Category> {
+    item.feats.iter().find_map(|f| match f {
+        Feature::Cat(cat) => Some(cat),
+        _ => None,
+    })
+}
+
+/// The phrase with each argument of its head replaced by a trace, paired
+/// with the selector the argument checked
+///
+/// The arguments (see `SyntacticObject::arguments`) are those of the nodes
+/// along the head's projection line.
+fn without_arguments(phrase: &SyntacticObject) -> Vec<(Feature, SyntacticObject)> {
+    let mut reduced = Vec::new();
+    let mut path = Vec::new();
+    let mut node = phrase;
+    while let Some(head) = node.head_index() {
+        if let Some((selector, arguments)) = node.arguments() {
+            for i in arguments {
+                let mut copy = phrase.clone();
+                let parent = path.iter().fold(&mut copy, |node: &mut SyntacticObject, &j| &mut node.children[j]);
+                parent.children[i] = SyntacticObject::trace(node.children[i].label.clone());
+                reduced.push((selector.clone(), copy));
+            }
+        }
+        path.push(head);
+        node = &node.children[head];
+    }
+    reduced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon};
+
+    #[test]
+    fn test_constructions_from_bundles() {
+        let constructions = synthesize_benchmark(&test_lexicon());
+        let find = |head: &str| constructions.iter().find(|c| c.heads.iter().any(|h| h == head)).unwrap();
+
+        // Heads with the same bundle share a construction; bare nouns define none
+        assert_eq!(find("left").heads, ["left", "smiled", "arrived"]);
+        assert_eq!(find("left").to_string(), "V D= (left, smiled, arrived)");
+        assert!(constructions.iter().all(|c| !c.heads.contains(&"student".to_string())));
+
+        let cases: Vec<(&str, Option<&Feature>)> = find("thinks").cases.iter()
+            .map(|case| (case.sentence.as_str(), case.omitted.as_ref()))
+            .collect();
+        assert_eq!(cases, [
+            ("the student thinks the student", None),
+            ("thinks the student", Some(&Feature::Spec(Category::D))),
+            ("the student thinks", Some(&Feature::Sel(Category::DP))),
+        ]);
+
+        // Nothing carries a licensee for 'said' to attract
+        assert!(find("said").cases.is_empty());
+    }
+
+    #[test]
+    fn test_expectations_hold_for_the_test_lexicon() {
+        let lexicon = test_lexicon();
+        for construction in synthesize_benchmark(&lexicon) {
+            for case in &construction.cases {
+                assert_eq!(parse_sentence(&case.sentence, &lexicon).is_ok(), case.grammatical(), "{:?}", case);
+            }
+        }
+    }
+
+    #[test]
+    fn test_adjuncts_and_movement() {
+        let mut lexicon = test_lexicon();
+        lexicon.extend([
+            LexItem::new("near", &[Feature::Cat(Category::P), Feature::Sel(Category::DP), Feature::Adjoin(Category::N)]),
+            LexItem::new("whom", &[Feature::Cat(Category::D), Feature::Neg(1)]),
+        ]);
+        let constructions = synthesize_benchmark(&lexicon);
+        let cases = |head: &str| -> Vec<(String, bool)> {
+            constructions.iter()
+                .flat_map(|c| &c.cases)
+                .filter(|case| case.head == head)
+                .map(|case| (case.sentence.clone(), case.grammatical()))
+                .collect()
+        };
+
+        assert_eq!(cases("near"), [
+            ("student near the student".to_string(), true),
+            ("near the student".to_string(), false),
+            ("student near".to_string(), false),
+        ]);
+        // 'said' now finds a mover, which its specifier supplies first
+        assert_eq!(cases("said")[0], ("whom said the student".to_string(), true));
+        for (sentence, grammatical) in cases("near").into_iter().chain(cases("said")) {
+            assert_eq!(parse_sentence(&sentence, &lexicon).is_ok(), grammatical, "{}", sentence);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Token IDs
+//!
+//! Language-model tokenizers hand over sentences as integer IDs, not text.
+//! A `Vocabulary` numbers a lexicon's tokens, its pronounced forms,
+//! densely from 0 in the order they first appear, and lists the entries
+//! each token ID stands for, so `Chart::build_token_ids` and
+//! `parse_token_ids` can look tokens up by index instead of comparing
+//! strings. Silent items are not tokens and have no ID.
+//! The lexicon is borrowed, so a vocabulary cannot outlive or drift from
+//! the lexicon it numbers.
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use crate::{DerivationError, LexItem};
+
+/// Dense IDs for the tokens of a lexicon
+#[derive(Debug, Clone)]
+pub struct Vocabulary<'l> {
+    lexicon: &'l [LexItem],
+    token_ids: BTreeMap<&'l str, u32>,
+    tokens: Vec<&'l str>,
+    /// Lexicon indices of the entries of each token, by token ID
+    entries: Vec<Vec<usize>>,
+}
+
+impl<'l> Vocabulary<'l> {
+    /// Number the tokens of `lexicon` in order of first appearance
+    pub fn new(lexicon: &'l [LexItem]) -> Self {
+        let mut vocabulary = Self { lexicon, token_ids: BTreeMap::new(), tokens: Vec::new(), entries: Vec::new() };
+        for (index, item) in lexicon.iter().enumerate().filter(|(_, item)| !item.phon.is_empty()) {
+            let next = vocabulary.tokens.len() as u32;
+            let id = *vocabulary.token_ids.entry(&item.phon).or_insert(next);
+            if id == next {
+                vocabulary.tokens.push(&item.phon);
+                vocabulary.entries.push(Vec::new());
+            }
+            vocabulary.entries[id as usize].push(index);
+        }
+        vocabulary
+    }
+
+    /// Lexicon the token IDs index
+    pub fn lexicon(&self) -> &'l [LexItem] {
+        self.lexicon
+    }
+
+    /// Number of tokens
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Check if the lexicon has no tokens
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// ID of a token, if the lexicon has it
+    pub fn token_id(&self, token: &str) -> Option<u32> {
+        self.token_ids.get(token).copied()
+    }
+
+    /// Token with an ID
+    pub fn token(&self, token_id: u32) -> Option<&'l str> {
+        self.tokens.get(token_id as usize).copied()
+    }
+
+    /// Lexicon indices of the entries pronounced as the token with an ID,
+    /// as `lookup` finds them by string
+    pub fn lookup(&self, token_id: u32) -> Option<&[usize]> {
+        self.entries.get(token_id as usize).map(Vec::as_slice)
+    }
+
+    /// Token IDs of a whitespace-separated sentence, failing on an unknown
+    /// token as parsing does
+    pub fn encode(&self, sentence: &str) -> Result<Vec<u32>, DerivationError> {
+        sentence.split_whitespace()
+            .map(|token| self.token_id(token).ok_or(DerivationError::InvalidOperation))
+            .collect()
+    }
+
+    /// Sentence spelled by token IDs, or `None` if one is out of range
+    pub fn decode(&self, token_ids: &[u32]) -> Option<String> {
+        let tokens: Option<Vec<&str>> = token_ids.iter().map(|&token_id| self.token(token_id)).collect();
+        tokens.map(|tokens| tokens.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_chart, parse_token_ids, Chart, GrammarOptions, RelativeClauseAnalysis};
+
+    #[test]
+    fn test_dense_ids() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let vocabulary = Vocabulary::new(&lexicon);
+
+        let tokens: Vec<&str> = (0..vocabulary.len() as u32).map(|id| vocabulary.token(id).unwrap()).collect();
+        assert!(tokens.iter().enumerate().all(|(id, token)| vocabulary.token_id(token) == Some(id as u32)));
+        assert!(!tokens.contains(&""));
+        assert_eq!(vocabulary.token(vocabulary.len() as u32), None);
+
+        // Homophones share an ID
+        let entries: usize = (0..vocabulary.len() as u32).map(|id| vocabulary.lookup(id).unwrap().len()).sum();
+        assert_eq!(entries, lexicon.iter().filter(|item| !item.phon.is_empty()).count());
+
+        let ids = vocabulary.encode("the student left").unwrap();
+        assert_eq!(vocabulary.decode(&ids).as_deref(), Some("the student left"));
+        assert_eq!(vocabulary.encode("the student flew"), Err(DerivationError::InvalidOperation));
+    }
+
+    #[test]
+    fn test_id_parsing_matches_chart() {
+        for analysis in [RelativeClauseAnalysis::OperatorMovement, RelativeClauseAnalysis::Promotion] {
+            let options = GrammarOptions { relative_clauses: analysis, ..Default::default() };
+            let lexicon = options.lexicon();
+            let vocabulary = Vocabulary::new(&lexicon);
+            for sentence in ["the student left", "the student who left smiled", "the student thinks the teacher", "left the student"] {
+                let ids = vocabulary.encode(sentence).unwrap();
+                assert_eq!(parse_token_ids(&ids, &vocabulary, &options), parse_chart(sentence, &lexicon, &options), "{}", sentence);
+                let chart = Chart::build_token_ids(&ids, &vocabulary, options.operations.movement).unwrap();
+                assert_eq!(chart.recognizes(), parse_chart(sentence, &lexicon, &options).is_ok());
+            }
+        }
+
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let vocabulary = Vocabulary::new(&lexicon);
+        assert_eq!(parse_token_ids(&[], &vocabulary, &options), Err(DerivationError::EmptyWorkspace));
+        assert_eq!(parse_token_ids(&[vocabulary.len() as u32], &vocabulary, &options), Err(DerivationError::InvalidOperation));
+    }
+}
@@ -0,0 +1,405 @@
+//! Derivation Traces
+//!
+//! A log of the steps `step` and `derive` take, for seeing where a failed
+//! derivation went wrong. Recording is off by default, since each entry
+//! keeps a snapshot of the workspace; `Workspace::record_trace` turns it on
+//! and `Workspace::trace` returns what was recorded:
+//!
+//! ```text
+//! step 1: Merge(0, 1) checks =N, N on [D the], [N student]
+//!   workspace: [DP [D the] [N student]] | [V left]
+//! step 2: Merge(1, 0) checks D=, D on [V left], [DP [D the] [N student]]
+//!   workspace: [VP [DP [D the] [N student]] [V left]]
+//! ```
+//!
+//! The depth-first search behind `parse` applies operations to copies of
+//! the workspace it is given and records nothing unless asked to;
+//! `trace_derivation` runs it recording every operation it tries, in the
+//! order tried, so the dead ends it backed out of appear too.
+//!
+//! Each step also records its wall time and, where the global allocator
+//! calls `count_allocation`, the heap allocations it made. `DerivationTrace::collapsed_stacks`
+//! exports either as input for flame graph tools, with steps grouped by
+//! the kind of operation they applied:
+//!
+//! ```text
+//! derive;Merge;step 1 Merge(0, 1) 5210
+//! derive;Merge;step 2 Merge(1, 0) 3890
+//! ```
+//!
+//! Without std there is no clock, and every step takes no time.
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::{
+    agreement, allocation_count, search, DerivationError, Feature, GrammarOptions, LexItem, Operation,
+    SyntacticObject, Workspace, WorkspacePool,
+};
+
+#[cfg(feature = "std")]
+use std::time::Instant as Clock;
+
+/// Stand-in for a clock where there is none
+#[cfg(not(feature = "std"))]
+#[derive(Clone, Copy)]
+struct Clock;
+
+#[cfg(not(feature = "std"))]
+impl Clock {
+    fn now() -> Self {
+        Clock
+    }
+
+    fn elapsed(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// Wall time and allocations of a step, excluding the trace's own bookkeeping
+pub(crate) struct Meter {
+    elapsed: Duration,
+    allocations: usize,
+    /// When and at what allocation count the running stretch started
+    since: Option<(Clock, usize)>,
+}
+
+impl Meter {
+    pub(crate) fn start() -> Self {
+        Self { elapsed: Duration::ZERO, allocations: 0, since: Some((Clock::now(), allocation_count())) }
+    }
+
+    pub(crate) fn pause(&mut self) {
+        if let Some((clock, allocations)) = self.since.take() {
+            self.elapsed += clock.elapsed();
+            self.allocations += allocation_count().saturating_sub(allocations);
+        }
+    }
+
+    pub(crate) fn resume(&mut self) {
+        self.since.get_or_insert_with(|| (Clock::now(), allocation_count()));
+    }
+}
+
+/// One step of a derivation
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceStep {
+    /// Step number, as `Workspace::step_count` counts steps
+    pub step: usize,
+    /// Operation applied, `None` if the step found none to apply
+    pub operation: Option<Operation>,
+    /// Objects the operation combined or moved within, as they were before
+    pub inputs: Vec<SyntacticObject>,
+    /// Features the operation checked: the selector with its probe and the
+    /// selectee's category, the adjunction feature, or the licensor and
+    /// licensee of a movement
+    pub checked: Vec<Feature>,
+    /// Whether the step succeeded
+    pub outcome: Result<(), DerivationError>,
+    /// Workspace items after the step
+    pub workspace: Vec<SyntacticObject>,
+    /// Wall time the step took
+    pub elapsed: Duration,
+    /// Heap allocations the step made, as `count_allocation` counts them
+    /// (on any thread)
+    pub allocations: usize,
+}
+
+impl TraceStep {
+    /// Entry for applying `operation` to `workspace`, before it is applied
+    pub(crate) fn before(workspace: &Workspace, operation: Option<Operation>) -> Self {
+        let (inputs, checked) = match operation {
+            Some(operation) => participants(workspace, operation),
+            None => (Vec::new(), Vec::new()),
+        };
+        Self {
+            step: workspace.step_count,
+            operation,
+            inputs,
+            checked,
+            outcome: Ok(()),
+            workspace: Vec::new(),
+            elapsed: Duration::ZERO,
+            allocations: 0,
+        }
+    }
+
+    /// Complete the entry with the step's outcome, the workspace after it
+    /// and what `meter` measured
+    pub(crate) fn after(mut self, workspace: &Workspace, outcome: Result<(), DerivationError>, mut meter: Meter) -> Self {
+        meter.pause();
+        self.outcome = outcome;
+        self.workspace = workspace.items().to_vec();
+        self.elapsed = meter.elapsed;
+        self.allocations = meter.allocations;
+        self
+    }
+
+    /// Weight of the step under a metric
+    pub fn cost(&self, metric: TimelineMetric) -> u64 {
+        match metric {
+            TimelineMetric::WallTime => self.elapsed.as_nanos() as u64,
+            TimelineMetric::Allocations => self.allocations as u64,
+        }
+    }
+}
+
+/// Objects taking part in `operation` and the features it checks
+fn participants(workspace: &Workspace, operation: Operation) -> (Vec<SyntacticObject>, Vec<Feature>) {
+    let item = |i: usize| workspace.items().get(i);
+    let (first, second) = match operation {
+        Operation::Merge(i, j) | Operation::Adjoin(i, j) => (item(i), item(j)),
+        Operation::MergeEmpty(h, i) => (workspace.empty_heads.get(h), item(i)),
+        Operation::Move(i) => (item(i), None),
+    };
+    let inputs = first.into_iter().chain(second).cloned().collect();
+
+    let mut checked = Vec::new();
+    match (operation, first, second) {
+        (Operation::Adjoin(..), Some(host), Some(adjunct)) => {
+            checked.extend(adjunct.features.iter()
+                .find(|f| matches!(f, Feature::Adjoin(target) if host.category().is_some_and(|cat| target.matches(cat))))
+                .cloned());
+        }
+        (Operation::Move(_), Some(obj), _) => {
+            if let Some(licensor) = obj.features.iter().find(|f| f.is_positive()) {
+                checked.push(licensor.clone());
+                checked.extend(licensor.movement_index().map(Feature::Neg));
+            }
+        }
+        (_, Some(selector), Some(selectee)) => {
+            checked.extend(selector.next_selector().cloned());
+            checked.extend(agreement::probe(&selector.features).copied().map(Feature::Agr));
+            checked.extend(selectee.category().cloned().map(Feature::Cat));
+        }
+        _ => {}
+    }
+    (inputs, checked)
+}
+
+/// What a flame graph of a trace measures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineMetric {
+    /// Wall time, in nanoseconds
+    WallTime,
+    /// Heap allocations
+    Allocations,
+}
+
+/// Steps recorded while a workspace was being derived, oldest first
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DerivationTrace {
+    /// Recorded steps
+    pub steps: Vec<TraceStep>,
+}
+
+impl DerivationTrace {
+    /// Number of recorded steps
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Check whether no step was recorded
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The first step that failed, if any
+    pub fn failure(&self) -> Option<&TraceStep> {
+        self.steps.iter().find(|step| step.outcome.is_err())
+    }
+
+    /// Operations applied successfully, in order
+    pub fn operations(&self) -> Vec<Operation> {
+        self.steps.iter()
+            .filter(|step| step.outcome.is_ok())
+            .filter_map(|step| step.operation)
+            .collect()
+    }
+
+    /// Total weight of the steps under a metric
+    pub fn cost(&self, metric: TimelineMetric) -> u64 {
+        self.steps.iter().map(|step| step.cost(metric)).sum()
+    }
+
+    /// The steps in the collapsed-stack format flame graph tools read
+    /// (`flamegraph.pl`, `inferno-flamegraph`): one `derive;KIND;step N OP
+    /// WEIGHT` line per step, with `failed` after the operation of a step
+    /// that failed
+    pub fn collapsed_stacks(&self, metric: TimelineMetric) -> String {
+        self.steps.iter()
+            .map(|step| {
+                let (kind, operation) = match step.operation {
+                    Some(operation) => (operation_kind(operation), format!("{:?}", operation)),
+                    None => ("none", "none".to_string()),
+                };
+                let failed = if step.outcome.is_err() { " failed" } else { "" };
+                format!("derive;{};step {} {}{} {}\n", kind, step.step, operation, failed, step.cost(metric))
+            })
+            .collect()
+    }
+}
+
+fn operation_kind(operation: Operation) -> &'static str {
+    match operation {
+        Operation::Merge(..) => "Merge",
+        Operation::Adjoin(..) => "Adjoin",
+        Operation::MergeEmpty(..) => "MergeEmpty",
+        Operation::Move(..) => "Move",
+    }
+}
+
+/// Trace the search `parse_derivation` makes for a sentence: every
+/// operation it tries until a derivation converges, in the order tried
+///
+/// Operations that fail are recorded with their error, and the steps of
+/// branches the search backed out of are kept, so the step numbers start
+/// over wherever it backtracked. The last step recorded completes the
+/// derivation found.
+pub fn trace_derivation(
+    sentence: &str,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+) -> Result<DerivationTrace, DerivationError> {
+    let mut trace = DerivationTrace::default();
+    search(sentence, lexicon, options, 1, &mut WorkspacePool::new(), None, Some(&mut trace)).0?;
+    Ok(trace)
+}
+
+fn joined<T: fmt::Display>(items: impl Iterator<Item = T>, separator: &str) -> String {
+    items.map(|item| item.to_string()).collect::<Vec<_>>().join(separator)
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "step {}: ", self.step)?;
+        match self.operation {
+            Some(operation) => write!(
+                f,
+                "{:?} checks {} on {}",
+                operation,
+                joined(self.checked.iter(), ", "),
+                joined(self.inputs.iter().map(SyntacticObject::bracketed), ", ")
+            )?,
+            None => write!(f, "no operation")?,
+        }
+        if let Err(e) = self.outcome {
+            write!(f, " failed: {}", e)?;
+        }
+        write!(f, "\n  workspace: {}", joined(self.workspace.iter().map(SyntacticObject::bracketed), " | "))
+    }
+}
+
+impl fmt::Display for DerivationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{derive, parse_derivation, parse_sentence, step, test_lexicon, Category, LexItem, Normalizer};
+
+    fn workspace(words: &[&str]) -> Workspace {
+        let lexicon = test_lexicon();
+        let mut workspace = Workspace::new(1000);
+        for word in words {
+            workspace.add_lex(lexicon.iter().find(|item| item.phon == *word).unwrap());
+        }
+        workspace
+    }
+
+    #[test]
+    fn test_derive_records_each_step() {
+        let mut workspace = workspace(&["the", "student", "left"]);
+        assert!(workspace.trace().is_none());
+        workspace.record_trace();
+        let tree = derive(&mut workspace, 10).unwrap();
+
+        let trace = workspace.trace().unwrap();
+        assert_eq!(trace.operations(), [Operation::Merge(0, 1), Operation::Merge(1, 0)]);
+        assert_eq!(trace.failure(), None);
+
+        let first = &trace.steps[0];
+        assert_eq!(first.step, 1);
+        assert_eq!(first.checked, [Feature::Sel(Category::N), Feature::Cat(Category::N)]);
+        assert_eq!(first.inputs.iter().map(SyntacticObject::linearize).collect::<Vec<_>>(), ["the", "student"]);
+        assert_eq!(first.workspace.len(), 2);
+        assert_eq!(trace.steps[1].workspace, [tree]);
+        assert!(trace.to_string().starts_with("step 1: Merge(0, 1) checks =N, N on "));
+    }
+
+    #[test]
+    fn test_failed_step_is_recorded() {
+        let mut workspace = workspace(&["left", "left"]);
+        workspace.record_trace();
+        assert_eq!(step(&mut workspace), Err(DerivationError::NoValidOperations));
+
+        let failure = workspace.trace().unwrap().failure().unwrap();
+        assert_eq!(failure.operation, None);
+        assert_eq!(failure.workspace.len(), 2);
+        assert!(failure.to_string().contains("failed: No valid operations available"));
+
+        // Stopping the recording hands the trace over
+        assert_eq!(workspace.take_trace().map(|trace| trace.len()), Some(1));
+        step(&mut workspace).unwrap_err();
+        assert!(workspace.trace().is_none());
+    }
+
+    #[test]
+    fn test_move_checks_licensor_and_licensee() {
+        let (licensor, licensee) = (Feature::Pos(1), Feature::Neg(1));
+        let mut workspace = Workspace::new(1000);
+        workspace.add_lex(&LexItem::new("c", &[Feature::Cat(Category::C), Feature::Sel(Category::V), licensor.clone()]));
+        workspace.add_lex(&LexItem::new("what", &[Feature::Cat(Category::V), licensee.clone()]));
+        workspace.record_trace();
+        derive(&mut workspace, 10).unwrap();
+
+        let trace = workspace.trace().unwrap();
+        assert_eq!(trace.operations(), [Operation::Merge(0, 1), Operation::Move(0)]);
+        assert_eq!(trace.steps[1].checked, [licensor, licensee]);
+    }
+
+    #[test]
+    fn test_collapsed_stacks_group_steps_by_kind() {
+        let options = GrammarOptions::default();
+        let lexicon = options.lexicon();
+        let mut trace = trace_derivation("the student who left smiled", &lexicon, &options).unwrap();
+        assert_eq!(trace.failure(), None);
+        assert_eq!(trace.steps.last().unwrap().workspace, [parse_sentence("the student who left smiled", &lexicon).unwrap()]);
+
+        for (i, step) in trace.steps.iter_mut().enumerate() {
+            step.elapsed = Duration::from_micros(i as u64 + 1);
+            step.allocations = 2;
+        }
+        let stacks = trace.collapsed_stacks(TimelineMetric::WallTime);
+        assert_eq!(stacks.lines().count(), trace.len());
+        assert!(stacks.lines().any(|line| line.starts_with("derive;Move;step ") && line.contains(" Move(")));
+        assert!(stacks.starts_with("derive;Merge;step 1 Merge(0, 1) 1000\n"));
+        assert_eq!(trace.cost(TimelineMetric::Allocations), 2 * trace.len() as u64);
+
+        // The search backtracks out of its first choice before converging
+        let parse = parse_derivation("the student who left smiled", &lexicon, &options).unwrap();
+        assert!(trace.len() > parse.derivation.len());
+        assert_eq!(trace.steps.iter().filter(|step| step.step == 1).count(), 2);
+        assert!(stacks.contains("derive;Merge;step 1 Merge(3, 2) 18000\n"));
+
+        trace.steps[0].outcome = Err(DerivationError::NoValidOperations);
+        assert!(trace.collapsed_stacks(TimelineMetric::WallTime).starts_with("derive;Merge;step 1 Merge(0, 1) failed 1000\n"));
+    }
+
+    #[test]
+    fn test_trace_normalizes_the_sentence() {
+        let options = GrammarOptions { normalizer: Normalizer::raw_text(), ..GrammarOptions::default() };
+        let lexicon = options.lexicon();
+        let trace = trace_derivation("The student left.", &lexicon, &options).unwrap();
+        assert_eq!(trace.steps.last().unwrap().workspace, [parse_sentence("the student left", &lexicon).unwrap()]);
+    }
+}
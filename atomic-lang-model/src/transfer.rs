@@ -0,0 +1,64 @@
+//! Transfer
+//!
+//! The derivation runs in phases. Narrow syntax (Merge, Adjoin, Move, in the
+//! crate root) builds a `SyntacticObject`; transfer hands a convergent
+//! object to the two interfaces, PF (`pf`, sound) and LF (`lf`, meaning),
+//! which each see only what they can interpret. Interface processes take a
+//! `Transfer` rather than a syntactic object, so they never depend on the
+//! features narrow syntax works with.
+
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+use crate::lf::{self, LfNode, Term};
+use crate::pf::{self, PfNode};
+use crate::{DerivationError, SyntacticObject};
+
+/// A derivation's output at both interfaces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    /// Phonological form
+    pub pf: PfNode,
+    /// Logical form
+    pub lf: LfNode,
+}
+
+impl Transfer {
+    /// Pronounced string
+    pub fn linearize(&self) -> String {
+        self.pf.linearize()
+    }
+
+    /// Composed meaning, if anything in the tree is overt
+    pub fn interpret(&self) -> Option<Term> {
+        self.lf.interpret()
+    }
+}
+
+/// Transfer a finished syntactic object to PF and LF
+///
+/// Fails with `FeatureMismatch` if the object still carries unchecked
+/// features other than its category, which neither interface can read.
+pub fn transfer(tree: &SyntacticObject) -> Result<Transfer, DerivationError> {
+    if !tree.is_complete() {
+        return Err(DerivationError::FeatureMismatch);
+    }
+    Ok(Transfer { pf: pf::spell_out(tree), lf: lf::lower(tree) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon, Category, Feature, LexItem};
+
+    #[test]
+    fn test_transfer_feeds_both_interfaces() {
+        let tree = parse_sentence("the student thinks the teacher", &test_lexicon()).unwrap();
+        let output = transfer(&tree).unwrap();
+        assert_eq!(output.linearize(), tree.linearize());
+        assert_eq!(output.interpret().unwrap().to_string(), "thinks(the(teacher))(the(student))");
+
+        let pending = SyntacticObject::from_lex(&LexItem::new("who", &[Feature::Cat(Category::D), Feature::Neg(1)]));
+        assert_eq!(transfer(&pending), Err(DerivationError::FeatureMismatch));
+    }
+}
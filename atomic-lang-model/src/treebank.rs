@@ -0,0 +1,342 @@
+//! Treebank Subcategorization Frames
+//!
+//! Reads Penn Treebank-style bracketed trees and extracts each verb's
+//! complement frame: the NP, SBAR and S sisters that follow it inside its
+//! VP. Other sisters (PP, ADVP, ...) are treated as adjuncts. Every frame
+//! becomes a candidate entry selecting its complements in order, with the
+//! subject as specifier, counted across the treebank:
+//!
+//! | Complements | Selectors        |
+//! |-------------|------------------|
+//! | none        | `V D=`           |
+//! | NP          | `V =DP D=`       |
+//! | NP NP       | `V =DP =DP D=`   |
+//! | SBAR        | `V =C D=`        |
+//! | S           | `V =V D=`        |
+//!
+//! ```text
+//! (S (NP-SBJ (DT the) (NN student)) (VP (VBD saw) (NP (DT the) (NN teacher))))
+//! ```
+//!
+//! `parse_indexed_treebank` keeps the coindexation that links a moved
+//! constituent to its trace, `(WHNP-1 (WP who)) ... (NP (-NONE- *T*-1))`,
+//! for scoring movement chains against gold trees (see `chain_eval`).
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::{Category, Feature, LexItem};
+
+/// Malformed bracketing at a byte offset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreebankError {
+    /// Byte offset of the error
+    pub position: usize,
+    /// What was expected
+    pub message: &'static str,
+}
+
+impl fmt::Display for TreebankError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} at byte {}", self.message, self.position)
+    }
+}
+
+/// Treebank tree, with function tags (`-SBJ`, `-1`) stripped from labels
+/// unless read with `parse_indexed_treebank`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PtbTree {
+    /// Part-of-speech tag and word
+    Leaf(String, String),
+    /// Phrase label and constituents
+    Node(String, Vec<PtbTree>),
+}
+
+impl PtbTree {
+    /// Label or part-of-speech tag
+    pub fn tag(&self) -> &str {
+        match self {
+            PtbTree::Leaf(tag, _) | PtbTree::Node(tag, _) => tag,
+        }
+    }
+
+    /// Check whether the tree is an empty element (`-NONE-`)
+    pub fn is_empty_element(&self) -> bool {
+        matches!(self, PtbTree::Leaf(tag, _) if tag == "-NONE-")
+    }
+
+    /// Coindex of an indexed constituent (`WHNP-1`) or empty element
+    /// (`*T*-1`)
+    pub fn index(&self) -> Option<usize> {
+        let indexed = match self {
+            PtbTree::Leaf(_, word) if self.is_empty_element() => word,
+            PtbTree::Leaf(..) => return None,
+            PtbTree::Node(tag, _) => tag,
+        };
+        indexed.rsplit_once('-')?.1.parse().ok()
+    }
+
+    /// Pronounced words, in order, skipping empty elements
+    pub fn words(&self) -> Vec<&str> {
+        match self {
+            PtbTree::Leaf(..) if self.is_empty_element() => Vec::new(),
+            PtbTree::Leaf(_, word) => Vec::from([word.as_str()]),
+            PtbTree::Node(_, children) => children.iter().flat_map(PtbTree::words).collect(),
+        }
+    }
+}
+
+/// Read every tree of a bracketed treebank
+pub fn parse_treebank(text: &str) -> Result<Vec<PtbTree>, TreebankError> {
+    read_treebank(text, false)
+}
+
+/// Read every tree of a bracketed treebank, keeping each constituent's
+/// coindex on its label (`NP-SBJ-1` → `NP-1`)
+pub fn parse_indexed_treebank(text: &str) -> Result<Vec<PtbTree>, TreebankError> {
+    read_treebank(text, true)
+}
+
+/// Deepest bracket nesting read, so malformed or hostile input cannot
+/// overflow the stack (treebank trees stay well under a hundred levels)
+pub const MAX_DEPTH: usize = 256;
+
+fn read_treebank(text: &str, indexed: bool) -> Result<Vec<PtbTree>, TreebankError> {
+    let mut reader = Reader { text, position: 0, indexed, depth: 0 };
+    let mut trees = Vec::new();
+    while reader.skip_whitespace() {
+        // Unwrap the unlabeled bracket the Penn files put around each tree
+        match reader.tree()? {
+            PtbTree::Node(tag, mut children) if tag.is_empty() && children.len() == 1 => trees.push(children.remove(0)),
+            tree => trees.push(tree),
+        }
+    }
+    Ok(trees)
+}
+
+struct Reader<'a> {
+    text: &'a str,
+    position: usize,
+    /// Whether labels keep their coindex
+    indexed: bool,
+    /// Brackets open around the current position
+    depth: usize,
+}
+
+impl Reader<'_> {
+    /// Skip whitespace, reporting whether input remains
+    fn skip_whitespace(&mut self) -> bool {
+        let rest = &self.text[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+        self.position < self.text.len()
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.text[self.position..].chars().next()
+    }
+
+    fn expect(&mut self, c: char, message: &'static str) -> Result<(), TreebankError> {
+        if self.peek() != Some(c) {
+            return Err(TreebankError { position: self.position, message });
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn atom(&mut self) -> &str {
+        self.skip_whitespace();
+        let rest = &self.text[self.position..];
+        let len = rest.find(|c: char| c.is_whitespace() || c == '(' || c == ')').unwrap_or(rest.len());
+        self.position += len;
+        &rest[..len]
+    }
+
+    fn tree(&mut self) -> Result<PtbTree, TreebankError> {
+        if self.depth == MAX_DEPTH {
+            return Err(TreebankError { position: self.position, message: "at most 256 nested brackets" });
+        }
+        self.expect('(', "'('")?;
+        self.depth += 1;
+        let indexed = self.indexed;
+        let tag = label(self.atom(), indexed);
+        let tree = if self.peek() == Some('(') {
+            let mut children = Vec::new();
+            while self.peek() == Some('(') {
+                children.push(self.tree()?);
+            }
+            PtbTree::Node(tag, children)
+        } else {
+            let word = self.atom().to_string();
+            if word.is_empty() {
+                return Err(TreebankError { position: self.position, message: "a word or '('" });
+            }
+            PtbTree::Leaf(tag, word)
+        };
+        self.expect(')', "')'")?;
+        self.depth -= 1;
+        Ok(tree)
+    }
+}
+
+/// Label without function tags or indices (`NP-SBJ-1` → `NP`), or keeping
+/// the coindex if `indexed` (`NP-SBJ-1` → `NP-1`); `-NONE-` is kept
+fn label(tag: &str, indexed: bool) -> String {
+    let base = match tag.split(['-', '=']).next() {
+        Some(base) if !base.is_empty() => base,
+        _ => return tag.to_string(),
+    };
+    // Gapping indices (`NP=2`) mark parallelism, not movement
+    let index = tag.split('=').next()
+        .and_then(|tag| tag.rsplit_once('-'))
+        .filter(|(_, index)| index.parse::<usize>().is_ok());
+    match index {
+        Some((_, index)) if indexed => format!("{}-{}", base, index),
+        _ => base.to_string(),
+    }
+}
+
+/// A verb's observed complement frame
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubcatFrame {
+    /// Verb form as it appears in the treebank
+    pub verb: String,
+    /// Categories of its complements, in order
+    pub complements: Vec<Category>,
+    /// Occurrences in the treebank
+    pub count: usize,
+}
+
+impl SubcatFrame {
+    /// Candidate entry selecting the complements and a DP specifier
+    pub fn item(&self) -> LexItem {
+        let mut features = Vec::from([Feature::Cat(Category::V)]);
+        features.extend(self.complements.iter().cloned().map(Feature::Sel));
+        features.push(Feature::Spec(Category::D));
+        LexItem::new(&self.verb, &features)
+    }
+}
+
+/// Count the complement frames of every verb in `trees`, most frequent
+/// first for each verb, verbs in order of first occurrence
+pub fn subcategorization(trees: &[PtbTree]) -> Vec<SubcatFrame> {
+    let mut frames: Vec<SubcatFrame> = Vec::new();
+    for tree in trees {
+        collect_frames(tree, &mut frames);
+    }
+    let verbs: Vec<String> = frames.iter().fold(Vec::new(), |mut verbs, frame| {
+        if !verbs.contains(&frame.verb) {
+            verbs.push(frame.verb.clone());
+        }
+        verbs
+    });
+    // Stable sort keeps first-seen order among equally frequent frames
+    frames.sort_by_key(|frame| (verbs.iter().position(|verb| *verb == frame.verb), usize::MAX - frame.count));
+    frames
+}
+
+fn collect_frames(tree: &PtbTree, frames: &mut Vec<SubcatFrame>) {
+    let PtbTree::Node(tag, children) = tree else {
+        return;
+    };
+    if tag == "VP" {
+        let verb = children.iter().enumerate().find_map(|(i, child)| match child {
+            PtbTree::Leaf(tag, word) if tag.starts_with("VB") => Some((i, word)),
+            _ => None,
+        });
+        if let Some((i, word)) = verb {
+            let complements: Vec<Category> = children[i + 1..].iter()
+                .filter_map(|sister| match sister.tag() {
+                    "NP" => Some(Category::DP),
+                    "SBAR" => Some(Category::C),
+                    "S" => Some(Category::V),
+                    _ => None,
+                })
+                .collect();
+            let verb = word.to_lowercase();
+            match frames.iter_mut().find(|frame| frame.verb == verb && frame.complements == complements) {
+                Some(frame) => frame.count += 1,
+                None => frames.push(SubcatFrame { verb, complements, count: 1 }),
+            }
+        }
+    }
+    children.iter().for_each(|child| collect_frames(child, frames));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_sentence, test_lexicon};
+
+    const TREEBANK: &str = "
+        ( (S (NP-SBJ (DT the) (NN student)) (VP (VBD saw) (NP (DT the) (NN teacher)))) )
+        ( (S (NP-SBJ (DT a) (NN tutor)) (VP (VBD saw) (NP (DT a) (NN student)) (PP (IN near) (NP (DT the) (NN teacher))))) )
+        ( (S (NP-SBJ (DT the) (NN teacher)) (VP (VBD saw))) )
+        ( (S (NP-SBJ (DT the) (NN tutor)) (VP (VBD gave) (NP (DT the) (NN student)) (NP (DT a) (NN book)))) )
+        ( (S (NP-SBJ (DT the) (NN student)) (VP (VBD said) (SBAR (IN that) (S (NP-SBJ (DT the) (NN tutor)) (VP (VBD left)))))) )
+    ";
+
+    #[test]
+    fn test_frames_are_counted() {
+        let trees = parse_treebank(TREEBANK).unwrap();
+        assert_eq!(trees.len(), 5);
+        assert_eq!(trees[0].tag(), "S");
+
+        let frames = subcategorization(&trees);
+        let summary: Vec<(&str, &[Category], usize)> = frames.iter()
+            .map(|frame| (frame.verb.as_str(), frame.complements.as_slice(), frame.count))
+            .collect();
+        assert_eq!(summary, vec![
+            ("saw", &[Category::DP][..], 2),
+            ("saw", &[][..], 1),
+            ("gave", &[Category::DP, Category::DP][..], 1),
+            ("said", &[Category::C][..], 1),
+            ("left", &[][..], 1),
+        ]);
+    }
+
+    #[test]
+    fn test_candidates_feed_the_lexicon() {
+        let frames = subcategorization(&parse_treebank(TREEBANK).unwrap());
+        let mut lexicon: Vec<LexItem> = frames.iter().map(SubcatFrame::item).collect();
+        assert_eq!(lexicon[0].to_string(), "saw :: V =DP D=");
+        lexicon.extend(test_lexicon());
+        assert!(parse_sentence("the student saw the teacher", &lexicon).is_ok());
+        assert!(parse_sentence("the tutor gave the student the teacher", &lexicon).is_ok());
+    }
+
+    #[test]
+    fn test_malformed_brackets_are_located() {
+        assert_eq!(
+            parse_treebank("(S (NP (DT the)").unwrap_err(),
+            TreebankError { position: 15, message: "')'" }
+        );
+        assert_eq!(parse_treebank("(NN)").unwrap_err().message, "a word or '('");
+
+        let deep = "(X ".repeat(MAX_DEPTH) + "(NN student)" + &")".repeat(MAX_DEPTH);
+        assert_eq!(
+            parse_treebank(&deep).unwrap_err(),
+            TreebankError { position: 3 * MAX_DEPTH, message: "at most 256 nested brackets" }
+        );
+        let nested = "(X ".repeat(MAX_DEPTH - 1) + "(NN student)" + &")".repeat(MAX_DEPTH - 1);
+        assert!(parse_treebank(&nested).is_ok());
+        assert_eq!(label("NP-SBJ-1", false), "NP");
+        assert_eq!(label("-NONE-", false), "-NONE-");
+    }
+
+    #[test]
+    fn test_coindexation_is_kept() {
+        assert_eq!(label("NP-SBJ-1", true), "NP-1");
+        assert_eq!(label("NP-SBJ=2", true), "NP");
+        assert_eq!(label("-NONE-", true), "-NONE-");
+
+        let trees = parse_indexed_treebank("(SBAR (WHNP-1 (WP who)) (S (NP-SBJ (-NONE- *T*-1)) (VP (VBD left))))").unwrap();
+        let PtbTree::Node(_, children) = &trees[0] else { panic!("expected a phrase") };
+        assert_eq!(children[0].tag(), "WHNP-1");
+        assert_eq!(children[0].index(), Some(1));
+        assert_eq!(children[1].index(), None);
+        assert_eq!(trees[0].words(), ["who", "left"]);
+    }
+}
@@ -0,0 +1,164 @@
+//! Parse-Failure Triage
+//!
+//! Turns a pile of failed sentences into a ranked list of grammar gaps.
+//! Each failure gets a signature: the derivation error and the objects the
+//! search was stuck with at its furthest dead end, written as their labels
+//! and unchecked features (`DP, V =DP`). Sentences with the same signature
+//! fail for the same reason, so clusters are ranked by size and each comes
+//! with an explanation of its first sentence (see `explain`).
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec, vec::Vec};
+
+use core::fmt;
+
+use crate::explain::{explain, Explanation};
+use crate::{Category, DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Sentences listed per cluster when a report is displayed
+pub const SHOWN_SENTENCES: usize = 3;
+
+/// What a failure looks like: the error and where the search got stuck
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureSignature {
+    /// Error the parser reported
+    pub error: DerivationError,
+    /// Label and unchecked features of each stuck object, in surface order
+    pub stuck: Vec<(Category, Vec<Feature>)>,
+}
+
+impl FailureSignature {
+    /// Signature of an explained failure
+    pub fn of(explanation: &Explanation) -> Self {
+        let stuck = explanation.stuck.iter().map(|obj| (obj.label.clone(), pending(obj))).collect();
+        Self { error: explanation.error, stuck }
+    }
+}
+
+/// Features still to check, the category and agreement aside
+fn pending(obj: &SyntacticObject) -> Vec<Feature> {
+    obj.features.iter().filter(|f| !matches!(f, Feature::Cat(_) | Feature::Agr(_))).cloned().collect()
+}
+
+impl fmt::Display for FailureSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        for (i, (label, features)) in self.stuck.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { ": " } else { ", " }, label)?;
+            for feature in features {
+                write!(f, " {}", feature)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Failed sentences sharing a signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureCluster {
+    /// The shared signature
+    pub signature: FailureSignature,
+    /// Sentences in input order
+    pub sentences: Vec<String>,
+    /// Explanation of the first sentence
+    pub explanation: Explanation,
+}
+
+/// Failure clusters, largest first
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TriageReport {
+    /// Sentences triaged
+    pub sentences: usize,
+    /// Clusters of the sentences that failed; equal sizes keep first-seen order
+    pub clusters: Vec<FailureCluster>,
+}
+
+impl TriageReport {
+    /// Sentences that failed
+    pub fn failures(&self) -> usize {
+        self.clusters.iter().map(|cluster| cluster.sentences.len()).sum()
+    }
+}
+
+impl fmt::Display for TriageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}/{} sentences failed in {} clusters", self.failures(), self.sentences, self.clusters.len())?;
+        for (rank, cluster) in self.clusters.iter().enumerate() {
+            writeln!(f, "{:>3}. {} × {}", rank + 1, cluster.sentences.len(), cluster.signature)?;
+            writeln!(f, "     {}", cluster.explanation)?;
+            for sentence in cluster.sentences.iter().take(SHOWN_SENTENCES) {
+                writeln!(f, "     - {}", sentence)?;
+            }
+            if cluster.sentences.len() > SHOWN_SENTENCES {
+                writeln!(f, "     … {} more", cluster.sentences.len() - SHOWN_SENTENCES)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cluster the sentences that fail to parse by failure signature
+pub fn triage<'s>(
+    sentences: impl IntoIterator<Item = &'s str>,
+    lexicon: &[LexItem],
+    options: &GrammarOptions,
+) -> TriageReport {
+    let mut report = TriageReport::default();
+    for sentence in sentences {
+        report.sentences += 1;
+        let Some(explanation) = explain(sentence, lexicon, options) else {
+            continue;
+        };
+        let signature = FailureSignature::of(&explanation);
+        match report.clusters.iter_mut().find(|cluster| cluster.signature == signature) {
+            Some(cluster) => cluster.sentences.push(sentence.to_string()),
+            None => report.clusters.push(FailureCluster { signature, sentences: vec![sentence.to_string()], explanation }),
+        }
+    }
+    // Stable, so clusters of equal size keep first-seen order
+    report.clusters.sort_by_key(|cluster| core::cmp::Reverse(cluster.sentences.len()));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_lexicon;
+
+    #[test]
+    fn test_failures_cluster_by_signature() {
+        let sentences = [
+            "the student thinks",
+            "the student left",
+            "a teacher thinks",
+            "the tutor thinks",
+            "left",
+            "the robot left",
+        ];
+        let report = triage(sentences, &test_lexicon(), &GrammarOptions::default());
+
+        assert_eq!((report.failures(), report.sentences), (5, 6));
+        let sizes: Vec<usize> = report.clusters.iter().map(|cluster| cluster.sentences.len()).collect();
+        assert_eq!(sizes, [3, 1, 1]);
+
+        // The largest gap: a transitive verb with no object
+        let top = &report.clusters[0];
+        assert_eq!(top.sentences, ["the student thinks", "a teacher thinks", "the tutor thinks"]);
+        assert_eq!(top.signature.to_string(), "No valid operations available: DP, V =DP D=");
+        assert_eq!(report.clusters[1].signature.to_string(), "No valid operations available: V D=");
+        assert_eq!(report.clusters[2].signature.to_string(), "Invalid operation");
+    }
+
+    #[test]
+    fn test_report_display() {
+        let report = triage(["the student thinks", "a tutor thinks"], &test_lexicon(), &GrammarOptions::default());
+        assert_eq!(
+            report.to_string(),
+            "2/2 sentences failed in 1 clusters\n  \
+             1. 2 × No valid operations available: DP, V =DP D=\n     \
+             'thinks' requires a DP complement to its right (=DP); nothing follows it\n     \
+             - the student thinks\n     \
+             - a tutor thinks\n"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Typed Grammars
+//!
+//! The engine labels constituents with `Category` and checks `Feature`s.
+//! Embedders with their own category enum implement `CategoryLike` for it
+//! and `FeatureLike` for a feature type over it (or use `PackedFeature`,
+//! two bytes per feature, for embedded lexicons), build a `Grammar` from
+//! typed entries, and read typed trees back; derivations run on the same
+//! engine. A `Grammar` stores its entries in the typed form and compiles
+//! them to `LexItem`s for each parse, so a packed lexicon stays two bytes
+//! per feature at rest.
+//!
+//! Typed categories stand for engine labels, so a type may name at most
+//! the eleven categories of `Category`, however many it declares.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use core::marker::PhantomData;
+
+use crate::{parse_with_options, AgrBundle, Category, DerivationError, Feature, GrammarOptions, LexItem, SyntacticObject};
+
+/// Category type usable as a derivation label
+pub trait CategoryLike: Clone + PartialEq {
+    /// Engine category this one stands for
+    fn to_category(&self) -> Category;
+
+    /// Category standing for an engine label, if the type has one
+    fn from_category(category: &Category) -> Option<Self>;
+
+    /// Category for a derived label, falling back to the head of a phrase
+    fn of_label(label: &Category) -> Option<Self> {
+        Self::from_category(label).or_else(|| Self::from_category(&label.head()))
+    }
+}
+
+/// Feature type usable in lexical entries
+pub trait FeatureLike: Clone {
+    /// Category type the features refer to
+    type Category: CategoryLike;
+
+    /// Engine feature this one stands for
+    fn to_feature(&self) -> Feature;
+
+    /// Feature standing for an engine feature, if the type has one
+    fn from_feature(feature: &Feature) -> Option<Self>;
+}
+
+impl CategoryLike for Category {
+    fn to_category(&self) -> Category {
+        self.clone()
+    }
+
+    fn from_category(category: &Category) -> Option<Self> {
+        Some(category.clone())
+    }
+}
+
+impl FeatureLike for Feature {
+    type Category = Category;
+
+    fn to_feature(&self) -> Feature {
+        self.clone()
+    }
+
+    fn from_feature(feature: &Feature) -> Option<Self> {
+        Some(feature.clone())
+    }
+}
+
+/// Engine categories in packing order
+const CATEGORIES: [Category; 11] = [
+    Category::N,
+    Category::V,
+    Category::D,
+    Category::C,
+    Category::P,
+    Category::S,
+    Category::NP,
+    Category::VP,
+    Category::DP,
+    Category::CP,
+    Category::PP,
+];
+
+/// Feature packed into two bytes: the kind, then the category's position,
+/// the movement index, or the agreement bundle's bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedFeature(u16);
+
+impl PackedFeature {
+    /// Pack an engine feature
+    pub fn pack(feature: &Feature) -> Self {
+        let category = |category: &Category| CATEGORIES.iter().position(|c| c == category).unwrap_or(0) as u16;
+        Self(match feature {
+            Feature::Cat(c) => category(c),
+            Feature::Sel(c) => 1 << 8 | category(c),
+            Feature::Spec(c) => 2 << 8 | category(c),
+            Feature::Adjoin(c) => 3 << 8 | category(c),
+            Feature::Pos(i) => 4 << 8 | *i as u16,
+            Feature::Neg(i) => 5 << 8 | *i as u16,
+            Feature::Agr(bundle) => 6 << 8 | bundle.bits() as u16,
+        })
+    }
+
+    /// Unpack into an engine feature
+    pub fn unpack(self) -> Feature {
+        let [kind, payload] = self.0.to_be_bytes();
+        let category = || CATEGORIES[payload as usize].clone();
+        match kind {
+            0 => Feature::Cat(category()),
+            1 => Feature::Sel(category()),
+            2 => Feature::Spec(category()),
+            3 => Feature::Adjoin(category()),
+            4 => Feature::Pos(payload),
+            5 => Feature::Neg(payload),
+            _ => Feature::Agr(AgrBundle::from_bits(payload).unwrap_or_default()),
+        }
+    }
+
+    /// Packed bits
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Feature from packed bits, if they encode one
+    pub const fn from_bits(bits: u16) -> Option<Self> {
+        let [kind, payload] = bits.to_be_bytes();
+        match kind {
+            0..=3 if (payload as usize) < CATEGORIES.len() => Some(Self(bits)),
+            4 | 5 => Some(Self(bits)),
+            6 if AgrBundle::from_bits(payload).is_some() => Some(Self(bits)),
+            _ => None,
+        }
+    }
+}
+
+impl FeatureLike for PackedFeature {
+    type Category = Category;
+
+    fn to_feature(&self) -> Feature {
+        self.unpack()
+    }
+
+    fn from_feature(feature: &Feature) -> Option<Self> {
+        Some(Self::pack(feature))
+    }
+}
+
+/// Derived constituent with typed labels
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tree<C> {
+    /// Label, if the category type has one for it
+    pub label: Option<C>,
+    /// Pronounced form of a leaf (`None` for phrases and traces)
+    pub phon: Option<String>,
+    /// Constituents, in order
+    pub children: Vec<Tree<C>>,
+}
+
+impl<C: CategoryLike> Tree<C> {
+    /// Typed view of a derived object
+    pub fn new(obj: &SyntacticObject) -> Self {
+        Self {
+            label: C::of_label(&obj.label),
+            phon: obj.phon.clone(),
+            children: obj.children.iter().map(Tree::new).collect(),
+        }
+    }
+}
+
+/// Grammar over embedder-defined category and feature types
+#[derive(Debug, Clone)]
+pub struct Grammar<C, F> {
+    /// Pronounced form and typed features of each entry
+    entries: Vec<(String, Vec<F>)>,
+    options: GrammarOptions,
+    types: PhantomData<C>,
+}
+
+impl<C: CategoryLike, F: FeatureLike<Category = C>> Grammar<C, F> {
+    /// Empty grammar with the given options
+    pub fn new(options: GrammarOptions) -> Self {
+        Self { entries: Vec::new(), options, types: PhantomData }
+    }
+
+    /// The grammar with an entry added
+    pub fn item(mut self, phon: &str, features: &[F]) -> Self {
+        self.entries.push((phon.to_string(), features.to_vec()));
+        self
+    }
+
+    /// Engine lexicon the entries compile to
+    pub fn lexicon(&self) -> Vec<LexItem> {
+        self.entries.iter()
+            .map(|(phon, features)| {
+                let features: Vec<Feature> = features.iter().map(FeatureLike::to_feature).collect();
+                LexItem::new(phon, &features)
+            })
+            .collect()
+    }
+
+    /// Typed features of each entry for `phon`
+    pub fn entries(&self, phon: &str) -> Vec<&[F]> {
+        self.entries.iter()
+            .filter(|(entry, _)| entry == phon)
+            .map(|(_, features)| features.as_slice())
+            .collect()
+    }
+
+    /// Derive a sentence, with typed labels
+    pub fn parse(&self, sentence: &str) -> Result<Tree<C>, DerivationError> {
+        parse_with_options(sentence, &self.lexicon(), &self.options).map(|tree| Tree::new(&tree))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Cat {
+        Noun,
+        Verb,
+        Det,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Feat {
+        Is(Cat),
+        Takes(Cat),
+        After(Cat),
+    }
+
+    impl CategoryLike for Cat {
+        fn to_category(&self) -> Category {
+            match self {
+                Cat::Noun => Category::N,
+                Cat::Verb => Category::V,
+                Cat::Det => Category::D,
+            }
+        }
+
+        fn from_category(category: &Category) -> Option<Self> {
+            [Cat::Noun, Cat::Verb, Cat::Det].into_iter().find(|cat| cat.to_category() == *category)
+        }
+    }
+
+    impl FeatureLike for Feat {
+        type Category = Cat;
+
+        fn to_feature(&self) -> Feature {
+            match self {
+                Feat::Is(cat) => Feature::Cat(cat.to_category()),
+                Feat::Takes(cat) => Feature::Sel(cat.to_category()),
+                Feat::After(cat) => Feature::Spec(cat.to_category()),
+            }
+        }
+
+        fn from_feature(feature: &Feature) -> Option<Self> {
+            match feature {
+                Feature::Cat(c) => Cat::from_category(c).map(Feat::Is),
+                Feature::Sel(c) => Cat::from_category(c).map(Feat::Takes),
+                Feature::Spec(c) => Cat::from_category(c).map(Feat::After),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_embedder_types() {
+        let grammar: Grammar<Cat, Feat> = Grammar::new(GrammarOptions::default())
+            .item("the", &[Feat::Is(Cat::Det), Feat::Takes(Cat::Noun)])
+            .item("student", &[Feat::Is(Cat::Noun)])
+            .item("left", &[Feat::Is(Cat::Verb), Feat::After(Cat::Det)]);
+
+        // Phrasal labels fall back to the head's typed category
+        let tree = grammar.parse("the student left").unwrap();
+        assert_eq!(tree.label, Some(Cat::Verb));
+        assert_eq!(tree.children[0].label, Some(Cat::Det));
+        assert_eq!(tree.children[0].children[1].phon.as_deref(), Some("student"));
+        assert_eq!(grammar.entries("left"), [&[Feat::Is(Cat::Verb), Feat::After(Cat::Det)][..]]);
+        assert!(grammar.parse("left the student").is_err());
+    }
+
+    #[test]
+    fn test_packed_features() {
+        let features = [
+            Feature::Cat(Category::PP),
+            Feature::Sel(Category::N),
+            Feature::Spec(Category::DP),
+            Feature::Adjoin(Category::V),
+            Feature::Pos(7),
+            Feature::Neg(255),
+            Feature::Agr(AgrBundle::third(crate::Number::Plural)),
+        ];
+        for feature in &features {
+            let packed = PackedFeature::pack(feature);
+            assert_eq!(packed.unpack(), *feature);
+            assert_eq!(PackedFeature::from_bits(packed.bits()), Some(packed));
+        }
+        assert_eq!(PackedFeature::from_bits(0x000b), None);
+        assert_eq!(PackedFeature::from_bits(0x060c), None);
+        assert_eq!(PackedFeature::from_bits(0x0700), None);
+
+        let grammar: Grammar<Category, PackedFeature> = Grammar::new(GrammarOptions::default())
+            .item("the", &[PackedFeature::pack(&Feature::Cat(Category::D)), PackedFeature::pack(&Feature::Sel(Category::N))])
+            .item("student", &[PackedFeature::pack(&Feature::Cat(Category::N))])
+            .item("left", &[PackedFeature::pack(&Feature::Cat(Category::V)), PackedFeature::pack(&Feature::Spec(Category::D))]);
+        assert_eq!(grammar.parse("the student left").unwrap().label, Some(Category::VP));
+        // The entries are kept packed
+        assert_eq!(core::mem::size_of_val(grammar.entries("left")[0]), 4);
+    }
+}
@@ -0,0 +1,213 @@
+//! Feature Valuation
+//!
+//! Abstract terminals (see `vocabulary`) may leave agreement unvalued, so
+//! one entry serves every cell of a paradigm instead of one entry per cell.
+//! A terminal agrees if its lexical entry carries an agreement feature of
+//! its own (`[pres] :: S [] =V D=`), and every attribute the terminal does
+//! not specify is unvalued. After the derivation converges, it takes its
+//! value from context, the agreement of the specifier of the phrase the
+//! terminal heads, which is the specifier's head noun's (Agree), and
+//! otherwise from the grammar's defaults (third person singular, say). Each
+//! resolution is logged with its source; an attribute with neither stays
+//! unvalued.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use crate::agreement;
+use crate::vocabulary::Terminal;
+use crate::{AgrBundle, Feature, Number, SyntacticObject};
+
+/// Where a feature's value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The specifier of the terminal's phrase
+    Agree,
+    /// The grammar's default for the attribute
+    Default,
+}
+
+/// Attributes valued from one source during resolution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resolution {
+    /// Terminal as listed in the lexicon
+    pub terminal: String,
+    /// Values assigned, with the attributes left alone unspecified
+    pub valued: AgrBundle,
+    /// Where the values came from
+    pub source: Source,
+}
+
+/// Default values for attributes left unvalued by context
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Valuation {
+    /// Default value of each attribute that has one
+    pub defaults: AgrBundle,
+}
+
+impl Valuation {
+    /// Valuation with the given defaults
+    pub fn new(defaults: AgrBundle) -> Self {
+        Self { defaults }
+    }
+
+    /// Third person singular by default
+    pub fn third_singular() -> Self {
+        Self::new(AgrBundle::third(Number::Singular))
+    }
+
+    /// Value the unvalued features of a derivation, logging each resolution
+    pub fn resolve(&self, tree: &SyntacticObject) -> (SyntacticObject, Vec<Resolution>) {
+        let mut tree = tree.clone();
+        let mut log = Vec::new();
+        self.value(&mut tree, AgrBundle::default(), &mut log);
+        (tree, log)
+    }
+
+    fn value(&self, node: &mut SyntacticObject, context: AgrBundle, log: &mut Vec<Resolution>) {
+        if node.phon.is_some() {
+            self.value_terminal(node, context, log);
+            return;
+        }
+        let agrees = node.children.len() == 2 && first_terminal(&node.children[1]).is_some_and(is_unvalued);
+        if agrees {
+            let specified = agreement::agreement(&node.children[0].features).copied().unwrap_or_default();
+            self.value(&mut node.children[0], context, log);
+            self.value(&mut node.children[1], specified, log);
+        } else {
+            node.children.iter_mut().for_each(|child| self.value(child, context, log));
+        }
+    }
+
+    /// Value an unvalued terminal from `context`, then from the defaults
+    fn value_terminal(&self, leaf: &mut SyntacticObject, context: AgrBundle, log: &mut Vec<Resolution>) {
+        let Some(mut terminal) = unvalued(leaf) else {
+            return;
+        };
+        let listed = terminal.to_string();
+        for (values, source) in [(context, Source::Agree), (self.defaults, Source::Default)] {
+            let valued = fill(&mut terminal.agreement, values);
+            if valued != AgrBundle::default() {
+                log.push(Resolution { terminal: listed.clone(), valued, source });
+            }
+        }
+        if terminal.to_string() != listed {
+            let features = &leaf.features;
+            let own = (0..features.len())
+                .find(|&i| matches!(features[i], Feature::Agr(_)) && (i == 0 || !features[i - 1].is_selector()));
+            if let Some(own) = own {
+                leaf.features[own] = Feature::Agr(terminal.agreement);
+            }
+            leaf.phon = Some(terminal.to_string());
+        }
+    }
+}
+
+/// Give each attribute `bundle` leaves unspecified its value in `values`,
+/// returning the values given
+fn fill(bundle: &mut AgrBundle, values: AgrBundle) -> AgrBundle {
+    let valued = AgrBundle {
+        person: values.person.filter(|_| bundle.person.is_none()),
+        number: values.number.filter(|_| bundle.number.is_none()),
+        gender: values.gender.filter(|_| bundle.gender.is_none()),
+    };
+    bundle.person = bundle.person.or(valued.person);
+    bundle.number = bundle.number.or(valued.number);
+    bundle.gender = bundle.gender.or(valued.gender);
+    valued
+}
+
+/// A leaf's terminal, if it is abstract and agrees, whether or not
+/// anything is left to value
+fn unvalued(leaf: &SyntacticObject) -> Option<Terminal> {
+    agreement::agreement(&leaf.features)?;
+    Terminal::parse(leaf.phon.as_deref()?)
+}
+
+/// Whether a leaf has an attribute left to value
+fn is_unvalued(leaf: &SyntacticObject) -> bool {
+    unvalued(leaf).is_some_and(|terminal| {
+        let AgrBundle { person, number, gender } = terminal.agreement;
+        person.is_none() || number.is_none() || gender.is_none()
+    })
+}
+
+/// Leftmost terminal of a constituent
+fn first_terminal(obj: &SyntacticObject) -> Option<&SyntacticObject> {
+    match &obj.phon {
+        Some(_) => Some(obj),
+        None => obj.children.first().and_then(first_terminal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_with_options, Category, GrammarOptions, LexItem, PfRule, SpellOut, VocabularyItem};
+
+    /// One tense entry for the whole present paradigm
+    fn lexicon() -> Vec<LexItem> {
+        let mut lexicon = vec![
+            LexItem::new("[pres]", &[
+                Feature::Cat(Category::S),
+                Feature::Agr(AgrBundle::default()),
+                Feature::Sel(Category::V),
+                Feature::Spec(Category::D),
+            ]),
+            LexItem::new("[pron:1.sg]", &[Feature::Cat(Category::D), Feature::Agr(AgrBundle::parse("1.sg").unwrap())]),
+            LexItem::new("[pron:3.pl]", &[Feature::Cat(Category::D), Feature::Agr(AgrBundle::third(Number::Plural))]),
+            LexItem::new("students", &[Feature::Cat(Category::N), Feature::Agr(AgrBundle::third(Number::Plural))]),
+            LexItem::new("leave", &[Feature::Cat(Category::V)]),
+        ];
+        lexicon.extend(GrammarOptions::default().lexicon());
+        lexicon
+    }
+
+    fn options(valuation: Valuation) -> GrammarOptions {
+        let vocabulary = vec![
+            VocabularyItem::new("I", "pron", AgrBundle::parse("1.sg").unwrap()),
+            VocabularyItem::new("they", "pron", AgrBundle::third(Number::Plural)),
+            VocabularyItem::new("-s", "pres", AgrBundle::third(Number::Singular)),
+            VocabularyItem::new("-", "pres", AgrBundle::default()),
+        ];
+        let mut spell_out = SpellOut::english();
+        spell_out.rules.insert(0, PfRule::VocabularyInsertion(vocabulary));
+        GrammarOptions { spell_out, valuation, ..GrammarOptions::default() }
+    }
+
+    #[test]
+    fn test_values_from_context_and_defaults() {
+        let options = options(Valuation::third_singular());
+        let pronounce = |terminals| options.pronounce(&parse_with_options(terminals, &lexicon(), &options).unwrap()).unwrap();
+        assert_eq!(pronounce("[pron:1.sg] [pres] leave"), "I leave");
+        assert_eq!(pronounce("[pron:3.pl] [pres] leave"), "they leave");
+        // An ordinary subject agrees through its head noun
+        assert_eq!(pronounce("the students [pres] leave"), "the students leave");
+        // A subject without phi-features leaves the defaults
+        assert_eq!(pronounce("the student [pres] leave"), "the student leaves");
+    }
+
+    #[test]
+    fn test_resolution_log() {
+        let tree = parse_with_options("the student [pres] leave", &lexicon(), &GrammarOptions::default()).unwrap();
+        let (resolved, log) = Valuation::third_singular().resolve(&tree);
+        assert_eq!(resolved.linearize(), "the student [pres:3.sg] leave");
+        assert_eq!(log, [Resolution {
+            terminal: "[pres]".to_string(),
+            valued: AgrBundle::third(Number::Singular),
+            source: Source::Default,
+        }]);
+
+        let tree = parse_with_options("[pron:1.sg] [pres] leave", &lexicon(), &GrammarOptions::default()).unwrap();
+        let (_, log) = Valuation::default().resolve(&tree);
+        assert_eq!(log.len(), 1);
+        assert_eq!((log[0].terminal.as_str(), log[0].source), ("[pres]", Source::Agree));
+        assert_eq!(log[0].valued, AgrBundle::parse("1.sg").unwrap());
+
+        // Without context or defaults the features stay unvalued
+        let tree = parse_with_options("the student [pres] leave", &lexicon(), &GrammarOptions::default()).unwrap();
+        let (resolved, log) = Valuation::default().resolve(&tree);
+        assert_eq!(resolved, tree);
+        assert!(log.is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! Tree Visualization
+//!
+//! Exports a derived tree as Graphviz DOT, as LaTeX (`tikz-qtree`) or as a
+//! Penn Treebank bracketing, with its movement chains marked: the moved
+//! phrase and the trace it leaves share a subscript index, numbered as in
+//! `lf`, and an arrow runs from the trace up to the landing site. Movement
+//! leaves traces rather than copies in this crate, so the lower link of a
+//! chain is always drawn as `t`.
+//!
+//! ```text
+//! digraph tree {
+//!   node [shape=plaintext];
+//!   edge [arrowhead=none];
+//!   n0 [label=<DP>];
+//!   ...
+//!   n9 -> n3 [style=dashed, arrowhead=normal, constraint=false];
+//! }
+//! ```
+//!
+//! The bracketing follows the treebank's conventions for empty elements, so
+//! `parse_indexed_treebank` reads it back: a trace is `*T*` under `-NONE-`,
+//! coindexed with the moved phrase, and a silent head is `0`:
+//!
+//! ```text
+//! (VP (DP (D the) (N (N student) (CP (D-1 who) (CP (C (-NONE- 0)) (VP (D (-NONE- *T*-1)) (V left)))))) (V smiled))
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::{String, ToString}, vec::Vec};
+
+use crate::constraints::{chains, Chain};
+use crate::SyntacticObject;
+
+/// Chain index (1-based) of the phrase at `path`, and whether it is the trace
+fn chain_index(chains: &[Chain], path: &[usize]) -> Option<(usize, bool)> {
+    chains.iter().enumerate().find_map(|(i, chain)| {
+        if chain.trace == path {
+            Some((i + 1, true))
+        } else if chain.landing.len() + 1 == path.len() && path.starts_with(&chain.landing) && path.last() == Some(&0) {
+            Some((i + 1, false))
+        } else {
+            None
+        }
+    })
+}
+
+/// Render `tree` as a Graphviz digraph, movement chains as dashed arrows
+pub fn to_dot(tree: &SyntacticObject) -> String {
+    let chains = chains(tree);
+    let mut lines = Vec::from([
+        String::from("digraph tree {"),
+        String::from("  node [shape=plaintext];"),
+        String::from("  edge [arrowhead=none];"),
+    ]);
+    // Node id of each chain's trace and moved phrase
+    let mut links = Vec::from_iter(chains.iter().map(|_| (0, 0)));
+    let mut next = 0;
+    dot_node(tree, &mut Vec::new(), &chains, &mut lines, &mut links, &mut next);
+    for (trace, mover) in links {
+        lines.push(format!("  n{} -> n{} [style=dashed, arrowhead=normal, constraint=false];", trace, mover));
+    }
+    lines.push(String::from("}"));
+    lines.join("\n") + "\n"
+}
+
+fn dot_node(
+    node: &SyntacticObject,
+    path: &mut Vec<usize>,
+    chains: &[Chain],
+    lines: &mut Vec<String>,
+    links: &mut [(usize, usize)],
+    next: &mut usize,
+) -> usize {
+    let id = *next;
+    *next += 1;
+    let index = chain_index(chains, path);
+    if let Some((i, trace)) = index {
+        if trace { links[i - 1].0 = id } else { links[i - 1].1 = id }
+    }
+    let subscript = index.map_or(String::new(), |(i, _)| format!("<sub>{}</sub>", i));
+
+    if node.is_trace() {
+        lines.push(format!("  n{} [label=<t{}>];", id, subscript));
+        return id;
+    }
+    lines.push(format!("  n{} [label=<{:?}{}>];", id, node.label, subscript));
+    if let Some(phon) = &node.phon {
+        let word = *next;
+        *next += 1;
+        let phon = if phon.is_empty() { "ε" } else { phon };
+        lines.push(format!("  n{} [label=\"{}\"];", word, phon.replace('\\', "\\\\").replace('"', "\\\"")));
+        lines.push(format!("  n{} -> n{};", id, word));
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        let child = dot_node(child, path, chains, lines, links, next);
+        path.pop();
+        lines.push(format!("  n{} -> n{};", id, child));
+    }
+    id
+}
+
+/// Render `tree` as a `tikz-qtree` picture, movement chains as dashed arrows
+///
+/// The picture needs `\usepackage{tikz-qtree}` in the preamble.
+pub fn to_latex(tree: &SyntacticObject) -> String {
+    let chains = chains(tree);
+    let mut out = String::from("\\begin{tikzpicture}\n\\Tree ");
+    latex_node(tree, &mut Vec::new(), &chains, &mut out);
+    out.push('\n');
+    for i in 1..=chains.len() {
+        out.push_str(&format!("\\draw[->, dashed] (t{}) to[bend left] (m{});\n", i, i));
+    }
+    out.push_str("\\end{tikzpicture}\n");
+    out
+}
+
+fn latex_node(node: &SyntacticObject, path: &mut Vec<usize>, chains: &[Chain], out: &mut String) {
+    let index = chain_index(chains, path);
+    if node.is_trace() {
+        match index {
+            Some((i, _)) => out.push_str(&format!("\\node(t{}){{$t_{{{}}}$}};", i, i)),
+            None => out.push_str("$t$"),
+        }
+        return;
+    }
+
+    let label = match index {
+        Some((i, _)) => format!("\\node(m{}){{{:?}$_{{{}}}$}};", i, node.label, i),
+        None => format!("{:?}", node.label),
+    };
+    out.push_str(&format!("[.{} ", label));
+    if let Some(phon) = &node.phon {
+        if phon.is_empty() {
+            out.push_str("$\\varepsilon$ ");
+        } else {
+            out.push_str(&latex_escape(phon));
+            out.push(' ');
+        }
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        latex_node(child, path, chains, out);
+        path.pop();
+        out.push(' ');
+    }
+    out.push(']');
+}
+
+/// Render `tree` as a Penn Treebank bracketing, each label followed by the
+/// node's features in brackets if `features` is set (see
+/// `SyntacticObject::penn_with_features`)
+pub fn to_penn(tree: &SyntacticObject, features: bool) -> String {
+    let mut out = String::new();
+    penn_node(tree, &mut Vec::new(), &chains(tree), features, &mut out);
+    out
+}
+
+fn penn_node(node: &SyntacticObject, path: &mut Vec<usize>, chains: &[Chain], features: bool, out: &mut String) {
+    let index = chain_index(chains, path);
+    out.push_str(&format!("({:?}", node.label));
+    if let Some((i, false)) = index {
+        out.push_str(&format!("-{}", i));
+    }
+    if features && !node.features.is_empty() {
+        let features: Vec<String> = node.features.iter().map(|f| f.to_string()).collect();
+        out.push_str(&format!("[{}]", features.join(" ")));
+    }
+
+    if node.is_trace() {
+        match index {
+            Some((i, _)) => out.push_str(&format!(" (-NONE- *T*-{}))", i)),
+            None => out.push_str(" (-NONE- *T*))"),
+        }
+        return;
+    }
+    match node.phon.as_deref() {
+        Some("") => out.push_str(" (-NONE- 0)"),
+        Some(phon) => {
+            out.push(' ');
+            out.push_str(phon);
+        }
+        None => {}
+    }
+    for (i, child) in node.children.iter().enumerate() {
+        path.push(i);
+        out.push(' ');
+        penn_node(child, path, chains, features, out);
+        path.pop();
+    }
+    out.push(')');
+}
+
+/// Escape LaTeX's special characters. `\`, `~` and `^` are written as
+/// text-mode commands, since a backslash before them makes a line break or
+/// an accent.
+fn latex_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '{' | '}' | '$' | '&' | '#' | '%' | '_' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_indexed_treebank, parse_sentence, test_lexicon, GrammarOptions, RelativeClauseAnalysis};
+
+    fn relative() -> SyntacticObject {
+        let options = GrammarOptions { relative_clauses: RelativeClauseAnalysis::OperatorMovement, ..GrammarOptions::default() };
+        parse_sentence("the student who left smiled", &options.lexicon()).unwrap()
+    }
+
+    #[test]
+    fn test_dot_draws_chains() {
+        let dot = to_dot(&relative());
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.contains("[label=<t<sub>1</sub>>];"));
+        assert_eq!(dot.matches("<sub>1</sub>").count(), 2);
+        assert!(dot.contains("[label=\"who\"];"));
+        assert_eq!(dot.matches("style=dashed").count(), 1);
+
+        // No movement, no arrows
+        let dot = to_dot(&parse_sentence("the student left", &test_lexicon()).unwrap());
+        assert!(!dot.contains("style=dashed"));
+        assert!(dot.contains("n0 [label=<VP>];"));
+    }
+
+    #[test]
+    fn test_latex_draws_chains() {
+        let latex = to_latex(&relative());
+        assert!(latex.contains("\\node(t1){$t_{1}$};"));
+        assert!(latex.contains("\\node(m1){"));
+        assert!(latex.contains("\\draw[->, dashed] (t1) to[bend left] (m1);"));
+        assert_eq!(latex.matches('[').count(), latex.matches(']').count());
+
+        let latex = to_latex(&parse_sentence("the student left", &test_lexicon()).unwrap());
+        assert!(latex.contains("\\Tree [.VP [.DP [.D the ] [.N student ] ] [.V left ] ]"), "{}", latex);
+        assert!(!latex.contains("\\draw"));
+    }
+
+    #[test]
+    fn test_latex_escapes_special_characters() {
+        assert_eq!(latex_escape("a_b {c} 50%"), "a\\_b \\{c\\} 50\\%");
+        assert_eq!(latex_escape("\\~^"), "\\textbackslash{}\\textasciitilde{}\\textasciicircum{}");
+    }
+
+    #[test]
+    fn test_penn_bracketing() {
+        let tree = parse_sentence("the student left", &test_lexicon()).unwrap();
+        assert_eq!(tree.penn_bracketed(), "(VP (DP (D the) (N student)) (V left))");
+        assert_eq!(tree.penn_with_features(), "(VP[V] (DP[D] (D[D =N] the) (N[N] student)) (V[V D=] left))");
+
+        // Empty elements read back as the treebank writes them
+        let tree = relative();
+        let bracketed = tree.penn_bracketed();
+        assert!(bracketed.contains("(D-1 who)") && bracketed.contains("(D (-NONE- *T*-1))"));
+        assert!(bracketed.contains("(C (-NONE- 0))"));
+        let read = parse_indexed_treebank(&bracketed).unwrap();
+        assert_eq!(read[0].words().join(" "), tree.linearize());
+        assert!(tree.penn_with_features().contains("(C[C =V +2 ~N] (-NONE- 0))"));
+    }
+}
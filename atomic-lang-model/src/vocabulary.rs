@@ -0,0 +1,198 @@
+//! Vocabulary Insertion
+//!
+//! Late insertion (Halle & Marantz 1993): a derivation may run over
+//! abstract terminals, an abstract morpheme with its phi-features written
+//! as a bracketed word (`[pres:3.sg]`, or `[past]` with none), and receive
+//! its exponents only at PF. Vocabulary items compete for each terminal by
+//! the Subset Principle: an item qualifies if it realizes the terminal's
+//! morpheme, the terminal has every agreement value the item specifies, and
+//! its context, if any, is adjacent; the qualifying item with the most
+//! features wins, a contextual item beats a free one, and an item with no
+//! features is the elsewhere case. Selected per grammar as the
+//! `PfRule::VocabularyInsertion` rule, so early-inserted `phon` strings and
+//! late-inserted terminals can share a lexicon.
+
+#[cfg(not(feature = "std"))]
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use core::fmt;
+
+use crate::AgrBundle;
+
+/// Abstract morpheme with its phi-features
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Terminal {
+    /// Abstract morpheme, e.g. `pres` or `pron`
+    pub morpheme: String,
+    /// Phi-features, unspecified where the terminal has no value
+    pub agreement: AgrBundle,
+}
+
+impl Terminal {
+    /// Terminal for `morpheme` with `agreement`
+    pub fn new(morpheme: &str, agreement: AgrBundle) -> Self {
+        Self { morpheme: morpheme.to_string(), agreement }
+    }
+
+    /// Read a terminal written as a word: the morpheme in brackets,
+    /// optionally followed by a colon and `AgrBundle` notation; `None` for
+    /// an early-inserted word
+    pub fn parse(word: &str) -> Option<Self> {
+        let inside = word.strip_prefix('[')?.strip_suffix(']')?;
+        let (morpheme, agreement) = inside.split_once(':').unwrap_or((inside, ""));
+        if morpheme.is_empty() {
+            return None;
+        }
+        Some(Self::new(morpheme, AgrBundle::parse(agreement)?))
+    }
+}
+
+impl fmt::Display for Terminal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.agreement == AgrBundle::default() {
+            return write!(f, "[{}]", self.morpheme);
+        }
+        let agreement = self.agreement.to_string();
+        write!(f, "[{}:{}]", self.morpheme, agreement.trim_start_matches('[').trim_end_matches(']'))
+    }
+}
+
+/// Exponent with the features it realizes and the stems it is restricted to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VocabularyItem {
+    /// Exponent; a leading hyphen marks an affix, `-` a null affix and the
+    /// empty string a null word
+    pub exponent: String,
+    /// Morpheme realized; none lets the item realize any morpheme
+    pub morpheme: Option<String>,
+    /// Agreement values realized
+    pub agreement: AgrBundle,
+    /// Stems one of which must be adjacent; none means any context
+    pub context: Vec<String>,
+}
+
+impl VocabularyItem {
+    /// Item inserting `exponent` for terminals of `morpheme` with at least
+    /// the values of `agreement`
+    pub fn new(exponent: &str, morpheme: &str, agreement: AgrBundle) -> Self {
+        Self { exponent: exponent.to_string(), morpheme: Some(morpheme.to_string()), agreement, context: Vec::new() }
+    }
+
+    /// Item inserting `exponent` for any terminal
+    pub fn elsewhere(exponent: &str) -> Self {
+        Self { exponent: exponent.to_string(), morpheme: None, agreement: AgrBundle::default(), context: Vec::new() }
+    }
+
+    /// The item restricted to terminals next to one of `stems`
+    pub fn in_context(mut self, stems: &[&str]) -> Self {
+        self.context = stems.iter().map(|stem| stem.to_string()).collect();
+        self
+    }
+
+    /// Whether the item realizes no features
+    pub fn is_elsewhere(&self) -> bool {
+        self.specificity() == 0
+    }
+
+    /// Number of features the item realizes
+    fn specificity(&self) -> usize {
+        let AgrBundle { person, number, gender } = self.agreement;
+        [self.morpheme.is_some(), person.is_some(), number.is_some(), gender.is_some()].into_iter().filter(|&f| f).count()
+    }
+
+    /// Whether the item may realize `terminal` next to `neighbors`
+    pub fn qualifies(&self, terminal: &Terminal, neighbors: &[&str]) -> bool {
+        fn realizes<T: PartialEq>(item: Option<T>, terminal: Option<T>) -> bool {
+            item.is_none_or(|value| terminal == Some(value))
+        }
+        let (item, other) = (self.agreement, terminal.agreement);
+        self.morpheme.as_ref().is_none_or(|morpheme| *morpheme == terminal.morpheme)
+            && realizes(item.person, other.person)
+            && realizes(item.number, other.number)
+            && realizes(item.gender, other.gender)
+            && (self.context.is_empty() || neighbors.iter().any(|neighbor| self.context.iter().any(|stem| stem == neighbor)))
+    }
+}
+
+/// Winner of the competition for `terminal` next to `neighbors`
+///
+/// Ties go to the first listed item.
+pub fn insert<'a>(vocabulary: &'a [VocabularyItem], terminal: &Terminal, neighbors: &[&str]) -> Option<&'a VocabularyItem> {
+    let mut winner: Option<&VocabularyItem> = None;
+    for item in vocabulary.iter().filter(|item| item.qualifies(terminal, neighbors)) {
+        let rank = |item: &VocabularyItem| (item.specificity(), !item.context.is_empty());
+        if winner.is_none_or(|best| rank(item) > rank(best)) {
+            winner = Some(item);
+        }
+    }
+    winner
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_with_options, Category, Feature, GrammarOptions, LexItem, Number, PfRule, SpellOut};
+
+    fn vocabulary() -> Vec<VocabularyItem> {
+        vec![
+            VocabularyItem::new("-s", "pres", AgrBundle::third(Number::Singular)),
+            VocabularyItem::new("-ed", "past", AgrBundle::default()),
+            VocabularyItem::new("-t", "past", AgrBundle::default()).in_context(&["burn", "dream"]),
+            VocabularyItem::elsewhere("-"),
+        ]
+    }
+
+    #[test]
+    fn test_competition() {
+        let vocabulary = vocabulary();
+        let winner = |terminal: &str, neighbors: &[&str]| {
+            insert(&vocabulary, &Terminal::parse(terminal).unwrap(), neighbors).map(|item| item.exponent.as_str())
+        };
+
+        // The most specified qualifying item wins
+        assert_eq!(winner("[pres:3.sg]", &["leave"]), Some("-s"));
+        // Underspecification: 'past' ignores person and number
+        assert_eq!(winner("[past:3.sg]", &["leave"]), Some("-ed"));
+        // A contextual item beats the free one it otherwise ties with
+        assert_eq!(winner("[past]", &["burn"]), Some("-t"));
+        // Anything else falls to the elsewhere item
+        assert_eq!(winner("[pres:1.sg]", &["leave"]), Some("-"));
+        assert!(vocabulary[3].is_elsewhere());
+        let first_singular = Terminal::new("pres", AgrBundle::parse("1.sg").unwrap());
+        assert_eq!(insert(&vocabulary[..3], &first_singular, &[]), None);
+
+        assert_eq!(Terminal::parse("[pres:sg.3]"), Some(Terminal::new("pres", AgrBundle::third(Number::Singular))));
+        assert_eq!(Terminal::parse("[past]"), Some(Terminal::new("past", AgrBundle::default())));
+        assert_eq!(Terminal::parse("[pres:x]"), None);
+        assert_eq!(Terminal::parse("[]"), None);
+        assert_eq!(Terminal::parse("leave"), None);
+        assert_eq!(first_singular.to_string(), "[pres:1.sg]");
+        assert_eq!(Terminal::new("past", AgrBundle::default()).to_string(), "[past]");
+    }
+
+    #[test]
+    fn test_late_insertion_at_pf() {
+        let tense = |terminal| LexItem::new(terminal, &[Feature::Cat(Category::S), Feature::Sel(Category::V), Feature::Spec(Category::D)]);
+        let mut lexicon = vec![
+            tense("[pres:3.sg]"),
+            tense("[pres:3.pl]"),
+            tense("[past]"),
+            LexItem::new("leave", &[Feature::Cat(Category::V)]),
+            LexItem::new("burn", &[Feature::Cat(Category::V)]),
+        ];
+        lexicon.extend(GrammarOptions::default().lexicon());
+
+        let mut spell_out = SpellOut::english();
+        spell_out.rules.insert(0, PfRule::VocabularyInsertion(vocabulary()));
+        let options = GrammarOptions { spell_out, ..GrammarOptions::default() };
+        for (terminals, pronounced) in [
+            ("the student [pres:3.sg] leave", "the student leaves"),
+            ("the student [pres:3.pl] leave", "the student leave"),
+            ("the student [past] leave", "the student left"),
+            ("the student [past] burn", "the student burnt"),
+        ] {
+            let tree = parse_with_options(terminals, &lexicon, &options).unwrap();
+            assert_eq!(options.pronounce(&tree), Ok(pronounced.to_string()));
+        }
+    }
+}
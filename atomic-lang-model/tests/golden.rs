@@ -0,0 +1,109 @@
+//! Golden-File Regression Tests
+//!
+//! Parses each fixture corpus in `tests/golden/*.txt` (one sentence per line,
+//! `#` for comments), renders the trees as labeled bracketings, and compares
+//! the result with the checked-in `tests/golden/*.golden` file. Sentences the
+//! grammar rejects are recorded with their error, so a change in either
+//! direction shows up as a diff.
+//!
+//! After an intended change to the parser, regenerate the goldens and review
+//! the diff before committing:
+//!
+//! ```bash
+//! BLESS=1 cargo test --test golden
+//! ```
+
+use atomic_lang_model::*;
+use std::fs;
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+#[path = "../bench/extraction_suite.rs"]
+mod extraction_suite;
+
+/// Directory holding corpora and goldens
+fn golden_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+/// Parse every sentence of a corpus and render the results
+fn render(corpus: &str, lexicon: &[LexItem], options: &GrammarOptions) -> String {
+    let mut out = String::new();
+    for sentence in corpus.lines().map(str::trim) {
+        if sentence.is_empty() || sentence.starts_with('#') {
+            continue;
+        }
+        let result = match parse_with_options(sentence, lexicon, options) {
+            Ok(tree) => tree.bracketed(),
+            Err(e) => format!("error: {}", e),
+        };
+        out.push_str(&format!("{}\n    {}\n", sentence, result));
+    }
+    out
+}
+
+/// Compare a corpus against its golden file, or rewrite the golden under `BLESS`
+fn check_golden(name: &str, lexicon: &[LexItem], options: &GrammarOptions) {
+    let dir = golden_dir();
+    let corpus = fs::read_to_string(dir.join(format!("{}.txt", name)))
+        .unwrap_or_else(|e| panic!("cannot read corpus {}.txt: {}", name, e));
+    let actual = render(&corpus, lexicon, options);
+    let golden = dir.join(format!("{}.golden", name));
+
+    if std::env::var_os("BLESS").is_some() {
+        fs::write(&golden, &actual).unwrap_or_else(|e| panic!("cannot write {}: {}", golden.display(), e));
+        println!("📝 Blessed {}", golden.display());
+        return;
+    }
+
+    let expected = fs::read_to_string(&golden)
+        .unwrap_or_else(|_| panic!("missing {}; run with BLESS=1 to create it", golden.display()));
+    if actual == expected {
+        return;
+    }
+
+    let mismatch = actual.lines().zip(expected.lines())
+        .position(|(a, e)| a != e)
+        .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+    panic!(
+        "{}.golden differs at line {}\n  expected: {}\n  actual:   {}\nrun with BLESS=1 to accept the new output",
+        name,
+        mismatch + 1,
+        expected.lines().nth(mismatch).unwrap_or("<end of file>"),
+        actual.lines().nth(mismatch).unwrap_or("<end of file>"),
+    );
+}
+
+#[test]
+fn test_relatives_golden() {
+    let options = GrammarOptions::default();
+    check_golden("relatives", &options.lexicon(), &options);
+}
+
+#[test]
+fn test_promotion_golden() {
+    let options = GrammarOptions {
+        relative_clauses: RelativeClauseAnalysis::Promotion,
+        ..Default::default()
+    };
+    check_golden("promotion", &options.lexicon(), &options);
+}
+
+#[test]
+fn test_extraction_golden() {
+    check_golden(
+        "extraction",
+        &extraction_suite::extraction_lexicon(),
+        &extraction_suite::extraction_options(),
+    );
+}
+
+#[test]
+fn test_render_skips_comments() {
+    let lexicon = test_lexicon();
+    let rendered = render("# comment\n\nthe student left\nthe left\n", &lexicon, &GrammarOptions::default());
+    assert_eq!(
+        rendered,
+        "the student left\n    [VP [DP [D the] [N student]] [V left]]\nthe left\n    error: No valid operations available\n"
+    );
+}
@@ -0,0 +1,70 @@
+//! Proof Assistant Certificates
+//!
+//! Exports certificates for Coq and Lean and checks them with `coqc` and
+//! `lean`, so the preludes are compiled rather than only matched as
+//! strings. A prover missing from `PATH` is skipped, unless
+//! `ALM_REQUIRE_PROVERS` is set, as in the `provers` CI job.
+
+use atomic_lang_model::*;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Derivations to certify: a plain one, and one whose object differs in
+/// number from its subject, which no selector probes
+fn proofs() -> Vec<(&'static str, Proof, Vec<LexItem>)> {
+    let plain = test_lexicon();
+    let noun = |phon, number| LexItem::new(phon, &[Feature::Cat(Category::N), Feature::Agr(AgrBundle::third(number))]);
+    let agreeing = vec![
+        LexItem::new("the", &[Feature::Cat(Category::D), Feature::Sel(Category::N)]),
+        noun("students", Number::Plural),
+        noun("teacher", Number::Singular),
+        LexItem::new("thinks", &[Feature::Cat(Category::V), Feature::Sel(Category::D), Feature::Spec(Category::D)]),
+    ];
+    let options = GrammarOptions::default();
+    vec![
+        ("plain", prove("the student left", &plain, &options).unwrap(), plain),
+        ("agreement", prove("the students thinks the teacher", &agreeing, &options).unwrap(), agreeing),
+    ]
+}
+
+/// Whether `program` runs, failing instead if provers are required
+fn available(program: &str) -> bool {
+    let found = Command::new(program).arg("--version").output().is_ok_and(|output| output.status.success());
+    if !found {
+        assert!(std::env::var_os("ALM_REQUIRE_PROVERS").is_none(), "{} is required but not on PATH", program);
+        eprintln!("{} not found, skipping", program);
+    }
+    found
+}
+
+/// Export each certificate and run `program` on it
+fn check(assistant: Assistant, program: &str) {
+    if !available(program) {
+        return;
+    }
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("provers");
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, proof, lexicon) in proofs() {
+        let path = dir.join(format!("certificate_{}.{}", name, assistant.extension()));
+        std::fs::write(&path, proof.export(&lexicon, assistant).unwrap()).unwrap();
+        let output = Command::new(program).arg(&path).current_dir(&dir).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{} rejected {}:\n{}{}",
+            program,
+            path.display(),
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr),
+        );
+    }
+}
+
+#[test]
+fn test_coq_checks_certificates() {
+    check(Assistant::Coq, "coqc");
+}
+
+#[test]
+fn test_lean_checks_certificates() {
+    check(Assistant::Lean, "lean");
+}
@@ -0,0 +1,57 @@
+//! Binary Size Budget
+//!
+//! Builds `alm-tiny`, a package of its own under `tiny/`, with the
+//! min-size profile, runs it, and holds the stripped executable to
+//! `binary_size::BUDGET`. The build goes to its own target directory, so it
+//! does not contend with the running `cargo test`. To see the section sizes
+//! of the same build:
+//!
+//! ```bash
+//! cargo build --manifest-path tiny/Cargo.toml --profile min-size
+//! cargo run -- size tiny/target/min-size/alm-tiny
+//! ```
+
+#![cfg(target_os = "linux")]
+
+use atomic_lang_model::binary_size::BUDGET;
+use atomic_lang_model::SizeReport;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Build `alm-tiny` and return the path of the executable
+fn build_tiny() -> PathBuf {
+    let target = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("size-budget");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--quiet", "--manifest-path", "tiny/Cargo.toml", "--profile", "min-size"])
+        .arg("--target-dir")
+        .arg(&target)
+        .status()
+        .expect("cargo runs");
+    assert!(status.success(), "alm-tiny failed to build");
+    target.join("min-size").join("alm-tiny")
+}
+
+#[test]
+fn test_tiny_binary_fits_budget() {
+    let binary = build_tiny();
+
+    let demo = Command::new(&binary).output().unwrap();
+    assert!(demo.status.success());
+    let demo = String::from_utf8(demo.stdout).unwrap();
+    assert!(demo.starts_with("the student left: accept\n"));
+    assert!(demo.contains("the student thinks the teacher: accept\n"));
+    assert!(demo.ends_with("left the student: reject\n"));
+
+    // Movement as well as merge, within the recognizer's fixed chart
+    let judged = Command::new(&binary)
+        .args(["the student who left smiled", "the student the teacher"])
+        .output()
+        .unwrap();
+    assert_eq!(judged.status.code(), Some(1));
+    assert_eq!(judged.stdout, b"the student who left smiled: accept\nthe student the teacher: reject\n");
+
+    let report = SizeReport::read(&binary).unwrap();
+    assert!(report.section(".text").is_some_and(|text| text.size > 0));
+    assert_eq!(report.check(BUDGET), Ok(()), "\n{}", report);
+}
@@ -0,0 +1,56 @@
+//! Derivation Timelines
+//!
+//! Runs `atomic-lm timeline` and reads back the collapsed stacks it
+//! writes. Allocations are only counted under `count-allocations`, which
+//! installs the counting global allocator.
+
+use std::process::{Command, ExitStatus};
+
+fn run(args: &[&str], path: &std::path::Path) -> ExitStatus {
+    Command::new(env!("CARGO_BIN_EXE_atomic-lm"))
+        .arg("timeline")
+        .args(args)
+        .arg(path)
+        .output()
+        .unwrap()
+        .status
+}
+
+fn timeline(args: &[&str]) -> Vec<(String, u64)> {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("timeline-{}.txt", args.join("-").replace(' ', "_")));
+    assert!(run(args, &path).success());
+    std::fs::read_to_string(&path).unwrap()
+        .lines()
+        .map(|line| {
+            let (stack, weight) = line.rsplit_once(' ').unwrap();
+            (stack.to_string(), weight.parse().unwrap())
+        })
+        .collect()
+}
+
+#[test]
+fn test_timeline_writes_weighted_stacks() {
+    let stacks = timeline(&["the student left"]);
+    let frames: Vec<&str> = stacks.iter().map(|(stack, _)| stack.as_str()).collect();
+    assert_eq!(frames, ["derive;Merge;step 1 Merge(0, 1)", "derive;Merge;step 2 Merge(1, 0)"]);
+    assert!(stacks.iter().map(|(_, nanoseconds)| nanoseconds).sum::<u64>() > 0);
+
+    // The search's dead ends are on the timeline too
+    let stacks = timeline(&["the student who left smiled"]);
+    assert!(stacks.iter().filter(|(stack, _)| stack.starts_with("derive;Merge;step 1 ")).count() > 1);
+}
+
+#[cfg(feature = "count-allocations")]
+#[test]
+fn test_timeline_counts_allocations() {
+    // Every merge builds a new feature list
+    let stacks = timeline(&["--allocations", "the student left"]);
+    assert!(stacks.iter().all(|&(_, allocations)| allocations > 0));
+}
+
+#[cfg(not(feature = "count-allocations"))]
+#[test]
+fn test_allocations_need_the_counting_allocator() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("timeline-uncounted.txt");
+    assert_eq!(run(&["--allocations", "the student left"], &path).code(), Some(2));
+}
@@ -0,0 +1,12 @@
+//! Compiles the bundled English lexicon into `lexicon.alm` in `OUT_DIR`,
+//! the blob `alm-tiny` recognizes with
+
+use atomic_lang_model::{compiled, GrammarOptions};
+use std::path::PathBuf;
+
+fn main() {
+    let blob = compiled::compile(&GrammarOptions::default().lexicon()).expect("the bundled lexicon compiles");
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("cargo sets OUT_DIR"));
+    std::fs::write(out.join("lexicon.alm"), blob).expect("OUT_DIR is writable");
+    println!("cargo:rerun-if-changed=../src");
+}
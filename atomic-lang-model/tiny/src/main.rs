@@ -0,0 +1,100 @@
+//! Atomic Language Model - Tiny Demo
+//!
+//! Minimalist Grammar recognition with the bundled English lexicon, and
+//! nothing else: the build script compiles the lexicon into a blob (see
+//! `compiled`), the library's blob interpreter is included as a module of
+//! its own, recognition runs in a fixed chart on the stack, and output goes
+//! straight to libc's `write`, with no std, no allocator and no formatting
+//! machinery. This is the binary the size budget is checked against (see
+//! `binary_size`).
+//!
+//! Usage:
+//! - `alm-tiny` judges a few grammatical and ungrammatical sentences
+//! - `alm-tiny SENTENCE...` prints `accept` or `reject` for each SENTENCE,
+//!   exiting 1 if any is rejected
+//!
+//! Build with the release or min-size profile, which abort on panic as a
+//! binary without std needs; the budget is measured with min-size:
+//!
+//! ```text
+//! cargo build --manifest-path tiny/Cargo.toml --profile min-size
+//! ```
+
+#![no_std]
+#![no_main]
+
+// Parts of the interpreter only the library's compiler uses go unused here
+#[allow(dead_code)]
+#[path = "../../src/compiled/interpreter.rs"]
+mod interpreter;
+
+use core::ffi::{c_char, c_int, CStr};
+use interpreter::{recognize, RecognizeError};
+
+#[link(name = "c")]
+extern "C" {
+    fn write(fd: c_int, buf: *const u8, count: usize) -> isize;
+    fn exit(status: c_int) -> !;
+}
+
+/// The bundled English lexicon, compiled by the build script
+static LEXICON: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/lexicon.alm"));
+
+/// Chart items the recognizer may build, enough for relative clauses
+const CAPACITY: usize = 512;
+
+/// Sentences judged when none are given
+const DEMO: [&str; 5] = [
+    "the student left",
+    "the student thinks the teacher",
+    "the student who left smiled",
+    "the student the teacher",
+    "left the student",
+];
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    print(b"panic\n");
+    unsafe { exit(101) }
+}
+
+fn print(bytes: &[u8]) {
+    // Nothing useful to do if stdout is gone
+    unsafe {
+        write(1, bytes.as_ptr(), bytes.len());
+    }
+}
+
+/// Print `sentence` with its verdict, returning whether it was accepted
+fn judge(sentence: &[u8]) -> bool {
+    let verdict = match core::str::from_utf8(sentence) {
+        Ok(sentence) => recognize::<CAPACITY>(LEXICON, sentence),
+        Err(_) => Ok(false),
+    };
+    print(sentence);
+    print(match verdict {
+        Ok(true) => b": accept\n",
+        Ok(false) => b": reject\n",
+        Err(RecognizeError::TooLong) => b": reject (too long)\n",
+        Err(_) => b": reject (capacity exceeded)\n",
+    });
+    verdict == Ok(true)
+}
+
+#[no_mangle]
+extern "C" fn main(argc: c_int, argv: *const *const c_char) -> c_int {
+    if argc <= 1 {
+        for sentence in DEMO {
+            judge(sentence.as_bytes());
+        }
+        return 0;
+    }
+
+    let mut rejected = false;
+    for i in 1..argc as usize {
+        // argv holds argc valid C strings
+        let arg = unsafe { CStr::from_ptr(*argv.add(i)) };
+        rejected |= !judge(arg.to_bytes());
+    }
+    rejected as c_int
+}